@@ -0,0 +1,5 @@
+// src/haptic/analytics/mod.rs
+pub mod session;
+pub mod study;
+pub use session::{SessionAnalytics, SessionReport, WidgetReport};
+pub use study::{balanced_latin_square, circular_targets, PlannedTrial, Study, StudyDesign, Target, TrialResult};
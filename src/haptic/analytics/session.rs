@@ -0,0 +1,261 @@
+//! Summary metrics for user-study sessions.
+//!
+//! A [`SessionAnalytics`] is fed one sample per update (device position, output
+//! force and the widget in contact, if any) plus error and overshoot events, and
+//! turns them into a [`SessionReport`] when the session ends: path length, time in
+//! contact and mean contact force per widget, and event counts. Timestamps are
+//! session-clock microseconds; each sample's state is held until the next one.
+
+use crate::core::{Meters, Meters3, Newtons, Newtons3, Seconds};
+use crate::scene::{NodeId, Scene};
+use std::collections::BTreeMap;
+use std::fmt::{self, Write as _};
+
+// ============================================================================
+// Recorder
+// ============================================================================
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    timestamp_us: u64,
+    position: Meters3,
+    force: Newtons,
+    contact: Option<NodeId>,
+}
+
+impl Sample {
+    /// Credits the contact of this sample for the time until `until_us`.
+    fn hold_until(&self, until_us: u64, widgets: &mut BTreeMap<NodeId, WidgetStats>) {
+        if let Some(node) = self.contact {
+            let dt_us = until_us.saturating_sub(self.timestamp_us);
+            let stats = widgets.entry(node).or_default();
+            stats.contact_us += dt_us;
+            stats.impulse += f64::from(self.force.value()) * dt_us as f64 * 1e-6;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct WidgetStats {
+    contact_us: u64,
+    contacts: u32,
+    /// Time integral of the force magnitude while in contact, in N·s.
+    impulse: f64,
+    errors: u32,
+    overshoots: u32,
+}
+
+/// Accumulates session metrics incrementally, without storing samples.
+#[derive(Debug, Clone, Default)]
+pub struct SessionAnalytics {
+    start_us: Option<u64>,
+    last: Option<Sample>,
+    path_length: f64,
+    peak_force: Newtons,
+    errors: u32,
+    overshoots: u32,
+    widgets: BTreeMap<NodeId, WidgetStats>,
+}
+
+impl SessionAnalytics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// True until the first sample is recorded.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.last.is_none()
+    }
+
+    /// Records the device state at `timestamp_us`. Samples older than the previous
+    /// one contribute no time.
+    pub fn record(&mut self, timestamp_us: u64, position: Meters3, force: Newtons3, contact: Option<NodeId>) {
+        let force = force.length();
+        self.start_us.get_or_insert(timestamp_us);
+        self.peak_force = self.peak_force.max(force);
+
+        if let Some(last) = self.last {
+            self.path_length += f64::from((position - last.position).length().value());
+            last.hold_until(timestamp_us, &mut self.widgets);
+        }
+        if let Some(node) = contact.filter(|&n| self.last.is_none_or(|last| last.contact != Some(n))) {
+            self.widgets.entry(node).or_default().contacts += 1;
+        }
+        let timestamp_us = self.last.map_or(timestamp_us, |last| timestamp_us.max(last.timestamp_us));
+        self.last = Some(Sample { timestamp_us, position, force, contact });
+    }
+
+    /// Counts an error (wrong target, missed activation), optionally attributed to a widget.
+    pub fn record_error(&mut self, node: Option<NodeId>) {
+        self.errors += 1;
+        if let Some(node) = node {
+            self.widgets.entry(node).or_default().errors += 1;
+        }
+    }
+
+    /// Counts an overshoot of a target widget.
+    pub fn record_overshoot(&mut self, node: Option<NodeId>) {
+        self.overshoots += 1;
+        if let Some(node) = node {
+            self.widgets.entry(node).or_default().overshoots += 1;
+        }
+    }
+
+    /// Summarizes the session so far. The last sample's state counts until `end_us`.
+    pub fn report(&self, end_us: u64) -> SessionReport {
+        let mut widgets = self.widgets.clone();
+        if let Some(last) = self.last {
+            last.hold_until(end_us, &mut widgets);
+        }
+        let contact_us: u64 = widgets.values().map(|s| s.contact_us).sum();
+        let impulse: f64 = widgets.values().map(|s| s.impulse).sum();
+
+        SessionReport {
+            duration: seconds(end_us.saturating_sub(self.start_us.unwrap_or(end_us))),
+            path_length: Meters(self.path_length as f32),
+            contact_time: seconds(contact_us),
+            mean_contact_force: mean(impulse, contact_us),
+            peak_force: self.peak_force,
+            errors: self.errors,
+            overshoots: self.overshoots,
+            widgets: widgets
+                .into_iter()
+                .map(|(node, stats)| WidgetReport {
+                    node,
+                    name: String::new(),
+                    contact_time: seconds(stats.contact_us),
+                    contacts: stats.contacts,
+                    mean_force: mean(stats.impulse, stats.contact_us),
+                    errors: stats.errors,
+                    overshoots: stats.overshoots,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[inline]
+fn seconds(us: u64) -> Seconds {
+    Seconds((us as f64 * 1e-6) as f32)
+}
+
+#[inline]
+fn mean(impulse: f64, duration_us: u64) -> Newtons {
+    if duration_us == 0 {
+        Newtons::ZERO
+    } else {
+        Newtons((impulse / (duration_us as f64 * 1e-6)) as f32)
+    }
+}
+
+// ============================================================================
+// Report
+// ============================================================================
+
+/// Metrics for one widget the user touched or was scored on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WidgetReport {
+    pub node: NodeId,
+    /// Node name, filled in by [`SessionReport::label_widgets`].
+    pub name: String,
+    pub contact_time: Seconds,
+    /// Number of separate contacts.
+    pub contacts: u32,
+    /// Time-weighted mean force magnitude while in contact.
+    pub mean_force: Newtons,
+    pub errors: u32,
+    pub overshoots: u32,
+}
+
+/// End-of-session summary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionReport {
+    pub duration: Seconds,
+    /// Distance travelled by the device.
+    pub path_length: Meters,
+    /// Total time in contact with any widget.
+    pub contact_time: Seconds,
+    /// Time-weighted mean force magnitude while in contact.
+    pub mean_contact_force: Newtons,
+    pub peak_force: Newtons,
+    /// All errors, including those not attributed to a widget.
+    pub errors: u32,
+    /// All overshoots, including those not attributed to a widget.
+    pub overshoots: u32,
+    /// Per-widget metrics, ordered by node id.
+    pub widgets: Vec<WidgetReport>,
+}
+
+impl SessionReport {
+    pub fn widget(&self, node: NodeId) -> Option<&WidgetReport> {
+        self.widgets.iter().find(|w| w.node == node)
+    }
+
+    /// Copies node names from the scene the session ran on.
+    pub fn label_widgets(&mut self, scene: &Scene) {
+        for widget in &mut self.widgets {
+            if let Some(node) = scene.get(widget.node) {
+                widget.name.clone_from(&node.name);
+            }
+        }
+    }
+
+    /// Per-widget table with a header row, for spreadsheets and stats packages.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("node,name,contact_time_s,contacts,mean_force_n,errors,overshoots\n");
+        for w in &self.widgets {
+            let _ = writeln!(
+                out,
+                "{},{},{},{},{},{},{}",
+                w.node.index(),
+                csv_field(&w.name),
+                w.contact_time.value(),
+                w.contacts,
+                w.mean_force.value(),
+                w.errors,
+                w.overshoots
+            );
+        }
+        out
+    }
+}
+
+impl fmt::Display for SessionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "duration: {}", self.duration)?;
+        writeln!(f, "path length: {}", self.path_length)?;
+        writeln!(f, "contact time: {}", self.contact_time)?;
+        writeln!(f, "mean contact force: {}", self.mean_contact_force)?;
+        writeln!(f, "peak force: {}", self.peak_force)?;
+        write!(f, "errors: {}, overshoots: {}", self.errors, self.overshoots)?;
+        for w in &self.widgets {
+            let name = if w.name.is_empty() { "-" } else { &w.name };
+            write!(
+                f,
+                "\n  #{} {}: {} in {} contacts, mean {}, {} errors, {} overshoots",
+                w.node.index(),
+                name,
+                w.contact_time,
+                w.contacts,
+                w.mean_force,
+                w.errors,
+                w.overshoots
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Quotes a field containing separators or quotes.
+pub(super) fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/session_tests.rs"]
+mod tests;
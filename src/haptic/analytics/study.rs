@@ -0,0 +1,261 @@
+//! Target-acquisition and peg-in-hole experiments.
+//!
+//! A [`StudyDesign`] lists the experimental conditions and the targets of a block.
+//! [`Study`] runs one participant through it: conditions are ordered by a balanced
+//! Latin square so order effects cancel across participants, each block presents the
+//! targets `repetitions` times, and every trial is measured with a
+//! [`SessionAnalytics`] from `begin` to `complete`. Results export to CSV with one
+//! row per trial, ready for the usual stats packages.
+
+use super::session::{csv_field, SessionAnalytics};
+use crate::core::{Meters, Meters3, Newtons, Newtons3, Seconds, Vec3};
+use crate::scene::{NodeBuilder, NodeId, Scene, SceneError};
+use std::fmt::Write as _;
+
+// ============================================================================
+// Targets and Ordering
+// ============================================================================
+
+/// A goal region: a selection within `tolerance` of `position` counts as a hit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Target {
+    pub position: Vec3,
+    /// Radius of the acceptance region; for buttons, half the target width.
+    pub tolerance: f32,
+}
+
+impl Target {
+    #[inline]
+    pub fn new(position: Vec3, tolerance: f32) -> Self {
+        Self { position, tolerance }
+    }
+}
+
+/// `count` targets of width `width` on a circle of `radius` in the XY plane, in the
+/// ISO 9241-9 order where each target lies across the circle from the previous one.
+pub fn circular_targets(center: Vec3, radius: f32, width: f32, count: usize) -> Vec<Target> {
+    let half = count.div_ceil(2);
+    (0..count)
+        .map(|k| {
+            let slot = if k % 2 == 0 { k / 2 } else { k / 2 + half };
+            let angle = std::f32::consts::TAU * slot as f32 / count as f32;
+            Target::new(center + Vec3::new(angle.sin(), angle.cos(), 0.0) * radius, width * 0.5)
+        })
+        .collect()
+}
+
+/// Rows of a balanced Latin square (Williams design) over `n` conditions: every
+/// condition appears once per position and follows every other condition equally
+/// often. Odd `n` needs the mirrored rows as well, giving `2n` rows.
+pub fn balanced_latin_square(n: usize) -> Vec<Vec<usize>> {
+    let first: Vec<usize> = (0..n).map(|j| if j % 2 == 1 { j.div_ceil(2) } else { (n - j / 2) % n }).collect();
+    let mut rows: Vec<Vec<usize>> = (0..n).map(|r| first.iter().map(|&c| (c + r) % n).collect()).collect();
+    if n % 2 == 1 {
+        let mirrored: Vec<Vec<usize>> = rows.iter().map(|row| row.iter().rev().copied().collect()).collect();
+        rows.extend(mirrored);
+    }
+    rows
+}
+
+// ============================================================================
+// Design and Results
+// ============================================================================
+
+/// Conditions and per-block targets of an experiment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StudyDesign {
+    pub conditions: Vec<String>,
+    pub targets: Vec<Target>,
+    /// Times the target sequence is repeated within each block.
+    pub repetitions: usize,
+}
+
+impl StudyDesign {
+    pub fn new(conditions: impl IntoIterator<Item = impl Into<String>>, targets: Vec<Target>) -> Self {
+        Self { conditions: conditions.into_iter().map(Into::into).collect(), targets, repetitions: 1 }
+    }
+
+    pub fn with_repetitions(mut self, repetitions: usize) -> Self {
+        self.repetitions = repetitions;
+        self
+    }
+}
+
+/// One trial of the plan.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlannedTrial {
+    /// Index into the design's conditions.
+    pub condition: usize,
+    /// Position of the block in this participant's order.
+    pub block: usize,
+    /// Index of the trial within its block.
+    pub trial: usize,
+    /// Index into the design's targets.
+    pub target: usize,
+}
+
+/// Measurements of a completed trial.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrialResult {
+    pub plan: PlannedTrial,
+    pub completion_time: Seconds,
+    /// Distance from the selection point to the target center.
+    pub error: Meters,
+    pub hit: bool,
+    pub mean_contact_force: Newtons,
+    pub peak_force: Newtons,
+    pub path_length: Meters,
+}
+
+// ============================================================================
+// Study
+// ============================================================================
+
+/// Runs one participant through a [`StudyDesign`].
+#[derive(Debug, Clone)]
+pub struct Study {
+    design: StudyDesign,
+    participant: usize,
+    plan: Vec<PlannedTrial>,
+    next: usize,
+    active: Option<(u64, SessionAnalytics)>,
+    results: Vec<TrialResult>,
+}
+
+impl Study {
+    /// Plans the trials, ordering blocks by the participant's row of the balanced Latin square.
+    pub fn new(design: StudyDesign, participant: usize) -> Self {
+        let square = balanced_latin_square(design.conditions.len());
+        let order = square.get(participant % square.len().max(1)).cloned().unwrap_or_default();
+        let per_block = design.targets.len() * design.repetitions;
+        let plan = order
+            .iter()
+            .enumerate()
+            .flat_map(|(block, &condition)| {
+                let targets = design.targets.len();
+                (0..per_block).map(move |trial| PlannedTrial { condition, block, trial, target: trial % targets })
+            })
+            .collect();
+        Self { design, participant, plan, next: 0, active: None, results: Vec::new() }
+    }
+
+    #[inline]
+    pub fn design(&self) -> &StudyDesign {
+        &self.design
+    }
+
+    #[inline]
+    pub fn participant(&self) -> usize {
+        self.participant
+    }
+
+    /// All trials in presentation order.
+    #[inline]
+    pub fn plan(&self) -> &[PlannedTrial] {
+        &self.plan
+    }
+
+    /// The trial in progress, or the next one to begin.
+    pub fn current(&self) -> Option<PlannedTrial> {
+        self.plan.get(self.next).copied()
+    }
+
+    pub fn current_target(&self) -> Option<Target> {
+        self.current().map(|t| self.design.targets[t.target])
+    }
+
+    /// Name of the current trial's condition, for switching the rendering setup between blocks.
+    pub fn current_condition(&self) -> Option<&str> {
+        self.current().map(|t| self.design.conditions[t.condition].as_str())
+    }
+
+    #[inline]
+    pub fn is_running(&self) -> bool {
+        self.active.is_some()
+    }
+
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.plan.len()
+    }
+
+    /// Adds a button for the current target to `scene`, named `target`. Returns
+    /// None when the study is finished, and fails if `parent` is not in the scene.
+    pub fn spawn_target(&self, scene: &mut Scene, parent: Option<NodeId>) -> Result<Option<NodeId>, SceneError> {
+        let Some(target) = self.current_target() else {
+            return Ok(None);
+        };
+        let width = target.tolerance * 2.0;
+        let builder = NodeBuilder::button("").name("target").position(target.position).size(width, width, 0.005);
+        builder.build(scene, parent).map(Some)
+    }
+
+    /// Starts timing the current trial. Returns it, or None when the study is finished.
+    pub fn begin(&mut self, timestamp_us: u64) -> Option<PlannedTrial> {
+        let trial = self.current()?;
+        self.active = Some((timestamp_us, SessionAnalytics::new()));
+        Some(trial)
+    }
+
+    /// Feeds one device sample to the trial in progress; ignored between trials.
+    pub fn record(&mut self, timestamp_us: u64, position: Meters3, force: Newtons3, contact: Option<NodeId>) {
+        if let Some((_, analytics)) = &mut self.active {
+            analytics.record(timestamp_us, position, force, contact);
+        }
+    }
+
+    /// Ends the trial in progress with a selection at `position` and advances to the next.
+    pub fn complete(&mut self, timestamp_us: u64, position: Meters3) -> Option<&TrialResult> {
+        let (start_us, analytics) = self.active.take()?;
+        let plan = self.current()?;
+        let target = self.design.targets[plan.target];
+        let report = analytics.report(timestamp_us);
+        let error = (position.0 - target.position).length();
+        self.results.push(TrialResult {
+            plan,
+            completion_time: Seconds(timestamp_us.saturating_sub(start_us) as f32 * 1e-6),
+            error: Meters(error),
+            hit: error <= target.tolerance,
+            mean_contact_force: report.mean_contact_force,
+            peak_force: report.peak_force,
+            path_length: report.path_length,
+        });
+        self.next += 1;
+        self.results.last()
+    }
+
+    /// Completed trials in order.
+    #[inline]
+    pub fn results(&self) -> &[TrialResult] {
+        &self.results
+    }
+
+    /// One row per completed trial, with a header row.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "participant,condition,block,trial,target,completion_time_s,error_m,hit,mean_contact_force_n,peak_force_n,path_length_m\n",
+        );
+        for r in &self.results {
+            let _ = writeln!(
+                out,
+                "{},{},{},{},{},{},{},{},{},{},{}",
+                self.participant,
+                csv_field(&self.design.conditions[r.plan.condition]),
+                r.plan.block,
+                r.plan.trial,
+                r.plan.target,
+                r.completion_time.value(),
+                r.error.value(),
+                u8::from(r.hit),
+                r.mean_contact_force.value(),
+                r.peak_force.value(),
+                r.path_length.value()
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/study_tests.rs"]
+mod tests;
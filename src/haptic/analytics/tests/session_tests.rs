@@ -0,0 +1,88 @@
+use super::*;
+use crate::scene::NodeBuilder;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+fn scene() -> (Scene, NodeId, NodeId) {
+    let scene = NodeBuilder::panel()
+        .name("panel")
+        .child(NodeBuilder::button("OK").name("ok"))
+        .child(NodeBuilder::button("Cancel").name("cancel, really"))
+        .into_scene();
+    let ok = scene.find("ok").unwrap();
+    let cancel = scene.find("cancel, really").unwrap();
+    (scene, ok, cancel)
+}
+
+/// 10 ms steps along +X; touches `ok` for 30 ms at 2 N, leaves, touches it again
+/// for 10 ms at 4 N, then `cancel` for 20 ms at 1 N.
+fn session(ok: NodeId, cancel: NodeId) -> SessionAnalytics {
+    let contacts = [None, Some(ok), Some(ok), Some(ok), None, Some(ok), Some(cancel), Some(cancel), None];
+    let forces = [0.0, 2.0, 2.0, 2.0, 0.0, 4.0, 1.0, 1.0, 0.0];
+    let mut analytics = SessionAnalytics::new();
+    assert!(analytics.is_empty());
+    for (i, (contact, force)) in contacts.into_iter().zip(forces).enumerate() {
+        let position = Meters3::new(i as f32 * 0.01, 0.0, 0.0);
+        analytics.record(1_000_000 + i as u64 * 10_000, position, Newtons3::new(0.0, force, 0.0), contact);
+    }
+    analytics
+}
+
+#[test]
+fn test_contact_time_force_and_path() {
+    let (_, ok, cancel) = scene();
+    let report = session(ok, cancel).report(1_100_000);
+
+    assert!((report.duration.value() - 0.1).abs() < TEST_EPSILON);
+    assert!((report.path_length.value() - 0.08).abs() < TEST_EPSILON);
+    assert!((report.contact_time.value() - 0.06).abs() < TEST_EPSILON);
+    // (2 N · 30 ms + 4 N · 10 ms + 1 N · 20 ms) / 60 ms
+    assert!((report.mean_contact_force.value() - 2.0).abs() < TEST_EPSILON);
+    assert_eq!(report.peak_force, Newtons(4.0));
+
+    let ok_report = report.widget(ok).unwrap();
+    assert_eq!(ok_report.contacts, 2);
+    assert!((ok_report.contact_time.value() - 0.04).abs() < TEST_EPSILON);
+    assert!((ok_report.mean_force.value() - 2.5).abs() < TEST_EPSILON);
+    assert_eq!(report.widget(cancel).unwrap().contacts, 1);
+}
+
+#[test]
+fn test_contact_at_end_counts_until_report() {
+    let (_, ok, _) = scene();
+    let mut analytics = SessionAnalytics::new();
+    analytics.record(0, Meters3::ZERO, Newtons3::new(1.0, 0.0, 0.0), Some(ok));
+    // Out-of-order samples add no time
+    analytics.record(50_000, Meters3::ZERO, Newtons3::new(1.0, 0.0, 0.0), Some(ok));
+    analytics.record(40_000, Meters3::ZERO, Newtons3::new(1.0, 0.0, 0.0), Some(ok));
+    let report = analytics.report(100_000);
+    assert!((report.widget(ok).unwrap().contact_time.value() - 0.1).abs() < TEST_EPSILON);
+    assert_eq!(report.widget(ok).unwrap().contacts, 1);
+}
+
+#[test]
+fn test_events_and_formatting() {
+    let (scene, ok, cancel) = scene();
+    let mut analytics = session(ok, cancel);
+    analytics.record_error(Some(cancel));
+    analytics.record_error(None);
+    analytics.record_overshoot(Some(ok));
+
+    let mut report = analytics.report(1_100_000);
+    assert_eq!((report.errors, report.overshoots), (2, 1));
+    assert_eq!(report.widget(cancel).unwrap().errors, 1);
+    assert_eq!(report.widget(ok).unwrap().overshoots, 1);
+
+    report.label_widgets(&scene);
+    let csv = report.to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[1].starts_with(&format!("{},ok,", ok.index())));
+    assert!(lines[2].starts_with(&format!("{},\"cancel, really\",", cancel.index())));
+    assert!(lines[2].ends_with(",1,0"));
+
+    let text = report.to_string();
+    assert!(text.starts_with("duration: "));
+    assert!(text.contains("errors: 2, overshoots: 1"));
+    assert!(text.contains(" ok: "));
+}
@@ -0,0 +1,121 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+fn design() -> StudyDesign {
+    let targets = vec![Target::new(Vec3::new(0.05, 0.0, 0.0), 0.01), Target::new(Vec3::new(-0.05, 0.0, 0.0), 0.01)];
+    StudyDesign::new(["force", "vibration", "none"], targets).with_repetitions(2)
+}
+
+#[test]
+fn test_balanced_latin_square_even() {
+    let square = balanced_latin_square(4);
+    assert_eq!(square, vec![vec![0, 1, 3, 2], vec![1, 2, 0, 3], vec![2, 3, 1, 0], vec![3, 0, 2, 1]]);
+    // Every ordered pair of adjacent conditions occurs exactly once.
+    let mut pairs = std::collections::HashSet::new();
+    for row in &square {
+        for w in row.windows(2) {
+            assert!(pairs.insert((w[0], w[1])));
+        }
+    }
+    assert_eq!(pairs.len(), 12);
+}
+
+#[test]
+fn test_balanced_latin_square_odd() {
+    let square = balanced_latin_square(3);
+    assert_eq!(square.len(), 6);
+    for position in 0..3 {
+        let mut counts = [0; 3];
+        for row in &square {
+            counts[row[position]] += 1;
+        }
+        assert_eq!(counts, [2, 2, 2]);
+    }
+    assert!(balanced_latin_square(0).is_empty());
+}
+
+#[test]
+fn test_circular_targets_alternate_across() {
+    let targets = circular_targets(Vec3::zero(), 0.1, 0.02, 5);
+    assert_eq!(targets.len(), 5);
+    for pair in targets.windows(2) {
+        // Consecutive targets are at least 144 degrees apart on a 5-target ring.
+        assert!((pair[0].position - pair[1].position).length() > 0.19);
+    }
+    for t in &targets {
+        assert!((t.position.length() - 0.1).abs() < TEST_EPSILON);
+        assert!((t.tolerance - 0.01).abs() < TEST_EPSILON);
+    }
+}
+
+#[test]
+fn test_plan_follows_participant_order() {
+    let study = Study::new(design(), 1);
+    assert_eq!(study.plan().len(), 12);
+    let blocks: Vec<usize> = study.plan().iter().step_by(4).map(|t| t.condition).collect();
+    assert_eq!(blocks, balanced_latin_square(3)[1]);
+    let targets: Vec<usize> = study.plan()[..4].iter().map(|t| t.target).collect();
+    assert_eq!(targets, vec![0, 1, 0, 1]);
+    assert_eq!(Study::new(design(), 7).plan(), Study::new(design(), 1).plan());
+}
+
+#[test]
+fn test_trial_measurement() {
+    let mut study = Study::new(design(), 0);
+    assert!(study.complete(0, Meters3::new(0.0, 0.0, 0.0)).is_none());
+    study.record(0, Meters3::new(0.0, 0.0, 0.0), Newtons3::new(0.0, 0.0, 5.0), None);
+
+    let trial = study.begin(1_000_000).unwrap();
+    assert!(study.is_running());
+    assert_eq!(trial.target, 0);
+    study.record(1_000_000, Meters3::new(0.0, 0.0, 0.0), Newtons3::new(0.0, 0.0, 0.0), None);
+    study.record(1_250_000, Meters3::new(0.03, 0.0, 0.0), Newtons3::new(0.0, 0.0, 1.0), None);
+    study.record(1_500_000, Meters3::new(0.053, 0.004, 0.0), Newtons3::new(0.0, 0.0, 2.0), None);
+    let result = study.complete(1_500_000, Meters3::new(0.053, 0.004, 0.0)).unwrap().clone();
+
+    assert!(!study.is_running());
+    assert!((result.completion_time.value() - 0.5).abs() < TEST_EPSILON);
+    assert!((result.error.value() - 0.005).abs() < TEST_EPSILON);
+    assert!(result.hit);
+    assert!((result.peak_force.value() - 2.0).abs() < TEST_EPSILON);
+    assert!(result.path_length.value() > 0.05);
+    assert_eq!(study.current().unwrap().target, 1);
+
+    study.begin(2_000_000);
+    let miss = study.complete(2_100_000, Meters3::new(0.0, 0.0, 0.0)).unwrap();
+    assert!(!miss.hit);
+    assert!((miss.error.value() - 0.05).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_run_to_completion_and_csv() {
+    let mut study = Study::new(StudyDesign::new(["a,b", "c"], vec![Target::new(Vec3::zero(), 0.01)]), 0);
+    let mut t = 0;
+    while study.begin(t).is_some() {
+        let target = study.current_target().unwrap();
+        t += 100_000;
+        study.complete(t, Meters3(target.position));
+    }
+    assert!(study.is_finished());
+    assert!(study.current_condition().is_none());
+    assert_eq!(study.results().len(), 2);
+    assert!(study.results().iter().all(|r| r.hit));
+
+    let csv = study.to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("participant,condition,block,trial,target,completion_time_s"));
+    assert!(lines[1].starts_with("0,\"a,b\",0,0,0,0.1,0,1,"));
+    assert!(lines[2].starts_with("0,c,1,0,0,"));
+}
+
+#[test]
+fn test_spawn_target() {
+    let mut scene = Scene::new();
+    let study = Study::new(design(), 0);
+    assert_eq!(study.current_condition(), Some("force"));
+    let id = study.spawn_target(&mut scene, None).unwrap().unwrap();
+    assert_eq!(scene.find("target"), Some(id));
+    assert!(scene.get(id).unwrap().flags.contains(crate::scene::NodeFlags::TOUCHABLE));
+}
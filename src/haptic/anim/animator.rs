@@ -0,0 +1,167 @@
+//! The animator: timelines driven by the frame clock.
+//!
+//! An [`Animator`] owns the running timelines. Each frame, [`Animator::tick`]
+//! takes the frame's session timestamp, advances every playing timeline by the
+//! time since the last frame and applies them to the scene; timelines that are
+//! paused or finished leave their nodes alone.
+//!
+//! It also runs [`Tween`]s, at most one per node property: a new tween on a
+//! property replaces the running one, carrying on from where it left the
+//! value. Tweens apply after timelines, so they win over a timeline driving
+//! the same property.
+
+use std::collections::BTreeMap;
+
+use super::props::AnimatedProps;
+use super::timeline::{Timeline, TimelineEvent};
+use super::track::Channel;
+use super::tween::Tween;
+use crate::core::Seconds;
+use crate::scene::{NodeId, Scene};
+
+/// Identifies a timeline added to an [`Animator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TimelineId(u32);
+
+/// Running timelines and the clock that drives them.
+#[derive(Debug, Clone, Default)]
+pub struct Animator {
+    timelines: BTreeMap<TimelineId, Timeline>,
+    next_id: u32,
+    tweens: Vec<Tween>,
+    /// Properties whose tweens finished since the last `take_completed`.
+    completed: Vec<(NodeId, Channel)>,
+    last_us: Option<u64>,
+}
+
+impl Animator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, timeline: Timeline) -> TimelineId {
+        let id = TimelineId(self.next_id);
+        self.next_id += 1;
+        self.timelines.insert(id, timeline);
+        id
+    }
+
+    pub fn get(&self, id: TimelineId) -> Option<&Timeline> {
+        self.timelines.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: TimelineId) -> Option<&mut Timeline> {
+        self.timelines.get_mut(&id)
+    }
+
+    pub fn remove(&mut self, id: TimelineId) -> Option<Timeline> {
+        self.timelines.remove(&id)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.timelines.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.timelines.is_empty()
+    }
+
+    /// Starts `tween`, replacing and returning the one running on its property.
+    pub fn tween(&mut self, tween: Tween) -> Option<Tween> {
+        let index = self.tween_index(tween.node(), tween.channel());
+        match index {
+            Some(i) => Some(std::mem::replace(&mut self.tweens[i], tween)),
+            None => {
+                self.tweens.push(tween);
+                None
+            }
+        }
+    }
+
+    /// The tween running on one property.
+    pub fn tween_on(&self, node: NodeId, channel: Channel) -> Option<&Tween> {
+        self.tween_index(node, channel).map(|i| &self.tweens[i])
+    }
+
+    #[inline]
+    pub fn is_tweening(&self, node: NodeId, channel: Channel) -> bool {
+        self.tween_index(node, channel).is_some()
+    }
+
+    /// Stops the tween on one property where it is.
+    pub fn cancel_tween(&mut self, node: NodeId, channel: Channel) -> Option<Tween> {
+        self.tween_index(node, channel).map(|i| self.tweens.remove(i))
+    }
+
+    /// Stops every tween on `node`; returns how many ran.
+    pub fn cancel_tweens(&mut self, node: NodeId) -> usize {
+        let before = self.tweens.len();
+        self.tweens.retain(|t| t.node() != node);
+        before - self.tweens.len()
+    }
+
+    /// Properties whose tweens ran to the end since the last call.
+    pub fn take_completed(&mut self) -> Vec<(NodeId, Channel)> {
+        std::mem::take(&mut self.completed)
+    }
+
+    /// Advances by the time since the previous tick, with the frame stamped
+    /// `now_us` in session time. The first tick only starts the clock.
+    pub fn tick(
+        &mut self,
+        now_us: u64,
+        scene: &mut Scene,
+        props: &mut AnimatedProps,
+    ) -> Vec<(TimelineId, TimelineEvent)> {
+        let dt = self.last_us.map_or(0, |last| now_us.saturating_sub(last));
+        self.last_us = Some(now_us);
+        self.advance(Seconds(dt as f32 * 1e-6), scene, props)
+    }
+
+    /// Advances every playing timeline by `dt` and applies it, then the
+    /// tweens; returns the events the timelines reached, in the order they
+    /// were added. Tweens on nodes that left the scene are dropped.
+    pub fn advance(
+        &mut self,
+        dt: Seconds,
+        scene: &mut Scene,
+        props: &mut AnimatedProps,
+    ) -> Vec<(TimelineId, TimelineEvent)> {
+        let mut events = Vec::new();
+        for (&id, timeline) in &mut self.timelines {
+            if !timeline.is_playing() {
+                continue;
+            }
+            if let Some(event) = timeline.advance(dt) {
+                events.push((id, event));
+            }
+            timeline.apply(scene, props);
+        }
+        let completed = &mut self.completed;
+        self.tweens.retain_mut(|tween| {
+            let Some(current) = props.current(scene, tween.node(), tween.channel()) else {
+                return false;
+            };
+            tween.advance(dt.value(), current);
+            if let Some(value) = tween.sample() {
+                props.apply(scene, tween.node(), value);
+            }
+            if tween.is_finished() {
+                completed.push((tween.node(), tween.channel()));
+                return false;
+            }
+            true
+        });
+        events
+    }
+
+    fn tween_index(&self, node: NodeId, channel: Channel) -> Option<usize> {
+        self.tweens.iter().position(|t| t.node() == node && t.channel() == channel)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/animator_tests.rs"]
+mod tests;
@@ -0,0 +1,61 @@
+//! Easing curves.
+//!
+//! An [`Ease`] reshapes the linear progress of an animation, 0 to 1, into the
+//! progress of its value. Every curve starts at 0 and ends at 1; `In` curves
+//! start slowly, `Out` curves finish slowly and `InOut` curves do both.
+
+use std::f32::consts::PI;
+
+/// Shape of an animation's progress over time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Ease {
+    #[default]
+    Linear,
+    InQuad,
+    OutQuad,
+    InOutQuad,
+    InCubic,
+    OutCubic,
+    InOutCubic,
+    InSine,
+    OutSine,
+    InOutSine,
+    /// Overshoots the end slightly before settling.
+    OutBack,
+}
+
+impl Ease {
+    /// Eased progress at linear progress `t`, clamped to 0..1.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Ease::Linear => t,
+            Ease::InQuad => t * t,
+            Ease::OutQuad => 1.0 - (1.0 - t) * (1.0 - t),
+            Ease::InOutQuad => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Ease::InCubic => t * t * t,
+            Ease::OutCubic => 1.0 - (1.0 - t).powi(3),
+            Ease::InOutCubic => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Ease::InSine => 1.0 - (t * PI / 2.0).cos(),
+            Ease::OutSine => (t * PI / 2.0).sin(),
+            Ease::InOutSine => -((t * PI).cos() - 1.0) / 2.0,
+            Ease::OutBack => {
+                const C1: f32 = 1.70158;
+                const C3: f32 = C1 + 1.0;
+                1.0 + C3 * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2)
+            }
+        }
+    }
+}
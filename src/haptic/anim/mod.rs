@@ -0,0 +1,13 @@
+// src/haptic/anim/mod.rs
+pub mod animator;
+pub mod ease;
+pub mod props;
+pub mod timeline;
+pub mod track;
+pub mod tween;
+pub use animator::{Animator, TimelineId};
+pub use ease::Ease;
+pub use props::{AnimatedProps, NodeProps};
+pub use timeline::{LoopMode, Timeline, TimelineEvent};
+pub use track::{AnimValue, Animatable, Channel, ChannelTrack, Interpolation, Key, Track};
+pub use tween::{tween, Property, Tween, TweenBuilder};
@@ -0,0 +1,102 @@
+//! Animated node properties beyond position.
+//!
+//! Scene nodes only carry a position; rotation, scale, opacity and haptic
+//! intensity exist for nodes while something animates them. [`AnimatedProps`]
+//! stores those per node for the renderers to read, next to the scene.
+
+use std::collections::HashMap;
+
+use super::track::{AnimValue, Channel};
+use crate::core::{Quat, Vec3};
+use crate::scene::{NodeId, Scene};
+
+/// Animated state of one node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NodeProps {
+    pub rotation: Quat,
+    pub scale: Vec3,
+    /// 0 (invisible) to 1 (opaque).
+    pub opacity: f32,
+    /// Factor on the node's haptic output, 0 to 1.
+    pub haptic_intensity: f32,
+}
+
+impl NodeProps {
+    /// Unrotated, unscaled, opaque and at full haptic strength.
+    pub const IDENTITY: Self =
+        Self { rotation: Quat::identity(), scale: Vec3::one(), opacity: 1.0, haptic_intensity: 1.0 };
+}
+
+impl Default for NodeProps {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Animated properties of scene nodes.
+#[derive(Debug, Clone, Default)]
+pub struct AnimatedProps {
+    props: HashMap<NodeId, NodeProps>,
+}
+
+impl AnimatedProps {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Properties of `node`; identity if nothing animated it.
+    pub fn get(&self, node: NodeId) -> NodeProps {
+        self.props.get(&node).copied().unwrap_or_default()
+    }
+
+    pub fn get_mut(&mut self, node: NodeId) -> &mut NodeProps {
+        self.props.entry(node).or_default()
+    }
+
+    pub fn remove(&mut self, node: NodeId) -> Option<NodeProps> {
+        self.props.remove(&node)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.props.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.props.is_empty()
+    }
+
+    /// Forgets nodes that left the scene.
+    pub fn retain_in(&mut self, scene: &Scene) {
+        self.props.retain(|&node, _| scene.contains(node));
+    }
+
+    /// Current value of one channel of `node`; `None` if it left the scene.
+    pub fn current(&self, scene: &Scene, node: NodeId, channel: Channel) -> Option<AnimValue> {
+        let position = scene.get(node)?.position;
+        let props = self.get(node);
+        Some(match channel {
+            Channel::Position => AnimValue::Position(position),
+            Channel::Rotation => AnimValue::Rotation(props.rotation),
+            Channel::Scale => AnimValue::Scale(props.scale),
+            Channel::Opacity => AnimValue::Opacity(props.opacity),
+            Channel::HapticIntensity => AnimValue::HapticIntensity(props.haptic_intensity),
+        })
+    }
+
+    /// Sets one channel of `node`: positions go to the scene, the rest here.
+    pub fn apply(&mut self, scene: &mut Scene, node: NodeId, value: AnimValue) {
+        match value {
+            AnimValue::Position(position) => {
+                if let Some(n) = scene.get_mut(node) {
+                    n.position = position;
+                }
+            }
+            AnimValue::Rotation(rotation) => self.get_mut(node).rotation = rotation,
+            AnimValue::Scale(scale) => self.get_mut(node).scale = scale,
+            AnimValue::Opacity(opacity) => self.get_mut(node).opacity = opacity.clamp(0.0, 1.0),
+            AnimValue::HapticIntensity(intensity) => self.get_mut(node).haptic_intensity = intensity.clamp(0.0, 1.0),
+        }
+    }
+}
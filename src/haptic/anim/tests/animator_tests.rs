@@ -0,0 +1,53 @@
+use super::*;
+use crate::anim::{ChannelTrack, LoopMode, Track};
+use crate::core::Vec3;
+use crate::scene::{Node, NodeKind};
+
+const TEST_EPSILON: f32 = 1e-5;
+
+fn slide(node: crate::scene::NodeId, seconds: f32) -> Timeline {
+    let track = Track::new().with_key(0.0, Vec3::zero()).with_key(seconds, Vec3::unit_x());
+    Timeline::new().with_track(node, ChannelTrack::Position(track))
+}
+
+#[test]
+fn test_tick_advances_by_clock_time() {
+    let mut scene = Scene::new();
+    let mut props = AnimatedProps::new();
+    let node = scene.insert(Node::new(NodeKind::Panel), None).unwrap();
+    let mut animator = Animator::new();
+    let id = animator.add(slide(node, 1.0));
+    animator.get_mut(id).unwrap().play();
+
+    assert!(animator.tick(5_000_000, &mut scene, &mut props).is_empty());
+    assert_eq!(animator.get(id).unwrap().time(), Seconds(0.0));
+    animator.tick(5_250_000, &mut scene, &mut props);
+    assert!((scene.get(node).unwrap().position.x - 0.25).abs() < TEST_EPSILON);
+
+    let events = animator.tick(6_500_000, &mut scene, &mut props);
+    assert_eq!(events, vec![(id, TimelineEvent::Completed)]);
+    assert_eq!(scene.get(node).unwrap().position, Vec3::unit_x());
+}
+
+#[test]
+fn test_paused_and_finished_timelines_leave_nodes_alone() {
+    let mut scene = Scene::new();
+    let mut props = AnimatedProps::new();
+    let node = scene.insert(Node::new(NodeKind::Panel), None).unwrap();
+    let mut animator = Animator::new();
+    let paused = animator.add(slide(node, 1.0));
+    let looping = animator.add(slide(node, 1.0).with_loop(LoopMode::Loop));
+    assert_eq!(animator.len(), 2);
+
+    scene.get_mut(node).unwrap().position = Vec3::new(0.0, 2.0, 0.0);
+    animator.advance(Seconds(0.5), &mut scene, &mut props);
+    assert_eq!(scene.get(node).unwrap().position, Vec3::new(0.0, 2.0, 0.0));
+
+    animator.get_mut(looping).unwrap().play();
+    let events = animator.advance(Seconds(1.25), &mut scene, &mut props);
+    assert_eq!(events, vec![(looping, TimelineEvent::Looped(1))]);
+    assert!((scene.get(node).unwrap().position.x - 0.25).abs() < TEST_EPSILON);
+
+    assert!(animator.remove(paused).is_some());
+    assert!(animator.get(paused).is_none());
+}
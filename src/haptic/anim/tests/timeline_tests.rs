@@ -0,0 +1,107 @@
+use super::*;
+use crate::anim::{Channel, Track};
+use crate::core::Vec3;
+use crate::scene::{Node, NodeKind};
+
+const TEST_EPSILON: f32 = 1e-5;
+
+/// A node sliding 1 m along X over a second and fading out over two.
+fn timeline(scene: &mut Scene) -> (Timeline, NodeId) {
+    let node = scene.insert(Node::new(NodeKind::Panel), None).unwrap();
+    let slide = Track::new().with_key(0.0, Vec3::zero()).with_key(1.0, Vec3::unit_x());
+    let fade = Track::new().with_key(0.0, 1.0).with_key(2.0, 0.0);
+    let timeline =
+        Timeline::new().with_track(node, ChannelTrack::Position(slide)).with_track(node, ChannelTrack::Opacity(fade));
+    (timeline, node)
+}
+
+fn close(a: Seconds, b: f32) -> bool {
+    (a.value() - b).abs() < TEST_EPSILON
+}
+
+#[test]
+fn test_duration_is_the_longest_track() {
+    let mut scene = Scene::new();
+    let (timeline, _) = timeline(&mut scene);
+    assert_eq!(timeline.duration(), Seconds(2.0));
+    assert_eq!(timeline.with_duration(Seconds(3.0)).duration(), Seconds(3.0));
+}
+
+#[test]
+fn test_plays_once_and_completes() {
+    let mut scene = Scene::new();
+    let (mut timeline, node) = timeline(&mut scene);
+    let mut props = AnimatedProps::new();
+
+    // Paused until played
+    assert_eq!(timeline.advance(Seconds(0.5)), None);
+    assert_eq!(timeline.time(), Seconds(0.0));
+
+    timeline.play();
+    assert_eq!(timeline.advance(Seconds(0.5)), None);
+    timeline.apply(&mut scene, &mut props);
+    assert!(scene.get(node).unwrap().position.distance_to(Vec3::new(0.5, 0.0, 0.0)) < TEST_EPSILON);
+    assert!((props.get(node).opacity - 0.75).abs() < TEST_EPSILON);
+
+    assert_eq!(timeline.advance(Seconds(2.0)), Some(TimelineEvent::Completed));
+    assert!(timeline.is_finished() && !timeline.is_playing());
+    assert_eq!(timeline.time(), Seconds(2.0));
+    assert_eq!(timeline.advance(Seconds(1.0)), None);
+
+    // Playing again starts over
+    timeline.play();
+    assert_eq!(timeline.time(), Seconds(0.0));
+}
+
+#[test]
+fn test_pause_seek_and_speed() {
+    let mut scene = Scene::new();
+    let (timeline, _) = timeline(&mut scene);
+    let mut timeline = timeline.with_speed(2.0);
+    timeline.play();
+    timeline.advance(Seconds(0.25));
+    assert!(close(timeline.time(), 0.5));
+    timeline.pause();
+    timeline.advance(Seconds(1.0));
+    assert!(close(timeline.time(), 0.5));
+    timeline.seek(Seconds(5.0));
+    assert_eq!(timeline.time(), Seconds(2.0));
+    timeline.seek(Seconds(1.5));
+    let sample = timeline.sample();
+    assert_eq!(sample.len(), 2);
+    assert_eq!(sample[0].1.channel(), Channel::Position);
+    assert_eq!(sample[1].1, AnimValue::Opacity(0.25));
+    timeline.stop();
+    assert_eq!(timeline.time(), Seconds(0.0));
+}
+
+#[test]
+fn test_loop_wraps_and_counts_laps() {
+    let mut scene = Scene::new();
+    let (timeline, _) = timeline(&mut scene);
+    let mut timeline = timeline.with_loop(LoopMode::Loop);
+    timeline.play();
+    assert_eq!(timeline.advance(Seconds(1.5)), None);
+    assert_eq!(timeline.advance(Seconds(1.0)), Some(TimelineEvent::Looped(1)));
+    assert!(close(timeline.time(), 0.5));
+    assert_eq!(timeline.advance(Seconds(4.0)), Some(TimelineEvent::Looped(2)));
+    assert!(close(timeline.time(), 0.5));
+    assert!(timeline.is_playing());
+}
+
+#[test]
+fn test_ping_pong_turns_at_both_ends() {
+    let mut scene = Scene::new();
+    let (timeline, _) = timeline(&mut scene);
+    let mut timeline = timeline.with_loop(LoopMode::PingPong);
+    timeline.play();
+    assert_eq!(timeline.advance(Seconds(1.5)), None);
+    // Turns at 2 s and heads back
+    assert_eq!(timeline.advance(Seconds(1.0)), Some(TimelineEvent::Looped(1)));
+    assert!(close(timeline.time(), 1.5));
+    assert_eq!(timeline.advance(Seconds(1.0)), None);
+    assert!(close(timeline.time(), 0.5));
+    // Turns at 0 s and heads forward again
+    assert_eq!(timeline.advance(Seconds(1.0)), Some(TimelineEvent::Looped(1)));
+    assert!(close(timeline.time(), 0.5));
+}
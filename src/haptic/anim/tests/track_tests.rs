@@ -0,0 +1,77 @@
+use super::*;
+use crate::core::Deg;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+#[test]
+fn test_keys_stay_sorted_and_replace() {
+    let track = Track::new().with_key(1.0, 10.0).with_key(0.0, 0.0).with_key(0.5, 2.0).with_key(0.5, 5.0);
+    let times: Vec<f32> = track.keys().iter().map(|k| k.time).collect();
+    assert_eq!(times, vec![0.0, 0.5, 1.0]);
+    assert_eq!(track.keys()[1].value, 5.0);
+    assert_eq!(track.duration(), 1.0);
+}
+
+#[test]
+fn test_sample_interpolates_and_holds_ends() {
+    let track = Track::new().with_key(1.0, 0.0).with_key(3.0, 10.0);
+    assert_eq!(track.sample(0.0), Some(0.0));
+    assert!((track.sample(2.5).unwrap() - 7.5).abs() < TEST_EPSILON);
+    assert_eq!(track.sample(3.0), Some(10.0));
+    assert_eq!(track.sample(9.0), Some(10.0));
+    assert_eq!(Track::<f32>::new().sample(0.0), None);
+}
+
+#[test]
+fn test_rotation_takes_the_shortest_arc() {
+    let half_turn = Quat::from_axis_angle(Vec3::unit_z(), Deg(90.0));
+    let track = Track::new().with_key(0.0, Quat::identity()).with_key(1.0, half_turn);
+    let middle = track.sample(0.5).unwrap();
+    let expected = Quat::from_axis_angle(Vec3::unit_z(), Deg(45.0));
+    assert!(middle.rotate(Vec3::unit_x()).distance_to(expected.rotate(Vec3::unit_x())) < TEST_EPSILON);
+}
+
+#[test]
+fn test_channel_tracks_tag_values() {
+    let track = ChannelTrack::Opacity(Track::new().with_key(0.0, 1.0).with_key(2.0, 0.0));
+    assert_eq!(track.channel(), Channel::Opacity);
+    assert_eq!(track.duration(), 2.0);
+    let value = track.sample(1.0).unwrap();
+    assert_eq!(value, AnimValue::Opacity(0.5));
+    assert_eq!(value.channel(), Channel::Opacity);
+}
+
+#[test]
+fn test_step_and_cubic_interpolation() {
+    let step = Track::new().with_interpolation(Interpolation::Step).with_key(0.0, 0.0).with_key(1.0, 10.0);
+    assert_eq!(step.sample(0.99), Some(0.0));
+    assert_eq!(step.sample(1.0), Some(10.0));
+
+    // Evenly spaced keys on a line stay on the line
+    let line = Track::new()
+        .with_interpolation(Interpolation::Cubic)
+        .with_key(0.0, 0.0)
+        .with_key(1.0, 1.0)
+        .with_key(2.0, 2.0)
+        .with_key(3.0, 3.0);
+    assert!((line.sample(1.5).unwrap() - 1.5).abs() < TEST_EPSILON);
+
+    // A peak is passed through smoothly rather than as a corner
+    let peak = Track::new()
+        .with_interpolation(Interpolation::Cubic)
+        .with_key(0.0, Vec3::zero())
+        .with_key(1.0, Vec3::unit_y())
+        .with_key(2.0, Vec3::zero());
+    assert_eq!(peak.sample(1.0), Some(Vec3::unit_y()));
+    assert!(peak.sample(0.75).unwrap().y > 0.75);
+}
+
+#[test]
+fn test_cubic_rotation_slerps() {
+    let quarter = Quat::from_axis_angle(Vec3::unit_y(), Deg(90.0));
+    let track =
+        Track::new().with_interpolation(Interpolation::Cubic).with_key(0.0, Quat::identity()).with_key(1.0, quarter);
+    let expected = Quat::from_axis_angle(Vec3::unit_y(), Deg(45.0));
+    assert!((track.sample(0.5).unwrap().rotate(Vec3::unit_z()) - expected.rotate(Vec3::unit_z())).length() < 1e-4);
+    assert_eq!(ChannelTrack::new(Channel::Rotation, Interpolation::Step).channel(), Channel::Rotation);
+}
@@ -0,0 +1,130 @@
+use super::*;
+use crate::anim::{AnimatedProps, Animator};
+use crate::core::Seconds;
+use crate::scene::{Node, NodeKind, Scene};
+
+const TEST_EPSILON: f32 = 1e-5;
+
+fn setup() -> (Scene, AnimatedProps, NodeId) {
+    let mut scene = Scene::new();
+    let node = scene.insert(Node::new(NodeKind::Panel), None).unwrap();
+    (scene, AnimatedProps::new(), node)
+}
+
+#[test]
+fn test_eases_run_from_zero_to_one() {
+    let eases = [
+        Ease::Linear,
+        Ease::InQuad,
+        Ease::OutQuad,
+        Ease::InOutQuad,
+        Ease::InCubic,
+        Ease::OutCubic,
+        Ease::InOutCubic,
+        Ease::InSine,
+        Ease::OutSine,
+        Ease::InOutSine,
+        Ease::OutBack,
+    ];
+    for ease in eases {
+        assert!(ease.apply(0.0).abs() < TEST_EPSILON, "{ease:?}");
+        assert!((ease.apply(1.0) - 1.0).abs() < TEST_EPSILON, "{ease:?}");
+    }
+    assert!(Ease::OutCubic.apply(0.5) > 0.5);
+    assert!(Ease::InCubic.apply(0.5) < 0.5);
+    assert!((Ease::InOutCubic.apply(0.5) - 0.5).abs() < TEST_EPSILON);
+    assert!(Ease::OutBack.apply(0.8) > 1.0);
+    assert_eq!(Ease::Linear.apply(2.0), 1.0);
+}
+
+#[test]
+fn test_tween_moves_from_current_value_with_ease() {
+    let (mut scene, mut props, node) = setup();
+    let mut animator = Animator::new();
+    let target = Vec3::new(1.0, 0.0, 0.0);
+    animator.tween(tween(Property::position(node)).to(target).duration(0.3).ease(Ease::OutCubic));
+    assert!(animator.is_tweening(node, Channel::Position));
+
+    animator.advance(Seconds(0.15), &mut scene, &mut props);
+    let x = scene.get(node).unwrap().position.x;
+    assert!((x - Ease::OutCubic.apply(0.5)).abs() < TEST_EPSILON);
+
+    animator.advance(Seconds(0.15), &mut scene, &mut props);
+    assert_eq!(scene.get(node).unwrap().position, target);
+    assert!(!animator.is_tweening(node, Channel::Position));
+    assert_eq!(animator.take_completed(), vec![(node, Channel::Position)]);
+    assert!(animator.take_completed().is_empty());
+}
+
+#[test]
+fn test_new_tween_replaces_running_one_on_same_property() {
+    let (mut scene, mut props, node) = setup();
+    let mut animator = Animator::new();
+    animator.tween(tween(Property::opacity(node)).to(0.0).duration(1.0));
+    animator.tween(tween(Property::scale(node)).to(Vec3::splat(2.0)).duration(1.0));
+    animator.advance(Seconds(0.5), &mut scene, &mut props);
+    assert!((props.get(node).opacity - 0.5).abs() < TEST_EPSILON);
+
+    let replaced = animator.tween(tween(Property::opacity(node)).to(1.0).duration(0.5));
+    assert_eq!(replaced.map(|t| t.target()), Some(AnimValue::Opacity(0.0)));
+    assert!(animator.is_tweening(node, Channel::Scale));
+
+    // Carries on from the half-faded value rather than jumping
+    animator.advance(Seconds(0.25), &mut scene, &mut props);
+    assert!((props.get(node).opacity - 0.75).abs() < TEST_EPSILON);
+    assert!((props.get(node).scale.x - 1.75).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_cancel_leaves_property_where_it_is() {
+    let (mut scene, mut props, node) = setup();
+    let mut animator = Animator::new();
+    animator.tween(tween(Property::haptic_intensity(node)).to(0.0).duration(1.0));
+    animator.tween(tween(Property::position(node)).to(Vec3::unit_y()).duration(1.0));
+    animator.advance(Seconds(0.25), &mut scene, &mut props);
+
+    assert!(animator.cancel_tween(node, Channel::HapticIntensity).is_some());
+    assert!(animator.cancel_tween(node, Channel::HapticIntensity).is_none());
+    animator.advance(Seconds(0.25), &mut scene, &mut props);
+    assert!((props.get(node).haptic_intensity - 0.75).abs() < TEST_EPSILON);
+    assert!((scene.get(node).unwrap().position.y - 0.5).abs() < TEST_EPSILON);
+
+    assert_eq!(animator.cancel_tweens(node), 1);
+    assert!(animator.take_completed().is_empty());
+}
+
+#[test]
+fn test_from_and_delay() {
+    let (mut scene, mut props, node) = setup();
+    let mut animator = Animator::new();
+    let spin = Quat::from_axis_angle(Vec3::unit_y(), crate::core::Deg(90.0));
+    animator.tween(tween(Property::rotation(node)).from(Quat::identity()).to(spin).duration(0.5).delay(0.5));
+
+    animator.advance(Seconds(0.25), &mut scene, &mut props);
+    assert_eq!(props.get(node).rotation, Quat::identity());
+    animator.advance(Seconds(0.5), &mut scene, &mut props);
+    let half = Quat::from_axis_angle(Vec3::unit_y(), crate::core::Deg(45.0));
+    let rotated = props.get(node).rotation.rotate(Vec3::unit_z());
+    assert!((rotated - half.rotate(Vec3::unit_z())).length() < 1e-4);
+}
+
+#[test]
+fn test_tweens_on_removed_nodes_are_dropped() {
+    let (mut scene, mut props, node) = setup();
+    let mut animator = Animator::new();
+    animator.tween(tween(Property::position(node)).to(Vec3::unit_x()));
+    scene.remove(node).unwrap();
+    animator.advance(Seconds(0.1), &mut scene, &mut props);
+    assert!(!animator.is_tweening(node, Channel::Position));
+    assert!(animator.take_completed().is_empty());
+}
+
+#[test]
+fn test_zero_duration_tween_snaps() {
+    let (mut scene, mut props, node) = setup();
+    let mut animator = Animator::new();
+    animator.tween(tween(Property::position(node)).to(Vec3::unit_z()).duration(0.0));
+    animator.advance(Seconds(0.0), &mut scene, &mut props);
+    assert_eq!(scene.get(node).unwrap().position, Vec3::unit_z());
+    assert_eq!(animator.take_completed(), vec![(node, Channel::Position)]);
+}
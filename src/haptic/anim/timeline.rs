@@ -0,0 +1,200 @@
+//! Timelines: tracks on scene nodes played together.
+//!
+//! A [`Timeline`] holds [`ChannelTrack`]s, each driving one channel of one node,
+//! and a playhead moved by [`Timeline::advance`]. Its length is that of its
+//! longest track unless set. It plays once, loops or ping-pongs, can be paused
+//! and sought, and reports loops and completion as [`TimelineEvent`]s.
+
+use super::props::AnimatedProps;
+use super::track::{AnimValue, ChannelTrack};
+use crate::core::Seconds;
+use crate::scene::{NodeId, Scene};
+
+/// What happens at the end of a timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoopMode {
+    /// Stops at the end.
+    #[default]
+    Once,
+    /// Starts over from the beginning.
+    Loop,
+    /// Plays backwards to the beginning, then forwards again.
+    PingPong,
+}
+
+/// Something a timeline reached while advancing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineEvent {
+    /// Went round (or turned, ping-ponging) this many times during the step.
+    Looped(u32),
+    /// Reached the end and stopped.
+    Completed,
+}
+
+/// Tracks on scene nodes sharing a playhead.
+#[derive(Debug, Clone, Default)]
+pub struct Timeline {
+    tracks: Vec<(NodeId, ChannelTrack)>,
+    /// Explicit length, overriding the longest track.
+    length: Option<f32>,
+    loop_mode: LoopMode,
+    /// Playback rate; 1 is real time.
+    speed: f32,
+    time: f32,
+    /// Playing backwards, in ping-pong.
+    reversed: bool,
+    playing: bool,
+    finished: bool,
+}
+
+impl Timeline {
+    /// Empty paused timeline playing once at normal speed.
+    pub fn new() -> Self {
+        Self { speed: 1.0, ..Self::default() }
+    }
+
+    pub fn with_track(mut self, node: NodeId, track: ChannelTrack) -> Self {
+        self.add_track(node, track);
+        self
+    }
+
+    pub fn with_loop(mut self, mode: LoopMode) -> Self {
+        self.loop_mode = mode;
+        self
+    }
+
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed.max(0.0);
+        self
+    }
+
+    /// Sets the length instead of taking that of the longest track.
+    pub fn with_duration(mut self, duration: Seconds) -> Self {
+        self.length = Some(duration.value().max(0.0));
+        self
+    }
+
+    pub fn add_track(&mut self, node: NodeId, track: ChannelTrack) {
+        self.tracks.push((node, track));
+    }
+
+    #[inline]
+    pub fn tracks(&self) -> &[(NodeId, ChannelTrack)] {
+        &self.tracks
+    }
+
+    pub fn duration(&self) -> Seconds {
+        Seconds(self.length.unwrap_or_else(|| self.tracks.iter().map(|(_, t)| t.duration()).fold(0.0, f32::max)))
+    }
+
+    #[inline]
+    pub fn loop_mode(&self) -> LoopMode {
+        self.loop_mode
+    }
+
+    /// Playhead position.
+    #[inline]
+    pub fn time(&self) -> Seconds {
+        Seconds(self.time)
+    }
+
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Whether a timeline playing once reached its end.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Plays on from the playhead, or from the start once finished.
+    pub fn play(&mut self) {
+        if self.finished {
+            self.time = 0.0;
+            self.reversed = false;
+            self.finished = false;
+        }
+        self.playing = true;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Pauses and rewinds to the start.
+    pub fn stop(&mut self) {
+        self.playing = false;
+        self.finished = false;
+        self.reversed = false;
+        self.time = 0.0;
+    }
+
+    /// Moves the playhead to `time`, clamped to the timeline.
+    pub fn seek(&mut self, time: Seconds) {
+        self.time = time.value().clamp(0.0, self.duration().value());
+        self.finished = false;
+    }
+
+    /// Moves the playhead by `dt` of real time if playing.
+    pub fn advance(&mut self, dt: Seconds) -> Option<TimelineEvent> {
+        if !self.playing {
+            return None;
+        }
+        let duration = self.duration().value();
+        let step = dt.value().max(0.0) * self.speed;
+        if duration <= 0.0 {
+            self.time = 0.0;
+            return self.finish();
+        }
+        match self.loop_mode {
+            LoopMode::Once => {
+                self.time += step;
+                if self.time < duration {
+                    return None;
+                }
+                self.time = duration;
+                self.finish()
+            }
+            LoopMode::Loop => {
+                let end = self.time + step;
+                self.time = end.rem_euclid(duration);
+                let laps = (end / duration) as u32;
+                (laps > 0).then_some(TimelineEvent::Looped(laps))
+            }
+            LoopMode::PingPong => {
+                // Position along an unfolded forward-then-back path
+                let start = if self.reversed { 2.0 * duration - self.time } else { self.time };
+                let unfolded = start + step;
+                let turns = (unfolded / duration) as u32 - (start / duration) as u32;
+                let folded = unfolded.rem_euclid(2.0 * duration);
+                self.reversed = folded > duration;
+                self.time = if self.reversed { 2.0 * duration - folded } else { folded };
+                (turns > 0).then_some(TimelineEvent::Looped(turns))
+            }
+        }
+    }
+
+    /// Every track's value at the playhead.
+    pub fn sample(&self) -> Vec<(NodeId, AnimValue)> {
+        self.tracks.iter().filter_map(|(node, track)| Some((*node, track.sample(self.time)?))).collect()
+    }
+
+    /// Sets the animated channels to their values at the playhead.
+    pub fn apply(&self, scene: &mut Scene, props: &mut AnimatedProps) {
+        for (node, value) in self.sample() {
+            props.apply(scene, node, value);
+        }
+    }
+
+    fn finish(&mut self) -> Option<TimelineEvent> {
+        self.playing = false;
+        self.finished = true;
+        Some(TimelineEvent::Completed)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/timeline_tests.rs"]
+mod tests;
@@ -0,0 +1,260 @@
+//! Keyframe tracks of animated values.
+//!
+//! A [`Track`] holds keys of one value type sorted by time and samples between
+//! them, holding each key ([`Interpolation::Step`]), blending linearly or along
+//! a Catmull-Rom curve through the neighbouring keys; rotations always slerp
+//! along the shortest arc. A [`ChannelTrack`] is a track tagged with the node
+//! property ([`Channel`]) it drives, which is what timelines hold.
+
+use crate::core::{Quat, Vec3};
+
+/// A value that can be blended between two keys.
+pub trait Animatable: Copy {
+    /// The value `t` (0 to 1) of the way from `self` to `other`.
+    fn interpolate(self, other: Self, t: f32) -> Self;
+
+    /// The value `t` of the way from `from` to `to` on a smooth curve that
+    /// also passes through `before` and `after`. Blends linearly by default.
+    fn cubic(_before: Self, from: Self, to: Self, _after: Self, t: f32) -> Self {
+        from.interpolate(to, t)
+    }
+}
+
+impl Animatable for f32 {
+    #[inline]
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+
+    fn cubic(before: Self, from: Self, to: Self, after: Self, t: f32) -> Self {
+        let (t2, t3) = (t * t, t * t * t);
+        0.5 * (2.0 * from
+            + (to - before) * t
+            + (2.0 * before - 5.0 * from + 4.0 * to - after) * t2
+            + (3.0 * from - before - 3.0 * to + after) * t3)
+    }
+}
+
+impl Animatable for Vec3 {
+    #[inline]
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        self.lerp(other, t)
+    }
+
+    fn cubic(before: Self, from: Self, to: Self, after: Self, t: f32) -> Self {
+        Vec3::new(
+            f32::cubic(before.x, from.x, to.x, after.x, t),
+            f32::cubic(before.y, from.y, to.y, after.y, t),
+            f32::cubic(before.z, from.z, to.z, after.z, t),
+        )
+    }
+}
+
+/// Rotations keep the default and slerp between keys even on cubic tracks.
+impl Animatable for Quat {
+    #[inline]
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        self.slerp(other, t)
+    }
+}
+
+/// How a track moves between two keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    /// Holds each key until the next.
+    Step,
+    #[default]
+    Linear,
+    /// Catmull-Rom through the surrounding keys.
+    Cubic,
+}
+
+/// A value at a point in time, in seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Key<T> {
+    pub time: f32,
+    pub value: T,
+}
+
+/// Keys of one value, sorted by time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Track<T> {
+    keys: Vec<Key<T>>,
+    interpolation: Interpolation,
+}
+
+impl<T: Animatable> Track<T> {
+    /// Empty track interpolating linearly.
+    pub fn new() -> Self {
+        Self { keys: Vec::new(), interpolation: Interpolation::Linear }
+    }
+
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    pub fn with_key(mut self, time: f32, value: T) -> Self {
+        self.insert(time, value);
+        self
+    }
+
+    /// Adds a key, replacing any key at the same time.
+    pub fn insert(&mut self, time: f32, value: T) {
+        let index = self.keys.partition_point(|k| k.time < time);
+        match self.keys.get_mut(index) {
+            Some(key) if key.time == time => key.value = value,
+            _ => self.keys.insert(index, Key { time, value }),
+        }
+    }
+
+    #[inline]
+    pub fn keys(&self) -> &[Key<T>] {
+        &self.keys
+    }
+
+    #[inline]
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Time of the last key.
+    pub fn duration(&self) -> f32 {
+        self.keys.last().map_or(0.0, |k| k.time)
+    }
+
+    /// Value at `time`, holding the first and last keys outside them.
+    pub fn sample(&self, time: f32) -> Option<T> {
+        let index = self.keys.partition_point(|k| k.time <= time);
+        match (index.checked_sub(1).map(|i| self.keys[i]), self.keys.get(index)) {
+            (Some(a), Some(b)) => {
+                let t = (time - a.time) / (b.time - a.time);
+                Some(match self.interpolation {
+                    Interpolation::Step => a.value,
+                    Interpolation::Linear => a.value.interpolate(b.value, t),
+                    Interpolation::Cubic => {
+                        let before = index.checked_sub(2).map_or(a.value, |i| self.keys[i].value);
+                        let after = self.keys.get(index + 1).map_or(b.value, |k| k.value);
+                        T::cubic(before, a.value, b.value, after, t)
+                    }
+                })
+            }
+            (Some(a), None) => Some(a.value),
+            (None, b) => b.map(|b| b.value),
+        }
+    }
+}
+
+impl<T: Animatable> Default for Track<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A node property an animation can drive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Channel {
+    Position,
+    Rotation,
+    Scale,
+    Opacity,
+    HapticIntensity,
+}
+
+/// A sampled channel value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnimValue {
+    Position(Vec3),
+    Rotation(Quat),
+    Scale(Vec3),
+    Opacity(f32),
+    HapticIntensity(f32),
+}
+
+impl AnimValue {
+    pub fn channel(&self) -> Channel {
+        match self {
+            AnimValue::Position(_) => Channel::Position,
+            AnimValue::Rotation(_) => Channel::Rotation,
+            AnimValue::Scale(_) => Channel::Scale,
+            AnimValue::Opacity(_) => Channel::Opacity,
+            AnimValue::HapticIntensity(_) => Channel::HapticIntensity,
+        }
+    }
+
+    /// The value `t` of the way to `other`; `other` if the channels differ.
+    pub fn interpolate(self, other: Self, t: f32) -> Self {
+        match (self, other) {
+            (AnimValue::Position(a), AnimValue::Position(b)) => AnimValue::Position(a.interpolate(b, t)),
+            (AnimValue::Rotation(a), AnimValue::Rotation(b)) => AnimValue::Rotation(a.interpolate(b, t)),
+            (AnimValue::Scale(a), AnimValue::Scale(b)) => AnimValue::Scale(a.interpolate(b, t)),
+            (AnimValue::Opacity(a), AnimValue::Opacity(b)) => AnimValue::Opacity(a.interpolate(b, t)),
+            (AnimValue::HapticIntensity(a), AnimValue::HapticIntensity(b)) => {
+                AnimValue::HapticIntensity(a.interpolate(b, t))
+            }
+            (_, other) => other,
+        }
+    }
+}
+
+/// A track driving one channel.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChannelTrack {
+    Position(Track<Vec3>),
+    Rotation(Track<Quat>),
+    Scale(Track<Vec3>),
+    Opacity(Track<f32>),
+    HapticIntensity(Track<f32>),
+}
+
+impl ChannelTrack {
+    /// Keyless track for `channel`.
+    pub fn new(channel: Channel, interpolation: Interpolation) -> Self {
+        match channel {
+            Channel::Position => ChannelTrack::Position(Track::new().with_interpolation(interpolation)),
+            Channel::Rotation => ChannelTrack::Rotation(Track::new().with_interpolation(interpolation)),
+            Channel::Scale => ChannelTrack::Scale(Track::new().with_interpolation(interpolation)),
+            Channel::Opacity => ChannelTrack::Opacity(Track::new().with_interpolation(interpolation)),
+            Channel::HapticIntensity => {
+                ChannelTrack::HapticIntensity(Track::new().with_interpolation(interpolation))
+            }
+        }
+    }
+
+    pub fn channel(&self) -> Channel {
+        match self {
+            ChannelTrack::Position(_) => Channel::Position,
+            ChannelTrack::Rotation(_) => Channel::Rotation,
+            ChannelTrack::Scale(_) => Channel::Scale,
+            ChannelTrack::Opacity(_) => Channel::Opacity,
+            ChannelTrack::HapticIntensity(_) => Channel::HapticIntensity,
+        }
+    }
+
+    pub fn duration(&self) -> f32 {
+        match self {
+            ChannelTrack::Position(track) | ChannelTrack::Scale(track) => track.duration(),
+            ChannelTrack::Rotation(track) => track.duration(),
+            ChannelTrack::Opacity(track) | ChannelTrack::HapticIntensity(track) => track.duration(),
+        }
+    }
+
+    pub fn sample(&self, time: f32) -> Option<AnimValue> {
+        match self {
+            ChannelTrack::Position(track) => track.sample(time).map(AnimValue::Position),
+            ChannelTrack::Rotation(track) => track.sample(time).map(AnimValue::Rotation),
+            ChannelTrack::Scale(track) => track.sample(time).map(AnimValue::Scale),
+            ChannelTrack::Opacity(track) => track.sample(time).map(AnimValue::Opacity),
+            ChannelTrack::HapticIntensity(track) => track.sample(time).map(AnimValue::HapticIntensity),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/track_tests.rs"]
+mod tests;
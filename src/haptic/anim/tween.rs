@@ -0,0 +1,172 @@
+//! Tweens: one-off animations of a single node property.
+//!
+//! ```ignore
+//! animator.tween(tween(Property::position(node)).to(target).duration(0.3).ease(Ease::OutCubic));
+//! ```
+//!
+//! - A [`Property`] names one channel of one node, typed by its value.
+//! - [`tween`] starts a builder; [`TweenBuilder::to`] gives the [`Tween`].
+//! - Without [`TweenBuilder::from`], a tween starts at the property's value
+//!   on its first frame, so a tween replacing another continues smoothly.
+//! - The [`Animator`](super::Animator) runs at most one tween per property.
+
+use std::fmt;
+
+use super::ease::Ease;
+use super::track::{AnimValue, Animatable, Channel};
+use crate::core::{Quat, Vec3};
+use crate::scene::NodeId;
+
+/// One animatable property of one node.
+#[derive(Clone, Copy)]
+pub struct Property<T> {
+    pub node: NodeId,
+    pub channel: Channel,
+    wrap: fn(T) -> AnimValue,
+}
+
+impl<T> fmt::Debug for Property<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Property").field("node", &self.node).field("channel", &self.channel).finish()
+    }
+}
+
+impl Property<Vec3> {
+    pub fn position(node: NodeId) -> Self {
+        Self { node, channel: Channel::Position, wrap: AnimValue::Position }
+    }
+
+    pub fn scale(node: NodeId) -> Self {
+        Self { node, channel: Channel::Scale, wrap: AnimValue::Scale }
+    }
+}
+
+impl Property<Quat> {
+    pub fn rotation(node: NodeId) -> Self {
+        Self { node, channel: Channel::Rotation, wrap: AnimValue::Rotation }
+    }
+}
+
+impl Property<f32> {
+    pub fn opacity(node: NodeId) -> Self {
+        Self { node, channel: Channel::Opacity, wrap: AnimValue::Opacity }
+    }
+
+    pub fn haptic_intensity(node: NodeId) -> Self {
+        Self { node, channel: Channel::HapticIntensity, wrap: AnimValue::HapticIntensity }
+    }
+}
+
+/// Starts a tween of `property`.
+pub fn tween<T: Animatable>(property: Property<T>) -> TweenBuilder<T> {
+    TweenBuilder { property, from: None }
+}
+
+/// A tween missing its target value.
+#[derive(Debug, Clone, Copy)]
+pub struct TweenBuilder<T> {
+    property: Property<T>,
+    from: Option<T>,
+}
+
+impl<T: Animatable> TweenBuilder<T> {
+    /// Starts from `value` rather than the property's current value.
+    pub fn from(mut self, value: T) -> Self {
+        self.from = Some(value);
+        self
+    }
+
+    pub fn to(self, value: T) -> Tween {
+        let wrap = self.property.wrap;
+        Tween {
+            node: self.property.node,
+            channel: self.property.channel,
+            from: self.from.map(wrap),
+            to: wrap(value),
+            duration: Tween::DEFAULT_DURATION,
+            delay: 0.0,
+            ease: Ease::default(),
+            elapsed: 0.0,
+        }
+    }
+}
+
+/// A node property moving to a value over time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tween {
+    node: NodeId,
+    channel: Channel,
+    /// Resolved on the first frame when not given.
+    from: Option<AnimValue>,
+    to: AnimValue,
+    duration: f32,
+    delay: f32,
+    ease: Ease,
+    /// Time since the tween was started, including the delay.
+    elapsed: f32,
+}
+
+impl Tween {
+    /// Length of a tween unless set, in seconds.
+    pub const DEFAULT_DURATION: f32 = 0.25;
+
+    /// Length in seconds.
+    pub fn duration(mut self, seconds: f32) -> Self {
+        self.duration = seconds.max(0.0);
+        self
+    }
+
+    /// Wait in seconds before the property starts moving.
+    pub fn delay(mut self, seconds: f32) -> Self {
+        self.delay = seconds.max(0.0);
+        self
+    }
+
+    pub fn ease(mut self, ease: Ease) -> Self {
+        self.ease = ease;
+        self
+    }
+
+    #[inline]
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    #[inline]
+    pub fn channel(&self) -> Channel {
+        self.channel
+    }
+
+    #[inline]
+    pub fn target(&self) -> AnimValue {
+        self.to
+    }
+
+    /// Linear progress, 0 until the delay passed and 1 once done.
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return if self.elapsed >= self.delay { 1.0 } else { 0.0 };
+        }
+        ((self.elapsed - self.delay) / self.duration).clamp(0.0, 1.0)
+    }
+
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.progress() >= 1.0
+    }
+
+    /// Moves on by `dt` seconds, taking `current` as the start if none was given.
+    pub fn advance(&mut self, dt: f32, current: AnimValue) {
+        self.from.get_or_insert(current);
+        self.elapsed += dt.max(0.0);
+    }
+
+    /// Value at the current progress; `None` before the start is known.
+    pub fn sample(&self) -> Option<AnimValue> {
+        Some(self.from?.interpolate(self.to, self.ease.apply(self.progress())))
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/tween_tests.rs"]
+mod tests;
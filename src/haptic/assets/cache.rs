@@ -0,0 +1,348 @@
+//! Memory-budgeted cache for derived spatial data.
+//!
+//! SDF grids, hulls, BVHs and decimated proxies are expensive to build but pure
+//! functions of their source data. The cache stores them under the source's
+//! content hash plus the build parameters, so identical meshes streamed in under
+//! different paths share one copy and a reloaded asset with unchanged bytes does
+//! not rebuild anything. When the memory budget is exceeded the least recently
+//! used entries are evicted, visual data before haptic data, and entries still
+//! referenced elsewhere (e.g. by the haptic loop) are never evicted.
+
+use crate::geometry::{CollisionProxy, ConvexHull, TriMesh, VoxelSdf};
+use crate::spatial::MeshBvh;
+use sha2::{Digest, Sha256};
+use std::any::{Any, TypeId};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+use std::hash::{Hash, Hasher};
+use std::mem::size_of;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+// ============================================================================
+// Content Hashes
+// ============================================================================
+
+/// SHA-256 of the source data a derived structure was built from.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ContentHash(pub [u8; 32]);
+
+impl ContentHash {
+    pub fn of_bytes(bytes: &[u8]) -> Self {
+        Self(Sha256::digest(bytes).into())
+    }
+
+    /// Hash of a mesh's positions and indices.
+    pub fn of_mesh(mesh: &TriMesh) -> Self {
+        let mut hasher = Sha256::new();
+        for p in &mesh.positions {
+            for c in [p.x, p.y, p.z] {
+                hasher.update(c.to_le_bytes());
+            }
+        }
+        hasher.update((mesh.indices.len() as u64).to_le_bytes());
+        for i in &mesh.indices {
+            hasher.update(i.to_le_bytes());
+        }
+        Self(hasher.finalize().into())
+    }
+}
+
+impl fmt::Display for ContentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{:02x}", b))
+    }
+}
+
+impl fmt::Debug for ContentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The first 8 bytes are plenty to tell entries apart in logs
+        let mut short = String::with_capacity(16);
+        for b in &self.0[..8] {
+            let _ = write!(short, "{:02x}", b);
+        }
+        write!(f, "ContentHash({})", short)
+    }
+}
+
+// ============================================================================
+// Memory Accounting
+// ============================================================================
+
+/// Approximate heap plus inline size of a cached value, in bytes.
+pub trait MemorySize {
+    fn memory_size(&self) -> usize;
+}
+
+impl MemorySize for Vec<u8> {
+    fn memory_size(&self) -> usize {
+        size_of::<Self>() + self.capacity()
+    }
+}
+
+impl MemorySize for String {
+    fn memory_size(&self) -> usize {
+        size_of::<Self>() + self.capacity()
+    }
+}
+
+impl MemorySize for TriMesh {
+    fn memory_size(&self) -> usize {
+        size_of::<Self>() + self.positions.capacity() * size_of::<crate::core::Vec3>() + self.indices.capacity() * 4
+    }
+}
+
+impl MemorySize for ConvexHull {
+    fn memory_size(&self) -> usize {
+        size_of::<Self>()
+            + std::mem::size_of_val(self.vertices())
+            + std::mem::size_of_val(self.faces())
+            + std::mem::size_of_val(self.planes())
+    }
+}
+
+impl MemorySize for VoxelSdf {
+    fn memory_size(&self) -> usize {
+        let [x, y, z] = self.dims();
+        size_of::<Self>() + x * y * z * size_of::<f32>()
+    }
+}
+
+impl MemorySize for MeshBvh {
+    fn memory_size(&self) -> usize {
+        size_of::<Self>() + self.memory_footprint()
+    }
+}
+
+impl MemorySize for CollisionProxy {
+    fn memory_size(&self) -> usize {
+        match self {
+            CollisionProxy::Hulls(hulls) => size_of::<Self>() + hulls.iter().map(|h| h.memory_size()).sum::<usize>(),
+            CollisionProxy::Sdf(sdf) => sdf.memory_size(),
+        }
+    }
+}
+
+// ============================================================================
+// Cache
+// ============================================================================
+
+/// Eviction class of an entry; `Visual` entries are evicted before `Haptic` ones
+/// because a haptic cache miss means rebuilding while the user is touching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum CachePriority {
+    Visual,
+    #[default]
+    Haptic,
+}
+
+/// Counters for tuning the budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub entries: usize,
+    pub bytes: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Key {
+    content: ContentHash,
+    ty: TypeId,
+    params: u64,
+}
+
+struct Entry {
+    value: Arc<dyn Any + Send + Sync>,
+    bytes: usize,
+    priority: CachePriority,
+    last_used: u64,
+}
+
+impl Entry {
+    /// True while someone other than the cache holds the value.
+    #[inline]
+    fn is_pinned(&self) -> bool {
+        Arc::strong_count(&self.value) > 1
+    }
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: HashMap<Key, Entry>,
+    tick: u64,
+    stats: CacheStats,
+}
+
+/// Thread-safe LRU cache of derived data, shared by asset workers and the UI thread.
+pub struct DerivedCache {
+    budget: usize,
+    inner: Mutex<Inner>,
+}
+
+impl DerivedCache {
+    /// Creates a cache that keeps at most `budget` bytes of unpinned data.
+    pub fn new(budget: usize) -> Self {
+        Self { budget, inner: Mutex::new(Inner::default()) }
+    }
+
+    #[inline]
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// The cache state; one caller panicking mid-update leaves it usable.
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn key<T: 'static>(content: ContentHash, params: &impl Hash) -> Key {
+        let mut hasher = DefaultHasher::new();
+        params.hash(&mut hasher);
+        Key { content, ty: TypeId::of::<T>(), params: hasher.finish() }
+    }
+
+    /// Cached value built from `content` with `params`, marking it recently used.
+    pub fn get<T: Send + Sync + 'static>(&self, content: ContentHash, params: &impl Hash) -> Option<Arc<T>> {
+        let key = Self::key::<T>(content, params);
+        let mut inner = self.lock();
+        inner.tick += 1;
+        let tick = inner.tick;
+        let found = inner.entries.get_mut(&key).map(|e| {
+            e.last_used = tick;
+            e.value.clone()
+        });
+        match found {
+            Some(value) => {
+                inner.stats.hits += 1;
+                value.downcast::<T>().ok()
+            }
+            None => {
+                inner.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Stores a value and evicts older entries if the budget is exceeded.
+    pub fn insert<T: MemorySize + Send + Sync + 'static>(
+        &self,
+        content: ContentHash,
+        params: &impl Hash,
+        value: T,
+        priority: CachePriority,
+    ) -> Arc<T> {
+        let key = Self::key::<T>(content, params);
+        let bytes = value.memory_size();
+        let value = Arc::new(value);
+        let mut inner = self.lock();
+        inner.tick += 1;
+        let entry = Entry { value: value.clone(), bytes, priority, last_used: inner.tick };
+        if let Some(old) = inner.entries.insert(key, entry) {
+            inner.stats.bytes -= old.bytes;
+        }
+        inner.stats.bytes += bytes;
+        inner.stats.entries = inner.entries.len();
+        self.evict(&mut inner, Some(key));
+        value
+    }
+
+    /// Returns the cached value or builds and stores it. `build` runs without the
+    /// lock held, so two threads missing at once may both build; the later insert wins.
+    pub fn get_or_insert_with<T: MemorySize + Send + Sync + 'static>(
+        &self,
+        content: ContentHash,
+        params: &impl Hash,
+        priority: CachePriority,
+        build: impl FnOnce() -> T,
+    ) -> Arc<T> {
+        if let Some(value) = self.get::<T>(content, params) {
+            return value;
+        }
+        self.insert(content, params, build(), priority)
+    }
+
+    /// Fallible form of [`get_or_insert_with`](Self::get_or_insert_with); errors are not cached.
+    pub fn get_or_try_insert_with<T: MemorySize + Send + Sync + 'static, E>(
+        &self,
+        content: ContentHash,
+        params: &impl Hash,
+        priority: CachePriority,
+        build: impl FnOnce() -> Result<T, E>,
+    ) -> Result<Arc<T>, E> {
+        if let Some(value) = self.get::<T>(content, params) {
+            return Ok(value);
+        }
+        Ok(self.insert(content, params, build()?, priority))
+    }
+
+    /// Drops every entry derived from `content`, e.g. after the source was deleted.
+    pub fn invalidate(&self, content: ContentHash) -> usize {
+        let mut inner = self.lock();
+        let before = inner.entries.len();
+        let mut freed = 0;
+        inner.entries.retain(|k, e| {
+            let keep = k.content != content;
+            if !keep {
+                freed += e.bytes;
+            }
+            keep
+        });
+        inner.stats.bytes -= freed;
+        inner.stats.entries = inner.entries.len();
+        before - inner.entries.len()
+    }
+
+    pub fn clear(&self) {
+        let mut inner = self.lock();
+        inner.entries.clear();
+        inner.stats.bytes = 0;
+        inner.stats.entries = 0;
+    }
+
+    /// Re-applies the budget, e.g. after handles to pinned entries were dropped.
+    pub fn trim(&self) {
+        let mut inner = self.lock();
+        self.evict(&mut inner, None);
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.lock().stats
+    }
+
+    /// Evicts unpinned entries, lowest priority and least recently used first,
+    /// until the budget holds. `keep` protects the entry just inserted.
+    fn evict(&self, inner: &mut Inner, keep: Option<Key>) {
+        if inner.stats.bytes <= self.budget {
+            return;
+        }
+        let mut candidates: Vec<(CachePriority, u64, Key)> = inner
+            .entries
+            .iter()
+            .filter(|(k, e)| Some(**k) != keep && !e.is_pinned())
+            .map(|(k, e)| (e.priority, e.last_used, *k))
+            .collect();
+        candidates.sort_unstable_by_key(|(priority, last_used, _)| (*priority, *last_used));
+        for (_, _, key) in candidates {
+            if inner.stats.bytes <= self.budget {
+                break;
+            }
+            if let Some(entry) = inner.entries.remove(&key) {
+                inner.stats.bytes -= entry.bytes;
+                inner.stats.evictions += 1;
+            }
+        }
+        inner.stats.entries = inner.entries.len();
+    }
+}
+
+impl fmt::Debug for DerivedCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DerivedCache").field("budget", &self.budget).field("stats", &self.stats()).finish()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/cache_tests.rs"]
+mod tests;
@@ -0,0 +1,207 @@
+//! Keyframe animation clips.
+//!
+//! An [`AnimationClip`] is a set of keyframe tracks aimed at nodes by name. It
+//! is authored once and instantiated onto any subtree with matching names,
+//! giving a [`Timeline`] to play. `ClipLoader` reads clips from a line-based
+//! text format:
+//!
+//! ```text
+//! clip wave
+//! track arm rotation cubic
+//! key 0.0 0 0 0 1
+//! key 0.5 0 0.3827 0 0.9239
+//! track . opacity step
+//! key 0 1
+//! ```
+//!
+//! - `track <target> <channel> [step|linear|cubic]` starts a track; the target
+//!   is a node name under the root, `.` the root itself.
+//! - `key <time> <value>` adds a key to it: one number for `opacity` and
+//!   `haptic_intensity`, three for `position` and `scale`, four (x y z w) for
+//!   `rotation`.
+
+use super::server::AssetLoader;
+use crate::anim::{Channel, ChannelTrack, Interpolation, Timeline};
+use crate::core::{Quat, Seconds, Vec3};
+use crate::scene::{NodeId, Scene};
+use std::fmt;
+use std::path::Path;
+
+/// Target name meaning the node a clip is instantiated on.
+pub const CLIP_ROOT: &str = ".";
+
+/// Instantiating a clip failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipError {
+    /// The root node is not in the scene.
+    MissingRoot,
+    /// No node under the root has a track's target name.
+    MissingTarget(String),
+}
+
+impl fmt::Display for ClipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClipError::MissingRoot => write!(f, "clip root is not in the scene"),
+            ClipError::MissingTarget(name) => write!(f, "no node named '{}' under the clip root", name),
+        }
+    }
+}
+
+impl std::error::Error for ClipError {}
+
+/// A track and the name of the node it drives.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipTrack {
+    pub target: String,
+    pub track: ChannelTrack,
+}
+
+/// Named keyframe tracks, not yet bound to scene nodes.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AnimationClip {
+    pub name: String,
+    pub tracks: Vec<ClipTrack>,
+}
+
+impl AnimationClip {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), tracks: Vec::new() }
+    }
+
+    pub fn with_track(mut self, target: impl Into<String>, track: ChannelTrack) -> Self {
+        self.tracks.push(ClipTrack { target: target.into(), track });
+        self
+    }
+
+    /// Time of the last key of any track.
+    pub fn duration(&self) -> Seconds {
+        Seconds(self.tracks.iter().map(|t| t.track.duration()).fold(0.0, f32::max))
+    }
+
+    /// Binds the tracks to `root` and the nodes named by their targets below
+    /// it, giving a paused timeline.
+    pub fn instantiate(&self, scene: &Scene, root: NodeId) -> Result<Timeline, ClipError> {
+        if !scene.contains(root) {
+            return Err(ClipError::MissingRoot);
+        }
+        let mut timeline = Timeline::new();
+        for clip_track in &self.tracks {
+            let node = find_below(scene, root, &clip_track.target)
+                .ok_or_else(|| ClipError::MissingTarget(clip_track.target.clone()))?;
+            timeline.add_track(node, clip_track.track.clone());
+        }
+        Ok(timeline)
+    }
+}
+
+/// First node named `name` in `root`'s subtree, breadth first.
+fn find_below(scene: &Scene, root: NodeId, name: &str) -> Option<NodeId> {
+    if name == CLIP_ROOT {
+        return Some(root);
+    }
+    let mut queue = std::collections::VecDeque::from([root]);
+    while let Some(id) = queue.pop_front() {
+        let node = scene.get(id)?;
+        if id != root && node.name == name {
+            return Some(id);
+        }
+        queue.extend(node.children().iter().copied());
+    }
+    None
+}
+
+/// Loads animation clips from their text format.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClipLoader;
+
+impl AssetLoader for ClipLoader {
+    type Asset = AnimationClip;
+
+    fn load(&self, bytes: &[u8], _path: &Path) -> Result<AnimationClip, String> {
+        let text = std::str::from_utf8(bytes).map_err(|e| e.to_string())?;
+        parse_clip(text)
+    }
+}
+
+/// Parses a clip in the text format described in the module docs. Blank
+/// lines and lines starting with `#` are skipped.
+pub fn parse_clip(text: &str) -> Result<AnimationClip, String> {
+    let mut clip = AnimationClip::default();
+    for (number, raw) in text.lines().enumerate() {
+        let line = number + 1;
+        let mut fields = raw.split_whitespace();
+        match fields.next() {
+            None => {}
+            Some(comment) if comment.starts_with('#') => {}
+            Some("clip") => clip.name = fields.collect::<Vec<_>>().join(" "),
+            Some("track") => {
+                let (Some(target), Some(channel)) = (fields.next(), fields.next()) else {
+                    return Err(format!("line {}: expected a target and a channel", line));
+                };
+                let channel =
+                    parse_channel(channel).ok_or_else(|| format!("line {}: unknown channel '{}'", line, channel))?;
+                let interpolation = match fields.next() {
+                    None | Some("linear") => Interpolation::Linear,
+                    Some("step") => Interpolation::Step,
+                    Some("cubic") => Interpolation::Cubic,
+                    Some(other) => return Err(format!("line {}: unknown interpolation '{}'", line, other)),
+                };
+                let track = ChannelTrack::new(channel, interpolation);
+                clip.tracks.push(ClipTrack { target: target.to_string(), track });
+            }
+            Some("key") => {
+                let track = clip.tracks.last_mut().ok_or_else(|| format!("line {}: key outside a track", line))?;
+                let numbers: Result<Vec<f32>, _> = fields.map(str::parse::<f32>).collect();
+                let numbers = numbers.map_err(|_| format!("line {}: invalid number", line))?;
+                insert_key(&mut track.track, &numbers).map_err(|e| format!("line {}: {}", line, e))?;
+            }
+            Some(other) => return Err(format!("line {}: unknown directive '{}'", line, other)),
+        }
+    }
+    Ok(clip)
+}
+
+fn parse_channel(name: &str) -> Option<Channel> {
+    match name {
+        "position" => Some(Channel::Position),
+        "rotation" => Some(Channel::Rotation),
+        "scale" => Some(Channel::Scale),
+        "opacity" => Some(Channel::Opacity),
+        "haptic_intensity" => Some(Channel::HapticIntensity),
+        _ => None,
+    }
+}
+
+/// Adds a key from its time followed by the value's components.
+fn insert_key(track: &mut ChannelTrack, numbers: &[f32]) -> Result<(), &'static str> {
+    let (&time, value) = numbers.split_first().ok_or("expected a time")?;
+    if !time.is_finite() {
+        return Err("key time must be finite");
+    }
+    if time < 0.0 {
+        return Err("key time must not be negative");
+    }
+    if !value.iter().all(|v| v.is_finite()) {
+        return Err("key values must be finite");
+    }
+    match (track, value) {
+        (ChannelTrack::Position(track) | ChannelTrack::Scale(track), &[x, y, z]) => {
+            track.insert(time, Vec3::new(x, y, z));
+        }
+        (ChannelTrack::Rotation(track), &[x, y, z, w]) => {
+            let rotation = Quat::new(x, y, z, w);
+            if rotation.length_squared() <= f32::EPSILON {
+                return Err("rotation must not be zero");
+            }
+            track.insert(time, rotation.normalize());
+        }
+        (ChannelTrack::Opacity(track) | ChannelTrack::HapticIntensity(track), &[v]) => track.insert(time, v),
+        _ => return Err("wrong number of values for the channel"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/clip_tests.rs"]
+mod tests;
@@ -0,0 +1,291 @@
+//! Haptic textures authored as grayscale images.
+//!
+//! Designers paint surface feel as a height map in any image tool and save it as
+//! PGM. [`HapticTexture::new`] turns the map into something the servo loop can
+//! sample cheaply: pixel noise is removed with a small binomial filter, a mip chain
+//! is built by 2x2 averaging, and the slope of every level is precomputed so that
+//! lateral texture forces need no finite differencing at runtime. Sampling picks
+//! the level whose texels are no smaller than the contact footprint, so a broad
+//! probe feels the coarse shape of a texture instead of aliasing on its detail.
+//!
+//! Textures tile: lookups wrap around the edges of the image.
+
+use super::server::AssetLoader;
+use crate::core::{Meters, Newtons, Vec2};
+use std::path::Path;
+
+// ============================================================================
+// Height Map
+// ============================================================================
+
+/// A grayscale image read as heights in 0..=1, row-major from the top-left.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeightMap {
+    width: usize,
+    height: usize,
+    heights: Vec<f32>,
+}
+
+impl HeightMap {
+    pub fn new(width: usize, height: usize, heights: Vec<f32>) -> Result<Self, String> {
+        if width == 0 || height == 0 {
+            return Err("image is empty".to_string());
+        }
+        if heights.len() != width * height {
+            return Err(format!("expected {} pixels for {}x{}, got {}", width * height, width, height, heights.len()));
+        }
+        Ok(Self { width, height, heights })
+    }
+
+    /// 8-bit luminance pixels; black is low, white is high.
+    pub fn from_luma(width: usize, height: usize, pixels: &[u8]) -> Result<Self, String> {
+        Self::new(width, height, pixels.iter().map(|&p| f32::from(p) / 255.0).collect())
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Height at pixel (`x`, `y`), wrapping around the edges.
+    #[inline]
+    pub fn get(&self, x: isize, y: isize) -> f32 {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        self.heights[y * self.width + x]
+    }
+
+    /// Separable 3x3 binomial blur, removing single-pixel noise that would buzz.
+    fn filtered(&self) -> Self {
+        let pass = |map: &Self, dx: isize, dy: isize| {
+            let heights = (0..map.height as isize)
+                .flat_map(|y| (0..map.width as isize).map(move |x| (x, y)))
+                .map(|(x, y)| (map.get(x - dx, y - dy) + 2.0 * map.get(x, y) + map.get(x + dx, y + dy)) * 0.25)
+                .collect();
+            Self { heights, ..*map }
+        };
+        pass(&pass(self, 1, 0), 0, 1)
+    }
+
+    /// Half the size (rounded up), each pixel the mean of a 2x2 block.
+    fn downsampled(&self) -> Self {
+        let (width, height) = (self.width.div_ceil(2), self.height.div_ceil(2));
+        let heights = (0..height as isize)
+            .flat_map(|y| (0..width as isize).map(move |x| (2 * x, 2 * y)))
+            .map(|(x, y)| (self.get(x, y) + self.get(x + 1, y) + self.get(x, y + 1) + self.get(x + 1, y + 1)) * 0.25)
+            .collect();
+        Self { width, height, heights }
+    }
+}
+
+/// Parses a binary (P5) or plain (P2) PGM image, 8 or 16 bits deep.
+pub fn parse_pgm(bytes: &[u8]) -> Result<HeightMap, String> {
+    let mut pos = 0;
+    let magic = next_token(bytes, &mut pos).ok_or("missing PGM header")?;
+    let binary = match magic {
+        "P5" => true,
+        "P2" => false,
+        _ => return Err(format!("not a PGM image (magic '{}')", magic)),
+    };
+    let mut header = [0usize; 3];
+    for (value, name) in header.iter_mut().zip(["width", "height", "maximum value"]) {
+        *value = next_token(bytes, &mut pos)
+            .and_then(|t| t.parse().ok())
+            .ok_or_else(|| format!("invalid {}", name))?;
+    }
+    let [width, height, max] = header;
+    if max == 0 || max > 65535 {
+        return Err(format!("invalid maximum value {}", max));
+    }
+    let count = width * height;
+    let scale = 1.0 / max as f32;
+    let heights: Vec<f32> = if binary {
+        // Exactly one whitespace byte separates the header from the raster
+        let raster = bytes.get(pos + 1..).unwrap_or_default();
+        let depth = if max > 255 { 2 } else { 1 };
+        if raster.len() < count * depth {
+            return Err(format!("raster truncated: expected {} bytes, got {}", count * depth, raster.len()));
+        }
+        raster[..count * depth]
+            .chunks_exact(depth)
+            .map(|c| c.iter().fold(0u32, |v, &b| v << 8 | u32::from(b)) as f32 * scale)
+            .collect()
+    } else {
+        (0..count)
+            .map(|i| {
+                next_token(bytes, &mut pos)
+                    .and_then(|t| t.parse::<u32>().ok())
+                    .map(|v| v as f32 * scale)
+                    .ok_or_else(|| format!("invalid pixel {}", i))
+            })
+            .collect::<Result<_, _>>()?
+    };
+    HeightMap::new(width, height, heights.into_iter().map(|h| h.min(1.0)).collect())
+}
+
+/// Next whitespace-separated header token, skipping `#` comments.
+fn next_token<'a>(bytes: &'a [u8], pos: &mut usize) -> Option<&'a str> {
+    loop {
+        while bytes.get(*pos).is_some_and(u8::is_ascii_whitespace) {
+            *pos += 1;
+        }
+        if bytes.get(*pos) != Some(&b'#') {
+            break;
+        }
+        while bytes.get(*pos).is_some_and(|&b| b != b'\n') {
+            *pos += 1;
+        }
+    }
+    let start = *pos;
+    while bytes.get(*pos).is_some_and(|b| !b.is_ascii_whitespace()) {
+        *pos += 1;
+    }
+    (*pos > start).then(|| std::str::from_utf8(&bytes[start..*pos]).ok()).flatten()
+}
+
+// ============================================================================
+// Haptic Texture
+// ============================================================================
+
+/// One level of a [`HapticTexture`] with its precomputed slopes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MipLevel {
+    map: HeightMap,
+    /// Size of one texel on the surface.
+    pub texel: Meters,
+    /// Height gradient per meter of surface, in map units.
+    gradients: Vec<Vec2>,
+}
+
+impl MipLevel {
+    fn new(map: HeightMap, texel: Meters) -> Self {
+        let scale = 0.5 / texel.value();
+        let gradients = (0..map.height as isize)
+            .flat_map(|y| (0..map.width as isize).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                Vec2::new(map.get(x + 1, y) - map.get(x - 1, y), map.get(x, y + 1) - map.get(x, y - 1)) * scale
+            })
+            .collect();
+        Self { map, texel, gradients }
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.map.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.map.height
+    }
+
+    /// Bilinear height (map units) and gradient at surface point `point`.
+    fn sample(&self, point: Vec2) -> (f32, Vec2) {
+        let u = point.x / self.texel.value() - 0.5;
+        let v = point.y / self.texel.value() - 0.5;
+        let (x, y) = (u.floor(), v.floor());
+        let (fx, fy) = (u - x, v - y);
+        let (x, y) = (x as isize, y as isize);
+        let w = self.map.width as isize;
+        let h = self.map.height as isize;
+        let gradient = |x: isize, y: isize| self.gradients[(y.rem_euclid(h) * w + x.rem_euclid(w)) as usize];
+        let corners = [(x, y, (1.0 - fx) * (1.0 - fy)), (x + 1, y, fx * (1.0 - fy)), (x, y + 1, (1.0 - fx) * fy)];
+        let corners = corners.into_iter().chain([(x + 1, y + 1, fx * fy)]);
+        corners.fold((0.0, Vec2::zero()), |(height, slope), (x, y, weight)| {
+            (height + self.map.get(x, y) * weight, slope + gradient(x, y) * weight)
+        })
+    }
+}
+
+/// A filtered, mip-mapped height texture ready for contact rendering.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HapticTexture {
+    /// Height of white above black.
+    pub depth: Meters,
+    levels: Vec<MipLevel>,
+}
+
+impl HapticTexture {
+    /// Texture with `map`'s pixels spaced `texel` apart on the surface and white
+    /// standing `depth` above black.
+    pub fn new(map: &HeightMap, texel: Meters, depth: Meters) -> Self {
+        let texel = Meters(texel.value().abs().max(f32::EPSILON));
+        let mut levels = vec![MipLevel::new(map.filtered(), texel)];
+        while let Some(last) = levels.last().filter(|l| l.width() > 1 || l.height() > 1) {
+            let next = MipLevel::new(last.map.downsampled(), Meters(last.texel.value() * 2.0));
+            levels.push(next);
+        }
+        Self { depth, levels }
+    }
+
+    /// Levels from finest to a single texel.
+    #[inline]
+    pub fn levels(&self) -> &[MipLevel] {
+        &self.levels
+    }
+
+    /// Finest level whose texels are at least `footprint` wide.
+    pub fn level_for(&self, footprint: Meters) -> usize {
+        let ratio = footprint.value() / self.levels[0].texel.value();
+        if ratio.is_nan() || ratio <= 1.0 {
+            return 0;
+        }
+        (ratio.log2().ceil() as usize).min(self.levels.len() - 1)
+    }
+
+    /// Surface height at `point`, seen by a contact `footprint` wide.
+    pub fn height(&self, point: Vec2, footprint: Meters) -> Meters {
+        self.sample(point, footprint).0
+    }
+
+    /// Height and slope (meters of height per meter of surface) at `point`.
+    pub fn sample(&self, point: Vec2, footprint: Meters) -> (Meters, Vec2) {
+        let (height, gradient) = self.levels[self.level_for(footprint)].sample(point);
+        (Meters(height * self.depth.value()), gradient * self.depth.value())
+    }
+
+    /// Tangential force of sliding over the texture while pressing with `normal`:
+    /// uphill resists, downhill assists.
+    pub fn lateral_force(&self, point: Vec2, footprint: Meters, normal: Newtons) -> Vec2 {
+        self.sample(point, footprint).1 * -normal.value()
+    }
+}
+
+/// Loads PGM height maps as haptic textures.
+#[derive(Debug, Clone, Copy)]
+pub struct TextureLoader {
+    /// Surface size of one pixel.
+    pub texel: Meters,
+    /// Height of white above black.
+    pub depth: Meters,
+}
+
+impl TextureLoader {
+    pub const fn new(texel: Meters, depth: Meters) -> Self {
+        Self { texel, depth }
+    }
+}
+
+impl Default for TextureLoader {
+    /// 0.1 mm pixels, 0.5 mm deep.
+    fn default() -> Self {
+        Self::new(Meters(0.0001), Meters(0.0005))
+    }
+}
+
+impl AssetLoader for TextureLoader {
+    type Asset = HapticTexture;
+
+    fn load(&self, bytes: &[u8], _path: &Path) -> Result<HapticTexture, String> {
+        Ok(HapticTexture::new(&parse_pgm(bytes)?, self.texel, self.depth))
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/heightmap_tests.rs"]
+mod tests;
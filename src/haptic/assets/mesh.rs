@@ -0,0 +1,122 @@
+//! Mesh import with automatic collision proxy generation.
+//!
+//! `MeshLoader` parses Wavefront OBJ files and, on the same worker thread,
+//! generates a [`CollisionProxy`] that is stored next to the visual mesh. Scenes
+//! render the mesh and hand the proxy to the haptic renderer, so touchable
+//! geometry never has to be authored by hand.
+
+use super::cache::{CachePriority, ContentHash, DerivedCache};
+use super::server::AssetLoader;
+use crate::core::Vec3;
+use crate::geometry::{CollisionProxy, ProxyMethod, TriMesh};
+use std::path::Path;
+use std::sync::Arc;
+
+/// A visual mesh and its generated haptic stand-in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshAsset {
+    pub mesh: TriMesh,
+    /// None if proxies are disabled or the mesh encloses no volume (e.g. a flat decal).
+    /// Shared with other assets of identical geometry when a cache is used.
+    pub proxy: Option<Arc<CollisionProxy>>,
+}
+
+/// Loads OBJ meshes and generates collision proxies for them.
+#[derive(Debug, Clone)]
+pub struct MeshLoader {
+    /// Proxy generation method; None imports the visual mesh only.
+    pub proxy: Option<ProxyMethod>,
+    cache: Option<Arc<DerivedCache>>,
+}
+
+impl MeshLoader {
+    pub fn new(proxy: Option<ProxyMethod>) -> Self {
+        Self { proxy, cache: None }
+    }
+
+    /// Reuses proxies from `cache` for meshes with identical geometry.
+    pub fn with_cache(mut self, cache: Arc<DerivedCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    fn generate(&self, mesh: &TriMesh, method: ProxyMethod) -> Option<Arc<CollisionProxy>> {
+        let build = || CollisionProxy::generate(mesh, method);
+        match &self.cache {
+            Some(cache) => {
+                // The Debug form captures every parameter, including the f32 ones
+                let params = format!("{:?}", method);
+                let proxy = cache.get_or_try_insert_with(ContentHash::of_mesh(mesh), &params, CachePriority::Haptic, build);
+                proxy.ok()
+            }
+            None => build().ok().map(Arc::new),
+        }
+    }
+}
+
+impl Default for MeshLoader {
+    fn default() -> Self {
+        Self::new(Some(ProxyMethod::default()))
+    }
+}
+
+impl AssetLoader for MeshLoader {
+    type Asset = MeshAsset;
+
+    fn load(&self, bytes: &[u8], _path: &Path) -> Result<MeshAsset, String> {
+        let text = std::str::from_utf8(bytes).map_err(|e| e.to_string())?;
+        let mesh = parse_obj(text)?;
+        let proxy = self.proxy.and_then(|method| self.generate(&mesh, method));
+        Ok(MeshAsset { mesh, proxy })
+    }
+}
+
+/// Parses the vertex positions and faces of an OBJ file. Polygons are
+/// triangulated as fans; texture coordinates, normals and groups are ignored.
+pub fn parse_obj(text: &str) -> Result<TriMesh, String> {
+    let mut positions = Vec::new();
+    let mut indices = Vec::new();
+    for (number, raw) in text.lines().enumerate() {
+        let line = number + 1;
+        let mut fields = raw.split_whitespace();
+        match fields.next() {
+            Some("v") => {
+                let coords: Result<Vec<f32>, _> = fields.take(3).map(str::parse::<f32>).collect();
+                match coords.as_deref() {
+                    Ok([x, y, z]) => positions.push(Vec3::new(*x, *y, *z)),
+                    _ => return Err(format!("line {}: expected three vertex coordinates", line)),
+                }
+            }
+            Some("f") => {
+                let corners = fields
+                    .map(|field| resolve_index(field, positions.len()))
+                    .collect::<Option<Vec<u32>>>()
+                    .ok_or_else(|| format!("line {}: invalid face index", line))?;
+                if corners.len() < 3 {
+                    return Err(format!("line {}: face needs at least three vertices", line));
+                }
+                for i in 1..corners.len() - 1 {
+                    indices.extend([corners[0], corners[i], corners[i + 1]]);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(TriMesh::new(positions, indices))
+}
+
+/// Resolves a face corner (`v`, `v/vt`, `v//vn`, `v/vt/vn`; 1-based or negative
+/// relative) to a zero-based position index.
+fn resolve_index(field: &str, count: usize) -> Option<u32> {
+    let index: i64 = field.split('/').next()?.parse().ok()?;
+    let resolved = match index {
+        i if i > 0 => i - 1,
+        i if i < 0 => count as i64 + i,
+        _ => return None,
+    };
+    (0..count as i64).contains(&resolved).then_some(resolved as u32)
+}
+
+#[cfg(test)]
+#[path = "tests/mesh_tests.rs"]
+mod tests;
@@ -0,0 +1,14 @@
+// src/haptic/assets/mod.rs
+pub mod cache;
+pub mod clip;
+pub mod heightmap;
+pub mod mesh;
+pub mod server;
+pub use cache::{CachePriority, CacheStats, ContentHash, DerivedCache, MemorySize};
+pub use clip::{parse_clip, AnimationClip, ClipError, ClipLoader, ClipTrack, CLIP_ROOT};
+pub use heightmap::{parse_pgm, HapticTexture, HeightMap, MipLevel, TextureLoader};
+pub use mesh::{parse_obj, MeshAsset, MeshLoader};
+pub use server::{
+    AssetError, AssetEvent, AssetLoader, AssetServer, AssetSource, BytesLoader, FileSource, Handle,
+    LoadState, MemorySource, ReloadHook, TextLoader,
+};
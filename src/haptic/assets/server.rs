@@ -0,0 +1,539 @@
+//! Asset server: handle-based, reference-counted assets loaded on background threads.
+//!
+//! Scenes hold cheap `Handle<T>` clones instead of owning mesh, texture, or effect
+//! buffers. Loading runs on a small worker pool; the UI thread drains `AssetEvent`s
+//! once per frame and never blocks. Reloading an asset swaps the data behind every
+//! existing handle, which is what the hot-reload hooks build on.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex, RwLock, Weak};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+
+// ============================================================================
+// Errors and Events
+// ============================================================================
+
+/// Reasons an asset failed to load.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetError {
+    /// The source could not be read.
+    Io { path: PathBuf, message: String },
+    /// No loader is registered for the requested asset type.
+    NoLoader(&'static str),
+    /// The loader rejected the data.
+    Parse { path: PathBuf, message: String },
+    /// `Handle::wait` gave up while the load was still running.
+    Timeout { path: PathBuf },
+}
+
+impl fmt::Display for AssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetError::Io { path, message } => write!(f, "cannot read {}: {}", path.display(), message),
+            AssetError::NoLoader(ty) => write!(f, "no loader registered for {}", ty),
+            AssetError::Parse { path, message } => write!(f, "cannot parse {}: {}", path.display(), message),
+            AssetError::Timeout { path } => write!(f, "timed out waiting for {}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for AssetError {}
+
+/// Notification delivered to the UI thread by `AssetServer::poll_events`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetEvent {
+    Loaded { path: PathBuf },
+    Reloaded { path: PathBuf },
+    Failed { path: PathBuf, error: AssetError },
+}
+
+// ============================================================================
+// Sources
+// ============================================================================
+
+/// Where asset bytes come from.
+pub trait AssetSource: Send + Sync + 'static {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+
+    /// Last modification time, used for hot-reload polling. None disables polling.
+    fn modified(&self, _path: &Path) -> Option<SystemTime> {
+        None
+    }
+}
+
+/// Reads assets from a directory on disk.
+#[derive(Debug, Clone)]
+pub struct FileSource {
+    root: PathBuf,
+}
+
+impl FileSource {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl AssetSource for FileSource {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        std::fs::read(self.root.join(path))
+    }
+
+    fn modified(&self, path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(self.root.join(path)).and_then(|m| m.modified()).ok()
+    }
+}
+
+/// In-memory asset source for embedded assets and tests.
+#[derive(Debug, Default)]
+pub struct MemorySource {
+    files: RwLock<HashMap<PathBuf, (Vec<u8>, SystemTime)>>,
+}
+
+impl MemorySource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts or replaces a file, updating its modification time.
+    pub fn insert(&self, path: impl Into<PathBuf>, bytes: impl Into<Vec<u8>>) {
+        self.files.write().unwrap_or_else(|e| e.into_inner()).insert(path.into(), (bytes.into(), SystemTime::now()));
+    }
+}
+
+impl AssetSource for MemorySource {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.files
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(path)
+            .map(|(bytes, _)| bytes.clone())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "not in memory source"))
+    }
+
+    fn modified(&self, path: &Path) -> Option<SystemTime> {
+        self.files.read().unwrap_or_else(|e| e.into_inner()).get(path).map(|(_, t)| *t)
+    }
+}
+
+impl<S: AssetSource> AssetSource for Arc<S> {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        (**self).read(path)
+    }
+
+    fn modified(&self, path: &Path) -> Option<SystemTime> {
+        (**self).modified(path)
+    }
+}
+
+// ============================================================================
+// Loaders
+// ============================================================================
+
+/// Converts raw bytes into an asset. Runs on worker threads.
+pub trait AssetLoader: Send + Sync + 'static {
+    type Asset: Send + Sync + 'static;
+
+    fn load(&self, bytes: &[u8], path: &Path) -> Result<Self::Asset, String>;
+}
+
+/// Loads files as raw byte buffers.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BytesLoader;
+
+impl AssetLoader for BytesLoader {
+    type Asset = Vec<u8>;
+
+    fn load(&self, bytes: &[u8], _path: &Path) -> Result<Vec<u8>, String> {
+        Ok(bytes.to_vec())
+    }
+}
+
+/// Loads UTF-8 text files.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextLoader;
+
+impl AssetLoader for TextLoader {
+    type Asset = String;
+
+    fn load(&self, bytes: &[u8], _path: &Path) -> Result<String, String> {
+        String::from_utf8(bytes.to_vec()).map_err(|e| e.to_string())
+    }
+}
+
+// ============================================================================
+// Handles
+// ============================================================================
+
+/// Load state of an asset.
+#[derive(Debug)]
+pub enum LoadState<T> {
+    Loading,
+    Loaded(Arc<T>),
+    Failed(AssetError),
+}
+
+struct Slot<T> {
+    path: PathBuf,
+    state: Mutex<LoadState<T>>,
+    ready: Condvar,
+    version: AtomicU32,
+}
+
+/// Reference-counted handle to an asset. Cloning is cheap; the asset is freed when the
+/// last handle is dropped.
+pub struct Handle<T> {
+    slot: Arc<Slot<T>>,
+}
+
+impl<T> Handle<T> {
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.slot.path
+    }
+
+    /// The asset data, or None while loading or after a failure.
+    pub fn get(&self) -> Option<Arc<T>> {
+        match &*self.slot.state.lock().unwrap_or_else(|e| e.into_inner()) {
+            LoadState::Loaded(asset) => Some(asset.clone()),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn is_loaded(&self) -> bool {
+        matches!(*self.slot.state.lock().unwrap_or_else(|e| e.into_inner()), LoadState::Loaded(_))
+    }
+
+    /// The load error, if loading failed.
+    pub fn error(&self) -> Option<AssetError> {
+        match &*self.slot.state.lock().unwrap_or_else(|e| e.into_inner()) {
+            LoadState::Failed(e) => Some(e.clone()),
+            _ => None,
+        }
+    }
+
+    /// Incremented every time the asset is (re)loaded; lets consumers detect hot reloads.
+    #[inline]
+    pub fn version(&self) -> u32 {
+        self.slot.version.load(Ordering::Acquire)
+    }
+
+    /// Number of live handles to this asset.
+    #[inline]
+    pub fn handle_count(&self) -> usize {
+        Arc::strong_count(&self.slot)
+    }
+
+    /// Blocks until the asset has finished loading or `timeout` expires.
+    /// Intended for tools and tests; the UI thread should poll instead.
+    pub fn wait(&self, timeout: Duration) -> Result<Arc<T>, AssetError> {
+        let guard = self.slot.state.lock().unwrap_or_else(|e| e.into_inner());
+        let (guard, _) = self
+            .slot
+            .ready
+            .wait_timeout_while(guard, timeout, |s| matches!(s, LoadState::Loading))
+            .unwrap_or_else(|e| e.into_inner());
+        match &*guard {
+            LoadState::Loaded(asset) => Ok(asset.clone()),
+            LoadState::Failed(e) => Err(e.clone()),
+            LoadState::Loading => Err(AssetError::Timeout { path: self.slot.path.clone() }),
+        }
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self { slot: self.slot.clone() }
+    }
+}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.slot, &other.slot)
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Handle").field("path", &self.slot.path).field("version", &self.version()).finish()
+    }
+}
+
+// ============================================================================
+// Asset Server
+// ============================================================================
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Type-erased entry of the asset registry.
+trait ErasedEntry: Send + Sync {
+    fn is_alive(&self) -> bool;
+    fn reload(&self, server: &AssetServer, loader: &Arc<dyn Any + Send + Sync>);
+    fn path(&self) -> &Path;
+    fn as_any(&self) -> &dyn Any;
+}
+
+struct Entry<T> {
+    path: PathBuf,
+    slot: Weak<Slot<T>>,
+}
+
+impl<T: Send + Sync + 'static> ErasedEntry for Entry<T> {
+    fn is_alive(&self) -> bool {
+        self.slot.strong_count() > 0
+    }
+
+    fn reload(&self, server: &AssetServer, loader: &Arc<dyn Any + Send + Sync>) {
+        if let (Some(slot), Some(loader)) = (self.slot.upgrade(), loader.downcast_ref::<LoaderFn<T>>()) {
+            server.spawn_load(slot, loader.clone(), true);
+        }
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+type LoaderFn<T> = Arc<dyn Fn(&[u8], &Path) -> Result<T, String> + Send + Sync>;
+
+/// Hook invoked on the UI thread for each reloaded path.
+pub type ReloadHook = Box<dyn FnMut(&Path) + Send>;
+
+/// Central asset registry with a background loading pool.
+pub struct AssetServer {
+    source: Arc<dyn AssetSource>,
+    loaders: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+    entries: HashMap<(TypeId, PathBuf), Box<dyn ErasedEntry>>,
+    modified: HashMap<PathBuf, SystemTime>,
+    reload_hooks: Vec<ReloadHook>,
+    jobs: Option<Sender<Job>>,
+    events_tx: Sender<AssetEvent>,
+    events_rx: Receiver<AssetEvent>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl AssetServer {
+    /// Default number of loader threads.
+    pub const DEFAULT_WORKERS: usize = 2;
+
+    /// Creates a server reading from `source` with `workers` loader threads.
+    ///
+    /// Fails if a loader thread cannot be spawned; the ones already started exit.
+    pub fn new(source: impl AssetSource, workers: usize) -> io::Result<Self> {
+        let (jobs_tx, jobs_rx) = mpsc::channel::<Job>();
+        let jobs_rx = Arc::new(Mutex::new(jobs_rx));
+        let workers = (0..workers.max(1))
+            .map(|i| {
+                let rx = jobs_rx.clone();
+                thread::Builder::new()
+                    .name(format!("asset-loader-{}", i))
+                    .spawn(move || loop {
+                        let job = rx.lock().unwrap_or_else(|e| e.into_inner()).recv();
+                        match job {
+                            Ok(job) => job(),
+                            Err(_) => break,
+                        }
+                    })
+            })
+            .collect::<io::Result<_>>()?;
+
+        let (events_tx, events_rx) = mpsc::channel();
+        Ok(Self {
+            source: Arc::new(source),
+            loaders: HashMap::new(),
+            entries: HashMap::new(),
+            modified: HashMap::new(),
+            reload_hooks: Vec::new(),
+            jobs: Some(jobs_tx),
+            events_tx,
+            events_rx,
+            workers,
+        })
+    }
+
+    /// Registers the loader for `L::Asset`, replacing any previous one.
+    pub fn register_loader<L: AssetLoader>(&mut self, loader: L) {
+        let loader = Arc::new(loader);
+        let f: LoaderFn<L::Asset> = Arc::new(move |bytes, path| loader.load(bytes, path));
+        self.loaders.insert(TypeId::of::<L::Asset>(), Arc::new(f));
+    }
+
+    /// Returns a handle to the asset at `path`, starting a background load if it is not
+    /// already loaded or loading. Repeated calls share the same handle.
+    pub fn load<T: Send + Sync + 'static>(&mut self, path: impl AsRef<Path>) -> Handle<T> {
+        let path = path.as_ref().to_path_buf();
+        let key = (TypeId::of::<T>(), path.clone());
+
+        if let Some(slot) = self.existing_slot::<T>(&key) {
+            return Handle { slot };
+        }
+
+        let slot = Arc::new(Slot {
+            path: path.clone(),
+            state: Mutex::new(LoadState::Loading),
+            ready: Condvar::new(),
+            version: AtomicU32::new(0),
+        });
+        self.entries.insert(key, Box::new(Entry { path: path.clone(), slot: Arc::downgrade(&slot) }));
+        if let Some(t) = self.source.modified(&path) {
+            self.modified.insert(path.clone(), t);
+        }
+
+        match self.loaders.get(&TypeId::of::<T>()).and_then(|l| l.downcast_ref::<LoaderFn<T>>()) {
+            Some(loader) => self.spawn_load(slot.clone(), loader.clone(), false),
+            None => {
+                let error = AssetError::NoLoader(std::any::type_name::<T>());
+                *slot.state.lock().unwrap_or_else(|e| e.into_inner()) = LoadState::Failed(error.clone());
+                let _ = self.events_tx.send(AssetEvent::Failed { path, error });
+            }
+        }
+        Handle { slot }
+    }
+
+    fn existing_slot<T: Send + Sync + 'static>(&self, key: &(TypeId, PathBuf)) -> Option<Arc<Slot<T>>> {
+        let entry = self.entries.get(key)?;
+        entry.as_any().downcast_ref::<Entry<T>>()?.slot.upgrade()
+    }
+
+    fn spawn_load<T: Send + Sync + 'static>(&self, slot: Arc<Slot<T>>, loader: LoaderFn<T>, reload: bool) {
+        let source = self.source.clone();
+        let events = self.events_tx.clone();
+        let job: Job = Box::new(move || {
+            let path = slot.path.clone();
+            let result = source
+                .read(&path)
+                .map_err(|e| AssetError::Io { path: path.clone(), message: e.to_string() })
+                .and_then(|bytes| {
+                    loader(&bytes, &path).map_err(|message| AssetError::Parse { path: path.clone(), message })
+                });
+
+            let event = {
+                let mut state = slot.state.lock().unwrap_or_else(|e| e.into_inner());
+                match result {
+                    Ok(asset) => {
+                        *state = LoadState::Loaded(Arc::new(asset));
+                        slot.version.fetch_add(1, Ordering::AcqRel);
+                        if reload { AssetEvent::Reloaded { path } } else { AssetEvent::Loaded { path } }
+                    }
+                    // A failed reload keeps serving the previous data
+                    Err(error) if reload && matches!(*state, LoadState::Loaded(_)) => AssetEvent::Failed { path, error },
+                    Err(error) => {
+                        *state = LoadState::Failed(error.clone());
+                        AssetEvent::Failed { path, error }
+                    }
+                }
+            };
+            slot.ready.notify_all();
+            let _ = events.send(event);
+        });
+        if let Some(jobs) = &self.jobs {
+            let _ = jobs.send(job);
+        }
+    }
+
+    /// Re-loads every live asset stored at `path`, whatever its type.
+    pub fn reload(&self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        for ((type_id, p), entry) in &self.entries {
+            if p == path && entry.is_alive() {
+                if let Some(loader) = self.loaders.get(type_id) {
+                    entry.reload(self, loader);
+                }
+            }
+        }
+    }
+
+    /// Polls the source for modified files and reloads them. Returns the number of
+    /// paths that changed.
+    pub fn check_for_changes(&mut self) -> usize {
+        let mut changed = Vec::new();
+        for entry in self.entries.values().filter(|e| e.is_alive()) {
+            let path = entry.path();
+            if let Some(t) = self.source.modified(path) {
+                if self.modified.get(path).is_some_and(|&prev| t > prev) && !changed.iter().any(|p: &PathBuf| p == path) {
+                    changed.push(path.to_path_buf());
+                }
+            }
+        }
+        for path in &changed {
+            if let Some(t) = self.source.modified(path) {
+                self.modified.insert(path.clone(), t);
+            }
+            self.reload(path);
+        }
+        changed.len()
+    }
+
+    /// Registers a hook called from `poll_events` for every reloaded asset.
+    pub fn on_reload(&mut self, hook: impl FnMut(&Path) + Send + 'static) {
+        self.reload_hooks.push(Box::new(hook));
+    }
+
+    /// Drains completed load notifications and runs reload hooks. Call once per frame.
+    pub fn poll_events(&mut self) -> Vec<AssetEvent> {
+        let events: Vec<AssetEvent> = self.events_rx.try_iter().collect();
+        for event in &events {
+            if let AssetEvent::Reloaded { path } = event {
+                for hook in &mut self.reload_hooks {
+                    hook(path);
+                }
+            }
+        }
+        events
+    }
+
+    /// Drops registry entries whose handles have all been released.
+    pub fn collect_garbage(&mut self) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|_, e| e.is_alive());
+        let live: Vec<PathBuf> = self.entries.values().map(|e| e.path().to_path_buf()).collect();
+        self.modified.retain(|p, _| live.contains(p));
+        before - self.entries.len()
+    }
+
+    /// Number of registered assets (including ones whose handles were dropped but not yet collected).
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Drop for AssetServer {
+    fn drop(&mut self) {
+        // Closing the job channel lets workers exit after finishing queued loads
+        self.jobs.take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl fmt::Debug for AssetServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AssetServer")
+            .field("assets", &self.entries.len())
+            .field("loaders", &self.loaders.len())
+            .field("workers", &self.workers.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/server_tests.rs"]
+mod tests;
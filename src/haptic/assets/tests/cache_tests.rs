@@ -0,0 +1,76 @@
+use super::*;
+use crate::assets::{AssetLoader, MeshLoader};
+use crate::core::Vec3;
+use crate::geometry::ProxyMethod;
+
+fn blob(len: usize) -> Vec<u8> {
+    vec![0; len]
+}
+
+#[test]
+fn test_content_hash() {
+    let a = ContentHash::of_bytes(b"abc");
+    assert_eq!(a.to_string(), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    assert_eq!(format!("{:?}", a), "ContentHash(ba7816bf8f01cfea)");
+
+    let cube = TriMesh::cuboid(Vec3::one());
+    assert_eq!(ContentHash::of_mesh(&cube), ContentHash::of_mesh(&cube.clone()));
+    assert_ne!(ContentHash::of_mesh(&cube), ContentHash::of_mesh(&TriMesh::cuboid(Vec3::splat(2.0))));
+}
+
+#[test]
+fn test_hit_miss_and_params() {
+    let cache = DerivedCache::new(1 << 20);
+    let key = ContentHash::of_bytes(b"mesh");
+    let mut builds = 0;
+    for _ in 0..3 {
+        cache.get_or_insert_with(key, &16, CachePriority::Haptic, || {
+            builds += 1;
+            blob(100)
+        });
+    }
+    assert_eq!(builds, 1);
+    // Different parameters and different types are separate entries
+    assert!(cache.get::<Vec<u8>>(key, &32).is_none());
+    assert!(cache.get::<String>(key, &16).is_none());
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (2, 3, 1));
+    assert_eq!(stats.bytes, blob(100).memory_size());
+    assert_eq!(cache.invalidate(key), 1);
+    assert_eq!(cache.stats().bytes, 0);
+}
+
+#[test]
+fn test_lru_eviction_prefers_visual_and_skips_pinned() {
+    let entry = blob(1000).memory_size();
+    let cache = DerivedCache::new(entry * 3);
+    let hash = |n: u8| ContentHash::of_bytes(&[n]);
+
+    let pinned = cache.insert(hash(0), &(), blob(1000), CachePriority::Haptic);
+    cache.insert(hash(1), &(), blob(1000), CachePriority::Haptic);
+    cache.insert(hash(2), &(), blob(1000), CachePriority::Visual);
+    cache.get::<Vec<u8>>(hash(1), &());
+    cache.insert(hash(3), &(), blob(1000), CachePriority::Haptic);
+    // Over budget: the visual entry goes first even though it is newer
+    assert!(cache.get::<Vec<u8>>(hash(2), &()).is_none());
+
+    cache.insert(hash(4), &(), blob(1000), CachePriority::Haptic);
+    // Entry 0 is oldest but pinned, so the least recently used haptic entry (1) goes
+    assert!(cache.get::<Vec<u8>>(hash(0), &()).is_some());
+    assert!(cache.get::<Vec<u8>>(hash(1), &()).is_none());
+    assert_eq!(cache.stats().evictions, 2);
+    assert!(cache.stats().bytes <= cache.budget());
+    drop(pinned);
+}
+
+#[test]
+fn test_proxy_shared_between_identical_meshes() {
+    let cache = Arc::new(DerivedCache::new(1 << 24));
+    let loader = MeshLoader::new(Some(ProxyMethod::ConvexHull)).with_cache(cache.clone());
+    let obj = "v 0 0 0\nv 1 0 0\nv 0 1 0\nv 0 0 1\nf 1 3 2\nf 1 2 4\nf 1 4 3\nf 2 3 4\n";
+    let a = loader.load(obj.as_bytes(), "a.obj".as_ref()).unwrap();
+    let b = loader.load(obj.as_bytes(), "b.obj".as_ref()).unwrap();
+    assert!(Arc::ptr_eq(a.proxy.as_ref().unwrap(), b.proxy.as_ref().unwrap()));
+    assert_eq!(cache.stats().hits, 1);
+}
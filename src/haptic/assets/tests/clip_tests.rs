@@ -0,0 +1,103 @@
+use super::*;
+use crate::anim::{AnimatedProps, Key, Track};
+use crate::assets::{AssetServer, MemorySource};
+use crate::scene::NodeBuilder;
+use std::sync::Arc;
+use std::time::Duration;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+const WAVE_CLIP: &str = "\
+# arm wave
+clip wave hello
+track arm rotation cubic
+key 0.0 0 0 0 1
+key 0.5 0 0 0.7071 0.7071
+key 1.0 0 0 0 2
+track . opacity step
+key 0 0
+key 0.25 1
+track arm position
+key 0 0 0 0
+key 1 0 0.1 0
+";
+
+fn rig() -> (Scene, NodeId, NodeId) {
+    let mut scene = Scene::new();
+    let root =
+        NodeBuilder::group().name("figure").child(NodeBuilder::panel().name("arm")).build(&mut scene, None).unwrap();
+    let arm = scene.find("arm").unwrap();
+    (scene, root, arm)
+}
+
+#[test]
+fn test_parse_clip() {
+    let clip = parse_clip(WAVE_CLIP).unwrap();
+    assert_eq!(clip.name, "wave hello");
+    assert_eq!(clip.tracks.len(), 3);
+    assert_eq!(clip.tracks[0].target, "arm");
+    assert_eq!(clip.duration(), Seconds(1.0));
+    match &clip.tracks[0].track {
+        ChannelTrack::Rotation(track) => {
+            assert_eq!(track.interpolation(), Interpolation::Cubic);
+            // Keys are normalized on load
+            assert_eq!(track.keys()[2], Key { time: 1.0, value: Quat::identity() });
+        }
+        other => panic!("expected a rotation track, got {:?}", other),
+    }
+    let expected = Track::new().with_interpolation(Interpolation::Step).with_key(0.0, 0.0).with_key(0.25, 1.0);
+    assert_eq!(clip.tracks[1].track, ChannelTrack::Opacity(expected));
+}
+
+#[test]
+fn test_parse_clip_errors() {
+    assert!(parse_clip("key 0 1\n").unwrap_err().contains("outside a track"));
+    assert!(parse_clip("track a colour\n").unwrap_err().contains("unknown channel"));
+    assert!(parse_clip("track a opacity smooth\n").unwrap_err().contains("unknown interpolation"));
+    assert!(parse_clip("track a scale\nkey 0 1 1\n").unwrap_err().starts_with("line 2"));
+    assert!(parse_clip("track a rotation\nkey 0 0 0 0 0\n").unwrap_err().contains("zero"));
+    assert!(parse_clip("track a opacity\nkey -1 0\n").unwrap_err().contains("negative"));
+    for key in ["key inf 0", "key NaN 0", "key 1 inf", "key 1 NaN"] {
+        let error = parse_clip(&format!("track a opacity\n{}\n", key)).unwrap_err();
+        assert!(error.starts_with("line 2") && error.contains("finite"), "{}", error);
+    }
+    assert!(parse_clip("track a position\nkey 0 1 -inf 1\n").unwrap_err().contains("finite"));
+    assert!(parse_clip("loop forever\n").unwrap_err().contains("unknown directive"));
+}
+
+#[test]
+fn test_instantiate_binds_targets_under_root() {
+    let (mut scene, root, arm) = rig();
+    let clip = parse_clip(WAVE_CLIP).unwrap();
+    let mut timeline = clip.instantiate(&scene, root).unwrap();
+    assert_eq!(timeline.tracks()[0].0, arm);
+    assert_eq!(timeline.tracks()[1].0, root);
+
+    let mut props = AnimatedProps::new();
+    timeline.play();
+    timeline.advance(Seconds(0.5));
+    timeline.apply(&mut scene, &mut props);
+    assert_eq!(props.get(root).opacity, 1.0);
+    assert!((scene.get(arm).unwrap().position.y - 0.05).abs() < TEST_EPSILON);
+    let rotated = props.get(arm).rotation.rotate(Vec3::unit_x());
+    assert!((rotated - Vec3::unit_y()).length() < 1e-3);
+
+    // Names are looked up below the root only
+    let other = scene.insert(crate::scene::Node::new(crate::scene::NodeKind::Group), None).unwrap();
+    assert_eq!(clip.instantiate(&scene, other).unwrap_err(), ClipError::MissingTarget("arm".into()));
+    scene.remove(other).unwrap();
+    assert_eq!(clip.instantiate(&scene, other).unwrap_err(), ClipError::MissingRoot);
+}
+
+#[test]
+fn test_loader_reads_clip_files() {
+    let source = Arc::new(MemorySource::new());
+    source.insert("wave.clip", WAVE_CLIP);
+    source.insert("broken.clip", "track arm\n");
+    let mut server = AssetServer::new(source, 1).unwrap();
+    server.register_loader(ClipLoader);
+
+    let clip = server.load::<AnimationClip>("wave.clip").wait(Duration::from_secs(5)).unwrap();
+    assert_eq!(clip.tracks.len(), 3);
+    assert!(server.load::<AnimationClip>("broken.clip").wait(Duration::from_secs(5)).is_err());
+}
@@ -0,0 +1,80 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+/// Stripes across X: `width` columns alternating between black and white pairs.
+fn stripes(width: usize, height: usize) -> HeightMap {
+    let pixels: Vec<u8> =
+        (0..width * height).map(|i| if ((i % width) / 2).is_multiple_of(2) { 0 } else { 255 }).collect();
+    HeightMap::from_luma(width, height, &pixels).unwrap()
+}
+
+#[test]
+fn test_parse_pgm() {
+    let plain = parse_pgm(b"P2\n# a comment\n3 2\n4\n0 1 2\n3 4 4\n").unwrap();
+    assert_eq!((plain.width(), plain.height()), (3, 2));
+    assert!((plain.get(1, 0) - 0.25).abs() < TEST_EPSILON);
+    assert!((plain.get(0, 1) - 0.75).abs() < TEST_EPSILON);
+
+    let mut binary = b"P5 2 1 255\n".to_vec();
+    binary.extend([0, 255]);
+    let map = parse_pgm(&binary).unwrap();
+    assert_eq!(map.get(1, 0), 1.0);
+    // Pixels wrap around the edges
+    assert_eq!(map.get(-1, 0), 1.0);
+
+    let mut deep = b"P5 1 1 65535\n".to_vec();
+    deep.extend([0x80, 0x00]);
+    assert!((parse_pgm(&deep).unwrap().get(0, 0) - 0.5).abs() < 1e-3);
+
+    assert!(parse_pgm(b"P6 1 1 255\n\0\0\0").unwrap_err().contains("not a PGM"));
+    assert!(parse_pgm(b"P5 2 2 255\n\0").unwrap_err().contains("truncated"));
+    assert!(parse_pgm(b"P2 2 1 255\n0 x").unwrap_err().contains("pixel 1"));
+}
+
+#[test]
+fn test_mip_chain() {
+    let texture = HapticTexture::new(&stripes(8, 4), Meters(0.001), Meters(0.0005));
+    let sizes: Vec<(usize, usize)> = texture.levels().iter().map(|l| (l.width(), l.height())).collect();
+    assert_eq!(sizes, vec![(8, 4), (4, 2), (2, 1), (1, 1)]);
+    assert!((texture.levels()[3].texel.value() - 0.008).abs() < TEST_EPSILON);
+
+    assert_eq!(texture.level_for(Meters(0.0005)), 0);
+    assert_eq!(texture.level_for(Meters(0.0015)), 1);
+    assert_eq!(texture.level_for(Meters(1.0)), 3);
+    // The coarsest level is the mean height
+    assert!((texture.height(Vec2::new(0.003, 0.001), Meters(1.0)).value() - 0.00025).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_filter_removes_single_pixel_spikes() {
+    let mut pixels = vec![0; 25];
+    pixels[12] = 255;
+    let texture = HapticTexture::new(&HeightMap::from_luma(5, 5, &pixels).unwrap(), Meters(1.0), Meters(1.0));
+    let peak = texture.height(Vec2::new(2.5, 2.5), Meters::ZERO).value();
+    assert!((peak - 0.25).abs() < TEST_EPSILON);
+    assert!((texture.height(Vec2::new(1.5, 2.5), Meters::ZERO).value() - 0.125).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_gradient_and_lateral_force() {
+    // A ramp rising along X; the wrap-around edge is far from the probe
+    let pixels: Vec<u8> = (0..16 * 4).map(|i| (i % 16) as u8 * 10).collect();
+    let texture = HapticTexture::new(&HeightMap::from_luma(16, 4, &pixels).unwrap(), Meters(0.001), Meters(0.002));
+    let (_, slope) = texture.sample(Vec2::new(0.008, 0.002), Meters::ZERO);
+    let expected = 10.0 / 255.0 * 0.002 / 0.001;
+    assert!((slope.x - expected).abs() < TEST_EPSILON);
+    assert!(slope.y.abs() < TEST_EPSILON);
+
+    let force = texture.lateral_force(Vec2::new(0.008, 0.002), Meters::ZERO, Newtons(2.0));
+    assert!((force.x + 2.0 * expected).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_loader() {
+    let loader = TextureLoader::default();
+    let texture = loader.load(b"P2 2 2 1\n0 1 1 0\n", Path::new("grain.pgm")).unwrap();
+    assert_eq!(texture.levels().len(), 2);
+    assert_eq!(texture.depth, loader.depth);
+    assert!(loader.load(b"nonsense", Path::new("bad.pgm")).is_err());
+}
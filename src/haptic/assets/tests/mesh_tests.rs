@@ -0,0 +1,55 @@
+use super::*;
+use crate::assets::{AssetServer, MemorySource};
+use std::sync::Arc;
+use std::time::Duration;
+
+const CUBE_OBJ: &str = "\
+# unit cube
+v -0.5 -0.5 -0.5
+v 0.5 -0.5 -0.5
+v 0.5 0.5 -0.5
+v -0.5 0.5 -0.5
+v -0.5 -0.5 0.5
+v 0.5 -0.5 0.5
+v 0.5 0.5 0.5
+v -0.5 0.5 0.5
+f 1 4 3 2
+f 5 6 7 8
+f 1 2 6 5
+f 3 4 8 7
+f 2 3 7 6
+f 1/1 5/2 8/3 4/4
+";
+
+#[test]
+fn test_parse_obj() {
+    let mesh = parse_obj(CUBE_OBJ).unwrap();
+    assert_eq!(mesh.positions.len(), 8);
+    assert_eq!(mesh.triangle_count(), 12);
+    assert!((mesh.volume() - 1.0).abs() < 1e-5);
+
+    let relative = parse_obj("v 0 0 0\nv 1 0 0\nv 0 1 0\nf -3//1 -2//1 -1//1\n").unwrap();
+    assert_eq!(relative.indices, vec![0, 1, 2]);
+
+    assert!(parse_obj("v 0 0\n").unwrap_err().starts_with("line 1"));
+    assert!(parse_obj("v 0 0 0\nf 1 2 3\n").unwrap_err().contains("invalid face index"));
+}
+
+#[test]
+fn test_loader_generates_proxy_on_worker() {
+    let source = Arc::new(MemorySource::new());
+    source.insert("cube.obj", CUBE_OBJ);
+    source.insert("decal.obj", "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n");
+    let mut server = AssetServer::new(source, 1).unwrap();
+    server.register_loader(MeshLoader::new(Some(ProxyMethod::ConvexHull)));
+
+    let cube = server.load::<MeshAsset>("cube.obj").wait(Duration::from_secs(5)).unwrap();
+    let proxy = cube.proxy.as_ref().expect("closed mesh gets a proxy");
+    assert!(proxy.contains(Vec3::zero()));
+    assert!(!proxy.contains(Vec3::new(0.0, 0.0, 0.6)));
+
+    // Flat meshes still load, just without a proxy
+    let decal = server.load::<MeshAsset>("decal.obj").wait(Duration::from_secs(5)).unwrap();
+    assert_eq!(decal.mesh.triangle_count(), 1);
+    assert!(decal.proxy.is_none());
+}
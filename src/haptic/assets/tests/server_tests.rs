@@ -0,0 +1,118 @@
+use super::*;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn server_with(files: &[(&str, &str)]) -> (AssetServer, Arc<MemorySource>) {
+    let source = Arc::new(MemorySource::new());
+    for (path, text) in files {
+        source.insert(*path, text.as_bytes());
+    }
+    let mut server = AssetServer::new(source.clone(), 2).unwrap();
+    server.register_loader(TextLoader);
+    server.register_loader(BytesLoader);
+    (server, source)
+}
+
+/// Drains events until `count` have arrived.
+fn wait_events(server: &mut AssetServer, count: usize) -> Vec<AssetEvent> {
+    let start = std::time::Instant::now();
+    let mut events = Vec::new();
+    while events.len() < count && start.elapsed() < TIMEOUT {
+        events.extend(server.poll_events());
+        thread::sleep(Duration::from_millis(1));
+    }
+    events
+}
+
+#[test]
+fn test_async_load_and_shared_handles() {
+    let (mut server, _) = server_with(&[("effects/click.txt", "click")]);
+    let a: Handle<String> = server.load("effects/click.txt");
+    let b: Handle<String> = server.load("effects/click.txt");
+    assert_eq!(a, b);
+    assert_eq!(server.len(), 1);
+
+    assert_eq!(*a.wait(TIMEOUT).unwrap(), "click");
+    assert_eq!(b.get().as_deref().map(String::as_str), Some("click"));
+    assert_eq!(a.version(), 1);
+    assert_eq!(wait_events(&mut server, 1), vec![AssetEvent::Loaded { path: "effects/click.txt".into() }]);
+}
+
+#[test]
+fn test_same_path_different_types_are_distinct() {
+    let (mut server, _) = server_with(&[("data.bin", "xyz")]);
+    let text: Handle<String> = server.load("data.bin");
+    let bytes: Handle<Vec<u8>> = server.load("data.bin");
+    assert_eq!(*bytes.wait(TIMEOUT).unwrap(), b"xyz".to_vec());
+    assert_eq!(*text.wait(TIMEOUT).unwrap(), "xyz");
+    assert_eq!(server.len(), 2);
+}
+
+#[test]
+fn test_failures_are_reported() {
+    let (mut server, _) = server_with(&[]);
+    let missing: Handle<String> = server.load("missing.txt");
+    assert!(matches!(missing.wait(TIMEOUT), Err(AssetError::Io { .. })));
+
+    let unsupported: Handle<f32> = server.load("number.txt");
+    assert!(matches!(unsupported.error(), Some(AssetError::NoLoader(_))));
+    assert_eq!(wait_events(&mut server, 2).len(), 2);
+}
+
+#[test]
+fn test_hot_reload_updates_existing_handles() {
+    let (mut server, source) = server_with(&[("label.txt", "old")]);
+    let handle: Handle<String> = server.load("label.txt");
+    handle.wait(TIMEOUT).unwrap();
+    wait_events(&mut server, 1);
+
+    let reloaded = Arc::new(Mutex::new(Vec::new()));
+    let log = reloaded.clone();
+    server.on_reload(move |path| log.lock().unwrap().push(path.to_path_buf()));
+
+    thread::sleep(Duration::from_millis(5));
+    source.insert("label.txt", "new");
+    assert_eq!(server.check_for_changes(), 1);
+
+    let events = wait_events(&mut server, 1);
+    assert_eq!(events, vec![AssetEvent::Reloaded { path: "label.txt".into() }]);
+    assert_eq!(*handle.get().unwrap(), "new");
+    assert_eq!(handle.version(), 2);
+    assert_eq!(*reloaded.lock().unwrap(), vec![PathBuf::from("label.txt")]);
+    assert_eq!(server.check_for_changes(), 0);
+}
+
+#[test]
+fn test_garbage_collection_after_handles_drop() {
+    let (mut server, _) = server_with(&[("a.txt", "a")]);
+    let handle: Handle<String> = server.load("a.txt");
+    handle.wait(TIMEOUT).unwrap();
+    wait_events(&mut server, 1);
+    assert_eq!(server.collect_garbage(), 0);
+
+    drop(handle);
+    assert_eq!(server.collect_garbage(), 1);
+    assert!(server.is_empty());
+}
+
+/// Loads text after holding the worker for a while.
+struct SlowLoader;
+
+impl AssetLoader for SlowLoader {
+    type Asset = String;
+
+    fn load(&self, bytes: &[u8], _path: &Path) -> Result<String, String> {
+        thread::sleep(Duration::from_millis(200));
+        String::from_utf8(bytes.to_vec()).map_err(|e| e.to_string())
+    }
+}
+
+#[test]
+fn test_wait_timeout_is_not_a_failure() {
+    let (mut server, _) = server_with(&[("slow.txt", "slow")]);
+    server.register_loader(SlowLoader);
+    let handle: Handle<String> = server.load("slow.txt");
+    assert_eq!(handle.wait(Duration::from_millis(1)), Err(AssetError::Timeout { path: "slow.txt".into() }));
+    assert!(handle.error().is_none());
+    assert_eq!(*handle.wait(TIMEOUT).unwrap(), "slow");
+}
@@ -0,0 +1,279 @@
+//! Typed angles so radians and degrees cannot be mixed up.
+//!
+//! Math and rotation constructors work in [`Rad`]; device SDKs and configuration
+//! files usually speak [`Deg`]. Both convert into each other with `From`, so any
+//! API taking `impl Into<Rad>` accepts either.
+
+use std::f32::consts::{PI, TAU};
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+/// An angle in radians.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Rad(pub f32);
+
+/// An angle in degrees.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Deg(pub f32);
+
+impl Rad {
+    pub const ZERO: Self = Self(0.0);
+    pub const QUARTER_TURN: Self = Self(PI * 0.5);
+    pub const HALF_TURN: Self = Self(PI);
+    pub const FULL_TURN: Self = Self(TAU);
+
+    #[inline]
+    pub fn to_deg(self) -> Deg {
+        Deg(self.0.to_degrees())
+    }
+
+    #[inline]
+    pub fn sin(self) -> f32 {
+        self.0.sin()
+    }
+
+    #[inline]
+    pub fn cos(self) -> f32 {
+        self.0.cos()
+    }
+
+    #[inline]
+    pub fn tan(self) -> f32 {
+        self.0.tan()
+    }
+
+    #[inline]
+    pub fn sin_cos(self) -> (f32, f32) {
+        self.0.sin_cos()
+    }
+
+    /// Arc sine; the input is clamped to [-1, 1].
+    #[inline]
+    pub fn asin(value: f32) -> Self {
+        Self(value.clamp(-1.0, 1.0).asin())
+    }
+
+    /// Arc cosine; the input is clamped to [-1, 1].
+    #[inline]
+    pub fn acos(value: f32) -> Self {
+        Self(value.clamp(-1.0, 1.0).acos())
+    }
+
+    #[inline]
+    pub fn atan2(y: f32, x: f32) -> Self {
+        Self(y.atan2(x))
+    }
+
+    /// Wraps into (-π, π].
+    #[inline]
+    pub fn wrap(self) -> Self {
+        let r = self.0.rem_euclid(TAU);
+        Self(if r > PI { r - TAU } else { r })
+    }
+
+    /// Normalizes into [0, 2π).
+    #[inline]
+    pub fn normalize(self) -> Self {
+        let r = self.0.rem_euclid(TAU);
+        // rem_euclid can round up to exactly TAU for tiny negative inputs
+        Self(if r >= TAU { 0.0 } else { r })
+    }
+
+    #[inline]
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// Signed shortest rotation from `self` to `other`, in (-π, π].
+    #[inline]
+    pub fn delta_to(self, other: Self) -> Self {
+        (other - self).wrap()
+    }
+
+    /// Interpolates along the shortest arc.
+    #[inline]
+    pub fn lerp_shortest(self, other: Self, t: f32) -> Self {
+        self + self.delta_to(other) * t
+    }
+
+    /// True if both angles describe the same direction within `epsilon` radians.
+    #[inline]
+    pub fn approx_eq(self, other: Self, epsilon: f32) -> bool {
+        self.delta_to(other).0.abs() <= epsilon
+    }
+}
+
+impl Deg {
+    pub const ZERO: Self = Self(0.0);
+    pub const QUARTER_TURN: Self = Self(90.0);
+    pub const HALF_TURN: Self = Self(180.0);
+    pub const FULL_TURN: Self = Self(360.0);
+
+    #[inline]
+    pub fn to_rad(self) -> Rad {
+        Rad(self.0.to_radians())
+    }
+
+    #[inline]
+    pub fn sin(self) -> f32 {
+        self.to_rad().sin()
+    }
+
+    #[inline]
+    pub fn cos(self) -> f32 {
+        self.to_rad().cos()
+    }
+
+    #[inline]
+    pub fn tan(self) -> f32 {
+        self.to_rad().tan()
+    }
+
+    #[inline]
+    pub fn sin_cos(self) -> (f32, f32) {
+        self.to_rad().sin_cos()
+    }
+
+    /// Wraps into (-180°, 180°].
+    #[inline]
+    pub fn wrap(self) -> Self {
+        let r = self.0.rem_euclid(360.0);
+        Self(if r > 180.0 { r - 360.0 } else { r })
+    }
+
+    /// Normalizes into [0°, 360°).
+    #[inline]
+    pub fn normalize(self) -> Self {
+        let r = self.0.rem_euclid(360.0);
+        Self(if r >= 360.0 { 0.0 } else { r })
+    }
+
+    #[inline]
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// Signed shortest rotation from `self` to `other`, in (-180°, 180°].
+    #[inline]
+    pub fn delta_to(self, other: Self) -> Self {
+        (other - self).wrap()
+    }
+}
+
+// ============================================================================
+// Conversions
+// ============================================================================
+
+impl From<Deg> for Rad {
+    #[inline]
+    fn from(deg: Deg) -> Self {
+        deg.to_rad()
+    }
+}
+
+impl From<Rad> for Deg {
+    #[inline]
+    fn from(rad: Rad) -> Self {
+        rad.to_deg()
+    }
+}
+
+impl fmt::Display for Rad {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.3} rad", self.0)
+    }
+}
+
+impl fmt::Display for Deg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.1}°", self.0)
+    }
+}
+
+// ============================================================================
+// Arithmetic
+// ============================================================================
+
+macro_rules! angle_ops {
+    ($t:ident) => {
+        impl Add for $t {
+            type Output = Self;
+            #[inline]
+            fn add(self, other: Self) -> Self {
+                $t(self.0 + other.0)
+            }
+        }
+
+        impl AddAssign for $t {
+            #[inline]
+            fn add_assign(&mut self, other: Self) {
+                self.0 += other.0;
+            }
+        }
+
+        impl Sub for $t {
+            type Output = Self;
+            #[inline]
+            fn sub(self, other: Self) -> Self {
+                $t(self.0 - other.0)
+            }
+        }
+
+        impl SubAssign for $t {
+            #[inline]
+            fn sub_assign(&mut self, other: Self) {
+                self.0 -= other.0;
+            }
+        }
+
+        impl Neg for $t {
+            type Output = Self;
+            #[inline]
+            fn neg(self) -> Self {
+                $t(-self.0)
+            }
+        }
+
+        impl Mul<f32> for $t {
+            type Output = Self;
+            #[inline]
+            fn mul(self, scalar: f32) -> Self {
+                $t(self.0 * scalar)
+            }
+        }
+
+        impl Mul<$t> for f32 {
+            type Output = $t;
+            #[inline]
+            fn mul(self, angle: $t) -> $t {
+                $t(self * angle.0)
+            }
+        }
+
+        impl Div<f32> for $t {
+            type Output = Self;
+            #[inline]
+            fn div(self, scalar: f32) -> Self {
+                $t(self.0 / scalar)
+            }
+        }
+
+        // Ratio of two angles
+        impl Div for $t {
+            type Output = f32;
+            #[inline]
+            fn div(self, other: Self) -> f32 {
+                self.0 / other.0
+            }
+        }
+    };
+}
+
+angle_ops!(Rad);
+angle_ops!(Deg);
+
+#[cfg(test)]
+#[path = "tests/angle_tests.rs"]
+mod tests;
@@ -0,0 +1,221 @@
+//! Monotonic session clock and NTP-style offset estimation.
+//!
+//! Every thread of a session timestamps against the same `SessionClock` epoch, so
+//! timestamps from the servo loop and the UI thread are directly comparable. Remote
+//! clocks (network peers, device firmware counters) are mapped into session time with
+//! an `OffsetEstimator` fed by request/response timestamp exchanges.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+// ============================================================================
+// Session Clock
+// ============================================================================
+
+/// Monotonic clock counting microseconds since the session epoch.
+/// Cheap to copy; all copies share the same epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionClock {
+    epoch: Instant,
+}
+
+impl SessionClock {
+    /// Starts a new session with the epoch at the current instant.
+    pub fn new() -> Self {
+        Self { epoch: Instant::now() }
+    }
+
+    /// Creates a clock with an explicit epoch.
+    pub fn with_epoch(epoch: Instant) -> Self {
+        Self { epoch }
+    }
+
+    #[inline]
+    pub fn epoch(&self) -> Instant {
+        self.epoch
+    }
+
+    /// Current session time in microseconds.
+    #[inline]
+    pub fn now_us(&self) -> u64 {
+        self.timestamp_of(Instant::now())
+    }
+
+    /// Session time of an instant in microseconds (0 for instants before the epoch).
+    #[inline]
+    pub fn timestamp_of(&self, instant: Instant) -> u64 {
+        instant.saturating_duration_since(self.epoch).as_micros() as u64
+    }
+
+    /// Converts a session timestamp back into an `Instant`.
+    #[inline]
+    pub fn instant_of(&self, timestamp_us: u64) -> Instant {
+        self.epoch + Duration::from_micros(timestamp_us)
+    }
+}
+
+impl Default for SessionClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// Offset Estimation
+// ============================================================================
+
+/// Result of one request/response exchange with a remote clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncSample {
+    /// Estimated remote − local offset in microseconds.
+    pub offset_us: i64,
+    /// Round-trip delay excluding remote processing time, in microseconds.
+    pub delay_us: i64,
+    /// Local time at which the response was received.
+    pub local_us: u64,
+}
+
+impl SyncSample {
+    /// Computes offset and delay from the four NTP timestamps:
+    /// `t0` local send, `t1` remote receive, `t2` remote send, `t3` local receive.
+    ///
+    /// Devices that report a single timestamp per query can pass it as both `t1` and `t2`.
+    pub fn from_exchange(t0: u64, t1: u64, t2: u64, t3: u64) -> Self {
+        let (t0, t1, t2, t3) = (t0 as i64, t1 as i64, t2 as i64, t3 as i64);
+        Self {
+            offset_us: ((t1 - t0) + (t2 - t3)) / 2,
+            delay_us: ((t3 - t0) - (t2 - t1)).max(0),
+            local_us: t3 as u64,
+        }
+    }
+}
+
+/// Current best estimate of a remote clock relative to session time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClockOffset {
+    /// Remote − local offset at `reference_us`, in microseconds.
+    pub offset_us: i64,
+    /// Half the round-trip delay of the sample the estimate is based on.
+    pub uncertainty_us: i64,
+    /// Local time the offset was measured at.
+    pub reference_us: u64,
+    /// Relative frequency error of the remote clock (remote seconds gained per local second).
+    pub drift: f64,
+}
+
+impl ClockOffset {
+    /// Offset extrapolated to the given local time.
+    #[inline]
+    pub fn offset_at(&self, local_us: u64) -> i64 {
+        let elapsed = local_us as f64 - self.reference_us as f64;
+        self.offset_us + (elapsed * self.drift).round() as i64
+    }
+
+    /// Maps a remote timestamp into session time.
+    #[inline]
+    pub fn to_local(&self, remote_us: u64) -> u64 {
+        // One fixed-point step is enough: drift is a few ppm
+        let guess = (remote_us as i64 - self.offset_us).max(0) as u64;
+        (remote_us as i64 - self.offset_at(guess)).max(0) as u64
+    }
+
+    /// Maps a session timestamp into the remote clock.
+    #[inline]
+    pub fn to_remote(&self, local_us: u64) -> u64 {
+        (local_us as i64 + self.offset_at(local_us)).max(0) as u64
+    }
+}
+
+/// Filters a sliding window of exchanges into a clock offset estimate.
+///
+/// Uses the NTP clock-filter heuristic: the sample with the smallest round-trip delay
+/// is the least disturbed by queueing, so its offset is trusted. Drift is fitted over
+/// the low-delay half of the window once it spans enough time.
+#[derive(Debug, Clone)]
+pub struct OffsetEstimator {
+    samples: VecDeque<SyncSample>,
+    capacity: usize,
+}
+
+impl OffsetEstimator {
+    /// Default number of exchanges kept in the filter window.
+    pub const DEFAULT_CAPACITY: usize = 32;
+
+    /// Minimum local time span (1 s) before drift is estimated.
+    const MIN_DRIFT_SPAN_US: u64 = 1_000_000;
+
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self { samples: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    /// Records an exchange and returns the resulting sample.
+    pub fn add_exchange(&mut self, t0: u64, t1: u64, t2: u64, t3: u64) -> SyncSample {
+        let sample = SyncSample::from_exchange(t0, t1, t2, t3);
+        self.add_sample(sample);
+        sample
+    }
+
+    /// Records a precomputed sample.
+    pub fn add_sample(&mut self, sample: SyncSample) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    #[inline]
+    pub fn sample_count(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Returns the current estimate, or None before the first exchange.
+    pub fn estimate(&self) -> Option<ClockOffset> {
+        let best = self.samples.iter().min_by_key(|s| s.delay_us)?;
+        Some(ClockOffset {
+            offset_us: best.offset_us,
+            uncertainty_us: best.delay_us / 2,
+            reference_us: best.local_us,
+            drift: self.estimate_drift(),
+        })
+    }
+
+    /// Least-squares slope of offset over local time, using the lower-delay half of the window.
+    fn estimate_drift(&self) -> f64 {
+        let mut delays: Vec<i64> = self.samples.iter().map(|s| s.delay_us).collect();
+        delays.sort_unstable();
+        let cutoff = delays[delays.len() / 2];
+        let good: Vec<&SyncSample> = self.samples.iter().filter(|s| s.delay_us <= cutoff).collect();
+
+        let first = good.iter().map(|s| s.local_us).min().unwrap_or(0);
+        let last = good.iter().map(|s| s.local_us).max().unwrap_or(0);
+        if good.len() < 2 || last - first < Self::MIN_DRIFT_SPAN_US {
+            return 0.0;
+        }
+
+        let n = good.len() as f64;
+        let mean_x = good.iter().map(|s| (s.local_us - first) as f64).sum::<f64>() / n;
+        let mean_y = good.iter().map(|s| s.offset_us as f64).sum::<f64>() / n;
+        let (mut sxy, mut sxx) = (0.0, 0.0);
+        for s in &good {
+            let dx = (s.local_us - first) as f64 - mean_x;
+            sxy += dx * (s.offset_us as f64 - mean_y);
+            sxx += dx * dx;
+        }
+        if sxx > 0.0 { sxy / sxx } else { 0.0 }
+    }
+}
+
+impl Default for OffsetEstimator {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/clock_tests.rs"]
+mod tests;
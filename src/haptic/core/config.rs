@@ -0,0 +1,425 @@
+//! Layered runtime configuration with change notifications.
+//!
+//! Values are resolved from the highest layer that sets them:
+//! defaults → config file → environment → command line. Subsystems read typed
+//! values by dotted key (`servo.rate_hz`) and subscribe to a key or a section
+//! (`safety`) to react when a value changes or is removed at runtime.
+
+use super::constants;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+// ============================================================================
+// Values and Layers
+// ============================================================================
+
+/// A configuration value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl ConfigValue {
+    /// Infers the type of a raw string: bool, then integer, then float, then text.
+    pub fn infer(raw: &str) -> Self {
+        let raw = raw.trim();
+        if let Ok(b) = raw.parse() {
+            ConfigValue::Bool(b)
+        } else if let Ok(i) = raw.parse() {
+            ConfigValue::Int(i)
+        } else if let Ok(f) = raw.parse() {
+            ConfigValue::Float(f)
+        } else {
+            ConfigValue::Text(raw.trim_matches('"').to_string())
+        }
+    }
+
+    /// Parses a raw string as the same type as `self`. Integers are accepted for floats.
+    pub fn parse_like(&self, raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        match self {
+            ConfigValue::Bool(_) => match raw {
+                "1" | "on" | "yes" => Some(ConfigValue::Bool(true)),
+                "0" | "off" | "no" => Some(ConfigValue::Bool(false)),
+                _ => raw.parse().ok().map(ConfigValue::Bool),
+            },
+            ConfigValue::Int(_) => raw.parse().ok().map(ConfigValue::Int),
+            ConfigValue::Float(_) => raw.parse().ok().map(ConfigValue::Float),
+            ConfigValue::Text(_) => Some(ConfigValue::Text(raw.trim_matches('"').to_string())),
+        }
+    }
+
+    #[inline]
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            ConfigValue::Float(f) => Some(f),
+            ConfigValue::Int(i) => Some(i as f64),
+            _ => None,
+        }
+    }
+
+    /// The number as a finite f32 of at least zero, the range of every numeric
+    /// tuning. `inf` and `nan` parse as floats, so files and flags can supply them.
+    pub fn as_tuning(&self) -> Option<f32> {
+        self.as_f64().map(|v| v as f32).filter(|v| v.is_finite() && *v >= 0.0)
+    }
+
+    #[inline]
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            ConfigValue::Int(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            ConfigValue::Bool(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ConfigValue::Text(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ConfigValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigValue::Bool(b) => write!(f, "{}", b),
+            ConfigValue::Int(i) => write!(f, "{}", i),
+            ConfigValue::Float(v) => write!(f, "{}", v),
+            ConfigValue::Text(s) => write!(f, "\"{}\"", s),
+        }
+    }
+}
+
+/// Configuration layers in increasing priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Layer {
+    Default = 0,
+    File = 1,
+    Env = 2,
+    Cli = 3,
+}
+
+impl Layer {
+    const ALL: [Layer; 4] = [Layer::Default, Layer::File, Layer::Env, Layer::Cli];
+}
+
+/// Errors while loading configuration sources.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    /// A line of a config file could not be parsed.
+    Syntax { line: usize, message: String },
+    /// A value does not match the type of the key's default.
+    Type { key: String, expected: ConfigValue, raw: String },
+    /// The config file could not be read.
+    Io(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            ConfigError::Type { key, expected, raw } => {
+                write!(f, "'{}' for {} does not match the type of default {}", raw, key, expected)
+            }
+            ConfigError::Io(message) => write!(f, "cannot read config: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+// ============================================================================
+// Config Service
+// ============================================================================
+
+/// Identifies a change subscription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+type ChangeCallback = Box<dyn FnMut(&str, Option<&ConfigValue>) + Send>;
+
+/// Layered configuration store.
+pub struct Config {
+    layers: [HashMap<String, ConfigValue>; 4],
+    subscribers: Vec<(SubscriptionId, String, ChangeCallback)>,
+    next_subscription: u64,
+    version: u64,
+}
+
+impl Config {
+    /// Creates a config holding only the built-in defaults from `constants`.
+    pub fn new() -> Self {
+        let mut config = Self::empty();
+        constants::register_defaults(&mut config);
+        config
+    }
+
+    /// Creates a config without any defaults.
+    pub fn empty() -> Self {
+        Self { layers: Default::default(), subscribers: Vec::new(), next_subscription: 0, version: 0 }
+    }
+
+    // ============================================================================
+    // Reading
+    // ============================================================================
+
+    /// Effective value of `key`.
+    pub fn get(&self, key: &str) -> Option<&ConfigValue> {
+        self.layers.iter().rev().find_map(|layer| layer.get(key))
+    }
+
+    /// Layer the effective value of `key` comes from.
+    pub fn source(&self, key: &str) -> Option<Layer> {
+        Layer::ALL.iter().rev().copied().find(|&l| self.layers[l as usize].contains_key(key))
+    }
+
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        self.get(key).and_then(ConfigValue::as_f64)
+    }
+
+    pub fn get_f32(&self, key: &str) -> Option<f32> {
+        self.get_f64(key).map(|v| v as f32)
+    }
+
+    /// `key` as a [tuning](ConfigValue::as_tuning); None if it is unset or out of range.
+    pub fn get_tuning(&self, key: &str) -> Option<f32> {
+        self.get(key).and_then(ConfigValue::as_tuning)
+    }
+
+    pub fn get_i64(&self, key: &str) -> Option<i64> {
+        self.get(key).and_then(ConfigValue::as_i64)
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key).and_then(ConfigValue::as_bool)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(ConfigValue::as_str)
+    }
+
+    /// All keys with an effective value, sorted.
+    pub fn keys(&self) -> Vec<&str> {
+        let mut keys: Vec<&str> = self.layers.iter().flat_map(|l| l.keys().map(String::as_str)).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        keys
+    }
+
+    /// Incremented on every effective change; cheap to poll from other threads' snapshots.
+    #[inline]
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    // ============================================================================
+    // Writing
+    // ============================================================================
+
+    pub fn set_default(&mut self, key: impl Into<String>, value: ConfigValue) {
+        self.set(Layer::Default, key, value);
+    }
+
+    /// Sets `key` in `layer` and notifies subscribers if the effective value changed.
+    pub fn set(&mut self, layer: Layer, key: impl Into<String>, value: ConfigValue) {
+        let key = key.into();
+        let before = self.get(&key).cloned();
+        self.layers[layer as usize].insert(key.clone(), value);
+        self.after_change(&key, before);
+    }
+
+    /// Removes `key` from `layer`, falling back to lower layers.
+    pub fn unset(&mut self, layer: Layer, key: &str) {
+        let before = self.get(key).cloned();
+        if self.layers[layer as usize].remove(key).is_some() {
+            self.after_change(key, before);
+        }
+    }
+
+    /// Parses `raw` with the type of the key's current value (or infers it) and sets it.
+    pub fn set_raw(&mut self, layer: Layer, key: &str, raw: &str) -> Result<(), ConfigError> {
+        let value = match self.get(key) {
+            Some(existing) => existing.parse_like(raw).ok_or_else(|| ConfigError::Type {
+                key: key.to_string(),
+                expected: existing.clone(),
+                raw: raw.to_string(),
+            })?,
+            None => ConfigValue::infer(raw),
+        };
+        self.set(layer, key, value);
+        Ok(())
+    }
+
+    fn after_change(&mut self, key: &str, before: Option<ConfigValue>) {
+        let after = self.get(key).cloned();
+        if after == before {
+            return;
+        }
+        self.version += 1;
+        for (_, prefix, callback) in &mut self.subscribers {
+            if in_section(key, prefix) {
+                callback(key, after.as_ref());
+            }
+        }
+    }
+
+    // ============================================================================
+    // Sources
+    // ============================================================================
+
+    /// Loads `key = value` lines into the `File` layer. `[section]` headers prefix the
+    /// following keys with `section.`; `#` outside double quotes starts a comment.
+    pub fn load_str(&mut self, text: &str) -> Result<(), ConfigError> {
+        let mut section = String::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = format!("{}.", name.trim());
+                continue;
+            }
+            let (key, raw) = line.split_once('=').ok_or_else(|| ConfigError::Syntax {
+                line: i + 1,
+                message: format!("expected 'key = value', found '{}'", line),
+            })?;
+            self.set_raw(Layer::File, &format!("{}{}", section, key.trim()), raw)?;
+        }
+        Ok(())
+    }
+
+    /// Loads a config file into the `File` layer.
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> Result<(), ConfigError> {
+        let text = std::fs::read_to_string(path).map_err(|e| ConfigError::Io(e.to_string()))?;
+        self.load_str(&text)
+    }
+
+    /// Loads variables named `{prefix}SECTION__KEY` into the `Env` layer as `section.key`.
+    pub fn load_env<I>(&mut self, prefix: &str, vars: I) -> Result<(), ConfigError>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        for (name, raw) in vars {
+            if let Some(rest) = name.strip_prefix(prefix) {
+                let key = rest.to_ascii_lowercase().replace("__", ".");
+                self.set_raw(Layer::Env, &key, &raw)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Loads `HAPTICUI_*` variables from the process environment.
+    pub fn load_process_env(&mut self) -> Result<(), ConfigError> {
+        self.load_env("HAPTICUI_", std::env::vars())
+    }
+
+    /// Loads `--key=value` and `--key value` flags into the `Cli` layer. A bare `--flag`
+    /// sets a boolean. Arguments that are not flags are returned untouched.
+    pub fn load_args<I, S>(&mut self, args: I) -> Result<Vec<String>, ConfigError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut rest = Vec::new();
+        let mut args = args.into_iter().map(Into::into).peekable();
+        while let Some(arg) = args.next() {
+            let Some(flag) = arg.strip_prefix("--") else {
+                rest.push(arg);
+                continue;
+            };
+            match flag.split_once('=') {
+                Some((key, raw)) => self.set_raw(Layer::Cli, key, raw)?,
+                None if args.peek().is_some_and(|next| !next.starts_with("--")) => {
+                    let raw = args.next().unwrap_or_default();
+                    self.set_raw(Layer::Cli, flag, &raw)?;
+                }
+                None => self.set(Layer::Cli, flag, ConfigValue::Bool(true)),
+            }
+        }
+        Ok(rest)
+    }
+
+    // ============================================================================
+    // Notifications
+    // ============================================================================
+
+    /// Calls `callback(key, new_value)` whenever the effective value of `prefix`, or
+    /// of a key in the section it names, changes; the value is None once the key is
+    /// unset in every layer. `servo` and `servo.` both cover `servo.rate_hz` but not
+    /// `servo_debug.x`, and `servo.rate_hz` does not cover `servo.rate_hz_max`. An
+    /// empty prefix subscribes to everything.
+    pub fn subscribe(
+        &mut self,
+        prefix: impl Into<String>,
+        callback: impl FnMut(&str, Option<&ConfigValue>) + Send + 'static,
+    ) -> SubscriptionId {
+        let id = SubscriptionId(self.next_subscription);
+        self.next_subscription += 1;
+        self.subscribers.push((id, prefix.into(), Box::new(callback)));
+        id
+    }
+
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        let before = self.subscribers.len();
+        self.subscribers.retain(|(s, _, _)| *s != id);
+        self.subscribers.len() != before
+    }
+}
+
+/// Whether `key` is `prefix` itself or lies in the section it names, matching whole
+/// dot-separated segments.
+fn in_section(key: &str, prefix: &str) -> bool {
+    let section = prefix.strip_suffix('.').unwrap_or(prefix);
+    match key.strip_prefix(section) {
+        Some(rest) => section.is_empty() || rest.is_empty() || rest.starts_with('.'),
+        None => false,
+    }
+}
+
+/// `line` up to a `#` that is not inside double quotes.
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut map = f.debug_map();
+        for key in self.keys() {
+            map.entry(&key, &self.get(key));
+        }
+        map.finish()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/config_tests.rs"]
+mod tests;
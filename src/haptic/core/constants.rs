@@ -0,0 +1,71 @@
+//! Default tunings of runtime-configurable subsystems.
+//!
+//! These are the bottom layer of `Config`; files, environment variables, and
+//! command-line flags override them by key. Numerical tolerances such as
+//! [`EPSILON`](super::EPSILON) are not tunings and stay compile-time constants.
+
+use super::config::{Config, ConfigValue};
+
+// Servo loop
+pub const SERVO_RATE_HZ: &str = "servo.rate_hz";
+pub const DEFAULT_SERVO_RATE_HZ: f64 = 1000.0;
+/// Highest servo rate accepted; faster settings are clamped to it.
+pub const MAX_SERVO_RATE_HZ: f64 = 10_000.0;
+
+// Safety limits
+pub const SAFETY_MAX_FORCE_N: &str = "safety.max_force_n";
+pub const DEFAULT_SAFETY_MAX_FORCE_N: f64 = 3.0;
+pub const SAFETY_MAX_FORCE_RATE_N_PER_S: &str = "safety.max_force_rate_n_per_s";
+pub const DEFAULT_SAFETY_MAX_FORCE_RATE_N_PER_S: f64 = 300.0;
+
+// Network deadband filters
+pub const NET_POSITION_DEADBAND_M: &str = "net.position_deadband_m";
+pub const DEFAULT_NET_POSITION_DEADBAND_M: f64 = 0.0005;
+pub const NET_FORCE_WEBER_FRACTION: &str = "net.force_weber_fraction";
+pub const DEFAULT_NET_FORCE_WEBER_FRACTION: f64 = 0.10;
+pub const NET_FORCE_DEADBAND_N: &str = "net.force_deadband_n";
+pub const DEFAULT_NET_FORCE_DEADBAND_N: f64 = 0.01;
+pub const NET_HEARTBEAT_INTERVAL: &str = "net.heartbeat_interval";
+pub const DEFAULT_NET_HEARTBEAT_INTERVAL: i64 = 100;
+
+// Force level of detail
+pub const LOD_SLOW_M_PER_S: &str = "lod.slow_m_per_s";
+pub const DEFAULT_LOD_SLOW_M_PER_S: f64 = 0.05;
+pub const LOD_FAST_M_PER_S: &str = "lod.fast_m_per_s";
+pub const DEFAULT_LOD_FAST_M_PER_S: f64 = 0.3;
+pub const LOD_MIN_DETAIL: &str = "lod.min_detail";
+pub const DEFAULT_LOD_MIN_DETAIL: f64 = 0.2;
+pub const LOD_FAST_CUTOFF_HZ: &str = "lod.fast_cutoff_hz";
+pub const DEFAULT_LOD_FAST_CUTOFF_HZ: f64 = 80.0;
+pub const LOD_RESPONSE_S: &str = "lod.response_s";
+pub const DEFAULT_LOD_RESPONSE_S: f64 = 0.03;
+
+// Contact engagement
+pub const CONTACT_ENGAGE_DEPTH_M: &str = "contact.engage_depth_m";
+pub const DEFAULT_CONTACT_ENGAGE_DEPTH_M: f64 = 0.0;
+pub const CONTACT_RELEASE_DEPTH_M: &str = "contact.release_depth_m";
+pub const DEFAULT_CONTACT_RELEASE_DEPTH_M: f64 = 0.0;
+pub const CONTACT_RAMP_IN_MS: &str = "contact.ramp_in_ms";
+pub const DEFAULT_CONTACT_RAMP_IN_MS: f64 = 0.0;
+
+/// Registers every default above in the `Default` layer of `config`.
+pub fn register_defaults(config: &mut Config) {
+    config.set_default(SERVO_RATE_HZ, ConfigValue::Float(DEFAULT_SERVO_RATE_HZ));
+    config.set_default(SAFETY_MAX_FORCE_N, ConfigValue::Float(DEFAULT_SAFETY_MAX_FORCE_N));
+    config.set_default(SAFETY_MAX_FORCE_RATE_N_PER_S, ConfigValue::Float(DEFAULT_SAFETY_MAX_FORCE_RATE_N_PER_S));
+
+    config.set_default(NET_POSITION_DEADBAND_M, ConfigValue::Float(DEFAULT_NET_POSITION_DEADBAND_M));
+    config.set_default(NET_FORCE_WEBER_FRACTION, ConfigValue::Float(DEFAULT_NET_FORCE_WEBER_FRACTION));
+    config.set_default(NET_FORCE_DEADBAND_N, ConfigValue::Float(DEFAULT_NET_FORCE_DEADBAND_N));
+    config.set_default(NET_HEARTBEAT_INTERVAL, ConfigValue::Int(DEFAULT_NET_HEARTBEAT_INTERVAL));
+
+    config.set_default(LOD_SLOW_M_PER_S, ConfigValue::Float(DEFAULT_LOD_SLOW_M_PER_S));
+    config.set_default(LOD_FAST_M_PER_S, ConfigValue::Float(DEFAULT_LOD_FAST_M_PER_S));
+    config.set_default(LOD_MIN_DETAIL, ConfigValue::Float(DEFAULT_LOD_MIN_DETAIL));
+    config.set_default(LOD_FAST_CUTOFF_HZ, ConfigValue::Float(DEFAULT_LOD_FAST_CUTOFF_HZ));
+    config.set_default(LOD_RESPONSE_S, ConfigValue::Float(DEFAULT_LOD_RESPONSE_S));
+
+    config.set_default(CONTACT_ENGAGE_DEPTH_M, ConfigValue::Float(DEFAULT_CONTACT_ENGAGE_DEPTH_M));
+    config.set_default(CONTACT_RELEASE_DEPTH_M, ConfigValue::Float(DEFAULT_CONTACT_RELEASE_DEPTH_M));
+    config.set_default(CONTACT_RAMP_IN_MS, ConfigValue::Float(DEFAULT_CONTACT_RAMP_IN_MS));
+}
@@ -0,0 +1,427 @@
+//! 4x4 affine/projective matrices (column-major, column vectors).
+//!
+//! Matches the memory layout of glTF and most graphics APIs: `cols[c][r]` is the
+//! element in row `r` of column `c`, and `a * b` applies `b` first.
+
+use super::angle::Rad;
+use super::quat::Quat;
+use super::vec3::{Vec3, Vec4, EPSILON};
+use std::fmt;
+use std::ops::Mul;
+
+/// Column-major 4x4 matrix.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat4 {
+    pub cols: [[f32; 4]; 4],
+}
+
+/// Shear components found during decomposition (XY, XZ, YZ).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shear {
+    pub xy: f32,
+    pub xz: f32,
+    pub yz: f32,
+}
+
+impl Shear {
+    #[inline]
+    pub fn max_abs(&self) -> f32 {
+        self.xy.abs().max(self.xz.abs()).max(self.yz.abs())
+    }
+}
+
+/// Reasons a matrix cannot be represented as translation, rotation and scale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DecomposeError {
+    /// The bottom row is not (0, 0, 0, 1).
+    Projective,
+    /// A basis vector has (near) zero length.
+    Singular,
+    /// The basis vectors are not orthogonal.
+    Sheared(Shear),
+}
+
+impl fmt::Display for DecomposeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecomposeError::Projective => write!(f, "matrix has a projective component"),
+            DecomposeError::Singular => write!(f, "matrix is singular"),
+            DecomposeError::Sheared(s) => write!(f, "matrix has shear (xy {}, xz {}, yz {})", s.xy, s.xz, s.yz),
+        }
+    }
+}
+
+impl std::error::Error for DecomposeError {}
+
+/// Clip-space depth convention of a projection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DepthRange {
+    /// Near plane maps to 0 and far to 1 (Vulkan, Metal, D3D, wgpu).
+    #[default]
+    ZeroToOne,
+    /// Near plane maps to -1 and far to 1 (OpenGL).
+    NegOneToOne,
+}
+
+impl DepthRange {
+    /// NDC depth of the near plane.
+    #[inline]
+    pub fn near(self) -> f32 {
+        match self {
+            DepthRange::ZeroToOne => 0.0,
+            DepthRange::NegOneToOne => -1.0,
+        }
+    }
+
+    /// NDC depth of the far plane.
+    #[inline]
+    pub fn far(self) -> f32 {
+        1.0
+    }
+}
+
+/// Maximum shear component accepted by [`Mat4::try_decompose`].
+pub const SHEAR_TOLERANCE: f32 = 1e-4;
+
+impl Mat4 {
+    // ============================================================================
+    // Constructors
+    // ============================================================================
+
+    pub const IDENTITY: Self = Self::from_cols([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+
+    #[inline]
+    pub const fn from_cols(cols: [[f32; 4]; 4]) -> Self {
+        Self { cols }
+    }
+
+    #[inline]
+    pub const fn identity() -> Self {
+        Self::IDENTITY
+    }
+
+    /// Builds a matrix from row-major data (as written on paper).
+    pub fn from_rows(rows: [[f32; 4]; 4]) -> Self {
+        Self::from_cols(std::array::from_fn(|c| std::array::from_fn(|r| rows[r][c])))
+    }
+
+    /// Reads a column-major array, e.g. a glTF `matrix`.
+    #[inline]
+    pub fn from_cols_array(data: &[f32; 16]) -> Self {
+        Self::from_cols(std::array::from_fn(|c| std::array::from_fn(|r| data[c * 4 + r])))
+    }
+
+    #[inline]
+    pub fn to_cols_array(&self) -> [f32; 16] {
+        std::array::from_fn(|i| self.cols[i / 4][i % 4])
+    }
+
+    #[inline]
+    pub fn from_translation(t: Vec3) -> Self {
+        let mut m = Self::IDENTITY;
+        m.cols[3] = [t.x, t.y, t.z, 1.0];
+        m
+    }
+
+    #[inline]
+    pub fn from_scale(s: Vec3) -> Self {
+        Self::from_cols([[s.x, 0.0, 0.0, 0.0], [0.0, s.y, 0.0, 0.0], [0.0, 0.0, s.z, 0.0], [0.0, 0.0, 0.0, 1.0]])
+    }
+
+    pub fn from_quat(q: Quat) -> Self {
+        Self::from_trs(Vec3::zero(), q, Vec3::one())
+    }
+
+    /// Translation * rotation * scale.
+    pub fn from_trs(translation: Vec3, rotation: Quat, scale: Vec3) -> Self {
+        let x = rotation.rotate(Vec3::unit_x()) * scale.x;
+        let y = rotation.rotate(Vec3::unit_y()) * scale.y;
+        let z = rotation.rotate(Vec3::unit_z()) * scale.z;
+        let t = translation;
+        Self::from_cols([[x.x, x.y, x.z, 0.0], [y.x, y.y, y.z, 0.0], [z.x, z.y, z.z, 0.0], [t.x, t.y, t.z, 1.0]])
+    }
+
+    // ============================================================================
+    // Camera and Projection
+    // ============================================================================
+    //
+    // Right-handed: the camera looks down -Z with +Y up, as in glTF.
+
+    /// Perspective projection with the default depth range.
+    #[inline]
+    pub fn perspective(fov_y: impl Into<Rad>, aspect: f32, near: f32, far: f32) -> Self {
+        Self::perspective_in(fov_y, aspect, near, far, DepthRange::default())
+    }
+
+    /// Perspective projection with a vertical field of view `fov_y`.
+    pub fn perspective_in(fov_y: impl Into<Rad>, aspect: f32, near: f32, far: f32, depth: DepthRange) -> Self {
+        let f = 1.0 / (fov_y.into() * 0.5).tan();
+        let range = near - far;
+        let (zz, zw) = match depth {
+            DepthRange::ZeroToOne => (far / range, near * far / range),
+            DepthRange::NegOneToOne => ((far + near) / range, 2.0 * near * far / range),
+        };
+        Self::from_cols([[f / aspect, 0.0, 0.0, 0.0], [0.0, f, 0.0, 0.0], [0.0, 0.0, zz, -1.0], [0.0, 0.0, zw, 0.0]])
+    }
+
+    /// Orthographic projection with the default depth range.
+    #[inline]
+    pub fn orthographic(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Self {
+        Self::orthographic_in(left, right, bottom, top, near, far, DepthRange::default())
+    }
+
+    /// Orthographic projection of the box `left..right`, `bottom..top`, `-near..-far`.
+    pub fn orthographic_in(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32, depth: DepthRange) -> Self {
+        let range = near - far;
+        let (zz, zw) = match depth {
+            DepthRange::ZeroToOne => (1.0 / range, near / range),
+            DepthRange::NegOneToOne => (2.0 / range, (far + near) / range),
+        };
+        Self::from_cols([
+            [2.0 / (right - left), 0.0, 0.0, 0.0],
+            [0.0, 2.0 / (top - bottom), 0.0, 0.0],
+            [0.0, 0.0, zz, 0.0],
+            [-(right + left) / (right - left), -(top + bottom) / (top - bottom), zw, 1.0],
+        ])
+    }
+
+    /// View matrix (world to camera) for a camera at `eye` looking at `target`.
+    pub fn look_at(eye: Vec3, target: Vec3, up: Vec3) -> Self {
+        let f = (target - eye).normalize();
+        let s = f.cross(up).normalize();
+        let u = s.cross(f);
+        Self::from_cols([
+            [s.x, u.x, -f.x, 0.0],
+            [s.y, u.y, -f.y, 0.0],
+            [s.z, u.z, -f.z, 0.0],
+            [-s.dot(eye), -u.dot(eye), f.dot(eye), 1.0],
+        ])
+    }
+
+    /// Maps a point in normalized device coordinates back through this
+    /// (view-)projection matrix. None if the matrix is singular.
+    pub fn unproject(&self, ndc: Vec3) -> Option<Vec3> {
+        self.inverse().map(|inverse| inverse.transform_point(ndc))
+    }
+
+    /// World-space ray through NDC `(x, y)` of this view-projection matrix, as
+    /// (origin on the near plane, unit direction). Used for pointer picking.
+    pub fn ndc_ray(&self, x: f32, y: f32, depth: DepthRange) -> Option<(Vec3, Vec3)> {
+        let inverse = self.inverse()?;
+        let near = inverse.transform_point(Vec3::new(x, y, depth.near()));
+        let far = inverse.transform_point(Vec3::new(x, y, depth.far()));
+        Some((near, (far - near).try_normalize()?))
+    }
+
+    // ============================================================================
+    // Access
+    // ============================================================================
+
+    #[inline]
+    pub fn col(&self, c: usize) -> Vec4 {
+        let [x, y, z, w] = self.cols[c];
+        Vec4::new(x, y, z, w)
+    }
+
+    #[inline]
+    pub fn row(&self, r: usize) -> Vec4 {
+        Vec4::new(self.cols[0][r], self.cols[1][r], self.cols[2][r], self.cols[3][r])
+    }
+
+    /// First three components of column `c` (a basis vector or the translation).
+    #[inline]
+    pub fn col3(&self, c: usize) -> Vec3 {
+        Vec3::new(self.cols[c][0], self.cols[c][1], self.cols[c][2])
+    }
+
+    #[inline]
+    pub fn translation(&self) -> Vec3 {
+        self.col3(3)
+    }
+
+    /// True if the bottom row is (0, 0, 0, 1).
+    pub fn is_affine(&self) -> bool {
+        let r = self.row(3);
+        r.x.abs() < EPSILON && r.y.abs() < EPSILON && r.z.abs() < EPSILON && (r.w - 1.0).abs() < EPSILON
+    }
+
+    // ============================================================================
+    // Operations
+    // ============================================================================
+
+    pub fn transpose(&self) -> Self {
+        Self::from_cols(std::array::from_fn(|c| std::array::from_fn(|r| self.cols[r][c])))
+    }
+
+    /// Determinant of the upper-left 3x3 block.
+    #[inline]
+    pub fn determinant3(&self) -> f32 {
+        self.col3(0).dot(self.col3(1).cross(self.col3(2)))
+    }
+
+    pub fn determinant(&self) -> f32 {
+        self.eliminate().1
+    }
+
+    /// General inverse, or None if the matrix is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        let (inverse, det) = self.eliminate();
+        (det.abs() > EPSILON * EPSILON).then_some(inverse)
+    }
+
+    /// Gauss-Jordan elimination with partial pivoting on the rows.
+    /// Returns (inverse, determinant); the inverse is meaningless if det is zero.
+    fn eliminate(&self) -> (Self, f32) {
+        let mut a: [[f32; 4]; 4] = std::array::from_fn(|r| std::array::from_fn(|c| self.cols[c][r]));
+        let mut inv: [[f32; 4]; 4] = std::array::from_fn(|r| std::array::from_fn(|c| if r == c { 1.0 } else { 0.0 }));
+        let mut det = 1.0;
+        for col in 0..4 {
+            let pivot = (col..4).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs())).unwrap_or(col);
+            if a[pivot][col] == 0.0 {
+                return (Self::IDENTITY, 0.0);
+            }
+            if pivot != col {
+                a.swap(pivot, col);
+                inv.swap(pivot, col);
+                det = -det;
+            }
+            let p = a[col][col];
+            det *= p;
+            for c in 0..4 {
+                a[col][c] /= p;
+                inv[col][c] /= p;
+            }
+            for r in 0..4 {
+                if r != col {
+                    let f = a[r][col];
+                    for c in 0..4 {
+                        a[r][c] -= f * a[col][c];
+                        inv[r][c] -= f * inv[col][c];
+                    }
+                }
+            }
+        }
+        (Self::from_rows(inv), det)
+    }
+
+    /// Transforms a point (w = 1), dividing by w for projective matrices.
+    pub fn transform_point(&self, p: Vec3) -> Vec3 {
+        let v = *self * p.to_point();
+        if self.is_affine() {
+            v.truncate()
+        } else {
+            v.truncate_with_perspective()
+        }
+    }
+
+    /// Transforms a direction (w = 0); translation is ignored.
+    #[inline]
+    pub fn transform_vector(&self, v: Vec3) -> Vec3 {
+        (*self * v.to_direction()).truncate()
+    }
+
+    // ============================================================================
+    // Decomposition
+    // ============================================================================
+
+    /// Splits an affine matrix into (translation, rotation, scale) and reports any
+    /// shear found while orthogonalizing the basis (Gram-Schmidt, X axis first).
+    /// Negative determinants are folded into a negative X scale.
+    pub fn decompose_with_shear(&self) -> Result<(Vec3, Quat, Vec3, Shear), DecomposeError> {
+        if !self.is_affine() {
+            return Err(DecomposeError::Projective);
+        }
+        let translation = self.translation();
+        let (mut c0, mut c1, mut c2) = (self.col3(0), self.col3(1), self.col3(2));
+
+        let mut sx = c0.length();
+        if sx < EPSILON {
+            return Err(DecomposeError::Singular);
+        }
+        c0 /= sx;
+
+        let mut xy = c0.dot(c1);
+        c1 -= c0 * xy;
+        let sy = c1.length();
+        if sy < EPSILON {
+            return Err(DecomposeError::Singular);
+        }
+        c1 /= sy;
+        xy /= sy;
+
+        let mut xz = c0.dot(c2);
+        c2 -= c0 * xz;
+        let mut yz = c1.dot(c2);
+        c2 -= c1 * yz;
+        let sz = c2.length();
+        if sz < EPSILON {
+            return Err(DecomposeError::Singular);
+        }
+        c2 /= sz;
+        xz /= sz;
+        yz /= sz;
+
+        // Mirrored basis: flip X so the remaining rotation is proper
+        if c0.dot(c1.cross(c2)) < 0.0 {
+            sx = -sx;
+            c0 = -c0;
+        }
+
+        let rotation = Quat::from_rotation_axes(c0, c1, c2);
+        Ok((translation, rotation, Vec3::new(sx, sy, sz), Shear { xy, xz, yz }))
+    }
+
+    /// Like [`decompose`](Self::decompose) but fails if the matrix is projective,
+    /// singular, or sheared beyond [`SHEAR_TOLERANCE`].
+    pub fn try_decompose(&self) -> Result<(Vec3, Quat, Vec3), DecomposeError> {
+        let (t, r, s, shear) = self.decompose_with_shear()?;
+        if shear.max_abs() > SHEAR_TOLERANCE {
+            return Err(DecomposeError::Sheared(shear));
+        }
+        Ok((t, r, s))
+    }
+
+    /// Splits the matrix into (translation, rotation, scale). Shear is discarded and
+    /// degenerate matrices yield identity rotation and zero scale; use
+    /// [`try_decompose`](Self::try_decompose) to detect either case.
+    pub fn decompose(&self) -> (Vec3, Quat, Vec3) {
+        match self.decompose_with_shear() {
+            Ok((t, r, s, _)) => (t, r, s),
+            Err(_) => (self.translation(), Quat::identity(), Vec3::zero()),
+        }
+    }
+}
+
+impl Default for Mat4 {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mul for Mat4 {
+    type Output = Self;
+    fn mul(self, o: Self) -> Self {
+        Self::from_cols(std::array::from_fn(|c| {
+            std::array::from_fn(|r| (0..4).map(|k| self.cols[k][r] * o.cols[c][k]).sum())
+        }))
+    }
+}
+
+impl Mul<Vec4> for Mat4 {
+    type Output = Vec4;
+    fn mul(self, v: Vec4) -> Vec4 {
+        let row = |r: usize| self.cols[0][r] * v.x + self.cols[1][r] * v.y + self.cols[2][r] * v.z + self.cols[3][r] * v.w;
+        Vec4::new(row(0), row(1), row(2), row(3))
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/mat4_tests.rs"]
+mod tests;
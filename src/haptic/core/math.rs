@@ -0,0 +1,73 @@
+//! Scalar math helpers shared by the spatial and haptic modules.
+//!
+//! Vec3 exposes component-wise counterparts of these functions as methods.
+
+// ============================================================================
+// Hermite Step Functions
+// ============================================================================
+
+/// Maps `x` to [0, 1] with a cubic Hermite curve between `edge0` and `edge1`.
+/// Has zero slope at both edges; degenerates to a hard step when the edges coincide.
+#[inline]
+pub fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = step_t(edge0, edge1, x);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Quintic variant of `smoothstep` (Perlin) with zero first and second derivatives at the edges.
+/// Preferred for force ramps, where a curvature jump at the edge is felt as a bump.
+#[inline]
+pub fn smootherstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = step_t(edge0, edge1, x);
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+/// Normalized, clamped position of `x` between the edges.
+#[inline]
+fn step_t(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let span = edge1 - edge0;
+    if span == 0.0 {
+        return if x < edge0 { 0.0 } else { 1.0 };
+    }
+    ((x - edge0) / span).clamp(0.0, 1.0)
+}
+
+// ============================================================================
+// Damped Following
+// ============================================================================
+
+/// Smallest smooth time accepted by `smooth_damp`, to keep omega finite.
+pub(crate) const MIN_SMOOTH_TIME: f32 = 1e-4;
+
+/// Critically damped approach of `current` towards `target` (Unity-style SmoothDamp).
+/// `velocity` carries state between calls; `smooth_time` is roughly the time to reach the target.
+#[inline]
+pub fn smooth_damp(current: f32, target: f32, velocity: &mut f32, smooth_time: f32, dt: f32) -> f32 {
+    if dt <= 0.0 {
+        return current;
+    }
+    let (omega, decay) = smooth_damp_coefficients(smooth_time, dt);
+    let change = current - target;
+    let temp = (*velocity + omega * change) * dt;
+    *velocity = (*velocity - omega * temp) * decay;
+    let mut output = target + (change + temp) * decay;
+
+    // Never overshoot the target
+    if (target - current > 0.0) == (output > target) {
+        output = target;
+        *velocity = 0.0;
+    }
+    output
+}
+
+/// Returns (omega, exp(-omega * dt)) using the Padé-style approximation of the exponential.
+#[inline]
+pub(crate) fn smooth_damp_coefficients(smooth_time: f32, dt: f32) -> (f32, f32) {
+    let omega = 2.0 / smooth_time.max(MIN_SMOOTH_TIME);
+    let x = omega * dt;
+    (omega, 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x))
+}
+
+#[cfg(test)]
+#[path = "tests/math_tests.rs"]
+mod tests;
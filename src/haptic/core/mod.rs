@@ -1,5 +1,35 @@
 // src/haptic/core/mod.rs
+pub mod angle;
+pub mod clock;
+pub mod config;
+pub mod constants;
+pub mod mat4;
+pub mod math;
+pub mod noise;
+pub mod quat;
+pub mod spline;
+pub mod spring;
+pub mod tangent;
+pub mod transform;
+pub mod units;
+pub mod vec2;
 pub mod vec3;
+pub use angle::{Deg, Rad};
+pub use clock::{ClockOffset, OffsetEstimator, SessionClock, SyncSample};
+pub use config::{Config, ConfigError, ConfigValue, Layer, SubscriptionId};
+pub use mat4::{DecomposeError, DepthRange, Mat4, Shear};
+pub use math::{smooth_damp, smootherstep, smoothstep};
+pub use noise::{Fbm, Perlin};
+pub use quat::{Quat, SquadPath};
+pub use spline::CatmullRom;
+pub use spring::{SpringConfig, SpringF32, SpringVec3};
+pub use tangent::{compute_tangent_space, triangle_tangent_frame, TangentFrame};
+pub use transform::Transform;
+pub use units::{
+    Hertz, Meters, Meters3, MetersPerSecond, MetersPerSecond3, Millimeters, Millimeters3, Milliseconds,
+    NewtonSecondsPerMeter, Newtons, Newtons3, NewtonsPerMeter, NewtonsPerSecond, Seconds,
+};
+pub use vec2::Vec2;
 pub use vec3::{Vec3, Vec4, EPSILON, SPATIAL_EPSILON};
 
 // Your application code
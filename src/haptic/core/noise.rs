@@ -0,0 +1,214 @@
+//! Coherent 3D gradient noise for procedural haptic textures.
+//!
+//! Improved Perlin noise (Perlin 2002) with analytic derivatives: the same sample
+//! gives the height used for surface displacement and the gradient used for lateral
+//! texture forces, without finite-difference sampling in the servo loop.
+
+use super::vec3::Vec3;
+
+// ============================================================================
+// Perlin Noise
+// ============================================================================
+
+/// Gradient directions: the 12 cube edge midpoints, padded to 16 for cheap hashing.
+const GRADIENTS: [Vec3; 16] = [
+    Vec3::new(1.0, 1.0, 0.0),
+    Vec3::new(-1.0, 1.0, 0.0),
+    Vec3::new(1.0, -1.0, 0.0),
+    Vec3::new(-1.0, -1.0, 0.0),
+    Vec3::new(1.0, 0.0, 1.0),
+    Vec3::new(-1.0, 0.0, 1.0),
+    Vec3::new(1.0, 0.0, -1.0),
+    Vec3::new(-1.0, 0.0, -1.0),
+    Vec3::new(0.0, 1.0, 1.0),
+    Vec3::new(0.0, -1.0, 1.0),
+    Vec3::new(0.0, 1.0, -1.0),
+    Vec3::new(0.0, -1.0, -1.0),
+    Vec3::new(1.0, 1.0, 0.0),
+    Vec3::new(-1.0, 1.0, 0.0),
+    Vec3::new(0.0, -1.0, 1.0),
+    Vec3::new(0.0, -1.0, -1.0),
+];
+
+/// Seeded 3D Perlin noise. Values lie approximately in [-1, 1] and are zero at
+/// integer lattice points.
+#[derive(Clone)]
+pub struct Perlin {
+    perm: [u8; 512],
+}
+
+impl Perlin {
+    /// Creates a noise field whose permutation table is shuffled from `seed`.
+    pub fn new(seed: u64) -> Self {
+        let mut table: [u8; 256] = std::array::from_fn(|i| i as u8);
+        let mut state = seed;
+        for i in (1..256).rev() {
+            let j = (splitmix64(&mut state) % (i as u64 + 1)) as usize;
+            table.swap(i, j);
+        }
+        let mut perm = [0; 512];
+        for (i, p) in perm.iter_mut().enumerate() {
+            *p = table[i & 255];
+        }
+        Self { perm }
+    }
+
+    #[inline]
+    fn gradient_at(&self, x: usize, y: usize, z: usize) -> Vec3 {
+        let h = self.perm[self.perm[self.perm[x] as usize + y] as usize + z];
+        GRADIENTS[(h & 15) as usize]
+    }
+
+    /// Noise value at `p`.
+    #[inline]
+    pub fn sample(&self, p: Vec3) -> f32 {
+        self.sample_with_gradient(p).0
+    }
+
+    /// Spatial gradient of the noise at `p`.
+    #[inline]
+    pub fn gradient(&self, p: Vec3) -> Vec3 {
+        self.sample_with_gradient(p).1
+    }
+
+    /// Noise value and its analytic gradient at `p`.
+    pub fn sample_with_gradient(&self, p: Vec3) -> (f32, Vec3) {
+        let (xf, yf, zf) = (p.x.floor(), p.y.floor(), p.z.floor());
+        let (xi, yi, zi) = ((xf as i32 & 255) as usize, (yf as i32 & 255) as usize, (zf as i32 & 255) as usize);
+        let f = Vec3::new(p.x - xf, p.y - yf, p.z - zf);
+
+        let corner = |dx: usize, dy: usize, dz: usize| {
+            let g = self.gradient_at(xi + dx, yi + dy, zi + dz);
+            (g, g.dot(f - Vec3::new(dx as f32, dy as f32, dz as f32)))
+        };
+        let (ga, a) = corner(0, 0, 0);
+        let (gb, b) = corner(1, 0, 0);
+        let (gc, c) = corner(0, 1, 0);
+        let (gd, d) = corner(1, 1, 0);
+        let (ge, e) = corner(0, 0, 1);
+        let (gf, ff) = corner(1, 0, 1);
+        let (gg, g) = corner(0, 1, 1);
+        let (gh, h) = corner(1, 1, 1);
+
+        let (u, v, w) = (fade(f.x), fade(f.y), fade(f.z));
+        let (du, dv, dw) = (fade_derivative(f.x), fade_derivative(f.y), fade_derivative(f.z));
+
+        // Trilinear interpolation expanded into polynomial coefficients
+        let k1 = b - a;
+        let k2 = c - a;
+        let k3 = e - a;
+        let k4 = a - b - c + d;
+        let k5 = a - c - e + g;
+        let k6 = a - b - e + ff;
+        let k7 = -a + b + c - d + e - ff - g + h;
+        let value = a + k1 * u + k2 * v + k3 * w + k4 * u * v + k5 * v * w + k6 * w * u + k7 * u * v * w;
+
+        // Interpolated corner gradients plus the derivative of the weights
+        let gradient = ga
+            + (gb - ga) * u
+            + (gc - ga) * v
+            + (ge - ga) * w
+            + (ga - gb - gc + gd) * (u * v)
+            + (ga - gc - ge + gg) * (v * w)
+            + (ga - gb - ge + gf) * (w * u)
+            + (-ga + gb + gc - gd + ge - gf - gg + gh) * (u * v * w)
+            + Vec3::new(
+                du * (k1 + k4 * v + k6 * w + k7 * v * w),
+                dv * (k2 + k5 * w + k4 * u + k7 * w * u),
+                dw * (k3 + k6 * u + k5 * v + k7 * u * v),
+            );
+
+        (value, gradient)
+    }
+}
+
+impl Default for Perlin {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl std::fmt::Debug for Perlin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Perlin").finish_non_exhaustive()
+    }
+}
+
+/// Quintic fade curve 6t⁵ − 15t⁴ + 10t³ (C2-continuous at lattice boundaries).
+#[inline]
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+#[inline]
+fn fade_derivative(t: f32) -> f32 {
+    30.0 * t * t * (t * (t - 2.0) + 1.0)
+}
+
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+// ============================================================================
+// Fractal Brownian Motion
+// ============================================================================
+
+/// Sum of noise octaves at increasing frequency and decreasing amplitude.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fbm {
+    pub octaves: u32,
+    /// Frequency of the first octave, in cycles per unit length.
+    pub frequency: f32,
+    /// Frequency multiplier between octaves.
+    pub lacunarity: f32,
+    /// Amplitude multiplier between octaves (persistence).
+    pub gain: f32,
+}
+
+impl Fbm {
+    pub const fn new(octaves: u32, frequency: f32) -> Self {
+        Self { octaves, frequency, lacunarity: 2.0, gain: 0.5 }
+    }
+
+    /// Fractal noise at `p`, normalized to approximately [-1, 1].
+    #[inline]
+    pub fn sample(&self, noise: &Perlin, p: Vec3) -> f32 {
+        self.sample_with_gradient(noise, p).0
+    }
+
+    /// Fractal noise and its gradient with respect to `p`.
+    pub fn sample_with_gradient(&self, noise: &Perlin, p: Vec3) -> (f32, Vec3) {
+        let mut value = 0.0;
+        let mut gradient = Vec3::zero();
+        let mut amplitude = 1.0;
+        let mut frequency = self.frequency;
+        let mut total = 0.0;
+        for _ in 0..self.octaves {
+            let (v, g) = noise.sample_with_gradient(p * frequency);
+            value += v * amplitude;
+            gradient += g * (amplitude * frequency);
+            total += amplitude;
+            amplitude *= self.gain;
+            frequency *= self.lacunarity;
+        }
+        if total > 0.0 {
+            (value / total, gradient / total)
+        } else {
+            (0.0, Vec3::zero())
+        }
+    }
+}
+
+impl Default for Fbm {
+    fn default() -> Self {
+        Self::new(4, 1.0)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/noise_tests.rs"]
+mod tests;
@@ -0,0 +1,372 @@
+//! Unit quaternions for 3D orientation of panels, tools, and cameras.
+//!
+//! Includes spherical cubic (squad) interpolation so keyframed orientation paths
+//! have continuous angular velocity across keys.
+
+use super::angle::Rad;
+use super::vec3::{Vec3, EPSILON};
+use std::ops::{Mul, MulAssign, Neg};
+
+/// Quaternion with vector part (x, y, z) and scalar part w.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quat {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl Quat {
+    // ============================================================================
+    // Constructors
+    // ============================================================================
+
+    #[inline]
+    pub const fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Self { x, y, z, w }
+    }
+
+    /// The identity rotation.
+    #[inline]
+    pub const fn identity() -> Self {
+        Self::new(0.0, 0.0, 0.0, 1.0)
+    }
+
+    /// Rotation of `angle` around `axis` (normalized internally).
+    #[inline]
+    pub fn from_axis_angle(axis: Vec3, angle: impl Into<Rad>) -> Self {
+        let axis = axis.normalize();
+        let (s, c) = (angle.into() * 0.5).sin_cos();
+        Self::new(axis.x * s, axis.y * s, axis.z * s, c)
+    }
+
+    /// Shortest rotation taking direction `from` onto direction `to`.
+    pub fn from_rotation_arc(from: Vec3, to: Vec3) -> Self {
+        let (from, to) = (from.normalize(), to.normalize());
+        let d = from.dot(to);
+        if d < -1.0 + EPSILON {
+            // Opposite directions: rotate 180° around any perpendicular axis
+            let axis = if from.x.abs() < 0.9 { from.cross(Vec3::unit_x()) } else { from.cross(Vec3::unit_y()) };
+            return Self::from_axis_angle(axis, Rad::HALF_TURN);
+        }
+        let c = from.cross(to);
+        Self::new(c.x, c.y, c.z, 1.0 + d).normalize()
+    }
+
+    /// Builds a quaternion from a vector part and scalar part.
+    #[inline]
+    pub fn from_parts(v: Vec3, w: f32) -> Self {
+        Self::new(v.x, v.y, v.z, w)
+    }
+
+    /// Rotation whose matrix has the given orthonormal columns (images of the X, Y
+    /// and Z axes). Uses Shepperd's method for numerical stability.
+    pub fn from_rotation_axes(x: Vec3, y: Vec3, z: Vec3) -> Self {
+        let trace = x.x + y.y + z.z;
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Self::new((y.z - z.y) / s, (z.x - x.z) / s, (x.y - y.x) / s, 0.25 * s)
+        } else if x.x > y.y && x.x > z.z {
+            let s = (1.0 + x.x - y.y - z.z).sqrt() * 2.0;
+            Self::new(0.25 * s, (y.x + x.y) / s, (z.x + x.z) / s, (y.z - z.y) / s)
+        } else if y.y > z.z {
+            let s = (1.0 + y.y - x.x - z.z).sqrt() * 2.0;
+            Self::new((y.x + x.y) / s, 0.25 * s, (z.y + y.z) / s, (z.x - x.z) / s)
+        } else {
+            let s = (1.0 + z.z - x.x - y.y).sqrt() * 2.0;
+            Self::new((z.x + x.z) / s, (z.y + y.z) / s, 0.25 * s, (x.y - y.x) / s)
+        };
+        q.normalize()
+    }
+
+    // ============================================================================
+    // Basic Operations
+    // ============================================================================
+
+    /// Vector part (x, y, z).
+    #[inline]
+    pub fn vector(self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    #[inline]
+    pub fn dot(self, other: Self) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    #[inline]
+    pub fn length_squared(self) -> f32 {
+        self.dot(self)
+    }
+
+    #[inline]
+    pub fn length(self) -> f32 {
+        self.length_squared().sqrt()
+    }
+
+    /// Normalizes to unit length; returns identity for degenerate input.
+    #[inline]
+    pub fn normalize(self) -> Self {
+        let len = self.length();
+        if len < EPSILON {
+            Self::identity()
+        } else {
+            self.scale(1.0 / len)
+        }
+    }
+
+    #[inline]
+    pub fn conjugate(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /// Multiplicative inverse (equals the conjugate for unit quaternions).
+    #[inline]
+    pub fn inverse(self) -> Self {
+        let len_sq = self.length_squared();
+        if len_sq < EPSILON * EPSILON {
+            Self::identity()
+        } else {
+            self.conjugate().scale(1.0 / len_sq)
+        }
+    }
+
+    #[inline]
+    fn scale(self, s: f32) -> Self {
+        Self::new(self.x * s, self.y * s, self.z * s, self.w * s)
+    }
+
+    #[inline]
+    fn add(self, o: Self) -> Self {
+        Self::new(self.x + o.x, self.y + o.y, self.z + o.z, self.w + o.w)
+    }
+
+    /// Rotates a vector by this (unit) quaternion.
+    #[inline]
+    pub fn rotate(self, v: Vec3) -> Vec3 {
+        let u = self.vector();
+        let t = u.cross(v) * 2.0;
+        v + t * self.w + u.cross(t)
+    }
+
+    /// Returns (axis, angle). The axis is +X for the identity.
+    pub fn to_axis_angle(self) -> (Vec3, Rad) {
+        let q = if self.w < 0.0 { -self.normalize() } else { self.normalize() };
+        let s = q.vector().length();
+        if s < EPSILON {
+            (Vec3::unit_x(), Rad::ZERO)
+        } else {
+            (q.vector() / s, Rad::atan2(s, q.w) * 2.0)
+        }
+    }
+
+    /// Angle of the rotation between two orientations.
+    #[inline]
+    pub fn angle_to(self, other: Self) -> Rad {
+        Rad::acos(self.dot(other).abs()) * 2.0
+    }
+
+    /// Checks if the quaternion has unit length within epsilon tolerance.
+    #[inline]
+    pub fn is_normalized(self) -> bool {
+        (self.length_squared() - 1.0).abs() < EPSILON * 10.0
+    }
+
+    // ============================================================================
+    // Exponential Map
+    // ============================================================================
+
+    /// Logarithm of a unit quaternion: a pure quaternion (w = 0) holding axis * half-angle.
+    pub fn ln(self) -> Self {
+        let v = self.vector();
+        let s = v.length();
+        if s < EPSILON {
+            return Self::from_parts(v, 0.0);
+        }
+        let half_angle = s.atan2(self.w);
+        Self::from_parts(v * (half_angle / s), 0.0)
+    }
+
+    /// Exponential of a pure quaternion, inverse of `ln`.
+    pub fn exp(self) -> Self {
+        let v = self.vector();
+        let half_angle = v.length();
+        if half_angle < EPSILON {
+            return Self::from_parts(v, 1.0).normalize();
+        }
+        let (s, c) = half_angle.sin_cos();
+        Self::from_parts(v * (s / half_angle), c)
+    }
+
+    // ============================================================================
+    // Interpolation
+    // ============================================================================
+
+    /// Normalized linear interpolation along the shortest arc.
+    #[inline]
+    pub fn nlerp(self, other: Self, t: f32) -> Self {
+        let other = if self.dot(other) < 0.0 { -other } else { other };
+        self.scale(1.0 - t).add(other.scale(t)).normalize()
+    }
+
+    /// Spherical linear interpolation along the shortest arc.
+    pub fn slerp(self, other: Self, t: f32) -> Self {
+        let other = if self.dot(other) < 0.0 { -other } else { other };
+        self.slerp_long(other, t)
+    }
+
+    /// Spherical linear interpolation without the shortest-arc flip (required by squad).
+    fn slerp_long(self, other: Self, t: f32) -> Self {
+        let d = self.dot(other).clamp(-1.0, 1.0);
+        if d.abs() > 1.0 - EPSILON {
+            return self.scale(1.0 - t).add(other.scale(t)).normalize();
+        }
+        let angle = d.acos();
+        let sin_angle = angle.sin();
+        let a = ((1.0 - t) * angle).sin() / sin_angle;
+        let b = (t * angle).sin() / sin_angle;
+        self.scale(a).add(other.scale(b))
+    }
+
+    /// Spherical cubic interpolation between `q1` and `q2` with inner control points `s1`, `s2`.
+    /// Control points come from `squad_control_point`.
+    pub fn squad(q1: Self, s1: Self, s2: Self, q2: Self, t: f32) -> Self {
+        let outer = q1.slerp_long(q2, t);
+        let inner = s1.slerp_long(s2, t);
+        outer.slerp_long(inner, 2.0 * t * (1.0 - t)).normalize()
+    }
+
+    /// Inner control point for key `current` given its neighbours, chosen so that the
+    /// angular velocity is continuous through `current`.
+    pub fn squad_control_point(prev: Self, current: Self, next: Self) -> Self {
+        let inv = current.inverse();
+        let to_next = (inv * next).ln();
+        let to_prev = (inv * prev).ln();
+        current * to_next.add(to_prev).scale(-0.25).exp()
+    }
+}
+
+impl Default for Quat {
+    #[inline]
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Hamilton product: `a * b` applies `b` first, then `a`.
+impl Mul for Quat {
+    type Output = Self;
+    #[inline]
+    fn mul(self, o: Self) -> Self {
+        Self::new(
+            self.w * o.x + self.x * o.w + self.y * o.z - self.z * o.y,
+            self.w * o.y - self.x * o.z + self.y * o.w + self.z * o.x,
+            self.w * o.z + self.x * o.y - self.y * o.x + self.z * o.w,
+            self.w * o.w - self.x * o.x - self.y * o.y - self.z * o.z,
+        )
+    }
+}
+
+impl MulAssign for Quat {
+    #[inline]
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl Mul<Vec3> for Quat {
+    type Output = Vec3;
+    #[inline]
+    fn mul(self, v: Vec3) -> Vec3 {
+        self.rotate(v)
+    }
+}
+
+impl Neg for Quat {
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z, -self.w)
+    }
+}
+
+// ============================================================================
+// Keyframed Orientation Paths
+// ============================================================================
+
+/// Orientation keyframes interpolated with squad.
+#[derive(Debug, Clone, Default)]
+pub struct SquadPath {
+    times: Vec<f32>,
+    keys: Vec<Quat>,
+    controls: Vec<Quat>,
+}
+
+impl SquadPath {
+    /// Builds a path from (time, orientation) keys. Keys are sorted by time and flipped
+    /// into a common hemisphere so the path never takes the long way around.
+    pub fn new(mut keys: Vec<(f32, Quat)>) -> Self {
+        keys.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let times: Vec<f32> = keys.iter().map(|k| k.0).collect();
+        let mut quats: Vec<Quat> = Vec::with_capacity(keys.len());
+        for (_, q) in keys {
+            let q = q.normalize();
+            match quats.last() {
+                Some(prev) if prev.dot(q) < 0.0 => quats.push(-q),
+                _ => quats.push(q),
+            }
+        }
+
+        let n = quats.len();
+        let controls = (0..n)
+            .map(|i| {
+                let prev = quats[i.saturating_sub(1)];
+                let next = quats[(i + 1).min(n - 1)];
+                Quat::squad_control_point(prev, quats[i], next)
+            })
+            .collect();
+
+        Self { times, keys: quats, controls }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Time span covered by the keys.
+    pub fn duration(&self) -> f32 {
+        match (self.times.first(), self.times.last()) {
+            (Some(a), Some(b)) => b - a,
+            _ => 0.0,
+        }
+    }
+
+    /// Orientation at time `t`, clamped to the first/last key outside the key range.
+    pub fn sample(&self, t: f32) -> Quat {
+        let n = self.keys.len();
+        if n == 0 {
+            return Quat::identity();
+        }
+        if n == 1 || t <= self.times[0] {
+            return self.keys[0];
+        }
+        if t >= self.times[n - 1] {
+            return self.keys[n - 1];
+        }
+        let i = self.times.partition_point(|&k| k <= t) - 1;
+        let span = self.times[i + 1] - self.times[i];
+        let u = if span > 0.0 { (t - self.times[i]) / span } else { 0.0 };
+        Quat::squad(self.keys[i], self.controls[i], self.controls[i + 1], self.keys[i + 1], u)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/quat_tests.rs"]
+mod tests;
@@ -0,0 +1,150 @@
+//! Catmull-Rom splines through authored control points.
+//!
+//! Uses the centripetal parameterization (alpha = 0.5), which avoids cusps and
+//! self-intersections when control points are unevenly spaced. The curve passes
+//! through every point; the first and last segments use mirrored phantom points.
+
+use super::vec3::{Vec3, EPSILON};
+
+/// Spline through a list of points. Segment `i` runs from `points[i]` to `points[i + 1]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatmullRom {
+    points: Vec<Vec3>,
+    /// 0 = uniform, 0.5 = centripetal, 1 = chordal.
+    pub alpha: f32,
+}
+
+impl CatmullRom {
+    /// Centripetal spline through `points`.
+    pub fn new(points: Vec<Vec3>) -> Self {
+        Self { points, alpha: 0.5 }
+    }
+
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    #[inline]
+    pub fn points(&self) -> &[Vec3] {
+        &self.points
+    }
+
+    #[inline]
+    pub fn segment_count(&self) -> usize {
+        self.points.len().saturating_sub(1)
+    }
+
+    /// Control points p0..p3 for a segment, extrapolating at the ends.
+    fn controls(&self, segment: usize) -> [Vec3; 4] {
+        let p = &self.points;
+        let p1 = p[segment];
+        let p2 = p[segment + 1];
+        let p0 = if segment > 0 { p[segment - 1] } else { p1 * 2.0 - p2 };
+        let p3 = p.get(segment + 2).copied().unwrap_or(p2 * 2.0 - p1);
+        [p0, p1, p2, p3]
+    }
+
+    /// Splits a global parameter `t` in `[0, segment_count]` into (segment, local u).
+    #[inline]
+    fn locate(&self, t: f32) -> (usize, f32) {
+        let n = self.segment_count();
+        let t = t.clamp(0.0, n as f32);
+        let segment = (t.floor() as usize).min(n - 1);
+        (segment, t - segment as f32)
+    }
+
+    /// Point at `u` in [0, 1] along one segment (Barry-Goldman pyramid).
+    pub fn sample_segment(&self, segment: usize, u: f32) -> Vec3 {
+        let [p0, p1, p2, p3] = self.controls(segment);
+        let knot = |a: Vec3, b: Vec3| (b - a).length().max(EPSILON).powf(self.alpha);
+        let t0 = 0.0;
+        let t1 = t0 + knot(p0, p1);
+        let t2 = t1 + knot(p1, p2);
+        let t3 = t2 + knot(p2, p3);
+        let t = t1 + (t2 - t1) * u;
+
+        let mix = |a: Vec3, b: Vec3, ta: f32, tb: f32| a * ((tb - t) / (tb - ta)) + b * ((t - ta) / (tb - ta));
+        let a1 = mix(p0, p1, t0, t1);
+        let a2 = mix(p1, p2, t1, t2);
+        let a3 = mix(p2, p3, t2, t3);
+        let b1 = mix(a1, a2, t0, t2);
+        let b2 = mix(a2, a3, t1, t3);
+        mix(b1, b2, t1, t2)
+    }
+
+    /// Point at global parameter `t` in `[0, segment_count]`.
+    pub fn sample(&self, t: f32) -> Vec3 {
+        match self.points.len() {
+            0 => Vec3::zero(),
+            1 => self.points[0],
+            _ => {
+                let (segment, u) = self.locate(t);
+                self.sample_segment(segment, u)
+            }
+        }
+    }
+
+    /// Unit tangent at global parameter `t` (central difference).
+    pub fn tangent(&self, t: f32) -> Vec3 {
+        let h = 1e-3;
+        let max = self.segment_count() as f32;
+        let (a, b) = ((t - h).max(0.0), (t + h).min(max));
+        (self.sample(b) - self.sample(a)).normalize()
+    }
+
+    /// Approximate arc length, using `samples_per_segment` chords per segment.
+    pub fn length(&self, samples_per_segment: usize) -> f32 {
+        let steps = (self.segment_count() * samples_per_segment.max(1)).max(1);
+        let max = self.segment_count() as f32;
+        let mut previous = self.sample(0.0);
+        let mut total = 0.0;
+        for i in 1..=steps {
+            let p = self.sample(max * i as f32 / steps as f32);
+            total += (p - previous).length();
+            previous = p;
+        }
+        total
+    }
+
+    /// Closest point to `p` within the parameter range `from..=to`.
+    /// Returns (parameter, point).
+    pub fn closest_point_in(&self, p: Vec3, from: f32, to: f32) -> (f32, Vec3) {
+        if self.points.len() < 2 {
+            return (0.0, self.sample(0.0));
+        }
+        let max = self.segment_count() as f32;
+        let (from, to) = (from.clamp(0.0, max), to.clamp(0.0, max));
+        let distance = |t: f32| (self.sample(t) - p).length_squared();
+
+        // Coarse scan, then golden-section refinement around the best sample
+        let steps = (((to - from) * 16.0).ceil() as usize).max(1);
+        let step = (to - from) / steps as f32;
+        let best = (0..=steps).map(|i| from + step * i as f32).min_by(|a, b| distance(*a).total_cmp(&distance(*b)));
+        let best = best.unwrap_or(from);
+
+        let (mut lo, mut hi) = ((best - step).max(from), (best + step).min(to));
+        let ratio = 0.618_034;
+        for _ in 0..24 {
+            let a = hi - (hi - lo) * ratio;
+            let b = lo + (hi - lo) * ratio;
+            if distance(a) < distance(b) {
+                hi = b;
+            } else {
+                lo = a;
+            }
+        }
+        let t = (lo + hi) * 0.5;
+        (t, self.sample(t))
+    }
+
+    /// Closest point to `p` on the whole spline.
+    #[inline]
+    pub fn closest_point(&self, p: Vec3) -> (f32, Vec3) {
+        self.closest_point_in(p, 0.0, self.segment_count() as f32)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/spline_tests.rs"]
+mod tests;
@@ -0,0 +1,187 @@
+//! Damped spring interpolators for springy UI motion and force ramping.
+//!
+//! Springs are integrated with implicit Euler, which stays stable for any stiffness
+//! and time step, so they can be driven from both the frame loop and the servo loop.
+
+use super::vec3::Vec3;
+
+// ============================================================================
+// Spring Configuration
+// ============================================================================
+
+/// Stiffness and damping of a unit-mass spring.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpringConfig {
+    /// Restoring acceleration per unit of displacement (1/s²).
+    pub stiffness: f32,
+    /// Velocity damping (1/s).
+    pub damping: f32,
+}
+
+impl SpringConfig {
+    /// Soft motion for panels and highlights.
+    pub const GENTLE: Self = Self::new(120.0, 22.0);
+    /// Responsive motion for buttons and handles.
+    pub const SNAPPY: Self = Self::new(400.0, 40.0);
+    /// Visibly bouncy motion.
+    pub const WOBBLY: Self = Self::new(180.0, 8.0);
+
+    #[inline]
+    pub const fn new(stiffness: f32, damping: f32) -> Self {
+        Self { stiffness, damping }
+    }
+
+    /// Creates a critically damped spring (fastest approach without overshoot).
+    #[inline]
+    pub fn critical(stiffness: f32) -> Self {
+        Self::new(stiffness, 2.0 * stiffness.max(0.0).sqrt())
+    }
+
+    /// Damping ratio ζ: < 1 oscillates, 1 is critical, > 1 is overdamped.
+    #[inline]
+    pub fn damping_ratio(&self) -> f32 {
+        let critical = 2.0 * self.stiffness.max(0.0).sqrt();
+        if critical > 0.0 { self.damping / critical } else { f32::INFINITY }
+    }
+
+    /// Implicit Euler velocity update: returns (velocity gain, displacement gain).
+    #[inline]
+    fn coefficients(&self, dt: f32) -> (f32, f32) {
+        let denom = 1.0 + dt * self.damping + dt * dt * self.stiffness;
+        (1.0 / denom, -dt * self.stiffness / denom)
+    }
+}
+
+impl Default for SpringConfig {
+    fn default() -> Self {
+        Self::SNAPPY
+    }
+}
+
+/// Default displacement and speed below which a spring counts as settled.
+pub const DEFAULT_SETTLE_THRESHOLD: f32 = 1e-3;
+
+// ============================================================================
+// Scalar Spring
+// ============================================================================
+
+/// Spring-driven scalar that follows a target value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpringF32 {
+    pub value: f32,
+    pub velocity: f32,
+    pub target: f32,
+    pub config: SpringConfig,
+    /// Displacement and speed below which the spring snaps to its target.
+    pub settle_threshold: f32,
+}
+
+impl SpringF32 {
+    /// Creates a spring at rest at `value`.
+    pub fn new(value: f32, config: SpringConfig) -> Self {
+        Self { value, velocity: 0.0, target: value, config, settle_threshold: DEFAULT_SETTLE_THRESHOLD }
+    }
+
+    #[inline]
+    pub fn set_target(&mut self, target: f32) {
+        self.target = target;
+    }
+
+    /// Jumps to `value` and stops all motion.
+    pub fn snap_to(&mut self, value: f32) {
+        self.value = value;
+        self.target = value;
+        self.velocity = 0.0;
+    }
+
+    /// Advances the spring by `dt` seconds and returns the new value.
+    pub fn update(&mut self, dt: f32) -> f32 {
+        if dt <= 0.0 || self.is_settled() {
+            return self.value;
+        }
+        let (v_gain, x_gain) = self.config.coefficients(dt);
+        self.velocity = self.velocity * v_gain + (self.value - self.target) * x_gain;
+        self.value += self.velocity * dt;
+
+        if (self.value - self.target).abs() < self.settle_threshold
+            && self.velocity.abs() < self.settle_threshold
+        {
+            self.snap_to(self.target);
+        }
+        self.value
+    }
+
+    /// True once the spring rests exactly on its target.
+    #[inline]
+    pub fn is_settled(&self) -> bool {
+        self.value == self.target && self.velocity == 0.0
+    }
+}
+
+// ============================================================================
+// Vector Spring
+// ============================================================================
+
+/// Spring-driven Vec3 that follows a target position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpringVec3 {
+    pub value: Vec3,
+    pub velocity: Vec3,
+    pub target: Vec3,
+    pub config: SpringConfig,
+    /// Displacement and speed below which the spring snaps to its target.
+    pub settle_threshold: f32,
+}
+
+impl SpringVec3 {
+    /// Creates a spring at rest at `value`.
+    pub fn new(value: Vec3, config: SpringConfig) -> Self {
+        Self {
+            value,
+            velocity: Vec3::zero(),
+            target: value,
+            config,
+            settle_threshold: DEFAULT_SETTLE_THRESHOLD,
+        }
+    }
+
+    #[inline]
+    pub fn set_target(&mut self, target: Vec3) {
+        self.target = target;
+    }
+
+    /// Jumps to `value` and stops all motion.
+    pub fn snap_to(&mut self, value: Vec3) {
+        self.value = value;
+        self.target = value;
+        self.velocity = Vec3::zero();
+    }
+
+    /// Advances the spring by `dt` seconds and returns the new value.
+    pub fn update(&mut self, dt: f32) -> Vec3 {
+        if dt <= 0.0 || self.is_settled() {
+            return self.value;
+        }
+        let (v_gain, x_gain) = self.config.coefficients(dt);
+        self.velocity = self.velocity * v_gain + (self.value - self.target) * x_gain;
+        self.value += self.velocity * dt;
+
+        let threshold_sq = self.settle_threshold * self.settle_threshold;
+        if self.value.distance_squared_to(self.target) < threshold_sq
+            && self.velocity.length_squared() < threshold_sq
+        {
+            self.snap_to(self.target);
+        }
+        self.value
+    }
+
+    /// True once the spring rests exactly on its target.
+    #[inline]
+    pub fn is_settled(&self) -> bool {
+        self.value == self.target && self.velocity == Vec3::zero()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/spring_tests.rs"]
+mod tests;
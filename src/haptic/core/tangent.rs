@@ -0,0 +1,120 @@
+//! Tangent-space (TBN) frames from triangle positions and texture coordinates.
+//!
+//! The tangent follows increasing U and the bitangent increasing V across the
+//! surface, so a texture-space offset maps to a world-space direction. The
+//! renderer uses the frames for normal mapping and the force shader uses them to
+//! orient haptic texture gradients on the surface.
+
+use super::vec2::Vec2;
+use super::vec3::{Vec3, EPSILON};
+
+/// Orthonormal tangent/bitangent/normal frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TangentFrame {
+    pub tangent: Vec3,
+    pub bitangent: Vec3,
+    pub normal: Vec3,
+    /// +1 for right-handed UV mapping, -1 where the UVs are mirrored.
+    pub handedness: f32,
+}
+
+impl TangentFrame {
+    /// Frame with the given normal and an arbitrary tangent.
+    pub fn from_normal(normal: Vec3) -> Self {
+        let normal = normal.normalize();
+        let (tangent, bitangent) = normal.any_orthonormal_pair();
+        Self { tangent, bitangent, normal, handedness: 1.0 }
+    }
+
+    /// Converts a tangent-space vector (x along U, y along V, z along the normal) to world space.
+    #[inline]
+    pub fn to_world(&self, v: Vec3) -> Vec3 {
+        self.tangent * v.x + self.bitangent * v.y + self.normal * v.z
+    }
+
+    /// Converts a world-space vector to tangent space.
+    #[inline]
+    pub fn to_tangent(&self, v: Vec3) -> Vec3 {
+        Vec3::new(v.dot(self.tangent), v.dot(self.bitangent), v.dot(self.normal))
+    }
+
+    /// Tangent as a four-component value (xyz, handedness) for vertex buffers.
+    #[inline]
+    pub fn packed_tangent(&self) -> [f32; 4] {
+        [self.tangent.x, self.tangent.y, self.tangent.z, self.handedness]
+    }
+}
+
+/// Unnormalized tangent and bitangent of one triangle, or None for degenerate UVs.
+fn triangle_axes(p: [Vec3; 3], uv: [Vec2; 3]) -> Option<(Vec3, Vec3)> {
+    let (e1, e2) = (p[1] - p[0], p[2] - p[0]);
+    let (d1, d2) = (uv[1] - uv[0], uv[2] - uv[0]);
+    let det = d1.perp_dot(d2);
+    if det.abs() < EPSILON {
+        return None;
+    }
+    let r = 1.0 / det;
+    Some(((e1 * d2.y - e2 * d1.y) * r, (e2 * d1.x - e1 * d2.x) * r))
+}
+
+/// Orthonormalizes `tangent` against `normal` (Gram-Schmidt) and derives the bitangent.
+fn orthonormal_frame(normal: Vec3, tangent: Vec3, bitangent: Vec3) -> TangentFrame {
+    let normal = normal.normalize();
+    let Some(t) = tangent.reject_from(normal).try_normalize() else {
+        return TangentFrame::from_normal(normal);
+    };
+    let handedness = if normal.cross(t).dot(bitangent) < 0.0 { -1.0 } else { 1.0 };
+    TangentFrame { tangent: t, bitangent: normal.cross(t) * handedness, normal, handedness }
+}
+
+/// Tangent frame of a single flat triangle. Falls back to an arbitrary tangent if
+/// the UVs are degenerate; returns None if the triangle itself has no area.
+pub fn triangle_tangent_frame(p: [Vec3; 3], uv: [Vec2; 3]) -> Option<TangentFrame> {
+    let normal = (p[1] - p[0]).cross(p[2] - p[0]).try_normalize()?;
+    Some(match triangle_axes(p, uv) {
+        Some((t, b)) => orthonormal_frame(normal, t, b),
+        None => TangentFrame::from_normal(normal),
+    })
+}
+
+/// Per-vertex tangent frames for an indexed triangle list.
+///
+/// Triangle tangents are accumulated weighted by area, then orthonormalized against
+/// the vertex normal. `normals` may be empty, in which case area-weighted face
+/// normals are used. Indices that are out of range are skipped.
+pub fn compute_tangent_space(positions: &[Vec3], normals: &[Vec3], uvs: &[Vec2], indices: &[u32]) -> Vec<TangentFrame> {
+    let n = positions.len().min(uvs.len());
+    let mut tangents = vec![Vec3::zero(); n];
+    let mut bitangents = vec![Vec3::zero(); n];
+    let mut face_normals = vec![Vec3::zero(); n];
+
+    for tri in indices.chunks_exact(3) {
+        let [a, b, c] = [tri[0] as usize, tri[1] as usize, tri[2] as usize];
+        if a >= n || b >= n || c >= n {
+            continue;
+        }
+        let p = [positions[a], positions[b], positions[c]];
+        // Cross product length is twice the area, which gives the area weighting
+        let face_normal = (p[1] - p[0]).cross(p[2] - p[0]);
+        let area = face_normal.length();
+        let axes = triangle_axes(p, [uvs[a], uvs[b], uvs[c]]);
+        for i in [a, b, c] {
+            face_normals[i] += face_normal;
+            if let Some((t, bt)) = axes {
+                tangents[i] += t.normalize() * area;
+                bitangents[i] += bt.normalize() * area;
+            }
+        }
+    }
+
+    (0..n)
+        .map(|i| {
+            let normal = normals.get(i).copied().unwrap_or(face_normals[i]);
+            orthonormal_frame(normal, tangents[i], bitangents[i])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "tests/tangent_tests.rs"]
+mod tests;
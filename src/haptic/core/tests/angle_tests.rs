@@ -0,0 +1,46 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+#[test]
+fn test_conversions() {
+    let rad: Rad = Deg(180.0).into();
+    assert!((rad.0 - PI).abs() < TEST_EPSILON);
+    assert!((Rad::QUARTER_TURN.to_deg().0 - 90.0).abs() < TEST_EPSILON);
+    assert!((Deg(30.0).sin() - 0.5).abs() < TEST_EPSILON);
+    assert!((Rad::acos(2.0).0).abs() < TEST_EPSILON);
+    assert_eq!(Rad::atan2(1.0, 0.0), Rad::QUARTER_TURN);
+}
+
+#[test]
+fn test_wrap_and_normalize() {
+    assert!(Rad(3.0 * PI).wrap().approx_eq(Rad::HALF_TURN, TEST_EPSILON));
+    assert!((Rad(-0.5 * PI).normalize().0 - 1.5 * PI).abs() < TEST_EPSILON);
+    assert!(Rad(-1e-9).normalize().0 < TAU);
+    assert_eq!(Deg(-190.0).wrap(), Deg(170.0));
+    assert_eq!(Deg(540.0).wrap(), Deg(180.0));
+    assert_eq!(Deg(-90.0).normalize(), Deg(270.0));
+    assert_eq!(Deg(350.0).delta_to(Deg(10.0)), Deg(20.0));
+}
+
+#[test]
+fn test_shortest_arc_interpolation() {
+    let a = Deg(170.0).to_rad();
+    let b = Deg(-170.0).to_rad();
+    let mid = a.lerp_shortest(b, 0.5);
+    assert!(mid.approx_eq(Rad::HALF_TURN, 1e-4));
+    assert!(Rad(0.1).approx_eq(Rad(0.1 + TAU), 1e-4));
+}
+
+#[test]
+fn test_arithmetic() {
+    let mut a = Deg(45.0);
+    a += Deg(45.0);
+    assert_eq!(a, Deg::QUARTER_TURN);
+    assert_eq!(a * 2.0, Deg::HALF_TURN);
+    assert_eq!(2.0 * a - Deg(90.0), Deg(90.0));
+    assert_eq!(Deg::FULL_TURN / Deg::QUARTER_TURN, 4.0);
+    assert_eq!(-Rad(1.0), Rad(-1.0));
+    assert!(Rad(0.5) < Rad(1.0));
+    assert_eq!(format!("{}", Deg(90.0)), "90.0°");
+}
@@ -0,0 +1,69 @@
+use super::*;
+
+/// Simulated remote clock: remote = local * (1 + drift) + offset.
+fn remote_time(local_us: u64, offset_us: i64, drift: f64) -> u64 {
+    (local_us as f64 * (1.0 + drift) + offset_us as f64) as u64
+}
+
+#[test]
+fn test_session_clock_is_monotonic() {
+    let clock = SessionClock::new();
+    let a = clock.now_us();
+    let b = clock.now_us();
+    assert!(b >= a);
+    assert_eq!(clock.timestamp_of(clock.instant_of(1234)), 1234);
+}
+
+#[test]
+fn test_symmetric_exchange_recovers_offset() {
+    // 200 us each way, 50 us remote processing, remote 10 ms ahead
+    let sample = SyncSample::from_exchange(1_000, 11_200, 11_250, 1_450);
+    assert_eq!(sample.offset_us, 10_000);
+    assert_eq!(sample.delay_us, 400);
+}
+
+#[test]
+fn test_filter_prefers_lowest_delay_sample() {
+    let mut estimator = OffsetEstimator::default();
+    let offset = 5_000;
+    // Congested exchange with asymmetric delay skews the raw offset
+    let t0 = 10_000;
+    estimator.add_exchange(t0, remote_time(t0 + 3_000, offset, 0.0), remote_time(t0 + 3_000, offset, 0.0), t0 + 3_200);
+    // Clean exchange
+    let t0 = 20_000;
+    estimator.add_exchange(t0, remote_time(t0 + 100, offset, 0.0), remote_time(t0 + 100, offset, 0.0), t0 + 200);
+
+    let estimate = estimator.estimate().unwrap();
+    assert_eq!(estimate.offset_us, offset);
+    assert_eq!(estimate.uncertainty_us, 100);
+}
+
+#[test]
+fn test_drift_compensation_keeps_sub_millisecond_alignment() {
+    let mut estimator = OffsetEstimator::default();
+    let (offset, drift) = (-25_000, 100e-6);
+    for i in 0..20u64 {
+        let t0 = 1_000_000 + i * 500_000;
+        let remote = remote_time(t0 + 150, offset, drift);
+        estimator.add_exchange(t0, remote, remote, t0 + 300);
+    }
+
+    let estimate = estimator.estimate().unwrap();
+    assert!((estimate.drift - drift).abs() < 10e-6, "drift = {}", estimate.drift);
+
+    // One minute later the mapping should still be within a millisecond
+    let local = 60_000_000;
+    let mapped = estimate.to_local(remote_time(local, offset, drift));
+    assert!((mapped as i64 - local as i64).abs() < 1_000, "mapped = {}", mapped);
+    assert!((estimate.to_remote(local) as i64 - remote_time(local, offset, drift) as i64).abs() < 1_000);
+}
+
+#[test]
+fn test_window_capacity() {
+    let mut estimator = OffsetEstimator::new(4);
+    assert!(estimator.estimate().is_none());
+    for i in 0..10 {
+        estimator.add_exchange(i * 10, i * 10, i * 10, i * 10 + 2);
+    }
+    assert_eq!(estimator.sample_count(), 4);
+}
@@ -0,0 +1,135 @@
+use super::*;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn test_defaults_are_registered() {
+    let config = Config::new();
+    assert_eq!(config.get_f64(constants::SERVO_RATE_HZ), Some(constants::DEFAULT_SERVO_RATE_HZ));
+    assert_eq!(config.get_f64(constants::SAFETY_MAX_FORCE_N), Some(constants::DEFAULT_SAFETY_MAX_FORCE_N));
+    assert_eq!(config.source(constants::SERVO_RATE_HZ), Some(Layer::Default));
+}
+
+#[test]
+fn test_layer_priority() {
+    let mut config = Config::new();
+    config.load_str("[servo]\nrate_hz = 2000 # overclocked\n\n[safety]\nmax_force_n = 2.5\n").unwrap();
+    config.load_env("HAPTICUI_", vec![("HAPTICUI_SERVO__RATE_HZ".to_string(), "4000".to_string())]).unwrap();
+    assert_eq!(config.get_f64("servo.rate_hz"), Some(4000.0));
+    assert_eq!(config.source("servo.rate_hz"), Some(Layer::Env));
+
+    let rest = config.load_args(["app", "--servo.rate_hz=500", "--safety.max_force_n", "1.5", "--verbose"]).unwrap();
+    assert_eq!(rest, vec!["app".to_string()]);
+    assert_eq!(config.get_f64("servo.rate_hz"), Some(500.0));
+    assert_eq!(config.source("safety.max_force_n"), Some(Layer::Cli));
+    assert_eq!(config.get_bool("verbose"), Some(true));
+    config.unset(Layer::Cli, "safety.max_force_n");
+    assert_eq!(config.get_f64("safety.max_force_n"), Some(2.5));
+
+    config.unset(Layer::Cli, "servo.rate_hz");
+    assert_eq!(config.get_f64("servo.rate_hz"), Some(4000.0));
+}
+
+#[test]
+fn test_type_checking_against_defaults() {
+    let mut config = Config::new();
+    let err = config.load_str("[servo]\nrate_hz = fast\n").unwrap_err();
+    assert!(matches!(err, ConfigError::Type { ref key, .. } if key == "servo.rate_hz"));
+    assert!(matches!(config.load_str("garbage"), Err(ConfigError::Syntax { line: 1, .. })));
+
+    config.load_str("ui.theme = \"dark\"\nui.columns = 4").unwrap();
+    assert_eq!(config.get_str("ui.theme"), Some("dark"));
+    // A `#` inside quotes is part of the value
+    config.load_str("ui.accent = \"#ff0000\" # red").unwrap();
+    assert_eq!(config.get_str("ui.accent"), Some("#ff0000"));
+    assert_eq!(config.get_i64("ui.columns"), Some(4));
+}
+
+#[test]
+fn test_change_notifications() {
+    let mut config = Config::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = seen.clone();
+    let id = config.subscribe("safety.", move |key, value| {
+        log.lock().unwrap().push((key.to_string(), value.cloned()));
+    });
+
+    let version = config.version();
+    config.set(Layer::Cli, "safety.max_force_n", ConfigValue::Float(1.0));
+    // Same effective value: no notification
+    config.set(Layer::Env, "safety.max_force_n", ConfigValue::Float(1.0));
+    config.set(Layer::Cli, "servo.rate_hz", ConfigValue::Float(800.0));
+    assert_eq!(*seen.lock().unwrap(), vec![("safety.max_force_n".to_string(), Some(ConfigValue::Float(1.0)))]);
+    assert_eq!(config.version(), version + 2);
+
+    assert!(config.unsubscribe(id));
+    config.set(Layer::Cli, "safety.max_force_n", ConfigValue::Float(2.0));
+    assert_eq!(seen.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn test_subscriptions_match_whole_segments() {
+    let mut config = Config::empty();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    for prefix in ["servo.rate_hz", "servo"] {
+        let log = seen.clone();
+        config.subscribe(prefix, move |key, _| log.lock().unwrap().push(format!("{} <- {}", prefix, key)));
+    }
+
+    config.set(Layer::Cli, "servo.rate_hz", ConfigValue::Float(500.0));
+    config.set(Layer::Cli, "servo.rate_hz_max", ConfigValue::Float(2000.0));
+    config.set(Layer::Cli, "servo_debug.trace", ConfigValue::Bool(true));
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec!["servo.rate_hz <- servo.rate_hz", "servo <- servo.rate_hz", "servo <- servo.rate_hz_max"]
+    );
+}
+
+#[test]
+fn test_removal_is_notified() {
+    let mut config = Config::empty();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = seen.clone();
+    config.subscribe("ui.", move |key, value| log.lock().unwrap().push((key.to_string(), value.cloned())));
+
+    config.set(Layer::File, "ui.columns", ConfigValue::Int(4));
+    config.set(Layer::Cli, "ui.columns", ConfigValue::Int(6));
+    config.unset(Layer::Cli, "ui.columns");
+    config.unset(Layer::File, "ui.columns");
+    let expected = [Some(4), Some(6), Some(4), None].map(|v| ("ui.columns".to_string(), v.map(ConfigValue::Int)));
+    assert_eq!(*seen.lock().unwrap(), expected.to_vec());
+}
+
+#[test]
+fn test_non_finite_tunings_are_ignored() {
+    use crate::net::DeadbandConfig;
+    use crate::render::{ContactModel, LodConfig, LoopConfig};
+    use crate::safety::SafetyCaps;
+
+    for raw in ["inf", "-inf", "nan"] {
+        let mut config = Config::new();
+        let keys: Vec<String> = config.keys().into_iter().map(String::from).collect();
+        for key in &keys {
+            // Integer tunings refuse the text outright
+            if config.set_raw(Layer::Cli, key, raw).is_ok() {
+                assert_eq!(config.get_tuning(key), None, "{} = {}", key, raw);
+            }
+        }
+        assert_eq!(SafetyCaps::from_config(&config), SafetyCaps::DEFAULT, "{}", raw);
+        assert_eq!(LoopConfig::from_config(&config), LoopConfig::default(), "{}", raw);
+        assert_eq!(LodConfig::from_config(&config), LodConfig::default(), "{}", raw);
+        assert_eq!(DeadbandConfig::position_from_config(&config), DeadbandConfig::POSITION, "{}", raw);
+        assert_eq!(DeadbandConfig::force_from_config(&config), DeadbandConfig::FORCE, "{}", raw);
+        let contact = ContactModel::new(crate::core::Newtons(5.0));
+        assert_eq!(contact.with_config(&config), contact, "{}", raw);
+
+        let mut caps = SafetyCaps::DEFAULT;
+        for key in &keys {
+            assert!(!caps.apply(key, config.get(key)), "{} = {}", key, raw);
+        }
+    }
+
+    // Finite rates beyond the servo's range are clamped rather than taken as is
+    let mut caps = SafetyCaps::DEFAULT;
+    assert!(caps.apply(constants::SERVO_RATE_HZ, Some(&ConfigValue::Float(1e12))));
+    assert_eq!(caps.servo_rate.value(), constants::MAX_SERVO_RATE_HZ as f32);
+}
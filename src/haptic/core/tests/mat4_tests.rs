@@ -0,0 +1,154 @@
+use super::*;
+use crate::core::angle::{Deg, Rad};
+
+const TEST_EPSILON: f32 = 1e-4;
+
+fn assert_mat_eq(a: &Mat4, b: &Mat4) {
+    for (x, y) in a.to_cols_array().iter().zip(b.to_cols_array().iter()) {
+        assert!((x - y).abs() < TEST_EPSILON, "{:?} != {:?}", a, b);
+    }
+}
+
+fn assert_same_rotation(a: Quat, b: Quat) {
+    assert!(a.dot(b).abs() > 1.0 - TEST_EPSILON, "{:?} != {:?}", a, b);
+}
+
+fn sample_trs() -> (Vec3, Quat, Vec3) {
+    let rotation = Quat::from_axis_angle(Vec3::new(0.3, -1.0, 0.5).normalize(), Rad(1.1));
+    (Vec3::new(1.0, -2.0, 3.5), rotation, Vec3::new(2.0, 0.5, 1.5))
+}
+
+#[test]
+fn test_layout() {
+    let m = Mat4::from_rows([
+        [1.0, 2.0, 3.0, 4.0],
+        [5.0, 6.0, 7.0, 8.0],
+        [9.0, 10.0, 11.0, 12.0],
+        [13.0, 14.0, 15.0, 16.0],
+    ]);
+    assert_eq!(m.cols[0], [1.0, 5.0, 9.0, 13.0]);
+    assert_eq!(m.row(1), Vec4::new(5.0, 6.0, 7.0, 8.0));
+    assert_eq!(Mat4::from_cols_array(&m.to_cols_array()), m);
+    assert_eq!(m.transpose().cols[0], [1.0, 2.0, 3.0, 4.0]);
+
+    let t = Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0));
+    assert_eq!(t.translation(), Vec3::new(1.0, 2.0, 3.0));
+    assert_eq!(t.transform_point(Vec3::zero()), Vec3::new(1.0, 2.0, 3.0));
+    assert_eq!(t.transform_vector(Vec3::unit_x()), Vec3::unit_x());
+}
+
+#[test]
+fn test_multiply_applies_right_first() {
+    let t = Mat4::from_translation(Vec3::new(1.0, 0.0, 0.0));
+    let s = Mat4::from_scale(Vec3::new(2.0, 2.0, 2.0));
+    assert_eq!((t * s).transform_point(Vec3::unit_x()), Vec3::new(3.0, 0.0, 0.0));
+    assert_eq!((s * t).transform_point(Vec3::unit_x()), Vec3::new(4.0, 0.0, 0.0));
+}
+
+#[test]
+fn test_determinant_and_inverse() {
+    let (t, r, s) = sample_trs();
+    let m = Mat4::from_trs(t, r, s);
+    assert!((m.determinant() - 1.5).abs() < TEST_EPSILON);
+    assert!((m.determinant3() - 1.5).abs() < TEST_EPSILON);
+    let inv = m.inverse().expect("invertible");
+    assert_mat_eq(&(m * inv), &Mat4::IDENTITY);
+    assert_mat_eq(&(inv * m), &Mat4::IDENTITY);
+
+    let projective = Mat4::from_rows([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.5, 1.0],
+    ]);
+    assert_mat_eq(&(projective * projective.inverse().unwrap()), &Mat4::IDENTITY);
+    assert!(Mat4::from_scale(Vec3::new(1.0, 0.0, 1.0)).inverse().is_none());
+}
+
+#[test]
+fn test_decompose_round_trip() {
+    let (t, r, s) = sample_trs();
+    let (t2, r2, s2) = Mat4::from_trs(t, r, s).try_decompose().expect("pure TRS");
+    assert!((t2 - t).length() < TEST_EPSILON);
+    assert!((s2 - s).length() < TEST_EPSILON);
+    assert_same_rotation(r2, r);
+    assert_eq!(Mat4::IDENTITY.decompose(), (Vec3::zero(), Quat::identity(), Vec3::one()));
+}
+
+#[test]
+fn test_decompose_mirror() {
+    let (t, r, _) = sample_trs();
+    let m = Mat4::from_trs(t, r, Vec3::new(1.0, -2.0, 1.0));
+    let (t2, r2, s2) = m.try_decompose().expect("mirror is not shear");
+    assert!(s2.x * s2.y * s2.z < 0.0);
+    assert_mat_eq(&Mat4::from_trs(t2, r2, s2), &m);
+}
+
+#[test]
+fn test_decompose_detects_shear() {
+    let shear = Mat4::from_rows([
+        [1.0, 0.5, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+    match shear.try_decompose() {
+        Err(DecomposeError::Sheared(s)) => {
+            assert!((s.xy - 0.5).abs() < TEST_EPSILON);
+            assert!(s.xz.abs() < TEST_EPSILON && s.yz.abs() < TEST_EPSILON);
+        }
+        other => panic!("expected shear, got {:?}", other),
+    }
+    // The lossy path still returns the closest TRS
+    let (_, r, s) = shear.decompose();
+    assert_same_rotation(r, Quat::identity());
+    assert!((s - Vec3::one()).length() < TEST_EPSILON);
+}
+
+#[test]
+fn test_decompose_errors() {
+    let mut projective = Mat4::IDENTITY;
+    projective.cols[2][3] = -1.0;
+    assert_eq!(projective.try_decompose(), Err(DecomposeError::Projective));
+    assert_eq!(Mat4::from_scale(Vec3::new(1.0, 1.0, 0.0)).try_decompose(), Err(DecomposeError::Singular));
+    assert_eq!(DecomposeError::Singular.to_string(), "matrix is singular");
+}
+
+#[test]
+fn test_perspective_depth_ranges() {
+    let zo = Mat4::perspective(Deg(90.0), 2.0, 0.1, 100.0);
+    let no = Mat4::perspective_in(Deg(90.0), 2.0, 0.1, 100.0, DepthRange::NegOneToOne);
+    for (m, depth) in [(zo, DepthRange::ZeroToOne), (no, DepthRange::NegOneToOne)] {
+        assert!((m.transform_point(Vec3::new(0.0, 0.0, -0.1)).z - depth.near()).abs() < TEST_EPSILON);
+        assert!((m.transform_point(Vec3::new(0.0, 0.0, -100.0)).z - depth.far()).abs() < 1e-3);
+    }
+    // 90° vertical fov: the frustum edge at depth 1 is at y = 1, x = aspect
+    let edge = zo.transform_point(Vec3::new(2.0, 1.0, -1.0));
+    assert!((edge.x - 1.0).abs() < TEST_EPSILON && (edge.y - 1.0).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_orthographic() {
+    let m = Mat4::orthographic(-2.0, 2.0, -1.0, 1.0, 0.5, 10.0);
+    assert!((m.transform_point(Vec3::new(2.0, -1.0, -0.5)) - Vec3::new(1.0, -1.0, 0.0)).length() < TEST_EPSILON);
+    assert!((m.transform_point(Vec3::new(0.0, 0.0, -10.0)).z - 1.0).abs() < TEST_EPSILON);
+    let gl = Mat4::orthographic_in(-2.0, 2.0, -1.0, 1.0, 0.5, 10.0, DepthRange::NegOneToOne);
+    assert!((gl.transform_point(Vec3::new(0.0, 0.0, -0.5)).z + 1.0).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_look_at_and_picking_ray() {
+    let eye = Vec3::new(0.0, 2.0, 5.0);
+    let view = Mat4::look_at(eye, Vec3::new(0.0, 2.0, 0.0), Vec3::unit_y());
+    assert!(view.transform_point(eye).length() < TEST_EPSILON);
+    assert!((view.transform_point(Vec3::new(1.0, 3.0, 0.0)) - Vec3::new(1.0, 1.0, -5.0)).length() < TEST_EPSILON);
+
+    let view_proj = Mat4::perspective(Deg(60.0), 1.5, 0.1, 50.0) * view;
+    let (origin, direction) = view_proj.ndc_ray(0.0, 0.0, DepthRange::ZeroToOne).unwrap();
+    assert!((origin - Vec3::new(0.0, 2.0, 4.9)).length() < 1e-3);
+    assert!((direction + Vec3::unit_z()).length() < 1e-3);
+
+    let target = Vec3::new(0.5, 2.5, -3.0);
+    let ndc = view_proj.transform_point(target);
+    assert!((view_proj.unproject(ndc).unwrap() - target).length() < 1e-2);
+}
@@ -0,0 +1,52 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-6;
+
+#[test]
+fn test_smoothstep_edges_and_midpoint() {
+    assert_eq!(smoothstep(0.0, 1.0, -1.0), 0.0);
+    assert_eq!(smoothstep(0.0, 1.0, 2.0), 1.0);
+    assert!((smoothstep(0.0, 1.0, 0.5) - 0.5).abs() < TEST_EPSILON);
+    assert!((smoothstep(2.0, 4.0, 3.0) - 0.5).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_smootherstep_edges_and_midpoint() {
+    assert_eq!(smootherstep(0.0, 1.0, 0.0), 0.0);
+    assert_eq!(smootherstep(0.0, 1.0, 1.0), 1.0);
+    assert!((smootherstep(0.0, 1.0, 0.5) - 0.5).abs() < TEST_EPSILON);
+    // Flatter than smoothstep near the edge
+    assert!(smootherstep(0.0, 1.0, 0.1) < smoothstep(0.0, 1.0, 0.1));
+}
+
+#[test]
+fn test_reversed_and_degenerate_edges() {
+    // Reversed edges produce a falling curve, useful for fade-outs
+    assert_eq!(smoothstep(1.0, 0.0, 0.0), 1.0);
+    assert_eq!(smoothstep(1.0, 0.0, 1.0), 0.0);
+
+    assert_eq!(smoothstep(1.0, 1.0, 0.5), 0.0);
+    assert_eq!(smoothstep(1.0, 1.0, 1.0), 1.0);
+}
+
+#[test]
+fn test_smooth_damp_is_frame_rate_independent() {
+    let run = |dt: f32, steps: usize| {
+        let (mut x, mut v) = (0.0, 0.0);
+        for _ in 0..steps {
+            x = smooth_damp(x, 10.0, &mut v, 0.2, dt);
+        }
+        x
+    };
+    let at_60 = run(1.0 / 60.0, 15);
+    let at_240 = run(1.0 / 240.0, 60);
+    assert!((at_60 - at_240).abs() < 0.1, "{} vs {}", at_60, at_240);
+    assert!(at_60 > 5.0 && at_60 < 10.0);
+}
+
+#[test]
+fn test_smooth_damp_zero_dt_is_noop() {
+    let mut v = 1.0;
+    assert_eq!(smooth_damp(3.0, 5.0, &mut v, 0.1, 0.0), 3.0);
+    assert_eq!(v, 1.0);
+}
@@ -0,0 +1,60 @@
+use super::*;
+
+fn numeric_gradient(f: impl Fn(Vec3) -> f32, p: Vec3) -> Vec3 {
+    let h = 1e-3;
+    Vec3::new(
+        (f(p + Vec3::new(h, 0.0, 0.0)) - f(p - Vec3::new(h, 0.0, 0.0))) / (2.0 * h),
+        (f(p + Vec3::new(0.0, h, 0.0)) - f(p - Vec3::new(0.0, h, 0.0))) / (2.0 * h),
+        (f(p + Vec3::new(0.0, 0.0, h)) - f(p - Vec3::new(0.0, 0.0, h))) / (2.0 * h),
+    )
+}
+
+fn sample_points() -> impl Iterator<Item = Vec3> {
+    (0..50).map(|i| {
+        let t = i as f32;
+        Vec3::new(t * 0.37 - 3.1, t * 0.71 + 0.2, -t * 0.53 + 7.9)
+    })
+}
+
+#[test]
+fn test_deterministic_and_seeded() {
+    let a = Perlin::new(42);
+    let b = Perlin::new(42);
+    let c = Perlin::new(7);
+    let p = Vec3::new(1.3, -2.7, 0.45);
+    assert_eq!(a.sample(p), b.sample(p));
+    assert!(sample_points().any(|p| a.sample(p) != c.sample(p)));
+}
+
+#[test]
+fn test_zero_at_lattice_points_and_bounded() {
+    let noise = Perlin::new(1);
+    assert_eq!(noise.sample(Vec3::new(3.0, -4.0, 12.0)), 0.0);
+    for p in sample_points() {
+        assert!(noise.sample(p).abs() <= 1.0 + 1e-4);
+    }
+    assert!(sample_points().any(|p| noise.sample(p).abs() > 0.05));
+}
+
+#[test]
+fn test_gradient_matches_finite_differences() {
+    let noise = Perlin::new(3);
+    for p in sample_points() {
+        let (_, analytic) = noise.sample_with_gradient(p);
+        let numeric = numeric_gradient(|q| noise.sample(q), p);
+        assert!((analytic - numeric).length() < 1e-2, "{} vs {} at {}", analytic, numeric, p);
+    }
+}
+
+#[test]
+fn test_fbm_gradient_and_range() {
+    let noise = Perlin::new(9);
+    let fbm = Fbm::new(5, 2.5);
+    for p in sample_points() {
+        let (value, analytic) = fbm.sample_with_gradient(&noise, p * 0.1);
+        assert!(value.abs() <= 1.0);
+        let numeric = numeric_gradient(|q| fbm.sample(&noise, q), p * 0.1);
+        assert!((analytic - numeric).length() < 0.1 * (1.0 + numeric.length()), "{} vs {}", analytic, numeric);
+    }
+    assert_eq!(Fbm::new(0, 1.0).sample(&noise, Vec3::one()), 0.0);
+}
@@ -0,0 +1,93 @@
+use super::*;
+use crate::core::angle::{Deg, Rad};
+use std::f32::consts::{FRAC_PI_2, PI};
+
+const TEST_EPSILON: f32 = 1e-4;
+
+fn assert_vec3_eq(a: Vec3, b: Vec3) {
+    assert!((a - b).length() < TEST_EPSILON, "Expected {:?}, got {:?}", b, a);
+}
+
+fn assert_same_rotation(a: Quat, b: Quat) {
+    assert!(a.angle_to(b).0 < 1e-3, "Expected {:?}, got {:?}", b, a);
+}
+
+#[test]
+fn test_rotation_and_composition() {
+    let q = Quat::from_axis_angle(Vec3::unit_z(), Rad(FRAC_PI_2));
+    assert_vec3_eq(q * Vec3::unit_x(), Vec3::unit_y());
+    assert_vec3_eq((q * q) * Vec3::unit_x(), -Vec3::unit_x());
+    assert_vec3_eq(q.inverse() * (q * Vec3::new(1.0, 2.0, 3.0)), Vec3::new(1.0, 2.0, 3.0));
+}
+
+#[test]
+fn test_axis_angle_round_trip() {
+    let axis = Vec3::new(1.0, 2.0, -1.0).normalize();
+    let (a, angle) = Quat::from_axis_angle(axis, Rad(1.2)).to_axis_angle();
+    assert_vec3_eq(a, axis);
+    assert!((angle.0 - 1.2).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_axis_angle_accepts_degrees() {
+    assert_same_rotation(Quat::from_axis_angle(Vec3::unit_x(), Deg(90.0)), Quat::from_axis_angle(Vec3::unit_x(), Rad(FRAC_PI_2)));
+}
+
+#[test]
+fn test_rotation_arc() {
+    let q = Quat::from_rotation_arc(Vec3::unit_x(), Vec3::unit_z());
+    assert_vec3_eq(q * Vec3::unit_x(), Vec3::unit_z());
+    let flip = Quat::from_rotation_arc(Vec3::unit_y(), -Vec3::unit_y());
+    assert_vec3_eq(flip * Vec3::unit_y(), -Vec3::unit_y());
+}
+
+#[test]
+fn test_ln_exp_inverse() {
+    let q = Quat::from_axis_angle(Vec3::new(0.3, -1.0, 0.2), Rad(2.0));
+    assert_same_rotation(q.ln().exp(), q);
+}
+
+#[test]
+fn test_slerp_takes_shortest_arc() {
+    let a = Quat::identity();
+    let b = -Quat::from_axis_angle(Vec3::unit_y(), Rad(FRAC_PI_2));
+    let mid = a.slerp(b, 0.5);
+    assert!((mid.angle_to(a).0 - PI / 4.0).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_squad_hits_keys() {
+    let q0 = Quat::identity();
+    let q1 = Quat::from_axis_angle(Vec3::unit_y(), Rad(1.0));
+    let q2 = Quat::from_axis_angle(Vec3::unit_x(), Rad(1.0));
+    let s1 = Quat::squad_control_point(q0, q1, q2);
+    let s2 = Quat::squad_control_point(q1, q2, q2);
+    assert_same_rotation(Quat::squad(q1, s1, s2, q2, 0.0), q1);
+    assert_same_rotation(Quat::squad(q1, s1, s2, q2, 1.0), q2);
+    assert!(Quat::squad(q1, s1, s2, q2, 0.37).is_normalized());
+}
+
+#[test]
+fn test_squad_path_has_continuous_angular_velocity() {
+    let path = SquadPath::new(vec![
+        (0.0, Quat::identity()),
+        (1.0, Quat::from_axis_angle(Vec3::unit_y(), Rad(1.0))),
+        (2.0, Quat::from_axis_angle(Vec3::new(1.0, 1.0, 0.0), Rad(2.0))),
+        (3.0, Quat::from_axis_angle(Vec3::unit_z(), Rad(0.5))),
+    ]);
+    assert_eq!(path.len(), 4);
+    assert_eq!(path.duration(), 3.0);
+    assert_same_rotation(path.sample(1.0), Quat::from_axis_angle(Vec3::unit_y(), Rad(1.0)));
+
+    // Angular speed just before and just after an interior key should match
+    let h = 1e-2;
+    let before = path.sample(1.0 - h).angle_to(path.sample(1.0)).0 / h;
+    let after = path.sample(1.0).angle_to(path.sample(1.0 + h)).0 / h;
+    assert!((before - after).abs() < 0.05 * before.max(after), "{} vs {}", before, after);
+
+    // Linear slerp would show a visible jump at the same key
+    let keys = [Quat::identity(), Quat::from_axis_angle(Vec3::unit_y(), Rad(1.0)), Quat::from_axis_angle(Vec3::new(1.0, 1.0, 0.0), Rad(2.0))];
+    let lin_before = keys[0].slerp(keys[1], 1.0 - h).angle_to(keys[1]).0 / h;
+    let lin_after = keys[1].angle_to(keys[1].slerp(keys[2], h)).0 / h;
+    assert!((lin_before - lin_after).abs() > (before - after).abs());
+}
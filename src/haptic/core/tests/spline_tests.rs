@@ -0,0 +1,52 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+fn zigzag() -> CatmullRom {
+    CatmullRom::new(vec![
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(1.0, 1.0, 0.0),
+        Vec3::new(2.0, 0.0, 0.0),
+        Vec3::new(5.0, 1.0, 0.0),
+    ])
+}
+
+#[test]
+fn test_passes_through_control_points() {
+    let spline = zigzag();
+    assert_eq!(spline.segment_count(), 3);
+    for (i, p) in spline.points().iter().enumerate() {
+        assert!((spline.sample(i as f32) - *p).length() < TEST_EPSILON);
+    }
+    assert!((spline.sample(-1.0) - spline.points()[0]).length() < TEST_EPSILON);
+    assert!((spline.sample(10.0) - spline.points()[3]).length() < TEST_EPSILON);
+}
+
+#[test]
+fn test_straight_line_and_length() {
+    let line = CatmullRom::new(vec![Vec3::zero(), Vec3::unit_x(), Vec3::unit_x() * 2.0]);
+    assert!((line.sample(0.5) - Vec3::new(0.5, 0.0, 0.0)).length() < TEST_EPSILON);
+    assert!((line.length(8) - 2.0).abs() < 1e-3);
+    assert!((line.tangent(1.0) - Vec3::unit_x()).length() < TEST_EPSILON);
+    assert!(zigzag().length(16) > 5.0);
+}
+
+#[test]
+fn test_closest_point() {
+    let spline = zigzag();
+    let target = spline.sample(1.3);
+    let (t, p) = spline.closest_point(target + Vec3::new(0.0, 0.0, 0.2));
+    assert!((t - 1.3).abs() < 1e-2, "t = {}", t);
+    assert!((p - target).length() < 1e-3);
+
+    let (t, _) = spline.closest_point_in(Vec3::new(5.0, 1.0, 0.0), 0.0, 1.0);
+    assert!((t - 1.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_degenerate_splines() {
+    assert_eq!(CatmullRom::new(vec![]).sample(0.5), Vec3::zero());
+    assert_eq!(CatmullRom::new(vec![Vec3::one()]).sample(0.5), Vec3::one());
+    let repeated = CatmullRom::new(vec![Vec3::one(), Vec3::one(), Vec3::unit_x()]);
+    assert!(repeated.sample(0.5).is_finite());
+}
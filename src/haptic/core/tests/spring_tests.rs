@@ -0,0 +1,58 @@
+use super::*;
+
+#[test]
+fn test_spring_settles_on_target() {
+    let mut spring = SpringF32::new(0.0, SpringConfig::SNAPPY);
+    spring.set_target(1.0);
+    assert!(!spring.is_settled());
+
+    for _ in 0..600 {
+        spring.update(1.0 / 60.0);
+    }
+    assert!(spring.is_settled());
+    assert_eq!(spring.value, 1.0);
+}
+
+#[test]
+fn test_critical_spring_does_not_overshoot() {
+    let config = SpringConfig::critical(300.0);
+    assert!((config.damping_ratio() - 1.0).abs() < 1e-5);
+
+    let mut spring = SpringF32::new(0.0, config);
+    spring.set_target(1.0);
+    for _ in 0..300 {
+        assert!(spring.update(1.0 / 120.0) <= 1.0);
+    }
+}
+
+#[test]
+fn test_underdamped_spring_overshoots() {
+    let mut spring = SpringF32::new(0.0, SpringConfig::WOBBLY);
+    spring.set_target(1.0);
+    let peak = (0..240).map(|_| spring.update(1.0 / 240.0)).fold(0.0, f32::max);
+    assert!(peak > 1.0);
+}
+
+#[test]
+fn test_stiff_spring_is_stable_at_large_dt() {
+    let mut spring = SpringVec3::new(Vec3::zero(), SpringConfig::new(1e6, 10.0));
+    spring.set_target(Vec3::new(1.0, 2.0, 3.0));
+    for _ in 0..100 {
+        let v = spring.update(0.1);
+        assert!(v.is_finite() && v.length() < 10.0);
+    }
+}
+
+#[test]
+fn test_vec_spring_snap_and_settle() {
+    let mut spring = SpringVec3::new(Vec3::one(), SpringConfig::GENTLE);
+    assert!(spring.is_settled());
+    assert_eq!(spring.update(0.016), Vec3::one());
+
+    spring.set_target(Vec3::zero());
+    for _ in 0..1000 {
+        spring.update(1.0 / 60.0);
+    }
+    assert!(spring.is_settled());
+    assert_eq!(spring.value, Vec3::zero());
+}
@@ -0,0 +1,77 @@
+use super::*;
+
+const TOL: f32 = 1e-5;
+
+fn approx(a: Vec3, b: Vec3) -> bool {
+    (a - b).length() < TOL
+}
+
+#[test]
+fn test_triangle_frame_follows_uvs() {
+    let p = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 2.0, 0.0)];
+    let uv = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0)];
+    let frame = triangle_tangent_frame(p, uv).unwrap();
+    assert!(approx(frame.tangent, Vec3::unit_x()));
+    assert!(approx(frame.bitangent, Vec3::unit_y()));
+    assert!(approx(frame.normal, Vec3::unit_z()));
+    assert_eq!(frame.handedness, 1.0);
+
+    // Rotated UVs: U runs along +y
+    let uv = [Vec2::new(0.0, 0.0), Vec2::new(0.0, -1.0), Vec2::new(1.0, 0.0)];
+    let frame = triangle_tangent_frame(p, uv).unwrap();
+    assert!(approx(frame.tangent, Vec3::unit_y()));
+    assert!(approx(frame.bitangent, -Vec3::unit_x()));
+}
+
+#[test]
+fn test_mirrored_uvs_flip_handedness() {
+    let p = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+    let uv = [Vec2::new(1.0, 0.0), Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0)];
+    let frame = triangle_tangent_frame(p, uv).unwrap();
+    assert_eq!(frame.handedness, -1.0);
+    assert!(approx(frame.tangent, -Vec3::unit_x()));
+    assert!(approx(frame.bitangent, Vec3::unit_y()));
+}
+
+#[test]
+fn test_degenerate_inputs() {
+    let p = [Vec3::zero(), Vec3::unit_x(), Vec3::unit_y()];
+    let frame = triangle_tangent_frame(p, [Vec2::zero(); 3]).unwrap();
+    assert!(frame.tangent.dot(frame.normal).abs() < TOL);
+    assert!((frame.tangent.length() - 1.0).abs() < TOL);
+
+    let collinear = [Vec3::zero(), Vec3::unit_x(), Vec3::unit_x() * 2.0];
+    assert!(triangle_tangent_frame(collinear, [Vec2::zero(); 3]).is_none());
+}
+
+#[test]
+fn test_compute_tangent_space_indexed_quad() {
+    let positions = [
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(1.0, 1.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    ];
+    let uvs = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(0.0, 1.0)];
+    let frames = compute_tangent_space(&positions, &[], &uvs, &[0, 1, 2, 0, 2, 3, 9, 9, 9]);
+    assert_eq!(frames.len(), 4);
+    for frame in &frames {
+        assert!(approx(frame.tangent, Vec3::unit_x()));
+        assert!(approx(frame.normal, Vec3::unit_z()));
+        assert_eq!(frame.packed_tangent(), [1.0, 0.0, 0.0, 1.0]);
+    }
+
+    // Supplied normals take precedence and the tangent is re-orthogonalized
+    let tilted = Vec3::new(0.0, -1.0, 1.0).normalize();
+    let frames = compute_tangent_space(&positions, &[tilted; 4], &uvs, &[0, 1, 2, 0, 2, 3]);
+    assert!(approx(frames[0].normal, tilted));
+    assert!(frames[0].tangent.dot(tilted).abs() < TOL);
+}
+
+#[test]
+fn test_frame_round_trip() {
+    let frame = TangentFrame::from_normal(Vec3::new(0.3, -0.5, 0.8));
+    let v = Vec3::new(0.2, 1.5, -0.7);
+    assert!(approx(frame.to_world(frame.to_tangent(v)), v));
+    assert!(approx(frame.tangent.cross(frame.bitangent), frame.normal));
+}
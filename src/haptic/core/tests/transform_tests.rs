@@ -0,0 +1,54 @@
+use super::*;
+use crate::core::angle::Deg;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+fn sample() -> Transform {
+    Transform::new(Vec3::new(1.0, 2.0, 3.0), Quat::from_axis_angle(Vec3::unit_y(), Deg(30.0)), Vec3::new(2.0, 2.0, 2.0))
+}
+
+#[test]
+fn test_matches_matrix() {
+    let t = sample();
+    let m = t.to_matrix();
+    let p = Vec3::new(0.5, -1.0, 2.0);
+    assert!((t.transform_point(p) - m.transform_point(p)).length() < TEST_EPSILON);
+    assert!((t.transform_vector(p) - m.transform_vector(p)).length() < TEST_EPSILON);
+}
+
+#[test]
+fn test_from_matrix() {
+    let t = sample();
+    let back = Transform::try_from(t.to_matrix()).expect("no shear");
+    assert!((back.translation - t.translation).length() < TEST_EPSILON);
+    assert!((back.scale - t.scale).length() < TEST_EPSILON);
+    assert!(back.rotation.dot(t.rotation).abs() > 1.0 - TEST_EPSILON);
+
+    let sheared = Mat4::from_rows([
+        [1.0, 0.0, 0.3, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+    assert!(matches!(Transform::try_from_matrix(&sheared), Err(DecomposeError::Sheared(_))));
+}
+
+#[test]
+fn test_compose_and_inverse() {
+    let parent = sample();
+    let child = Transform::from_translation(Vec3::new(0.0, 1.0, 0.0));
+    let p = Vec3::new(1.0, 0.0, -1.0);
+    let composed = parent * child;
+    assert!((composed.transform_point(p) - parent.transform_point(child.transform_point(p))).length() < TEST_EPSILON);
+
+    let round = parent.inverse().transform_point(parent.transform_point(p));
+    assert!((round - p).length() < TEST_EPSILON);
+    assert_eq!(Transform::default(), Transform::IDENTITY);
+}
+
+#[test]
+fn test_lerp() {
+    let a = Transform::IDENTITY;
+    let b = Transform::from_translation(Vec3::new(2.0, 0.0, 0.0));
+    assert_eq!(a.lerp(&b, 0.5).translation, Vec3::new(1.0, 0.0, 0.0));
+}
@@ -0,0 +1,50 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-6;
+
+#[test]
+fn test_explicit_conversions() {
+    let device = Millimeters(12.5);
+    let scene: Meters = device.into();
+    assert!((scene.value() - 0.0125).abs() < TEST_EPSILON);
+    assert!((Millimeters::from(Meters(0.2)).value() - 200.0).abs() < 1e-4);
+
+    let p: Meters3 = Millimeters3::new(100.0, -50.0, 0.0).into();
+    assert!((p.value() - Vec3::new(0.1, -0.05, 0.0)).length() < TEST_EPSILON);
+    assert_eq!(Seconds::from(Milliseconds(1.0)), Seconds(0.001));
+    assert_eq!(Hertz(1000.0).period(), Seconds(0.001));
+    assert_eq!(Seconds(0.5).frequency(), Hertz(2.0));
+    assert_eq!(Hertz(250.0) * Seconds(0.1), 25.0);
+}
+
+#[test]
+fn test_derived_arithmetic() {
+    let stiffness = NewtonsPerMeter(800.0);
+    let force: Newtons = stiffness * Meters(0.002);
+    assert!((force.value() - 1.6).abs() < TEST_EPSILON);
+    assert!(((force / Meters(0.002)).value() - 800.0).abs() < 1e-3);
+    assert!(((force / stiffness).value() - 0.002).abs() < TEST_EPSILON);
+
+    let damping = NewtonSecondsPerMeter(2.0);
+    assert_eq!(damping * MetersPerSecond(0.5), Newtons(1.0));
+    assert_eq!(Meters(3.0) / Seconds(2.0), MetersPerSecond(1.5));
+    assert_eq!(Newtons(3.0) / Seconds(0.01), NewtonsPerSecond(300.0));
+
+    let spring = stiffness * Meters3::new(0.0, 0.0, -0.001);
+    assert_eq!(spring, Newtons3::new(0.0, 0.0, -0.8));
+    assert!((spring.length().value() - 0.8).abs() < TEST_EPSILON);
+    assert_eq!(Newtons3::new(3.0, 4.0, 0.0).clamp_length(Newtons(1.0)), Newtons3::new(0.6, 0.8, 0.0));
+    assert_eq!(MetersPerSecond3::new(1.0, 0.0, 0.0) * Seconds(2.0), Meters3::new(2.0, 0.0, 0.0));
+}
+
+#[test]
+fn test_same_unit_ops_and_display() {
+    let mut total = Newtons(1.0) + Newtons(2.0) * 2.0;
+    total -= Newtons(0.5);
+    assert_eq!(total, Newtons(4.5));
+    assert_eq!(total / Newtons(1.5), 3.0);
+    assert_eq!((-total).abs().clamp(Newtons::ZERO, Newtons(3.0)), Newtons(3.0));
+    assert_eq!(Newtons(2.5).to_string(), "2.5 N");
+    assert_eq!(NewtonSecondsPerMeter(1.0).to_string(), "1 N·s/m");
+    assert_eq!(Millimeters3::new(1.0, 2.0, 3.0).to_string(), "(1, 2, 3) mm");
+}
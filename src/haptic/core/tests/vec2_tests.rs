@@ -0,0 +1,14 @@
+use super::*;
+
+#[test]
+fn test_vec2_basics() {
+    let a = Vec2::new(3.0, 4.0);
+    assert_eq!(a.length(), 5.0);
+    assert_eq!(a.dot(Vec2::new(1.0, 0.0)), 3.0);
+    assert_eq!(Vec2::new(1.0, 0.0).perp_dot(Vec2::new(0.0, 1.0)), 1.0);
+    assert_eq!(Vec2::new(1.0, 0.0).perp(), Vec2::new(0.0, 1.0));
+    assert!(a.normalize().approx_eq(Vec2::new(0.6, 0.8), 1e-6));
+    assert_eq!(Vec2::zero().try_normalize(), None);
+    assert_eq!(Vec2::zero().lerp(a, 0.5), Vec2::new(1.5, 2.0));
+    assert_eq!(Vec2::from([1.0, 2.0]) - Vec2::from((1.0, 1.0)), Vec2::new(0.0, 1.0));
+}
@@ -172,4 +172,69 @@ mod tests {
         assert!(_cross.length() > 0.0);
         assert!((_normalized.length() - 1.0).abs() < TEST_EPSILON);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_smoothstep_componentwise() {
+        let v = Vec3::new(-1.0, 0.5, 2.0);
+        let s = v.smoothstep(Vec3::zero(), Vec3::one());
+        assert_vec3_eq(s, Vec3::new(0.0, 0.5, 1.0));
+
+        let e = Vec3::splat(0.25).smootherstep(Vec3::zero(), Vec3::one());
+        assert!(e.x < 0.25 && e.x > 0.0);
+        assert_eq!(e.x, e.y);
+    }
+
+    #[test]
+    fn test_smooth_damp_converges_without_overshoot() {
+        let target = Vec3::new(1.0, -2.0, 0.5);
+        let mut current = Vec3::zero();
+        let mut velocity = Vec3::zero();
+
+        for _ in 0..200 {
+            current = Vec3::smooth_damp(current, target, &mut velocity, 0.1, 1.0 / 60.0);
+            assert!(current.x <= target.x + TEST_EPSILON, "overshoot: {:?}", current);
+        }
+        assert_vec3_eq(current, target);
+    }
+
+    #[test]
+    fn test_any_orthonormal_pair() {
+        let normals = [
+            Vec3::unit_z(),
+            -Vec3::unit_z(),
+            Vec3::unit_x(),
+            Vec3::new(1.0, 2.0, 3.0).normalize(),
+            Vec3::new(0.0, 1e-7, -1.0).normalize(),
+        ];
+        for n in normals {
+            let (t, b) = n.any_orthonormal_pair();
+            assert!((t.length() - 1.0).abs() < TEST_EPSILON);
+            assert!((b.length() - 1.0).abs() < TEST_EPSILON);
+            assert!(t.dot(n).abs() < TEST_EPSILON && b.dot(n).abs() < TEST_EPSILON && t.dot(b).abs() < TEST_EPSILON);
+            assert_vec3_eq(t.cross(b), n);
+        }
+    }
+
+    #[test]
+    fn test_named_swizzles() {
+        let v = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(v.zyx(), Vec3::new(3.0, 2.0, 1.0));
+        assert_eq!(v.yzx(), v.swizzle(1, 2, 0));
+        assert_eq!(v.xz(), Vec2::new(1.0, 3.0));
+        assert_eq!(v.xz0(), Vec3::new(1.0, 3.0, 0.0));
+        assert_eq!(v.x0z(), Vec3::new(1.0, 0.0, 3.0));
+        assert_eq!(v.to_direction().xyz(), v);
+        assert_eq!(v.xy().extend(3.0), v);
+        assert_eq!(v.xz().x0y(), v.x0z());
+    }
+
+    #[test]
+    fn test_swizzle_macro() {
+        let v = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(crate::swizzle!(v, z, y, x), v.zyx());
+        assert_eq!(crate::swizzle!(v, x, z), Vec2::new(1.0, 3.0));
+        assert_eq!(crate::swizzle!(v, x, y, z, 1), v.to_point());
+        assert_eq!(crate::swizzle!(Vec2::new(4.0, 5.0), y, 0, x), Vec3::new(5.0, 0.0, 4.0));
+        assert_eq!(crate::swizzle!(v.to_direction(), w, w), Vec2::zero());
+    }
+}
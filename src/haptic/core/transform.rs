@@ -0,0 +1,121 @@
+//! Translation/rotation/scale transforms for scene nodes and tracked objects.
+
+use super::mat4::{DecomposeError, Mat4};
+use super::quat::Quat;
+use super::vec3::Vec3;
+use std::ops::Mul;
+
+/// A transform applied as scale, then rotation, then translation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+}
+
+impl Transform {
+    pub const IDENTITY: Self = Self { translation: Vec3::zero(), rotation: Quat::identity(), scale: Vec3::one() };
+
+    #[inline]
+    pub const fn new(translation: Vec3, rotation: Quat, scale: Vec3) -> Self {
+        Self { translation, rotation, scale }
+    }
+
+    #[inline]
+    pub const fn from_translation(translation: Vec3) -> Self {
+        Self { translation, ..Self::IDENTITY }
+    }
+
+    #[inline]
+    pub const fn from_rotation(rotation: Quat) -> Self {
+        Self { rotation, ..Self::IDENTITY }
+    }
+
+    #[inline]
+    pub const fn from_scale(scale: Vec3) -> Self {
+        Self { scale, ..Self::IDENTITY }
+    }
+
+    /// Converts a matrix, failing on shear, projection or singular bases.
+    pub fn try_from_matrix(m: &Mat4) -> Result<Self, DecomposeError> {
+        m.try_decompose().map(|(t, r, s)| Self::new(t, r, s))
+    }
+
+    /// Converts a matrix, discarding any shear.
+    pub fn from_matrix_lossy(m: &Mat4) -> Self {
+        let (t, r, s) = m.decompose();
+        Self::new(t, r, s)
+    }
+
+    #[inline]
+    pub fn to_matrix(&self) -> Mat4 {
+        Mat4::from_trs(self.translation, self.rotation, self.scale)
+    }
+
+    #[inline]
+    pub fn transform_point(&self, p: Vec3) -> Vec3 {
+        self.rotation.rotate(scale(p, self.scale)) + self.translation
+    }
+
+    #[inline]
+    pub fn transform_vector(&self, v: Vec3) -> Vec3 {
+        self.rotation.rotate(scale(v, self.scale))
+    }
+
+    /// Inverse transform. Exact for uniform scale; with non-uniform scale and
+    /// rotation the true inverse has shear, which this approximation drops.
+    pub fn inverse(&self) -> Self {
+        let inv_scale = Vec3::new(1.0 / self.scale.x, 1.0 / self.scale.y, 1.0 / self.scale.z);
+        let inv_rotation = self.rotation.inverse();
+        let translation = scale(inv_rotation.rotate(-self.translation), inv_scale);
+        Self::new(translation, inv_rotation, inv_scale)
+    }
+
+    /// Interpolates translation and scale linearly and rotation spherically.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self::new(
+            self.translation.lerp(other.translation, t),
+            self.rotation.slerp(other.rotation, t),
+            self.scale.lerp(other.scale, t),
+        )
+    }
+}
+
+/// Component-wise product.
+#[inline]
+fn scale(v: Vec3, s: Vec3) -> Vec3 {
+    Vec3::new(v.x * s.x, v.y * s.y, v.z * s.z)
+}
+
+impl Default for Transform {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Parent * child: applies `child` first, then `self`.
+impl Mul for Transform {
+    type Output = Self;
+    fn mul(self, child: Self) -> Self {
+        Self::new(self.transform_point(child.translation), self.rotation * child.rotation, scale(self.scale, child.scale))
+    }
+}
+
+impl TryFrom<Mat4> for Transform {
+    type Error = DecomposeError;
+    fn try_from(m: Mat4) -> Result<Self, Self::Error> {
+        Self::try_from_matrix(&m)
+    }
+}
+
+impl From<Transform> for Mat4 {
+    #[inline]
+    fn from(t: Transform) -> Self {
+        t.to_matrix()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/transform_tests.rs"]
+mod tests;
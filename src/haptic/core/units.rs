@@ -0,0 +1,413 @@
+//! Typed physical quantities for device and physics boundaries.
+//!
+//! Scene space is in meters and SI units throughout; many device SDKs report
+//! millimeters. Wrapping values in these types makes every crossing explicit:
+//! `Millimeters` only becomes `Meters` through `From`, and stiffness times
+//! displacement yields `Newtons` rather than a bare `f32`.
+
+use super::vec3::Vec3;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+// ============================================================================
+// Scalar Quantities
+// ============================================================================
+
+macro_rules! quantity {
+    ($(#[$doc:meta])* $t:ident, $symbol:literal) => {
+        $(#[$doc])*
+        #[repr(transparent)]
+        #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+        pub struct $t(pub f32);
+
+        impl $t {
+            pub const ZERO: Self = Self(0.0);
+
+            /// The raw value in this type's unit.
+            #[inline]
+            pub const fn value(self) -> f32 {
+                self.0
+            }
+
+            #[inline]
+            pub fn abs(self) -> Self {
+                Self(self.0.abs())
+            }
+
+            #[inline]
+            pub fn min(self, other: Self) -> Self {
+                Self(self.0.min(other.0))
+            }
+
+            #[inline]
+            pub fn max(self, other: Self) -> Self {
+                Self(self.0.max(other.0))
+            }
+
+            #[inline]
+            pub fn clamp(self, min: Self, max: Self) -> Self {
+                Self(self.0.clamp(min.0, max.0))
+            }
+        }
+
+        impl fmt::Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} {}", self.0, $symbol)
+            }
+        }
+
+        impl Add for $t {
+            type Output = Self;
+            #[inline]
+            fn add(self, other: Self) -> Self {
+                $t(self.0 + other.0)
+            }
+        }
+
+        impl AddAssign for $t {
+            #[inline]
+            fn add_assign(&mut self, other: Self) {
+                self.0 += other.0;
+            }
+        }
+
+        impl Sub for $t {
+            type Output = Self;
+            #[inline]
+            fn sub(self, other: Self) -> Self {
+                $t(self.0 - other.0)
+            }
+        }
+
+        impl SubAssign for $t {
+            #[inline]
+            fn sub_assign(&mut self, other: Self) {
+                self.0 -= other.0;
+            }
+        }
+
+        impl Neg for $t {
+            type Output = Self;
+            #[inline]
+            fn neg(self) -> Self {
+                $t(-self.0)
+            }
+        }
+
+        impl Mul<f32> for $t {
+            type Output = Self;
+            #[inline]
+            fn mul(self, scalar: f32) -> Self {
+                $t(self.0 * scalar)
+            }
+        }
+
+        impl Mul<$t> for f32 {
+            type Output = $t;
+            #[inline]
+            fn mul(self, quantity: $t) -> $t {
+                $t(self * quantity.0)
+            }
+        }
+
+        impl Div<f32> for $t {
+            type Output = Self;
+            #[inline]
+            fn div(self, scalar: f32) -> Self {
+                $t(self.0 / scalar)
+            }
+        }
+
+        // Ratio of two quantities of the same kind
+        impl Div for $t {
+            type Output = f32;
+            #[inline]
+            fn div(self, other: Self) -> f32 {
+                self.0 / other.0
+            }
+        }
+    };
+}
+
+quantity!(
+    /// Length in meters (scene units).
+    Meters, "m"
+);
+quantity!(
+    /// Length in millimeters (common device SDK unit).
+    Millimeters, "mm"
+);
+quantity!(
+    /// Duration in seconds.
+    Seconds, "s"
+);
+quantity!(
+    /// Duration in milliseconds.
+    Milliseconds, "ms"
+);
+quantity!(
+    /// Frequency in hertz.
+    Hertz, "Hz"
+);
+quantity!(
+    /// Force in newtons.
+    Newtons, "N"
+);
+quantity!(
+    /// Rate of change of force, used by slew-rate limits.
+    NewtonsPerSecond, "N/s"
+);
+quantity!(
+    /// Speed in meters per second.
+    MetersPerSecond, "m/s"
+);
+quantity!(
+    /// Spring stiffness.
+    NewtonsPerMeter, "N/m"
+);
+quantity!(
+    /// Viscous damping coefficient.
+    NewtonSecondsPerMeter, "N·s/m"
+);
+
+// ============================================================================
+// Vector Quantities
+// ============================================================================
+
+macro_rules! vector_quantity {
+    ($(#[$doc:meta])* $t:ident, $scalar:ident, $symbol:literal) => {
+        $(#[$doc])*
+        #[repr(transparent)]
+        #[derive(Debug, Clone, Copy, Default, PartialEq)]
+        pub struct $t(pub Vec3);
+
+        impl $t {
+            pub const ZERO: Self = Self(Vec3::zero());
+
+            #[inline]
+            pub const fn new(x: f32, y: f32, z: f32) -> Self {
+                Self(Vec3::new(x, y, z))
+            }
+
+            /// The raw vector in this type's unit.
+            #[inline]
+            pub const fn value(self) -> Vec3 {
+                self.0
+            }
+
+            #[inline]
+            pub fn length(self) -> $scalar {
+                $scalar(self.0.length())
+            }
+
+            /// Rescales the vector to at most `max` long.
+            #[inline]
+            pub fn clamp_length(self, max: $scalar) -> Self {
+                let length = self.0.length();
+                if length > max.0 && length > 0.0 {
+                    Self(self.0 * (max.0 / length))
+                } else {
+                    self
+                }
+            }
+        }
+
+        impl fmt::Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "({}, {}, {}) {}", self.0.x, self.0.y, self.0.z, $symbol)
+            }
+        }
+
+        impl Add for $t {
+            type Output = Self;
+            #[inline]
+            fn add(self, other: Self) -> Self {
+                $t(self.0 + other.0)
+            }
+        }
+
+        impl AddAssign for $t {
+            #[inline]
+            fn add_assign(&mut self, other: Self) {
+                self.0 += other.0;
+            }
+        }
+
+        impl Sub for $t {
+            type Output = Self;
+            #[inline]
+            fn sub(self, other: Self) -> Self {
+                $t(self.0 - other.0)
+            }
+        }
+
+        impl Neg for $t {
+            type Output = Self;
+            #[inline]
+            fn neg(self) -> Self {
+                $t(-self.0)
+            }
+        }
+
+        impl Mul<f32> for $t {
+            type Output = Self;
+            #[inline]
+            fn mul(self, scalar: f32) -> Self {
+                $t(self.0 * scalar)
+            }
+        }
+
+        impl Div<f32> for $t {
+            type Output = Self;
+            #[inline]
+            fn div(self, scalar: f32) -> Self {
+                $t(self.0 / scalar)
+            }
+        }
+    };
+}
+
+vector_quantity!(
+    /// Position or displacement in meters.
+    Meters3, Meters, "m"
+);
+vector_quantity!(
+    /// Position or displacement in millimeters.
+    Millimeters3, Millimeters, "mm"
+);
+vector_quantity!(
+    /// Velocity in meters per second.
+    MetersPerSecond3, MetersPerSecond, "m/s"
+);
+vector_quantity!(
+    /// Force vector in newtons.
+    Newtons3, Newtons, "N"
+);
+
+// ============================================================================
+// Conversions
+// ============================================================================
+
+impl From<Millimeters> for Meters {
+    #[inline]
+    fn from(mm: Millimeters) -> Self {
+        Meters(mm.0 * 1e-3)
+    }
+}
+
+impl From<Meters> for Millimeters {
+    #[inline]
+    fn from(m: Meters) -> Self {
+        Millimeters(m.0 * 1e3)
+    }
+}
+
+impl From<Millimeters3> for Meters3 {
+    #[inline]
+    fn from(mm: Millimeters3) -> Self {
+        Meters3(mm.0 * 1e-3)
+    }
+}
+
+impl From<Meters3> for Millimeters3 {
+    #[inline]
+    fn from(m: Meters3) -> Self {
+        Millimeters3(m.0 * 1e3)
+    }
+}
+
+impl From<Milliseconds> for Seconds {
+    #[inline]
+    fn from(ms: Milliseconds) -> Self {
+        Seconds(ms.0 * 1e-3)
+    }
+}
+
+impl From<Seconds> for Milliseconds {
+    #[inline]
+    fn from(s: Seconds) -> Self {
+        Milliseconds(s.0 * 1e3)
+    }
+}
+
+impl Hertz {
+    /// Duration of one cycle.
+    #[inline]
+    pub fn period(self) -> Seconds {
+        Seconds(1.0 / self.0)
+    }
+}
+
+impl Seconds {
+    /// Frequency whose period is this duration.
+    #[inline]
+    pub fn frequency(self) -> Hertz {
+        Hertz(1.0 / self.0)
+    }
+}
+
+// ============================================================================
+// Derived Arithmetic
+// ============================================================================
+
+/// Implements `$a * $b = $out` in both operand orders.
+macro_rules! product {
+    ($a:ident * $b:ident = $out:ident) => {
+        impl Mul<$b> for $a {
+            type Output = $out;
+            #[inline]
+            fn mul(self, other: $b) -> $out {
+                $out(self.0 * other.0)
+            }
+        }
+
+        impl Mul<$a> for $b {
+            type Output = $out;
+            #[inline]
+            fn mul(self, other: $a) -> $out {
+                $out(self.0 * other.0)
+            }
+        }
+    };
+}
+
+/// Implements `$a / $b = $out`.
+macro_rules! quotient {
+    ($a:ident / $b:ident = $out:ident) => {
+        impl Div<$b> for $a {
+            type Output = $out;
+            #[inline]
+            fn div(self, other: $b) -> $out {
+                $out(self.0 / other.0)
+            }
+        }
+    };
+}
+
+product!(NewtonsPerMeter * Meters = Newtons);
+product!(NewtonSecondsPerMeter * MetersPerSecond = Newtons);
+product!(MetersPerSecond * Seconds = Meters);
+product!(NewtonsPerSecond * Seconds = Newtons);
+quotient!(Newtons / Meters = NewtonsPerMeter);
+quotient!(Newtons / NewtonsPerMeter = Meters);
+quotient!(Meters / Seconds = MetersPerSecond);
+quotient!(Newtons / Seconds = NewtonsPerSecond);
+
+// Scalar coefficients applied to vector quantities
+product!(NewtonsPerMeter * Meters3 = Newtons3);
+product!(NewtonSecondsPerMeter * MetersPerSecond3 = Newtons3);
+product!(MetersPerSecond3 * Seconds = Meters3);
+quotient!(Meters3 / Seconds = MetersPerSecond3);
+
+impl Mul<Seconds> for Hertz {
+    type Output = f32;
+    /// Number of cycles in the duration.
+    #[inline]
+    fn mul(self, duration: Seconds) -> f32 {
+        self.0 * duration.0
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/units_tests.rs"]
+mod tests;
@@ -0,0 +1,229 @@
+//! 2D vector for texture coordinates and planar layout.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use super::vec3::{Vec3, EPSILON};
+
+/// 2D vector with x, y f32 components.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vec2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vec2 {
+    // ============================================================================
+    // Constructors
+    // ============================================================================
+
+    #[inline]
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    #[inline]
+    pub const fn zero() -> Self {
+        Self::new(0.0, 0.0)
+    }
+
+    #[inline]
+    pub const fn one() -> Self {
+        Self::new(1.0, 1.0)
+    }
+
+    #[inline]
+    pub const fn splat(value: f32) -> Self {
+        Self::new(value, value)
+    }
+
+    // ============================================================================
+    // Operations
+    // ============================================================================
+
+    #[inline]
+    pub fn dot(self, other: Self) -> f32 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// 2D cross product (z component of the 3D cross product); twice the signed
+    /// area of the triangle spanned by the two vectors.
+    #[inline]
+    pub fn perp_dot(self, other: Self) -> f32 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Rotates the vector by 90° counter-clockwise.
+    #[inline]
+    pub fn perp(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    #[inline]
+    pub fn length_squared(self) -> f32 {
+        self.dot(self)
+    }
+
+    #[inline]
+    pub fn length(self) -> f32 {
+        self.length_squared().sqrt()
+    }
+
+    /// Normalizes the vector, returning zero if it is too small.
+    #[inline]
+    pub fn normalize(self) -> Self {
+        self.try_normalize().unwrap_or_else(Self::zero)
+    }
+
+    #[inline]
+    pub fn try_normalize(self) -> Option<Self> {
+        let length_sq = self.length_squared();
+        if length_sq < EPSILON * EPSILON {
+            None
+        } else {
+            Some(self * (1.0 / length_sq.sqrt()))
+        }
+    }
+
+    #[inline]
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+
+    #[inline]
+    pub fn distance(self, other: Self) -> f32 {
+        (other - self).length()
+    }
+
+    #[inline]
+    pub fn approx_eq(self, other: Self, epsilon: f32) -> bool {
+        (self.x - other.x).abs() <= epsilon && (self.y - other.y).abs() <= epsilon
+    }
+}
+
+// ============================================================================
+// Swizzling
+// ============================================================================
+
+impl Vec2 {
+    #[inline]
+    pub fn yx(self) -> Self {
+        Self::new(self.y, self.x)
+    }
+
+    /// Extends to a Vec3 with the given z.
+    #[inline]
+    pub fn extend(self, z: f32) -> Vec3 {
+        Vec3::new(self.x, self.y, z)
+    }
+
+    /// Point in the XY plane: (x, y, 0).
+    #[inline]
+    pub fn xy0(self) -> Vec3 {
+        self.extend(0.0)
+    }
+
+    /// Point on the XZ ground plane: (x, 0, y).
+    #[inline]
+    pub fn x0y(self) -> Vec3 {
+        Vec3::new(self.x, 0.0, self.y)
+    }
+}
+
+// ============================================================================
+// Trait Implementations
+// ============================================================================
+
+impl Add for Vec2 {
+    type Output = Self;
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    #[inline]
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Self;
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Vec2 {
+    #[inline]
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<f32> for Vec2 {
+    type Output = Self;
+    #[inline]
+    fn mul(self, scalar: f32) -> Self {
+        Self::new(self.x * scalar, self.y * scalar)
+    }
+}
+
+impl Mul<Vec2> for f32 {
+    type Output = Vec2;
+    #[inline]
+    fn mul(self, vec: Vec2) -> Vec2 {
+        vec * self
+    }
+}
+
+impl Div<f32> for Vec2 {
+    type Output = Self;
+    #[inline]
+    fn div(self, scalar: f32) -> Self {
+        self * (1.0 / scalar)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl fmt::Display for Vec2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({:.3}, {:.3})", self.x, self.y)
+    }
+}
+
+impl From<[f32; 2]> for Vec2 {
+    #[inline]
+    fn from(arr: [f32; 2]) -> Self {
+        Self::new(arr[0], arr[1])
+    }
+}
+
+impl From<Vec2> for [f32; 2] {
+    #[inline]
+    fn from(vec: Vec2) -> Self {
+        [vec.x, vec.y]
+    }
+}
+
+impl From<(f32, f32)> for Vec2 {
+    #[inline]
+    fn from(tuple: (f32, f32)) -> Self {
+        Self::new(tuple.0, tuple.1)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/vec2_tests.rs"]
+mod tests;
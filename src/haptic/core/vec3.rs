@@ -6,6 +6,9 @@
 use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Neg, Index, IndexMut};
 use std::fmt;
 
+use super::math;
+use super::vec2::Vec2;
+
 // Constants for numerical stability
 pub const EPSILON: f32 = 1e-6;
 pub const SPATIAL_EPSILON: f32 = 1e-4;
@@ -235,6 +238,55 @@ impl Vec3 {
         self * a + other * b
     }
 
+    /// Component-wise smoothstep of this vector between edge0 and edge1.
+    /// Each component is mapped to [0, 1] with zero slope at the edges.
+    #[inline]
+    pub fn smoothstep(self, edge0: Self, edge1: Self) -> Self {
+        Self::new(
+            math::smoothstep(edge0.x, edge1.x, self.x),
+            math::smoothstep(edge0.y, edge1.y, self.y),
+            math::smoothstep(edge0.z, edge1.z, self.z),
+        )
+    }
+
+    /// Component-wise smootherstep of this vector between edge0 and edge1.
+    #[inline]
+    pub fn smootherstep(self, edge0: Self, edge1: Self) -> Self {
+        Self::new(
+            math::smootherstep(edge0.x, edge1.x, self.x),
+            math::smootherstep(edge0.y, edge1.y, self.y),
+            math::smootherstep(edge0.z, edge1.z, self.z),
+        )
+    }
+
+    /// Interpolates towards another vector with a smoothstep-eased t.
+    /// Useful for fading highlights without a visible start/stop jerk.
+    #[inline]
+    pub fn smooth_lerp(self, other: Self, t: f32) -> Self {
+        self.lerp(other, math::smoothstep(0.0, 1.0, t))
+    }
+
+    /// Critically damped approach of `current` towards `target` (Unity-style SmoothDamp).
+    /// `velocity` carries state between calls; stable and overshoot-free for any dt.
+    #[inline]
+    pub fn smooth_damp(current: Self, target: Self, velocity: &mut Self, smooth_time: f32, dt: f32) -> Self {
+        if dt <= 0.0 {
+            return current;
+        }
+        let (omega, decay) = math::smooth_damp_coefficients(smooth_time, dt);
+        let change = current - target;
+        let temp = (*velocity + change * omega) * dt;
+        *velocity = (*velocity - temp * omega) * decay;
+        let output = target + (change + temp) * decay;
+
+        // Never overshoot the target
+        if (target - current).dot(output - target) > 0.0 {
+            *velocity = Self::zero();
+            return target;
+        }
+        output
+    }
+
     /// Reflects the vector around a normal vector.
     /// Normal should be normalized for correct results.
     #[inline]
@@ -269,6 +321,19 @@ impl Vec3 {
         self - self.project_onto(other)
     }
 
+    /// Returns two unit tangents (t, b) such that (t, b, self) is a right-handed orthonormal basis.
+    /// Branchless method of Duff et al. (2017); self must be normalized.
+    #[inline]
+    pub fn any_orthonormal_pair(self) -> (Self, Self) {
+        let sign = 1.0f32.copysign(self.z);
+        let a = -1.0 / (sign + self.z);
+        let b = self.x * self.y * a;
+        (
+            Self::new(1.0 + sign * self.x * self.x * a, sign * b, -sign * self.x),
+            Self::new(b, sign + self.y * self.y * a, -self.y),
+        )
+    }
+
     // ============================================================================
     // Coordinate Space Transformations
     // ============================================================================
@@ -332,12 +397,92 @@ impl Vec3 {
     }
 
     /// Returns a vector with components rearranged according to swizzle pattern.
+    /// Prefer the named accessors below or `swizzle!`, which are checked at compile time.
     #[inline]
     pub fn swizzle(self, x_idx: usize, y_idx: usize, z_idx: usize) -> Self {
         Self::new(self[x_idx], self[y_idx], self[z_idx])
     }
 }
 
+// ============================================================================
+// Swizzling
+// ============================================================================
+
+impl Vec3 {
+    #[inline]
+    pub fn xzy(self) -> Self {
+        Self::new(self.x, self.z, self.y)
+    }
+
+    #[inline]
+    pub fn yxz(self) -> Self {
+        Self::new(self.y, self.x, self.z)
+    }
+
+    #[inline]
+    pub fn yzx(self) -> Self {
+        Self::new(self.y, self.z, self.x)
+    }
+
+    #[inline]
+    pub fn zxy(self) -> Self {
+        Self::new(self.z, self.x, self.y)
+    }
+
+    #[inline]
+    pub fn zyx(self) -> Self {
+        Self::new(self.z, self.y, self.x)
+    }
+
+    #[inline]
+    pub fn xy(self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
+    #[inline]
+    pub fn xz(self) -> Vec2 {
+        Vec2::new(self.x, self.z)
+    }
+
+    #[inline]
+    pub fn yx(self) -> Vec2 {
+        Vec2::new(self.y, self.x)
+    }
+
+    #[inline]
+    pub fn yz(self) -> Vec2 {
+        Vec2::new(self.y, self.z)
+    }
+
+    #[inline]
+    pub fn zx(self) -> Vec2 {
+        Vec2::new(self.z, self.x)
+    }
+
+    #[inline]
+    pub fn zy(self) -> Vec2 {
+        Vec2::new(self.z, self.y)
+    }
+
+    /// Projection onto the XY plane (z = 0).
+    #[inline]
+    pub fn xy0(self) -> Self {
+        Self::new(self.x, self.y, 0.0)
+    }
+
+    /// Projection onto the XZ (ground) plane (y = 0).
+    #[inline]
+    pub fn x0z(self) -> Self {
+        Self::new(self.x, 0.0, self.z)
+    }
+
+    /// Ground-plane (XZ) coordinates laid into the XY plane: (x, z, 0).
+    #[inline]
+    pub fn xz0(self) -> Self {
+        Self::new(self.x, self.z, 0.0)
+    }
+}
+
 // ============================================================================
 // Fast Inverse Square Root Implementation
 // ============================================================================
@@ -387,6 +532,17 @@ impl Vec4 {
         Vec3::new(self.x, self.y, self.z)
     }
 
+    /// Same as `truncate`, named for symmetry with the Vec3 swizzles.
+    #[inline]
+    pub fn xyz(self) -> Vec3 {
+        self.truncate()
+    }
+
+    #[inline]
+    pub fn xy(self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
     /// Truncates to Vec3 by performing perspective division (x/w, y/w, z/w).
     #[inline]
     pub fn truncate_with_perspective(self) -> Vec3 {
@@ -551,4 +707,38 @@ impl From<Vec3> for (f32, f32, f32) {
     fn from(vec: Vec3) -> Self {
         (vec.x, vec.y, vec.z)
     }
-}
\ No newline at end of file
+}
+
+// ============================================================================
+// Swizzle Macro
+// ============================================================================
+
+/// Builds a Vec2, Vec3 or Vec4 from named components of any vector, checked at
+/// compile time: `swizzle!(v, z, y, x)`, `swizzle!(v, x, z)`, `swizzle!(v, x, y, z, 1)`.
+/// The literals `0` and `1` insert constants.
+#[macro_export]
+macro_rules! swizzle {
+    (@c $v:ident, 0) => { 0.0f32 };
+    (@c $v:ident, 1) => { 1.0f32 };
+    (@c $v:ident, $f:ident) => { $v.$f };
+    ($v:expr, $a:tt, $b:tt) => {{
+        let v = $v;
+        $crate::core::Vec2::new($crate::swizzle!(@c v, $a), $crate::swizzle!(@c v, $b))
+    }};
+    ($v:expr, $a:tt, $b:tt, $c:tt) => {{
+        let v = $v;
+        $crate::core::Vec3::new($crate::swizzle!(@c v, $a), $crate::swizzle!(@c v, $b), $crate::swizzle!(@c v, $c))
+    }};
+    ($v:expr, $a:tt, $b:tt, $c:tt, $d:tt) => {{
+        let v = $v;
+        $crate::core::Vec4::new(
+            $crate::swizzle!(@c v, $a),
+            $crate::swizzle!(@c v, $b),
+            $crate::swizzle!(@c v, $c),
+            $crate::swizzle!(@c v, $d),
+        )
+    }};
+}
+
+// The test file carries its own `#[cfg(test)] mod tests` wrapper
+include!("tests/vec3_tests.rs");
@@ -0,0 +1,475 @@
+//! Android `Vibrator` output.
+//!
+//! Android plays vibrations as `VibrationEffect`s of two kinds. Waveforms are
+//! steps of (duration, amplitude); every phone with amplitude control plays them,
+//! but they cannot express sharpness. Compositions chain predefined primitives
+//! (click, tick, rises and falls) that the vendor tuned for its actuator; they feel
+//! far crisper but are only available on newer devices. [`AndroidWaveform`] and
+//! [`Composition`] translate the crate's vibrotactile signals into either, and
+//! with the `android` feature [`AndroidVibrator`] plays them through JNI, picking
+//! a composition when the device supports the primitives it uses.
+
+use crate::effects::{HapticSample, HapticSignal};
+
+// ============================================================================
+// Waveforms
+// ============================================================================
+
+/// Largest amplitude `VibrationEffect` accepts.
+pub const MAX_AMPLITUDE: i32 = 255;
+
+/// A `VibrationEffect.createWaveform` timeline: step durations in milliseconds
+/// and the amplitude (0..=255) held during each.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AndroidWaveform {
+    pub timings: Vec<i64>,
+    pub amplitudes: Vec<i32>,
+}
+
+impl AndroidWaveform {
+    /// Samples `signal` every `step_ms` milliseconds and merges equal steps.
+    /// Endless signals are cut at `limit_ms`.
+    pub fn from_signal(signal: &dyn HapticSignal, step_ms: u32, limit_ms: u32) -> Self {
+        let step_ms = step_ms.max(1);
+        let length_ms = signal.duration().map_or(limit_ms, |d| ((d * 1000.0).ceil() as u32).min(limit_ms));
+        let mut waveform = Self::default();
+        for start in (0..length_ms).step_by(step_ms as usize) {
+            let width = step_ms.min(length_ms - start);
+            // Sample mid-step so short features are not skipped
+            let sample = signal.sample((start as f32 + width as f32 * 0.5) * 1e-3);
+            waveform.push(i64::from(width), amplitude(sample.intensity));
+        }
+        waveform
+    }
+
+    /// Appends a step, extending the last one if the amplitude is unchanged.
+    pub fn push(&mut self, duration_ms: i64, amplitude: i32) {
+        if self.amplitudes.last() == Some(&amplitude) {
+            if let Some(last) = self.timings.last_mut() {
+                *last += duration_ms;
+                return;
+            }
+        }
+        self.timings.push(duration_ms);
+        self.amplitudes.push(amplitude);
+    }
+
+    /// Total length in milliseconds.
+    pub fn duration_ms(&self) -> i64 {
+        self.timings.iter().sum()
+    }
+
+    /// Whether any step vibrates.
+    pub fn is_silent(&self) -> bool {
+        self.amplitudes.iter().all(|&a| a == 0)
+    }
+}
+
+/// Amplitude step for an intensity in 0..=1.
+#[inline]
+pub fn amplitude(intensity: f32) -> i32 {
+    if intensity.is_nan() {
+        return 0;
+    }
+    (intensity.clamp(0.0, 1.0) * MAX_AMPLITUDE as f32).round() as i32
+}
+
+// ============================================================================
+// Compositions
+// ============================================================================
+
+/// `VibrationEffect.Composition` primitives, with their Android ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum Primitive {
+    Click = 1,
+    Thud = 2,
+    Spin = 3,
+    QuickRise = 4,
+    SlowRise = 5,
+    QuickFall = 6,
+    Tick = 7,
+    LowTick = 8,
+}
+
+impl Primitive {
+    #[inline]
+    pub fn id(self) -> i32 {
+        self as i32
+    }
+
+    /// Typical length in milliseconds. The real length depends on the device; it
+    /// is only used to turn onset times into the gaps the composition needs.
+    pub fn nominal_ms(self) -> u32 {
+        match self {
+            Primitive::Click | Primitive::Tick | Primitive::LowTick => 12,
+            Primitive::Thud => 50,
+            Primitive::Spin => 150,
+            Primitive::QuickRise | Primitive::QuickFall => 80,
+            Primitive::SlowRise => 300,
+        }
+    }
+}
+
+/// One primitive of a composition.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrimitiveStep {
+    pub primitive: Primitive,
+    /// Strength in 0..=1.
+    pub scale: f32,
+    /// Pause before this primitive, after the previous one ends.
+    pub delay_ms: u32,
+}
+
+/// A sequence of primitives for `VibrationEffect.startComposition`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Composition {
+    pub steps: Vec<PrimitiveStep>,
+}
+
+/// Pulses at most this long become a single click or tick.
+const TAP_MS: u32 = 30;
+
+/// Rises faster than this use `QuickRise`; falls likewise use `QuickFall`.
+const QUICK_MS: u32 = 100;
+
+/// Intensity above which the signal counts as on when finding pulses.
+const PULSE_THRESHOLD: f32 = 0.05;
+
+/// A stretch of signal above the pulse threshold, in milliseconds from the start.
+#[derive(Debug, Clone, Copy)]
+struct Pulse {
+    start: u32,
+    peak_at: u32,
+    end: u32,
+    peak: HapticSample,
+}
+
+impl Composition {
+    pub fn with_step(mut self, primitive: Primitive, scale: f32, delay_ms: u32) -> Self {
+        self.steps.push(PrimitiveStep { primitive, scale: scale.clamp(0.0, 1.0), delay_ms });
+        self
+    }
+
+    /// Approximates `signal` with primitives, scanning it every `step_ms`.
+    ///
+    /// Each pulse of the envelope becomes a tap (click or tick, by sharpness and
+    /// strength) when short. A long pulse becomes a rise to its peak, followed by a
+    /// quick fall if it ends abruptly.
+    pub fn from_signal(signal: &dyn HapticSignal, step_ms: u32, limit_ms: u32) -> Self {
+        let mut composition = Self::default();
+        let mut cursor = 0;
+        for pulse in pulses(signal, step_ms.max(1), limit_ms) {
+            let (peak, sharp) = (pulse.peak.intensity, pulse.peak.sharpness >= 0.5);
+            let delay = pulse.start.saturating_sub(cursor);
+            if pulse.end - pulse.start <= TAP_MS {
+                let primitive = match (peak >= 0.5, sharp) {
+                    (true, true) => Primitive::Click,
+                    (true, false) => Primitive::Thud,
+                    (false, true) => Primitive::Tick,
+                    (false, false) => Primitive::LowTick,
+                };
+                composition = composition.with_step(primitive, peak, delay);
+                cursor = pulse.start + primitive.nominal_ms();
+                continue;
+            }
+            let rise = if pulse.peak_at - pulse.start < QUICK_MS { Primitive::QuickRise } else { Primitive::SlowRise };
+            composition = composition.with_step(rise, peak, delay);
+            cursor = pulse.start + rise.nominal_ms();
+            if pulse.end - pulse.peak_at < QUICK_MS {
+                let fall_at = pulse.end.saturating_sub(Primitive::QuickFall.nominal_ms());
+                composition = composition.with_step(Primitive::QuickFall, peak, fall_at.saturating_sub(cursor));
+                cursor = cursor.max(fall_at) + Primitive::QuickFall.nominal_ms();
+            }
+        }
+        composition
+    }
+
+    /// Distinct primitives used, for checking device support.
+    pub fn primitives(&self) -> Vec<Primitive> {
+        let mut used: Vec<Primitive> = Vec::new();
+        for step in &self.steps {
+            if !used.contains(&step.primitive) {
+                used.push(step.primitive);
+            }
+        }
+        used
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+/// Splits the envelope of `signal` into pulses.
+fn pulses(signal: &dyn HapticSignal, step_ms: u32, limit_ms: u32) -> Vec<Pulse> {
+    let length_ms = signal.duration().map_or(limit_ms, |d| ((d * 1000.0).ceil() as u32).min(limit_ms));
+    let mut pulses = Vec::new();
+    let mut current: Option<Pulse> = None;
+    let mut t = 0;
+    while t <= length_ms {
+        let sample = signal.sample(t as f32 * 1e-3);
+        match (&mut current, sample.intensity > PULSE_THRESHOLD) {
+            (Some(pulse), true) => {
+                pulse.end = t;
+                if sample.intensity > pulse.peak.intensity {
+                    pulse.peak = sample;
+                    pulse.peak_at = t;
+                }
+            }
+            (Some(_), false) => pulses.extend(current.take()),
+            (None, true) => current = Some(Pulse { start: t, peak_at: t, end: t, peak: sample }),
+            (None, false) => {}
+        }
+        t += step_ms;
+    }
+    pulses.extend(current);
+    pulses
+}
+
+// ============================================================================
+// Device
+// ============================================================================
+
+#[cfg(all(feature = "android", target_os = "android"))]
+pub use self::jni_vibrator::AndroidVibrator;
+
+#[cfg(all(feature = "android", target_os = "android"))]
+mod jni_vibrator {
+    use std::time::{Duration, Instant};
+
+    use jni::objects::{GlobalRef, JObject, JValue};
+    use jni::{JNIEnv, JavaVM};
+
+    use super::{amplitude, AndroidWaveform, Composition, Primitive, MAX_AMPLITUDE};
+    use crate::core::{Hertz, Newtons, NewtonsPerMeter};
+    use crate::device::{DeviceCapabilities, DeviceError, DeviceTier, HapticDevice};
+    use crate::effects::{HapticSample, HapticSignal};
+
+    const EFFECT: &str = "android/os/VibrationEffect";
+
+    /// Compositions need Android 11 (API level 30).
+    const COMPOSITION_API: i32 = 30;
+
+    /// Length of the one-shot vibrations used for frame-rate updates; they are
+    /// renewed well before running out.
+    const ONE_SHOT: Duration = Duration::from_millis(250);
+
+    /// Resolution used when translating signals.
+    const STEP_MS: u32 = 10;
+
+    /// Longest translated signal.
+    const LIMIT_MS: u32 = 10_000;
+
+    fn backend(error: jni::errors::Error) -> DeviceError {
+        DeviceError::Backend(error.to_string())
+    }
+
+    /// The system vibrator, reached through the activity's JVM.
+    pub struct AndroidVibrator {
+        vm: JavaVM,
+        vibrator: GlobalRef,
+        capabilities: DeviceCapabilities,
+        api_level: i32,
+        amplitude_control: bool,
+        last: Option<(i32, Instant)>,
+    }
+
+    impl AndroidVibrator {
+        /// Looks up the vibrator service of the current Android context.
+        pub fn open() -> Result<Self, DeviceError> {
+            let context = ndk_context::android_context();
+            // SAFETY: ndk-context hands out the process JavaVM and the activity context.
+            let vm = unsafe { JavaVM::from_raw(context.vm().cast()) }.map_err(backend)?;
+            let (vibrator, api_level, amplitude_control) = {
+                let mut env = vm.attach_current_thread().map_err(backend)?;
+                // SAFETY: the context is a live global reference owned by the activity.
+                let activity = unsafe { JObject::from_raw(context.context().cast()) };
+                let name = env.new_string("vibrator").map_err(backend)?;
+                let vibrator = env
+                    .call_method(
+                        &activity,
+                        "getSystemService",
+                        "(Ljava/lang/String;)Ljava/lang/Object;",
+                        &[JValue::Object(&name)],
+                    )
+                    .and_then(|v| v.l())
+                    .map_err(backend)?;
+                if vibrator.is_null() {
+                    return Err(DeviceError::Unsupported);
+                }
+                let has_vibrator =
+                    env.call_method(&vibrator, "hasVibrator", "()Z", &[]).and_then(|v| v.z()).map_err(backend)?;
+                if !has_vibrator {
+                    return Err(DeviceError::Unsupported);
+                }
+                let amplitude_control = env
+                    .call_method(&vibrator, "hasAmplitudeControl", "()Z", &[])
+                    .and_then(|v| v.z())
+                    .map_err(backend)?;
+                let api_level = env
+                    .get_static_field("android/os/Build$VERSION", "SDK_INT", "I")
+                    .and_then(|v| v.i())
+                    .map_err(backend)?;
+                (env.new_global_ref(vibrator).map_err(backend)?, api_level, amplitude_control)
+            };
+            let capabilities = DeviceCapabilities {
+                tier: DeviceTier::Vibrotactile,
+                max_force: Newtons::ZERO,
+                max_stiffness: NewtonsPerMeter::ZERO,
+                update_rate: Hertz(60.0),
+                actuators: 1,
+                gripper: None,
+            };
+            Ok(Self { vm, vibrator, capabilities, api_level, amplitude_control, last: None })
+        }
+
+        /// Whether the device supports every primitive in `composition`.
+        pub fn supports(&self, composition: &Composition) -> Result<bool, DeviceError> {
+            if self.api_level < COMPOSITION_API {
+                return Ok(false);
+            }
+            let ids: Vec<i32> = composition.primitives().into_iter().map(Primitive::id).collect();
+            let mut env = self.vm.attach_current_thread().map_err(backend)?;
+            let array = env.new_int_array(ids.len() as i32).map_err(backend)?;
+            env.set_int_array_region(&array, 0, &ids).map_err(backend)?;
+            env.call_method(self.vibrator.as_obj(), "areAllPrimitivesSupported", "([I)Z", &[JValue::Object(&array)])
+                .and_then(|v| v.z())
+                .map_err(backend)
+        }
+
+        /// Plays `signal` once: as a composition when the device supports its
+        /// primitives, otherwise as a waveform.
+        pub fn play(&mut self, signal: &dyn HapticSignal) -> Result<(), DeviceError> {
+            let composition = Composition::from_signal(signal, STEP_MS, LIMIT_MS);
+            if !composition.is_empty() && self.supports(&composition)? {
+                return self.play_composition(&composition);
+            }
+            self.play_waveform(&AndroidWaveform::from_signal(signal, STEP_MS, LIMIT_MS))
+        }
+
+        pub fn play_waveform(&mut self, waveform: &AndroidWaveform) -> Result<(), DeviceError> {
+            if waveform.is_silent() {
+                return Ok(());
+            }
+            let mut env = self.vm.attach_current_thread().map_err(backend)?;
+            let timings = env.new_long_array(waveform.timings.len() as i32).map_err(backend)?;
+            env.set_long_array_region(&timings, 0, &waveform.timings).map_err(backend)?;
+            let amplitudes = env.new_int_array(waveform.amplitudes.len() as i32).map_err(backend)?;
+            env.set_int_array_region(&amplitudes, 0, &waveform.amplitudes).map_err(backend)?;
+            let effect = env
+                .call_static_method(
+                    EFFECT,
+                    "createWaveform",
+                    "([J[II)Landroid/os/VibrationEffect;",
+                    &[JValue::Object(&timings), JValue::Object(&amplitudes), JValue::Int(-1)],
+                )
+                .and_then(|v| v.l())
+                .map_err(backend)?;
+            self.last = None;
+            vibrate(&mut env, &self.vibrator, &effect)
+        }
+
+        pub fn play_composition(&mut self, composition: &Composition) -> Result<(), DeviceError> {
+            if self.api_level < COMPOSITION_API {
+                return Err(DeviceError::Unsupported);
+            }
+            let mut env = self.vm.attach_current_thread().map_err(backend)?;
+            let mut builder = env
+                .call_static_method(EFFECT, "startComposition", "()Landroid/os/VibrationEffect$Composition;", &[])
+                .and_then(|v| v.l())
+                .map_err(backend)?;
+            for step in &composition.steps {
+                builder = env
+                    .call_method(
+                        &builder,
+                        "addPrimitive",
+                        "(IFI)Landroid/os/VibrationEffect$Composition;",
+                        &[
+                            JValue::Int(step.primitive.id()),
+                            JValue::Float(step.scale),
+                            JValue::Int(step.delay_ms.min(i32::MAX as u32) as i32),
+                        ],
+                    )
+                    .and_then(|v| v.l())
+                    .map_err(backend)?;
+            }
+            let effect = env
+                .call_method(&builder, "compose", "()Landroid/os/VibrationEffect;", &[])
+                .and_then(|v| v.l())
+                .map_err(backend)?;
+            self.last = None;
+            vibrate(&mut env, &self.vibrator, &effect)
+        }
+
+        /// Stops any vibration.
+        pub fn cancel(&mut self) -> Result<(), DeviceError> {
+            self.last = None;
+            let mut env = self.vm.attach_current_thread().map_err(backend)?;
+            env.call_method(self.vibrator.as_obj(), "cancel", "()V", &[]).map_err(backend)?;
+            Ok(())
+        }
+    }
+
+    fn vibrate(env: &mut JNIEnv, vibrator: &GlobalRef, effect: &JObject) -> Result<(), DeviceError> {
+        env.call_method(vibrator.as_obj(), "vibrate", "(Landroid/os/VibrationEffect;)V", &[JValue::Object(effect)])
+            .map_err(backend)?;
+        Ok(())
+    }
+
+    impl HapticDevice for AndroidVibrator {
+        fn name(&self) -> &str {
+            "Android vibrator"
+        }
+
+        fn capabilities(&self) -> DeviceCapabilities {
+            self.capabilities
+        }
+
+        /// Holds the sample's intensity with a one-shot vibration, renewed when the
+        /// amplitude changes or the previous one nears its end.
+        fn set_vibration(&mut self, actuator: usize, sample: HapticSample) -> Result<(), DeviceError> {
+            if actuator != 0 {
+                return Err(DeviceError::InvalidActuator(actuator));
+            }
+            let mut level = amplitude(sample.intensity);
+            if !self.amplitude_control {
+                // The motor is only on or off
+                level = if level > MAX_AMPLITUDE / 2 { MAX_AMPLITUDE } else { 0 };
+            }
+            let now = Instant::now();
+            match self.last {
+                Some((last, at)) if last == level && now - at < ONE_SHOT / 2 => return Ok(()),
+                None if level == 0 => return Ok(()),
+                _ => {}
+            }
+            if level == 0 {
+                return self.cancel();
+            }
+            let mut env = self.vm.attach_current_thread().map_err(backend)?;
+            let effect = env
+                .call_static_method(
+                    EFFECT,
+                    "createOneShot",
+                    "(JI)Landroid/os/VibrationEffect;",
+                    &[JValue::Long(ONE_SHOT.as_millis() as i64), JValue::Int(level)],
+                )
+                .and_then(|v| v.l())
+                .map_err(backend)?;
+            vibrate(&mut env, &self.vibrator, &effect)?;
+            self.last = Some((level, now));
+            Ok(())
+        }
+    }
+
+    impl Drop for AndroidVibrator {
+        fn drop(&mut self) {
+            let _ = self.cancel();
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/android_tests.rs"]
+mod tests;
@@ -0,0 +1,99 @@
+//! What a haptic device can render.
+//!
+//! Hardware spans two tiers: grounded force-feedback arms that run a servo loop
+//! at around 1 kHz, and vibration-only actuators (gamepad rumble, phone motors)
+//! that are updated at frame rate. Playback and rendering code branch on the
+//! tier rather than on the concrete backend.
+//!
+//! Axes beyond the 3-DOF position, such as the gripper of an omega.7 or sigma.7,
+//! are described by optional extensions that backends without them leave unset.
+
+use crate::core::{Hertz, Meters, Newtons, NewtonsPerMeter};
+
+/// Rendering tier of a device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeviceTier {
+    /// Grounded force feedback with a servo-rate control loop.
+    Kinesthetic,
+    /// Vibrotactile actuators only, driven at frame rate.
+    Vibrotactile,
+}
+
+/// A force-sensing grasp axis between thumb and finger.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GripperCapabilities {
+    /// Widest opening of the gripper.
+    pub max_gap: Meters,
+    /// Largest closing or opening force; zero for a passive gripper.
+    pub max_force: Newtons,
+}
+
+impl GripperCapabilities {
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.max_force.value() > 0.0
+    }
+}
+
+/// Static description of a device reported by its backend.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeviceCapabilities {
+    pub tier: DeviceTier,
+    /// Largest continuous force the device can output.
+    pub max_force: Newtons,
+    /// Largest stiffness the device renders stably.
+    pub max_stiffness: NewtonsPerMeter,
+    /// Rate at which the backend accepts new output.
+    pub update_rate: Hertz,
+    /// Number of independently driven vibration actuators.
+    pub actuators: usize,
+    /// Grasp axis, on devices that have one.
+    pub gripper: Option<GripperCapabilities>,
+}
+
+impl DeviceCapabilities {
+    /// Desktop force-feedback arm in the class of a Touch or Falcon.
+    pub const KINESTHETIC: Self = Self {
+        tier: DeviceTier::Kinesthetic,
+        max_force: Newtons(3.3),
+        max_stiffness: NewtonsPerMeter(1000.0),
+        update_rate: Hertz(1000.0),
+        actuators: 0,
+        gripper: None,
+    };
+
+    /// Dual-motor rumble controller.
+    pub const GAMEPAD: Self = Self {
+        tier: DeviceTier::Vibrotactile,
+        max_force: Newtons::ZERO,
+        max_stiffness: NewtonsPerMeter::ZERO,
+        update_rate: Hertz(60.0),
+        actuators: 2,
+        gripper: None,
+    };
+
+    pub const fn with_gripper(mut self, gripper: GripperCapabilities) -> Self {
+        self.gripper = Some(gripper);
+        self
+    }
+
+    #[inline]
+    pub fn renders_force(&self) -> bool {
+        self.tier == DeviceTier::Kinesthetic
+    }
+
+    #[inline]
+    pub fn renders_vibration(&self) -> bool {
+        self.actuators > 0
+    }
+
+    #[inline]
+    pub fn has_gripper(&self) -> bool {
+        self.gripper.is_some()
+    }
+
+    #[inline]
+    pub fn renders_gripper_force(&self) -> bool {
+        self.gripper.is_some_and(|g| g.is_active())
+    }
+}
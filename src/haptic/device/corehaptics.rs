@@ -0,0 +1,526 @@
+//! Apple Core Haptics (iPhone, iPad) and Force Touch trackpad output.
+//!
+//! Core Haptics plays patterns described in AHAP, Apple's JSON pattern format.
+//! [`AhapPattern`] translates the crate's vibrotactile signals into it: a
+//! [`KeyframeEffect`] becomes one continuous event whose intensity and sharpness
+//! control curves pass exactly through its keyframes, and any other
+//! [`HapticSignal`] is sampled and reduced to the points the curves need. A composed
+//! [`Effect`] keeps its transients as transient events. The resulting JSON can be shipped as an
+//! `.ahap` file or played directly by [`CoreHapticsDevice`].
+//!
+//! Mac trackpads only play three fixed feedback patterns on demand.
+//! [`TrackpadFeedback`] picks one from the sharpness of each onset in a sample
+//! stream, so UI effects still produce a tick on macOS.
+//!
+//! The devices need the `corehaptics` feature and an Apple target.
+
+use std::fmt::Write;
+
+use std::ops::Range;
+
+use crate::effects::{Effect, HapticSample, HapticSignal, KeyframeEffect};
+
+// ============================================================================
+// AHAP Patterns
+// ============================================================================
+
+/// Longest continuous event Core Haptics accepts, in seconds.
+pub const MAX_EVENT_DURATION: f32 = 30.0;
+
+/// Most control points Core Haptics accepts in one parameter curve.
+const MAX_CURVE_POINTS: usize = 16;
+
+/// Largest deviation tolerated when dropping points from a sampled curve.
+const CURVE_TOLERANCE: f32 = 0.01;
+
+/// Most samples a single curve segment may span, bounding the reduction cost.
+const MAX_SEGMENT_SAMPLES: usize = 256;
+
+/// A Core Haptics event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AhapEvent {
+    /// A short tap; its length is chosen by the hardware.
+    Transient { time: f32, sample: HapticSample },
+    /// A sustained vibration.
+    Continuous { time: f32, duration: f32, sample: HapticSample },
+}
+
+/// Which event parameter a curve modulates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AhapControl {
+    /// Multiplies the intensity of running events.
+    Intensity,
+    /// Added to the sharpness of running events.
+    Sharpness,
+}
+
+impl AhapEvent {
+    fn shifted(&self, offset: f32) -> Self {
+        match *self {
+            AhapEvent::Transient { time, sample } => AhapEvent::Transient { time: time + offset, sample },
+            AhapEvent::Continuous { time, duration, sample } => {
+                AhapEvent::Continuous { time: time + offset, duration, sample }
+            }
+        }
+    }
+}
+
+impl AhapControl {
+    fn id(self) -> &'static str {
+        match self {
+            AhapControl::Intensity => "HapticIntensityControl",
+            AhapControl::Sharpness => "HapticSharpnessControl",
+        }
+    }
+}
+
+/// A piecewise-linear control curve; point times are relative to `time`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AhapCurve {
+    pub control: AhapControl,
+    pub time: f32,
+    pub points: Vec<(f32, f32)>,
+}
+
+/// A pattern of events and control curves, serializable to AHAP.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AhapPattern {
+    pub events: Vec<AhapEvent>,
+    pub curves: Vec<AhapCurve>,
+}
+
+impl AhapPattern {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_transient(mut self, time: f32, sample: HapticSample) -> Self {
+        self.events.push(AhapEvent::Transient { time, sample });
+        self
+    }
+
+    /// Translates an effect exactly, with a control point per keyframe.
+    pub fn from_keyframes(effect: &KeyframeEffect) -> Self {
+        let points: Vec<(f32, HapticSample)> = effect
+            .keys()
+            .iter()
+            .map(|k| k.time / effect.speed)
+            .chain([0.0, effect.duration().unwrap_or(0.0)])
+            .map(|t| (t, effect.sample(t)))
+            .collect();
+        Self::from_points(points)
+    }
+
+    /// Samples `signal` every `resolution` seconds, keeping only the points needed
+    /// to follow it closely. Endless signals are cut at [`MAX_EVENT_DURATION`].
+    pub fn from_signal(signal: &dyn HapticSignal, resolution: f32) -> Self {
+        let duration = signal.duration().unwrap_or(MAX_EVENT_DURATION);
+        Self::from_points(reduce(&sample_grid(0.0..duration, resolution, |t| signal.sample(t))))
+    }
+
+    /// Compiles a composed effect: transients stay transient events, and each
+    /// stretch of continuous sections becomes a continuous event whose curves are
+    /// sampled every `resolution` seconds.
+    ///
+    /// Core Haptics applies the control curves to every event playing at the time,
+    /// so transients inside a stretch are compensated for its curves; they can be
+    /// no stronger than the stretch is at that moment.
+    pub fn from_effect(effect: &Effect, resolution: f32) -> Self {
+        let mut pattern = Self::new();
+        let mut shaped = Vec::new();
+        for span in spans(effect.sections()) {
+            let samples = sample_grid(span.clone(), resolution, |t| effect.sustained(t));
+            let part = Self::from_points(reduce(&samples).into_iter().map(|(t, s)| (t - span.start, s)).collect());
+            if !part.events.is_empty() {
+                pattern.events.extend(part.events.iter().map(|e| e.shifted(span.start)));
+                pattern.curves.extend(part.curves.into_iter().map(|c| AhapCurve { time: c.time + span.start, ..c }));
+                shaped.push(span);
+            }
+        }
+        for (time, mut sample) in effect.transients() {
+            if shaped.iter().any(|span| span.start <= time && time <= span.end) {
+                let control = effect.sustained(time);
+                if control.intensity > 0.0 {
+                    sample.intensity = (sample.intensity / control.intensity).min(1.0);
+                }
+                sample.sharpness = (sample.sharpness - control.sharpness).clamp(0.0, 1.0);
+            }
+            pattern.events.push(AhapEvent::Transient { time, sample });
+        }
+        pattern
+    }
+
+    /// One continuous event at full intensity, shaped by curves through `points`.
+    fn from_points(mut points: Vec<(f32, HapticSample)>) -> Self {
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        points.dedup_by(|b, a| (b.0 - a.0).abs() < 1e-6);
+        let duration = points.last().map_or(0.0, |p| p.0).min(MAX_EVENT_DURATION);
+        if duration <= 0.0 || points.iter().all(|(_, s)| s.intensity <= 0.0) {
+            return Self::new();
+        }
+        points.retain(|p| p.0 <= duration);
+
+        // The base event is at full intensity and zero sharpness, so the intensity
+        // control carries the intensity and the sharpness control the sharpness
+        let base = HapticSample::new(1.0, 0.0);
+        let mut pattern = Self::new();
+        pattern.events.push(AhapEvent::Continuous { time: 0.0, duration, sample: base });
+        for control in [AhapControl::Intensity, AhapControl::Sharpness] {
+            let value = |s: &HapticSample| match control {
+                AhapControl::Intensity => s.intensity,
+                AhapControl::Sharpness => s.sharpness,
+            };
+            // Consecutive curves share their boundary point so there is no gap
+            let mut start = 0;
+            while start + 1 < points.len() {
+                let end = (start + MAX_CURVE_POINTS).min(points.len());
+                let time = points[start].0;
+                let curve = points[start..end].iter().map(|(t, s)| (t - time, value(s))).collect();
+                pattern.curves.push(AhapCurve { control, time, points: curve });
+                start = end - 1;
+            }
+        }
+        pattern
+    }
+
+    /// Length until the last event ends.
+    pub fn duration(&self) -> f32 {
+        self.events
+            .iter()
+            .map(|e| match *e {
+                AhapEvent::Transient { time, .. } => time,
+                AhapEvent::Continuous { time, duration, .. } => time + duration,
+            })
+            .fold(0.0, f32::max)
+    }
+
+    /// The pattern as AHAP JSON.
+    pub fn to_json(&self) -> String {
+        let mut entries = Vec::new();
+        for event in &self.events {
+            let (kind, time, duration, sample) = match *event {
+                AhapEvent::Transient { time, sample } => ("HapticTransient", time, None, sample),
+                AhapEvent::Continuous { time, duration, sample } => ("HapticContinuous", time, Some(duration), sample),
+            };
+            let mut json = format!("{{\"Event\":{{\"Time\":{},\"EventType\":\"{}\"", number(time), kind);
+            if let Some(duration) = duration {
+                let _ = write!(json, ",\"EventDuration\":{}", number(duration));
+            }
+            let _ = write!(
+                json,
+                ",\"EventParameters\":[{},{}]}}}}",
+                parameter("HapticIntensity", sample.intensity),
+                parameter("HapticSharpness", sample.sharpness)
+            );
+            entries.push(json);
+        }
+        for curve in &self.curves {
+            let points: Vec<String> = curve
+                .points
+                .iter()
+                .map(|&(t, v)| format!("{{\"Time\":{},\"ParameterValue\":{}}}", number(t), number(v)))
+                .collect();
+            entries.push(format!(
+                "{{\"ParameterCurve\":{{\"ParameterID\":\"{}\",\"Time\":{},\"ParameterCurveControlPoints\":[{}]}}}}",
+                curve.control.id(),
+                number(curve.time),
+                points.join(",")
+            ));
+        }
+        format!("{{\"Version\":1.0,\"Pattern\":[{}]}}", entries.join(","))
+    }
+}
+
+fn parameter(id: &str, value: f32) -> String {
+    format!("{{\"ParameterID\":\"{}\",\"ParameterValue\":{}}}", id, number(value.clamp(0.0, 1.0)))
+}
+
+/// A JSON number; non-finite values, which JSON cannot express, become 0.
+fn number(value: f32) -> String {
+    if value.is_finite() {
+        format!("{:?}", value)
+    } else {
+        "0.0".to_string()
+    }
+}
+
+/// Samples over `range` at `resolution` spacing, both ends included.
+fn sample_grid(range: Range<f32>, resolution: f32, sample: impl Fn(f32) -> HapticSample) -> Vec<(f32, HapticSample)> {
+    let length = range.end - range.start;
+    let steps = (length / resolution.max(1e-3)).ceil().max(1.0) as usize;
+    (0..=steps)
+        .map(|i| range.start + length * i as f32 / steps as f32)
+        .map(|t| (t, sample(t)))
+        .collect()
+}
+
+/// Merges overlapping or touching ranges into sorted, disjoint spans.
+fn spans(ranges: impl Iterator<Item = Range<f32>>) -> Vec<Range<f32>> {
+    let mut ranges: Vec<Range<f32>> = ranges.filter(|r| r.end > r.start).collect();
+    ranges.sort_by(|a, b| a.start.total_cmp(&b.start));
+    let mut merged: Vec<Range<f32>> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Drops samples that linear interpolation between the kept ones reproduces.
+fn reduce(samples: &[(f32, HapticSample)]) -> Vec<(f32, HapticSample)> {
+    let Some(&first) = samples.first() else {
+        return Vec::new();
+    };
+    // Whether the line from `a` to `c` passes close to every sample between them
+    let fits = |a: usize, c: usize| {
+        let ((ta, sa), (tc, sc)) = (samples[a], samples[c]);
+        samples[a + 1..c].iter().all(|&(t, s)| {
+            let u = (t - ta) / (tc - ta).max(f32::EPSILON);
+            (sa.intensity + (sc.intensity - sa.intensity) * u - s.intensity).abs() <= CURVE_TOLERANCE
+                && (sa.sharpness + (sc.sharpness - sa.sharpness) * u - s.sharpness).abs() <= CURVE_TOLERANCE
+        })
+    };
+    let mut kept = vec![first];
+    let mut anchor = 0;
+    for next in 2..samples.len() {
+        if next - anchor > MAX_SEGMENT_SAMPLES || !fits(anchor, next) {
+            anchor = next - 1;
+            kept.push(samples[anchor]);
+        }
+    }
+    if samples.len() > 1 {
+        kept.push(samples[samples.len() - 1]);
+    }
+    kept
+}
+
+// ============================================================================
+// Trackpad
+// ============================================================================
+
+/// The feedback patterns a Force Touch trackpad can play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrackpadPattern {
+    Generic,
+    /// Crisp tick, meant for snapping into alignment.
+    Alignment,
+    /// Fuller click, meant for stepping between discrete levels.
+    LevelChange,
+}
+
+/// Turns a vibrotactile sample stream into discrete trackpad clicks: one click at
+/// each onset, where the intensity rises through the threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrackpadFeedback {
+    /// Intensity that counts as an onset.
+    pub threshold: f32,
+    active: bool,
+}
+
+impl Default for TrackpadFeedback {
+    fn default() -> Self {
+        Self::new(0.2)
+    }
+}
+
+impl TrackpadFeedback {
+    pub fn new(threshold: f32) -> Self {
+        Self { threshold, active: false }
+    }
+
+    /// Pattern to play for `sample`, if it starts an onset.
+    pub fn update(&mut self, sample: HapticSample) -> Option<TrackpadPattern> {
+        let was_active = std::mem::replace(&mut self.active, sample.intensity >= self.threshold);
+        if !self.active || was_active {
+            return None;
+        }
+        Some(match sample.sharpness {
+            s if s >= 2.0 / 3.0 => TrackpadPattern::Alignment,
+            s if s < 1.0 / 3.0 => TrackpadPattern::LevelChange,
+            _ => TrackpadPattern::Generic,
+        })
+    }
+}
+
+// ============================================================================
+// Devices
+// ============================================================================
+
+#[cfg(all(feature = "corehaptics", target_vendor = "apple"))]
+pub use self::apple::CoreHapticsDevice;
+#[cfg(all(feature = "corehaptics", target_os = "macos"))]
+pub use self::apple::TrackpadDevice;
+
+#[cfg(all(feature = "corehaptics", target_vendor = "apple"))]
+mod apple {
+    use objc2::rc::Retained;
+    use objc2::AllocAnyThread;
+    use objc2_core_haptics::{CHHapticDeviceCapability, CHHapticEngine};
+    use objc2_foundation::{NSData, NSError};
+
+    use super::{AhapEvent, AhapPattern};
+    use crate::core::{Hertz, Newtons, NewtonsPerMeter};
+    use crate::device::{DeviceCapabilities, DeviceError, DeviceTier, HapticDevice};
+    use crate::effects::HapticSample;
+
+    /// Frame-rate updates of a single actuator.
+    const CAPABILITIES: DeviceCapabilities = DeviceCapabilities {
+        tier: DeviceTier::Vibrotactile,
+        max_force: Newtons::ZERO,
+        max_stiffness: NewtonsPerMeter::ZERO,
+        update_rate: Hertz(60.0),
+        actuators: 1,
+        gripper: None,
+    };
+
+    fn backend(error: Retained<NSError>) -> DeviceError {
+        DeviceError::Backend(error.localizedDescription().to_string())
+    }
+
+    /// The Taptic Engine through a `CHHapticEngine`.
+    pub struct CoreHapticsDevice {
+        engine: Retained<CHHapticEngine>,
+        last: HapticSample,
+    }
+
+    // SAFETY: CHHapticEngine is documented as thread-safe; all calls go through
+    // `&mut self`, so they are also serialized.
+    unsafe impl Send for CoreHapticsDevice {}
+
+    impl CoreHapticsDevice {
+        /// Starts the engine; Unsupported on hardware without haptics.
+        pub fn open() -> Result<Self, DeviceError> {
+            // SAFETY: plain Core Haptics calls with no pointers beyond those objc2 manages.
+            unsafe {
+                if !CHHapticEngine::capabilitiesForHardware().supportsHaptics() {
+                    return Err(DeviceError::Unsupported);
+                }
+                let engine = CHHapticEngine::initAndReturnError(CHHapticEngine::alloc()).map_err(backend)?;
+                engine.startAndReturnError().map_err(backend)?;
+                Ok(Self { engine, last: HapticSample::SILENT })
+            }
+        }
+
+        /// Plays `pattern` once, mixed with anything already playing.
+        pub fn play(&mut self, pattern: &AhapPattern) -> Result<(), DeviceError> {
+            if pattern.events.is_empty() {
+                return Ok(());
+            }
+            let data = NSData::with_bytes(pattern.to_json().as_bytes());
+            // SAFETY: `data` holds a complete AHAP document.
+            unsafe {
+                if self.engine.playPatternFromData_error(&data).is_ok() {
+                    return Ok(());
+                }
+                // The system stops the engine on interruptions; restart it once
+                self.engine.startAndReturnError().map_err(backend)?;
+                self.engine.playPatternFromData_error(&data).map_err(backend)
+            }
+        }
+    }
+
+    impl HapticDevice for CoreHapticsDevice {
+        fn name(&self) -> &str {
+            "Core Haptics"
+        }
+
+        fn capabilities(&self) -> DeviceCapabilities {
+            CAPABILITIES
+        }
+
+        /// Plays `sample` for one update period.
+        fn set_vibration(&mut self, actuator: usize, sample: HapticSample) -> Result<(), DeviceError> {
+            if actuator != 0 {
+                return Err(DeviceError::InvalidActuator(actuator));
+            }
+            let silent = sample.intensity <= 0.0;
+            let was_silent = std::mem::replace(&mut self.last, sample).intensity <= 0.0;
+            if silent {
+                if !was_silent {
+                    // SAFETY: stops all players; the engine is restarted on the next play.
+                    unsafe { self.engine.stopWithCompletionHandler(None) };
+                }
+                return Ok(());
+            }
+            let duration = 1.0 / CAPABILITIES.update_rate.value();
+            let mut pattern = AhapPattern::new();
+            pattern.events.push(AhapEvent::Continuous { time: 0.0, duration, sample });
+            self.play(&pattern)
+        }
+    }
+
+    impl Drop for CoreHapticsDevice {
+        fn drop(&mut self) {
+            // SAFETY: stopping an engine is always allowed.
+            unsafe { self.engine.stopWithCompletionHandler(None) };
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    pub use self::trackpad::TrackpadDevice;
+
+    #[cfg(target_os = "macos")]
+    mod trackpad {
+        use objc2_app_kit::{
+            NSHapticFeedbackManager, NSHapticFeedbackPattern, NSHapticFeedbackPerformanceTime,
+            NSHapticFeedbackPerformer,
+        };
+
+        use super::CAPABILITIES;
+        use crate::device::corehaptics::{TrackpadFeedback, TrackpadPattern};
+        use crate::device::{DeviceCapabilities, DeviceError, HapticDevice};
+        use crate::effects::HapticSample;
+
+        /// The Force Touch trackpad, clicking at each onset of the sample stream.
+        #[derive(Debug, Default)]
+        pub struct TrackpadDevice {
+            feedback: TrackpadFeedback,
+        }
+
+        impl TrackpadDevice {
+            pub fn new(feedback: TrackpadFeedback) -> Self {
+                Self { feedback }
+            }
+
+            /// Plays `pattern` right away.
+            pub fn perform(&self, pattern: TrackpadPattern) {
+                let pattern = match pattern {
+                    TrackpadPattern::Generic => NSHapticFeedbackPattern::Generic,
+                    TrackpadPattern::Alignment => NSHapticFeedbackPattern::Alignment,
+                    TrackpadPattern::LevelChange => NSHapticFeedbackPattern::LevelChange,
+                };
+                // SAFETY: the default performer is a process-wide singleton.
+                unsafe {
+                    NSHapticFeedbackManager::defaultPerformer()
+                        .performFeedbackPattern_performanceTime(pattern, NSHapticFeedbackPerformanceTime::Now);
+                }
+            }
+        }
+
+        impl HapticDevice for TrackpadDevice {
+            fn name(&self) -> &str {
+                "Force Touch trackpad"
+            }
+
+            fn capabilities(&self) -> DeviceCapabilities {
+                CAPABILITIES
+            }
+
+            fn set_vibration(&mut self, actuator: usize, sample: HapticSample) -> Result<(), DeviceError> {
+                if actuator != 0 {
+                    return Err(DeviceError::InvalidActuator(actuator));
+                }
+                if let Some(pattern) = self.feedback.update(sample) {
+                    self.perform(pattern);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/corehaptics_tests.rs"]
+mod tests;
@@ -0,0 +1,391 @@
+//! PlayStation 5 DualSense: adaptive triggers and voice-coil haptics.
+//!
+//! The controller has two kinds of haptic output. Each trigger has a motor that can
+//! resist the finger: a [`TriggerEffect`] describes the resistance across the
+//! trigger travel (split into ten zones) and is uploaded once, after which the
+//! controller renders it on its own. The grips hold two voice-coil actuators that
+//! reproduce arbitrary waveforms up to about 1 kHz. Over HID they only accept a
+//! rumble emulation (two levels, as on older pads); their full bandwidth is reached
+//! through channels 3 and 4 of the controller's USB audio interface, for which
+//! [`VoiceCoil`] synthesizes PCM from [`HapticSample`]s.
+//!
+//! [`OutputReport`] encodes the USB HID output report. The reverse-engineered
+//! layout follows the Linux `hid-playstation` driver and the community trigger
+//! effect documentation. With the `dualsense` feature, [`DualSenseDevice`] sends it
+//! through hidapi and implements [`HapticDevice`](super::HapticDevice).
+
+use std::f32::consts::TAU;
+
+use super::rumble::RumbleLevels;
+use crate::core::Hertz;
+use crate::effects::{HapticSample, HapticSignal};
+
+// ============================================================================
+// Adaptive Triggers
+// ============================================================================
+
+/// Number of resistance zones along the trigger travel.
+pub const TRIGGER_ZONES: usize = 10;
+
+/// Which trigger an effect is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Trigger {
+    Left,
+    Right,
+}
+
+/// Resistance program for one adaptive trigger. Positions run from 0 (released)
+/// to 1 (fully pulled); strengths and amplitudes from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TriggerEffect {
+    /// No resistance.
+    Off,
+    /// Constant resistance per zone, for arbitrary force curves along the travel.
+    Resistance { zones: [f32; TRIGGER_ZONES] },
+    /// Resistance between `start` and `end` that gives way past `end`, like a
+    /// gun trigger breaking.
+    Weapon { start: f32, end: f32, strength: f32 },
+    /// Vibration of the trigger from `start` onward.
+    Vibration { start: f32, amplitude: f32, frequency: Hertz },
+}
+
+impl TriggerEffect {
+    /// Uniform resistance from `start` to the end of the travel.
+    pub fn feedback(start: f32, strength: f32) -> Self {
+        let first = zone(start);
+        let mut zones = [0.0; TRIGGER_ZONES];
+        zones[first..].fill(strength);
+        TriggerEffect::Resistance { zones }
+    }
+
+    /// Resistance sampled from `curve` at each zone's center position.
+    pub fn curve(curve: impl Fn(f32) -> f32) -> Self {
+        let zones = std::array::from_fn(|i| curve((i as f32 + 0.5) / TRIGGER_ZONES as f32));
+        TriggerEffect::Resistance { zones }
+    }
+
+    /// The 11-byte block (mode and parameters) of the output report.
+    pub fn encode(&self) -> [u8; 11] {
+        let mut block = [0u8; 11];
+        match *self {
+            TriggerEffect::Off => block[0] = 0x05,
+            TriggerEffect::Resistance { zones } => {
+                let (mut active, mut forces) = (0u16, 0u32);
+                for (i, strength) in zones.iter().enumerate() {
+                    if let Some(level) = level(*strength) {
+                        active |= 1 << i;
+                        forces |= u32::from(level) << (3 * i);
+                    }
+                }
+                if active == 0 {
+                    block[0] = 0x05;
+                } else {
+                    block[0] = 0x21;
+                    block[1..3].copy_from_slice(&active.to_le_bytes());
+                    block[3..7].copy_from_slice(&forces.to_le_bytes());
+                }
+            }
+            TriggerEffect::Weapon { start, end, strength } => {
+                // The break must fall in zones 2..=8, after a start in 2..=7
+                let start = zone(start).clamp(2, 7);
+                let end = zone(end).clamp(start + 1, 8);
+                match level(strength) {
+                    Some(level) => {
+                        let zones = (1u16 << start) | (1u16 << end);
+                        block[0] = 0x25;
+                        block[1..3].copy_from_slice(&zones.to_le_bytes());
+                        block[3] = level;
+                    }
+                    None => block[0] = 0x05,
+                }
+            }
+            TriggerEffect::Vibration { start, amplitude, frequency } => {
+                let frequency = frequency.value().round().clamp(0.0, 255.0) as u8;
+                match level(amplitude) {
+                    Some(level) if frequency > 0 => {
+                        let (mut active, mut amplitudes) = (0u16, 0u32);
+                        for i in zone(start)..TRIGGER_ZONES {
+                            active |= 1 << i;
+                            amplitudes |= u32::from(level) << (3 * i);
+                        }
+                        block[0] = 0x26;
+                        block[1..3].copy_from_slice(&active.to_le_bytes());
+                        block[3..7].copy_from_slice(&amplitudes.to_le_bytes());
+                        block[9] = frequency;
+                    }
+                    _ => block[0] = 0x05,
+                }
+            }
+        }
+        block
+    }
+}
+
+/// Zone index containing travel position `position`.
+fn zone(position: f32) -> usize {
+    let position = if position.is_nan() { 0.0 } else { position.clamp(0.0, 1.0) };
+    ((position * TRIGGER_ZONES as f32) as usize).min(TRIGGER_ZONES - 1)
+}
+
+/// 3-bit hardware level (0 = weakest, 7 = strongest) for a strength in 0..=1, or
+/// None for no effect.
+fn level(strength: f32) -> Option<u8> {
+    (strength > 0.0).then(|| (strength.min(1.0) * 8.0).ceil().clamp(1.0, 8.0) as u8 - 1)
+}
+
+// ============================================================================
+// Output Report
+// ============================================================================
+
+/// Length of the USB output report, including its id.
+pub const USB_REPORT_LEN: usize = 48;
+
+const USB_REPORT_ID: u8 = 0x02;
+const FLAG_RUMBLE: u8 = 0x01;
+const FLAG_HAPTICS_SELECT: u8 = 0x02;
+const FLAG_RIGHT_TRIGGER: u8 = 0x04;
+const FLAG_LEFT_TRIGGER: u8 = 0x08;
+const RIGHT_TRIGGER_OFFSET: usize = 11;
+const LEFT_TRIGGER_OFFSET: usize = 22;
+
+/// State to send to the controller. Triggers left at None keep their current effect.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OutputReport {
+    /// Rumble emulation on the voice coils.
+    pub rumble: RumbleLevels,
+    pub left_trigger: Option<TriggerEffect>,
+    pub right_trigger: Option<TriggerEffect>,
+}
+
+impl OutputReport {
+    pub fn with_trigger(mut self, trigger: Trigger, effect: TriggerEffect) -> Self {
+        match trigger {
+            Trigger::Left => self.left_trigger = Some(effect),
+            Trigger::Right => self.right_trigger = Some(effect),
+        }
+        self
+    }
+
+    /// The report for a USB connection.
+    pub fn to_usb(&self) -> [u8; USB_REPORT_LEN] {
+        let mut report = [0u8; USB_REPORT_LEN];
+        report[0] = USB_REPORT_ID;
+        report[1] = FLAG_RUMBLE | FLAG_HAPTICS_SELECT;
+        // The right motor is the light, high-frequency one
+        report[3] = (self.rumble.weak.clamp(0.0, 1.0) * 255.0).round() as u8;
+        report[4] = (self.rumble.strong.clamp(0.0, 1.0) * 255.0).round() as u8;
+        if let Some(effect) = self.right_trigger {
+            report[1] |= FLAG_RIGHT_TRIGGER;
+            report[RIGHT_TRIGGER_OFFSET..RIGHT_TRIGGER_OFFSET + 11].copy_from_slice(&effect.encode());
+        }
+        if let Some(effect) = self.left_trigger {
+            report[1] |= FLAG_LEFT_TRIGGER;
+            report[LEFT_TRIGGER_OFFSET..LEFT_TRIGGER_OFFSET + 11].copy_from_slice(&effect.encode());
+        }
+        report
+    }
+}
+
+// ============================================================================
+// Voice Coils
+// ============================================================================
+
+/// Sample rate of the controller's USB audio interface.
+pub const AUDIO_RATE: Hertz = Hertz(48_000.0);
+
+/// Lowest and highest carrier frequency, for sharpness 0 and 1.
+const CARRIER_RANGE: (f32, f32) = (60.0, 320.0);
+
+/// Interval at which signals are sampled while rendering PCM.
+const CONTROL_FRAMES: usize = 48;
+
+/// Carrier frequency rendering `sharpness`: duller samples vibrate lower.
+pub fn carrier_frequency(sharpness: f32) -> Hertz {
+    let (low, high) = CARRIER_RANGE;
+    Hertz(low + (high - low) * sharpness.clamp(0.0, 1.0))
+}
+
+/// Synthesizes voice-coil PCM (left and right actuator) from haptic samples.
+///
+/// Each actuator plays a sine carrier whose amplitude is the sample intensity and
+/// whose frequency follows its sharpness. Phase is continuous and amplitude is
+/// ramped across each buffer, so changing samples never clicks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoiceCoil {
+    sample_rate: Hertz,
+    phase: [f32; 2],
+    amplitude: [f32; 2],
+}
+
+impl Default for VoiceCoil {
+    fn default() -> Self {
+        Self::new(AUDIO_RATE)
+    }
+}
+
+impl VoiceCoil {
+    pub fn new(sample_rate: Hertz) -> Self {
+        Self { sample_rate, phase: [0.0; 2], amplitude: [0.0; 2] }
+    }
+
+    #[inline]
+    pub fn sample_rate(&self) -> Hertz {
+        self.sample_rate
+    }
+
+    /// Fills `out` with stereo frames moving from the previous samples to these.
+    pub fn render(&mut self, left: HapticSample, right: HapticSample, out: &mut [[f32; 2]]) {
+        let rate = self.sample_rate.value();
+        for (channel, sample) in [left, right].into_iter().enumerate() {
+            let from = self.amplitude[channel];
+            let to = if sample.intensity.is_nan() { 0.0 } else { sample.intensity.clamp(0.0, 1.0) };
+            let step = TAU * carrier_frequency(sample.sharpness).value() / rate;
+            let count = out.len().max(1) as f32;
+            let mut phase = self.phase[channel];
+            for (i, frame) in out.iter_mut().enumerate() {
+                let amplitude = from + (to - from) * (i + 1) as f32 / count;
+                frame[channel] = amplitude * phase.sin();
+                phase = (phase + step) % TAU;
+            }
+            self.phase[channel] = phase;
+            if !out.is_empty() {
+                self.amplitude[channel] = to;
+            }
+        }
+    }
+
+    /// Fills `out` with `signal` on both actuators from `start` seconds, and returns
+    /// the time just past the rendered frames.
+    pub fn render_signal(&mut self, signal: &dyn HapticSignal, start: f32, out: &mut [[f32; 2]]) -> f32 {
+        let period = 1.0 / self.sample_rate.value();
+        let mut t = start;
+        for chunk in out.chunks_mut(CONTROL_FRAMES) {
+            let sample = signal.sample(t);
+            self.render(sample, sample, chunk);
+            t += chunk.len() as f32 * period;
+        }
+        t
+    }
+
+    /// Ramps both actuators to silence over `out`.
+    pub fn silence(&mut self, out: &mut [[f32; 2]]) {
+        self.render(HapticSample::SILENT, HapticSample::SILENT, out);
+    }
+}
+
+// ============================================================================
+// HID Device
+// ============================================================================
+
+#[cfg(feature = "dualsense")]
+pub use self::hid::DualSenseDevice;
+
+#[cfg(feature = "dualsense")]
+mod hid {
+    use hidapi::{HidApi, HidDevice};
+
+    use super::{OutputReport, Trigger, TriggerEffect};
+    use crate::core::{Hertz, Newtons, NewtonsPerMeter};
+    use crate::device::{DeviceCapabilities, DeviceError, DeviceTier, HapticDevice, RumbleLevels};
+    use crate::effects::HapticSample;
+
+    const SONY: u16 = 0x054c;
+    /// DualSense and DualSense Edge.
+    const PRODUCTS: [u16; 2] = [0x0ce6, 0x0df2];
+
+    const CAPABILITIES: DeviceCapabilities = DeviceCapabilities {
+        tier: DeviceTier::Vibrotactile,
+        max_force: Newtons::ZERO,
+        max_stiffness: NewtonsPerMeter::ZERO,
+        update_rate: Hertz(250.0),
+        actuators: 2,
+        gripper: None,
+    };
+
+    /// A DualSense connected over USB.
+    ///
+    /// Actuators 0 and 1 drive the left and right voice coil in rumble emulation.
+    /// Bluetooth uses a different, checksummed report and is not supported.
+    pub struct DualSenseDevice {
+        device: HidDevice,
+        name: String,
+        report: OutputReport,
+    }
+
+    impl DualSenseDevice {
+        /// Opens the first DualSense found.
+        pub fn open() -> Result<Self, DeviceError> {
+            let api = HidApi::new().map_err(|e| DeviceError::Backend(e.to_string()))?;
+            let device = PRODUCTS
+                .iter()
+                .find_map(|&product| api.open(SONY, product).ok())
+                .ok_or(DeviceError::Disconnected)?;
+            let name = device
+                .get_product_string()
+                .ok()
+                .flatten()
+                .unwrap_or_else(|| "DualSense Wireless Controller".to_string());
+            let mut device = Self { device, name, report: OutputReport::default() };
+            device.report = device.report.with_trigger(Trigger::Left, TriggerEffect::Off);
+            device.report = device.report.with_trigger(Trigger::Right, TriggerEffect::Off);
+            device.send()?;
+            Ok(device)
+        }
+
+        /// Uploads `effect` to `trigger`; it stays active until replaced.
+        pub fn set_trigger(&mut self, trigger: Trigger, effect: TriggerEffect) -> Result<(), DeviceError> {
+            self.report = self.report.with_trigger(trigger, effect);
+            self.send()
+        }
+
+        /// Sets both voice coils at once.
+        pub fn set_rumble(&mut self, levels: RumbleLevels) -> Result<(), DeviceError> {
+            self.report.rumble = RumbleLevels::new(levels.strong, levels.weak);
+            self.send()
+        }
+
+        /// Current output state.
+        #[inline]
+        pub fn report(&self) -> &OutputReport {
+            &self.report
+        }
+
+        fn send(&mut self) -> Result<(), DeviceError> {
+            self.device.write(&self.report.to_usb()).map_err(|_| DeviceError::Disconnected)?;
+            Ok(())
+        }
+    }
+
+    impl HapticDevice for DualSenseDevice {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn capabilities(&self) -> DeviceCapabilities {
+            CAPABILITIES
+        }
+
+        fn set_vibration(&mut self, actuator: usize, sample: HapticSample) -> Result<(), DeviceError> {
+            let mut levels = self.report.rumble;
+            match actuator {
+                0 => levels.strong = sample.intensity,
+                1 => levels.weak = sample.intensity,
+                _ => return Err(DeviceError::InvalidActuator(actuator)),
+            }
+            self.set_rumble(levels)
+        }
+    }
+
+    impl Drop for DualSenseDevice {
+        fn drop(&mut self) {
+            self.report = OutputReport::default()
+                .with_trigger(Trigger::Left, TriggerEffect::Off)
+                .with_trigger(Trigger::Right, TriggerEffect::Off);
+            let _ = self.send();
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/dualsense_tests.rs"]
+mod tests;
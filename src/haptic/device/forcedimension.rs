@@ -0,0 +1,261 @@
+//! Backend for Force Dimension devices (omega.x, delta.x, sigma.x, lambda.x)
+//! through the DHD SDK.
+//!
+//! Unlike OpenHaptics, DHD has no scheduler of its own: every call talks to the
+//! device directly, so [`ForceDimensionDevice`] is driven from the application's
+//! servo thread (see [`HapticLoop`](crate::render::HapticLoop)). Position,
+//! orientation and force already use meters and newtons.
+//!
+//! Models with a gripper (omega.7, sigma.7, lambda.7) report it through
+//! [`GripperCapabilities`] and the gripper methods of [`HapticDevice`]. DHD sets the
+//! translational force and the gripper force in one command, so both calls resend
+//! the latest value of the other.
+//!
+//! Requires the `forcedimension` feature and the DHD library (`libdhd` /
+//! `dhdms64.dll`) at link time.
+
+use std::ffi::{c_char, c_double, c_int, CStr};
+
+use super::capabilities::{DeviceCapabilities, DeviceTier, GripperCapabilities};
+use super::interface::{DeviceError, DeviceState, GripperState, HapticDevice};
+use crate::core::{
+    Hertz, Meters, Meters3, MetersPerSecond, MetersPerSecond3, Newtons, Newtons3, NewtonsPerMeter, Quat, Vec3,
+};
+
+// ============================================================================
+// DHD API
+// ============================================================================
+
+mod ffi {
+    use super::*;
+
+    #[cfg_attr(windows, link(name = "dhdms64"))]
+    #[cfg_attr(not(windows), link(name = "dhd"))]
+    extern "system" {
+        pub fn dhdGetDeviceCount() -> c_int;
+        pub fn dhdOpen() -> c_int;
+        pub fn dhdOpenID(index: c_char) -> c_int;
+        pub fn dhdClose(id: c_char) -> c_int;
+        pub fn dhdGetSystemName(id: c_char) -> *const c_char;
+        pub fn dhdHasGripper(id: c_char) -> bool;
+        pub fn dhdHasActiveGripper(id: c_char) -> bool;
+        pub fn dhdEnableForce(on: u8, id: c_char) -> c_int;
+        pub fn dhdGetPosition(px: *mut c_double, py: *mut c_double, pz: *mut c_double, id: c_char) -> c_int;
+        pub fn dhdGetLinearVelocity(vx: *mut c_double, vy: *mut c_double, vz: *mut c_double, id: c_char) -> c_int;
+        pub fn dhdGetOrientationFrame(matrix: *mut [c_double; 3], id: c_char) -> c_int;
+        pub fn dhdGetButtonMask(id: c_char) -> u32;
+        pub fn dhdGetGripperGap(gap: *mut c_double, id: c_char) -> c_int;
+        pub fn dhdGetGripperLinearVelocity(velocity: *mut c_double, id: c_char) -> c_int;
+        pub fn dhdSetForceAndTorqueAndGripperForce(
+            fx: c_double,
+            fy: c_double,
+            fz: c_double,
+            tx: c_double,
+            ty: c_double,
+            tz: c_double,
+            fg: c_double,
+            id: c_char,
+        ) -> c_int;
+        pub fn dhdErrorGetLastStr() -> *const c_char;
+    }
+}
+
+/// Turns a DHD status into a result. Negative values are errors; positive ones
+/// are warnings such as a saturated motor, which still apply the command.
+fn check(status: c_int) -> Result<(), DeviceError> {
+    if status >= 0 {
+        return Ok(());
+    }
+    // SAFETY: DHD returns a static, NUL-terminated description of the last error.
+    let text = unsafe { CStr::from_ptr(ffi::dhdErrorGetLastStr()) };
+    Err(DeviceError::Backend(format!("DHD: {}", text.to_string_lossy())))
+}
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// Nominal ratings of a device family, from the vendor data sheets.
+struct Ratings {
+    max_force: f32,
+    max_stiffness: f32,
+    gripper_gap: f32,
+    gripper_force: f32,
+}
+
+fn ratings(name: &str) -> Ratings {
+    let name = name.to_ascii_lowercase();
+    // The omega family is the weakest; unknown models get its ratings
+    let max_force = if ["delta", "sigma", "lambda"].iter().any(|family| name.starts_with(family)) {
+        20.0
+    } else {
+        12.0
+    };
+    Ratings { max_force, max_stiffness: 14_500.0, gripper_gap: 0.025, gripper_force: 8.0 }
+}
+
+// ============================================================================
+// Device
+// ============================================================================
+
+/// An open Force Dimension device.
+pub struct ForceDimensionDevice {
+    id: c_char,
+    name: String,
+    capabilities: DeviceCapabilities,
+    force: Newtons3,
+    gripper_force: Newtons,
+}
+
+impl ForceDimensionDevice {
+    /// Number of Force Dimension devices connected.
+    pub fn count() -> usize {
+        // SAFETY: takes no arguments and only enumerates USB devices.
+        unsafe { ffi::dhdGetDeviceCount() }.max(0) as usize
+    }
+
+    /// Opens the first available device.
+    pub fn open_default() -> Result<Self, DeviceError> {
+        Self::open(None)
+    }
+
+    /// Opens the device at `index` (in `0..count()`), or the first available one for
+    /// None, and enables force output at zero force.
+    pub fn open(index: Option<usize>) -> Result<Self, DeviceError> {
+        let index = index
+            .map(|i| c_char::try_from(i).map_err(|_| DeviceError::Backend(format!("no device at index {}", i))))
+            .transpose()?;
+        // SAFETY: opening takes no pointers; a negative result means no device.
+        let id = unsafe {
+            match index {
+                Some(i) => ffi::dhdOpenID(i),
+                None => ffi::dhdOpen(),
+            }
+        };
+        if id < 0 {
+            return Err(check(id).err().unwrap_or(DeviceError::Disconnected));
+        }
+        let id = id as c_char;
+
+        // SAFETY: `id` was just returned by dhdOpen; the name is a static string.
+        let (name, has_gripper, active_gripper) = unsafe {
+            let name = ffi::dhdGetSystemName(id);
+            let name = if name.is_null() {
+                "Force Dimension device".to_string()
+            } else {
+                CStr::from_ptr(name).to_string_lossy().into_owned()
+            };
+            (name, ffi::dhdHasGripper(id), ffi::dhdHasActiveGripper(id))
+        };
+        let r = ratings(&name);
+        let mut capabilities = DeviceCapabilities {
+            tier: DeviceTier::Kinesthetic,
+            max_force: Newtons(r.max_force),
+            max_stiffness: NewtonsPerMeter(r.max_stiffness),
+            // DHD sustains several kHz, but the servo loop drives it at a nominal 1 kHz
+            update_rate: Hertz(1000.0),
+            actuators: 0,
+            gripper: None,
+        };
+        if has_gripper {
+            capabilities = capabilities.with_gripper(GripperCapabilities {
+                max_gap: Meters(r.gripper_gap),
+                max_force: if active_gripper { Newtons(r.gripper_force) } else { Newtons::ZERO },
+            });
+        }
+
+        let mut device = Self { id, name, capabilities, force: Newtons3::ZERO, gripper_force: Newtons::ZERO };
+        device.send()?;
+        // SAFETY: valid id; forces were zeroed above.
+        check(unsafe { ffi::dhdEnableForce(1, id) })?;
+        Ok(device)
+    }
+
+    /// Sends the stored force and gripper force.
+    fn send(&mut self) -> Result<(), DeviceError> {
+        let f = self.force.0;
+        let fg = self.gripper_force.value();
+        // SAFETY: valid id; the device ignores the gripper force without an active gripper.
+        check(unsafe {
+            ffi::dhdSetForceAndTorqueAndGripperForce(
+                f64::from(f.x),
+                f64::from(f.y),
+                f64::from(f.z),
+                0.0,
+                0.0,
+                0.0,
+                f64::from(fg),
+                self.id,
+            )
+        })
+    }
+}
+
+impl HapticDevice for ForceDimensionDevice {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn capabilities(&self) -> DeviceCapabilities {
+        self.capabilities
+    }
+
+    fn poll(&mut self) -> Result<DeviceState, DeviceError> {
+        let (mut p, mut v, mut frame) = ([0.0f64; 3], [0.0f64; 3], [[0.0f64; 3]; 3]);
+        // SAFETY: valid id; every pointer refers to a live local of the right size.
+        let buttons = unsafe {
+            check(ffi::dhdGetPosition(&mut p[0], &mut p[1], &mut p[2], self.id))?;
+            check(ffi::dhdGetLinearVelocity(&mut v[0], &mut v[1], &mut v[2], self.id))?;
+            check(ffi::dhdGetOrientationFrame(frame.as_mut_ptr(), self.id))?;
+            ffi::dhdGetButtonMask(self.id)
+        };
+        let vec = |v: [f64; 3]| Vec3::new(v[0] as f32, v[1] as f32, v[2] as f32);
+        // Row-major rotation matrix: column c is the device's c-th axis
+        let column = |c: usize| Vec3::new(frame[0][c] as f32, frame[1][c] as f32, frame[2][c] as f32);
+        Ok(DeviceState {
+            position: Meters3(vec(p)),
+            orientation: Quat::from_rotation_axes(column(0), column(1), column(2)),
+            velocity: MetersPerSecond3(vec(v)),
+            buttons,
+        })
+    }
+
+    fn set_force(&mut self, force: Newtons3) -> Result<(), DeviceError> {
+        self.force = force;
+        self.send()
+    }
+
+    fn poll_gripper(&mut self) -> Result<GripperState, DeviceError> {
+        if !self.capabilities.has_gripper() {
+            return Err(DeviceError::Unsupported);
+        }
+        let (mut gap, mut velocity) = (0.0f64, 0.0f64);
+        // SAFETY: valid id and live locals.
+        unsafe {
+            check(ffi::dhdGetGripperGap(&mut gap, self.id))?;
+            check(ffi::dhdGetGripperLinearVelocity(&mut velocity, self.id))?;
+        }
+        Ok(GripperState { gap: Meters(gap as f32), velocity: MetersPerSecond(velocity as f32) })
+    }
+
+    fn set_gripper_force(&mut self, force: Newtons) -> Result<(), DeviceError> {
+        if !self.capabilities.renders_gripper_force() {
+            return Err(DeviceError::Unsupported);
+        }
+        self.gripper_force = force;
+        self.send()
+    }
+}
+
+impl Drop for ForceDimensionDevice {
+    fn drop(&mut self) {
+        self.force = Newtons3::ZERO;
+        self.gripper_force = Newtons::ZERO;
+        let _ = self.send();
+        // SAFETY: valid id, closed exactly once.
+        unsafe {
+            ffi::dhdEnableForce(0, self.id);
+            ffi::dhdClose(self.id);
+        }
+    }
+}
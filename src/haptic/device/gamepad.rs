@@ -0,0 +1,253 @@
+//! Gamepad rumble through gilrs, as a low-fidelity stand-in for a haptic device.
+//!
+//! gilrs is not `Send` on every platform and expects its event queue to be pumped
+//! regularly, so [`GamepadDevice`] keeps it on a small thread of its own and only
+//! sends motor levels across. Each motor is a looping gilrs effect at full
+//! magnitude whose gain is updated, which avoids re-uploading effects every frame.
+//!
+//! Besides the two actuators (0 = strong, 1 = weak), the device accepts
+//! `set_force`, translating forces with a [`RumbleMapping`], so force-rendered
+//! scenes can be previewed on a gamepad. Its capabilities still report the
+//! vibrotactile tier, so code that branches on the tier picks vibration effects.
+//!
+//! Requires the `gamepad` feature.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use gilrs::ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Repeat, Replay, Ticks};
+use gilrs::{EventType, GamepadId, Gilrs};
+
+use super::capabilities::DeviceCapabilities;
+use super::interface::{DeviceError, DeviceState, HapticDevice};
+use super::rumble::{RumbleLevels, RumbleMapping};
+use crate::core::{Newtons3, Seconds};
+use crate::effects::HapticSample;
+
+/// How often the gilrs thread pumps events while no levels arrive.
+const PUMP_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Longest gap between forces still treated as one stream for impact cues.
+const MAX_FORCE_GAP: Duration = Duration::from_millis(100);
+
+/// A force-feedback capable gamepad driven as a two-actuator device.
+pub struct GamepadDevice {
+    name: String,
+    mapping: RumbleMapping,
+    levels: RumbleLevels,
+    last_force: Option<Instant>,
+    commands: Option<Sender<RumbleLevels>>,
+    connected: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl GamepadDevice {
+    /// Opens the first connected gamepad that supports force feedback.
+    pub fn open_default() -> Result<Self, DeviceError> {
+        Self::open(None)
+    }
+
+    /// Opens the `index`-th connected gamepad with force feedback, or the first for None.
+    pub fn open(index: Option<usize>) -> Result<Self, DeviceError> {
+        let (commands, receiver) = mpsc::channel();
+        let (opened, result) = mpsc::sync_channel(1);
+        let connected = Arc::new(AtomicBool::new(true));
+        let flag = connected.clone();
+        let thread = thread::Builder::new()
+            .name("haptic-gamepad".into())
+            .spawn(move || {
+                let mut rumble = match Rumble::open(index.unwrap_or(0)) {
+                    Ok((rumble, name)) => {
+                        let _ = opened.send(Ok(name));
+                        rumble
+                    }
+                    Err(error) => {
+                        let _ = opened.send(Err(error));
+                        return;
+                    }
+                };
+                rumble.run(&receiver, &flag);
+            })
+            .map_err(|e| DeviceError::Backend(e.to_string()))?;
+
+        let name = result.recv().unwrap_or_else(|_| Err(DeviceError::Backend("gamepad thread exited".into())));
+        match name {
+            Ok(name) => Ok(Self {
+                name,
+                mapping: RumbleMapping::default(),
+                levels: RumbleLevels::OFF,
+                last_force: None,
+                commands: Some(commands),
+                connected,
+                thread: Some(thread),
+            }),
+            Err(error) => {
+                let _ = thread.join();
+                Err(error)
+            }
+        }
+    }
+
+    /// Uses `mapping` to turn forces into rumble.
+    pub fn with_mapping(mut self, mapping: RumbleMapping) -> Self {
+        self.mapping = mapping;
+        self
+    }
+
+    #[inline]
+    pub fn mapping(&self) -> &RumbleMapping {
+        &self.mapping
+    }
+
+    /// Current motor levels.
+    #[inline]
+    pub fn levels(&self) -> RumbleLevels {
+        self.levels
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Acquire)
+    }
+
+    /// Sets both motors at once.
+    pub fn set_rumble(&mut self, levels: RumbleLevels) -> Result<(), DeviceError> {
+        if !self.is_connected() {
+            return Err(DeviceError::Disconnected);
+        }
+        self.levels = RumbleLevels::new(levels.strong, levels.weak);
+        self.commands
+            .as_ref()
+            .and_then(|c| c.send(self.levels).ok())
+            .ok_or(DeviceError::Disconnected)
+    }
+}
+
+impl HapticDevice for GamepadDevice {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn capabilities(&self) -> DeviceCapabilities {
+        DeviceCapabilities::GAMEPAD
+    }
+
+    /// Gamepads have no pose; this only reports disconnection.
+    fn poll(&mut self) -> Result<DeviceState, DeviceError> {
+        if !self.is_connected() {
+            return Err(DeviceError::Disconnected);
+        }
+        Ok(DeviceState::default())
+    }
+
+    /// Previews `force` as rumble through the device's [`RumbleMapping`].
+    fn set_force(&mut self, force: Newtons3) -> Result<(), DeviceError> {
+        let now = Instant::now();
+        let dt = match self.last_force.replace(now) {
+            Some(last) if now - last <= MAX_FORCE_GAP => now - last,
+            _ => {
+                self.mapping.reset();
+                Duration::ZERO
+            }
+        };
+        let levels = self.mapping.force(force, Seconds(dt.as_secs_f32()));
+        self.set_rumble(levels)
+    }
+
+    fn set_vibration(&mut self, actuator: usize, sample: HapticSample) -> Result<(), DeviceError> {
+        let mut levels = self.levels;
+        match actuator {
+            0 => levels.strong = sample.intensity,
+            1 => levels.weak = sample.intensity,
+            _ => return Err(DeviceError::InvalidActuator(actuator)),
+        }
+        self.set_rumble(levels)
+    }
+}
+
+impl Drop for GamepadDevice {
+    fn drop(&mut self) {
+        // Closing the channel stops the motors and ends the thread
+        self.commands = None;
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+// ============================================================================
+// gilrs Thread
+// ============================================================================
+
+/// State owned by the gilrs thread.
+struct Rumble {
+    gilrs: Gilrs,
+    id: GamepadId,
+    strong: Effect,
+    weak: Effect,
+}
+
+impl Rumble {
+    fn open(index: usize) -> Result<(Self, String), DeviceError> {
+        let mut gilrs = Gilrs::new().map_err(|e| DeviceError::Backend(e.to_string()))?;
+        let (id, name) = gilrs
+            .gamepads()
+            .filter(|(_, pad)| pad.is_ff_supported())
+            .nth(index)
+            .map(|(id, pad)| (id, pad.name().to_string()))
+            .ok_or(DeviceError::Disconnected)?;
+
+        let mut effect = |kind| {
+            EffectBuilder::new()
+                .add_effect(BaseEffect {
+                    kind,
+                    scheduling: Replay { play_for: Ticks::from_ms(1000), ..Default::default() },
+                    ..Default::default()
+                })
+                .repeat(Repeat::Infinitely)
+                .gain(0.0)
+                .gamepads(&[id])
+                .finish(&mut gilrs)
+                .map_err(|e| DeviceError::Backend(e.to_string()))
+        };
+        let strong = effect(BaseEffectType::Strong { magnitude: u16::MAX })?;
+        let weak = effect(BaseEffectType::Weak { magnitude: u16::MAX })?;
+        for motor in [&strong, &weak] {
+            motor.play().map_err(|e| DeviceError::Backend(e.to_string()))?;
+        }
+        Ok((Self { gilrs, id, strong, weak }, name))
+    }
+
+    /// Applies levels until the device is dropped or the gamepad disconnects.
+    fn run(&mut self, commands: &Receiver<RumbleLevels>, connected: &AtomicBool) {
+        loop {
+            while let Some(event) = self.gilrs.next_event() {
+                if event.id == self.id && event.event == EventType::Disconnected {
+                    connected.store(false, Ordering::Release);
+                    return;
+                }
+            }
+            match commands.recv_timeout(PUMP_INTERVAL) {
+                Ok(mut levels) => {
+                    // Only the newest levels matter
+                    while let Ok(newer) = commands.try_recv() {
+                        levels = newer;
+                    }
+                    let applied = self.strong.set_gain(levels.strong).and_then(|_| self.weak.set_gain(levels.weak));
+                    if applied.is_err() {
+                        connected.store(false, Ordering::Release);
+                        return;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    let _ = self.strong.stop();
+                    let _ = self.weak.stop();
+                    return;
+                }
+            }
+        }
+    }
+}
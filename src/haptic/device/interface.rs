@@ -0,0 +1,131 @@
+//! The trait implemented by every device backend.
+
+use std::fmt;
+
+use super::capabilities::DeviceCapabilities;
+use crate::core::{Meters, Meters3, MetersPerSecond, MetersPerSecond3, Newtons, Newtons3, Quat};
+use crate::effects::HapticSample;
+
+/// Latest pose and button state read from a device, in device space.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DeviceState {
+    pub position: Meters3,
+    pub orientation: Quat,
+    pub velocity: MetersPerSecond3,
+    /// Bit per button, set while pressed.
+    pub buttons: u32,
+}
+
+impl DeviceState {
+    #[inline]
+    pub fn is_pressed(&self, button: u32) -> bool {
+        button < 32 && self.buttons & (1 << button) != 0
+    }
+}
+
+/// Reading of a device's gripper.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GripperState {
+    /// Distance between the finger pads.
+    pub gap: Meters,
+    /// Rate of opening; negative while closing.
+    pub velocity: MetersPerSecond,
+}
+
+/// Errors reported by device backends.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeviceError {
+    /// The device was unplugged or stopped responding.
+    Disconnected,
+    /// The device cannot render this kind of output.
+    Unsupported,
+    /// An actuator index past the device's actuator count.
+    InvalidActuator(usize),
+    /// Backend-specific failure.
+    Backend(String),
+}
+
+impl fmt::Display for DeviceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeviceError::Disconnected => write!(f, "device disconnected"),
+            DeviceError::Unsupported => write!(f, "output not supported by this device"),
+            DeviceError::InvalidActuator(index) => write!(f, "no actuator with index {}", index),
+            DeviceError::Backend(message) => write!(f, "backend error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for DeviceError {}
+
+/// A connected haptic device.
+///
+/// Kinesthetic backends implement `poll` and `set_force`; vibration-only backends
+/// implement `set_vibration`. Devices with a gripper also implement `poll_gripper`
+/// and, if it is actuated, `set_gripper_force`. Outputs a device cannot render
+/// report `Unsupported`.
+pub trait HapticDevice: Send {
+    fn name(&self) -> &str;
+
+    fn capabilities(&self) -> DeviceCapabilities;
+
+    /// Reads the current pose and buttons.
+    fn poll(&mut self) -> Result<DeviceState, DeviceError> {
+        Ok(DeviceState::default())
+    }
+
+    /// Commands an output force until the next call.
+    fn set_force(&mut self, force: Newtons3) -> Result<(), DeviceError> {
+        let _ = force;
+        Err(DeviceError::Unsupported)
+    }
+
+    /// Reads the gripper opening.
+    fn poll_gripper(&mut self) -> Result<GripperState, DeviceError> {
+        Err(DeviceError::Unsupported)
+    }
+
+    /// Commands a gripper force until the next call; positive pushes the fingers apart.
+    fn set_gripper_force(&mut self, force: Newtons) -> Result<(), DeviceError> {
+        let _ = force;
+        Err(DeviceError::Unsupported)
+    }
+
+    /// Drives one vibration actuator until the next call.
+    fn set_vibration(&mut self, actuator: usize, sample: HapticSample) -> Result<(), DeviceError> {
+        let _ = (actuator, sample);
+        Err(DeviceError::Unsupported)
+    }
+}
+
+/// Lets a boxed device be wrapped like a concrete one, e.g. in a
+/// [`SafeDevice`](crate::safety::SafeDevice).
+impl<D: HapticDevice + ?Sized> HapticDevice for Box<D> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn capabilities(&self) -> DeviceCapabilities {
+        (**self).capabilities()
+    }
+
+    fn poll(&mut self) -> Result<DeviceState, DeviceError> {
+        (**self).poll()
+    }
+
+    fn set_force(&mut self, force: Newtons3) -> Result<(), DeviceError> {
+        (**self).set_force(force)
+    }
+
+    fn poll_gripper(&mut self) -> Result<GripperState, DeviceError> {
+        (**self).poll_gripper()
+    }
+
+    fn set_gripper_force(&mut self, force: Newtons) -> Result<(), DeviceError> {
+        (**self).set_gripper_force(force)
+    }
+
+    fn set_vibration(&mut self, actuator: usize, sample: HapticSample) -> Result<(), DeviceError> {
+        (**self).set_vibration(actuator, sample)
+    }
+}
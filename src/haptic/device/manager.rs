@@ -0,0 +1,401 @@
+//! Several devices at once, with hot-plug and role assignment.
+//!
+//! A [`DeviceManager`] owns every connected device. New devices come from
+//! [`DeviceSource`]s, which [`DeviceManager::scan`] asks for anything that appeared
+//! since the last scan; a device whose poll reports [`DeviceError::Disconnected`] is
+//! dropped, so a source can hand it out again once it is plugged back in. Any
+//! closure returning newly opened devices is a source.
+//!
+//! Bimanual interaction addresses devices by [`Role`] rather than by identity:
+//! vacant roles are filled with unassigned force-capable devices in connection
+//! order, or by promoting the off-hand device, and forces are routed to whichever
+//! device holds the role. Roles can also be assigned explicitly. Every change is
+//! reported as a [`DeviceEvent`].
+//!
+//! Every device is wrapped in a [`SafeDevice`] as it is added, so forces routed
+//! through the manager, or commanded on a device it hands out, always pass a
+//! [`ForceSafety`] stage of the device's own. Its limits stay within the
+//! [`SafetyCaps`] of the `safety.*` config keys, which the manager can follow.
+//! The stage's watchdog is timed per device by the rate it is commanded at: its
+//! update rate, or the rate given to [`DeviceManager::add_commanded_at`] for a
+//! device commanded more slowly, such as once a frame.
+
+use std::fmt;
+use std::sync::{Arc, Weak};
+use std::thread;
+
+use super::interface::{DeviceError, DeviceState, HapticDevice};
+use crate::core::{Config, Hertz, Newtons3, SubscriptionId};
+use crate::render::Snapshot;
+use crate::safety::{ForceLimits, ForceSafety, SafeDevice, SafetyCaps, SafetyReporter};
+
+/// Identifies a device for as long as it stays connected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DeviceId(pub u32);
+
+impl fmt::Display for DeviceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "device {}", self.0)
+    }
+}
+
+/// What a device is used for in bimanual interaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    Dominant,
+    OffHand,
+}
+
+impl Role {
+    /// Roles in the order vacancies are filled.
+    pub const ALL: [Role; 2] = [Role::Dominant, Role::OffHand];
+}
+
+/// Something the manager reports.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeviceEvent {
+    Connected { id: DeviceId, name: String },
+    Disconnected { id: DeviceId },
+    /// `role` moved to `id`, or became vacant.
+    RoleChanged { role: Role, id: Option<DeviceId> },
+    /// A poll failed for a reason other than a disconnect; the device stays.
+    /// Reported once when polls start failing, and again if the error changes.
+    Error { id: DeviceId, error: DeviceError },
+    /// Polls of `id` succeed again after an [`Error`](DeviceEvent::Error).
+    Recovered { id: DeviceId },
+}
+
+/// Supplies devices as they are plugged in.
+pub trait DeviceSource: Send {
+    /// Devices that appeared since the last call.
+    fn discover(&mut self) -> Vec<Box<dyn HapticDevice>>;
+}
+
+impl<F> DeviceSource for F
+where
+    F: FnMut() -> Vec<Box<dyn HapticDevice>> + Send,
+{
+    fn discover(&mut self) -> Vec<Box<dyn HapticDevice>> {
+        self()
+    }
+}
+
+struct Managed {
+    id: DeviceId,
+    device: SafeDevice<Box<dyn HapticDevice>>,
+    state: DeviceState,
+    /// Whether the limits follow the manager's caps.
+    capped: bool,
+    /// Rate forces are commanded at, which times the watchdog of capped limits.
+    rate: Hertz,
+    /// Error the polls have been failing with, until one succeeds.
+    failing: Option<DeviceError>,
+}
+
+/// Owns the connected devices and routes input and output by role.
+#[derive(Default)]
+pub struct DeviceManager {
+    sources: Vec<Box<dyn DeviceSource>>,
+    devices: Vec<Managed>,
+    roles: [Option<DeviceId>; 2],
+    next_id: u32,
+    events: Vec<DeviceEvent>,
+    reporter: Option<SafetyReporter>,
+    caps: SafetyCaps,
+    /// Caps published by a config subscription, and the version last applied.
+    config_caps: Option<(Arc<Snapshot<SafetyCaps>>, u64)>,
+}
+
+impl DeviceManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_source(mut self, source: impl DeviceSource + 'static) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+
+    /// Reports the safety events of every device added from now on.
+    pub fn with_reporter(mut self, reporter: SafetyReporter) -> Self {
+        self.reporter = Some(reporter);
+        self
+    }
+
+    /// Takes ownership of an already opened device, limiting its forces to what
+    /// its capabilities and the caps allow. Forces are expected at the device's
+    /// update rate.
+    pub fn add(&mut self, device: Box<dyn HapticDevice>) -> DeviceId {
+        let rate = device.capabilities().update_rate;
+        self.add_commanded_at(device, rate)
+    }
+
+    /// As [`add`](Self::add), for a device whose forces are commanded at `rate`
+    /// rather than its update rate, such as once a frame.
+    pub fn add_commanded_at(&mut self, device: Box<dyn HapticDevice>, rate: Hertz) -> DeviceId {
+        let capabilities = device.capabilities();
+        let caps = SafetyCaps { servo_rate: rate, ..self.caps };
+        let safety = ForceSafety::new(ForceLimits::capped(&capabilities, &caps))
+            .with_gripper_limits(ForceLimits::gripper_capped(&capabilities, &caps));
+        self.insert(device, safety, true, rate)
+    }
+
+    /// Takes ownership of an already opened device, limiting its forces with
+    /// `safety` regardless of the caps.
+    pub fn add_with_safety(&mut self, device: Box<dyn HapticDevice>, safety: ForceSafety) -> DeviceId {
+        let rate = device.capabilities().update_rate;
+        self.insert(device, safety, false, rate)
+    }
+
+    fn insert(&mut self, device: Box<dyn HapticDevice>, safety: ForceSafety, capped: bool, rate: Hertz) -> DeviceId {
+        let safety = match &self.reporter {
+            Some(reporter) => safety.with_reporter(reporter.clone()),
+            None => safety,
+        };
+        let id = DeviceId(self.next_id);
+        self.next_id += 1;
+        self.events.push(DeviceEvent::Connected { id, name: device.name().to_string() });
+        let device = SafeDevice::with_safety(device, safety);
+        self.devices.push(Managed { id, device, state: DeviceState::default(), capped, rate, failing: None });
+        self.fill_roles();
+        id
+    }
+
+    /// Drops a device, vacating its role. It stays behind its safety stage.
+    pub fn remove(&mut self, id: DeviceId) -> Option<Box<dyn HapticDevice>> {
+        let index = self.devices.iter().position(|d| d.id == id)?;
+        let managed = self.devices.remove(index);
+        self.events.push(DeviceEvent::Disconnected { id });
+        for role in Role::ALL {
+            if self.roles[role as usize] == Some(id) {
+                self.set_role(role, None);
+            }
+        }
+        self.fill_roles();
+        Some(Box::new(managed.device))
+    }
+
+    /// Adds every device the sources discovered; returns how many.
+    pub fn scan(&mut self) -> usize {
+        let found: Vec<Box<dyn HapticDevice>> = self.sources.iter_mut().flat_map(|s| s.discover()).collect();
+        let count = found.len();
+        for device in found {
+            self.add(device);
+        }
+        count
+    }
+
+    #[inline]
+    pub fn caps(&self) -> SafetyCaps {
+        self.caps
+    }
+
+    /// Limits every device added with [`add`](Self::add) to `caps` from now on.
+    /// Each keeps the watchdog of the rate it is commanded at, whatever
+    /// `caps.servo_rate` says.
+    pub fn set_caps(&mut self, caps: SafetyCaps) {
+        self.caps = caps;
+        for managed in self.devices.iter_mut().filter(|d| d.capped) {
+            let capabilities = managed.device.capabilities();
+            let caps = SafetyCaps { servo_rate: managed.rate, ..caps };
+            managed.device.set_limits(ForceLimits::capped(&capabilities, &caps));
+            managed.device.set_gripper_limits(ForceLimits::gripper_capped(&capabilities, &caps));
+        }
+    }
+
+    /// Caps devices by the `safety.*` keys of `config`, picking
+    /// up changes to them on the next [`poll`](Self::poll).
+    ///
+    /// Pass the returned id to [`Config::unsubscribe`] when the manager goes away;
+    /// until then the subscription does nothing once the manager is dropped.
+    pub fn follow_config(&mut self, config: &mut Config) -> SubscriptionId {
+        let mut caps = SafetyCaps::from_config(config);
+        self.set_caps(caps);
+        let shared = Arc::new(Snapshot::new(caps));
+        let publish = Arc::downgrade(&shared);
+        self.config_caps = Some((shared, 0));
+        config.subscribe("", move |key, value| {
+            let Some(publish) = Weak::upgrade(&publish) else {
+                return;
+            };
+            if !caps.apply(key, value) {
+                return;
+            }
+            // Only fails while a poll copies out of the slot, which is brief
+            while !publish.publish(caps) {
+                thread::yield_now();
+            }
+        })
+    }
+
+    /// Polls every device, dropping the ones that disconnected.
+    pub fn poll(&mut self) {
+        let mut caps = self.caps;
+        if let Some((shared, seen)) = &mut self.config_caps {
+            if let Some(version) = shared.read_if_newer(*seen, &mut caps) {
+                *seen = version;
+                self.set_caps(caps);
+            }
+        }
+        let mut gone = Vec::new();
+        for managed in &mut self.devices {
+            match managed.device.poll() {
+                Ok(state) => {
+                    managed.state = state;
+                    if managed.failing.take().is_some() {
+                        self.events.push(DeviceEvent::Recovered { id: managed.id });
+                    }
+                }
+                Err(DeviceError::Disconnected) => gone.push(managed.id),
+                // A stalled device fails every poll; report the stall, not each poll
+                Err(error) if managed.failing.as_ref() == Some(&error) => {}
+                Err(error) => {
+                    managed.failing = Some(error.clone());
+                    self.events.push(DeviceEvent::Error { id: managed.id, error });
+                }
+            }
+        }
+        for id in gone {
+            self.remove(id);
+        }
+    }
+
+    /// Gives `role` to `id`, taking it from its previous holder. A device holds
+    /// at most one role, so `id` leaves any other role it had.
+    pub fn assign(&mut self, role: Role, id: DeviceId) -> Result<(), DeviceError> {
+        if !self.devices.iter().any(|d| d.id == id) {
+            return Err(DeviceError::Disconnected);
+        }
+        for other in Role::ALL {
+            if other != role && self.roles[other as usize] == Some(id) {
+                self.set_role(other, None);
+            }
+        }
+        self.set_role(role, Some(id));
+        self.fill_roles();
+        Ok(())
+    }
+
+    /// Swaps the dominant and off-hand devices, e.g. for a left-handed user.
+    pub fn swap_hands(&mut self) {
+        let [dominant, off_hand] = self.roles;
+        self.set_role(Role::Dominant, off_hand);
+        self.set_role(Role::OffHand, dominant);
+    }
+
+    #[inline]
+    pub fn role(&self, role: Role) -> Option<DeviceId> {
+        self.roles[role as usize]
+    }
+
+    /// Connected devices in connection order.
+    pub fn ids(&self) -> impl Iterator<Item = DeviceId> + '_ {
+        self.devices.iter().map(|d| d.id)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.devices.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty()
+    }
+
+    /// A device behind its safety stage.
+    pub fn device(&self, id: DeviceId) -> Option<&dyn HapticDevice> {
+        self.find(id).map(|d| &d.device as &dyn HapticDevice)
+    }
+
+    pub fn device_mut(&mut self, id: DeviceId) -> Option<&mut (dyn HapticDevice + 'static)> {
+        self.find_mut(id).map(|d| &mut d.device as &mut (dyn HapticDevice + 'static))
+    }
+
+    /// Safety stage limiting a device's forces.
+    pub fn safety(&self, id: DeviceId) -> Option<&ForceSafety> {
+        self.find(id).map(|d| d.device.safety())
+    }
+
+    /// Raises an emergency stop on every device.
+    pub fn emergency_stop(&self) {
+        for managed in &self.devices {
+            managed.device.handle().emergency_stop();
+        }
+    }
+
+    /// Clears a device's emergency stop.
+    pub fn reset(&mut self, id: DeviceId) -> Result<(), DeviceError> {
+        self.find_mut(id).ok_or(DeviceError::Disconnected)?.device.reset();
+        Ok(())
+    }
+
+    /// State from the latest poll.
+    pub fn state(&self, id: DeviceId) -> Option<DeviceState> {
+        self.find(id).map(|d| d.state)
+    }
+
+    /// State of the device holding `role`, from the latest poll.
+    pub fn state_of(&self, role: Role) -> Option<DeviceState> {
+        self.role(role).and_then(|id| self.state(id))
+    }
+
+    /// Commands a force on one device, expected at the rate it was added with.
+    pub fn set_force(&mut self, id: DeviceId, force: Newtons3) -> Result<(), DeviceError> {
+        self.device_mut(id).ok_or(DeviceError::Disconnected)?.set_force(force)
+    }
+
+    /// Commands a force on the device holding `role`; fails with `Disconnected`
+    /// while the role is vacant.
+    pub fn set_force_for(&mut self, role: Role, force: Newtons3) -> Result<(), DeviceError> {
+        let id = self.role(role).ok_or(DeviceError::Disconnected)?;
+        self.set_force(id, force)
+    }
+
+    /// Events since the last call.
+    pub fn take_events(&mut self) -> Vec<DeviceEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    fn find(&self, id: DeviceId) -> Option<&Managed> {
+        self.devices.iter().find(|d| d.id == id)
+    }
+
+    fn find_mut(&mut self, id: DeviceId) -> Option<&mut Managed> {
+        self.devices.iter_mut().find(|d| d.id == id)
+    }
+
+    fn set_role(&mut self, role: Role, id: Option<DeviceId>) {
+        if self.roles[role as usize] != id {
+            self.roles[role as usize] = id;
+            self.events.push(DeviceEvent::RoleChanged { role, id });
+        }
+    }
+
+    /// Fills vacant roles with unassigned force-capable devices. With none left,
+    /// a vacant role takes the device of a less important one, so a lone device
+    /// always ends up dominant.
+    fn fill_roles(&mut self) {
+        for (rank, role) in Role::ALL.into_iter().enumerate() {
+            if self.roles[role as usize].is_some() {
+                continue;
+            }
+            let unassigned = self
+                .devices
+                .iter()
+                .find(|d| d.device.capabilities().renders_force() && !self.roles.contains(&Some(d.id)))
+                .map(|d| d.id);
+            if let Some(id) = unassigned {
+                self.set_role(role, Some(id));
+            } else if let Some(&lower) = Role::ALL[rank + 1..].iter().find(|r| self.roles[**r as usize].is_some()) {
+                let id = self.roles[lower as usize];
+                self.set_role(role, id);
+                self.set_role(lower, None);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/manager_tests.rs"]
+mod tests;
@@ -0,0 +1,320 @@
+//! A scriptable stand-in for real hardware.
+//!
+//! [`MockDevice`] reports whatever pose it is given and records every output it is
+//! commanded, so application and safety code can be exercised without a device on
+//! the desk. Faults are scheduled against the poll counter: the n-th call to `poll`
+//! is tick n (from zero), and outputs commanded after it belong to the same tick.
+//! Any fault whose window covers a tick shapes that tick's responses. Random
+//! disconnects are drawn from a seeded generator so failing runs replay exactly.
+
+use std::collections::VecDeque;
+
+use super::capabilities::DeviceCapabilities;
+use super::interface::{DeviceError, DeviceState, GripperState, HapticDevice};
+use crate::core::noise::splitmix64;
+use crate::core::{Meters3, Newtons, Newtons3, Vec3};
+use crate::effects::HapticSample;
+
+// ============================================================================
+// Faults
+// ============================================================================
+
+/// Misbehavior a [`MockDevice`] can simulate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fault {
+    /// Poses arrive `polls` ticks late.
+    Latency { polls: usize },
+    /// The position reads as NaN.
+    NanPose,
+    /// Output force is clipped to `limit`, as by an amplifier at its rail.
+    Saturation { limit: Newtons },
+    /// Every call fails with [`DeviceError::Disconnected`].
+    Disconnect,
+}
+
+/// A fault active for ticks `start..end`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScheduledFault {
+    pub start: u64,
+    pub end: u64,
+    pub fault: Fault,
+}
+
+impl ScheduledFault {
+    #[inline]
+    pub fn is_active(&self, tick: u64) -> bool {
+        (self.start..self.end).contains(&tick)
+    }
+}
+
+/// Disconnects that start at random ticks.
+#[derive(Debug, Clone, Copy)]
+struct RandomDisconnects {
+    state: u64,
+    probability: f32,
+    duration: u64,
+}
+
+/// Longest latency the pose history keeps.
+const MAX_LATENCY: usize = 1024;
+
+// ============================================================================
+// Mock Device
+// ============================================================================
+
+type Motion = Box<dyn FnMut(u64) -> DeviceState + Send>;
+
+/// Fake device with scriptable motion and fault injection.
+pub struct MockDevice {
+    name: String,
+    capabilities: DeviceCapabilities,
+    state: DeviceState,
+    motion: Option<Motion>,
+    tick: u64,
+    history: VecDeque<DeviceState>,
+    faults: Vec<ScheduledFault>,
+    random: Option<RandomDisconnects>,
+    disconnected_until: u64,
+    requested_force: Newtons3,
+    output_force: Newtons3,
+    forces: Vec<Newtons3>,
+    gripper: GripperState,
+    gripper_force: Newtons,
+    vibrations: Vec<(usize, HapticSample)>,
+}
+
+impl MockDevice {
+    pub fn new(name: impl Into<String>, capabilities: DeviceCapabilities) -> Self {
+        Self {
+            name: name.into(),
+            capabilities,
+            state: DeviceState::default(),
+            motion: None,
+            tick: 0,
+            history: VecDeque::new(),
+            faults: Vec::new(),
+            random: None,
+            disconnected_until: 0,
+            requested_force: Newtons3::ZERO,
+            output_force: Newtons3::ZERO,
+            forces: Vec::new(),
+            gripper: GripperState::default(),
+            gripper_force: Newtons::ZERO,
+            vibrations: Vec::new(),
+        }
+    }
+
+    /// Mock of a desktop force-feedback arm.
+    pub fn kinesthetic() -> Self {
+        Self::new("mock kinesthetic", DeviceCapabilities::KINESTHETIC)
+    }
+
+    /// Mock of a rumble gamepad.
+    pub fn gamepad() -> Self {
+        Self::new("mock gamepad", DeviceCapabilities::GAMEPAD)
+    }
+
+    /// Generates the true state from the tick number, replacing `set_state`.
+    pub fn with_motion(mut self, motion: impl FnMut(u64) -> DeviceState + Send + 'static) -> Self {
+        self.motion = Some(Box::new(motion));
+        self
+    }
+
+    /// Schedules `fault` for `duration` ticks starting at `start`.
+    pub fn with_fault(mut self, start: u64, duration: u64, fault: Fault) -> Self {
+        self.inject(start, duration, fault);
+        self
+    }
+
+    /// Each tick, starts a disconnect lasting `duration` ticks with `probability`.
+    pub fn with_random_disconnects(mut self, seed: u64, probability: f32, duration: u64) -> Self {
+        self.random = Some(RandomDisconnects { state: seed, probability, duration });
+        self
+    }
+
+    /// Schedules `fault` for `duration` ticks starting at `start`.
+    pub fn inject(&mut self, start: u64, duration: u64, fault: Fault) {
+        self.faults.push(ScheduledFault { start, end: start.saturating_add(duration), fault });
+    }
+
+    /// Removes all scheduled faults and ends any disconnect.
+    pub fn clear_faults(&mut self) {
+        self.faults.clear();
+        self.random = None;
+        self.disconnected_until = 0;
+    }
+
+    /// Sets the true state reported by the next polls.
+    pub fn set_state(&mut self, state: DeviceState) {
+        self.state = state;
+    }
+
+    pub fn set_position(&mut self, position: Meters3) {
+        self.state.position = position;
+    }
+
+    /// Sets the gripper reading; only reported if the capabilities include a gripper.
+    pub fn set_gripper(&mut self, gripper: GripperState) {
+        self.gripper = gripper;
+    }
+
+    /// Number of polls so far.
+    #[inline]
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Faults affecting the most recent poll and the outputs that follow it.
+    pub fn active_faults(&self) -> impl Iterator<Item = Fault> + '_ {
+        self.faults_at(self.tick.saturating_sub(1))
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected_at(self.tick.saturating_sub(1))
+    }
+
+    /// Last force passed to `set_force`.
+    #[inline]
+    pub fn requested_force(&self) -> Newtons3 {
+        self.requested_force
+    }
+
+    /// Last force the simulated hardware actually produced.
+    #[inline]
+    pub fn output_force(&self) -> Newtons3 {
+        self.output_force
+    }
+
+    /// Every force produced, in order.
+    #[inline]
+    pub fn force_log(&self) -> &[Newtons3] {
+        &self.forces
+    }
+
+    /// Last gripper force passed to `set_gripper_force`.
+    #[inline]
+    pub fn gripper_force(&self) -> Newtons {
+        self.gripper_force
+    }
+
+    /// Every accepted vibration command, in order.
+    #[inline]
+    pub fn vibration_log(&self) -> &[(usize, HapticSample)] {
+        &self.vibrations
+    }
+
+    fn saturation(&self) -> Option<Newtons> {
+        self.active_faults()
+            .filter_map(|f| match f {
+                Fault::Saturation { limit } => Some(limit),
+                _ => None,
+            })
+            .reduce(|a, b| if b.value() < a.value() { b } else { a })
+    }
+
+    fn latency(&self) -> usize {
+        self.active_faults()
+            .filter_map(|f| match f {
+                Fault::Latency { polls } => Some(polls),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn faults_at(&self, tick: u64) -> impl Iterator<Item = Fault> + '_ {
+        self.faults.iter().filter(move |f| f.is_active(tick)).map(|f| f.fault)
+    }
+
+    fn connected_at(&self, tick: u64) -> bool {
+        tick >= self.disconnected_until && !self.faults_at(tick).any(|f| f == Fault::Disconnect)
+    }
+
+    fn check_connected(&self) -> Result<(), DeviceError> {
+        if self.is_connected() {
+            Ok(())
+        } else {
+            Err(DeviceError::Disconnected)
+        }
+    }
+}
+
+impl HapticDevice for MockDevice {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn capabilities(&self) -> DeviceCapabilities {
+        self.capabilities
+    }
+
+    fn poll(&mut self) -> Result<DeviceState, DeviceError> {
+        let tick = self.tick;
+        self.tick += 1;
+        if let Some(random) = &mut self.random {
+            let roll = (splitmix64(&mut random.state) >> 40) as f32 / (1u64 << 24) as f32;
+            if tick >= self.disconnected_until && roll < random.probability {
+                self.disconnected_until = tick + random.duration;
+            }
+        }
+        if let Some(motion) = &mut self.motion {
+            self.state = motion(tick);
+        }
+        self.history.push_front(self.state);
+        self.history.truncate(MAX_LATENCY + 1);
+
+        self.check_connected()?;
+        let mut state = self.history[self.latency().min(self.history.len() - 1)];
+        if self.active_faults().any(|f| f == Fault::NanPose) {
+            state.position = Meters3(Vec3::new(f32::NAN, f32::NAN, f32::NAN));
+        }
+        Ok(state)
+    }
+
+    fn set_force(&mut self, force: Newtons3) -> Result<(), DeviceError> {
+        if !self.capabilities.renders_force() {
+            return Err(DeviceError::Unsupported);
+        }
+        self.check_connected()?;
+        self.requested_force = force;
+        self.output_force = match self.saturation() {
+            Some(limit) => force.clamp_length(limit),
+            None => force,
+        };
+        self.forces.push(self.output_force);
+        Ok(())
+    }
+
+    fn poll_gripper(&mut self) -> Result<GripperState, DeviceError> {
+        if !self.capabilities.has_gripper() {
+            return Err(DeviceError::Unsupported);
+        }
+        self.check_connected()?;
+        Ok(self.gripper)
+    }
+
+    fn set_gripper_force(&mut self, force: Newtons) -> Result<(), DeviceError> {
+        if !self.capabilities.renders_gripper_force() {
+            return Err(DeviceError::Unsupported);
+        }
+        self.check_connected()?;
+        self.gripper_force = force;
+        Ok(())
+    }
+
+    fn set_vibration(&mut self, actuator: usize, sample: HapticSample) -> Result<(), DeviceError> {
+        if !self.capabilities.renders_vibration() {
+            return Err(DeviceError::Unsupported);
+        }
+        if actuator >= self.capabilities.actuators {
+            return Err(DeviceError::InvalidActuator(actuator));
+        }
+        self.check_connected()?;
+        self.vibrations.push((actuator, sample));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/mock_tests.rs"]
+mod tests;
@@ -0,0 +1,41 @@
+// src/haptic/device/mod.rs
+pub mod android;
+pub mod capabilities;
+pub mod corehaptics;
+pub mod dualsense;
+#[cfg(feature = "forcedimension")]
+pub mod forcedimension;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
+pub mod interface;
+pub mod manager;
+pub mod mock;
+pub mod openhaptics;
+pub mod recording;
+pub mod rumble;
+pub mod workspace;
+pub use android::{AndroidWaveform, Composition, Primitive};
+#[cfg(all(feature = "android", target_os = "android"))]
+pub use android::AndroidVibrator;
+pub use capabilities::{DeviceCapabilities, DeviceTier, GripperCapabilities};
+pub use corehaptics::{AhapPattern, TrackpadFeedback, TrackpadPattern};
+#[cfg(all(feature = "corehaptics", target_vendor = "apple"))]
+pub use corehaptics::CoreHapticsDevice;
+#[cfg(all(feature = "corehaptics", target_os = "macos"))]
+pub use corehaptics::TrackpadDevice;
+#[cfg(feature = "dualsense")]
+pub use dualsense::DualSenseDevice;
+pub use dualsense::{OutputReport, Trigger, TriggerEffect, VoiceCoil};
+#[cfg(feature = "forcedimension")]
+pub use forcedimension::ForceDimensionDevice;
+#[cfg(feature = "gamepad")]
+pub use gamepad::GamepadDevice;
+pub use interface::{DeviceError, DeviceState, GripperState, HapticDevice};
+pub use manager::{DeviceEvent, DeviceId, DeviceManager, DeviceSource, Role};
+pub use mock::{Fault, MockDevice, ScheduledFault};
+pub use openhaptics::ButtonEvent;
+#[cfg(feature = "openhaptics")]
+pub use openhaptics::{OpenHapticsDevice, ServoFn};
+pub use recording::{ButtonChange, Divergence, Frame, PlaybackDevice, Recorder, Recording, RecordingError};
+pub use rumble::{RumbleLevels, RumbleMapping};
+pub use workspace::{WorkspaceCalibration, WorkspaceMapping};
@@ -0,0 +1,591 @@
+//! Backend for 3D Systems devices (Touch, Touch X, Phantom Omni and Premium)
+//! through the OpenHaptics HD API.
+//!
+//! The HD library runs its own servo thread and only allows device access inside
+//! scheduler callbacks framed by `hdBeginFrame`/`hdEndFrame`. This backend installs
+//! one asynchronous callback per device at the highest scheduler priority that,
+//! every servo tick, reads the pose and buttons, optionally runs a user servo
+//! function through a [`ForceSafety`](crate::safety::ForceSafety) stage of its own,
+//! and writes the commanded force. [`HapticDevice`](super::HapticDevice)
+//! calls from other threads only exchange [`Snapshot`](crate::render::Snapshot)s
+//! and queues with that callback, which never waits on them, so polling at frame
+//! rate never disturbs the 1 kHz loop and button presses shorter than a frame are
+//! still reported.
+//!
+//! With the `openhaptics` feature, [`OpenHapticsDevice`] links the HD library
+//! (`libHD` / `hd.dll`). HD reports lengths in millimeters; this backend converts
+//! to meters.
+
+// The servo sample and button queue are only used by the feature-gated device
+#![cfg_attr(not(feature = "openhaptics"), allow(dead_code))]
+
+use std::collections::VecDeque;
+
+use super::interface::DeviceState;
+use crate::core::{Meters3, MetersPerSecond3, Quat, Vec3};
+
+// ============================================================================
+// Servo Samples
+// ============================================================================
+
+/// A button changing state between two servo ticks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ButtonEvent {
+    pub button: u32,
+    pub pressed: bool,
+    /// Servo tick at which the change was seen.
+    pub tick: u64,
+}
+
+/// Pending button events kept between polls; older ones are dropped.
+const MAX_BUTTON_EVENTS: usize = 64;
+
+/// Raw values HD reports in one servo tick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Sample {
+    /// Millimeters.
+    position: [f64; 3],
+    /// Millimeters per second.
+    velocity: [f64; 3],
+    /// Column-major 4x4 pose, translation in millimeters.
+    transform: [f64; 16],
+    buttons: u32,
+}
+
+impl Sample {
+    fn state(&self) -> DeviceState {
+        DeviceState {
+            position: Meters3(millimeters(self.position)),
+            orientation: transform_orientation(&self.transform),
+            velocity: MetersPerSecond3(millimeters(self.velocity)),
+            buttons: self.buttons,
+        }
+    }
+}
+
+/// HD millimeters in meters.
+fn millimeters(v: [f64; 3]) -> Vec3 {
+    Vec3::new(v[0] as f32, v[1] as f32, v[2] as f32) * 1e-3
+}
+
+/// Rotation of a column-major 4x4 transform, whose first three columns are the
+/// rotated axes.
+fn transform_orientation(transform: &[f64; 16]) -> Quat {
+    let column = |c: usize| {
+        let axis = &transform[4 * c..4 * c + 3];
+        Vec3::new(axis[0] as f32, axis[1] as f32, axis[2] as f32)
+    };
+    Quat::from_rotation_axes(column(0), column(1), column(2))
+}
+
+/// Button events waiting to be taken, keeping the newest [`MAX_BUTTON_EVENTS`].
+#[derive(Debug, Clone, Default)]
+struct ButtonQueue {
+    events: VecDeque<ButtonEvent>,
+}
+
+impl ButtonQueue {
+    /// Queues an event for every button that differs between `before` and `after`.
+    fn record(&mut self, before: u32, after: u32, tick: u64) {
+        let changed = before ^ after;
+        for button in (0..32).filter(|b| changed & (1 << b) != 0) {
+            self.push(ButtonEvent { button, pressed: after & (1 << button) != 0, tick });
+        }
+    }
+
+    /// Moves every event of `other` to the back of this queue.
+    fn append(&mut self, other: &mut ButtonQueue) {
+        for event in other.events.drain(..) {
+            self.push(event);
+        }
+    }
+
+    fn push(&mut self, event: ButtonEvent) {
+        if self.events.len() == MAX_BUTTON_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+
+    fn take(&mut self) -> Vec<ButtonEvent> {
+        self.events.drain(..).collect()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+// ============================================================================
+// Device
+// ============================================================================
+
+#[cfg(feature = "openhaptics")]
+pub use self::hd::{OpenHapticsDevice, ServoFn};
+
+#[cfg(feature = "openhaptics")]
+mod hd {
+    use std::ffi::{c_char, c_int, c_uint, c_ulong, c_ushort, c_void, CStr, CString};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex, MutexGuard, OnceLock, TryLockError};
+    use std::thread;
+
+    use super::{ButtonEvent, ButtonQueue, Sample};
+    use crate::core::{Hertz, Newtons, Newtons3, NewtonsPerMeter, SessionClock};
+    use crate::device::{DeviceCapabilities, DeviceError, DeviceState, DeviceTier, HapticDevice};
+    use crate::render::Snapshot;
+    use crate::safety::ForceSafety;
+
+    #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+    mod ffi {
+        use super::*;
+
+        pub type HHD = c_uint;
+        pub type HDenum = c_uint;
+        pub type HDerror = c_uint;
+        pub type HDCallbackCode = c_uint;
+        pub type HDSchedulerHandle = c_ulong;
+        pub type HDSchedulerCallback = extern "system" fn(*mut c_void) -> HDCallbackCode;
+
+        #[repr(C)]
+        #[derive(Debug, Clone, Copy)]
+        pub struct HDErrorInfo {
+            pub error_code: HDerror,
+            pub internal_error_code: c_int,
+            pub hhd: HHD,
+        }
+
+        // Values from hdDefines.h
+        pub const HD_SUCCESS: HDerror = 0x0000;
+        pub const HD_INVALID_HANDLE: HHD = 0xFFFF_FFFF;
+        pub const HD_CALLBACK_DONE: HDCallbackCode = 0;
+        pub const HD_CALLBACK_CONTINUE: HDCallbackCode = 1;
+        pub const HD_MAX_SCHEDULER_PRIORITY: c_ushort = 0xFFFF;
+        pub const HD_MIN_SCHEDULER_PRIORITY: c_ushort = 0x0000;
+
+        // Get parameters, with the number of values each one writes
+        /// 1 integer.
+        pub const HD_CURRENT_BUTTONS: HDenum = 0x2000;
+        /// 3 doubles.
+        pub const HD_CURRENT_POSITION: HDenum = 0x2050;
+        /// 3 doubles.
+        pub const HD_CURRENT_VELOCITY: HDenum = 0x2051;
+        /// 16 doubles.
+        pub const HD_CURRENT_TRANSFORM: HDenum = 0x2052;
+        /// String.
+        pub const HD_DEVICE_MODEL_TYPE: HDenum = 0x2501;
+        /// 1 integer.
+        pub const HD_UPDATE_RATE: HDenum = 0x2600;
+        /// 1 double, N/mm.
+        pub const HD_NOMINAL_MAX_STIFFNESS: HDenum = 0x2602;
+        /// 1 double, N.
+        pub const HD_NOMINAL_MAX_CONTINUOUS_FORCE: HDenum = 0x2604;
+
+        // Set parameters
+        /// 3 doubles.
+        pub const HD_CURRENT_FORCE: HDenum = 0x2700;
+
+        // Capabilities
+        pub const HD_FORCE_OUTPUT: HDenum = 0x4000;
+
+        #[cfg_attr(windows, link(name = "hd"))]
+        #[cfg_attr(not(windows), link(name = "HD"))]
+        extern "system" {
+            pub fn hdInitDevice(config_name: *const c_char) -> HHD;
+            pub fn hdDisableDevice(hhd: HHD);
+            pub fn hdMakeCurrentDevice(hhd: HHD);
+            pub fn hdGetError() -> HDErrorInfo;
+            pub fn hdGetErrorString(error: HDerror) -> *const c_char;
+            pub fn hdEnable(cap: HDenum);
+            pub fn hdBeginFrame(hhd: HHD);
+            pub fn hdEndFrame(hhd: HHD);
+            pub fn hdGetDoublev(pname: HDenum, params: *mut f64);
+            pub fn hdGetIntegerv(pname: HDenum, params: *mut c_int);
+            pub fn hdGetString(pname: HDenum) -> *const c_char;
+            pub fn hdSetDoublev(pname: HDenum, params: *const f64);
+            pub fn hdStartScheduler();
+            pub fn hdStopScheduler();
+            pub fn hdScheduleAsynchronous(
+                callback: HDSchedulerCallback,
+                user_data: *mut c_void,
+                priority: c_ushort,
+            ) -> HDSchedulerHandle;
+            pub fn hdScheduleSynchronous(callback: HDSchedulerCallback, user_data: *mut c_void, priority: c_ushort);
+            pub fn hdUnschedule(handle: HDSchedulerHandle);
+        }
+    }
+
+    /// Reads a parameter HD reports as `N` doubles.
+    ///
+    /// # Safety
+    /// A device must be current, and `N` must be the length hdDefines.h gives `pname`.
+    unsafe fn get_doubles<const N: usize>(pname: ffi::HDenum) -> [f64; N] {
+        let mut values = [0.0; N];
+        ffi::hdGetDoublev(pname, values.as_mut_ptr());
+        values
+    }
+
+    /// Reads a parameter HD reports as `N` integers.
+    ///
+    /// # Safety
+    /// As for [`get_doubles`].
+    unsafe fn get_integers<const N: usize>(pname: ffi::HDenum) -> [c_int; N] {
+        let mut values = [0; N];
+        ffi::hdGetIntegerv(pname, values.as_mut_ptr());
+        values
+    }
+
+    /// Pending HD error as a device error, if any.
+    fn last_error() -> Option<DeviceError> {
+        // SAFETY: hdGetError only reads the calling thread's error stack.
+        let info = unsafe { ffi::hdGetError() };
+        if info.error_code == ffi::HD_SUCCESS {
+            return None;
+        }
+        // SAFETY: HD returns a static, NUL-terminated string for every error code.
+        let text = unsafe { CStr::from_ptr(ffi::hdGetErrorString(info.error_code)) };
+        Some(DeviceError::Backend(format!(
+            "HD error {:#06x} ({}), internal {}",
+            info.error_code,
+            text.to_string_lossy(),
+            info.internal_error_code
+        )))
+    }
+
+    /// Devices with a callback scheduled. The HD scheduler is shared by the
+    /// process, so it starts with the first device and stops with the last.
+    static OPEN_DEVICES: Mutex<usize> = Mutex::new(0);
+
+    // ========================================================================
+    // Servo Exchange
+    // ========================================================================
+
+    /// Function run inside every HD servo tick: gets the fresh state and returns the
+    /// force to output, replacing the value passed to `set_force`.
+    pub type ServoFn = Box<dyn FnMut(&DeviceState) -> Newtons3 + Send>;
+
+    /// A servo function and the safety stage its forces pass.
+    struct Servo {
+        run: ServoFn,
+        safety: ForceSafety,
+        clock: SessionClock,
+    }
+
+    impl Servo {
+        /// The limited force for `state`; zero while the stage is stopped.
+        fn output(&mut self, state: &DeviceState) -> Newtons3 {
+            let requested = (self.run)(state);
+            self.safety.filter(requested, self.clock.now_us())
+        }
+    }
+
+    /// What the servo callback shares with the app. The callback only publishes,
+    /// reads snapshots and tries locks, so it never waits on the app thread.
+    struct Exchange {
+        hhd: ffi::HHD,
+        /// Latest state, published by the callback.
+        state: Snapshot<DeviceState>,
+        /// Force from `set_force`, published by the app.
+        force: Snapshot<Newtons3>,
+        servo: Mutex<Option<Servo>>,
+        events: Mutex<ButtonQueue>,
+        ticks: AtomicU64,
+        /// First HD error the callback hit; it stops running after one.
+        error: OnceLock<DeviceError>,
+        /// State only the callback uses.
+        local: Mutex<ServoLocal>,
+    }
+
+    /// The callback's own state between ticks.
+    #[derive(Default)]
+    struct ServoLocal {
+        buttons: u32,
+        tick: u64,
+        /// Events not yet handed over because the app held the queue.
+        pending: ButtonQueue,
+        force: Newtons3,
+        force_version: u64,
+    }
+
+    impl ServoLocal {
+        /// Shares the tick's sample and returns the force to output.
+        fn step(&mut self, ex: &Exchange, sample: &Sample) -> Newtons3 {
+            let state = sample.state();
+            self.pending.record(self.buttons, state.buttons, self.tick);
+            self.buttons = state.buttons;
+            if !self.pending.is_empty() {
+                if let Some(mut events) = try_lock(&ex.events) {
+                    events.append(&mut self.pending);
+                }
+            }
+            ex.state.publish(state);
+            self.tick += 1;
+            ex.ticks.store(self.tick, Ordering::Release);
+
+            let version = ex.force.version();
+            if version != self.force_version {
+                if let Some(force) = ex.force.try_read() {
+                    (self.force, self.force_version) = (force, version);
+                }
+            }
+            match try_lock(&ex.servo) {
+                Some(mut servo) => match servo.as_mut() {
+                    Some(servo) => servo.output(&state),
+                    None => self.force,
+                },
+                // `set_servo` is swapping the function
+                None => Newtons3::ZERO,
+            }
+        }
+    }
+
+    /// Locks `mutex` unless another thread holds it.
+    fn try_lock<T>(mutex: &Mutex<T>) -> Option<MutexGuard<'_, T>> {
+        match mutex.try_lock() {
+            Ok(guard) => Some(guard),
+            Err(TryLockError::Poisoned(e)) => Some(e.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+
+    /// The scheduler callback. `user_data` points to the device's `Exchange`, which
+    /// outlives the callback because the device waits for it to be unscheduled
+    /// before releasing it.
+    extern "system" fn servo_tick(user_data: *mut c_void) -> ffi::HDCallbackCode {
+        // SAFETY: see above; the pointer comes from `Arc::as_ptr` on a live Arc.
+        let ex = unsafe { &*(user_data as *const Exchange) };
+        // SAFETY: called on the HD servo thread; the getters run inside the frame and
+        // each buffer has the length HD writes for its parameter.
+        let sample = unsafe {
+            ffi::hdBeginFrame(ex.hhd);
+            Sample {
+                position: get_doubles(ffi::HD_CURRENT_POSITION),
+                velocity: get_doubles(ffi::HD_CURRENT_VELOCITY),
+                transform: get_doubles(ffi::HD_CURRENT_TRANSFORM),
+                buttons: get_integers::<1>(ffi::HD_CURRENT_BUTTONS)[0] as u32,
+            }
+        };
+        let force = match try_lock(&ex.local) {
+            Some(mut local) => local.step(ex, &sample),
+            None => Newtons3::ZERO,
+        };
+        let f = force.0;
+        let out = [f64::from(f.x), f64::from(f.y), f64::from(f.z)];
+        // SAFETY: still inside the frame opened above.
+        unsafe {
+            ffi::hdSetDoublev(ffi::HD_CURRENT_FORCE, out.as_ptr());
+            ffi::hdEndFrame(ex.hhd);
+        }
+        if let Some(error) = last_error() {
+            // Typically a force or velocity safety trip; HD disables output by itself
+            let _ = ex.error.set(error);
+            return ffi::HD_CALLBACK_DONE;
+        }
+        ffi::HD_CALLBACK_CONTINUE
+    }
+
+    /// Does nothing; scheduled synchronously, it returns once the servo tick in
+    /// progress has run every asynchronous callback.
+    extern "system" fn servo_barrier(_: *mut c_void) -> ffi::HDCallbackCode {
+        ffi::HD_CALLBACK_DONE
+    }
+
+    // ========================================================================
+    // Device
+    // ========================================================================
+
+    /// A 3D Systems device driven by the OpenHaptics servo scheduler.
+    pub struct OpenHapticsDevice {
+        name: String,
+        capabilities: DeviceCapabilities,
+        hhd: ffi::HHD,
+        exchange: Arc<Exchange>,
+        schedule: ffi::HDSchedulerHandle,
+    }
+
+    impl OpenHapticsDevice {
+        /// Opens the default device.
+        pub fn open_default() -> Result<Self, DeviceError> {
+            Self::open(None)
+        }
+
+        /// Opens the device configured under `config` in the Touch setup utility, or
+        /// the default device for None, and starts the servo scheduler if no other
+        /// device has.
+        pub fn open(config: Option<&str>) -> Result<Self, DeviceError> {
+            let config = config
+                .map(CString::new)
+                .transpose()
+                .map_err(|_| DeviceError::Backend("device name contains NUL".into()))?;
+            let mut open = OPEN_DEVICES.lock().unwrap_or_else(|e| e.into_inner());
+            // SAFETY: the name pointer is NUL-terminated and outlives the call; null
+            // selects the default device.
+            let hhd = unsafe { ffi::hdInitDevice(config.as_ref().map_or(std::ptr::null(), |c| c.as_ptr())) };
+            if hhd == ffi::HD_INVALID_HANDLE {
+                return Err(last_error().unwrap_or(DeviceError::Disconnected));
+            }
+
+            // SAFETY: `hhd` is a valid handle made current, and each getter has the
+            // length hdDefines.h gives its parameter.
+            let (max_stiffness, max_force, rate, model) = unsafe {
+                ffi::hdMakeCurrentDevice(hhd);
+                ffi::hdEnable(ffi::HD_FORCE_OUTPUT);
+                let [max_stiffness] = get_doubles(ffi::HD_NOMINAL_MAX_STIFFNESS);
+                let [max_force] = get_doubles(ffi::HD_NOMINAL_MAX_CONTINUOUS_FORCE);
+                let [rate] = get_integers(ffi::HD_UPDATE_RATE);
+                let model = ffi::hdGetString(ffi::HD_DEVICE_MODEL_TYPE);
+                let model = if model.is_null() {
+                    "OpenHaptics device".to_string()
+                } else {
+                    CStr::from_ptr(model).to_string_lossy().into_owned()
+                };
+                (max_stiffness, max_force, rate, model)
+            };
+            if let Some(error) = last_error() {
+                // SAFETY: the handle is valid and no callback is scheduled yet.
+                unsafe { ffi::hdDisableDevice(hhd) };
+                return Err(error);
+            }
+
+            let capabilities = DeviceCapabilities {
+                tier: DeviceTier::Kinesthetic,
+                max_force: Newtons(max_force as f32),
+                // HD reports stiffness in N/mm
+                max_stiffness: NewtonsPerMeter(max_stiffness as f32 * 1000.0),
+                update_rate: Hertz(if rate > 0 { rate as f32 } else { 1000.0 }),
+                actuators: 0,
+                gripper: None,
+            };
+            let exchange = Arc::new(Exchange {
+                hhd,
+                state: Snapshot::new(DeviceState::default()),
+                force: Snapshot::new(Newtons3::ZERO),
+                servo: Mutex::new(None),
+                events: Mutex::new(ButtonQueue::default()),
+                ticks: AtomicU64::new(0),
+                error: OnceLock::new(),
+                local: Mutex::new(ServoLocal::default()),
+            });
+            // SAFETY: the exchange lives in an Arc owned by the returned device, which
+            // waits for the callback to be unscheduled before releasing it.
+            let schedule = unsafe {
+                let schedule = ffi::hdScheduleAsynchronous(
+                    servo_tick,
+                    Arc::as_ptr(&exchange) as *mut c_void,
+                    ffi::HD_MAX_SCHEDULER_PRIORITY,
+                );
+                if *open == 0 {
+                    ffi::hdStartScheduler();
+                }
+                schedule
+            };
+            if let Some(error) = last_error() {
+                // SAFETY: undoes the scheduling above on valid handles; with other
+                // devices open the scheduler keeps running for them.
+                unsafe {
+                    if *open == 0 {
+                        ffi::hdStopScheduler();
+                    }
+                    ffi::hdUnschedule(schedule);
+                    if *open > 0 {
+                        ffi::hdScheduleSynchronous(servo_barrier, std::ptr::null_mut(), ffi::HD_MIN_SCHEDULER_PRIORITY);
+                    }
+                    ffi::hdDisableDevice(hhd);
+                }
+                return Err(error);
+            }
+            *open += 1;
+            Ok(Self { name: model, capabilities, hhd, exchange, schedule })
+        }
+
+        /// Runs `servo` inside every servo tick instead of replaying the last
+        /// `set_force`, for rendering at the full device rate. Its forces pass
+        /// `safety`, timestamped with `clock`, before reaching the motors. For a
+        /// device wrapped in a [`SafeDevice`](crate::safety::SafeDevice), give
+        /// `safety` the wrapper's [`handle`](crate::safety::SafeDevice::handle) and
+        /// use its [`clock`](crate::safety::SafeDevice::clock), so that its
+        /// emergency stop and watchdog cover the servo too. Ticks running while the
+        /// function is swapped output no force.
+        pub fn set_servo(&self, servo: ServoFn, safety: ForceSafety, clock: SessionClock) {
+            self.replace_servo(Some(Servo { run: servo, safety, clock }));
+        }
+
+        /// Goes back to replaying the last `set_force`.
+        pub fn clear_servo(&self) {
+            self.replace_servo(None);
+        }
+
+        fn replace_servo(&self, servo: Option<Servo>) {
+            let old = std::mem::replace(&mut *self.exchange.servo.lock().unwrap_or_else(|e| e.into_inner()), servo);
+            // Dropped outside the lock, so the servo is not kept waiting on it
+            drop(old);
+        }
+
+        /// Button changes since the last call, in order, including presses shorter
+        /// than the polling interval.
+        pub fn take_button_events(&mut self) -> Vec<ButtonEvent> {
+            self.exchange.events.lock().unwrap_or_else(|e| e.into_inner()).take()
+        }
+
+        /// Number of servo ticks run so far.
+        pub fn ticks(&self) -> u64 {
+            self.exchange.ticks.load(Ordering::Acquire)
+        }
+
+        fn check(&self) -> Result<(), DeviceError> {
+            match self.exchange.error.get() {
+                Some(error) => Err(error.clone()),
+                None => Ok(()),
+            }
+        }
+    }
+
+    impl HapticDevice for OpenHapticsDevice {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn capabilities(&self) -> DeviceCapabilities {
+            self.capabilities
+        }
+
+        fn poll(&mut self) -> Result<DeviceState, DeviceError> {
+            self.check()?;
+            Ok(self.exchange.state.read())
+        }
+
+        fn set_force(&mut self, force: Newtons3) -> Result<(), DeviceError> {
+            self.check()?;
+            // Only fails while the servo copies out of the slot, which is brief
+            while !self.exchange.force.publish(force) {
+                thread::yield_now();
+            }
+            Ok(())
+        }
+    }
+
+    impl Drop for OpenHapticsDevice {
+        fn drop(&mut self) {
+            let mut open = OPEN_DEVICES.lock().unwrap_or_else(|e| e.into_inner());
+            *open = open.saturating_sub(1);
+            // SAFETY: once unscheduled, the synchronous barrier returns only after any
+            // tick still running this device's callback has finished, or stopping the
+            // scheduler with the last device does the same, so the exchange is no
+            // longer used when released.
+            unsafe {
+                ffi::hdUnschedule(self.schedule);
+                if *open == 0 {
+                    ffi::hdStopScheduler();
+                } else {
+                    ffi::hdScheduleSynchronous(servo_barrier, std::ptr::null_mut(), ffi::HD_MIN_SCHEDULER_PRIORITY);
+                }
+                ffi::hdDisableDevice(self.hhd);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/openhaptics_tests.rs"]
+mod tests;
@@ -0,0 +1,402 @@
+//! Recording and deterministic playback of device interaction.
+//!
+//! A [`Recorder`] wraps a device and captures one [`Frame`] per poll: the pose and
+//! buttons read, and the force commanded after it, timestamped against a
+//! [`SessionClock`]. Polled from the servo loop this is a servo-rate stream. The
+//! [`Recording`] serializes to a compact little-endian format.
+//!
+//! A [`PlaybackDevice`] replays a recording frame by frame, one frame per poll
+//! regardless of wall-clock time, so the same input reaches the pipeline on every
+//! run. It keeps the forces the pipeline commands in response, and
+//! [`PlaybackDevice::compare`] reports the first frame where they diverge from the
+//! recorded ones, which turns any captured session into a regression test.
+
+use std::fmt;
+
+use super::capabilities::DeviceCapabilities;
+use super::interface::{DeviceError, DeviceState, HapticDevice};
+use crate::core::{Meters3, MetersPerSecond3, Newtons, Newtons3, Quat, SessionClock, Vec3};
+
+/// Leading bytes of an encoded recording.
+const MAGIC: [u8; 4] = *b"HREC";
+/// Format version written by [`Recording::to_bytes`].
+const VERSION: u8 = 1;
+
+// ============================================================================
+// Frames
+// ============================================================================
+
+/// One servo tick: the state polled and the force commanded after it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Frame {
+    pub time_us: u64,
+    pub state: DeviceState,
+    pub force: Newtons3,
+}
+
+impl Frame {
+    /// Size of an encoded frame: timestamp, position, orientation, velocity,
+    /// buttons and force.
+    pub const ENCODED_LEN: usize = 8 + 12 + 16 + 12 + 4 + 12;
+
+    pub fn to_bytes(&self) -> [u8; Self::ENCODED_LEN] {
+        let s = &self.state;
+        let (p, v, f, q) = (s.position.value(), s.velocity.value(), self.force.value(), s.orientation);
+        let mut out = [0u8; Self::ENCODED_LEN];
+        out[0..8].copy_from_slice(&self.time_us.to_le_bytes());
+        let floats = [p.x, p.y, p.z, q.x, q.y, q.z, q.w, v.x, v.y, v.z];
+        for (i, value) in floats.iter().enumerate() {
+            out[8 + 4 * i..12 + 4 * i].copy_from_slice(&value.to_le_bytes());
+        }
+        out[48..52].copy_from_slice(&s.buttons.to_le_bytes());
+        for (i, value) in [f.x, f.y, f.z].iter().enumerate() {
+            out[52 + 4 * i..56 + 4 * i].copy_from_slice(&value.to_le_bytes());
+        }
+        out
+    }
+
+    /// Deserializes a frame produced by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RecordingError> {
+        if bytes.len() < Self::ENCODED_LEN {
+            return Err(RecordingError::Truncated);
+        }
+        let u32_at = |i: usize| u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+        let f32_at = |i: usize| f32::from_bits(u32_at(i));
+        let vec_at = |i: usize| Vec3::new(f32_at(i), f32_at(i + 4), f32_at(i + 8));
+        let mut time = [0u8; 8];
+        time.copy_from_slice(&bytes[0..8]);
+        let state = DeviceState {
+            position: Meters3(vec_at(8)),
+            orientation: Quat { x: f32_at(20), y: f32_at(24), z: f32_at(28), w: f32_at(32) },
+            velocity: MetersPerSecond3(vec_at(36)),
+            buttons: u32_at(48),
+        };
+        Ok(Self { time_us: u64::from_le_bytes(time), state, force: Newtons3(vec_at(52)) })
+    }
+}
+
+/// A button press or release found between consecutive frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ButtonChange {
+    pub time_us: u64,
+    pub button: u32,
+    pub pressed: bool,
+}
+
+// ============================================================================
+// Recording
+// ============================================================================
+
+/// Timestamped frames captured from one device.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recording {
+    /// Name of the recorded device.
+    pub device: String,
+    frames: Vec<Frame>,
+}
+
+impl Recording {
+    pub fn new(device: impl Into<String>) -> Self {
+        Self { device: device.into(), frames: Vec::new() }
+    }
+
+    pub fn push(&mut self, frame: Frame) {
+        self.frames.push(frame);
+    }
+
+    #[inline]
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Time from the first frame to the last.
+    pub fn duration_us(&self) -> u64 {
+        match (self.frames.first(), self.frames.last()) {
+            (Some(first), Some(last)) => last.time_us.saturating_sub(first.time_us),
+            _ => 0,
+        }
+    }
+
+    /// Presses and releases in order; buttons held in the first frame count as
+    /// pressed at its time.
+    pub fn button_changes(&self) -> Vec<ButtonChange> {
+        let mut changes = Vec::new();
+        let mut held = 0u32;
+        for frame in &self.frames {
+            let changed = held ^ frame.state.buttons;
+            for button in (0..32).filter(|b| changed & (1 << b) != 0) {
+                changes.push(ButtonChange { time_us: frame.time_us, button, pressed: frame.state.is_pressed(button) });
+            }
+            held = frame.state.buttons;
+        }
+        changes
+    }
+
+    /// Serializes as magic, version, length-prefixed device name and frames.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let name = self.device.as_bytes();
+        let name_len = name.len().min(u16::MAX as usize);
+        let mut out = Vec::with_capacity(7 + name_len + self.frames.len() * Frame::ENCODED_LEN);
+        out.extend_from_slice(&MAGIC);
+        out.push(VERSION);
+        out.extend_from_slice(&(name_len as u16).to_le_bytes());
+        out.extend_from_slice(&name[..name_len]);
+        for frame in &self.frames {
+            out.extend_from_slice(&frame.to_bytes());
+        }
+        out
+    }
+
+    /// Deserializes a recording produced by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RecordingError> {
+        if bytes.len() < 7 {
+            return Err(RecordingError::Truncated);
+        }
+        if bytes[0..4] != MAGIC {
+            return Err(RecordingError::NotARecording);
+        }
+        if bytes[4] != VERSION {
+            return Err(RecordingError::UnsupportedVersion(bytes[4]));
+        }
+        let name_len = u16::from_le_bytes([bytes[5], bytes[6]]) as usize;
+        let body = bytes.get(7 + name_len..).ok_or(RecordingError::Truncated)?;
+        let device = String::from_utf8(bytes[7..7 + name_len].to_vec()).map_err(|_| RecordingError::InvalidName)?;
+        if body.len() % Frame::ENCODED_LEN != 0 {
+            return Err(RecordingError::Truncated);
+        }
+        let frames = body.chunks_exact(Frame::ENCODED_LEN).map(Frame::from_bytes).collect::<Result<_, _>>()?;
+        Ok(Self { device, frames })
+    }
+}
+
+/// Errors produced while decoding recordings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingError {
+    /// The data ends in the middle of the header or a frame.
+    Truncated,
+    /// The data does not start with the recording magic.
+    NotARecording,
+    UnsupportedVersion(u8),
+    /// The device name is not UTF-8.
+    InvalidName,
+}
+
+impl fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordingError::Truncated => write!(f, "recording is truncated"),
+            RecordingError::NotARecording => write!(f, "data is not a haptic recording"),
+            RecordingError::UnsupportedVersion(version) => write!(f, "unsupported recording version {}", version),
+            RecordingError::InvalidName => write!(f, "device name is not valid UTF-8"),
+        }
+    }
+}
+
+impl std::error::Error for RecordingError {}
+
+// ============================================================================
+// Recorder
+// ============================================================================
+
+/// Device wrapper that records every poll and the force commanded after it.
+pub struct Recorder<D> {
+    device: D,
+    clock: SessionClock,
+    recording: Recording,
+}
+
+impl<D: HapticDevice> Recorder<D> {
+    pub fn new(device: D) -> Self {
+        Self::with_clock(device, SessionClock::new())
+    }
+
+    /// Timestamps frames against a shared session clock.
+    pub fn with_clock(device: D, clock: SessionClock) -> Self {
+        let recording = Recording::new(device.name());
+        Self { device, clock, recording }
+    }
+
+    #[inline]
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    #[inline]
+    pub fn device(&self) -> &D {
+        &self.device
+    }
+
+    /// Stops recording, returning the device and what was captured.
+    pub fn finish(self) -> (D, Recording) {
+        (self.device, self.recording)
+    }
+}
+
+impl<D: HapticDevice> HapticDevice for Recorder<D> {
+    fn name(&self) -> &str {
+        self.device.name()
+    }
+
+    fn capabilities(&self) -> DeviceCapabilities {
+        self.device.capabilities()
+    }
+
+    fn poll(&mut self) -> Result<DeviceState, DeviceError> {
+        let state = self.device.poll()?;
+        self.recording.push(Frame { time_us: self.clock.now_us(), state, force: Newtons3::ZERO });
+        Ok(state)
+    }
+
+    /// Forces commanded before the first successful poll are not recorded.
+    fn set_force(&mut self, force: Newtons3) -> Result<(), DeviceError> {
+        self.device.set_force(force)?;
+        if let Some(frame) = self.recording.frames.last_mut() {
+            frame.force = force;
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Playback
+// ============================================================================
+
+/// The first frame whose replayed force differs from the recorded one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Divergence {
+    pub frame: usize,
+    pub time_us: u64,
+    pub recorded: Newtons3,
+    /// None if the pipeline commanded no force for this frame.
+    pub played: Option<Newtons3>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.played {
+            Some(played) => write!(
+                f,
+                "frame {} at {} us: played {} but recorded {}",
+                self.frame, self.time_us, played, self.recorded
+            ),
+            None => {
+                write!(f, "frame {} at {} us: no force played, recorded {}", self.frame, self.time_us, self.recorded)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Divergence {}
+
+/// A device that replays a recording one frame per poll.
+pub struct PlaybackDevice {
+    recording: Recording,
+    capabilities: DeviceCapabilities,
+    looping: bool,
+    cursor: usize,
+    played: Vec<Option<Newtons3>>,
+}
+
+impl PlaybackDevice {
+    pub fn new(recording: Recording, capabilities: DeviceCapabilities) -> Self {
+        let played = vec![None; recording.len()];
+        Self { recording, capabilities, looping: false, cursor: 0, played }
+    }
+
+    /// Starts over after the last frame instead of disconnecting, for demos.
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    #[inline]
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Index of the next frame to be polled.
+    #[inline]
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        !self.looping && self.cursor >= self.recording.len()
+    }
+
+    /// Rewinds to the first frame and forgets the forces played so far.
+    pub fn rewind(&mut self) {
+        self.cursor = 0;
+        self.played.iter_mut().for_each(|f| *f = None);
+    }
+
+    /// Force last commanded for each frame; None for frames not reached or
+    /// without a force.
+    #[inline]
+    pub fn played(&self) -> &[Option<Newtons3>] {
+        &self.played
+    }
+
+    /// Checks every frame reached so far against the recorded force.
+    pub fn compare(&self, tolerance: Newtons) -> Result<(), Divergence> {
+        let reached = if self.looping && self.cursor > 0 { self.recording.len() } else { self.cursor };
+        for (index, frame) in self.recording.frames.iter().enumerate().take(reached) {
+            let played = self.played[index];
+            let matches = match played {
+                Some(force) => (force - frame.force).length().value() <= tolerance.value(),
+                None => frame.force.length().value() <= tolerance.value(),
+            };
+            if !matches {
+                return Err(Divergence { frame: index, time_us: frame.time_us, recorded: frame.force, played });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl HapticDevice for PlaybackDevice {
+    fn name(&self) -> &str {
+        &self.recording.device
+    }
+
+    fn capabilities(&self) -> DeviceCapabilities {
+        self.capabilities
+    }
+
+    fn poll(&mut self) -> Result<DeviceState, DeviceError> {
+        if self.recording.is_empty() || self.is_finished() {
+            return Err(DeviceError::Disconnected);
+        }
+        if self.cursor >= self.recording.len() {
+            self.cursor = 0;
+        }
+        let state = self.recording.frames[self.cursor].state;
+        self.cursor += 1;
+        Ok(state)
+    }
+
+    fn set_force(&mut self, force: Newtons3) -> Result<(), DeviceError> {
+        if !self.capabilities.renders_force() {
+            return Err(DeviceError::Unsupported);
+        }
+        if let Some(index) = self.cursor.checked_sub(1) {
+            self.played[index] = Some(force);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/recording_tests.rs"]
+mod tests;
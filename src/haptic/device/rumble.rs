@@ -0,0 +1,109 @@
+//! Mapping force rendering onto dual-motor rumble.
+//!
+//! Consumer gamepads have two eccentric-mass motors: a heavy one that gives a
+//! low, strong rumble and a light one that gives a high, weak buzz. They cannot
+//! push back, but they can still tell the user that something is being touched.
+//! [`RumbleMapping`] does that translation so an application written against a
+//! force-feedback arm keeps working on a gamepad: the force magnitude drives the
+//! strong motor, and sudden changes in force (impacts, detents, edges) drive the
+//! weak one. Vibrotactile samples are split between the motors by sharpness.
+
+use std::f32::consts::FRAC_PI_2;
+
+use crate::core::{Newtons, Newtons3, Seconds};
+use crate::effects::HapticSample;
+
+/// Drive levels of the two rumble motors, each in 0..=1.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RumbleLevels {
+    /// Heavy, low-frequency motor (conventionally on the left).
+    pub strong: f32,
+    /// Light, high-frequency motor (conventionally on the right).
+    pub weak: f32,
+}
+
+impl RumbleLevels {
+    pub const OFF: Self = Self { strong: 0.0, weak: 0.0 };
+
+    /// Levels clamped to 0..=1, with NaN treated as off.
+    #[inline]
+    pub fn new(strong: f32, weak: f32) -> Self {
+        let level = |v: f32| if v.is_nan() { 0.0 } else { v.clamp(0.0, 1.0) };
+        Self { strong: level(strong), weak: level(weak) }
+    }
+
+    /// Splits `sample` between the motors: dull samples go to the strong motor,
+    /// sharp ones to the weak motor, keeping the total power constant.
+    pub fn from_sample(sample: HapticSample) -> Self {
+        let angle = sample.sharpness.clamp(0.0, 1.0) * FRAC_PI_2;
+        Self::new(sample.intensity * angle.cos(), sample.intensity * angle.sin())
+    }
+
+    #[inline]
+    pub fn is_off(&self) -> bool {
+        self.strong == 0.0 && self.weak == 0.0
+    }
+}
+
+/// Translates rendered forces into rumble levels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RumbleMapping {
+    /// Force that drives the strong motor fully.
+    pub full_scale: Newtons,
+    /// Exponent applied to the normalized force; below 1 makes light contact easier
+    /// to feel, since small motor drive is barely perceptible.
+    pub gamma: f32,
+    /// Time over which a full-scale change in force gives a full buzz on the weak
+    /// motor; 0 disables impact cues.
+    pub impact_time: Seconds,
+    last: Option<Newtons3>,
+}
+
+impl Default for RumbleMapping {
+    fn default() -> Self {
+        // Full scale of a desktop arm, so scenes tuned for one map sensibly
+        Self::new(Newtons(3.3))
+    }
+}
+
+impl RumbleMapping {
+    pub fn new(full_scale: Newtons) -> Self {
+        Self { full_scale, gamma: 0.5, impact_time: Seconds(0.05), last: None }
+    }
+
+    pub fn with_gamma(mut self, gamma: f32) -> Self {
+        self.gamma = gamma.max(0.0);
+        self
+    }
+
+    pub fn with_impact_time(mut self, impact_time: Seconds) -> Self {
+        self.impact_time = impact_time;
+        self
+    }
+
+    /// Forgets the previous force, so the next one is not treated as an impact.
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+
+    /// Levels for `force`, commanded `dt` after the previous one.
+    pub fn force(&mut self, force: Newtons3, dt: Seconds) -> RumbleLevels {
+        let full = self.full_scale.value();
+        if full <= 0.0 {
+            return RumbleLevels::OFF;
+        }
+        let strong = (force.length().value() / full).clamp(0.0, 1.0).powf(self.gamma);
+
+        let change = self.last.replace(force).map_or(0.0, |last| (force - last).length().value());
+        let weak = if self.impact_time.value() > 0.0 && dt.value() > 0.0 {
+            change / full * self.impact_time.value() / dt.value()
+        } else {
+            0.0
+        };
+        RumbleLevels::new(strong, weak)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/rumble_tests.rs"]
+mod tests;
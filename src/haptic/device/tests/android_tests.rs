@@ -0,0 +1,78 @@
+use super::*;
+use crate::effects::{Keyframe, KeyframeEffect};
+
+fn keys(keys: &[(f32, f32, f32)], duration: f32) -> KeyframeEffect {
+    let keys = keys.iter().map(|&(time, intensity, sharpness)| Keyframe { time, intensity, sharpness }).collect();
+    KeyframeEffect::new(keys, Some(duration))
+}
+
+#[test]
+fn test_amplitude_quantization() {
+    assert_eq!(amplitude(0.0), 0);
+    assert_eq!(amplitude(1.0), 255);
+    assert_eq!(amplitude(0.5), 128);
+    assert_eq!(amplitude(2.0), 255);
+    assert_eq!(amplitude(f32::NAN), 0);
+}
+
+#[test]
+fn test_waveform_merges_equal_steps() {
+    let effect = keys(&[(0.0, 1.0, 0.5), (0.1, 1.0, 0.5), (0.1001, 0.0, 0.5), (0.2, 0.0, 0.5)], 0.2);
+    let waveform = AndroidWaveform::from_signal(&effect, 10, 1000);
+    assert_eq!(waveform.timings, vec![100, 100]);
+    assert_eq!(waveform.amplitudes, vec![255, 0]);
+    assert_eq!(waveform.duration_ms(), 200);
+    assert!(!waveform.is_silent());
+}
+
+#[test]
+fn test_waveform_is_cut_at_limit() {
+    let effect = keys(&[(0.0, 0.5, 0.5)], 5.0);
+    let waveform = AndroidWaveform::from_signal(&effect, 10, 300);
+    assert_eq!(waveform.duration_ms(), 300);
+    assert_eq!(waveform.amplitudes, vec![128]);
+}
+
+#[test]
+fn test_short_pulses_become_taps() {
+    let effect = keys(
+        &[
+            (0.0, 1.0, 1.0),
+            (0.02, 1.0, 1.0),
+            (0.021, 0.0, 1.0),
+            (0.1, 0.0, 0.0),
+            (0.101, 0.3, 0.0),
+            (0.12, 0.3, 0.0),
+            (0.121, 0.0, 0.0),
+        ],
+        0.2,
+    );
+    let composition = Composition::from_signal(&effect, 5, 1000);
+    let steps: Vec<(Primitive, u32)> = composition.steps.iter().map(|s| (s.primitive, s.delay_ms)).collect();
+    // The second tap starts 105 ms in, 12 ms of click after the first
+    assert_eq!(steps, vec![(Primitive::Click, 0), (Primitive::LowTick, 93)]);
+    assert!((composition.steps[1].scale - 0.3).abs() < 1e-5);
+    assert_eq!(composition.primitives(), vec![Primitive::Click, Primitive::LowTick]);
+}
+
+#[test]
+fn test_long_pulses_rise_and_fall() {
+    let swell = keys(&[(0.0, 0.1, 0.5), (0.4, 0.8, 0.5), (0.45, 0.0, 0.5)], 0.5);
+    let composition = Composition::from_signal(&swell, 5, 1000);
+    let primitives: Vec<Primitive> = composition.steps.iter().map(|s| s.primitive).collect();
+    assert_eq!(primitives, vec![Primitive::SlowRise, Primitive::QuickFall]);
+    assert!(composition.steps.iter().all(|s| (s.scale - 0.8).abs() < 0.02));
+
+    // A fast attack and a slow release
+    let hit = keys(&[(0.0, 1.0, 0.5), (0.5, 0.1, 0.5), (0.51, 0.0, 0.5)], 0.6);
+    let primitives: Vec<Primitive> =
+        Composition::from_signal(&hit, 5, 1000).steps.iter().map(|s| s.primitive).collect();
+    assert_eq!(primitives, vec![Primitive::QuickRise]);
+}
+
+#[test]
+fn test_silence_composes_to_nothing() {
+    let effect = keys(&[(0.0, 0.0, 0.5)], 0.5);
+    assert!(Composition::from_signal(&effect, 5, 1000).is_empty());
+    assert!(AndroidWaveform::from_signal(&effect, 10, 1000).is_silent());
+}
@@ -0,0 +1,173 @@
+use super::*;
+use crate::effects::builder::constant;
+use crate::effects::Keyframe;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+fn ramp() -> KeyframeEffect {
+    KeyframeEffect::new(
+        vec![
+            Keyframe { time: 0.0, intensity: 0.0, sharpness: 0.2 },
+            Keyframe { time: 0.1, intensity: 1.0, sharpness: 0.8 },
+            Keyframe { time: 0.4, intensity: 0.5, sharpness: 0.5 },
+        ],
+        Some(0.5),
+    )
+}
+
+fn curve(pattern: &AhapPattern, control: AhapControl) -> Vec<(f32, f32)> {
+    pattern
+        .curves
+        .iter()
+        .filter(|c| c.control == control)
+        .flat_map(|c| c.points.iter().map(move |&(t, v)| (c.time + t, v)))
+        .collect()
+}
+
+#[test]
+fn test_keyframes_become_one_shaped_event() {
+    let pattern = AhapPattern::from_keyframes(&ramp());
+    assert_eq!(
+        pattern.events,
+        vec![AhapEvent::Continuous { time: 0.0, duration: 0.5, sample: HapticSample::new(1.0, 0.0) }]
+    );
+    let intensity = curve(&pattern, AhapControl::Intensity);
+    let expected = [(0.0, 0.0), (0.1, 1.0), (0.4, 0.5), (0.5, 0.5)];
+    assert_eq!(intensity.len(), expected.len());
+    for ((t, v), (et, ev)) in intensity.iter().zip(expected) {
+        assert!((t - et).abs() < TEST_EPSILON && (v - ev).abs() < TEST_EPSILON, "{:?}", intensity);
+    }
+    assert!((curve(&pattern, AhapControl::Sharpness)[1].1 - 0.8).abs() < TEST_EPSILON);
+    assert!((pattern.duration() - 0.5).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_keyframes_follow_speed_and_gain() {
+    let mut effect = ramp();
+    effect.speed = 2.0;
+    effect.gain = 0.5;
+    let pattern = AhapPattern::from_keyframes(&effect);
+    assert!((pattern.duration() - 0.25).abs() < TEST_EPSILON);
+    let intensity = curve(&pattern, AhapControl::Intensity);
+    assert!((intensity[1].0 - 0.05).abs() < TEST_EPSILON);
+    assert!((intensity[1].1 - 0.5).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_silent_signal_gives_empty_pattern() {
+    let effect = KeyframeEffect::new(vec![Keyframe { time: 0.2, intensity: 0.0, sharpness: 0.5 }], None);
+    assert_eq!(AhapPattern::from_keyframes(&effect), AhapPattern::new());
+    assert_eq!(AhapPattern::new().to_json(), "{\"Version\":1.0,\"Pattern\":[]}");
+}
+
+#[test]
+fn test_sampled_signal_is_reduced() {
+    // Linear segments need only their corners
+    let pattern = AhapPattern::from_signal(&ramp(), 0.001);
+    let intensity = curve(&pattern, AhapControl::Intensity);
+    assert!(intensity.len() <= 8, "{}", intensity.len());
+    let effect = ramp();
+    for t in [0.05, 0.2, 0.45] {
+        let next = intensity.iter().position(|p| p.0 >= t).unwrap();
+        let (a, b) = (intensity[next - 1], intensity[next]);
+        let v = a.1 + (b.1 - a.1) * (t - a.0) / (b.0 - a.0);
+        assert!((v - effect.sample(t).intensity).abs() < 0.02, "{} at {}", v, t);
+    }
+}
+
+#[test]
+fn test_long_curves_are_split() {
+    let keys = (0..40)
+        .map(|i| Keyframe { time: i as f32 * 0.01, intensity: (i % 2) as f32, sharpness: 0.5 })
+        .collect();
+    let pattern = AhapPattern::from_keyframes(&KeyframeEffect::new(keys, None));
+    let curves: Vec<&AhapCurve> = pattern.curves.iter().filter(|c| c.control == AhapControl::Intensity).collect();
+    assert!(curves.len() > 2);
+    assert!(curves.iter().all(|c| c.points.len() <= 16));
+    // Each curve starts where the previous one ended
+    for pair in curves.windows(2) {
+        let end = pair[0].time + pair[0].points.last().unwrap().0;
+        assert!((end - pair[1].time).abs() < TEST_EPSILON);
+    }
+}
+
+#[test]
+fn test_json_output() {
+    let json = AhapPattern::new().with_transient(0.25, HapticSample::new(0.5, 1.0)).to_json();
+    assert_eq!(
+        json,
+        "{\"Version\":1.0,\"Pattern\":[{\"Event\":{\"Time\":0.25,\"EventType\":\"HapticTransient\",\
+         \"EventParameters\":[{\"ParameterID\":\"HapticIntensity\",\"ParameterValue\":0.5},\
+         {\"ParameterID\":\"HapticSharpness\",\"ParameterValue\":1.0}]}}]}"
+    );
+    let json = AhapPattern::from_keyframes(&ramp()).to_json();
+    assert!(json.contains("\"EventType\":\"HapticContinuous\",\"EventDuration\":0.5"));
+    assert!(json.contains("{\"ParameterCurve\":{\"ParameterID\":\"HapticIntensityControl\",\"Time\":0.0,"));
+    assert_eq!(json.matches('{').count(), json.matches('}').count());
+}
+
+#[test]
+fn test_trackpad_clicks_on_onsets() {
+    let mut feedback = TrackpadFeedback::default();
+    let pulses = [0.0, 0.5, 0.6, 0.1, 0.9, 0.9, 0.0, 0.3];
+    let sharpness = [0.5, 1.0, 0.0, 0.5, 0.0, 1.0, 0.5, 0.5];
+    let clicks: Vec<_> = pulses
+        .iter()
+        .zip(sharpness)
+        .map(|(&i, s)| feedback.update(HapticSample::new(i, s)))
+        .collect();
+    assert_eq!(
+        clicks,
+        vec![
+            None,
+            Some(TrackpadPattern::Alignment),
+            None,
+            None,
+            Some(TrackpadPattern::LevelChange),
+            None,
+            None,
+            Some(TrackpadPattern::Generic),
+        ]
+    );
+}
+
+#[test]
+fn test_effect_keeps_transients() {
+    let effect = Effect::new()
+        .transient_with(0.0, HapticSample::new(0.8, 0.9))
+        .continuous(0.1..0.5, constant(0.5).with_sharpness(0.3))
+        .continuous(0.4..0.6, constant(0.5).with_sharpness(0.3))
+        .transient(1.0, 0.4);
+    let pattern = AhapPattern::from_effect(&effect, 0.01);
+    let continuous: Vec<(f32, f32)> = pattern
+        .events
+        .iter()
+        .filter_map(|e| match *e {
+            AhapEvent::Continuous { time, duration, .. } => Some((time, duration)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(continuous.len(), 1);
+    assert!((continuous[0].0 - 0.1).abs() < TEST_EPSILON && (continuous[0].1 - 0.5).abs() < TEST_EPSILON);
+    let intensity = curve(&pattern, AhapControl::Intensity);
+    assert!((intensity[0].0 - 0.1).abs() < TEST_EPSILON);
+    assert!(intensity.iter().all(|&(t, v)| t <= 0.6 + TEST_EPSILON && v > 0.0));
+    assert!(pattern.events.contains(&AhapEvent::Transient { time: 0.0, sample: HapticSample::new(0.8, 0.9) }));
+    assert!(pattern.events.contains(&AhapEvent::Transient { time: 1.0, sample: HapticSample::new(0.4, 0.5) }));
+}
+
+#[test]
+fn test_transients_inside_sections_are_compensated() {
+    let effect = Effect::new()
+        .continuous(0.0..0.4, constant(0.5).with_sharpness(0.25))
+        .transient_with(0.2, HapticSample::new(0.4, 0.75));
+    let pattern = AhapPattern::from_effect(&effect, 0.01);
+    let transient = pattern.events.iter().find_map(|e| match *e {
+        AhapEvent::Transient { sample, .. } => Some(sample),
+        _ => None,
+    });
+    let transient = transient.unwrap();
+    // The intensity curve scales by 0.5 and the sharpness curve adds 0.25
+    assert!((transient.intensity - 0.8).abs() < TEST_EPSILON);
+    assert!((transient.sharpness - 0.5).abs() < TEST_EPSILON);
+}
@@ -0,0 +1,105 @@
+use super::*;
+use crate::effects::KeyframeEffect;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+#[test]
+fn test_off_and_empty_effects_encode_as_off() {
+    assert_eq!(TriggerEffect::Off.encode()[0], 0x05);
+    assert_eq!(TriggerEffect::feedback(0.5, 0.0).encode(), TriggerEffect::Off.encode());
+    assert_eq!(TriggerEffect::Weapon { start: 0.2, end: 0.6, strength: 0.0 }.encode()[0], 0x05);
+}
+
+#[test]
+fn test_feedback_encoding() {
+    let block = TriggerEffect::feedback(0.5, 1.0).encode();
+    assert_eq!(block[0], 0x21);
+    // Zones 5..=9 active at the strongest level
+    assert_eq!(u16::from_le_bytes([block[1], block[2]]), 0b11_1110_0000);
+    let forces = u32::from_le_bytes([block[3], block[4], block[5], block[6]]);
+    for zone in 0..TRIGGER_ZONES {
+        let expected = if zone >= 5 { 7 } else { 0 };
+        assert_eq!((forces >> (3 * zone)) & 0b111, expected, "zone {}", zone);
+    }
+}
+
+#[test]
+fn test_curve_samples_zone_centers() {
+    let TriggerEffect::Resistance { zones } = TriggerEffect::curve(|x| x) else {
+        panic!("expected a resistance curve");
+    };
+    assert!((zones[0] - 0.05).abs() < TEST_EPSILON);
+    assert!((zones[9] - 0.95).abs() < TEST_EPSILON);
+    let block = TriggerEffect::Resistance { zones }.encode();
+    let forces = u32::from_le_bytes([block[3], block[4], block[5], block[6]]);
+    assert!((forces >> 27) & 0b111 > forces & 0b111);
+}
+
+#[test]
+fn test_weapon_and_vibration_encoding() {
+    let block = TriggerEffect::Weapon { start: 0.25, end: 0.55, strength: 0.5 }.encode();
+    assert_eq!(block[0], 0x25);
+    assert_eq!(u16::from_le_bytes([block[1], block[2]]), (1 << 2) | (1 << 5));
+    assert_eq!(block[3], 3);
+
+    // The break point is kept after the start
+    let block = TriggerEffect::Weapon { start: 0.9, end: 0.1, strength: 1.0 }.encode();
+    assert_eq!(u16::from_le_bytes([block[1], block[2]]), (1 << 7) | (1 << 8));
+
+    let block = TriggerEffect::Vibration { start: 0.0, amplitude: 1.0, frequency: Hertz(40.0) }.encode();
+    assert_eq!(block[0], 0x26);
+    assert_eq!(u16::from_le_bytes([block[1], block[2]]), 0x3ff);
+    assert_eq!(block[9], 40);
+}
+
+#[test]
+fn test_usb_report_layout() {
+    let report = OutputReport { rumble: RumbleLevels::new(1.0, 0.5), ..OutputReport::default() };
+    let bytes = report.to_usb();
+    assert_eq!(bytes[0], 0x02);
+    assert_eq!(bytes[1], 0x03);
+    assert_eq!((bytes[3], bytes[4]), (128, 255));
+
+    let bytes = report.with_trigger(Trigger::Right, TriggerEffect::feedback(0.0, 1.0)).to_usb();
+    assert_eq!(bytes[1], 0x07);
+    assert_eq!(bytes[11], 0x21);
+    assert_eq!(bytes[22], 0x00);
+}
+
+#[test]
+fn test_voice_coil_follows_intensity_and_sharpness() {
+    let mut coil = VoiceCoil::default();
+    let mut out = vec![[0.0f32; 2]; 4800];
+    coil.render(HapticSample::new(1.0, 0.0), HapticSample::SILENT, &mut out);
+    assert!(out.iter().all(|f| f[1] == 0.0 && f[0].abs() <= 1.0));
+
+    // Steady state: a full-scale carrier at the low end of the range
+    coil.render(HapticSample::new(1.0, 0.0), HapticSample::SILENT, &mut out);
+    let peak = out.iter().map(|f| f[0].abs()).fold(0.0, f32::max);
+    assert!((peak - 1.0).abs() < 0.01, "{}", peak);
+    let crossings = out.windows(2).filter(|w| (w[0][0] < 0.0) != (w[1][0] < 0.0)).count();
+    let frequency = crossings as f32 / 2.0 / 0.1;
+    assert!((frequency - carrier_frequency(0.0).value()).abs() < 10.0, "{}", frequency);
+}
+
+#[test]
+fn test_voice_coil_ramps_without_clicks() {
+    let mut coil = VoiceCoil::default();
+    let mut out = vec![[0.0f32; 2]; 480];
+    coil.render(HapticSample::new(1.0, 1.0), HapticSample::new(1.0, 1.0), &mut out);
+    coil.silence(&mut out);
+    let step = TAU * carrier_frequency(1.0).value() / AUDIO_RATE.value();
+    assert!(out.windows(2).all(|w| (w[1][0] - w[0][0]).abs() <= step + 0.01));
+    assert!(out.last().unwrap()[0].abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_render_signal_advances_time() {
+    let effect = KeyframeEffect::parse("duration 0.01\nkey 0 1 0.5\nkey 0.01 1 0.5").unwrap();
+    let mut coil = VoiceCoil::default();
+    let mut out = vec![[0.0f32; 2]; 960];
+    let end = coil.render_signal(&effect, 0.0, &mut out);
+    assert!((end - 0.02).abs() < TEST_EPSILON);
+    assert!(out[..400].iter().any(|f| f[0].abs() > 0.5));
+    assert!(out[900..].iter().all(|f| f[0].abs() < 0.05));
+}
@@ -0,0 +1,261 @@
+use super::*;
+use crate::device::{Fault, MockDevice};
+use std::sync::{Arc, Mutex};
+
+const TEST_EPSILON: f32 = 1e-4;
+
+type Plugged = Arc<Mutex<Vec<Box<dyn HapticDevice>>>>;
+
+/// A manager whose source hands out whatever the test plugs in.
+fn manager() -> (DeviceManager, Plugged) {
+    let plugged: Plugged = Arc::default();
+    let queue = plugged.clone();
+    let manager = DeviceManager::new().with_source(move || std::mem::take(&mut *queue.lock().unwrap()));
+    (manager, plugged)
+}
+
+fn arm(name: &str) -> Box<dyn HapticDevice> {
+    Box::new(MockDevice::new(name, crate::device::DeviceCapabilities::KINESTHETIC))
+}
+
+/// Arm recording every force it is sent into a shared log.
+struct RecordingDevice {
+    forces: Arc<Mutex<Vec<Newtons3>>>,
+}
+
+impl HapticDevice for RecordingDevice {
+    fn name(&self) -> &str {
+        "recording"
+    }
+
+    fn capabilities(&self) -> crate::device::DeviceCapabilities {
+        crate::device::DeviceCapabilities::KINESTHETIC
+    }
+
+    fn poll(&mut self) -> Result<DeviceState, DeviceError> {
+        Ok(DeviceState::default())
+    }
+
+    fn set_force(&mut self, force: Newtons3) -> Result<(), DeviceError> {
+        self.forces.lock().unwrap().push(force);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_scan_connects_and_assigns_roles() {
+    let (mut manager, plugged) = manager();
+    let gamepad: Box<dyn HapticDevice> = Box::new(MockDevice::gamepad());
+    plugged.lock().unwrap().extend([gamepad, arm("right"), arm("left")]);
+    assert_eq!(manager.scan(), 3);
+    assert_eq!(manager.len(), 3);
+    // The gamepad renders no force, so it gets no role
+    assert_eq!(manager.role(Role::Dominant), Some(DeviceId(1)));
+    assert_eq!(manager.role(Role::OffHand), Some(DeviceId(2)));
+    let events = manager.take_events();
+    assert_eq!(events[0], DeviceEvent::Connected { id: DeviceId(0), name: "mock gamepad".to_string() });
+    assert!(events.contains(&DeviceEvent::RoleChanged { role: Role::OffHand, id: Some(DeviceId(2)) }));
+    assert_eq!(manager.scan(), 0);
+    assert!(manager.take_events().is_empty());
+}
+
+#[test]
+fn test_disconnect_vacates_and_refills_role() {
+    let (mut manager, plugged) = manager();
+    let dominant = MockDevice::kinesthetic().with_fault(1, u64::MAX, Fault::Disconnect);
+    plugged.lock().unwrap().extend([Box::new(dominant) as Box<dyn HapticDevice>, arm("second")]);
+    manager.scan();
+    manager.poll();
+    assert_eq!(manager.len(), 2);
+    manager.take_events();
+
+    manager.poll();
+    assert_eq!(manager.ids().collect::<Vec<_>>(), vec![DeviceId(1)]);
+    assert_eq!(
+        manager.take_events(),
+        vec![
+            DeviceEvent::Disconnected { id: DeviceId(0) },
+            DeviceEvent::RoleChanged { role: Role::Dominant, id: None },
+            DeviceEvent::RoleChanged { role: Role::Dominant, id: Some(DeviceId(1)) },
+            DeviceEvent::RoleChanged { role: Role::OffHand, id: None },
+        ]
+    );
+    assert_eq!(manager.set_force_for(Role::OffHand, Newtons3::ZERO), Err(DeviceError::Disconnected));
+
+    // Plugging a device back in fills the vacant role with a fresh id
+    plugged.lock().unwrap().push(arm("replugged"));
+    manager.scan();
+    assert_eq!(manager.role(Role::OffHand), Some(DeviceId(2)));
+}
+
+/// Arm whose polls fail with the shared error while one is set.
+struct StallingDevice {
+    error: Arc<Mutex<Option<DeviceError>>>,
+}
+
+impl HapticDevice for StallingDevice {
+    fn name(&self) -> &str {
+        "stalling"
+    }
+
+    fn capabilities(&self) -> crate::device::DeviceCapabilities {
+        crate::device::DeviceCapabilities::KINESTHETIC
+    }
+
+    fn poll(&mut self) -> Result<DeviceState, DeviceError> {
+        self.error.lock().unwrap().clone().map_or(Ok(DeviceState::default()), Err)
+    }
+
+    fn set_force(&mut self, _force: Newtons3) -> Result<(), DeviceError> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_stall_is_reported_once_until_recovery() {
+    let error = Arc::new(Mutex::new(Some(DeviceError::Backend("stalled".into()))));
+    let mut manager = DeviceManager::new();
+    let id = manager.add(Box::new(StallingDevice { error: error.clone() }));
+    manager.take_events();
+
+    for _ in 0..3 {
+        manager.poll();
+    }
+    let stalled = DeviceEvent::Error { id, error: DeviceError::Backend("stalled".into()) };
+    assert_eq!(manager.take_events(), vec![stalled.clone()]);
+
+    // A different error is news; the same one again is not
+    *error.lock().unwrap() = Some(DeviceError::Unsupported);
+    manager.poll();
+    manager.poll();
+    assert_eq!(manager.take_events(), vec![DeviceEvent::Error { id, error: DeviceError::Unsupported }]);
+
+    *error.lock().unwrap() = None;
+    manager.poll();
+    manager.poll();
+    assert_eq!(manager.take_events(), vec![DeviceEvent::Recovered { id }]);
+    assert_eq!(manager.len(), 1);
+
+    // A later stall is reported afresh
+    *error.lock().unwrap() = Some(DeviceError::Backend("stalled".into()));
+    manager.poll();
+    assert_eq!(manager.take_events(), vec![stalled]);
+}
+
+#[test]
+fn test_forces_route_by_role() {
+    let mut manager = DeviceManager::new();
+    let right = manager.add(arm("right"));
+    let left = manager.add(arm("left"));
+    manager.swap_hands();
+    assert_eq!(manager.role(Role::Dominant), Some(left));
+    manager.set_force_for(Role::Dominant, Newtons3::new(1.0, 0.0, 0.0)).unwrap();
+    manager.set_force_for(Role::OffHand, Newtons3::new(0.0, 2.0, 0.0)).unwrap();
+    let name = |id| manager.device(id).unwrap().name().to_string();
+    assert_eq!((name(left), name(right)), ("left".to_string(), "right".to_string()));
+
+    let mut right_device = manager.remove(right).unwrap();
+    assert_eq!(right_device.poll().unwrap(), DeviceState::default());
+    assert_eq!(manager.role(Role::OffHand), None);
+}
+
+#[test]
+fn test_assign_moves_roles() {
+    let mut manager = DeviceManager::new();
+    let a = manager.add(arm("a"));
+    let b = manager.add(arm("b"));
+    let c = manager.add(arm("c"));
+    manager.assign(Role::Dominant, c).unwrap();
+    assert_eq!((manager.role(Role::Dominant), manager.role(Role::OffHand)), (Some(c), Some(b)));
+    // Moving the off-hand device to dominant refills the off hand
+    manager.assign(Role::Dominant, b).unwrap();
+    assert_eq!((manager.role(Role::Dominant), manager.role(Role::OffHand)), (Some(b), Some(a)));
+    assert_eq!(manager.assign(Role::OffHand, DeviceId(42)), Err(DeviceError::Disconnected));
+}
+
+#[test]
+fn test_poll_keeps_states_and_reports_errors() {
+    let mut manager = DeviceManager::new();
+    let mut mock = MockDevice::kinesthetic();
+    mock.set_position(crate::core::Meters3::new(0.1, 0.0, 0.0));
+    let id = manager.add(Box::new(mock));
+    manager.poll();
+    assert_eq!(manager.state_of(Role::Dominant).unwrap().position, crate::core::Meters3::new(0.1, 0.0, 0.0));
+    assert_eq!(manager.state(id), manager.state_of(Role::Dominant));
+    assert_eq!(manager.state(DeviceId(7)), None);
+}
+
+#[test]
+fn test_routed_forces_pass_safety() {
+    let mut manager = DeviceManager::new();
+    let forces = Arc::new(Mutex::new(Vec::new()));
+    let right = manager.add(Box::new(RecordingDevice { forces: forces.clone() }));
+    let left = manager.add(arm("left"));
+    for _ in 0..5 {
+        manager.set_force_for(Role::Dominant, Newtons3::new(0.0, 50.0, 0.0)).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+    // Ramping towards the 3 N default cap, and what the device was actually sent
+    let output = manager.safety(right).unwrap().output();
+    assert!(output.0.y > 0.0 && output.0.y <= 3.0 + TEST_EPSILON, "{output}");
+    assert_eq!(forces.lock().unwrap().last(), Some(&output));
+
+    // A bad command on one device stops it, not the other
+    manager.device_mut(right).unwrap().set_force(Newtons3::new(f32::NAN, 0.0, 0.0)).unwrap();
+    assert!(manager.safety(right).unwrap().is_stopped());
+    assert!(!manager.safety(left).unwrap().is_stopped());
+
+    manager.reset(right).unwrap();
+    manager.emergency_stop();
+    assert!(manager.ids().all(|id| manager.safety(id).unwrap().is_stopped()));
+    assert_eq!(manager.reset(DeviceId(9)), Err(DeviceError::Disconnected));
+}
+
+#[test]
+fn test_frame_rate_commands_pass_safety() {
+    use crate::core::Hertz;
+    use std::time::Duration;
+
+    let mut manager = DeviceManager::new();
+    let servo = manager.add(arm("servo"));
+    let id = manager.add_commanded_at(arm("touch"), Hertz(60.0));
+    manager.assign(Role::Dominant, id).unwrap();
+    assert_eq!(manager.safety(servo).unwrap().limits().watchdog_timeout, Duration::from_millis(5));
+    assert_eq!(manager.safety(id).unwrap().limits().watchdog_timeout, Duration::from_secs_f32(5.0 / 60.0));
+
+    for _ in 0..4 {
+        manager.set_force_for(Role::Dominant, Newtons3::new(1.0, 0.0, 0.0)).unwrap();
+        std::thread::sleep(Duration::from_millis(16));
+    }
+    assert!((manager.safety(id).unwrap().output().0.x - 1.0).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_caps_follow_config() {
+    use crate::core::{Config, ConfigValue, Layer, Newtons};
+
+    let mut config = Config::new();
+    config.set(Layer::File, "safety.max_force_n", ConfigValue::Float(2.0));
+    let mut manager = DeviceManager::new();
+    let id = manager.add(arm("arm"));
+    let custom = manager.add_with_safety(arm("custom"), ForceSafety::new(ForceLimits::default()));
+    let _subscription = manager.follow_config(&mut config);
+    assert_eq!(manager.safety(id).unwrap().limits().max_force, Newtons(2.0));
+
+    config.set(Layer::Cli, "safety.max_force_n", ConfigValue::Float(1.0));
+    config.set(Layer::Cli, "safety.max_force_rate_n_per_s", ConfigValue::Float(50.0));
+    manager.poll();
+    let limits = *manager.safety(id).unwrap().limits();
+    assert_eq!((limits.max_force, limits.max_slew.value()), (Newtons(1.0), 50.0));
+    // Devices with their own limits keep them; new ones get the caps
+    assert_eq!(manager.safety(custom).unwrap().limits(), &ForceLimits::default());
+    let late = manager.add(arm("late"));
+    assert_eq!(manager.safety(late).unwrap().limits().max_force, Newtons(1.0));
+
+    // Watchdogs stay timed by the rate each device is commanded at
+    config.set(Layer::Cli, "servo.rate_hz", ConfigValue::Float(250.0));
+    manager.poll();
+    let timeout = std::time::Duration::from_millis(5);
+    assert_eq!(manager.safety(id).unwrap().limits().watchdog_timeout, timeout);
+    assert_eq!(manager.safety(id).unwrap().gripper_limits().watchdog_timeout, timeout);
+}
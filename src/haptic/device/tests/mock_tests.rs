@@ -0,0 +1,113 @@
+use super::*;
+use crate::core::{Meters, MetersPerSecond};
+use crate::device::GripperCapabilities;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+fn moving() -> MockDevice {
+    MockDevice::kinesthetic().with_motion(|tick| DeviceState {
+        position: Meters3::new(tick as f32 * 0.001, 0.0, 0.0),
+        ..DeviceState::default()
+    })
+}
+
+#[test]
+fn test_reports_state_and_records_output() {
+    let mut device = MockDevice::kinesthetic();
+    device.set_position(Meters3::new(0.01, 0.02, 0.03));
+    assert_eq!(device.poll().unwrap().position, Meters3::new(0.01, 0.02, 0.03));
+    device.set_force(Newtons3::new(1.0, 0.0, 0.0)).unwrap();
+    device.set_force(Newtons3::new(0.0, 2.0, 0.0)).unwrap();
+    assert_eq!(device.force_log().len(), 2);
+    assert_eq!(device.output_force(), Newtons3::new(0.0, 2.0, 0.0));
+    assert_eq!(device.tick(), 1);
+    assert_eq!(device.set_vibration(0, HapticSample::SILENT), Err(DeviceError::Unsupported));
+}
+
+#[test]
+fn test_gamepad_vibration() {
+    let mut device = MockDevice::gamepad();
+    assert_eq!(device.set_force(Newtons3::ZERO), Err(DeviceError::Unsupported));
+    device.set_vibration(1, HapticSample::SILENT).unwrap();
+    assert_eq!(device.set_vibration(2, HapticSample::SILENT), Err(DeviceError::InvalidActuator(2)));
+    assert_eq!(device.vibration_log(), &[(1, HapticSample::SILENT)]);
+}
+
+#[test]
+fn test_latency_delays_poses() {
+    let mut device = moving().with_fault(5, 3, Fault::Latency { polls: 2 });
+    let xs: Vec<f32> = (0..10).map(|_| device.poll().unwrap().position.0.x * 1000.0).collect();
+    let expected = [0.0, 1.0, 2.0, 3.0, 4.0, 3.0, 4.0, 5.0, 8.0, 9.0];
+    for (x, e) in xs.iter().zip(expected) {
+        assert!((x - e).abs() < 1e-3, "{:?}", xs);
+    }
+}
+
+#[test]
+fn test_nan_pose() {
+    let mut device = moving().with_fault(1, 1, Fault::NanPose);
+    assert!(device.poll().unwrap().position.0.x.is_finite());
+    assert!(device.poll().unwrap().position.0.x.is_nan());
+    assert_eq!(device.active_faults().collect::<Vec<_>>(), vec![Fault::NanPose]);
+    assert!(device.poll().unwrap().position.0.x.is_finite());
+}
+
+#[test]
+fn test_saturation_clips_output() {
+    let mut device = MockDevice::kinesthetic().with_fault(0, 1, Fault::Saturation { limit: Newtons(1.5) });
+    device.poll().unwrap();
+    device.set_force(Newtons3::new(0.0, 0.0, 3.0)).unwrap();
+    assert_eq!(device.requested_force(), Newtons3::new(0.0, 0.0, 3.0));
+    assert!((device.output_force().length().value() - 1.5).abs() < TEST_EPSILON);
+    device.poll().unwrap();
+    device.set_force(Newtons3::new(0.0, 0.0, 3.0)).unwrap();
+    assert_eq!(device.output_force(), Newtons3::new(0.0, 0.0, 3.0));
+}
+
+#[test]
+fn test_scheduled_disconnect() {
+    let mut device = MockDevice::kinesthetic().with_fault(2, 2, Fault::Disconnect);
+    let results: Vec<bool> = (0..5).map(|_| device.poll().is_ok()).collect();
+    assert_eq!(results, vec![true, true, false, false, true]);
+
+    let mut device = MockDevice::kinesthetic().with_fault(0, 10, Fault::Disconnect);
+    assert_eq!(device.poll(), Err(DeviceError::Disconnected));
+    assert_eq!(device.set_force(Newtons3::ZERO), Err(DeviceError::Disconnected));
+    device.clear_faults();
+    assert!(device.is_connected());
+    assert!(device.poll().is_ok());
+}
+
+#[test]
+fn test_random_disconnects_replay() {
+    let run = |seed| {
+        let mut device = MockDevice::kinesthetic().with_random_disconnects(seed, 0.05, 10);
+        (0..1000).map(|_| device.poll().is_ok()).collect::<Vec<_>>()
+    };
+    let a = run(7);
+    assert_eq!(a, run(7));
+    assert_ne!(a, run(8));
+    let dropped = a.iter().filter(|ok| !**ok).count();
+    assert!(dropped > 100 && dropped < 900, "{}", dropped);
+}
+
+#[test]
+fn test_gripper() {
+    let mut device = MockDevice::kinesthetic();
+    assert_eq!(device.poll_gripper(), Err(DeviceError::Unsupported));
+    assert_eq!(device.set_gripper_force(Newtons(1.0)), Err(DeviceError::Unsupported));
+
+    let passive = GripperCapabilities { max_gap: Meters(0.025), max_force: Newtons::ZERO };
+    let mut device = MockDevice::new("omega.7", DeviceCapabilities::KINESTHETIC.with_gripper(passive));
+    assert!(device.capabilities().has_gripper());
+    assert!(!device.capabilities().renders_gripper_force());
+    let gripper = GripperState { gap: Meters(0.01), velocity: MetersPerSecond(-0.02) };
+    device.set_gripper(gripper);
+    assert_eq!(device.poll_gripper(), Ok(gripper));
+    assert_eq!(device.set_gripper_force(Newtons(1.0)), Err(DeviceError::Unsupported));
+
+    let active = GripperCapabilities { max_force: Newtons(8.0), ..passive };
+    let mut device = MockDevice::new("sigma.7", DeviceCapabilities::KINESTHETIC.with_gripper(active));
+    device.set_gripper_force(Newtons(-2.0)).unwrap();
+    assert_eq!(device.gripper_force(), Newtons(-2.0));
+}
@@ -0,0 +1,95 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+fn close(a: Vec3, b: Vec3) -> bool {
+    a.distance_to(b) < TEST_EPSILON
+}
+
+/// Column-major transform with the given axes as its first three columns.
+fn transform(x: Vec3, y: Vec3, z: Vec3, translation: Vec3) -> [f64; 16] {
+    let mut m = [0.0; 16];
+    for (c, v) in [x, y, z, translation].into_iter().enumerate() {
+        m[4 * c] = f64::from(v.x);
+        m[4 * c + 1] = f64::from(v.y);
+        m[4 * c + 2] = f64::from(v.z);
+    }
+    m[15] = 1.0;
+    m
+}
+
+#[test]
+fn test_state_converts_millimeters() {
+    let sample = Sample {
+        position: [10.0, -20.0, 150.0],
+        velocity: [100.0, 0.0, -50.0],
+        transform: transform(Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z(), Vec3::zero()),
+        buttons: 0b10,
+    };
+    let state = sample.state();
+    assert!(close(state.position.0, Vec3::new(0.01, -0.02, 0.15)));
+    assert!(close(state.velocity.0, Vec3::new(0.1, 0.0, -0.05)));
+    assert!(close(state.orientation.rotate(Vec3::unit_x()), Vec3::unit_x()));
+    assert!(state.is_pressed(1));
+    assert!(!state.is_pressed(0));
+}
+
+#[test]
+fn test_orientation_from_column_major_transform() {
+    // Quarter turn about Z: X goes to Y, Y to -X. The translation column is ignored.
+    let m = transform(Vec3::unit_y(), -Vec3::unit_x(), Vec3::unit_z(), Vec3::new(5.0, 6.0, 7.0));
+    let q = transform_orientation(&m);
+    assert!(close(q.rotate(Vec3::unit_x()), Vec3::unit_y()));
+    assert!(close(q.rotate(Vec3::unit_y()), -Vec3::unit_x()));
+    assert!(close(q.rotate(Vec3::unit_z()), Vec3::unit_z()));
+
+    // The same turn as built from its axis and angle
+    let expected = Quat::from_axis_angle(Vec3::unit_z(), crate::core::Deg(90.0));
+    assert!(close(q.rotate(Vec3::new(1.0, 2.0, 3.0)), expected.rotate(Vec3::new(1.0, 2.0, 3.0))));
+}
+
+#[test]
+fn test_button_edges() {
+    let mut queue = ButtonQueue::default();
+    queue.record(0, 0, 0);
+    assert!(queue.is_empty());
+
+    queue.record(0, 0b101, 3);
+    queue.record(0b101, 0b100, 4);
+    assert_eq!(
+        queue.take(),
+        vec![
+            ButtonEvent { button: 0, pressed: true, tick: 3 },
+            ButtonEvent { button: 2, pressed: true, tick: 3 },
+            ButtonEvent { button: 0, pressed: false, tick: 4 },
+        ]
+    );
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn test_short_press_survives_a_held_queue() {
+    // The servo keeps events while the app holds the shared queue, then hands them over
+    let (mut pending, mut shared) = (ButtonQueue::default(), ButtonQueue::default());
+    pending.record(0, 1, 10);
+    pending.record(1, 0, 11);
+    shared.record(0, 1 << 31, 9);
+    shared.append(&mut pending);
+    assert!(pending.is_empty());
+
+    let events = shared.take();
+    assert_eq!(events.iter().map(|e| e.tick).collect::<Vec<_>>(), vec![9, 10, 11]);
+    assert_eq!(events[0].button, 31);
+}
+
+#[test]
+fn test_queue_keeps_newest_events() {
+    let mut queue = ButtonQueue::default();
+    for tick in 0..(MAX_BUTTON_EVENTS as u64 + 6) {
+        queue.record((tick % 2) as u32, ((tick + 1) % 2) as u32, tick);
+    }
+    let events = queue.take();
+    assert_eq!(events.len(), MAX_BUTTON_EVENTS);
+    assert_eq!(events[0].tick, 6);
+    assert_eq!(events.last().unwrap().tick, MAX_BUTTON_EVENTS as u64 + 5);
+}
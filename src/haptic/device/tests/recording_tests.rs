@@ -0,0 +1,92 @@
+use super::*;
+use crate::device::MockDevice;
+
+fn state(x: f32, buttons: u32) -> DeviceState {
+    DeviceState { position: Meters3::new(x, 0.0, 0.0), buttons, ..DeviceState::default() }
+}
+
+/// Spring pulling toward the origin, standing in for the rendering pipeline.
+fn pipeline(device: &mut dyn HapticDevice, stiffness: f32) -> Result<(), DeviceError> {
+    let state = device.poll()?;
+    device.set_force(Newtons3(state.position.value() * -stiffness))
+}
+
+fn record(ticks: u64) -> Recording {
+    let mock = MockDevice::kinesthetic().with_motion(|tick| state(tick as f32 * 0.001, (tick >= 2) as u32));
+    let mut recorder = Recorder::new(mock);
+    for _ in 0..ticks {
+        pipeline(&mut recorder, 100.0).unwrap();
+    }
+    recorder.finish().1
+}
+
+#[test]
+fn test_recorder_captures_state_and_force() {
+    let recording = record(4);
+    assert_eq!(recording.device, "mock kinesthetic");
+    assert_eq!(recording.len(), 4);
+    let frame = recording.frames()[3];
+    assert_eq!(frame.state, state(0.003, 1));
+    assert!((frame.force.value().x + 0.3).abs() < 1e-6);
+    assert!(recording.frames().windows(2).all(|w| w[0].time_us <= w[1].time_us));
+    assert_eq!(
+        recording.button_changes(),
+        vec![ButtonChange { time_us: recording.frames()[2].time_us, button: 0, pressed: true }]
+    );
+}
+
+#[test]
+fn test_round_trip_bytes() {
+    let mut recording = record(3);
+    recording.push(Frame {
+        time_us: 99,
+        state: DeviceState { orientation: Quat { x: 0.0, y: 1.0, z: 0.0, w: 0.0 }, buttons: 0b101, ..state(1.0, 0) },
+        force: Newtons3::new(1.0, -2.0, 3.0),
+    });
+    let bytes = recording.to_bytes();
+    assert_eq!(bytes.len(), 7 + recording.device.len() + 4 * Frame::ENCODED_LEN);
+    assert_eq!(Recording::from_bytes(&bytes), Ok(recording));
+
+    assert_eq!(Recording::from_bytes(&bytes[..bytes.len() - 1]), Err(RecordingError::Truncated));
+    assert_eq!(Recording::from_bytes(b"nope, not at all"), Err(RecordingError::NotARecording));
+    let mut future = bytes.clone();
+    future[4] = 9;
+    assert_eq!(Recording::from_bytes(&future), Err(RecordingError::UnsupportedVersion(9)));
+}
+
+#[test]
+fn test_playback_is_deterministic() {
+    let recording = record(5);
+    let mut playback = PlaybackDevice::new(recording.clone(), DeviceCapabilities::KINESTHETIC);
+    for frame in recording.frames() {
+        assert_eq!(playback.poll(), Ok(frame.state));
+    }
+    assert!(playback.is_finished());
+    assert_eq!(playback.poll(), Err(DeviceError::Disconnected));
+
+    let mut looping = PlaybackDevice::new(recording.clone(), DeviceCapabilities::KINESTHETIC).with_looping(true);
+    let states: Vec<DeviceState> = (0..7).map(|_| looping.poll().unwrap()).collect();
+    assert_eq!(states[5], recording.frames()[0].state);
+    assert_eq!(looping.cursor(), 2);
+}
+
+#[test]
+fn test_compare_finds_regressions() {
+    let recording = record(5);
+    let mut playback = PlaybackDevice::new(recording.clone(), DeviceCapabilities::KINESTHETIC);
+    while pipeline(&mut playback, 100.0).is_ok() {}
+    assert_eq!(playback.compare(Newtons(1e-6)), Ok(()));
+
+    playback.rewind();
+    while pipeline(&mut playback, 120.0).is_ok() {}
+    let divergence = playback.compare(Newtons(1e-3)).unwrap_err();
+    assert_eq!(divergence.frame, 1);
+    assert_eq!(divergence.time_us, recording.frames()[1].time_us);
+
+    // Frames without a commanded force diverge where the recording had one
+    playback.rewind();
+    for _ in 0..3 {
+        playback.poll().unwrap();
+    }
+    assert_eq!(playback.compare(Newtons(1e-3)).unwrap_err().played, None);
+}
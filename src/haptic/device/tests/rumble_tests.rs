@@ -0,0 +1,55 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+#[test]
+fn test_levels_clamp_and_reject_nan() {
+    assert_eq!(RumbleLevels::new(1.5, -0.2), RumbleLevels::new(1.0, 0.0));
+    assert!(RumbleLevels::new(f32::NAN, f32::NAN).is_off());
+}
+
+#[test]
+fn test_sample_split_by_sharpness() {
+    let dull = RumbleLevels::from_sample(HapticSample::new(0.8, 0.0));
+    assert!((dull.strong - 0.8).abs() < TEST_EPSILON);
+    assert!(dull.weak.abs() < TEST_EPSILON);
+
+    let sharp = RumbleLevels::from_sample(HapticSample::new(0.8, 1.0));
+    assert!(sharp.strong.abs() < TEST_EPSILON);
+    assert!((sharp.weak - 0.8).abs() < TEST_EPSILON);
+
+    let mid = RumbleLevels::from_sample(HapticSample::new(1.0, 0.5));
+    assert!((mid.strong * mid.strong + mid.weak * mid.weak - 1.0).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_force_drives_strong_motor() {
+    let mut mapping = RumbleMapping::new(Newtons(4.0)).with_gamma(1.0).with_impact_time(Seconds(0.0));
+    let dt = Seconds(1.0 / 60.0);
+    assert!(mapping.force(Newtons3::ZERO, dt).is_off());
+    let half = mapping.force(Newtons3::new(0.0, 2.0, 0.0), dt);
+    assert!((half.strong - 0.5).abs() < TEST_EPSILON);
+    assert_eq!(half.weak, 0.0);
+    assert_eq!(mapping.force(Newtons3::new(0.0, 0.0, 10.0), dt).strong, 1.0);
+
+    // The default curve lifts light contact
+    let mut mapping = RumbleMapping::new(Newtons(4.0));
+    assert!(mapping.force(Newtons3::new(0.0, 0.4, 0.0), dt).strong > 0.3);
+}
+
+#[test]
+fn test_impacts_drive_weak_motor() {
+    let mut mapping = RumbleMapping::new(Newtons(2.0)).with_impact_time(Seconds(0.05));
+    let dt = Seconds(0.01);
+    mapping.force(Newtons3::ZERO, dt);
+    let hit = mapping.force(Newtons3::new(1.0, 0.0, 0.0), dt);
+    assert!((hit.weak - 1.0).abs() < TEST_EPSILON, "{:?}", hit);
+
+    let steady = mapping.force(Newtons3::new(1.0, 0.0, 0.0), dt);
+    assert_eq!(steady.weak, 0.0);
+    assert!(steady.strong > 0.0);
+
+    // The first force after a reset is not an impact
+    mapping.reset();
+    assert_eq!(mapping.force(Newtons3::new(2.0, 0.0, 0.0), dt).weak, 0.0);
+}
@@ -0,0 +1,104 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+fn assert_near(actual: Vec3, expected: Vec3) {
+    assert!(actual.distance_to(expected) < TEST_EPSILON, "{actual:?} != {expected:?}");
+}
+
+/// A 10 cm cube centered 15 cm in front of the device base.
+fn workspace() -> Obb {
+    Obb::new(Vec3::new(0.0, 0.0, 0.15), Vec3::splat(0.05), Quat::identity())
+}
+
+#[test]
+fn test_calibration_from_sweep() {
+    let mut calibration = WorkspaceCalibration::new();
+    assert_eq!(calibration.workspace(0.0), None);
+    for p in [Vec3::new(-0.06, -0.05, 0.1), Vec3::new(0.06, 0.05, 0.2), Vec3::new(f32::NAN, 0.0, 0.0)] {
+        calibration.add(Meters3(p));
+    }
+    assert_eq!(calibration.samples(), 2);
+
+    let obb = calibration.workspace(0.01).unwrap();
+    assert_near(obb.center, Vec3::new(0.0, 0.0, 0.15));
+    assert_near(obb.half_extents, Vec3::new(0.05, 0.04, 0.04));
+    assert_eq!(calibration.workspace(0.05), None);
+}
+
+#[test]
+fn test_round_trip_with_scale_rotation_offset() {
+    let rotation = Quat::from_axis_angle(Vec3::unit_y(), crate::core::Rad(0.7));
+    let mapping = WorkspaceMapping::new(workspace())
+        .with_scale(20.0)
+        .with_rotation(rotation)
+        .with_offset(Vec3::new(1.0, 2.0, 3.0));
+
+    assert_near(mapping.to_scene(Meters3::new(0.0, 0.0, 0.15)), Vec3::new(1.0, 2.0, 3.0));
+    let device = Meters3::new(0.01, -0.02, 0.17);
+    let scene = mapping.to_scene(device);
+    assert!((scene.distance_to(Vec3::new(1.0, 2.0, 3.0)) - 20.0 * 0.03).abs() < TEST_EPSILON);
+    assert_near(mapping.to_device(scene).0, device.0);
+
+    let force = mapping.force_to_device(Newtons3(rotation.rotate(Vec3::unit_x())));
+    assert_near(force.0, Vec3::unit_x());
+    assert_eq!(mapping.stiffness_to_device(NewtonsPerMeter(10.0)), NewtonsPerMeter(200.0));
+}
+
+#[test]
+fn test_fit_region() {
+    let region = Aabb::new(Vec3::new(-1.0, -0.5, -2.0), Vec3::new(1.0, 0.5, 2.0));
+    let mapping = WorkspaceMapping::fit(workspace(), &region);
+    assert!((mapping.scale - 10.0).abs() < TEST_EPSILON);
+    assert_near(mapping.to_scene(Meters3::new(0.0, 0.05, 0.15)), Vec3::new(0.0, 0.5, 0.0));
+}
+
+#[test]
+fn test_clutch_recenters_without_moving_cursor() {
+    let mut mapping = WorkspaceMapping::new(workspace()).with_scale(10.0);
+    let edge = Meters3::new(0.04, 0.0, 0.15);
+    let cursor = mapping.update(edge, 0.001);
+
+    mapping.engage_clutch(edge);
+    assert!(mapping.is_clutched());
+    assert_near(mapping.update(Meters3::new(0.02, 0.0, 0.15), 0.001), cursor);
+    assert_near(mapping.update(Meters3::new(0.0, 0.0, 0.15), 0.001), cursor);
+    mapping.release_clutch();
+
+    // Back at the center, motion continues from the held cursor
+    assert_near(mapping.update(Meters3::new(0.01, 0.0, 0.15), 0.001), cursor + Vec3::new(0.1, 0.0, 0.0));
+}
+
+#[test]
+fn test_drift_correction_pulls_hand_to_center() {
+    let mut mapping = WorkspaceMapping::new(workspace()).with_drift_correction(0.2);
+    let center = Meters3::new(0.0, 0.0, 0.15);
+    mapping.update(center, 0.001);
+
+    // Out to the right and back: the cursor ends up right of where it started
+    for i in 1..=40 {
+        mapping.update(Meters3::new(0.001 * i as f32, 0.0, 0.15), 0.001);
+    }
+    for i in (0..40).rev() {
+        mapping.update(Meters3::new(0.001 * i as f32, 0.0, 0.15), 0.001);
+    }
+    let cursor = mapping.to_scene(center);
+    assert!(cursor.x > 0.001, "{cursor:?}");
+    assert!(cursor.y.abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_ballistic_scrolling_at_edge() {
+    let mut mapping = WorkspaceMapping::new(workspace()).with_ballistic_scrolling(0.8, 0.5);
+    mapping.update(Meters3::new(0.03, 0.0, 0.15), 0.1);
+    assert!(!mapping.is_scrolling());
+
+    // 90% out is halfway through the edge band: a quarter of full speed
+    let start = mapping.update(Meters3::new(0.045, 0.0, 0.15), 0.0);
+    assert_near(mapping.scroll_velocity(), Vec3::new(0.125, 0.0, 0.0));
+    let later = mapping.update(Meters3::new(0.045, 0.0, 0.15), 1.0);
+    assert!((later.x - start.x - 0.125).abs() < TEST_EPSILON);
+
+    mapping.update(Meters3::new(0.0, -0.05, 0.15), 0.0);
+    assert_near(mapping.scroll_velocity(), Vec3::new(0.0, -0.5, 0.0));
+}
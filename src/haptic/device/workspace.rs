@@ -0,0 +1,252 @@
+//! Mapping between the device's physical workspace and scene space.
+//!
+//! A [`WorkspaceMapping`] places the workspace box (in device coordinates, meters)
+//! into the scene with a uniform scale, a rotation and an offset. Two mechanisms keep
+//! a small workspace usable over a large scene:
+//!
+//! - Drift correction slightly amplifies motion away from the workspace center and
+//!   damps motion toward it, so over time the hand returns to the middle of the
+//!   workspace without the cursor visibly jumping.
+//! - Ballistic scrolling switches to rate control near the workspace edge: holding
+//!   the device there pans the mapping at a speed that grows with how far past the
+//!   edge band the hand is, like pushing a mouse against the edge of the screen.
+//!
+//! For a manual re-center, a clutch freezes the cursor while the user moves the hand
+//! back, like lifting a mouse off the desk.
+//!
+//! The workspace itself can be measured with a [`WorkspaceCalibration`] while the
+//! user sweeps the device through its full range.
+
+use super::interface::DeviceState;
+use crate::core::{Meters3, Newtons3, NewtonsPerMeter, Quat, Vec3};
+use crate::geometry::{Aabb, Obb};
+
+// ============================================================================
+// Calibration
+// ============================================================================
+
+/// Accumulates device positions into a workspace box.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WorkspaceCalibration {
+    bounds: Option<Aabb>,
+    samples: usize,
+}
+
+impl WorkspaceCalibration {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a position; non-finite readings are ignored.
+    pub fn add(&mut self, position: Meters3) {
+        let p = position.0;
+        if !(p.x.is_finite() && p.y.is_finite() && p.z.is_finite()) {
+            return;
+        }
+        self.bounds = Some(match self.bounds {
+            Some(b) => Aabb::new(b.min.min(p), b.max.max(p)),
+            None => Aabb::new(p, p),
+        });
+        self.samples += 1;
+    }
+
+    pub fn add_state(&mut self, state: &DeviceState) {
+        self.add(state.position);
+    }
+
+    #[inline]
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// The swept box shrunk by `margin` on every side, keeping clear of the
+    /// mechanical limits. None until the sweep covers some volume.
+    pub fn workspace(&self, margin: f32) -> Option<Obb> {
+        let b = self.bounds?;
+        let half = (b.half_extents() - Vec3::splat(margin)).max(Vec3::zero());
+        (half.x > 0.0 && half.y > 0.0 && half.z > 0.0).then(|| Obb::new(b.center(), half, Quat::identity()))
+    }
+}
+
+// ============================================================================
+// Mapping
+// ============================================================================
+
+/// Device-to-scene transform with drift correction and edge scrolling.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspaceMapping {
+    /// Usable workspace in device coordinates.
+    pub workspace: Obb,
+    /// Scene units per meter of device motion.
+    pub scale: f32,
+    /// Orientation of the workspace axes in the scene.
+    pub rotation: Quat,
+    /// Scene position of the workspace center.
+    pub offset: Vec3,
+    /// Strength of drift correction; 0 disables it. Around 0.2 goes unnoticed.
+    pub drift_gain: f32,
+    /// Fraction of the half extents (0..1) beyond which ballistic scrolling starts;
+    /// 1 or more disables it.
+    pub edge: f32,
+    /// Scroll speed in scene units per second with the device at the boundary.
+    pub scroll_speed: f32,
+    /// Accumulated drift correction and scrolling, in scene units.
+    pan: Vec3,
+    last_local: Option<Vec3>,
+    scroll_velocity: Vec3,
+    /// Cursor held in place while the clutch is engaged.
+    clutch: Option<Vec3>,
+}
+
+impl WorkspaceMapping {
+    /// Identity placement of `workspace`, with no drift correction or scrolling.
+    pub fn new(workspace: Obb) -> Self {
+        Self {
+            workspace,
+            scale: 1.0,
+            rotation: Quat::identity(),
+            offset: Vec3::zero(),
+            drift_gain: 0.0,
+            edge: 1.0,
+            scroll_speed: 0.0,
+            pan: Vec3::zero(),
+            last_local: None,
+            scroll_velocity: Vec3::zero(),
+            clutch: None,
+        }
+    }
+
+    /// Scales and centers `workspace` so it fits inside `region` of the scene.
+    pub fn fit(workspace: Obb, region: &Aabb) -> Self {
+        let (h, r) = (workspace.half_extents, region.half_extents());
+        let scale = [r.x / h.x, r.y / h.y, r.z / h.z]
+            .into_iter()
+            .filter(|s| s.is_finite())
+            .fold(f32::INFINITY, f32::min);
+        Self::new(workspace).with_scale(if scale.is_finite() { scale } else { 1.0 }).with_offset(region.center())
+    }
+
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn with_rotation(mut self, rotation: Quat) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: Vec3) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_drift_correction(mut self, gain: f32) -> Self {
+        self.drift_gain = gain.max(0.0);
+        self
+    }
+
+    /// Scrolls at up to `speed` once the device is past `edge` of the half extents.
+    pub fn with_ballistic_scrolling(mut self, edge: f32, speed: f32) -> Self {
+        self.edge = edge.clamp(0.0, 1.0);
+        self.scroll_speed = speed;
+        self
+    }
+
+    /// Scene point for a device position under the current pan.
+    pub fn to_scene(&self, device: Meters3) -> Vec3 {
+        self.offset + self.pan + self.rotation.rotate(self.workspace.to_local(device.0) * self.scale)
+    }
+
+    /// Device position that maps to `scene`.
+    pub fn to_device(&self, scene: Vec3) -> Meters3 {
+        let local = self.rotation.inverse().rotate(scene - self.offset - self.pan) / self.scale;
+        Meters3(self.workspace.to_world(local))
+    }
+
+    /// Scene-space force expressed in device axes.
+    #[inline]
+    pub fn force_to_device(&self, force: Newtons3) -> Newtons3 {
+        Newtons3(self.workspace.rotation.rotate(self.rotation.inverse().rotate(force.0)))
+    }
+
+    /// Device stiffness that renders `stiffness` given per scene unit.
+    #[inline]
+    pub fn stiffness_to_device(&self, stiffness: NewtonsPerMeter) -> NewtonsPerMeter {
+        stiffness * self.scale
+    }
+
+    /// Current pan from drift correction and scrolling.
+    #[inline]
+    pub fn pan(&self) -> Vec3 {
+        self.pan
+    }
+
+    /// Pan velocity from ballistic scrolling during the last update.
+    #[inline]
+    pub fn scroll_velocity(&self) -> Vec3 {
+        self.scroll_velocity
+    }
+
+    /// Whether the last update was scrolling.
+    #[inline]
+    pub fn is_scrolling(&self) -> bool {
+        self.scroll_velocity != Vec3::zero()
+    }
+
+    /// Engages the clutch: the cursor stays at its position for `device` while the
+    /// hand moves, until [`release_clutch`](Self::release_clutch).
+    pub fn engage_clutch(&mut self, device: Meters3) {
+        self.clutch = Some(self.to_scene(device));
+    }
+
+    pub fn release_clutch(&mut self) {
+        self.clutch = None;
+    }
+
+    #[inline]
+    pub fn is_clutched(&self) -> bool {
+        self.clutch.is_some()
+    }
+
+    /// Advances drift correction and scrolling by `dt` seconds and returns the
+    /// cursor position in the scene.
+    pub fn update(&mut self, device: Meters3, dt: f32) -> Vec3 {
+        let local = self.workspace.to_local(device.0);
+        if let Some(cursor) = self.clutch {
+            self.pan = cursor - self.offset - self.rotation.rotate(local * self.scale);
+            self.last_local = Some(local);
+            self.scroll_velocity = Vec3::zero();
+            return cursor;
+        }
+        let half = self.workspace.half_extents;
+        let normalized = Vec3::new(local.x / half.x, local.y / half.y, local.z / half.z);
+
+        if let Some(last) = self.last_local.replace(local) {
+            let delta = local - last;
+            if self.drift_gain > 0.0 {
+                if let Some(direction) = delta.try_normalize() {
+                    // Outward motion counts a little extra, inward a little less
+                    let outward = direction.dot(normalized.clamp(Vec3::splat(-1.0), Vec3::splat(1.0)));
+                    self.pan += self.rotation.rotate(delta * (self.scale * self.drift_gain * outward));
+                }
+            }
+        }
+
+        self.scroll_velocity = Vec3::zero();
+        if self.edge < 1.0 && self.scroll_speed != 0.0 {
+            let mut rate = Vec3::zero();
+            for axis in [0, 1, 2] {
+                let past = ((normalized[axis].abs() - self.edge) / (1.0 - self.edge)).clamp(0.0, 1.0);
+                rate[axis] = normalized[axis].signum() * past * past;
+            }
+            self.scroll_velocity = self.rotation.rotate(rate * self.scroll_speed);
+            self.pan += self.scroll_velocity * dt;
+        }
+        self.to_scene(device)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/workspace_tests.rs"]
+mod tests;
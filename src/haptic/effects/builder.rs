@@ -0,0 +1,195 @@
+//! Declarative effect composition.
+//!
+//! An [`Effect`] is built from three kinds of parts placed on one timeline:
+//! transients (short taps), continuous sections (any signal, played over a time
+//! range) and parameter curves that shape the whole effect:
+//!
+//! ```text
+//! Effect::new()
+//!     .transient(0.0, 0.8)
+//!     .continuous(0.1..0.5, sine(120.0))
+//!     .intensity_curve(&[(0.1, 1.0), (0.5, 0.2)])
+//! ```
+//!
+//! An effect is itself a [`HapticSignal`], so every backend can sample it. Backends
+//! with a richer native model compile it instead; Core Haptics, for example, keeps
+//! transients as transient events (see `AhapPattern::from_effect`).
+
+use std::ops::Range;
+
+use super::signal::{HapticSample, HapticSignal};
+use super::waveform::Waveform;
+use crate::core::Hertz;
+
+/// Sharpness of transients added with [`Effect::transient`].
+const TRANSIENT_SHARPNESS: f32 = 0.5;
+
+// ============================================================================
+// Parts
+// ============================================================================
+
+/// A signal played over a time range of the effect.
+struct Section {
+    range: Range<f32>,
+    signal: Box<dyn HapticSignal>,
+}
+
+impl Section {
+    /// Sample at effect time `t`; silent outside the range, which includes its end.
+    fn sample(&self, t: f32) -> Option<HapticSample> {
+        (self.range.start <= t && t <= self.range.end).then(|| self.signal.sample(t - self.range.start))
+    }
+}
+
+/// Piecewise-linear curve through `(time, value)` points, holding its end values.
+fn curve_value(points: &[(f32, f32)], t: f32) -> Option<f32> {
+    let first = points.first()?;
+    let last = points.last()?;
+    if t <= first.0 {
+        return Some(first.1);
+    }
+    if t >= last.0 {
+        return Some(last.1);
+    }
+    let i = points.partition_point(|p| p.0 <= t);
+    let ((t0, v0), (t1, v1)) = (points[i - 1], points[i]);
+    let u = if t1 > t0 { (t - t0) / (t1 - t0) } else { 1.0 };
+    Some(v0 + (v1 - v0) * u)
+}
+
+// ============================================================================
+// Effect
+// ============================================================================
+
+/// A composed effect; build it with the chained methods, then play or compile it.
+#[derive(Default)]
+pub struct Effect {
+    transients: Vec<(f32, HapticSample)>,
+    sections: Vec<Section>,
+    intensity_curve: Vec<(f32, f32)>,
+    sharpness_curve: Vec<(f32, f32)>,
+}
+
+impl Effect {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a tap of `intensity` (0..=1) at `time` seconds.
+    pub fn transient(self, time: f32, intensity: f32) -> Self {
+        self.transient_with(time, HapticSample::new(intensity, TRANSIENT_SHARPNESS))
+    }
+
+    /// Adds a tap with explicit intensity and sharpness.
+    pub fn transient_with(mut self, time: f32, sample: HapticSample) -> Self {
+        let time = time.max(0.0);
+        let sample = HapticSample::new(sample.intensity.clamp(0.0, 1.0), sample.sharpness.clamp(0.0, 1.0));
+        let at = self.transients.partition_point(|(t, _)| *t <= time);
+        self.transients.insert(at, (time, sample));
+        self
+    }
+
+    /// Plays `signal` from `range.start`, cut off at `range.end`.
+    pub fn continuous(mut self, range: Range<f32>, signal: impl HapticSignal + 'static) -> Self {
+        let range = range.start.max(0.0)..range.end.max(range.start.max(0.0));
+        self.sections.push(Section { range, signal: Box::new(signal) });
+        self
+    }
+
+    /// Multiplies the effect's intensity by a curve through `(time, gain)` points.
+    pub fn intensity_curve(mut self, points: &[(f32, f32)]) -> Self {
+        self.intensity_curve = sorted(points);
+        self
+    }
+
+    /// Adds a curve through `(time, offset)` points to the effect's sharpness.
+    pub fn sharpness_curve(mut self, points: &[(f32, f32)]) -> Self {
+        self.sharpness_curve = sorted(points);
+        self
+    }
+
+    /// Transients in time order, as heard: with the curves applied.
+    pub fn transients(&self) -> impl Iterator<Item = (f32, HapticSample)> + '_ {
+        self.transients.iter().map(|&(time, sample)| (time, self.shape(time, sample)))
+    }
+
+    /// Time ranges of the continuous sections, in the order they were added.
+    pub fn sections(&self) -> impl Iterator<Item = Range<f32>> + '_ {
+        self.sections.iter().map(|s| s.range.clone())
+    }
+
+    /// The continuous sections alone at `t`, mixed and with the curves applied.
+    pub fn sustained(&self, t: f32) -> HapticSample {
+        self.shape(t, HapticSample::mix(self.sections.iter().filter_map(|s| s.sample(t))))
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.transients.is_empty() && self.sections.is_empty()
+    }
+
+    /// Applies the parameter curves to a sample at `t`.
+    fn shape(&self, t: f32, sample: HapticSample) -> HapticSample {
+        let gain = curve_value(&self.intensity_curve, t).unwrap_or(1.0);
+        let offset = curve_value(&self.sharpness_curve, t).unwrap_or(0.0);
+        HapticSample::new((sample.intensity * gain).clamp(0.0, 1.0), (sample.sharpness + offset).clamp(0.0, 1.0))
+    }
+}
+
+/// The click a transient plays as when the effect is sampled.
+fn tap(sample: HapticSample) -> Waveform {
+    Waveform::click().with_intensity(sample.intensity).with_sharpness(sample.sharpness)
+}
+
+fn sorted(points: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    points
+}
+
+impl HapticSignal for Effect {
+    fn duration(&self) -> Option<f32> {
+        let transient_end = self
+            .transients
+            .iter()
+            .map(|&(t, sample)| t + tap(sample).duration.unwrap_or(0.0))
+            .fold(0.0, f32::max);
+        Some(self.sections.iter().map(|s| s.range.end).fold(transient_end, f32::max))
+    }
+
+    fn sample(&self, t: f32) -> HapticSample {
+        if t < 0.0 || self.duration().is_some_and(|d| t > d) {
+            return HapticSample::SILENT;
+        }
+        let taps = self
+            .transients
+            .iter()
+            .filter(|(time, _)| t >= *time)
+            .map(|&(time, sample)| tap(sample).sample(t - time));
+        let sections = self.sections.iter().filter_map(|s| s.sample(t));
+        self.shape(t, HapticSample::mix(taps.chain(sections)))
+    }
+}
+
+// ============================================================================
+// Textures
+// ============================================================================
+
+/// Endless sine throb at `frequency` hertz, for [`Effect::continuous`].
+pub fn sine(frequency: f32) -> Waveform {
+    Waveform::sine(Hertz(frequency), None)
+}
+
+/// Endless square pulse train at `frequency` hertz, on for `duty` of each period.
+pub fn square(frequency: f32, duty: f32) -> Waveform {
+    Waveform::square(Hertz(frequency), duty, None)
+}
+
+/// Endless steady vibration at `intensity`.
+pub fn constant(intensity: f32) -> Waveform {
+    Waveform::constant(None).with_intensity(intensity)
+}
+
+#[cfg(test)]
+#[path = "tests/builder_tests.rs"]
+mod tests;
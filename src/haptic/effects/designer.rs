@@ -0,0 +1,356 @@
+//! Runtime support for external effect editors.
+//!
+//! A [`Designer`] holds one effect, a playhead and an optional loop range, and
+//! streams samples to the attached device while playing. Editors drive it through
+//! [`Designer::handle_command`], a line-based protocol that any control transport
+//! (WebSocket, gRPC stream, stdin) can forward verbatim:
+//!
+//! ```text
+//! load click duration 0.1; key 0 0 0.5; key 0.02 1 0.9; key 0.1 0 0.5
+//! play | pause | stop | status | params
+//! scrub 0.05
+//! loop 0.02 0.08 | loop off
+//! set gain 0.7
+//! ```
+//!
+//! Every command gets a single-line reply starting with `ok` or `err`.
+
+use super::signal::{HapticSample, HapticSignal, ParameterError};
+use std::fmt;
+
+// ============================================================================
+// Keyframe Effect
+// ============================================================================
+
+/// Intensity/sharpness keyframe.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe {
+    pub time: f32,
+    pub intensity: f32,
+    pub sharpness: f32,
+}
+
+/// Piecewise-linear effect with live-tweakable `gain`, `speed` and `sharpness` parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyframeEffect {
+    keys: Vec<Keyframe>,
+    duration: f32,
+    /// Intensity multiplier (0..=2).
+    pub gain: f32,
+    /// Playback rate (0.1..=10).
+    pub speed: f32,
+    /// Added to every keyframe's sharpness (-1..=1).
+    pub sharpness_offset: f32,
+}
+
+impl KeyframeEffect {
+    /// Creates an effect from keyframes; they are sorted by time. The duration
+    /// defaults to the last keyframe's time.
+    pub fn new(mut keys: Vec<Keyframe>, duration: Option<f32>) -> Self {
+        keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+        let duration = duration.unwrap_or_else(|| keys.last().map_or(0.0, |k| k.time));
+        Self { keys, duration, gain: 1.0, speed: 1.0, sharpness_offset: 0.0 }
+    }
+
+    pub fn keys(&self) -> &[Keyframe] {
+        &self.keys
+    }
+
+    /// Parses `duration <s>` and `key <time> <intensity> <sharpness>` statements,
+    /// separated by newlines or `;`. `#` starts a comment.
+    pub fn parse(text: &str) -> Result<Self, DesignerError> {
+        let mut keys = Vec::new();
+        let mut duration = None;
+        for statement in text.split(['\n', ';']) {
+            let statement = statement.split('#').next().unwrap_or("").trim();
+            let words: Vec<&str> = statement.split_whitespace().collect();
+            let numbers: Option<Vec<f32>> = words.iter().skip(1).map(|w| w.parse().ok()).collect();
+            match (words.first(), numbers.as_deref()) {
+                (None, _) => {}
+                (Some(&"duration"), Some(&[d])) if d >= 0.0 => duration = Some(d),
+                (Some(&"key"), Some(&[time, intensity, sharpness])) => {
+                    keys.push(Keyframe { time, intensity: intensity.clamp(0.0, 1.0), sharpness: sharpness.clamp(0.0, 1.0) })
+                }
+                _ => return Err(DesignerError::Syntax(statement.to_string())),
+            }
+        }
+        if keys.is_empty() {
+            return Err(DesignerError::Syntax("effect has no keyframes".to_string()));
+        }
+        Ok(Self::new(keys, duration))
+    }
+}
+
+impl HapticSignal for KeyframeEffect {
+    fn duration(&self) -> Option<f32> {
+        Some(self.duration / self.speed)
+    }
+
+    fn sample(&self, t: f32) -> HapticSample {
+        let t = t * self.speed;
+        if t < 0.0 || t > self.duration {
+            return HapticSample::SILENT;
+        }
+        let next = self.keys.partition_point(|k| k.time <= t);
+        let (intensity, sharpness) = match (next.checked_sub(1).map(|i| self.keys[i]), self.keys.get(next).copied()) {
+            (Some(a), Some(b)) => {
+                let s = (t - a.time) / (b.time - a.time).max(f32::EPSILON);
+                (a.intensity + (b.intensity - a.intensity) * s, a.sharpness + (b.sharpness - a.sharpness) * s)
+            }
+            (Some(k), None) | (None, Some(k)) => (k.intensity, k.sharpness),
+            (None, None) => return HapticSample::SILENT,
+        };
+        HapticSample::new(
+            (intensity * self.gain).clamp(0.0, 1.0),
+            (sharpness + self.sharpness_offset).clamp(0.0, 1.0),
+        )
+    }
+
+    fn parameters(&self) -> Vec<(String, f32)> {
+        vec![
+            ("gain".to_string(), self.gain),
+            ("speed".to_string(), self.speed),
+            ("sharpness".to_string(), self.sharpness_offset),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), ParameterError> {
+        let (slot, range) = match name {
+            "gain" => (&mut self.gain, 0.0..=2.0),
+            "speed" => (&mut self.speed, 0.1..=10.0),
+            "sharpness" => (&mut self.sharpness_offset, -1.0..=1.0),
+            _ => return Err(ParameterError::Unknown(name.to_string())),
+        };
+        if !range.contains(&value) {
+            return Err(ParameterError::OutOfRange { name: name.to_string(), value });
+        }
+        *slot = value;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Designer
+// ============================================================================
+
+/// Errors reported to the editor.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DesignerError {
+    NoEffect,
+    Syntax(String),
+    UnknownCommand(String),
+    InvalidRange { start: f32, end: f32 },
+    Parameter(ParameterError),
+}
+
+impl fmt::Display for DesignerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DesignerError::NoEffect => write!(f, "no effect loaded"),
+            DesignerError::Syntax(statement) => write!(f, "cannot parse '{}'", statement),
+            DesignerError::UnknownCommand(command) => write!(f, "unknown command '{}'", command),
+            DesignerError::InvalidRange { start, end } => write!(f, "invalid loop range {}..{}", start, end),
+            DesignerError::Parameter(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for DesignerError {}
+
+impl From<ParameterError> for DesignerError {
+    fn from(e: ParameterError) -> Self {
+        DesignerError::Parameter(e)
+    }
+}
+
+/// Receives samples destined for the attached device.
+pub type SampleSink = Box<dyn FnMut(HapticSample) + Send>;
+
+/// Preview session for one effect.
+pub struct Designer {
+    name: String,
+    effect: Option<Box<dyn HapticSignal>>,
+    playhead: f32,
+    playing: bool,
+    loop_range: Option<(f32, f32)>,
+    sink: SampleSink,
+}
+
+impl Designer {
+    /// Creates a session that sends samples to `sink` (usually the device output).
+    pub fn new(sink: impl FnMut(HapticSample) + Send + 'static) -> Self {
+        Self { name: String::new(), effect: None, playhead: 0.0, playing: false, loop_range: None, sink: Box::new(sink) }
+    }
+
+    /// Replaces the current effect and rewinds.
+    pub fn load(&mut self, name: impl Into<String>, effect: Box<dyn HapticSignal>) {
+        self.name = name.into();
+        self.effect = Some(effect);
+        self.playhead = 0.0;
+        self.playing = false;
+        self.loop_range = None;
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn playhead(&self) -> f32 {
+        self.playhead
+    }
+
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    #[inline]
+    pub fn loop_range(&self) -> Option<(f32, f32)> {
+        self.loop_range
+    }
+
+    pub fn effect(&self) -> Option<&dyn HapticSignal> {
+        self.effect.as_deref()
+    }
+
+    pub fn play(&mut self) -> Result<(), DesignerError> {
+        self.effect.as_ref().ok_or(DesignerError::NoEffect)?;
+        self.playing = true;
+        Ok(())
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+        (self.sink)(HapticSample::SILENT);
+    }
+
+    pub fn stop(&mut self) {
+        self.pause();
+        self.playhead = self.loop_range.map_or(0.0, |(start, _)| start);
+    }
+
+    /// Moves the playhead and renders that single sample on the device.
+    pub fn scrub(&mut self, time: f32) -> Result<HapticSample, DesignerError> {
+        let effect = self.effect.as_ref().ok_or(DesignerError::NoEffect)?;
+        let end = effect.duration().unwrap_or(f32::INFINITY);
+        self.playhead = time.clamp(0.0, end);
+        let sample = effect.sample(self.playhead);
+        (self.sink)(sample);
+        Ok(sample)
+    }
+
+    /// Loops playback over `start..end` seconds; None plays to the end once.
+    pub fn set_loop(&mut self, range: Option<(f32, f32)>) -> Result<(), DesignerError> {
+        if let Some((start, end)) = range {
+            if !(start >= 0.0 && end > start) {
+                return Err(DesignerError::InvalidRange { start, end });
+            }
+            if !(start..end).contains(&self.playhead) {
+                self.playhead = start;
+            }
+        }
+        self.loop_range = range;
+        Ok(())
+    }
+
+    pub fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), DesignerError> {
+        let effect = self.effect.as_mut().ok_or(DesignerError::NoEffect)?;
+        Ok(effect.set_parameter(name, value)?)
+    }
+
+    /// Advances playback by `dt` seconds and sends the sample to the device.
+    /// Returns None while paused.
+    pub fn tick(&mut self, dt: f32) -> Option<HapticSample> {
+        if !self.playing {
+            return None;
+        }
+        let effect = self.effect.as_ref()?;
+        let sample = effect.sample(self.playhead);
+        (self.sink)(sample);
+
+        self.playhead += dt;
+        match (self.loop_range, effect.duration()) {
+            (Some((start, end)), _) if self.playhead >= end => {
+                self.playhead = start + (self.playhead - end) % (end - start);
+            }
+            (None, Some(duration)) if self.playhead > duration => {
+                self.playing = false;
+                self.playhead = duration;
+            }
+            _ => {}
+        }
+        Some(sample)
+    }
+
+    // ============================================================================
+    // Control Protocol
+    // ============================================================================
+
+    /// Executes one protocol line and returns the reply line.
+    pub fn handle_command(&mut self, line: &str) -> String {
+        match self.execute(line.trim()) {
+            Ok(reply) if reply.is_empty() => "ok".to_string(),
+            Ok(reply) => format!("ok {}", reply),
+            Err(e) => format!("err {}", e),
+        }
+    }
+
+    fn execute(&mut self, line: &str) -> Result<String, DesignerError> {
+        let (command, args) = line.split_once(' ').unwrap_or((line, ""));
+        let number = |s: &str| s.parse::<f32>().map_err(|_| DesignerError::Syntax(line.to_string()));
+        let words: Vec<&str> = args.split_whitespace().collect();
+        match (command, words.as_slice()) {
+            ("load", [name, ..]) => {
+                let body = args.trim_start().strip_prefix(name).unwrap_or("");
+                let effect = KeyframeEffect::parse(body)?;
+                self.load(*name, Box::new(effect));
+                Ok(String::new())
+            }
+            ("play", []) => self.play().map(|_| String::new()),
+            ("pause", []) => {
+                self.pause();
+                Ok(String::new())
+            }
+            ("stop", []) => {
+                self.stop();
+                Ok(String::new())
+            }
+            ("scrub", [t]) => {
+                let s = self.scrub(number(t)?)?;
+                Ok(format!("{} {}", s.intensity, s.sharpness))
+            }
+            ("loop", ["off"]) => self.set_loop(None).map(|_| String::new()),
+            ("loop", [start, end]) => self.set_loop(Some((number(start)?, number(end)?))).map(|_| String::new()),
+            ("set", [name, value]) => self.set_parameter(name, number(value)?).map(|_| String::new()),
+            ("params", []) => {
+                let effect = self.effect.as_ref().ok_or(DesignerError::NoEffect)?;
+                let params: Vec<String> = effect.parameters().iter().map(|(n, v)| format!("{}={}", n, v)).collect();
+                Ok(params.join(" "))
+            }
+            ("status", []) => {
+                let state = if self.playing { "playing" } else { "paused" };
+                let duration = self.effect.as_ref().and_then(|e| e.duration()).unwrap_or(0.0);
+                let looping = self.loop_range.map_or("off".to_string(), |(s, e)| format!("{}..{}", s, e));
+                Ok(format!("{} {} t={} duration={} loop={}", self.name, state, self.playhead, duration, looping))
+            }
+            _ => Err(DesignerError::UnknownCommand(line.to_string())),
+        }
+    }
+}
+
+impl fmt::Debug for Designer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Designer")
+            .field("name", &self.name)
+            .field("playhead", &self.playhead)
+            .field("playing", &self.playing)
+            .field("loop_range", &self.loop_range)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/designer_tests.rs"]
+mod tests;
@@ -0,0 +1,141 @@
+//! Duty-cycle and thermal budgets for vibrotactile actuators.
+//!
+//! Small LRA and ERM motors overheat when driven continuously. Each actuator gets a
+//! first-order thermal model (heating proportional to drive², exponential cooling)
+//! and a sliding duty-cycle estimate. As either approaches its limit the budget
+//! reports a gain below one, and at the limit it refuses new effects.
+
+// ============================================================================
+// Limits
+// ============================================================================
+
+/// Thermal and duty-cycle limits of one actuator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActuatorLimits {
+    /// Heat added per second at full drive (arbitrary units; 1.0 = thermal limit).
+    pub heating_rate: f32,
+    /// Fraction of the stored heat dissipated per second.
+    pub cooling_rate: f32,
+    /// Maximum fraction of time the actuator may be driven, over `duty_window` seconds.
+    pub max_duty: f32,
+    pub duty_window: f32,
+    /// Fraction of either budget above which output is attenuated.
+    pub soft_limit: f32,
+    /// Drive level below which the actuator counts as idle.
+    pub idle_threshold: f32,
+}
+
+impl ActuatorLimits {
+    /// Linear resonant actuator: cools quickly, tolerates long buzzes.
+    pub const LRA: Self = Self {
+        heating_rate: 0.05,
+        cooling_rate: 0.1,
+        max_duty: 0.6,
+        duty_window: 10.0,
+        soft_limit: 0.75,
+        idle_threshold: 0.02,
+    };
+
+    /// Eccentric rotating mass motor: heats faster and needs more rest.
+    pub const ERM: Self = Self {
+        heating_rate: 0.1,
+        cooling_rate: 0.05,
+        max_duty: 0.4,
+        duty_window: 10.0,
+        soft_limit: 0.7,
+        idle_threshold: 0.05,
+    };
+}
+
+impl Default for ActuatorLimits {
+    fn default() -> Self {
+        Self::LRA
+    }
+}
+
+// ============================================================================
+// Budget
+// ============================================================================
+
+/// Tracks heat and duty cycle of one actuator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActuatorBudget {
+    pub limits: ActuatorLimits,
+    heat: f32,
+    duty: f32,
+}
+
+impl ActuatorBudget {
+    pub fn new(limits: ActuatorLimits) -> Self {
+        Self { limits, heat: 0.0, duty: 0.0 }
+    }
+
+    /// Stored heat relative to the thermal limit (1.0 = limit).
+    #[inline]
+    pub fn heat(&self) -> f32 {
+        self.heat
+    }
+
+    /// Recent fraction of time the actuator was driven.
+    #[inline]
+    pub fn duty(&self) -> f32 {
+        self.duty
+    }
+
+    /// Usage of the tighter of the two budgets, where 1.0 means exhausted.
+    #[inline]
+    pub fn usage(&self) -> f32 {
+        self.heat.max(self.duty / self.limits.max_duty)
+    }
+
+    /// Output gain: 1 below the soft limit, falling linearly to 0 at the hard limit.
+    pub fn gain(&self) -> f32 {
+        let soft = self.limits.soft_limit;
+        let usage = self.usage();
+        if usage <= soft {
+            1.0
+        } else {
+            ((1.0 - usage) / (1.0 - soft)).clamp(0.0, 1.0)
+        }
+    }
+
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.usage() >= 1.0
+    }
+
+    /// Predicts whether an effect of `intensity` lasting `duration` seconds fits the
+    /// thermal budget without crossing the soft limit.
+    pub fn can_afford(&self, intensity: f32, duration: f32) -> bool {
+        let drive = intensity.clamp(0.0, 1.0);
+        let heat = self.heat + self.limits.heating_rate * drive * drive * duration;
+        heat.max(self.duty / self.limits.max_duty) <= self.limits.soft_limit
+    }
+
+    /// Advances the model by `dt` seconds at the given drive level (0..=1).
+    pub fn update(&mut self, drive: f32, dt: f32) {
+        let drive = drive.abs().min(1.0);
+        let l = &self.limits;
+        // Exact solution of dH/dt = k·d² − c·H over the step
+        let decay = (-l.cooling_rate * dt).exp();
+        let input = l.heating_rate * drive * drive;
+        self.heat = if l.cooling_rate > 0.0 {
+            self.heat * decay + input / l.cooling_rate * (1.0 - decay)
+        } else {
+            self.heat + input * dt
+        };
+        let on = if drive > l.idle_threshold { 1.0 } else { 0.0 };
+        let alpha = 1.0 - (-dt / l.duty_window.max(f32::EPSILON)).exp();
+        self.duty += (on - self.duty) * alpha;
+    }
+
+    /// Clears accumulated heat and duty (e.g. after replacing the actuator).
+    pub fn reset(&mut self) {
+        self.heat = 0.0;
+        self.duty = 0.0;
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/duty_tests.rs"]
+mod tests;
@@ -0,0 +1,199 @@
+//! Mixes concurrent vibrotactile effects onto actuators under duty-cycle budgets.
+//!
+//! Effects played on the same actuator are summed and clipped to full drive. The
+//! actuator's [`ActuatorBudget`] scales the mix down as it approaches its thermal or
+//! duty limit; new effects that would push it over the soft limit are deferred until
+//! it has cooled down, or dropped if they wait longer than their deadline.
+
+use super::duty::{ActuatorBudget, ActuatorLimits};
+use std::collections::VecDeque;
+
+/// Index of an actuator registered with a [`Mixer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ActuatorId(pub usize);
+
+/// A constant-intensity effect.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EffectRequest {
+    /// Drive level in 0..=1.
+    pub intensity: f32,
+    /// Seconds.
+    pub duration: f32,
+    /// Higher priorities are started first when deferred effects compete.
+    pub priority: u8,
+    /// Seconds the effect may wait for budget before being dropped.
+    pub max_delay: f32,
+}
+
+impl EffectRequest {
+    pub fn new(intensity: f32, duration: f32) -> Self {
+        Self { intensity, duration, priority: 0, max_delay: 0.25 }
+    }
+
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn with_max_delay(mut self, max_delay: f32) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+}
+
+/// What happened to a played effect.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlayOutcome {
+    Started,
+    /// Started, but the actuator is near its limit and output is scaled by the gain.
+    Attenuated(f32),
+    /// Queued until the budget allows it.
+    Deferred,
+    /// The actuator is exhausted and the effect cannot wait.
+    Rejected,
+}
+
+#[derive(Debug, Clone)]
+struct Active {
+    intensity: f32,
+    remaining: f32,
+}
+
+#[derive(Debug, Clone)]
+struct Deferred {
+    request: EffectRequest,
+    waited: f32,
+}
+
+#[derive(Debug, Clone)]
+struct Actuator {
+    name: String,
+    budget: ActuatorBudget,
+    active: Vec<Active>,
+    deferred: VecDeque<Deferred>,
+    output: f32,
+}
+
+/// Counters for effects that did not play as requested.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MixerStats {
+    pub started: u64,
+    pub deferred: u64,
+    pub dropped: u64,
+}
+
+/// Effect mixer with per-actuator duty-cycle management.
+#[derive(Debug, Clone, Default)]
+pub struct Mixer {
+    actuators: Vec<Actuator>,
+    stats: MixerStats,
+}
+
+impl Mixer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_actuator(&mut self, name: impl Into<String>, limits: ActuatorLimits) -> ActuatorId {
+        self.actuators.push(Actuator {
+            name: name.into(),
+            budget: ActuatorBudget::new(limits),
+            active: Vec::new(),
+            deferred: VecDeque::new(),
+            output: 0.0,
+        });
+        ActuatorId(self.actuators.len() - 1)
+    }
+
+    pub fn find(&self, name: &str) -> Option<ActuatorId> {
+        self.actuators.iter().position(|a| a.name == name).map(ActuatorId)
+    }
+
+    pub fn budget(&self, id: ActuatorId) -> Option<&ActuatorBudget> {
+        self.actuators.get(id.0).map(|a| &a.budget)
+    }
+
+    /// Last computed drive level of an actuator.
+    pub fn output(&self, id: ActuatorId) -> f32 {
+        self.actuators.get(id.0).map_or(0.0, |a| a.output)
+    }
+
+    pub fn stats(&self) -> MixerStats {
+        self.stats
+    }
+
+    /// Plays an effect on an actuator. Effects the budget cannot afford are deferred,
+    /// or started at reduced gain if their `max_delay` is zero.
+    pub fn play(&mut self, id: ActuatorId, request: EffectRequest) -> PlayOutcome {
+        let Some(actuator) = self.actuators.get_mut(id.0) else {
+            return PlayOutcome::Rejected;
+        };
+        if actuator.budget.can_afford(request.intensity, request.duration) {
+            actuator.active.push(Active { intensity: request.intensity, remaining: request.duration });
+            self.stats.started += 1;
+            return PlayOutcome::Started;
+        }
+        if request.max_delay > 0.0 {
+            actuator.deferred.push_back(Deferred { request, waited: 0.0 });
+            self.stats.deferred += 1;
+            return PlayOutcome::Deferred;
+        }
+        if actuator.budget.is_exhausted() {
+            self.stats.dropped += 1;
+            return PlayOutcome::Rejected;
+        }
+        actuator.active.push(Active { intensity: request.intensity, remaining: request.duration });
+        self.stats.started += 1;
+        match actuator.budget.gain() {
+            gain if gain < 1.0 => PlayOutcome::Attenuated(gain),
+            _ => PlayOutcome::Started,
+        }
+    }
+
+    /// Stops all active and deferred effects on an actuator.
+    pub fn stop(&mut self, id: ActuatorId) {
+        if let Some(actuator) = self.actuators.get_mut(id.0) {
+            actuator.active.clear();
+            actuator.deferred.clear();
+        }
+    }
+
+    /// Advances all actuators by `dt` seconds and returns their drive levels.
+    pub fn update(&mut self, dt: f32) -> Vec<f32> {
+        let stats = &mut self.stats;
+        self.actuators
+            .iter_mut()
+            .map(|actuator| {
+                // Start deferred effects the budget now affords, highest priority first
+                actuator.deferred.make_contiguous().sort_by_key(|d| std::cmp::Reverse(d.request.priority));
+                let mut waiting = VecDeque::with_capacity(actuator.deferred.len());
+                while let Some(mut d) = actuator.deferred.pop_front() {
+                    if actuator.budget.can_afford(d.request.intensity, d.request.duration) {
+                        actuator.active.push(Active { intensity: d.request.intensity, remaining: d.request.duration });
+                        stats.started += 1;
+                    } else if d.waited + dt > d.request.max_delay {
+                        stats.dropped += 1;
+                    } else {
+                        d.waited += dt;
+                        waiting.push_back(d);
+                    }
+                }
+                actuator.deferred = waiting;
+
+                let mix: f32 = actuator.active.iter().map(|a| a.intensity).sum::<f32>().clamp(0.0, 1.0);
+                actuator.output = mix * actuator.budget.gain();
+                actuator.budget.update(actuator.output, dt);
+
+                for a in &mut actuator.active {
+                    a.remaining -= dt;
+                }
+                actuator.active.retain(|a| a.remaining > 0.0);
+                actuator.output
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/mixer_tests.rs"]
+mod tests;
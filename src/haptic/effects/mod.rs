@@ -0,0 +1,17 @@
+// src/haptic/effects/mod.rs
+pub mod builder;
+pub mod designer;
+pub mod duty;
+pub mod mixer;
+pub mod pattern;
+pub mod player;
+pub mod signal;
+pub mod waveform;
+pub use builder::Effect;
+pub use designer::{Designer, DesignerError, Keyframe, KeyframeEffect, SampleSink};
+pub use duty::{ActuatorBudget, ActuatorLimits};
+pub use mixer::{ActuatorId, EffectRequest, Mixer, MixerStats, PlayOutcome};
+pub use pattern::{Looped, Sequence};
+pub use player::{EffectPlayer, PlaybackId, PlaybackRate};
+pub use signal::{HapticSample, HapticSignal, ParameterError};
+pub use waveform::{Adsr, Shape, Waveform};
@@ -0,0 +1,155 @@
+//! Sequencing and looping of haptic signals.
+//!
+//! A [`Sequence`] places signals on a timeline, back to back or at explicit start
+//! times; overlapping parts are mixed the way the [`EffectPlayer`](super::EffectPlayer)
+//! mixes concurrent effects. [`Looped`] repeats a signal a number of times or
+//! forever, with an optional pause between repetitions.
+
+use super::signal::{HapticSample, HapticSignal, ParameterError};
+
+// ============================================================================
+// Sequence
+// ============================================================================
+
+/// Signals placed on a shared timeline.
+pub struct Sequence {
+    steps: Vec<(f32, Box<dyn HapticSignal>)>,
+    /// Where the next `then` starts; None after an endless signal.
+    end: Option<f32>,
+}
+
+impl Default for Sequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sequence {
+    pub fn new() -> Self {
+        Self { steps: Vec::new(), end: Some(0.0) }
+    }
+
+    /// Appends `signal` after the previous one ends. Nothing can follow an endless
+    /// signal; later `then` calls are ignored.
+    pub fn then(self, signal: impl HapticSignal + 'static) -> Self {
+        self.then_after(0.0, signal)
+    }
+
+    /// Appends `signal` after a pause of `gap` seconds.
+    pub fn then_after(mut self, gap: f32, signal: impl HapticSignal + 'static) -> Self {
+        if let Some(end) = self.end {
+            self = self.at(end + gap.max(0.0), signal);
+        }
+        self
+    }
+
+    /// Adds `signal` starting at `start` seconds, possibly overlapping others.
+    pub fn at(mut self, start: f32, signal: impl HapticSignal + 'static) -> Self {
+        let start = start.max(0.0);
+        let end = signal.duration().map(|d| start + d);
+        self.end = match (self.end, end) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            _ => None,
+        };
+        self.steps.push((start, Box::new(signal)));
+        self
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+impl HapticSignal for Sequence {
+    fn duration(&self) -> Option<f32> {
+        self.end
+    }
+
+    fn sample(&self, t: f32) -> HapticSample {
+        if t < 0.0 || self.end.is_some_and(|end| t > end) {
+            return HapticSample::SILENT;
+        }
+        HapticSample::mix(
+            self.steps
+                .iter()
+                .filter(|(start, _)| t >= *start)
+                .map(|(start, signal)| signal.sample(t - start)),
+        )
+    }
+}
+
+// ============================================================================
+// Loop
+// ============================================================================
+
+/// A signal repeated `count` times, or forever, with `gap` seconds between plays.
+pub struct Looped<S> {
+    signal: S,
+    pub count: Option<u32>,
+    pub gap: f32,
+}
+
+impl<S: HapticSignal> Looped<S> {
+    /// Repeats forever.
+    pub fn new(signal: S) -> Self {
+        Self { signal, count: None, gap: 0.0 }
+    }
+
+    pub fn times(signal: S, count: u32) -> Self {
+        Self { signal, count: Some(count), gap: 0.0 }
+    }
+
+    pub fn with_gap(mut self, gap: f32) -> Self {
+        self.gap = gap.max(0.0);
+        self
+    }
+
+    #[inline]
+    pub fn signal(&self) -> &S {
+        &self.signal
+    }
+
+    /// Length of one repetition including the gap; None if the signal is endless.
+    fn period(&self) -> Option<f32> {
+        self.signal.duration().map(|d| d + self.gap).filter(|p| *p > 0.0)
+    }
+}
+
+impl<S: HapticSignal> HapticSignal for Looped<S> {
+    fn duration(&self) -> Option<f32> {
+        match (self.period(), self.count) {
+            // The last repetition needs no trailing gap
+            (Some(period), Some(count)) => Some((period * count as f32 - self.gap).max(0.0)),
+            (Some(_), None) => None,
+            (None, _) => self.signal.duration(),
+        }
+    }
+
+    fn sample(&self, t: f32) -> HapticSample {
+        if t < 0.0 || self.duration().is_some_and(|d| t > d) {
+            return HapticSample::SILENT;
+        }
+        match self.period() {
+            Some(period) => self.signal.sample(t % period),
+            None => self.signal.sample(t),
+        }
+    }
+
+    fn parameters(&self) -> Vec<(String, f32)> {
+        self.signal.parameters()
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), ParameterError> {
+        self.signal.set_parameter(name, value)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/pattern_tests.rs"]
+mod tests;
@@ -0,0 +1,180 @@
+//! Effect playback that adapts to the device tier.
+//!
+//! Effects are authored once as [`HapticSignal`]s. On kinesthetic devices the
+//! player is ticked from the servo loop and samples each effect at the current
+//! time. Vibration-only backends (gamepads, phones) are updated from the UI thread
+//! at frame rate, where point sampling would skip any transient shorter than a
+//! frame: a 5 ms click falls between two 16 ms frames. In frame mode the player
+//! therefore scans the whole frame interval and holds each effect's peak for the
+//! frame, so short transients stretch to one frame instead of vanishing.
+
+use super::signal::{HapticSample, HapticSignal};
+use crate::device::{DeviceCapabilities, DeviceError, DeviceTier, HapticDevice};
+
+/// Spacing of the samples taken across one frame in frame mode.
+const FRAME_SCAN_STEP: f32 = 0.001;
+/// Upper bound on samples per effect per frame, for long hitches.
+const MAX_FRAME_SCAN_SAMPLES: usize = 64;
+
+// ============================================================================
+// Playback Rate
+// ============================================================================
+
+/// How often the player is ticked, which decides how effects are sampled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlaybackRate {
+    /// Ticked at servo rate; each effect is sampled at the current time.
+    Servo,
+    /// Ticked once per UI frame; each effect contributes its peak over the frame.
+    Frame,
+}
+
+impl PlaybackRate {
+    /// Servo rate for kinesthetic devices, frame rate for vibration-only ones.
+    pub fn for_device(capabilities: &DeviceCapabilities) -> Self {
+        match capabilities.tier {
+            DeviceTier::Kinesthetic => PlaybackRate::Servo,
+            DeviceTier::Vibrotactile => PlaybackRate::Frame,
+        }
+    }
+}
+
+// ============================================================================
+// Player
+// ============================================================================
+
+/// Handle to an effect started on an [`EffectPlayer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PlaybackId(u64);
+
+struct Playing {
+    id: PlaybackId,
+    actuator: usize,
+    signal: Box<dyn HapticSignal>,
+    elapsed: f32,
+}
+
+/// Plays concurrent effects onto a device's vibration actuators.
+pub struct EffectPlayer {
+    rate: PlaybackRate,
+    outputs: Vec<HapticSample>,
+    playing: Vec<Playing>,
+    next_id: u64,
+}
+
+impl EffectPlayer {
+    pub fn new(rate: PlaybackRate, actuators: usize) -> Self {
+        Self { rate, outputs: vec![HapticSample::SILENT; actuators], playing: Vec::new(), next_id: 0 }
+    }
+
+    /// Player with the rate and actuator count suited to a device.
+    pub fn for_device(capabilities: &DeviceCapabilities) -> Self {
+        Self::new(PlaybackRate::for_device(capabilities), capabilities.actuators)
+    }
+
+    #[inline]
+    pub fn rate(&self) -> PlaybackRate {
+        self.rate
+    }
+
+    #[inline]
+    pub fn actuator_count(&self) -> usize {
+        self.outputs.len()
+    }
+
+    /// Number of effects still playing.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.playing.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.playing.is_empty()
+    }
+
+    /// Starts an effect on an actuator from the next tick.
+    pub fn play(&mut self, actuator: usize, signal: Box<dyn HapticSignal>) -> Result<PlaybackId, DeviceError> {
+        if actuator >= self.outputs.len() {
+            return Err(DeviceError::InvalidActuator(actuator));
+        }
+        let id = PlaybackId(self.next_id);
+        self.next_id += 1;
+        self.playing.push(Playing { id, actuator, signal, elapsed: 0.0 });
+        Ok(id)
+    }
+
+    /// Stops an effect; returns false if it had already finished.
+    pub fn stop(&mut self, id: PlaybackId) -> bool {
+        let before = self.playing.len();
+        self.playing.retain(|p| p.id != id);
+        self.playing.len() != before
+    }
+
+    pub fn stop_all(&mut self) {
+        self.playing.clear();
+    }
+
+    #[inline]
+    pub fn is_playing(&self, id: PlaybackId) -> bool {
+        self.playing.iter().any(|p| p.id == id)
+    }
+
+    /// Last computed output per actuator.
+    #[inline]
+    pub fn outputs(&self) -> &[HapticSample] {
+        &self.outputs
+    }
+
+    /// Advances playback by `dt` seconds and returns the mixed output per actuator.
+    ///
+    /// Intensities are summed and clipped to full drive; sharpness is the
+    /// intensity-weighted mean of the contributing effects.
+    pub fn update(&mut self, dt: f32) -> &[HapticSample] {
+        let dt = dt.max(0.0);
+        let mut weighted_sharpness = vec![0.0f32; self.outputs.len()];
+        self.outputs.fill(HapticSample::SILENT);
+
+        for playing in &mut self.playing {
+            let sample = match self.rate {
+                PlaybackRate::Servo => playing.signal.sample(playing.elapsed),
+                PlaybackRate::Frame => peak_over(playing.signal.as_ref(), playing.elapsed, dt),
+            };
+            let out = &mut self.outputs[playing.actuator];
+            out.intensity += sample.intensity;
+            weighted_sharpness[playing.actuator] += sample.intensity * sample.sharpness;
+            playing.elapsed += dt;
+        }
+        for (out, sharpness) in self.outputs.iter_mut().zip(weighted_sharpness) {
+            if out.intensity > 0.0 {
+                out.sharpness = (sharpness / out.intensity).clamp(0.0, 1.0);
+            }
+            out.intensity = out.intensity.clamp(0.0, 1.0);
+        }
+
+        self.playing.retain(|p| p.signal.duration().is_none_or(|d| p.elapsed < d));
+        &self.outputs
+    }
+
+    /// Advances playback and writes the result to every actuator of `device`.
+    pub fn drive(&mut self, device: &mut dyn HapticDevice, dt: f32) -> Result<(), DeviceError> {
+        self.update(dt);
+        for (actuator, sample) in self.outputs.iter().enumerate() {
+            device.set_vibration(actuator, *sample)?;
+        }
+        Ok(())
+    }
+}
+
+/// Strongest sample of `signal` within `[start, start + dt)`.
+fn peak_over(signal: &dyn HapticSignal, start: f32, dt: f32) -> HapticSample {
+    let steps = ((dt / FRAME_SCAN_STEP).ceil() as usize).clamp(1, MAX_FRAME_SCAN_SAMPLES);
+    let step = dt / steps as f32;
+    (0..steps)
+        .map(|i| signal.sample(start + i as f32 * step))
+        .fold(HapticSample::SILENT, |peak, s| if s.intensity > peak.intensity { s } else { peak })
+}
+
+#[cfg(test)]
+#[path = "tests/player_tests.rs"]
+mod tests;
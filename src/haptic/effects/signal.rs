@@ -0,0 +1,73 @@
+//! Time-domain haptic signals shared by the mixer, designer and device backends.
+
+use std::fmt;
+
+/// One output sample of a vibrotactile signal.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HapticSample {
+    /// Drive strength in 0..=1.
+    pub intensity: f32,
+    /// Perceived crispness in 0..=1 (maps to frequency or waveform on the actuator).
+    pub sharpness: f32,
+}
+
+impl HapticSample {
+    pub const SILENT: Self = Self { intensity: 0.0, sharpness: 0.0 };
+
+    #[inline]
+    pub const fn new(intensity: f32, sharpness: f32) -> Self {
+        Self { intensity, sharpness }
+    }
+
+    /// Mixes concurrent samples the way the player does: intensities are summed
+    /// and clipped, sharpness is intensity-weighted.
+    pub fn mix(samples: impl IntoIterator<Item = HapticSample>) -> Self {
+        let (mut intensity, mut weighted_sharpness) = (0.0, 0.0);
+        for sample in samples {
+            intensity += sample.intensity;
+            weighted_sharpness += sample.intensity * sample.sharpness;
+        }
+        if intensity <= 0.0 {
+            return Self::SILENT;
+        }
+        Self::new(intensity.min(1.0), (weighted_sharpness / intensity).clamp(0.0, 1.0))
+    }
+}
+
+/// A signal that can be sampled at any time offset.
+pub trait HapticSignal: Send {
+    /// Length in seconds, or None for signals that run until stopped.
+    fn duration(&self) -> Option<f32>;
+
+    /// Sample at `t` seconds from the start; outside the duration the signal is silent.
+    fn sample(&self, t: f32) -> HapticSample;
+
+    /// Tweakable parameters and their current values.
+    fn parameters(&self) -> Vec<(String, f32)> {
+        Vec::new()
+    }
+
+    /// Sets a parameter; fails for unknown names or out-of-range values.
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), ParameterError> {
+        let _ = value;
+        Err(ParameterError::Unknown(name.to_string()))
+    }
+}
+
+/// Errors when tweaking signal parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParameterError {
+    Unknown(String),
+    OutOfRange { name: String, value: f32 },
+}
+
+impl fmt::Display for ParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParameterError::Unknown(name) => write!(f, "unknown parameter '{}'", name),
+            ParameterError::OutOfRange { name, value } => write!(f, "{} is out of range for '{}'", value, name),
+        }
+    }
+}
+
+impl std::error::Error for ParameterError {}
@@ -0,0 +1,66 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+fn example() -> Effect {
+    Effect::new().transient(0.0, 0.8).continuous(0.1..0.5, constant(0.6))
+}
+
+#[test]
+fn test_parts_play_on_one_timeline() {
+    let effect = example();
+    assert!((effect.duration().unwrap() - 0.5).abs() < TEST_EPSILON);
+    assert_eq!(effect.sample(0.0), HapticSample::new(0.8, TRANSIENT_SHARPNESS));
+    assert_eq!(effect.sample(0.05), HapticSample::SILENT);
+    assert!((effect.sample(0.3).intensity - 0.6).abs() < TEST_EPSILON);
+    assert!((effect.sample(0.5).intensity - 0.6).abs() < TEST_EPSILON);
+    assert_eq!(effect.sample(0.51), HapticSample::SILENT);
+}
+
+#[test]
+fn test_transient_duration_counts() {
+    let effect = Effect::new().transient(0.3, 1.0);
+    assert!(effect.duration().unwrap() > 0.3);
+    assert_eq!(Effect::new().duration(), Some(0.0));
+    assert!(Effect::new().is_empty());
+}
+
+#[test]
+fn test_sections_are_cut_and_mixed() {
+    let effect = Effect::new()
+        .continuous(0.0..0.2, constant(0.5).with_sharpness(0.0))
+        .continuous(0.1..0.3, constant(0.5).with_sharpness(1.0));
+    let overlap = effect.sample(0.15);
+    assert!((overlap.intensity - 1.0).abs() < TEST_EPSILON);
+    assert!((overlap.sharpness - 0.5).abs() < TEST_EPSILON);
+    assert_eq!(effect.sample(0.25), HapticSample::new(0.5, 1.0));
+    assert_eq!(effect.sections().collect::<Vec<_>>(), vec![0.0..0.2, 0.1..0.3]);
+
+    // A section starts its signal from zero
+    let throb = Effect::new().continuous(1.0..2.0, sine(1.0));
+    assert!(throb.sample(1.0).intensity.abs() < TEST_EPSILON);
+    assert!((throb.sample(1.5).intensity - 1.0).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_curves_shape_everything() {
+    let effect = example().intensity_curve(&[(0.5, 0.0), (0.1, 1.0)]).sharpness_curve(&[(0.0, 0.2)]);
+    assert!((effect.sample(0.3).intensity - 0.3).abs() < TEST_EPSILON);
+    assert!((effect.sample(0.3).sharpness - 0.7).abs() < TEST_EPSILON);
+    assert!((effect.sustained(0.3).intensity - 0.3).abs() < TEST_EPSILON);
+    // Curves hold their end values
+    let (time, tap) = effect.transients().next().unwrap();
+    assert_eq!(time, 0.0);
+    assert!((tap.intensity - 0.8).abs() < TEST_EPSILON);
+    assert!((tap.sharpness - 0.7).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_transients_are_sorted_and_clamped() {
+    let effect = Effect::new().transient(0.2, 1.5).transient_with(-1.0, HapticSample::new(0.5, 2.0));
+    let transients: Vec<(f32, HapticSample)> = effect.transients().collect();
+    assert_eq!(
+        transients,
+        vec![(0.0, HapticSample::new(0.5, 1.0)), (0.2, HapticSample::new(1.0, TRANSIENT_SHARPNESS))]
+    );
+}
@@ -0,0 +1,82 @@
+use super::*;
+use std::sync::{Arc, Mutex};
+
+const CLICK: &str = "load click duration 0.1; key 0 0 0.5; key 0.02 1 0.9; key 0.1 0 0.5";
+
+fn designer() -> (Designer, Arc<Mutex<Vec<HapticSample>>>) {
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let sink = output.clone();
+    (Designer::new(move |s| sink.lock().unwrap().push(s)), output)
+}
+
+#[test]
+fn test_keyframe_effect_interpolates() {
+    let effect = KeyframeEffect::parse("key 0.2 0 0\nkey 0 0 1 # attack\nkey 0.1 1 0.5").unwrap();
+    assert_eq!(effect.keys()[0].time, 0.0);
+    assert_eq!(effect.duration(), Some(0.2));
+    let s = effect.sample(0.05);
+    assert!((s.intensity - 0.5).abs() < 1e-6 && (s.sharpness - 0.75).abs() < 1e-6);
+    assert_eq!(effect.sample(0.3), HapticSample::SILENT);
+    assert!(KeyframeEffect::parse("key 0 one 0").is_err());
+    assert!(KeyframeEffect::parse("duration 1").is_err());
+}
+
+#[test]
+fn test_parameters_are_live() {
+    let mut effect = KeyframeEffect::parse("key 0 0.5 0.5; key 1 0.5 0.5").unwrap();
+    effect.set_parameter("gain", 2.0).unwrap();
+    effect.set_parameter("speed", 2.0).unwrap();
+    assert_eq!(effect.sample(0.4).intensity, 1.0);
+    assert_eq!(effect.duration(), Some(0.5));
+    assert_eq!(effect.set_parameter("gain", 5.0), Err(ParameterError::OutOfRange { name: "gain".into(), value: 5.0 }));
+    assert!(matches!(effect.set_parameter("tone", 1.0), Err(ParameterError::Unknown(_))));
+}
+
+#[test]
+fn test_scrub_renders_on_device() {
+    let (mut designer, output) = designer();
+    assert_eq!(designer.scrub(0.0), Err(DesignerError::NoEffect));
+    assert_eq!(designer.handle_command(CLICK), "ok");
+    assert_eq!(designer.handle_command("scrub 0.02"), "ok 1 0.9");
+    assert_eq!(designer.playhead(), 0.02);
+    assert_eq!(designer.handle_command("scrub 5"), "ok 0 0.5");
+    assert_eq!(designer.playhead(), 0.1);
+    assert_eq!(output.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn test_play_once_and_loop() {
+    let (mut designer, output) = designer();
+    designer.handle_command(CLICK);
+    designer.play().unwrap();
+    let mut ticks = 0;
+    while designer.tick(0.01).is_some() {
+        ticks += 1;
+    }
+    assert_eq!(ticks, 11);
+    assert!(!designer.is_playing());
+
+    designer.handle_command("loop 0.02 0.06");
+    assert_eq!(designer.playhead(), 0.02);
+    designer.handle_command("play");
+    for _ in 0..100 {
+        designer.tick(0.01);
+        assert!((0.02..0.06).contains(&designer.playhead()));
+    }
+    designer.handle_command("stop");
+    assert_eq!(designer.playhead(), 0.02);
+    assert_eq!(*output.lock().unwrap().last().unwrap(), HapticSample::SILENT);
+}
+
+#[test]
+fn test_control_protocol_replies() {
+    let (mut designer, _) = designer();
+    assert_eq!(designer.handle_command("play"), "err no effect loaded");
+    designer.handle_command(CLICK);
+    assert_eq!(designer.handle_command("set gain 0.5"), "ok");
+    assert_eq!(designer.handle_command("params"), "ok gain=0.5 speed=1 sharpness=0");
+    assert_eq!(designer.handle_command("loop 0.5 0.1"), "err invalid loop range 0.5..0.1");
+    assert_eq!(designer.handle_command("status"), "ok click paused t=0 duration=0.1 loop=off");
+    assert!(designer.handle_command("rewind").starts_with("err unknown command"));
+    assert!(designer.handle_command("scrub soon").starts_with("err cannot parse"));
+}
@@ -0,0 +1,50 @@
+use super::*;
+
+fn drive(budget: &mut ActuatorBudget, level: f32, seconds: f32) {
+    let steps = (seconds / 0.01).round() as usize;
+    for _ in 0..steps {
+        budget.update(level, 0.01);
+    }
+}
+
+#[test]
+fn test_idle_budget_is_full() {
+    let budget = ActuatorBudget::new(ActuatorLimits::ERM);
+    assert_eq!(budget.gain(), 1.0);
+    assert!(budget.can_afford(1.0, 1.0));
+    assert!(!budget.can_afford(1.0, 10.0));
+}
+
+#[test]
+fn test_continuous_drive_attenuates_then_exhausts() {
+    let mut budget = ActuatorBudget::new(ActuatorLimits::ERM);
+    drive(&mut budget, 1.0, 2.0);
+    assert_eq!(budget.gain(), 1.0);
+    drive(&mut budget, 1.0, 6.0);
+    assert!(budget.gain() < 1.0);
+    drive(&mut budget, 1.0, 20.0);
+    assert!(budget.is_exhausted());
+    assert_eq!(budget.gain(), 0.0);
+}
+
+#[test]
+fn test_budget_recovers_when_idle() {
+    let mut budget = ActuatorBudget::new(ActuatorLimits::ERM);
+    drive(&mut budget, 1.0, 20.0);
+    let heat = budget.heat();
+    drive(&mut budget, 0.0, 30.0);
+    assert!(budget.heat() < heat * 0.5);
+    assert!(budget.duty() < 0.1);
+    assert_eq!(budget.gain(), 1.0);
+}
+
+#[test]
+fn test_lra_is_duty_limited() {
+    // LRA heat settles at heating/cooling = 0.5, so the duty cycle is the binding limit
+    let mut budget = ActuatorBudget::new(ActuatorLimits::LRA);
+    drive(&mut budget, 1.0, 60.0);
+    assert!(budget.heat() < 0.51);
+    assert!(budget.is_exhausted());
+    budget.reset();
+    assert_eq!(budget.usage(), 0.0);
+}
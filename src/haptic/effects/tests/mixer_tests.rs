@@ -0,0 +1,67 @@
+use super::*;
+
+#[test]
+fn test_effects_sum_and_expire() {
+    let mut mixer = Mixer::new();
+    let id = mixer.add_actuator("wrist", ActuatorLimits::LRA);
+    assert_eq!(mixer.find("wrist"), Some(id));
+    assert_eq!(mixer.play(id, EffectRequest::new(0.4, 0.05)), PlayOutcome::Started);
+    assert_eq!(mixer.play(id, EffectRequest::new(0.8, 0.02)), PlayOutcome::Started);
+
+    assert_eq!(mixer.update(0.01), vec![1.0]);
+    mixer.update(0.01);
+    assert!((mixer.update(0.01)[0] - 0.4).abs() < 1e-6);
+    for _ in 0..3 {
+        mixer.update(0.01);
+    }
+    assert_eq!(mixer.output(id), 0.0);
+}
+
+#[test]
+fn test_hot_actuator_defers_and_then_plays() {
+    let mut mixer = Mixer::new();
+    let id = mixer.add_actuator("erm", ActuatorLimits::ERM);
+    mixer.play(id, EffectRequest::new(1.0, 6.0));
+    for _ in 0..600 {
+        mixer.update(0.01);
+    }
+    let outcome = mixer.play(id, EffectRequest::new(1.0, 0.5).with_max_delay(30.0));
+    assert_eq!(outcome, PlayOutcome::Deferred);
+    assert_eq!(mixer.update(0.01), vec![0.0]);
+
+    let mut started = false;
+    for _ in 0..3000 {
+        if mixer.update(0.01)[0] > 0.0 {
+            started = true;
+            break;
+        }
+    }
+    assert!(started);
+    assert_eq!(mixer.stats().deferred, 1);
+    assert_eq!(mixer.stats().dropped, 0);
+}
+
+#[test]
+fn test_urgent_effects_attenuate_and_deferred_ones_expire() {
+    let mut mixer = Mixer::new();
+    let id = mixer.add_actuator("erm", ActuatorLimits::ERM);
+    // Too long for the thermal budget, but cannot wait: starts at full gain while the actuator is cold
+    assert_eq!(mixer.play(id, EffectRequest::new(1.0, 10.0).with_max_delay(0.0)), PlayOutcome::Started);
+    // Past the soft duty limit (~3.3 s of continuous drive) but not yet exhausted
+    for _ in 0..400 {
+        mixer.update(0.01);
+    }
+    match mixer.play(id, EffectRequest::new(0.5, 1.0).with_max_delay(0.0)) {
+        PlayOutcome::Attenuated(gain) => assert!(gain > 0.0 && gain < 1.0),
+        other => panic!("expected attenuation, got {:?}", other),
+    }
+
+    assert_eq!(mixer.play(id, EffectRequest::new(0.5, 1.0).with_max_delay(0.05)), PlayOutcome::Deferred);
+    for _ in 0..10 {
+        mixer.update(0.01);
+    }
+    assert_eq!(mixer.stats().dropped, 1);
+
+    mixer.stop(id);
+    assert_eq!(mixer.update(0.01), vec![0.0]);
+}
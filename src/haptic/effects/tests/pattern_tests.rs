@@ -0,0 +1,60 @@
+use super::*;
+use crate::core::Hertz;
+use crate::effects::Waveform;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+#[test]
+fn test_sequence_plays_back_to_back() {
+    let sequence = Sequence::new()
+        .then(Waveform::constant(Some(0.1)).with_sharpness(0.2))
+        .then_after(0.05, Waveform::constant(Some(0.1)).with_sharpness(0.9));
+    assert_eq!(sequence.len(), 2);
+    assert!((sequence.duration().unwrap() - 0.25).abs() < TEST_EPSILON);
+    assert_eq!(sequence.sample(0.05), HapticSample::new(1.0, 0.2));
+    assert_eq!(sequence.sample(0.12), HapticSample::SILENT);
+    assert_eq!(sequence.sample(0.2), HapticSample::new(1.0, 0.9));
+    assert_eq!(sequence.sample(0.3), HapticSample::SILENT);
+}
+
+#[test]
+fn test_sequence_mixes_overlaps() {
+    let sequence = Sequence::new()
+        .at(0.0, Waveform::constant(Some(0.2)).with_intensity(0.25).with_sharpness(0.0))
+        .at(0.1, Waveform::constant(Some(0.2)).with_intensity(0.75).with_sharpness(1.0));
+    let mixed = sequence.sample(0.15);
+    assert!((mixed.intensity - 1.0).abs() < TEST_EPSILON);
+    assert!((mixed.sharpness - 0.75).abs() < TEST_EPSILON);
+    assert!((sequence.duration().unwrap() - 0.3).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_nothing_follows_endless_signal() {
+    let sequence = Sequence::new().then(Waveform::sine(Hertz(1.0), None)).then(Waveform::click());
+    assert_eq!(sequence.len(), 1);
+    assert_eq!(sequence.duration(), None);
+}
+
+#[test]
+fn test_loop_count_and_gap() {
+    let looped = Looped::times(Waveform::constant(Some(0.1)), 3).with_gap(0.1);
+    assert!((looped.duration().unwrap() - 0.5).abs() < TEST_EPSILON);
+    let on: Vec<bool> = [0.05, 0.15, 0.25, 0.35, 0.45, 0.55]
+        .iter()
+        .map(|t| looped.sample(*t).intensity > 0.0)
+        .collect();
+    assert_eq!(on, vec![true, false, true, false, true, false]);
+
+    let forever = Looped::new(Waveform::constant(Some(0.25))).with_gap(0.25);
+    assert_eq!(forever.duration(), None);
+    assert_eq!(forever.sample(100.1).intensity, 1.0);
+    assert_eq!(forever.sample(100.3), HapticSample::SILENT);
+}
+
+#[test]
+fn test_loop_forwards_parameters() {
+    let mut looped = Looped::new(Waveform::click());
+    looped.set_parameter("intensity", 0.5).unwrap();
+    assert!((looped.sample(0.0).intensity - 0.5).abs() < TEST_EPSILON);
+    assert_eq!(looped.parameters(), looped.signal().parameters());
+}
@@ -0,0 +1,96 @@
+use super::*;
+use crate::effects::{Keyframe, KeyframeEffect};
+
+const FRAME: f32 = 1.0 / 60.0;
+
+/// 4 ms click that starts 6 ms in, entirely between two 60 Hz frame samples.
+fn click() -> Box<dyn HapticSignal> {
+    let key = |time, intensity| Keyframe { time, intensity, sharpness: 1.0 };
+    Box::new(KeyframeEffect::new(vec![key(0.0, 0.0), key(0.006, 0.0), key(0.008, 1.0), key(0.010, 0.0)], None))
+}
+
+fn buzz(intensity: f32, sharpness: f32, duration: f32) -> Box<dyn HapticSignal> {
+    let key = |time| Keyframe { time, intensity, sharpness };
+    Box::new(KeyframeEffect::new(vec![key(0.0), key(duration)], None))
+}
+
+struct Rumble {
+    motors: Vec<HapticSample>,
+}
+
+impl HapticDevice for Rumble {
+    fn name(&self) -> &str {
+        "rumble"
+    }
+
+    fn capabilities(&self) -> DeviceCapabilities {
+        DeviceCapabilities::GAMEPAD
+    }
+
+    fn set_vibration(&mut self, actuator: usize, sample: HapticSample) -> Result<(), DeviceError> {
+        let motor = self.motors.get_mut(actuator).ok_or(DeviceError::InvalidActuator(actuator))?;
+        *motor = sample;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_rate_follows_device_tier() {
+    assert_eq!(PlaybackRate::for_device(&DeviceCapabilities::KINESTHETIC), PlaybackRate::Servo);
+    let player = EffectPlayer::for_device(&DeviceCapabilities::GAMEPAD);
+    assert_eq!(player.rate(), PlaybackRate::Frame);
+    assert_eq!(player.actuator_count(), 2);
+}
+
+#[test]
+fn test_frame_mode_keeps_short_transients() {
+    let mut servo = EffectPlayer::new(PlaybackRate::Servo, 1);
+    servo.play(0, click()).unwrap();
+    assert_eq!(servo.update(FRAME)[0], HapticSample::SILENT);
+
+    let mut frame = EffectPlayer::new(PlaybackRate::Frame, 1);
+    let id = frame.play(0, click()).unwrap();
+    let out = frame.update(FRAME)[0];
+    assert!(out.intensity > 0.9, "{out:?}");
+    assert_eq!(out.sharpness, 1.0);
+
+    // The click has finished and is dropped after its frame
+    assert!(!frame.is_playing(id));
+    assert_eq!(frame.update(FRAME)[0], HapticSample::SILENT);
+}
+
+#[test]
+fn test_mixing_and_stop() {
+    let mut player = EffectPlayer::new(PlaybackRate::Servo, 2);
+    let soft = player.play(0, buzz(0.25, 0.0, 1.0)).unwrap();
+    player.play(0, buzz(0.75, 1.0, 1.0)).unwrap();
+    player.play(1, buzz(0.9, 0.5, 1.0)).unwrap();
+    player.play(1, buzz(0.9, 0.5, 1.0)).unwrap();
+    assert_eq!(player.play(2, buzz(1.0, 1.0, 1.0)), Err(DeviceError::InvalidActuator(2)));
+
+    let out = player.update(0.001).to_vec();
+    assert!((out[0].intensity - 1.0).abs() < 1e-6);
+    assert!((out[0].sharpness - 0.75).abs() < 1e-6);
+    assert_eq!(out[1], HapticSample::new(1.0, 0.5));
+
+    assert!(player.stop(soft));
+    assert!(!player.stop(soft));
+    assert_eq!(player.update(0.001)[0], HapticSample::new(0.75, 1.0));
+    player.stop_all();
+    assert!(player.is_empty());
+}
+
+#[test]
+fn test_drive_writes_every_actuator() {
+    let mut device = Rumble { motors: vec![HapticSample::new(1.0, 1.0); 2] };
+    let mut player = EffectPlayer::for_device(&device.capabilities());
+    player.play(1, buzz(0.5, 0.2, 0.1)).unwrap();
+    player.drive(&mut device, FRAME).unwrap();
+    assert_eq!(device.motors, vec![HapticSample::SILENT, HapticSample::new(0.5, 0.2)]);
+
+    for _ in 0..6 {
+        player.drive(&mut device, FRAME).unwrap();
+    }
+    assert!(player.is_empty());
+    assert_eq!(device.motors[1], HapticSample::SILENT);
+}
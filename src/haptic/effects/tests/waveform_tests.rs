@@ -0,0 +1,70 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+#[test]
+fn test_sine_throbs_at_frequency() {
+    let wave = Waveform::sine(Hertz(2.0), Some(1.0));
+    assert!(wave.sample(0.0).intensity.abs() < TEST_EPSILON);
+    assert!((wave.sample(0.25).intensity - 1.0).abs() < TEST_EPSILON);
+    assert!(wave.sample(0.5).intensity.abs() < TEST_EPSILON);
+    assert_eq!(wave.sample(1.5), HapticSample::SILENT);
+}
+
+#[test]
+fn test_square_duty_cycle() {
+    let wave = Waveform::square(Hertz(10.0), 0.25, None);
+    let on = (0..1000).filter(|i| wave.sample(*i as f32 * 0.001).intensity > 0.5).count();
+    assert!((on as i32 - 250).abs() <= 2, "{}", on);
+    assert_eq!(wave.duration(), None);
+}
+
+#[test]
+fn test_click_is_short_and_sharp() {
+    let click = Waveform::click();
+    assert_eq!(click.sample(0.0), HapticSample::new(1.0, 1.0));
+    assert!(click.sample(0.01).intensity < 0.1);
+    assert_eq!(click.sample(0.05), HapticSample::SILENT);
+}
+
+#[test]
+fn test_ramp_and_intensity() {
+    let ramp = Waveform::ramp(0.2, 1.0, 0.4).with_intensity(0.5).with_sharpness(0.8);
+    assert!((ramp.sample(0.0).intensity - 0.1).abs() < TEST_EPSILON);
+    assert!((ramp.sample(0.2).intensity - 0.3).abs() < TEST_EPSILON);
+    assert!((ramp.sample(0.4).intensity - 0.5).abs() < TEST_EPSILON);
+    assert_eq!(ramp.sample(0.3).sharpness, 0.8);
+}
+
+#[test]
+fn test_adsr_envelope() {
+    let adsr = Adsr::new(0.1, 0.1, 0.5, 0.2);
+    let end = Some(1.0);
+    assert!((adsr.gain(0.05, end) - 0.5).abs() < TEST_EPSILON);
+    assert!((adsr.gain(0.1, end) - 1.0).abs() < TEST_EPSILON);
+    assert!((adsr.gain(0.15, end) - 0.75).abs() < TEST_EPSILON);
+    assert!((adsr.gain(0.5, end) - 0.5).abs() < TEST_EPSILON);
+    assert!((adsr.gain(0.9, end) - 0.25).abs() < TEST_EPSILON);
+    assert!(adsr.gain(1.0, end).abs() < TEST_EPSILON);
+    // Endless signals sustain
+    assert!((adsr.gain(100.0, None) - 0.5).abs() < TEST_EPSILON);
+
+    let wave = Waveform::constant(end).with_envelope(adsr);
+    assert!((wave.sample(0.15).intensity - 0.75).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_parameters() {
+    let mut wave = Waveform::sine(Hertz(4.0), None);
+    let names: Vec<String> = wave.parameters().into_iter().map(|(name, _)| name).collect();
+    assert_eq!(names, vec!["intensity", "sharpness", "frequency"]);
+    wave.set_parameter("frequency", 8.0).unwrap();
+    assert_eq!(wave.shape, Shape::Sine { frequency: Hertz(8.0) });
+    wave.set_parameter("intensity", 0.5).unwrap();
+    assert!((wave.sample(1.0 / 16.0).intensity - 0.5).abs() < TEST_EPSILON);
+    assert!(matches!(wave.set_parameter("sharpness", 1.5), Err(ParameterError::OutOfRange { .. })));
+
+    let mut click = Waveform::click();
+    assert_eq!(click.parameters().len(), 2);
+    assert_eq!(click.set_parameter("frequency", 8.0), Err(ParameterError::Unknown("frequency".to_string())));
+}
@@ -0,0 +1,209 @@
+//! Primitive vibrotactile waveforms and ADSR envelopes.
+//!
+//! Waveforms describe the intensity envelope of a vibration, not the actuator's
+//! carrier: a 4 Hz [`Shape::Sine`] is a slow throb that a backend renders on top of
+//! whatever carrier frequency the sample's sharpness selects. Every [`Waveform`] has
+//! an overall intensity and sharpness, tweakable at runtime as the `intensity` and
+//! `sharpness` parameters (plus `frequency` for periodic shapes), and can be shaped
+//! by an [`Adsr`] envelope.
+
+use std::f32::consts::TAU;
+
+use super::signal::{HapticSample, HapticSignal, ParameterError};
+use crate::core::Hertz;
+
+/// Default length of a click.
+const CLICK_DURATION: f32 = 0.02;
+/// Time constant of a click's exponential decay.
+const CLICK_DECAY: f32 = 0.004;
+/// Range accepted for the `frequency` parameter, in hertz.
+const FREQUENCY_RANGE: std::ops::RangeInclusive<f32> = 0.1..=500.0;
+
+// ============================================================================
+// Envelope
+// ============================================================================
+
+/// Attack-decay-sustain-release envelope; times in seconds, `sustain` a level
+/// in 0..=1. The release ends with the signal, so it needs a finite duration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Adsr {
+    pub attack: f32,
+    pub decay: f32,
+    pub sustain: f32,
+    pub release: f32,
+}
+
+impl Adsr {
+    pub const fn new(attack: f32, decay: f32, sustain: f32, release: f32) -> Self {
+        Self { attack, decay, sustain, release }
+    }
+
+    /// Gain at `t` for a signal lasting `duration` (None for endless).
+    pub fn gain(&self, t: f32, duration: Option<f32>) -> f32 {
+        if t < 0.0 {
+            return 0.0;
+        }
+        let sustain = self.sustain.clamp(0.0, 1.0);
+        let level = if t < self.attack {
+            t / self.attack
+        } else if t < self.attack + self.decay {
+            1.0 - (1.0 - sustain) * (t - self.attack) / self.decay
+        } else {
+            sustain
+        };
+        match duration {
+            Some(end) if self.release > 0.0 && t > end - self.release => level * ((end - t) / self.release).max(0.0),
+            _ => level,
+        }
+    }
+}
+
+// ============================================================================
+// Waveform
+// ============================================================================
+
+/// Intensity envelope of a primitive waveform; each yields levels in 0..=1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shape {
+    Constant,
+    /// Smooth throb between silence and full level.
+    Sine { frequency: Hertz },
+    /// On for `duty` (0..=1) of each period.
+    Square { frequency: Hertz, duty: f32 },
+    /// Instant onset with a fast exponential decay.
+    Click,
+    /// Linear change between two levels over the duration.
+    Ramp { from: f32, to: f32 },
+}
+
+/// A primitive waveform with overall intensity, sharpness and envelope.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Waveform {
+    pub shape: Shape,
+    /// Length in seconds; None runs until stopped.
+    pub duration: Option<f32>,
+    pub intensity: f32,
+    pub sharpness: f32,
+    pub envelope: Option<Adsr>,
+}
+
+impl Waveform {
+    pub fn new(shape: Shape, duration: Option<f32>) -> Self {
+        Self { shape, duration, intensity: 1.0, sharpness: 0.5, envelope: None }
+    }
+
+    pub fn constant(duration: Option<f32>) -> Self {
+        Self::new(Shape::Constant, duration)
+    }
+
+    pub fn sine(frequency: Hertz, duration: Option<f32>) -> Self {
+        Self::new(Shape::Sine { frequency }, duration)
+    }
+
+    pub fn square(frequency: Hertz, duty: f32, duration: Option<f32>) -> Self {
+        Self::new(Shape::Square { frequency, duty: duty.clamp(0.0, 1.0) }, duration)
+    }
+
+    /// A short, crisp tap.
+    pub fn click() -> Self {
+        Self::new(Shape::Click, Some(CLICK_DURATION)).with_sharpness(1.0)
+    }
+
+    pub fn ramp(from: f32, to: f32, duration: f32) -> Self {
+        Self::new(Shape::Ramp { from, to }, Some(duration))
+    }
+
+    pub fn with_intensity(mut self, intensity: f32) -> Self {
+        self.intensity = intensity.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_sharpness(mut self, sharpness: f32) -> Self {
+        self.sharpness = sharpness.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_envelope(mut self, envelope: Adsr) -> Self {
+        self.envelope = Some(envelope);
+        self
+    }
+
+    /// Level of the shape alone at `t`.
+    fn level(&self, t: f32) -> f32 {
+        match self.shape {
+            Shape::Constant => 1.0,
+            Shape::Sine { frequency } => 0.5 - 0.5 * (TAU * frequency.value() * t).cos(),
+            Shape::Square { frequency, duty } => {
+                let phase = (frequency.value() * t).fract();
+                if phase < duty {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Shape::Click => (-t / CLICK_DECAY).exp(),
+            Shape::Ramp { from, to } => match self.duration {
+                Some(d) if d > 0.0 => from + (to - from) * (t / d).min(1.0),
+                _ => from,
+            },
+        }
+    }
+
+    fn frequency_mut(&mut self) -> Option<&mut Hertz> {
+        match &mut self.shape {
+            Shape::Sine { frequency } | Shape::Square { frequency, .. } => Some(frequency),
+            _ => None,
+        }
+    }
+}
+
+impl HapticSignal for Waveform {
+    fn duration(&self) -> Option<f32> {
+        self.duration
+    }
+
+    fn sample(&self, t: f32) -> HapticSample {
+        if t < 0.0 || self.duration.is_some_and(|d| t > d) {
+            return HapticSample::SILENT;
+        }
+        let envelope = self.envelope.map_or(1.0, |e| e.gain(t, self.duration));
+        let intensity = (self.level(t).clamp(0.0, 1.0) * envelope * self.intensity).clamp(0.0, 1.0);
+        HapticSample::new(intensity, self.sharpness)
+    }
+
+    fn parameters(&self) -> Vec<(String, f32)> {
+        let mut parameters = vec![("intensity".to_string(), self.intensity), ("sharpness".to_string(), self.sharpness)];
+        if let Shape::Sine { frequency } | Shape::Square { frequency, .. } = self.shape {
+            parameters.push(("frequency".to_string(), frequency.value()));
+        }
+        parameters
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), ParameterError> {
+        let out_of_range = || ParameterError::OutOfRange { name: name.to_string(), value };
+        match name {
+            "intensity" | "sharpness" if !(0.0..=1.0).contains(&value) => Err(out_of_range()),
+            "intensity" => {
+                self.intensity = value;
+                Ok(())
+            }
+            "sharpness" => {
+                self.sharpness = value;
+                Ok(())
+            }
+            "frequency" => match self.frequency_mut() {
+                Some(_) if !FREQUENCY_RANGE.contains(&value) => Err(out_of_range()),
+                Some(frequency) => {
+                    *frequency = Hertz(value);
+                    Ok(())
+                }
+                None => Err(ParameterError::Unknown(name.to_string())),
+            },
+            _ => Err(ParameterError::Unknown(name.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/waveform_tests.rs"]
+mod tests;
@@ -0,0 +1,161 @@
+//! Axis-aligned bounding boxes.
+
+use super::ray::Ray;
+use crate::core::Vec3;
+
+/// Axis-aligned box given by its minimum and maximum corners.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    /// Inverted box that any point grows into.
+    pub const EMPTY: Self = Self {
+        min: Vec3::splat(f32::INFINITY),
+        max: Vec3::splat(f32::NEG_INFINITY),
+    };
+
+    #[inline]
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min: min.min(max), max: min.max(max) }
+    }
+
+    #[inline]
+    pub fn from_center_half_extents(center: Vec3, half_extents: Vec3) -> Self {
+        Self::new(center - half_extents, center + half_extents)
+    }
+
+    /// Smallest box containing all points; EMPTY for an empty iterator.
+    pub fn from_points(points: impl IntoIterator<Item = Vec3>) -> Self {
+        points.into_iter().fold(Self::EMPTY, |b, p| b.grow(p))
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    #[inline]
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    #[inline]
+    pub fn size(&self) -> Vec3 {
+        self.max - self.min
+    }
+
+    #[inline]
+    pub fn half_extents(&self) -> Vec3 {
+        self.size() * 0.5
+    }
+
+    /// Index (0, 1, 2) of the axis with the largest extent.
+    pub fn longest_axis(&self) -> usize {
+        let s = self.size();
+        if s.x >= s.y && s.x >= s.z {
+            0
+        } else if s.y >= s.z {
+            1
+        } else {
+            2
+        }
+    }
+
+    #[inline]
+    pub fn volume(&self) -> f32 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let s = self.size();
+        s.x * s.y * s.z
+    }
+
+    #[inline]
+    pub fn surface_area(&self) -> f32 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let s = self.size();
+        2.0 * (s.x * s.y + s.y * s.z + s.z * s.x)
+    }
+
+    #[inline]
+    pub fn grow(self, p: Vec3) -> Self {
+        Self { min: self.min.min(p), max: self.max.max(p) }
+    }
+
+    #[inline]
+    pub fn union(self, other: Self) -> Self {
+        Self { min: self.min.min(other.min), max: self.max.max(other.max) }
+    }
+
+    /// Grows the box by `margin` on every side.
+    #[inline]
+    pub fn expand(self, margin: f32) -> Self {
+        Self { min: self.min - Vec3::splat(margin), max: self.max + Vec3::splat(margin) }
+    }
+
+    #[inline]
+    pub fn contains(&self, p: Vec3) -> bool {
+        p.x >= self.min.x && p.x <= self.max.x && p.y >= self.min.y && p.y <= self.max.y && p.z >= self.min.z && p.z <= self.max.z
+    }
+
+    #[inline]
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min.x <= other.max.x
+            && self.max.x >= other.min.x
+            && self.min.y <= other.max.y
+            && self.max.y >= other.min.y
+            && self.min.z <= other.max.z
+            && self.max.z >= other.min.z
+    }
+
+    /// Closest point inside the box to `p`.
+    #[inline]
+    pub fn closest_point(&self, p: Vec3) -> Vec3 {
+        p.clamp(self.min, self.max)
+    }
+
+    #[inline]
+    pub fn distance_squared(&self, p: Vec3) -> f32 {
+        (self.closest_point(p) - p).length_squared()
+    }
+
+    /// Entry and exit distances of `ray` through the box (slab test). The entry
+    /// is negative when the ray starts inside. None if the ray misses.
+    pub fn ray_interval(&self, ray: &Ray) -> Option<(f32, f32)> {
+        let mut t_min = f32::NEG_INFINITY;
+        let mut t_max = f32::INFINITY;
+        for axis in 0..3 {
+            let inv = 1.0 / ray.direction[axis];
+            let t0 = (self.min[axis] - ray.origin[axis]) * inv;
+            let t1 = (self.max[axis] - ray.origin[axis]) * inv;
+            // NaN from 0 * inf (origin on a slab plane, parallel ray) is ignored by min/max
+            t_min = t_min.max(t0.min(t1));
+            t_max = t_max.min(t0.max(t1));
+        }
+        (t_max >= t_min.max(0.0)).then_some((t_min, t_max))
+    }
+
+    /// Distance along `ray` to the box (0 if the origin is inside), up to `max_distance`.
+    #[inline]
+    pub fn ray_distance(&self, ray: &Ray, max_distance: f32) -> Option<f32> {
+        let (enter, _) = self.ray_interval(ray)?;
+        let t = enter.max(0.0);
+        (t <= max_distance).then_some(t)
+    }
+}
+
+impl Default for Aabb {
+    #[inline]
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/aabb_tests.rs"]
+mod tests;
@@ -0,0 +1,337 @@
+//! Closest-point queries behind one trait.
+//!
+//! Every touchable primitive answers the same question, "where is the surface
+//! nearest to this point", with the same [`SurfacePoint`]: the point, the surface
+//! normal there, and which feature (face, edge or vertex) it lies on. Proxy-based
+//! rendering only needs this query, so it can run against any shape without
+//! knowing what the shape is.
+//!
+//! Solids report the nearest point on their boundary, also for points inside, with
+//! an outward normal; [`SurfacePoint::signed_distance`] is then negative inside.
+
+use super::aabb::Aabb;
+use super::hull::ConvexHull;
+use super::mesh::{closest_on_triangle, closest_point_on_segment, ray_triangle, triangle_normal};
+use super::obb::Obb;
+use super::plane::Plane;
+use super::proxy::CollisionProxy;
+use super::ray::Ray;
+use super::sdf::VoxelSdf;
+use super::sweep::Capsule;
+use crate::core::{Vec3, EPSILON};
+
+// ============================================================================
+// Results
+// ============================================================================
+
+/// Part of a shape's boundary. Indices are per shape and documented on each
+/// implementation of [`ClosestPoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Feature {
+    Vertex(u32),
+    Edge(u32),
+    Face(u32),
+}
+
+/// Nearest surface point of a shape to a query point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfacePoint {
+    pub point: Vec3,
+    /// Unit surface normal at `point`. On edges and vertices, the direction from
+    /// `point` toward the query point.
+    pub normal: Vec3,
+    pub feature: Feature,
+}
+
+impl SurfacePoint {
+    /// Reported by shapes with no surface, such as an empty mesh.
+    pub const NONE: Self = Self {
+        point: Vec3::new(f32::INFINITY, f32::INFINITY, f32::INFINITY),
+        normal: Vec3::zero(),
+        feature: Feature::Face(u32::MAX),
+    };
+
+    /// Distance of `p` from the surface along the normal; negative behind it.
+    #[inline]
+    pub fn signed_distance(&self, p: Vec3) -> f32 {
+        (p - self.point).dot(self.normal)
+    }
+
+    /// Whether this is [`SurfacePoint::NONE`].
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        !self.point.x.is_finite()
+    }
+}
+
+/// Shapes that can report their nearest surface point.
+///
+/// Some implementors also have an inherent `closest_point` with different
+/// semantics (e.g. [`Aabb::closest_point`] clamps into the solid); call this one
+/// as `ClosestPoint::closest_point(&shape, p)` or through a generic bound.
+pub trait ClosestPoint {
+    fn closest_point(&self, p: Vec3) -> SurfacePoint;
+}
+
+/// Direction from `q` to `p`, or `fallback` when they coincide.
+#[inline]
+fn direction(q: Vec3, p: Vec3, fallback: Vec3) -> Vec3 {
+    let d = p - q;
+    if d.length_squared() > EPSILON * EPSILON {
+        d.normalize()
+    } else {
+        fallback
+    }
+}
+
+// ============================================================================
+// Primitives
+// ============================================================================
+
+/// Faces are `2 * axis + side` (side 1 on the max face), vertices use the corner
+/// bits of [`Obb::corners`] (bit `axis` set on the max side), and an edge running
+/// along `axis` is `4 * axis` plus the corner bits of the other two axes in order.
+impl ClosestPoint for Aabb {
+    fn closest_point(&self, p: Vec3) -> SurfacePoint {
+        let center = self.center();
+        let local = box_surface(self.half_extents(), p - center);
+        SurfacePoint { point: local.point + center, ..local }
+    }
+}
+
+/// Feature indices as for [`Aabb`], in the box's local axes.
+impl ClosestPoint for Obb {
+    fn closest_point(&self, p: Vec3) -> SurfacePoint {
+        let local = box_surface(self.half_extents, self.to_local(p));
+        SurfacePoint {
+            point: self.to_world(local.point),
+            normal: self.rotation.rotate(local.normal),
+            feature: local.feature,
+        }
+    }
+}
+
+/// Nearest surface point of a centered box in its own frame.
+fn box_surface(half: Vec3, p: Vec3) -> SurfacePoint {
+    let mut point = p.clamp(-half, half);
+    let (mut outside, mut count) = ([0usize; 3], 0);
+    for axis in [0, 1, 2] {
+        if p[axis].abs() > half[axis] {
+            outside[count] = axis;
+            count += 1;
+        }
+    }
+    let side = |axis: usize| u32::from(p[axis] > 0.0);
+    let unit = |axis: usize| {
+        let mut n = Vec3::zero();
+        n[axis] = if p[axis] > 0.0 { 1.0 } else { -1.0 };
+        n
+    };
+    match outside[..count] {
+        [] => {
+            // Inside: project onto the nearest face.
+            let depth = |axis: usize| half[axis] - p[axis].abs();
+            let axis = [1, 2].into_iter().fold(0, |best, axis| if depth(axis) < depth(best) { axis } else { best });
+            let normal = unit(axis);
+            point[axis] = normal[axis] * half[axis];
+            SurfacePoint { point, normal, feature: Feature::Face(2 * axis as u32 + side(axis)) }
+        }
+        [axis] => SurfacePoint { point, normal: unit(axis), feature: Feature::Face(2 * axis as u32 + side(axis)) },
+        [a, b] => {
+            let along = 3 - a - b;
+            SurfacePoint {
+                point,
+                normal: direction(point, p, unit(a)),
+                feature: Feature::Edge(4 * along as u32 + side(a) + 2 * side(b)),
+            }
+        }
+        _ => SurfacePoint {
+            point,
+            normal: direction(point, p, unit(0)),
+            feature: Feature::Vertex(side(0) + 2 * side(1) + 4 * side(2)),
+        },
+    }
+}
+
+/// A single face, `Face(0)`.
+impl ClosestPoint for Plane {
+    fn closest_point(&self, p: Vec3) -> SurfacePoint {
+        SurfacePoint { point: self.project(p), normal: self.normal, feature: Feature::Face(0) }
+    }
+}
+
+/// Triangle `abc`: `Face(0)`, edges ab, bc, ca as `Edge(0..3)` and corners as
+/// `Vertex(0..3)`. Face normals follow the winding, so points behind the face have
+/// negative signed distance.
+impl ClosestPoint for [Vec3; 3] {
+    fn closest_point(&self, p: Vec3) -> SurfacePoint {
+        let (point, feature) = closest_on_triangle(p, *self);
+        let face = triangle_normal(*self);
+        let normal = match feature {
+            Feature::Face(_) => face,
+            _ => direction(point, p, face),
+        };
+        SurfacePoint { point, normal, feature }
+    }
+}
+
+/// `Face(0)` on the cylindrical side, `Vertex(0)` and `Vertex(1)` on the caps
+/// around `a` and `b`.
+impl ClosestPoint for Capsule {
+    fn closest_point(&self, p: Vec3) -> SurfacePoint {
+        let axis = self.b - self.a;
+        let c = closest_point_on_segment(p, self.a, self.b);
+        let t = if axis.length_squared() > 0.0 { (c - self.a).dot(axis) / axis.length_squared() } else { 0.0 };
+        let feature = if t <= 0.0 {
+            Feature::Vertex(0)
+        } else if t >= 1.0 {
+            Feature::Vertex(1)
+        } else {
+            Feature::Face(0)
+        };
+        let normal = direction(c, p, any_perpendicular(axis));
+        SurfacePoint { point: c + normal * self.radius, normal, feature }
+    }
+}
+
+/// Some unit vector perpendicular to `v` (or +X for a zero vector).
+fn any_perpendicular(v: Vec3) -> Vec3 {
+    let other = if v.x.abs() < 0.9 { Vec3::unit_x() } else { Vec3::unit_y() };
+    v.cross(other).try_normalize().unwrap_or(Vec3::unit_x())
+}
+
+/// `Face(i)` is `faces()[i]`; `Vertex(i)` is `vertices()[i]`; `Edge(3 * f + e)`
+/// is edge `e` of face `f` (an edge is shared by two faces and reported by either).
+impl ClosestPoint for ConvexHull {
+    fn closest_point(&self, p: Vec3) -> SurfacePoint {
+        let vertices = self.vertices();
+        let triangle = |f: &[u32; 3]| f.map(|i| vertices[i as usize]);
+        if self.contains(p) {
+            // Inside, the nearest boundary point is the projection onto the nearest face plane.
+            let nearest = self
+                .faces()
+                .iter()
+                .enumerate()
+                .filter_map(|(i, f)| {
+                    let t = triangle(f);
+                    let normal = triangle_normal(t);
+                    (normal != Vec3::zero()).then(|| (i, Plane::from_point_normal(t[0], normal)))
+                })
+                .max_by(|(_, a), (_, b)| a.signed_distance(p).total_cmp(&b.signed_distance(p)));
+            if let Some((i, plane)) = nearest {
+                return SurfacePoint { point: plane.project(p), normal: plane.normal, feature: Feature::Face(i as u32) };
+            }
+        }
+        self.faces()
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let local = triangle(f).closest_point(p);
+                let feature = match local.feature {
+                    Feature::Face(_) => Feature::Face(i as u32),
+                    Feature::Edge(e) => Feature::Edge(3 * i as u32 + e),
+                    Feature::Vertex(v) => Feature::Vertex(f[v as usize]),
+                };
+                SurfacePoint { feature, ..local }
+            })
+            .min_by(|a, b| (a.point - p).length_squared().total_cmp(&(b.point - p).length_squared()))
+            .unwrap_or(SurfacePoint::NONE)
+    }
+}
+
+/// A field has no discrete features; every point is `Face(0)`. The point is one
+/// gradient step from `p`, exact where the field is a true distance.
+impl ClosestPoint for VoxelSdf {
+    fn closest_point(&self, p: Vec3) -> SurfacePoint {
+        match self.normal(p) {
+            Some(normal) => SurfacePoint { point: p - normal * self.sample(p), normal, feature: Feature::Face(0) },
+            None => SurfacePoint::NONE,
+        }
+    }
+}
+
+/// For hull proxies, the surface point of the hull that `p` is deepest inside (or
+/// nearest to), with that hull's feature indices.
+impl ClosestPoint for CollisionProxy {
+    fn closest_point(&self, p: Vec3) -> SurfacePoint {
+        match self {
+            CollisionProxy::Hulls(hulls) => hulls
+                .iter()
+                .map(|hull| hull.closest_point(p))
+                .min_by(|a, b| a.signed_distance(p).total_cmp(&b.signed_distance(p)))
+                .unwrap_or(SurfacePoint::NONE),
+            CollisionProxy::Sdf(sdf) => sdf.closest_point(p),
+        }
+    }
+}
+
+// ============================================================================
+// Segment Queries
+// ============================================================================
+
+/// Closest points between segments `p1q1` and `p2q2`, as (on first, on second)
+/// (Ericson, Real-Time Collision Detection 5.1.9).
+pub fn closest_points_on_segments(p1: Vec3, q1: Vec3, p2: Vec3, q2: Vec3) -> (Vec3, Vec3) {
+    let d1 = q1 - p1;
+    let d2 = q2 - p2;
+    let r = p1 - p2;
+    let a = d1.length_squared();
+    let e = d2.length_squared();
+    let f = d2.dot(r);
+    if a <= EPSILON && e <= EPSILON {
+        return (p1, p2);
+    }
+    let (s, t) = if a <= EPSILON {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = d1.dot(r);
+        if e <= EPSILON {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = d1.dot(d2);
+            let denom = a * e - b * b;
+            // Parallel segments: any s works, start from the first endpoint
+            let s = if denom > EPSILON { ((b * f - c * e) / denom).clamp(0.0, 1.0) } else { 0.0 };
+            let t = (b * s + f) / e;
+            if t < 0.0 {
+                ((-c / a).clamp(0.0, 1.0), 0.0)
+            } else if t > 1.0 {
+                (((b - c) / a).clamp(0.0, 1.0), 1.0)
+            } else {
+                (s, t)
+            }
+        }
+    };
+    (p1 + d1 * s, p2 + d2 * t)
+}
+
+/// Closest points between segment `ab` and a triangle: the point on the segment
+/// and the triangle's surface point nearest to it. Where the segment crosses the
+/// triangle both are the crossing point.
+pub fn closest_segment_triangle(a: Vec3, b: Vec3, triangle: [Vec3; 3]) -> (Vec3, SurfacePoint) {
+    let length = (b - a).length();
+    if length > 0.0 {
+        let ray = Ray::new(a, (b - a) / length);
+        if let Some(t) = ray_triangle(&ray, triangle, length) {
+            let s = ray.at(t);
+            return (s, triangle.closest_point(s));
+        }
+    }
+    let [t0, t1, t2] = triangle;
+    let on_segment = [
+        a,
+        b,
+        closest_points_on_segments(a, b, t0, t1).0,
+        closest_points_on_segments(a, b, t1, t2).0,
+        closest_points_on_segments(a, b, t2, t0).0,
+    ];
+    on_segment
+        .into_iter()
+        .map(|s| (s, triangle.closest_point(s)))
+        .min_by(|(s, x), (t, y)| (x.point - *s).length_squared().total_cmp(&(y.point - *t).length_squared()))
+        .unwrap_or((a, SurfacePoint::NONE))
+}
+
+#[cfg(test)]
+#[path = "tests/closest_tests.rs"]
+mod tests;
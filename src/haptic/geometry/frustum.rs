@@ -0,0 +1,87 @@
+//! View frustums for culling.
+
+use super::aabb::Aabb;
+use super::plane::Plane;
+use crate::core::{DepthRange, Mat4, Vec3};
+
+/// How a volume relates to a frustum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Containment {
+    Outside,
+    Intersects,
+    Inside,
+}
+
+/// Six planes bounding the region a camera sees, with normals pointing inward.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frustum {
+    /// Left, right, bottom, top, near, far.
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    /// Extracts the planes of a view-projection matrix (Gribb–Hartmann).
+    /// `depth` must match the convention the projection was built with.
+    pub fn from_matrix(view_projection: &Mat4, depth: DepthRange) -> Self {
+        let row = |r: usize| {
+            let v = view_projection.row(r);
+            (v.xyz(), v.w)
+        };
+        let (x, y, z, w) = (row(0), row(1), row(2), row(3));
+        let add = |a: (Vec3, f32), b: (Vec3, f32)| (a.0 + b.0, a.1 + b.1);
+        let sub = |a: (Vec3, f32), b: (Vec3, f32)| (a.0 - b.0, a.1 - b.1);
+        let near = match depth {
+            DepthRange::ZeroToOne => z,
+            DepthRange::NegOneToOne => add(w, z),
+        };
+        let planes = [add(w, x), sub(w, x), add(w, y), sub(w, y), near, sub(w, z)].map(|(n, d)| {
+            // n · p + d >= 0 inside
+            let length = n.length();
+            Plane { normal: n / length, offset: -d / length }
+        });
+        Self { planes }
+    }
+
+    #[inline]
+    pub fn contains_point(&self, p: Vec3) -> bool {
+        self.planes.iter().all(|plane| plane.signed_distance(p) >= 0.0)
+    }
+
+    /// Conservative sphere test: may report spheres near a corner as intersecting.
+    #[inline]
+    pub fn intersects_sphere(&self, center: Vec3, radius: f32) -> bool {
+        self.planes.iter().all(|plane| plane.signed_distance(center) >= -radius)
+    }
+
+    /// Classifies a box by testing its extreme corners against each plane. Like the
+    /// sphere test it is conservative near the frustum's edges.
+    pub fn classify(&self, aabb: &Aabb) -> Containment {
+        if aabb.is_empty() {
+            return Containment::Outside;
+        }
+        let mut result = Containment::Inside;
+        for plane in &self.planes {
+            let n = plane.normal;
+            let pick = |positive: bool| {
+                let corner = |axis: usize| if (n[axis] >= 0.0) == positive { aabb.max[axis] } else { aabb.min[axis] };
+                Vec3::new(corner(0), corner(1), corner(2))
+            };
+            if plane.signed_distance(pick(true)) < 0.0 {
+                return Containment::Outside;
+            }
+            if plane.signed_distance(pick(false)) < 0.0 {
+                result = Containment::Intersects;
+            }
+        }
+        result
+    }
+
+    #[inline]
+    pub fn intersects(&self, aabb: &Aabb) -> bool {
+        self.classify(aabb) != Containment::Outside
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/frustum_tests.rs"]
+mod tests;
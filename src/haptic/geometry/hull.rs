@@ -0,0 +1,157 @@
+//! 3D convex hulls built incrementally from point clouds.
+
+use super::aabb::Aabb;
+use super::mesh::TriMesh;
+use super::plane::Plane;
+use crate::core::Vec3;
+use std::collections::HashSet;
+
+/// Closed convex polyhedron with outward-facing triangles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvexHull {
+    vertices: Vec<Vec3>,
+    faces: Vec<[u32; 3]>,
+    planes: Vec<Plane>,
+}
+
+impl ConvexHull {
+    /// Hull of `points`, or None if they are all (nearly) coplanar.
+    pub fn from_points(points: &[Vec3]) -> Option<Self> {
+        if points.len() < 4 {
+            return None;
+        }
+        let bounds = Aabb::from_points(points.iter().copied());
+        let eps = (bounds.size().length() * 1e-5).max(f32::EPSILON);
+
+        // Initial tetrahedron from extreme points
+        let axis = bounds.longest_axis();
+        // Ties go to the point farthest from the center, so a point in the middle
+        // of a face never seeds the hull in place of a corner
+        let center = bounds.center();
+        let farthest = |score: &dyn Fn(Vec3) -> f32| {
+            (0..points.len())
+                .max_by(|&i, &j| {
+                    let (a, b) = (points[i], points[j]);
+                    score(a).total_cmp(&score(b)).then((a - center).length_squared().total_cmp(&(b - center).length_squared()))
+                })
+                .unwrap_or(0)
+        };
+        let i0 = farthest(&|p| -p[axis]);
+        let i1 = farthest(&|p| p[axis]);
+        let (a, b) = (points[i0], points[i1]);
+        let dir = (b - a).try_normalize()?;
+        let i2 = farthest(&|p| (p - a).cross(dir).length_squared());
+        let base = Plane::from_points(a, b, points[i2])?;
+        let i3 = farthest(&|p| base.signed_distance(p).abs());
+        if base.signed_distance(points[i3]).abs() <= eps {
+            return None;
+        }
+
+        let centroid = (a + b + points[i2] + points[i3]) * 0.25;
+        let mut faces: Vec<([usize; 3], Plane)> = Vec::new();
+        for tri in [[i0, i1, i2], [i0, i1, i3], [i0, i2, i3], [i1, i2, i3]] {
+            let mut face = (tri, face_plane(points, tri));
+            if face.1.signed_distance(centroid) > 0.0 {
+                face.0.swap(1, 2);
+                face.1 = face.1.flipped();
+            }
+            faces.push(face);
+        }
+
+        // Add remaining points farthest-first, which keeps the hull well conditioned
+        let seed = [i0, i1, i2, i3];
+        let mut order: Vec<usize> = (0..points.len()).filter(|i| !seed.contains(i)).collect();
+        order.sort_by(|&i, &j| (points[j] - centroid).length_squared().total_cmp(&(points[i] - centroid).length_squared()));
+
+        for index in order {
+            let p = points[index];
+            let visible: Vec<bool> = faces.iter().map(|(_, plane)| plane.signed_distance(p) > eps).collect();
+            if !visible.contains(&true) {
+                continue;
+            }
+            let edges: HashSet<(usize, usize)> = faces
+                .iter()
+                .zip(&visible)
+                .filter(|(_, v)| **v)
+                .flat_map(|(([a, b, c], _), _)| [(*a, *b), (*b, *c), (*c, *a)])
+                .collect();
+            // Edges of the visible region whose twin faces stay form the horizon
+            let horizon: Vec<(usize, usize)> = edges.iter().filter(|(a, b)| !edges.contains(&(*b, *a))).copied().collect();
+
+            let mut keep = visible.iter().map(|v| !v);
+            faces.retain(|_| keep.next().unwrap_or(true));
+            for (a, b) in horizon {
+                let tri = [a, b, index];
+                faces.push((tri, face_plane(points, tri)));
+            }
+        }
+
+        // Compact to the vertices actually used
+        let mut remap = vec![u32::MAX; points.len()];
+        let mut vertices = Vec::new();
+        let mut out_faces = Vec::with_capacity(faces.len());
+        let mut planes = Vec::with_capacity(faces.len());
+        for (tri, plane) in faces {
+            out_faces.push(tri.map(|i| {
+                if remap[i] == u32::MAX {
+                    remap[i] = vertices.len() as u32;
+                    vertices.push(points[i]);
+                }
+                remap[i]
+            }));
+            if plane.normal != Vec3::zero() {
+                planes.push(plane);
+            }
+        }
+        Some(Self { vertices, faces: out_faces, planes })
+    }
+
+    #[inline]
+    pub fn vertices(&self) -> &[Vec3] {
+        &self.vertices
+    }
+
+    #[inline]
+    pub fn faces(&self) -> &[[u32; 3]] {
+        &self.faces
+    }
+
+    /// Face planes, excluding degenerate slivers.
+    #[inline]
+    pub fn planes(&self) -> &[Plane] {
+        &self.planes
+    }
+
+    pub fn bounds(&self) -> Aabb {
+        Aabb::from_points(self.vertices.iter().copied())
+    }
+
+    pub fn volume(&self) -> f32 {
+        let v = &self.vertices;
+        self.faces.iter().map(|f| v[f[0] as usize].dot(v[f[1] as usize].cross(v[f[2] as usize]))).sum::<f32>() / 6.0
+    }
+
+    /// Largest signed face-plane distance. Exact (negative) inside the hull; outside
+    /// it is a lower bound of the true distance, exact in front of a face.
+    pub fn signed_distance(&self, p: Vec3) -> f32 {
+        self.planes.iter().map(|plane| plane.signed_distance(p)).fold(f32::NEG_INFINITY, f32::max)
+    }
+
+    #[inline]
+    pub fn contains(&self, p: Vec3) -> bool {
+        self.signed_distance(p) <= 0.0
+    }
+
+    pub fn to_mesh(&self) -> TriMesh {
+        TriMesh::new(self.vertices.clone(), self.faces.iter().flatten().copied().collect())
+    }
+}
+
+/// Plane of a face; degenerate slivers get a plane nothing is ever in front of.
+fn face_plane(points: &[Vec3], [a, b, c]: [usize; 3]) -> Plane {
+    Plane::from_points(points[a], points[b], points[c]).unwrap_or(Plane { normal: Vec3::zero(), offset: 0.0 })
+}
+
+#[cfg(test)]
+#[path = "tests/hull_tests.rs"]
+mod tests;
@@ -0,0 +1,215 @@
+//! Indexed triangle meshes and per-triangle queries.
+//!
+//! Triangles are wound counter-clockwise when seen from outside. Inside tests use
+//! the generalized winding number, which tolerates small holes and overlapping
+//! parts in imported meshes.
+
+use super::aabb::Aabb;
+use super::closest::Feature;
+use super::ray::Ray;
+use crate::core::Vec3;
+use std::f32::consts::PI;
+
+/// Indexed triangle mesh.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TriMesh {
+    pub positions: Vec<Vec3>,
+    /// Three indices per triangle.
+    pub indices: Vec<u32>,
+}
+
+impl TriMesh {
+    pub fn new(positions: Vec<Vec3>, indices: Vec<u32>) -> Self {
+        Self { positions, indices }
+    }
+
+    /// Closed box centered on the origin.
+    pub fn cuboid(half_extents: Vec3) -> Self {
+        let h = half_extents;
+        let positions: Vec<Vec3> = (0..8)
+            .map(|i| {
+                let sign = |bit: u32| if i & bit != 0 { 1.0 } else { -1.0 };
+                Vec3::new(h.x * sign(1), h.y * sign(2), h.z * sign(4))
+            })
+            .collect();
+        let quads = [[0, 2, 6, 4], [1, 3, 7, 5], [0, 1, 5, 4], [2, 3, 7, 6], [0, 1, 3, 2], [4, 5, 7, 6]];
+        let mut indices = Vec::with_capacity(36);
+        for [a, b, c, d] in quads {
+            for mut tri in [[a, b, c], [a, c, d]] {
+                let [p0, p1, p2] = tri.map(|i| positions[i as usize]);
+                // Flip triangles whose normal points toward the center
+                if (p1 - p0).cross(p2 - p0).dot(p0 + p1 + p2) < 0.0 {
+                    tri.swap(1, 2);
+                }
+                indices.extend(tri);
+            }
+        }
+        Self { positions, indices }
+    }
+
+    #[inline]
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.triangle_count() == 0
+    }
+
+    /// Corner positions of triangle `i`.
+    #[inline]
+    pub fn triangle(&self, i: usize) -> [Vec3; 3] {
+        let t = &self.indices[i * 3..i * 3 + 3];
+        [self.positions[t[0] as usize], self.positions[t[1] as usize], self.positions[t[2] as usize]]
+    }
+
+    pub fn triangles(&self) -> impl Iterator<Item = [Vec3; 3]> + '_ {
+        (0..self.triangle_count()).map(|i| self.triangle(i))
+    }
+
+    pub fn bounds(&self) -> Aabb {
+        Aabb::from_points(self.positions.iter().copied())
+    }
+
+    /// Enclosed volume; only meaningful for closed meshes.
+    pub fn volume(&self) -> f32 {
+        self.triangles().map(|[a, b, c]| a.dot(b.cross(c))).sum::<f32>() / 6.0
+    }
+
+    /// Generalized winding number: ~1 inside a closed mesh, ~0 outside.
+    pub fn winding_number(&self, p: Vec3) -> f32 {
+        self.triangles().map(|t| solid_angle(p, t)).sum::<f32>() / (4.0 * PI)
+    }
+
+    #[inline]
+    pub fn contains(&self, p: Vec3) -> bool {
+        self.winding_number(p) > 0.5
+    }
+
+    /// Closest point on the surface (brute force over all triangles).
+    pub fn closest_point(&self, p: Vec3) -> Option<Vec3> {
+        self.triangles()
+            .map(|t| closest_point_on_triangle(p, t))
+            .min_by(|a, b| (*a - p).length_squared().total_cmp(&(*b - p).length_squared()))
+    }
+
+    /// Unsigned distance to the surface; infinite for an empty mesh.
+    pub fn distance(&self, p: Vec3) -> f32 {
+        self.closest_point(p).map_or(f32::INFINITY, |q| (q - p).length())
+    }
+}
+
+/// Closest point to `p` on triangle `abc` (Ericson, Real-Time Collision Detection 5.1.5).
+#[inline]
+pub fn closest_point_on_triangle(p: Vec3, triangle: [Vec3; 3]) -> Vec3 {
+    closest_on_triangle(p, triangle).0
+}
+
+/// Closest point on triangle `abc` and the feature it lies on: vertices a, b, c are
+/// 0, 1, 2 and edges ab, bc, ca are 0, 1, 2.
+pub(super) fn closest_on_triangle(p: Vec3, [a, b, c]: [Vec3; 3]) -> (Vec3, Feature) {
+    let ab = b - a;
+    let ac = c - a;
+    // Degenerate triangles (e.g. at the poles of UV spheres) collapse to segments
+    if ab.cross(ac).length_squared() <= f32::EPSILON * f32::EPSILON * ab.length_squared() * ac.length_squared() {
+        return [(a, b), (b, c), (c, a)]
+            .map(|(s, e)| closest_point_on_segment(p, s, e))
+            .into_iter()
+            .zip(0..)
+            .min_by(|(x, _), (y, _)| (*x - p).length_squared().total_cmp(&(*y - p).length_squared()))
+            .map_or((a, Feature::Vertex(0)), |(q, edge)| (q, Feature::Edge(edge)));
+    }
+    let ap = p - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return (a, Feature::Vertex(0));
+    }
+
+    let bp = p - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return (b, Feature::Vertex(1));
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return (a + ab * (d1 / (d1 - d3)), Feature::Edge(0));
+    }
+
+    let cp = p - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return (c, Feature::Vertex(2));
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return (a + ac * (d2 / (d2 - d6)), Feature::Edge(2));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+        return (b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6))), Feature::Edge(1));
+    }
+
+    let denom = 1.0 / (va + vb + vc);
+    (a + ab * (vb * denom) + ac * (vc * denom), Feature::Face(0))
+}
+
+/// Closest point to `p` on the segment `ab`.
+pub fn closest_point_on_segment(p: Vec3, a: Vec3, b: Vec3) -> Vec3 {
+    let ab = b - a;
+    let len2 = ab.length_squared();
+    if len2 <= 0.0 {
+        return a;
+    }
+    a + ab * ((p - a).dot(ab) / len2).clamp(0.0, 1.0)
+}
+
+/// Distance along `ray` to triangle `abc` (either side), up to `max_distance`
+/// (Möller-Trumbore).
+pub fn ray_triangle(ray: &Ray, [a, b, c]: [Vec3; 3], max_distance: f32) -> Option<f32> {
+    let e1 = b - a;
+    let e2 = c - a;
+    let p = ray.direction.cross(e2);
+    let det = e1.dot(p);
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    let inv = 1.0 / det;
+    let s = ray.origin - a;
+    let u = s.dot(p) * inv;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(e1);
+    let v = ray.direction.dot(q) * inv;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = e2.dot(q) * inv;
+    (t >= 0.0 && t <= max_distance).then_some(t)
+}
+
+/// Unit normal of triangle `abc` following its winding, or zero if degenerate.
+#[inline]
+pub fn triangle_normal([a, b, c]: [Vec3; 3]) -> Vec3 {
+    (b - a).cross(c - a).try_normalize().unwrap_or(Vec3::zero())
+}
+
+/// Signed solid angle subtended by triangle `abc` at `p` (Van Oosterom-Strackee).
+fn solid_angle(p: Vec3, [a, b, c]: [Vec3; 3]) -> f32 {
+    let (a, b, c) = (a - p, b - p, c - p);
+    let (la, lb, lc) = (a.length(), b.length(), c.length());
+    let numerator = a.dot(b.cross(c));
+    let denominator = la * lb * lc + a.dot(b) * lc + b.dot(c) * la + c.dot(a) * lb;
+    2.0 * numerator.atan2(denominator)
+}
+
+#[cfg(test)]
+#[path = "tests/mesh_tests.rs"]
+mod tests;
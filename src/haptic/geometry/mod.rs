@@ -0,0 +1,25 @@
+// src/haptic/geometry/mod.rs
+pub mod aabb;
+pub mod closest;
+pub mod frustum;
+pub mod hull;
+pub mod mesh;
+pub mod obb;
+pub mod plane;
+pub mod proxy;
+pub mod ray;
+pub mod sat;
+pub mod sdf;
+pub mod sweep;
+pub use aabb::Aabb;
+pub use closest::{closest_points_on_segments, closest_segment_triangle, ClosestPoint, Feature, SurfacePoint};
+pub use frustum::{Containment, Frustum};
+pub use hull::ConvexHull;
+pub use mesh::{closest_point_on_segment, closest_point_on_triangle, ray_triangle, triangle_normal, TriMesh};
+pub use obb::Obb;
+pub use plane::Plane;
+pub use proxy::{CollisionProxy, ProxyError, ProxyMethod};
+pub use ray::Ray;
+pub use sat::{obb_overlap, obb_triangle_overlap, Penetration};
+pub use sdf::VoxelSdf;
+pub use sweep::{sweep_capsule, sweep_sphere, Capsule, SweepHit};
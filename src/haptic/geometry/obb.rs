@@ -0,0 +1,86 @@
+//! Oriented bounding boxes.
+
+use super::aabb::Aabb;
+use crate::core::{Quat, Vec3};
+
+/// Box with a center, half extents along its local axes, and an orientation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Obb {
+    pub center: Vec3,
+    pub half_extents: Vec3,
+    pub rotation: Quat,
+}
+
+impl Obb {
+    #[inline]
+    pub fn new(center: Vec3, half_extents: Vec3, rotation: Quat) -> Self {
+        Self { center, half_extents, rotation }
+    }
+
+    #[inline]
+    pub fn from_aabb(aabb: &Aabb) -> Self {
+        Self::new(aabb.center(), aabb.half_extents(), Quat::identity())
+    }
+
+    /// Unit local axes in world space.
+    pub fn axes(&self) -> [Vec3; 3] {
+        [Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z()].map(|a| self.rotation.rotate(a))
+    }
+
+    /// World point in box-local coordinates (centered, unrotated).
+    #[inline]
+    pub fn to_local(&self, p: Vec3) -> Vec3 {
+        self.rotation.inverse().rotate(p - self.center)
+    }
+
+    #[inline]
+    pub fn to_world(&self, local: Vec3) -> Vec3 {
+        self.rotation.rotate(local) + self.center
+    }
+
+    /// World point mapped to [0, 1]^3 across the box (values outside mean outside).
+    pub fn normalized(&self, p: Vec3) -> Vec3 {
+        let local = self.to_local(p);
+        let h = self.half_extents;
+        Vec3::new(
+            (local.x / h.x + 1.0) * 0.5,
+            (local.y / h.y + 1.0) * 0.5,
+            (local.z / h.z + 1.0) * 0.5,
+        )
+    }
+
+    #[inline]
+    pub fn contains(&self, p: Vec3) -> bool {
+        let local = self.to_local(p).abs();
+        local.x <= self.half_extents.x && local.y <= self.half_extents.y && local.z <= self.half_extents.z
+    }
+
+    pub fn closest_point(&self, p: Vec3) -> Vec3 {
+        self.to_world(self.to_local(p).clamp(-self.half_extents, self.half_extents))
+    }
+
+    /// Signed distance to the surface, negative inside.
+    pub fn signed_distance(&self, p: Vec3) -> f32 {
+        let q = self.to_local(p).abs() - self.half_extents;
+        let outside = q.max(Vec3::zero()).length();
+        let inside = q.x.max(q.y).max(q.z).min(0.0);
+        outside + inside
+    }
+
+    pub fn corners(&self) -> [Vec3; 8] {
+        let h = self.half_extents;
+        std::array::from_fn(|i| {
+            let sign = |bit: usize| if i & bit != 0 { 1.0 } else { -1.0 };
+            self.to_world(Vec3::new(h.x * sign(1), h.y * sign(2), h.z * sign(4)))
+        })
+    }
+
+    /// World-space axis-aligned bounds.
+    pub fn bounds(&self) -> Aabb {
+        Aabb::from_points(self.corners())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/obb_tests.rs"]
+mod tests;
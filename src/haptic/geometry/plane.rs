@@ -0,0 +1,43 @@
+//! Planes in Hessian normal form.
+
+use crate::core::Vec3;
+
+/// Plane `normal · x = offset` with a unit normal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane {
+    pub normal: Vec3,
+    pub offset: f32,
+}
+
+impl Plane {
+    /// Plane through `point` with the given (unit) normal.
+    #[inline]
+    pub fn from_point_normal(point: Vec3, normal: Vec3) -> Self {
+        Self { normal, offset: normal.dot(point) }
+    }
+
+    /// Plane through three points, facing the side from which they appear
+    /// counter-clockwise. None if the points are collinear.
+    pub fn from_points(a: Vec3, b: Vec3, c: Vec3) -> Option<Self> {
+        let normal = (b - a).cross(c - a).try_normalize()?;
+        Some(Self::from_point_normal(a, normal))
+    }
+
+    /// Distance from the plane, positive on the side the normal points to.
+    #[inline]
+    pub fn signed_distance(&self, p: Vec3) -> f32 {
+        self.normal.dot(p) - self.offset
+    }
+
+    /// Orthogonal projection of `p` onto the plane.
+    #[inline]
+    pub fn project(&self, p: Vec3) -> Vec3 {
+        p - self.normal * self.signed_distance(p)
+    }
+
+    /// The same plane facing the other way.
+    #[inline]
+    pub fn flipped(&self) -> Self {
+        Self { normal: -self.normal, offset: -self.offset }
+    }
+}
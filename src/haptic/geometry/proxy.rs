@@ -0,0 +1,252 @@
+//! Automatic collision proxies for imported meshes.
+//!
+//! Visual meshes are usually too dense, open or self-intersecting to render
+//! haptically. At import time they are replaced by a simpler stand-in: a single
+//! convex hull, a set of convex hulls from an approximate convex decomposition
+//! (voxel-based, in the spirit of V-HACD), or a baked signed distance field.
+
+use super::aabb::Aabb;
+use super::hull::ConvexHull;
+use super::mesh::TriMesh;
+use super::sdf::VoxelSdf;
+use crate::core::Vec3;
+use std::collections::HashSet;
+use std::fmt;
+
+// ============================================================================
+// Settings and Errors
+// ============================================================================
+
+/// How a proxy is generated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProxyMethod {
+    /// One convex hull around all vertices.
+    ConvexHull,
+    /// Voxelize the solid and split it until each part is nearly convex.
+    Decomposition {
+        /// Upper bound on the number of hulls.
+        max_hulls: usize,
+        /// Accepted fraction of a part's hull volume not covered by the solid.
+        concavity: f32,
+        /// Voxels along the longest axis.
+        resolution: usize,
+    },
+    /// Signed distance field with `resolution` cells along the longest axis.
+    Sdf { resolution: usize },
+}
+
+impl Default for ProxyMethod {
+    fn default() -> Self {
+        ProxyMethod::Decomposition { max_hulls: 8, concavity: 0.05, resolution: 24 }
+    }
+}
+
+/// Reasons a proxy could not be generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProxyError {
+    /// The mesh has no triangles.
+    EmptyMesh,
+    /// The mesh is flat or too thin to enclose any volume.
+    Degenerate,
+}
+
+impl fmt::Display for ProxyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProxyError::EmptyMesh => write!(f, "mesh has no triangles"),
+            ProxyError::Degenerate => write!(f, "mesh encloses no volume"),
+        }
+    }
+}
+
+impl std::error::Error for ProxyError {}
+
+// ============================================================================
+// Proxy
+// ============================================================================
+
+/// Simplified touchable geometry stored alongside a visual mesh.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CollisionProxy {
+    /// Union of convex hulls.
+    Hulls(Vec<ConvexHull>),
+    Sdf(VoxelSdf),
+}
+
+impl CollisionProxy {
+    pub fn generate(mesh: &TriMesh, method: ProxyMethod) -> Result<Self, ProxyError> {
+        if mesh.is_empty() {
+            return Err(ProxyError::EmptyMesh);
+        }
+        match method {
+            ProxyMethod::ConvexHull => Ok(CollisionProxy::Hulls(vec![hull_of_mesh(mesh)?])),
+            ProxyMethod::Decomposition { max_hulls, concavity, resolution } => {
+                decompose(mesh, max_hulls.max(1), concavity, resolution.max(2)).map(CollisionProxy::Hulls)
+            }
+            ProxyMethod::Sdf { resolution } => {
+                VoxelSdf::from_mesh(mesh, resolution).map(CollisionProxy::Sdf).ok_or(ProxyError::Degenerate)
+            }
+        }
+    }
+
+    /// Signed distance to the proxy surface (negative inside).
+    pub fn signed_distance(&self, p: Vec3) -> f32 {
+        match self {
+            CollisionProxy::Hulls(hulls) => hulls.iter().map(|h| h.signed_distance(p)).fold(f32::INFINITY, f32::min),
+            CollisionProxy::Sdf(sdf) => sdf.sample(p),
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, p: Vec3) -> bool {
+        self.signed_distance(p) <= 0.0
+    }
+
+    pub fn bounds(&self) -> Aabb {
+        match self {
+            CollisionProxy::Hulls(hulls) => hulls.iter().fold(Aabb::EMPTY, |b, h| b.union(h.bounds())),
+            CollisionProxy::Sdf(sdf) => sdf.bounds(),
+        }
+    }
+}
+
+fn hull_of_mesh(mesh: &TriMesh) -> Result<ConvexHull, ProxyError> {
+    ConvexHull::from_points(&mesh.positions).ok_or(ProxyError::Degenerate)
+}
+
+// ============================================================================
+// Convex Decomposition
+// ============================================================================
+
+type Cell = [usize; 3];
+
+/// Solid voxels of a mesh on a grid anchored at the mesh bounds.
+struct VoxelGrid {
+    origin: Vec3,
+    cell: f32,
+}
+
+impl VoxelGrid {
+    fn corner(&self, c: Cell) -> Vec3 {
+        self.origin + Vec3::new(c[0] as f32, c[1] as f32, c[2] as f32) * self.cell
+    }
+}
+
+/// A connected-or-not group of voxels and the hull around it.
+struct Part {
+    cells: Vec<Cell>,
+    hull: ConvexHull,
+    /// Hull volume not covered by voxels.
+    excess: f32,
+    concavity: f32,
+}
+
+impl Part {
+    fn new(grid: &VoxelGrid, cells: Vec<Cell>) -> Option<Self> {
+        let set: HashSet<Cell> = cells.iter().copied().collect();
+        let neighbours = |c: Cell| {
+            let [x, y, z] = c;
+            [
+                x.checked_sub(1).map(|x| [x, y, z]),
+                Some([x + 1, y, z]),
+                y.checked_sub(1).map(|y| [x, y, z]),
+                Some([x, y + 1, z]),
+                z.checked_sub(1).map(|z| [x, y, z]),
+                Some([x, y, z + 1]),
+            ]
+        };
+        // Only boundary voxels can contribute hull vertices
+        let mut corners: HashSet<Cell> = HashSet::new();
+        for &c in &cells {
+            if neighbours(c).iter().any(|n| n.is_none_or(|n| !set.contains(&n))) {
+                for i in 0..8 {
+                    corners.insert([c[0] + (i & 1), c[1] + ((i >> 1) & 1), c[2] + ((i >> 2) & 1)]);
+                }
+            }
+        }
+        let points: Vec<Vec3> = corners.into_iter().map(|c| grid.corner(c)).collect();
+        let hull = ConvexHull::from_points(&points)?;
+        let hull_volume = hull.volume();
+        let solid_volume = cells.len() as f32 * grid.cell.powi(3);
+        let excess = (hull_volume - solid_volume).max(0.0);
+        let concavity = if hull_volume > 0.0 { excess / hull_volume } else { 0.0 };
+        Some(Self { cells, hull, excess, concavity })
+    }
+
+    /// Best axis-aligned cut, minimizing the summed excess volume of both halves.
+    fn split(&self, grid: &VoxelGrid) -> Option<(Part, Part)> {
+        const CANDIDATES_PER_AXIS: usize = 8;
+        let mut best: Option<(f32, Part, Part)> = None;
+        for axis in 0..3 {
+            let lo = self.cells.iter().map(|c| c[axis]).min()?;
+            let hi = self.cells.iter().map(|c| c[axis]).max()?;
+            if hi == lo {
+                continue;
+            }
+            let span = hi - lo;
+            let step = span.div_ceil(CANDIDATES_PER_AXIS).max(1);
+            for cut in (lo + 1..=hi).step_by(step) {
+                let (a, b): (Vec<Cell>, Vec<Cell>) = self.cells.iter().partition(|c| c[axis] < cut);
+                let (Some(a), Some(b)) = (Part::new(grid, a), Part::new(grid, b)) else {
+                    continue;
+                };
+                let cost = a.excess + b.excess;
+                if best.as_ref().is_none_or(|(c, _, _)| cost < *c) {
+                    best = Some((cost, a, b));
+                }
+            }
+        }
+        best.map(|(_, a, b)| (a, b))
+    }
+}
+
+fn decompose(mesh: &TriMesh, max_hulls: usize, concavity: f32, resolution: usize) -> Result<Vec<ConvexHull>, ProxyError> {
+    let bounds = mesh.bounds();
+    let cell = bounds.size().max_component() / resolution as f32;
+    if cell <= 0.0 {
+        return Err(ProxyError::Degenerate);
+    }
+    let grid = VoxelGrid { origin: bounds.min, cell };
+    let dims = [0, 1, 2].map(|axis| ((bounds.size()[axis] / cell).ceil() as usize).max(1));
+
+    let mut solid = Vec::new();
+    for z in 0..dims[2] {
+        for y in 0..dims[1] {
+            for x in 0..dims[0] {
+                let center = grid.corner([x, y, z]) + Vec3::splat(cell * 0.5);
+                if mesh.contains(center) {
+                    solid.push([x, y, z]);
+                }
+            }
+        }
+    }
+
+    // Too thin to voxelize at this resolution: fall back to one hull
+    let Some(root) = Part::new(&grid, solid) else {
+        return hull_of_mesh(mesh).map(|h| vec![h]);
+    };
+
+    let mut parts = vec![root];
+    while parts.len() < max_hulls {
+        let Some((worst, part)) = parts.iter().enumerate().max_by(|a, b| a.1.concavity.total_cmp(&b.1.concavity)) else {
+            break;
+        };
+        if part.concavity <= concavity {
+            break;
+        }
+        match part.split(&grid) {
+            Some((a, b)) => {
+                parts.swap_remove(worst);
+                parts.push(a);
+                parts.push(b);
+            }
+            // Unsplittable (e.g. a single voxel); stop considering it
+            None => parts[worst].concavity = 0.0,
+        }
+    }
+    Ok(parts.into_iter().map(|p| p.hull).collect())
+}
+
+#[cfg(test)]
+#[path = "tests/proxy_tests.rs"]
+mod tests;
@@ -0,0 +1,30 @@
+//! Rays for picking and line-of-sight queries.
+
+use crate::core::Vec3;
+
+/// Half-line from `origin` along the unit vector `direction`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    pub origin: Vec3,
+    pub direction: Vec3,
+}
+
+impl Ray {
+    /// Ray from `origin` along `direction`, which is normalized.
+    #[inline]
+    pub fn new(origin: Vec3, direction: Vec3) -> Self {
+        Self { origin, direction: direction.normalize() }
+    }
+
+    /// Ray from `from` through `to`.
+    #[inline]
+    pub fn between(from: Vec3, to: Vec3) -> Self {
+        Self::new(from, to - from)
+    }
+
+    /// Point at distance `t` along the ray.
+    #[inline]
+    pub fn at(&self, t: f32) -> Vec3 {
+        self.origin + self.direction * t
+    }
+}
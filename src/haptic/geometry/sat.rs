@@ -0,0 +1,104 @@
+//! Separating Axis Theorem tests for oriented boxes.
+//!
+//! Two convex shapes are disjoint exactly when their projections onto some axis do
+//! not overlap. For boxes and triangles the candidate axes are the face normals of
+//! each shape and the cross products of their edge directions; the axis with the
+//! smallest overlap gives the penetration depth and the minimum translation vector
+//! that separates the shapes.
+
+use super::obb::Obb;
+use crate::core::{Vec3, EPSILON};
+
+/// Overlap found by a SAT test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Penetration {
+    /// Unit direction to move the first shape to separate it from the second.
+    pub normal: Vec3,
+    /// Overlap along `normal`.
+    pub depth: f32,
+}
+
+impl Penetration {
+    /// Minimum translation vector: moving the first shape by this separates the pair.
+    #[inline]
+    pub fn translation(&self) -> Vec3 {
+        self.normal * self.depth
+    }
+}
+
+/// Overlap between two oriented boxes, or None if they are disjoint or only touching.
+pub fn obb_overlap(a: &Obb, b: &Obb) -> Option<Penetration> {
+    let (axes_a, axes_b) = (a.axes(), b.axes());
+    let mut axes = Vec::with_capacity(15);
+    axes.extend(axes_a);
+    axes.extend(axes_b);
+    for u in axes_a {
+        axes.extend(axes_b.map(|v| u.cross(v)));
+    }
+    separate(&axes, |axis| project_obb(a, &axes_a, axis), |axis| project_obb(b, &axes_b, axis))
+}
+
+/// Overlap between a box and a triangle, or None if they are disjoint or only touching.
+pub fn obb_triangle_overlap(obb: &Obb, triangle: [Vec3; 3]) -> Option<Penetration> {
+    let [p0, p1, p2] = triangle;
+    let box_axes = obb.axes();
+    let edges = [p1 - p0, p2 - p1, p0 - p2];
+    let mut axes = Vec::with_capacity(13);
+    axes.extend(box_axes);
+    axes.push(edges[0].cross(edges[1]));
+    for u in box_axes {
+        axes.extend(edges.map(|e| u.cross(e)));
+    }
+    separate(&axes, |axis| project_obb(obb, &box_axes, axis), |axis| project_points(&triangle, axis))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Finds the axis of least overlap, returning None as soon as one separates.
+/// Degenerate axes (parallel edges) are skipped.
+fn separate(
+    axes: &[Vec3],
+    project_a: impl Fn(Vec3) -> (f32, f32),
+    project_b: impl Fn(Vec3) -> (f32, f32),
+) -> Option<Penetration> {
+    let mut best: Option<Penetration> = None;
+    for &axis in axes {
+        let length = axis.length();
+        if length < EPSILON {
+            continue;
+        }
+        let axis = axis / length;
+        let (min_a, max_a) = project_a(axis);
+        let (min_b, max_b) = project_b(axis);
+        // Pushing A toward -axis clears `max_a - min_b`; toward +axis clears `max_b - min_a`.
+        let (depth, normal) =
+            if max_a - min_b < max_b - min_a { (max_a - min_b, -axis) } else { (max_b - min_a, axis) };
+        if depth <= 0.0 {
+            return None;
+        }
+        if best.is_none_or(|b| depth < b.depth) {
+            best = Some(Penetration { normal, depth });
+        }
+    }
+    best
+}
+
+fn project_obb(obb: &Obb, axes: &[Vec3; 3], axis: Vec3) -> (f32, f32) {
+    let center = obb.center.dot(axis);
+    let h = obb.half_extents;
+    let radius = h.x * axes[0].dot(axis).abs() + h.y * axes[1].dot(axis).abs() + h.z * axes[2].dot(axis).abs();
+    (center - radius, center + radius)
+}
+
+fn project_points(points: &[Vec3], axis: Vec3) -> (f32, f32) {
+    points.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), p| {
+        let d = p.dot(axis);
+        (lo.min(d), hi.max(d))
+    })
+}
+
+#[cfg(test)]
+#[path = "tests/sat_tests.rs"]
+mod tests;
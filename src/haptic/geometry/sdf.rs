@@ -0,0 +1,111 @@
+//! Signed distance fields sampled on a regular voxel grid.
+
+use super::aabb::Aabb;
+use super::mesh::TriMesh;
+use crate::core::Vec3;
+
+/// Grid of signed distances (negative inside), sampled with trilinear interpolation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoxelSdf {
+    origin: Vec3,
+    cell: f32,
+    dims: [usize; 3],
+    values: Vec<f32>,
+}
+
+impl VoxelSdf {
+    /// Cells of padding around the mesh bounds, so the field has a usable
+    /// gradient just outside the surface.
+    pub const PADDING: usize = 2;
+
+    /// Bakes the field of a closed mesh with `resolution` cells along its longest
+    /// axis. Returns None for empty or flat meshes.
+    pub fn from_mesh(mesh: &TriMesh, resolution: usize) -> Option<Self> {
+        let bounds = mesh.bounds();
+        if mesh.is_empty() || bounds.is_empty() {
+            return None;
+        }
+        let cell = bounds.size().max_component() / resolution.max(1) as f32;
+        if cell <= 0.0 {
+            return None;
+        }
+        let pad = Self::PADDING as f32 * cell;
+        let origin = bounds.min - Vec3::splat(pad);
+        let size = bounds.size() + Vec3::splat(2.0 * pad);
+        let dims = [0, 1, 2].map(|axis| (size[axis] / cell).ceil() as usize + 1);
+
+        let mut values = Vec::with_capacity(dims[0] * dims[1] * dims[2]);
+        for z in 0..dims[2] {
+            for y in 0..dims[1] {
+                for x in 0..dims[0] {
+                    let p = origin + Vec3::new(x as f32, y as f32, z as f32) * cell;
+                    let distance = mesh.distance(p);
+                    values.push(if mesh.contains(p) { -distance } else { distance });
+                }
+            }
+        }
+        Some(Self { origin, cell, dims, values })
+    }
+
+    #[inline]
+    pub fn dims(&self) -> [usize; 3] {
+        self.dims
+    }
+
+    #[inline]
+    pub fn cell_size(&self) -> f32 {
+        self.cell
+    }
+
+    pub fn bounds(&self) -> Aabb {
+        let extent = Vec3::new(
+            (self.dims[0] - 1) as f32,
+            (self.dims[1] - 1) as f32,
+            (self.dims[2] - 1) as f32,
+        ) * self.cell;
+        Aabb::new(self.origin, self.origin + extent)
+    }
+
+    #[inline]
+    fn value(&self, x: usize, y: usize, z: usize) -> f32 {
+        self.values[x + self.dims[0] * (y + self.dims[1] * z)]
+    }
+
+    /// Signed distance at `p`. Outside the grid, the distance to the grid is added
+    /// to the value at the nearest grid point.
+    pub fn sample(&self, p: Vec3) -> f32 {
+        let bounds = self.bounds();
+        let q = bounds.closest_point(p);
+        let outside = (p - q).length();
+        let local = (q - self.origin) / self.cell;
+
+        let mut base = [0usize; 3];
+        let mut frac = [0.0f32; 3];
+        for axis in 0..3 {
+            let max = self.dims[axis].saturating_sub(2);
+            base[axis] = (local[axis].floor().max(0.0) as usize).min(max);
+            frac[axis] = (local[axis] - base[axis] as f32).clamp(0.0, 1.0);
+        }
+        let [x, y, z] = base;
+        let [fx, fy, fz] = frac;
+        let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+        let c00 = lerp(self.value(x, y, z), self.value(x + 1, y, z), fx);
+        let c10 = lerp(self.value(x, y + 1, z), self.value(x + 1, y + 1, z), fx);
+        let c01 = lerp(self.value(x, y, z + 1), self.value(x + 1, y, z + 1), fx);
+        let c11 = lerp(self.value(x, y + 1, z + 1), self.value(x + 1, y + 1, z + 1), fx);
+        lerp(lerp(c00, c10, fy), lerp(c01, c11, fy), fz) + outside
+    }
+
+    /// Gradient of the field by central differences (points away from the surface).
+    pub fn gradient(&self, p: Vec3) -> Vec3 {
+        let h = self.cell * 0.5;
+        let d = |axis: Vec3| (self.sample(p + axis * h) - self.sample(p - axis * h)) / (2.0 * h);
+        Vec3::new(d(Vec3::unit_x()), d(Vec3::unit_y()), d(Vec3::unit_z()))
+    }
+
+    /// Unit surface normal at `p`, or None where the gradient vanishes.
+    #[inline]
+    pub fn normal(&self, p: Vec3) -> Option<Vec3> {
+        self.gradient(p).try_normalize()
+    }
+}
@@ -0,0 +1,147 @@
+//! Swept-sphere and swept-capsule casts against convex shapes.
+//!
+//! A haptic proxy can move several millimeters between servo ticks, enough to
+//! step through a thin wall when only its end positions are tested. These casts
+//! find the first time of impact along the motion instead. They use conservative
+//! advancement: the distance between the moving shape and a convex target can
+//! shrink no faster than the shape moves, so advancing by the current gap never
+//! skips a contact. Targets are given as a closest-point function, which covers
+//! boxes, triangles and hulls alike.
+
+use super::ray::Ray;
+use crate::core::Vec3;
+
+/// Gap at which a sweep reports contact.
+pub const SWEEP_TOLERANCE: f32 = 1e-5;
+/// Advancement steps before a sweep gives up and reports a miss.
+const MAX_SWEEP_STEPS: usize = 128;
+/// Golden-section steps for the closest point along a capsule's segment.
+const SEGMENT_SEARCH_STEPS: usize = 32;
+
+// ============================================================================
+// Shapes and Hits
+// ============================================================================
+
+/// Segment from `a` to `b` inflated by `radius`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capsule {
+    pub a: Vec3,
+    pub b: Vec3,
+    pub radius: f32,
+}
+
+impl Capsule {
+    #[inline]
+    pub fn new(a: Vec3, b: Vec3, radius: f32) -> Self {
+        Self { a, b, radius }
+    }
+
+    #[inline]
+    pub fn center(&self) -> Vec3 {
+        (self.a + self.b) * 0.5
+    }
+
+    /// Radius of the smallest sphere around the center that encloses the capsule.
+    #[inline]
+    pub fn bounding_radius(&self) -> f32 {
+        (self.b - self.a).length() * 0.5 + self.radius
+    }
+
+    #[inline]
+    pub fn translated(&self, offset: Vec3) -> Self {
+        Self::new(self.a + offset, self.b + offset, self.radius)
+    }
+
+    /// Closest points on the capsule's segment and on a convex target, found by
+    /// golden-section search over the segment (distance to a convex set is convex).
+    pub fn closest_points(&self, closest: impl Fn(Vec3) -> Vec3) -> (Vec3, Vec3) {
+        const INV_PHI: f32 = 0.618_034;
+        let at = |s: f32| self.a + (self.b - self.a) * s;
+        let gap = |s: f32| {
+            let p = at(s);
+            (closest(p) - p).length_squared()
+        };
+        let (mut lo, mut hi) = (0.0f32, 1.0f32);
+        let mut x1 = hi - (hi - lo) * INV_PHI;
+        let mut x2 = lo + (hi - lo) * INV_PHI;
+        let (mut f1, mut f2) = (gap(x1), gap(x2));
+        for _ in 0..SEGMENT_SEARCH_STEPS {
+            if f1 <= f2 {
+                hi = x2;
+                (x2, f2) = (x1, f1);
+                x1 = hi - (hi - lo) * INV_PHI;
+                f1 = gap(x1);
+            } else {
+                lo = x1;
+                (x1, f1) = (x2, f2);
+                x2 = lo + (hi - lo) * INV_PHI;
+                f2 = gap(x2);
+            }
+        }
+        // The search never evaluates the endpoints exactly
+        let s = [0.0, (lo + hi) * 0.5, 1.0].into_iter().min_by(|&s, &t| gap(s).total_cmp(&gap(t))).unwrap_or(0.0);
+        let p = at(s);
+        (p, closest(p))
+    }
+}
+
+/// First contact of a swept shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepHit {
+    /// Distance travelled along the sweep direction before contact.
+    pub distance: f32,
+    /// Contact point on the target.
+    pub point: Vec3,
+    /// Unit normal at the contact, pointing from the target toward the shape.
+    pub normal: Vec3,
+}
+
+// ============================================================================
+// Casts
+// ============================================================================
+
+/// Sweeps a sphere of `radius` from `ray.origin` along `ray.direction` against a
+/// convex target given by its closest-point function. A sphere that starts in
+/// contact hits at distance 0.
+pub fn sweep_sphere(ray: &Ray, radius: f32, max_distance: f32, closest: impl Fn(Vec3) -> Vec3) -> Option<SweepHit> {
+    advance(ray.direction, max_distance, |t| {
+        let center = ray.at(t);
+        let q = closest(center);
+        ((center - q).length() - radius, center, q)
+    })
+}
+
+/// Sweeps a capsule along the unit vector `direction` against a convex target.
+pub fn sweep_capsule(
+    capsule: &Capsule,
+    direction: Vec3,
+    max_distance: f32,
+    closest: impl Fn(Vec3) -> Vec3,
+) -> Option<SweepHit> {
+    advance(direction, max_distance, |t| {
+        let (p, q) = capsule.translated(direction * t).closest_points(&closest);
+        ((p - q).length() - capsule.radius, p, q)
+    })
+}
+
+/// Conservative advancement. `gap(t)` returns the separation at distance `t` and
+/// the closest points on the shape's core and on the target.
+fn advance(direction: Vec3, max_distance: f32, gap: impl Fn(f32) -> (f32, Vec3, Vec3)) -> Option<SweepHit> {
+    let mut t = 0.0;
+    for _ in 0..MAX_SWEEP_STEPS {
+        let (d, p, q) = gap(t);
+        if d <= SWEEP_TOLERANCE {
+            let normal = (p - q).try_normalize().unwrap_or(-direction);
+            return Some(SweepHit { distance: t, point: q, normal });
+        }
+        t += d;
+        if t > max_distance {
+            return None;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+#[path = "tests/sweep_tests.rs"]
+mod tests;
@@ -0,0 +1,42 @@
+use super::*;
+use crate::geometry::Ray;
+
+#[test]
+fn test_from_points_and_queries() {
+    assert!(Aabb::EMPTY.is_empty());
+    assert_eq!(Aabb::EMPTY.volume(), 0.0);
+
+    let b = Aabb::from_points([Vec3::new(1.0, 0.0, 2.0), Vec3::new(-1.0, 3.0, 0.0)]);
+    assert_eq!(b.min, Vec3::new(-1.0, 0.0, 0.0));
+    assert_eq!(b.max, Vec3::new(1.0, 3.0, 2.0));
+    assert_eq!(b.center(), Vec3::new(0.0, 1.5, 1.0));
+    assert_eq!(b.longest_axis(), 1);
+    assert_eq!(b.volume(), 12.0);
+    assert_eq!(b.surface_area(), 2.0 * (6.0 + 6.0 + 4.0));
+    assert!(b.contains(Vec3::new(0.0, 3.0, 1.0)));
+    assert!(!b.contains(Vec3::new(0.0, 3.1, 1.0)));
+}
+
+#[test]
+fn test_overlap_and_distance() {
+    let a = Aabb::from_center_half_extents(Vec3::zero(), Vec3::one());
+    let b = Aabb::new(Vec3::new(3.0, 0.0, 0.0), Vec3::new(0.5, 0.5, 0.5));
+    assert!(a.intersects(&b));
+    assert!(!a.intersects(&Aabb::new(Vec3::splat(2.0), Vec3::splat(3.0))));
+    assert!(a.union(b).contains(Vec3::new(3.0, 0.0, 0.0)));
+    assert_eq!(a.closest_point(Vec3::new(3.0, 0.0, 0.0)), Vec3::new(1.0, 0.0, 0.0));
+    assert_eq!(a.distance_squared(Vec3::new(3.0, 3.0, 0.0)), 8.0);
+    assert_eq!(a.expand(1.0).size(), Vec3::splat(4.0));
+}
+
+#[test]
+fn test_ray_intersection() {
+    let b = Aabb::from_center_half_extents(Vec3::zero(), Vec3::one());
+    let ray = Ray::new(Vec3::new(-5.0, 0.5, 0.0), Vec3::unit_x());
+    assert_eq!(b.ray_interval(&ray), Some((4.0, 6.0)));
+    assert_eq!(b.ray_distance(&ray, 10.0), Some(4.0));
+    assert_eq!(b.ray_distance(&ray, 3.0), None);
+    assert_eq!(b.ray_distance(&Ray::new(Vec3::zero(), Vec3::unit_y()), 10.0), Some(0.0));
+    assert!(b.ray_interval(&Ray::new(Vec3::new(-5.0, 2.0, 0.0), Vec3::unit_x())).is_none());
+    assert!(b.ray_interval(&Ray::new(Vec3::new(5.0, 0.0, 0.0), Vec3::unit_x())).is_none());
+}
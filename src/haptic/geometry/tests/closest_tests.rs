@@ -0,0 +1,163 @@
+use super::*;
+use crate::core::{Deg, Quat};
+use crate::geometry::TriMesh;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+fn near(a: Vec3, b: Vec3) -> bool {
+    (a - b).length() < TEST_EPSILON
+}
+
+fn unit_box() -> Aabb {
+    Aabb::new(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0))
+}
+
+#[test]
+fn test_aabb_features() {
+    let b = unit_box();
+    let face = ClosestPoint::closest_point(&b, Vec3::new(0.2, 3.0, 0.1));
+    assert!(near(face.point, Vec3::new(0.2, 1.0, 0.1)));
+    assert!(near(face.normal, Vec3::unit_y()));
+    assert_eq!(face.feature, Feature::Face(3));
+    assert!((face.signed_distance(Vec3::new(0.2, 3.0, 0.1)) - 2.0).abs() < TEST_EPSILON);
+
+    // Edge along Z on the +X, -Y side
+    let edge = ClosestPoint::closest_point(&b, Vec3::new(2.0, -2.0, 0.5));
+    assert!(near(edge.point, Vec3::new(1.0, -1.0, 0.5)));
+    assert!(near(edge.normal, Vec3::new(1.0, -1.0, 0.0).normalize()));
+    assert_eq!(edge.feature, Feature::Edge(8 + 1));
+
+    let vertex = ClosestPoint::closest_point(&b, Vec3::new(-3.0, 2.0, 4.0));
+    assert!(near(vertex.point, Vec3::new(-1.0, 1.0, 1.0)));
+    assert_eq!(vertex.feature, Feature::Vertex(2 + 4));
+}
+
+#[test]
+fn test_aabb_inside_projects_to_nearest_face() {
+    let p = Vec3::new(0.1, 0.2, -0.7);
+    let inside = ClosestPoint::closest_point(&unit_box(), p);
+    assert!(near(inside.point, Vec3::new(0.1, 0.2, -1.0)));
+    assert!(near(inside.normal, -Vec3::unit_z()));
+    assert_eq!(inside.feature, Feature::Face(4));
+    assert!((inside.signed_distance(p) + 0.3).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_obb_matches_rotated_box() {
+    let quarter_turn = Quat::from_axis_angle(Vec3::unit_z(), Deg(90.0));
+    let obb = Obb::new(Vec3::new(1.0, 0.0, 0.0), Vec3::new(2.0, 1.0, 0.5), quarter_turn);
+    // Local +X is world +Y, so the point above the box's local max-X face
+    let hit = ClosestPoint::closest_point(&obb, Vec3::new(1.0, 3.0, 0.0));
+    assert!(near(hit.point, Vec3::new(1.0, 2.0, 0.0)));
+    assert!(near(hit.normal, Vec3::unit_y()));
+    assert_eq!(hit.feature, Feature::Face(1));
+}
+
+#[test]
+fn test_triangle_regions() {
+    let tri = [Vec3::zero(), Vec3::unit_x(), Vec3::unit_y()];
+    let face = tri.closest_point(Vec3::new(0.2, 0.2, -1.0));
+    assert_eq!(face.feature, Feature::Face(0));
+    assert!(near(face.normal, Vec3::unit_z()));
+    assert!((face.signed_distance(Vec3::new(0.2, 0.2, -1.0)) + 1.0).abs() < TEST_EPSILON);
+
+    let edge = tri.closest_point(Vec3::new(1.0, 1.0, 0.0));
+    assert_eq!(edge.feature, Feature::Edge(1));
+    assert!(near(edge.point, Vec3::new(0.5, 0.5, 0.0)));
+    assert!(near(edge.normal, Vec3::new(1.0, 1.0, 0.0).normalize()));
+
+    assert_eq!(tri.closest_point(Vec3::new(-1.0, -1.0, 0.5)).feature, Feature::Vertex(0));
+    assert_eq!(tri.closest_point(Vec3::new(0.5, -1.0, 0.0)).feature, Feature::Edge(0));
+    assert_eq!(tri.closest_point(Vec3::new(-1.0, 0.5, 0.0)).feature, Feature::Edge(2));
+}
+
+#[test]
+fn test_plane_and_capsule() {
+    let plane = Plane::from_point_normal(Vec3::new(0.0, 0.0, 1.0), Vec3::unit_z());
+    let hit = plane.closest_point(Vec3::new(3.0, 4.0, 0.0));
+    assert!(near(hit.point, Vec3::new(3.0, 4.0, 1.0)));
+    assert!((hit.signed_distance(Vec3::new(3.0, 4.0, 0.0)) + 1.0).abs() < TEST_EPSILON);
+
+    let capsule = Capsule::new(Vec3::zero(), Vec3::new(0.0, 2.0, 0.0), 0.5);
+    let side = capsule.closest_point(Vec3::new(2.0, 1.0, 0.0));
+    assert!(near(side.point, Vec3::new(0.5, 1.0, 0.0)));
+    assert_eq!(side.feature, Feature::Face(0));
+    let cap = capsule.closest_point(Vec3::new(0.0, 4.0, 0.0));
+    assert!(near(cap.point, Vec3::new(0.0, 2.5, 0.0)));
+    assert_eq!(cap.feature, Feature::Vertex(1));
+    // On the axis: still a valid surface point at `radius`
+    let axis = capsule.closest_point(Vec3::new(0.0, 1.0, 0.0));
+    assert!((axis.point - Vec3::new(0.0, 1.0, 0.0)).length() - 0.5 < TEST_EPSILON);
+    assert!(axis.signed_distance(Vec3::new(0.0, 1.0, 0.0)) < 0.0);
+}
+
+#[test]
+fn test_hull_inside_and_outside() {
+    let hull = ConvexHull::from_points(&TriMesh::cuboid(Vec3::new(1.0, 1.0, 1.0)).positions).unwrap();
+    let outside = hull.closest_point(Vec3::new(0.3, 0.2, 5.0));
+    assert!(near(outside.point, Vec3::new(0.3, 0.2, 1.0)));
+    assert!(near(outside.normal, Vec3::unit_z()));
+    assert!(matches!(outside.feature, Feature::Face(_) | Feature::Edge(_)));
+
+    let inside = hull.closest_point(Vec3::new(0.9, 0.1, 0.0));
+    assert!(near(inside.point, Vec3::new(1.0, 0.1, 0.0)));
+    assert!(near(inside.normal, Vec3::unit_x()));
+
+    let corner = hull.closest_point(Vec3::new(2.0, 2.0, 2.0));
+    let Feature::Vertex(v) = corner.feature else { panic!("{:?}", corner.feature) };
+    assert!(near(hull.vertices()[v as usize], Vec3::new(1.0, 1.0, 1.0)));
+}
+
+#[test]
+fn test_generic_use() {
+    fn depth<S: ClosestPoint>(shape: &S, p: Vec3) -> f32 {
+        -shape.closest_point(p).signed_distance(p).min(0.0)
+    }
+    let p = Vec3::new(0.0, 0.0, 0.9);
+    assert!((depth(&unit_box(), p) - 0.1).abs() < TEST_EPSILON);
+    assert!((depth(&Capsule::new(Vec3::zero(), Vec3::zero(), 1.0), p) - 0.1).abs() < TEST_EPSILON);
+    let floor = Plane::from_point_normal(Vec3::zero(), Vec3::unit_z());
+    let shapes: Vec<Box<dyn ClosestPoint>> = vec![Box::new(unit_box()), Box::new(floor)];
+    assert!(shapes.iter().all(|s| s.closest_point(Vec3::new(0.0, 0.0, 2.0)).normal.z > 0.99));
+}
+
+#[test]
+fn test_segments() {
+    let (a, b) =
+        closest_points_on_segments(Vec3::zero(), Vec3::unit_x() * 2.0, Vec3::new(1.0, 1.0, -1.0), Vec3::new(1.0, 1.0, 1.0));
+    assert!(near(a, Vec3::new(1.0, 0.0, 0.0)));
+    assert!(near(b, Vec3::new(1.0, 1.0, 0.0)));
+    // Parallel, overlapping in X
+    let (a, b) =
+        closest_points_on_segments(Vec3::zero(), Vec3::unit_x(), Vec3::new(0.5, 1.0, 0.0), Vec3::new(2.0, 1.0, 0.0));
+    assert!(((a - b).length() - 1.0).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_segment_triangle() {
+    let tri = [Vec3::new(-1.0, -1.0, 0.0), Vec3::new(1.0, -1.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+    // Crossing
+    let (s, hit) = closest_segment_triangle(Vec3::new(0.0, 0.0, -1.0), Vec3::new(0.0, 0.0, 1.0), tri);
+    assert!(near(s, Vec3::zero()) && near(hit.point, Vec3::zero()));
+    // Hovering over the face: the lower endpoint is closest
+    let (s, hit) = closest_segment_triangle(Vec3::new(0.0, 0.0, 0.5), Vec3::new(0.2, 0.0, 2.0), tri);
+    assert!(near(s, Vec3::new(0.0, 0.0, 0.5)));
+    assert_eq!(hit.feature, Feature::Face(0));
+    // Passing beside an edge: interior point of the segment against the edge
+    let (s, hit) = closest_segment_triangle(Vec3::new(0.0, -2.0, -1.0), Vec3::new(0.0, -2.0, 1.0), tri);
+    assert!(near(s, Vec3::new(0.0, -2.0, 0.0)));
+    assert!(near(hit.point, Vec3::new(0.0, -1.0, 0.0)));
+    assert_eq!(hit.feature, Feature::Edge(0));
+}
+
+#[test]
+fn test_proxy_dispatch() {
+    let mesh = TriMesh::cuboid(Vec3::new(1.0, 1.0, 1.0));
+    let proxy = CollisionProxy::generate(&mesh, crate::geometry::ProxyMethod::ConvexHull).unwrap();
+    let hit = proxy.closest_point(Vec3::new(0.0, 3.0, 0.0));
+    assert!(near(hit.point, Vec3::new(0.0, 1.0, 0.0)));
+    let sdf = CollisionProxy::generate(&mesh, crate::geometry::ProxyMethod::Sdf { resolution: 16 }).unwrap();
+    let hit = sdf.closest_point(Vec3::new(0.0, 1.5, 0.0));
+    assert!((hit.point - Vec3::new(0.0, 1.0, 0.0)).length() < 0.1);
+    assert!(hit.normal.y > 0.9);
+}
@@ -0,0 +1,45 @@
+use super::*;
+use crate::core::Deg;
+
+/// Camera at (0, 0, 5) looking at the origin with a 90° field of view and depth 1..10.
+fn camera(depth: DepthRange) -> Frustum {
+    let projection = Mat4::perspective_in(Deg(90.0), 1.0, 1.0, 10.0, depth);
+    let view = Mat4::look_at(Vec3::new(0.0, 0.0, 5.0), Vec3::zero(), Vec3::unit_y());
+    Frustum::from_matrix(&(projection * view), depth)
+}
+
+fn cube(center: Vec3, half: f32) -> Aabb {
+    Aabb::from_center_half_extents(center, Vec3::splat(half))
+}
+
+#[test]
+fn test_planes_match_camera() {
+    for depth in [DepthRange::ZeroToOne, DepthRange::NegOneToOne] {
+        let frustum = camera(depth);
+        for plane in &frustum.planes {
+            assert!((plane.normal.length() - 1.0).abs() < 1e-5);
+        }
+        assert!(frustum.contains_point(Vec3::zero()));
+        assert!(frustum.contains_point(Vec3::new(0.0, 0.0, 3.9)));
+        assert!(!frustum.contains_point(Vec3::new(0.0, 0.0, 4.1)));
+        assert!(frustum.contains_point(Vec3::new(0.0, 0.0, -4.9)));
+        assert!(!frustum.contains_point(Vec3::new(0.0, 0.0, -5.1)));
+        // Half-width at the origin is 5 for a 90° field of view
+        assert!(frustum.contains_point(Vec3::new(4.9, 0.0, 0.0)));
+        assert!(!frustum.contains_point(Vec3::new(0.0, -5.1, 0.0)));
+        assert!(frustum.intersects_sphere(Vec3::new(5.5, 0.0, 0.0), 1.0));
+        assert!(!frustum.intersects_sphere(Vec3::new(7.0, 0.0, 0.0), 1.0));
+    }
+}
+
+#[test]
+fn test_classify_boxes() {
+    let frustum = camera(DepthRange::default());
+    assert_eq!(frustum.classify(&cube(Vec3::zero(), 1.0)), Containment::Inside);
+    assert_eq!(frustum.classify(&cube(Vec3::new(5.0, 0.0, 0.0), 1.0)), Containment::Intersects);
+    assert_eq!(frustum.classify(&cube(Vec3::new(0.0, 0.0, 4.0), 0.5)), Containment::Intersects);
+    assert_eq!(frustum.classify(&cube(Vec3::new(8.0, 0.0, 0.0), 1.0)), Containment::Outside);
+    assert_eq!(frustum.classify(&cube(Vec3::new(0.0, 0.0, 7.0), 1.0)), Containment::Outside);
+    assert_eq!(frustum.classify(&Aabb::EMPTY), Containment::Outside);
+    assert!(frustum.intersects(&cube(Vec3::new(0.0, 0.0, -20.0), 16.0)));
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+#[test]
+fn test_hull_of_cube_with_interior_points() {
+    let mut points = TriMesh::cuboid(Vec3::one()).positions;
+    points.extend([Vec3::zero(), Vec3::new(0.5, -0.2, 0.1), Vec3::new(1.0, 0.0, 0.0)]);
+    let hull = ConvexHull::from_points(&points).unwrap();
+    assert_eq!(hull.vertices().len(), 8);
+    assert!((hull.volume() - 8.0).abs() < TEST_EPSILON);
+    assert!((hull.signed_distance(Vec3::zero()) + 1.0).abs() < TEST_EPSILON);
+    assert!((hull.signed_distance(Vec3::new(3.0, 0.0, 0.0)) - 2.0).abs() < TEST_EPSILON);
+    assert!(hull.contains(Vec3::new(0.9, -0.9, 0.9)));
+    assert!((hull.to_mesh().volume() - 8.0).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_hull_of_sphere_samples() {
+    let points: Vec<Vec3> = (0..200)
+        .map(|i| {
+            // Fibonacci sphere
+            let y = 1.0 - 2.0 * (i as f32 + 0.5) / 200.0;
+            let r = (1.0 - y * y).sqrt();
+            let phi = i as f32 * 2.399_963;
+            Vec3::new(r * phi.cos(), y, r * phi.sin())
+        })
+        .collect();
+    let hull = ConvexHull::from_points(&points).unwrap();
+    assert_eq!(hull.vertices().len(), 200);
+    let sphere = 4.0 / 3.0 * std::f32::consts::PI;
+    assert!(hull.volume() < sphere && hull.volume() > sphere * 0.95);
+    for p in &points {
+        assert!(hull.signed_distance(*p).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn test_degenerate_inputs() {
+    assert!(ConvexHull::from_points(&[Vec3::zero(); 3]).is_none());
+    let flat = [Vec3::zero(), Vec3::unit_x(), Vec3::unit_y(), Vec3::new(1.0, 1.0, 0.0)];
+    assert!(ConvexHull::from_points(&flat).is_none());
+}
@@ -0,0 +1,52 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+#[test]
+fn test_cuboid_is_closed_and_outward() {
+    let mesh = TriMesh::cuboid(Vec3::new(1.0, 0.5, 0.25));
+    assert_eq!(mesh.triangle_count(), 12);
+    assert!((mesh.volume() - 1.0).abs() < TEST_EPSILON);
+    assert!((mesh.winding_number(Vec3::zero()) - 1.0).abs() < TEST_EPSILON);
+    assert!(mesh.winding_number(Vec3::new(3.0, 0.0, 0.0)).abs() < TEST_EPSILON);
+    assert!(mesh.contains(Vec3::new(0.9, 0.4, 0.2)));
+    assert!(!mesh.contains(Vec3::new(0.0, 0.6, 0.0)));
+    assert!((mesh.distance(Vec3::new(0.0, 0.0, 1.25)) - 1.0).abs() < TEST_EPSILON);
+    assert!((mesh.distance(Vec3::zero()) - 0.25).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_closest_point_on_triangle_regions() {
+    let tri = [Vec3::zero(), Vec3::unit_x(), Vec3::unit_y()];
+    let inside = closest_point_on_triangle(Vec3::new(0.2, 0.2, 1.0), tri);
+    assert!((inside - Vec3::new(0.2, 0.2, 0.0)).length() < TEST_EPSILON);
+    assert_eq!(closest_point_on_triangle(Vec3::new(-1.0, -1.0, 0.0), tri), Vec3::zero());
+    assert_eq!(closest_point_on_triangle(Vec3::new(0.5, -1.0, 0.0), tri), Vec3::new(0.5, 0.0, 0.0));
+    let hyp = closest_point_on_triangle(Vec3::new(1.0, 1.0, 0.0), tri);
+    assert!((hyp - Vec3::new(0.5, 0.5, 0.0)).length() < TEST_EPSILON);
+    assert_eq!(closest_point_on_triangle(Vec3::new(2.0, 0.0, 0.0), tri), Vec3::unit_x());
+}
+
+#[test]
+fn test_ray_triangle() {
+    use crate::geometry::Ray;
+    let tri = [Vec3::zero(), Vec3::unit_x(), Vec3::unit_y()];
+    let down = Ray::new(Vec3::new(0.25, 0.25, 2.0), -Vec3::unit_z());
+    assert!((ray_triangle(&down, tri, 10.0).unwrap() - 2.0).abs() < TEST_EPSILON);
+    assert!(ray_triangle(&down, tri, 1.0).is_none());
+    // Two-sided, but never behind the origin
+    let up = Ray::new(Vec3::new(0.25, 0.25, -1.0), Vec3::unit_z());
+    assert!((ray_triangle(&up, tri, 10.0).unwrap() - 1.0).abs() < TEST_EPSILON);
+    assert!(ray_triangle(&Ray::new(Vec3::new(0.25, 0.25, 1.0), Vec3::unit_z()), tri, 10.0).is_none());
+    assert!(ray_triangle(&Ray::new(Vec3::new(0.8, 0.8, 1.0), -Vec3::unit_z()), tri, 10.0).is_none());
+    assert_eq!(triangle_normal(tri), Vec3::unit_z());
+}
+
+#[test]
+fn test_degenerate_triangle_and_segment() {
+    let p = Vec3::new(0.5, 1.0, 0.0);
+    assert_eq!(closest_point_on_segment(p, Vec3::zero(), Vec3::unit_x()), Vec3::new(0.5, 0.0, 0.0));
+    assert_eq!(closest_point_on_segment(Vec3::new(-2.0, 0.0, 0.0), Vec3::zero(), Vec3::unit_x()), Vec3::zero());
+    let collapsed = [Vec3::zero(), Vec3::zero(), Vec3::unit_x()];
+    assert_eq!(closest_point_on_triangle(p, collapsed), Vec3::new(0.5, 0.0, 0.0));
+}
@@ -0,0 +1,31 @@
+use super::*;
+use crate::core::Deg;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+fn rotated() -> Obb {
+    Obb::new(Vec3::new(1.0, 0.0, 0.0), Vec3::new(2.0, 1.0, 0.5), Quat::from_axis_angle(Vec3::unit_z(), Deg(90.0)))
+}
+
+#[test]
+fn test_local_frame() {
+    let obb = rotated();
+    // Local +X points along world +Y after the rotation
+    assert!((obb.to_world(Vec3::new(2.0, 0.0, 0.0)) - Vec3::new(1.0, 2.0, 0.0)).length() < TEST_EPSILON);
+    assert!((obb.axes()[0] - Vec3::unit_y()).length() < TEST_EPSILON);
+    assert!((obb.normalized(Vec3::new(1.0, 2.0, 0.0)) - Vec3::new(1.0, 0.5, 0.5)).length() < TEST_EPSILON);
+    assert!(obb.contains(Vec3::new(1.5, 1.9, 0.0)));
+    assert!(!obb.contains(Vec3::new(2.5, 0.0, 0.0)));
+}
+
+#[test]
+fn test_distance_and_bounds() {
+    let obb = rotated();
+    assert!((obb.signed_distance(Vec3::new(1.0, 0.0, 0.0)) + 0.5).abs() < TEST_EPSILON);
+    assert!((obb.signed_distance(Vec3::new(3.0, 0.0, 0.0)) - 1.0).abs() < TEST_EPSILON);
+    assert!((obb.closest_point(Vec3::new(3.0, 0.0, 0.0)) - Vec3::new(2.0, 0.0, 0.0)).length() < TEST_EPSILON);
+    let bounds = obb.bounds();
+    assert!((bounds.min - Vec3::new(0.0, -2.0, -0.5)).length() < TEST_EPSILON);
+    assert!((bounds.max - Vec3::new(2.0, 2.0, 0.5)).length() < TEST_EPSILON);
+    assert_eq!(Obb::from_aabb(&bounds).center, bounds.center());
+}
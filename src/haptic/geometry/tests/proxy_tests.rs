@@ -0,0 +1,57 @@
+use super::*;
+
+/// L-shaped solid: a 2x1x1 bar with a 1x1x1 block on top of its left half.
+fn l_shape() -> TriMesh {
+    let mut mesh = TriMesh::cuboid(Vec3::new(1.0, 0.5, 0.5));
+    let top = TriMesh::cuboid(Vec3::splat(0.5));
+    let offset = mesh.positions.len() as u32;
+    mesh.positions.extend(top.positions.iter().map(|p| *p + Vec3::new(-0.5, 1.0, 0.0)));
+    mesh.indices.extend(top.indices.iter().map(|i| i + offset));
+    mesh
+}
+
+#[test]
+fn test_convex_hull_proxy_fills_concavity() {
+    let proxy = CollisionProxy::generate(&l_shape(), ProxyMethod::ConvexHull).unwrap();
+    assert!(proxy.contains(Vec3::new(0.6, 0.9, 0.0)));
+    assert!(matches!(&proxy, CollisionProxy::Hulls(h) if h.len() == 1));
+}
+
+#[test]
+fn test_decomposition_follows_concavity() {
+    let method = ProxyMethod::Decomposition { max_hulls: 4, concavity: 0.02, resolution: 8 };
+    let proxy = CollisionProxy::generate(&l_shape(), method).unwrap();
+    let CollisionProxy::Hulls(hulls) = &proxy else { panic!("expected hulls") };
+    assert!(hulls.len() >= 2 && hulls.len() <= 4);
+    assert!(proxy.contains(Vec3::new(0.5, 0.0, 0.0)));
+    assert!(proxy.contains(Vec3::new(-0.5, 1.0, 0.0)));
+    assert!(!proxy.contains(Vec3::new(0.6, 0.9, 0.0)));
+    assert!((proxy.signed_distance(Vec3::new(0.5, -1.0, 0.0)) - 0.5).abs() < 1e-4);
+
+    let bounds = proxy.bounds();
+    assert!((bounds.min - Vec3::new(-1.0, -0.5, -0.5)).length() < 1e-4);
+    assert!((bounds.max - Vec3::new(1.0, 1.5, 0.5)).length() < 1e-4);
+}
+
+#[test]
+fn test_sdf_proxy() {
+    let mesh = TriMesh::cuboid(Vec3::splat(0.5));
+    let proxy = CollisionProxy::generate(&mesh, ProxyMethod::Sdf { resolution: 8 }).unwrap();
+    let CollisionProxy::Sdf(sdf) = &proxy else { panic!("expected sdf") };
+    assert_eq!(sdf.dims(), [13, 13, 13]);
+    assert!((proxy.signed_distance(Vec3::zero()) + 0.5).abs() < 1e-3);
+    assert!((proxy.signed_distance(Vec3::new(0.7, 0.0, 0.0)) - 0.2).abs() < 1e-3);
+    // Beyond the padded grid the distance keeps growing
+    assert!((proxy.signed_distance(Vec3::new(3.0, 0.0, 0.0)) - 2.5).abs() < 1e-3);
+    let normal = sdf.normal(Vec3::new(0.6, 0.1, 0.0)).unwrap();
+    assert!((normal - Vec3::unit_x()).length() < 1e-3);
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(CollisionProxy::generate(&TriMesh::default(), ProxyMethod::ConvexHull), Err(ProxyError::EmptyMesh));
+    let flat = TriMesh::new(vec![Vec3::zero(), Vec3::unit_x(), Vec3::unit_y()], vec![0, 1, 2]);
+    assert_eq!(CollisionProxy::generate(&flat, ProxyMethod::ConvexHull), Err(ProxyError::Degenerate));
+    assert_eq!(CollisionProxy::generate(&flat, ProxyMethod::default()), Err(ProxyError::Degenerate));
+    assert_eq!(ProxyError::Degenerate.to_string(), "mesh encloses no volume");
+}
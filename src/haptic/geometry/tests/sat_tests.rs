@@ -0,0 +1,75 @@
+use super::*;
+use crate::core::{Deg, Quat};
+
+const TEST_EPSILON: f32 = 1e-5;
+
+fn unit_box(center: Vec3) -> Obb {
+    Obb::new(center, Vec3::new(1.0, 1.0, 1.0), Quat::identity())
+}
+
+fn turned(center: Vec3, degrees: f32) -> Obb {
+    Obb::new(center, Vec3::new(1.0, 1.0, 1.0), Quat::from_axis_angle(Vec3::unit_z(), Deg(degrees)))
+}
+
+#[test]
+fn test_aligned_boxes() {
+    let a = unit_box(Vec3::zero());
+    let hit = obb_overlap(&a, &unit_box(Vec3::new(1.5, 0.2, 0.0))).unwrap();
+    assert!((hit.depth - 0.5).abs() < TEST_EPSILON);
+    assert!((hit.normal - Vec3::new(-1.0, 0.0, 0.0)).length() < TEST_EPSILON);
+    assert!((hit.translation() - Vec3::new(-0.5, 0.0, 0.0)).length() < TEST_EPSILON);
+
+    assert!(obb_overlap(&a, &unit_box(Vec3::new(2.0, 0.0, 0.0))).is_none());
+    assert!(obb_overlap(&a, &unit_box(Vec3::new(0.0, 0.0, -2.1))).is_none());
+}
+
+#[test]
+fn test_rotated_boxes() {
+    let a = unit_box(Vec3::zero());
+    // The rotated box reaches sqrt(2) along X, so its corner pokes 0.214 into A.
+    let hit = obb_overlap(&a, &turned(Vec3::new(2.2, 0.0, 0.0), 45.0)).unwrap();
+    assert!((hit.depth - (2.0f32.sqrt() - 1.2)).abs() < 1e-4);
+    assert!((hit.normal - Vec3::new(-1.0, 0.0, 0.0)).length() < TEST_EPSILON);
+    assert!(obb_overlap(&a, &turned(Vec3::new(2.5, 0.0, 0.0), 45.0)).is_none());
+    // Diagonal offset: separated only along the rotated box's face axis.
+    assert!(obb_overlap(&a, &turned(Vec3::new(1.75, 1.75, 0.0), 45.0)).is_none());
+    assert!(obb_overlap(&a, &turned(Vec3::new(1.6, 1.6, 0.0), 45.0)).is_some());
+}
+
+#[test]
+fn test_translation_separates() {
+    let tilt = Quat::from_axis_angle(Vec3::new(1.0, 1.0, 0.0).normalize(), Deg(30.0));
+    let a = Obb::new(Vec3::new(0.1, -0.2, 0.3), Vec3::new(0.5, 0.8, 0.3), tilt);
+    for (i, degrees) in [0.0, 20.0, 45.0, 70.0].into_iter().enumerate() {
+        let roll = Quat::from_axis_angle(Vec3::unit_x(), Deg(degrees));
+        let b = Obb::new(Vec3::new(0.4, 0.3 * i as f32, -0.2), Vec3::new(0.6, 0.4, 0.5), roll);
+        let hit = obb_overlap(&a, &b).unwrap();
+        let moved = Obb { center: a.center + hit.translation() * 1.001, ..a };
+        assert!(obb_overlap(&moved, &b).is_none(), "case {}", i);
+        assert!(obb_overlap(&b, &a).is_some_and(|back| (back.depth - hit.depth).abs() < 1e-4));
+    }
+}
+
+#[test]
+fn test_box_triangle() {
+    let floor = [Vec3::new(-5.0, -5.0, 0.0), Vec3::new(5.0, -5.0, 0.0), Vec3::new(0.0, 5.0, 0.0)];
+    let obb = Obb::new(Vec3::new(0.0, 0.0, 0.4), Vec3::new(0.5, 0.5, 0.5), Quat::identity());
+    let hit = obb_triangle_overlap(&obb, floor).unwrap();
+    assert!((hit.depth - 0.1).abs() < TEST_EPSILON);
+    assert!((hit.normal - Vec3::unit_z()).length() < TEST_EPSILON);
+
+    assert!(obb_triangle_overlap(&Obb { center: Vec3::new(0.0, 0.0, 0.6), ..obb }, floor).is_none());
+    let aside = [Vec3::new(2.0, 0.0, -1.0), Vec3::new(3.0, 0.0, 1.0), Vec3::new(2.5, 1.0, 0.0)];
+    assert!(obb_triangle_overlap(&obb, aside).is_none());
+}
+
+#[test]
+fn test_box_triangle_edge_axis() {
+    // A sliver triangle passing beside the corner of a box turned 45 degrees: no face
+    // axis separates them, only an edge cross product does.
+    let obb = turned(Vec3::zero(), 45.0);
+    let sliver = [Vec3::new(1.0, 1.0, -3.0), Vec3::new(1.0, 1.0, 3.0), Vec3::new(1.05, 1.05, 0.0)];
+    assert!(obb_triangle_overlap(&obb, sliver).is_none());
+    let through = [Vec3::new(0.5, 0.5, -3.0), Vec3::new(0.5, 0.5, 3.0), Vec3::new(0.55, 0.55, 0.0)];
+    assert!(obb_triangle_overlap(&obb, through).is_some());
+}
@@ -0,0 +1,66 @@
+use super::*;
+use crate::geometry::{closest_point_on_triangle, Aabb};
+
+const TEST_EPSILON: f32 = 1e-3;
+
+fn unit_box() -> Aabb {
+    Aabb::from_center_half_extents(Vec3::zero(), Vec3::splat(0.5))
+}
+
+fn down_from(x: f32) -> Ray {
+    Ray::new(Vec3::new(x, 0.0, 2.0), Vec3::new(0.0, 0.0, -1.0))
+}
+
+#[test]
+fn test_sphere_face_edge_and_miss() {
+    let b = unit_box();
+    let face = sweep_sphere(&down_from(0.0), 0.1, 10.0, |p| b.closest_point(p)).unwrap();
+    assert!((face.distance - 1.4).abs() < TEST_EPSILON);
+    assert!((face.point - Vec3::new(0.0, 0.0, 0.5)).length() < TEST_EPSILON);
+    assert!((face.normal - Vec3::unit_z()).length() < TEST_EPSILON);
+
+    // Grazes the top edge at x = 0.5 with 0.05 of overlap
+    let edge = sweep_sphere(&down_from(0.55), 0.1, 10.0, |p| b.closest_point(p)).unwrap();
+    let rise = (0.1f32 * 0.1 - 0.05 * 0.05).sqrt();
+    assert!((edge.distance - (1.5 - rise)).abs() < TEST_EPSILON);
+    assert!((edge.normal - Vec3::new(0.05, 0.0, rise) / 0.1).length() < TEST_EPSILON);
+
+    assert!(sweep_sphere(&down_from(0.7), 0.1, 10.0, |p| b.closest_point(p)).is_none());
+    assert!(sweep_sphere(&down_from(0.0), 0.1, 1.0, |p| b.closest_point(p)).is_none());
+}
+
+#[test]
+fn test_sphere_does_not_tunnel_through_thin_triangle() {
+    let tri = [Vec3::new(-1.0, -1.0, 0.0), Vec3::new(1.0, -1.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+    // One 1 ms tick of a fast proxy: 10 cm of travel with a 1 mm radius, starting 5 cm above
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 0.05), Vec3::new(0.0, 0.0, -1.0));
+    let hit = sweep_sphere(&ray, 0.001, 0.1, |p| closest_point_on_triangle(p, tri)).unwrap();
+    assert!((hit.distance - 0.049).abs() < TEST_EPSILON);
+    assert!((hit.normal - Vec3::unit_z()).length() < TEST_EPSILON);
+
+    // Already touching
+    let start = sweep_sphere(&Ray::new(Vec3::zero(), Vec3::unit_x()), 0.01, 1.0, |p| closest_point_on_triangle(p, tri));
+    assert_eq!(start.map(|h| h.distance), Some(0.0));
+}
+
+#[test]
+fn test_capsule_sweeps() {
+    let b = unit_box();
+    let down = Vec3::new(0.0, 0.0, -1.0);
+
+    let flat = Capsule::new(Vec3::new(-1.0, 0.0, 1.0), Vec3::new(1.0, 0.0, 1.0), 0.1);
+    assert!((flat.bounding_radius() - 1.1).abs() < TEST_EPSILON);
+    let hit = sweep_capsule(&flat, down, 10.0, |p| b.closest_point(p)).unwrap();
+    assert!((hit.distance - 0.4).abs() < TEST_EPSILON);
+    assert!((hit.normal - Vec3::unit_z()).length() < TEST_EPSILON);
+
+    // Tilted: the low end leads
+    let tilted = Capsule::new(Vec3::new(0.0, 0.0, 1.0), Vec3::new(1.0, 0.0, 2.0), 0.1);
+    let hit = sweep_capsule(&tilted, down, 10.0, |p| b.closest_point(p)).unwrap();
+    assert!((hit.distance - 0.4).abs() < TEST_EPSILON);
+    assert!((hit.point.z - 0.5).abs() < TEST_EPSILON && hit.point.x.abs() < 0.1);
+
+    // Passes beside the box
+    let beside = Capsule::new(Vec3::new(0.7, -1.0, 1.0), Vec3::new(0.7, 1.0, 1.0), 0.1);
+    assert!(sweep_capsule(&beside, down, 10.0, |p| b.closest_point(p)).is_none());
+}
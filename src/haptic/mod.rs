@@ -0,0 +1,18 @@
+// src/haptic/mod.rs
+pub mod analytics;
+pub mod anim;
+pub mod assets;
+pub mod core;
+pub mod device;
+pub mod effects;
+pub mod geometry;
+pub mod net;
+pub mod render;
+pub mod safety;
+pub mod scene;
+#[cfg(feature = "scripting")]
+pub mod script;
+pub mod spatial;
+pub mod text;
+pub mod ui;
+pub mod widgets;
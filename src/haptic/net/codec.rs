@@ -0,0 +1,392 @@
+//! Perceptual deadband codec for networked haptic streams.
+//!
+//! Pose and force samples are only transmitted when they differ from the last
+//! transmitted value by more than a Weber fraction of that value. Changes below the
+//! just-noticeable difference are dropped on the sender and held on the receiver,
+//! which typically removes ~90% of the packets of a 1 kHz stream. Positions have no
+//! perceived magnitude, only a distance from wherever the origin happens to be, so
+//! their deadband is a fixed radius instead.
+
+use crate::core::constants::{
+    DEFAULT_NET_FORCE_DEADBAND_N, DEFAULT_NET_FORCE_WEBER_FRACTION, DEFAULT_NET_HEARTBEAT_INTERVAL,
+    DEFAULT_NET_POSITION_DEADBAND_M, NET_FORCE_DEADBAND_N, NET_FORCE_WEBER_FRACTION, NET_HEARTBEAT_INTERVAL,
+    NET_POSITION_DEADBAND_M,
+};
+use crate::core::{Config, Vec3};
+use std::fmt;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Deadband parameters for a single Vec3 stream.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeadbandConfig {
+    /// Relative threshold (Weber fraction) applied to the last transmitted magnitude.
+    /// Zero for streams such as positions, whose magnitude depends on the frame.
+    pub weber_fraction: f32,
+    /// Absolute threshold floor, so that values near zero are not sent on every jitter.
+    pub absolute_threshold: f32,
+    /// Forces a transmission after this many suppressed samples (0 disables heartbeats).
+    pub heartbeat_interval: u32,
+}
+
+impl DeadbandConfig {
+    /// Default for position streams: a fixed 0.5 mm radius, heartbeat every 100 samples.
+    pub const POSITION: Self =
+        Self::new(0.0, DEFAULT_NET_POSITION_DEADBAND_M as f32, DEFAULT_NET_HEARTBEAT_INTERVAL as u32);
+
+    /// Default for force streams: 10% Weber fraction, 0.01 N floor, heartbeat every 100 samples.
+    pub const FORCE: Self = Self::new(
+        DEFAULT_NET_FORCE_WEBER_FRACTION as f32,
+        DEFAULT_NET_FORCE_DEADBAND_N as f32,
+        DEFAULT_NET_HEARTBEAT_INTERVAL as u32,
+    );
+
+    /// [`POSITION`](Self::POSITION) with `net.position_deadband_m` and
+    /// `net.heartbeat_interval` from `config`.
+    pub fn position_from_config(config: &Config) -> Self {
+        let radius = config.get_tuning(NET_POSITION_DEADBAND_M);
+        Self {
+            absolute_threshold: radius.unwrap_or(Self::POSITION.absolute_threshold),
+            heartbeat_interval: heartbeat_from_config(config),
+            ..Self::POSITION
+        }
+    }
+
+    /// [`FORCE`](Self::FORCE) with `net.force_weber_fraction`, `net.force_deadband_n`
+    /// and `net.heartbeat_interval` from `config`.
+    pub fn force_from_config(config: &Config) -> Self {
+        let fraction = config.get_tuning(NET_FORCE_WEBER_FRACTION);
+        let floor = config.get_tuning(NET_FORCE_DEADBAND_N);
+        Self {
+            weber_fraction: fraction.unwrap_or(Self::FORCE.weber_fraction),
+            absolute_threshold: floor.unwrap_or(Self::FORCE.absolute_threshold),
+            heartbeat_interval: heartbeat_from_config(config),
+        }
+    }
+
+    /// Creates a new deadband configuration.
+    #[inline]
+    pub const fn new(weber_fraction: f32, absolute_threshold: f32, heartbeat_interval: u32) -> Self {
+        Self { weber_fraction, absolute_threshold, heartbeat_interval }
+    }
+
+    /// Returns the deadband radius around a reference value.
+    #[inline]
+    pub fn threshold(&self, reference: Vec3) -> f32 {
+        self.weber_fraction * reference.length() + self.absolute_threshold
+    }
+}
+
+/// `net.heartbeat_interval`, or the default when unset or out of range.
+fn heartbeat_from_config(config: &Config) -> u32 {
+    config
+        .get_i64(NET_HEARTBEAT_INTERVAL)
+        .and_then(|n| u32::try_from(n).ok())
+        .unwrap_or(DEFAULT_NET_HEARTBEAT_INTERVAL as u32)
+}
+
+// ============================================================================
+// Wire Format
+// ============================================================================
+
+/// Logical stream a transmitted update belongs to.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Channel {
+    Position = 0,
+    Force = 1,
+}
+
+impl Channel {
+    /// Parses a channel from its wire tag.
+    #[inline]
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(Channel::Position),
+            1 => Some(Channel::Force),
+            _ => None,
+        }
+    }
+}
+
+/// A single transmitted sample.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Update {
+    pub channel: Channel,
+    pub timestamp_us: u64,
+    pub value: Vec3,
+}
+
+impl Update {
+    /// Size of an encoded update in bytes: tag + timestamp + three f32 components.
+    pub const ENCODED_LEN: usize = 1 + 8 + 12;
+
+    /// Serializes the update into a fixed-size little-endian datagram.
+    pub fn to_bytes(&self) -> [u8; Self::ENCODED_LEN] {
+        let mut out = [0u8; Self::ENCODED_LEN];
+        out[0] = self.channel as u8;
+        out[1..9].copy_from_slice(&self.timestamp_us.to_le_bytes());
+        out[9..13].copy_from_slice(&self.value.x.to_le_bytes());
+        out[13..17].copy_from_slice(&self.value.y.to_le_bytes());
+        out[17..21].copy_from_slice(&self.value.z.to_le_bytes());
+        out
+    }
+
+    /// Deserializes an update produced by `to_bytes`; `bytes` must hold exactly
+    /// one update.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CodecError> {
+        if bytes.len() < Self::ENCODED_LEN {
+            return Err(CodecError::Truncated(bytes.len()));
+        }
+        if bytes.len() > Self::ENCODED_LEN {
+            return Err(CodecError::TrailingBytes(bytes.len()));
+        }
+        let channel = Channel::from_tag(bytes[0]).ok_or(CodecError::UnknownChannel(bytes[0]))?;
+        let f32_at = |i: usize| f32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+        let mut ts = [0u8; 8];
+        ts.copy_from_slice(&bytes[1..9]);
+        let value = Vec3::new(f32_at(9), f32_at(13), f32_at(17));
+        if !value.is_finite() {
+            return Err(CodecError::NonFinite);
+        }
+        Ok(Self { channel, timestamp_us: u64::from_le_bytes(ts), value })
+    }
+}
+
+/// Errors produced while decoding updates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodecError {
+    /// Datagram shorter than `Update::ENCODED_LEN`.
+    Truncated(usize),
+    /// Datagram longer than `Update::ENCODED_LEN`.
+    TrailingBytes(usize),
+    /// Unknown channel tag.
+    UnknownChannel(u8),
+    /// Payload contained NaN or infinite components.
+    NonFinite,
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::Truncated(len) => {
+                write!(f, "truncated update: {} of {} bytes", len, Update::ENCODED_LEN)
+            }
+            CodecError::TrailingBytes(len) => {
+                write!(f, "oversized update: {} bytes, expected {}", len, Update::ENCODED_LEN)
+            }
+            CodecError::UnknownChannel(tag) => write!(f, "unknown channel tag {}", tag),
+            CodecError::NonFinite => write!(f, "update contains non-finite components"),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+// ============================================================================
+// Single-Channel Encoder / Decoder
+// ============================================================================
+
+/// Transmission statistics of an encoder.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CodecStats {
+    pub samples: u64,
+    pub transmitted: u64,
+    /// Samples dropped for NaN or infinite components; not counted in `samples`.
+    pub non_finite: u64,
+}
+
+impl CodecStats {
+    /// Fraction of samples that were transmitted (1.0 when nothing was encoded yet).
+    #[inline]
+    pub fn transmit_ratio(&self) -> f32 {
+        if self.samples == 0 {
+            1.0
+        } else {
+            self.transmitted as f32 / self.samples as f32
+        }
+    }
+}
+
+/// Sender side of a deadband-coded Vec3 stream.
+#[derive(Debug, Clone)]
+pub struct DeadbandEncoder {
+    channel: Channel,
+    config: DeadbandConfig,
+    last_sent: Option<Vec3>,
+    suppressed: u32,
+    stats: CodecStats,
+}
+
+impl DeadbandEncoder {
+    /// Creates an encoder for the given channel.
+    pub fn new(channel: Channel, config: DeadbandConfig) -> Self {
+        Self { channel, config, last_sent: None, suppressed: 0, stats: CodecStats::default() }
+    }
+
+    /// Offers a new sample; returns the update to transmit, if any.
+    /// The first sample and non-perceptible changes after a heartbeat timeout are always sent.
+    /// Non-finite samples are never sent, and leave the reference untouched.
+    pub fn encode(&mut self, timestamp_us: u64, value: Vec3) -> Option<Update> {
+        if !value.is_finite() {
+            self.stats.non_finite += 1;
+            return None;
+        }
+        self.stats.samples += 1;
+
+        let send = match self.last_sent {
+            None => true,
+            Some(reference) => {
+                let heartbeat = self.config.heartbeat_interval != 0
+                    && self.suppressed + 1 >= self.config.heartbeat_interval;
+                heartbeat || value.distance_to(reference) > self.config.threshold(reference)
+            }
+        };
+
+        if send {
+            self.last_sent = Some(value);
+            self.suppressed = 0;
+            self.stats.transmitted += 1;
+            Some(Update { channel: self.channel, timestamp_us, value })
+        } else {
+            self.suppressed += 1;
+            None
+        }
+    }
+
+    /// Forgets the reference value so the next sample is transmitted unconditionally.
+    pub fn reset(&mut self) {
+        self.last_sent = None;
+        self.suppressed = 0;
+    }
+
+    #[inline]
+    pub fn config(&self) -> DeadbandConfig {
+        self.config
+    }
+
+    #[inline]
+    pub fn stats(&self) -> CodecStats {
+        self.stats
+    }
+}
+
+/// Receiver side of a deadband-coded Vec3 stream (zero-order hold).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DeadbandDecoder {
+    last: Option<Update>,
+}
+
+impl DeadbandDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies a received update. Updates older than the current one are ignored,
+    /// since UDP may reorder datagrams. Returns true if the update was accepted.
+    pub fn apply(&mut self, update: Update) -> bool {
+        match self.last {
+            Some(last) if update.timestamp_us < last.timestamp_us => false,
+            _ => {
+                self.last = Some(update);
+                true
+            }
+        }
+    }
+
+    /// Reconstructed value, or None before the first update arrives.
+    #[inline]
+    pub fn value(&self) -> Option<Vec3> {
+        self.last.map(|u| u.value)
+    }
+
+    /// Timestamp of the most recently accepted update.
+    #[inline]
+    pub fn timestamp_us(&self) -> Option<u64> {
+        self.last.map(|u| u.timestamp_us)
+    }
+}
+
+// ============================================================================
+// Pose/Force Stream
+// ============================================================================
+
+/// Encodes a combined position + force stream for a single device.
+#[derive(Debug, Clone)]
+pub struct StreamEncoder {
+    position: DeadbandEncoder,
+    force: DeadbandEncoder,
+}
+
+impl StreamEncoder {
+    pub fn new(position: DeadbandConfig, force: DeadbandConfig) -> Self {
+        Self {
+            position: DeadbandEncoder::new(Channel::Position, position),
+            force: DeadbandEncoder::new(Channel::Force, force),
+        }
+    }
+
+    /// Deadbands from the `net.*` keys of `config`.
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(DeadbandConfig::position_from_config(config), DeadbandConfig::force_from_config(config))
+    }
+
+    /// Encodes one servo tick, appending the updates to transmit to `out`.
+    pub fn encode_into(&mut self, timestamp_us: u64, position: Vec3, force: Vec3, out: &mut Vec<Update>) {
+        out.extend(self.position.encode(timestamp_us, position));
+        out.extend(self.force.encode(timestamp_us, force));
+    }
+
+    pub fn reset(&mut self) {
+        self.position.reset();
+        self.force.reset();
+    }
+
+    /// Combined statistics over both channels.
+    pub fn stats(&self) -> CodecStats {
+        let (p, f) = (self.position.stats(), self.force.stats());
+        CodecStats {
+            samples: p.samples + f.samples,
+            transmitted: p.transmitted + f.transmitted,
+            non_finite: p.non_finite + f.non_finite,
+        }
+    }
+}
+
+impl Default for StreamEncoder {
+    fn default() -> Self {
+        Self::new(DeadbandConfig::POSITION, DeadbandConfig::FORCE)
+    }
+}
+
+/// Decodes a combined position + force stream.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StreamDecoder {
+    pub position: DeadbandDecoder,
+    pub force: DeadbandDecoder,
+}
+
+impl StreamDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Routes an update to its channel decoder.
+    pub fn apply(&mut self, update: Update) -> bool {
+        match update.channel {
+            Channel::Position => self.position.apply(update),
+            Channel::Force => self.force.apply(update),
+        }
+    }
+
+    /// Decodes and applies a raw datagram.
+    pub fn apply_bytes(&mut self, bytes: &[u8]) -> Result<bool, CodecError> {
+        Update::from_bytes(bytes).map(|update| self.apply(update))
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/codec_tests.rs"]
+mod tests;
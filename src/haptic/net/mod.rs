@@ -0,0 +1,6 @@
+// src/haptic/net/mod.rs
+pub mod codec;
+pub use codec::{
+    Channel, CodecError, CodecStats, DeadbandConfig, DeadbandDecoder, DeadbandEncoder,
+    StreamDecoder, StreamEncoder, Update,
+};
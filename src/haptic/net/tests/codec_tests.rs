@@ -0,0 +1,119 @@
+use super::*;
+
+#[test]
+fn test_first_sample_always_sent() {
+    let mut encoder = DeadbandEncoder::new(Channel::Force, DeadbandConfig::FORCE);
+    let update = encoder.encode(0, Vec3::zero()).expect("first sample must be sent");
+    assert_eq!(update.channel, Channel::Force);
+    assert_eq!(update.value, Vec3::zero());
+}
+
+#[test]
+fn test_weber_threshold_suppresses_small_changes() {
+    let config = DeadbandConfig::new(0.1, 0.0, 0);
+    let mut encoder = DeadbandEncoder::new(Channel::Force, config);
+    encoder.encode(0, Vec3::new(1.0, 0.0, 0.0));
+
+    // 5% change is below the 10% Weber fraction
+    assert!(encoder.encode(1, Vec3::new(1.05, 0.0, 0.0)).is_none());
+    // 15% change relative to the last *transmitted* value is sent
+    assert!(encoder.encode(2, Vec3::new(1.15, 0.0, 0.0)).is_some());
+
+    let stats = encoder.stats();
+    assert_eq!(stats.samples, 3);
+    assert_eq!(stats.transmitted, 2);
+}
+
+#[test]
+fn test_heartbeat_forces_transmission() {
+    let config = DeadbandConfig::new(0.1, 0.01, 4);
+    let mut encoder = DeadbandEncoder::new(Channel::Position, config);
+    encoder.encode(0, Vec3::one());
+    assert!(encoder.encode(1, Vec3::one()).is_none());
+    assert!(encoder.encode(2, Vec3::one()).is_none());
+    assert!(encoder.encode(3, Vec3::one()).is_none());
+    assert!(encoder.encode(4, Vec3::one()).is_some());
+}
+
+#[test]
+fn test_slow_drift_reduces_traffic() {
+    let mut encoder = StreamEncoder::default();
+    let mut decoder = StreamDecoder::new();
+    let mut out = Vec::new();
+
+    for i in 0..1000u64 {
+        let t = i as f32 * 0.001;
+        let position = Vec3::new(0.05 * t.sin(), 0.02, 0.0);
+        let force = Vec3::new(0.0, 2.0 + 0.5 * (t * 3.0).sin(), 0.0);
+        encoder.encode_into(i * 1000, position, force, &mut out);
+    }
+    for update in &out {
+        decoder.apply(*update);
+    }
+
+    assert!(encoder.stats().transmit_ratio() < 0.1, "ratio = {}", encoder.stats().transmit_ratio());
+    let force = decoder.force.value().unwrap();
+    assert!((force.y - (2.0 + 0.5 * 3.0f32.sin())).abs() < 0.3);
+}
+
+#[test]
+fn test_wire_round_trip() {
+    let update = Update { channel: Channel::Position, timestamp_us: 123_456_789, value: Vec3::new(1.5, -2.0, 0.25) };
+    let bytes = update.to_bytes();
+    assert_eq!(Update::from_bytes(&bytes), Ok(update));
+
+    assert_eq!(Update::from_bytes(&bytes[..5]), Err(CodecError::Truncated(5)));
+    let mut long = bytes.to_vec();
+    long.push(0);
+    assert_eq!(Update::from_bytes(&long), Err(CodecError::TrailingBytes(Update::ENCODED_LEN + 1)));
+    let mut bad = bytes;
+    bad[0] = 9;
+    assert_eq!(Update::from_bytes(&bad), Err(CodecError::UnknownChannel(9)));
+}
+
+#[test]
+fn test_decoder_ignores_reordered_updates() {
+    let mut decoder = DeadbandDecoder::new();
+    assert!(decoder.apply(Update { channel: Channel::Force, timestamp_us: 10, value: Vec3::one() }));
+    assert!(!decoder.apply(Update { channel: Channel::Force, timestamp_us: 5, value: Vec3::zero() }));
+    assert_eq!(decoder.value(), Some(Vec3::one()));
+}
+
+#[test]
+fn test_position_deadband_ignores_origin() {
+    let mut near = DeadbandEncoder::new(Channel::Position, DeadbandConfig::POSITION);
+    let mut far = DeadbandEncoder::new(Channel::Position, DeadbandConfig::POSITION);
+    let offset = Vec3::new(1.0, 0.0, 0.0);
+    near.encode(0, Vec3::zero());
+    far.encode(0, offset);
+
+    // A 1 mm move is sent the same way wherever it happens
+    let step = Vec3::new(0.0, 0.001, 0.0);
+    assert!(near.encode(1, step).is_some());
+    assert!(far.encode(1, offset + step).is_some());
+}
+
+#[test]
+fn test_non_finite_samples_are_skipped() {
+    let mut encoder = DeadbandEncoder::new(Channel::Force, DeadbandConfig::FORCE);
+    encoder.encode(0, Vec3::one());
+    assert!(encoder.encode(1, Vec3::new(f32::NAN, 0.0, 0.0)).is_none());
+    assert!(encoder.encode(2, Vec3::new(0.0, f32::INFINITY, 0.0)).is_none());
+
+    // The reference is still the last finite sample
+    assert!(encoder.encode(3, Vec3::one()).is_none());
+    assert!(encoder.encode(4, Vec3::new(2.0, 1.0, 1.0)).is_some());
+    let stats = encoder.stats();
+    assert_eq!((stats.samples, stats.transmitted, stats.non_finite), (3, 2, 2));
+}
+
+#[test]
+fn test_deadbands_from_config() {
+    let mut config = Config::new();
+    assert_eq!(DeadbandConfig::position_from_config(&config), DeadbandConfig::POSITION);
+    assert_eq!(DeadbandConfig::force_from_config(&config), DeadbandConfig::FORCE);
+
+    config.load_str("[net]\nposition_deadband_m = 0.002\nforce_weber_fraction = 0.2\nheartbeat_interval = 10\n").unwrap();
+    assert_eq!(DeadbandConfig::position_from_config(&config), DeadbandConfig::new(0.0, 0.002, 10));
+    assert_eq!(DeadbandConfig::force_from_config(&config), DeadbandConfig::new(0.2, 0.01, 10));
+}
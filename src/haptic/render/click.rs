@@ -0,0 +1,122 @@
+//! Authored force profiles for push buttons.
+//!
+//! A plain spring under a button feels like pressing a sponge. Physical keys feel
+//! crisp because their force is not monotonic: a [`ClickProfile`] describes the
+//! buckling-spring curve of a key over its displacement. The force ramps up over
+//! the pre-travel to the break force, collapses to a lower plateau as the spring
+//! buckles (the tactile click), climbs back over the rest of the travel, and ends
+//! on a stiff bottom-out. The collapse is eased rather than a step so that the
+//! device does not buzz at the break point.
+//!
+//! The press registers at the break point and releases only once the button has
+//! risen back above the release point, so a finger resting near the break cannot
+//! chatter between pressed and released.
+
+use crate::core::{smootherstep, Meters, Meters3, Newtons, Newtons3, NewtonsPerMeter, Vec3};
+
+/// Force over displacement of a buckling-spring key.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClickProfile {
+    /// Displacement at which the spring buckles and the press registers.
+    pub break_travel: Meters,
+    /// Peak force reached at the break.
+    pub break_force: Newtons,
+    /// Distance past the break over which the force collapses.
+    pub collapse_travel: Meters,
+    /// Force at the bottom of the collapse.
+    pub collapse_force: Newtons,
+    /// Full travel; the button bottoms out here.
+    pub travel: Meters,
+    /// Stiffness of the end stop past the full travel.
+    pub bottom_stiffness: NewtonsPerMeter,
+    /// Height the button must rise back above to release.
+    pub release_travel: Meters,
+}
+
+impl ClickProfile {
+    /// Clicky keyboard switch: 0.6 N break at 2 mm, 4 mm travel.
+    pub const KEYBOARD: Self = Self {
+        break_travel: Meters(0.002),
+        break_force: Newtons(0.6),
+        collapse_travel: Meters(0.0005),
+        collapse_force: Newtons(0.3),
+        travel: Meters(0.004),
+        bottom_stiffness: NewtonsPerMeter(3000.0),
+        release_travel: Meters(0.0012),
+    };
+
+    /// Large, soft panel button with a gentle click.
+    pub const SOFT: Self = Self {
+        break_travel: Meters(0.004),
+        break_force: Newtons(1.0),
+        collapse_travel: Meters(0.002),
+        collapse_force: Newtons(0.7),
+        travel: Meters(0.008),
+        bottom_stiffness: NewtonsPerMeter(2000.0),
+        release_travel: Meters(0.0025),
+    };
+
+    /// The same feel over a different full travel: every distance scales with it.
+    pub fn with_travel(self, travel: Meters) -> Self {
+        let scale = travel.value().max(0.0) / self.travel.value().max(f32::EPSILON);
+        Self {
+            break_travel: self.break_travel * scale,
+            collapse_travel: self.collapse_travel * scale,
+            travel: self.travel * scale,
+            release_travel: self.release_travel * scale,
+            ..self
+        }
+    }
+
+    /// Where the collapse ends.
+    #[inline]
+    pub fn collapse_end(&self) -> Meters {
+        self.break_travel + self.collapse_travel
+    }
+
+    /// Force pushing the button back up at `displacement` (positive pressing in).
+    pub fn force(&self, displacement: Meters) -> Newtons {
+        let d = displacement.value();
+        let (b, c, travel) = (self.break_travel.value(), self.collapse_end().value(), self.travel.value());
+        let (peak, low) = (self.break_force.value(), self.collapse_force.value());
+        let force = if d <= 0.0 {
+            0.0
+        } else if d < b {
+            peak * d / b
+        } else if d < c {
+            peak - (peak - low) * smootherstep(b, c, d)
+        } else if d < travel {
+            low + (peak - low) * (d - c) / (travel - c).max(f32::EPSILON)
+        } else {
+            peak + self.bottom_stiffness.value() * (d - travel)
+        };
+        Newtons(force)
+    }
+
+    /// Force on a device at `device` pressing a button whose cap rests at `rest` and
+    /// travels along the unit vector `axis` (pointing into the button).
+    pub fn force_along(&self, rest: Vec3, axis: Vec3, device: Meters3) -> Newtons3 {
+        let displacement = Meters((device.value() - rest).dot(axis));
+        Newtons3(axis * -self.force(displacement).value())
+    }
+
+    /// Whether the button reads as pressed at `displacement`, given whether it did
+    /// on the previous tick.
+    pub fn pressed(&self, displacement: Meters, was_pressed: bool) -> bool {
+        if was_pressed {
+            displacement.value() > self.release_travel.value()
+        } else {
+            displacement.value() >= self.break_travel.value()
+        }
+    }
+}
+
+impl Default for ClickProfile {
+    fn default() -> Self {
+        Self::KEYBOARD
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/click_tests.rs"]
+mod tests;
@@ -0,0 +1,250 @@
+//! Spring-damper contact forces.
+//!
+//! A [`ContactModel`] turns the geometric state of one contact (how deep the device
+//! is behind the surface and how fast it moves along the normal) into a force
+//! `k * penetration - b * normal_velocity`, using the stiffness and damping of the
+//! touched surface. The force only ever pushes out, is saturated at what the device
+//! can produce, and can be dropped entirely once the user pushes deeper than a
+//! pop-through depth, which lets them break through a surface on purpose.
+//!
+//! Touching a stiff surface at speed puts the device a few hundred microns deep on
+//! the first tick, and the full spring force arrives as a kick. An engagement
+//! dead-band and ramp-in tame that: a contact only engages once it is deeper than
+//! the engage depth, stays engaged until it rises above the (shallower) release
+//! depth, and its force fades in over the ramp time. [`ContactEngagement`] tracks
+//! this per contact.
+
+use crate::core::constants::{
+    CONTACT_ENGAGE_DEPTH_M, CONTACT_RAMP_IN_MS, CONTACT_RELEASE_DEPTH_M, DEFAULT_CONTACT_ENGAGE_DEPTH_M,
+    DEFAULT_CONTACT_RAMP_IN_MS, DEFAULT_CONTACT_RELEASE_DEPTH_M,
+};
+use crate::core::{
+    smoothstep, Config, Meters, MetersPerSecond, MetersPerSecond3, Milliseconds, NewtonSecondsPerMeter, Newtons,
+    Newtons3, NewtonsPerMeter, Seconds, Vec3,
+};
+use crate::device::DeviceCapabilities;
+use crate::scene::NodeHaptics;
+
+// ============================================================================
+// Material and State
+// ============================================================================
+
+/// Compliance and friction of a rendered surface.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContactMaterial {
+    pub stiffness: NewtonsPerMeter,
+    pub damping: NewtonSecondsPerMeter,
+    /// Coulomb coefficient that must be overcome to start sliding.
+    pub static_friction: f32,
+    /// Coulomb coefficient while sliding; at most `static_friction`.
+    pub kinetic_friction: f32,
+    /// Drag proportional to the sliding speed.
+    pub viscous_friction: NewtonSecondsPerMeter,
+}
+
+impl ContactMaterial {
+    /// Frictionless surface.
+    #[inline]
+    pub const fn new(stiffness: NewtonsPerMeter, damping: NewtonSecondsPerMeter) -> Self {
+        Self {
+            stiffness,
+            damping,
+            static_friction: 0.0,
+            kinetic_friction: 0.0,
+            viscous_friction: NewtonSecondsPerMeter::ZERO,
+        }
+    }
+
+    pub const fn with_friction(mut self, static_friction: f32, kinetic_friction: f32) -> Self {
+        self.static_friction = static_friction;
+        self.kinetic_friction = kinetic_friction;
+        self
+    }
+
+    pub const fn with_viscous_friction(mut self, viscous: NewtonSecondsPerMeter) -> Self {
+        self.viscous_friction = viscous;
+        self
+    }
+}
+
+impl Default for ContactMaterial {
+    fn default() -> Self {
+        NodeHaptics::default().into()
+    }
+}
+
+impl From<NodeHaptics> for ContactMaterial {
+    /// Nodes carry a single Coulomb coefficient, used for sticking and sliding alike.
+    fn from(haptics: NodeHaptics) -> Self {
+        Self::new(haptics.stiffness, haptics.damping).with_friction(haptics.friction, haptics.friction)
+    }
+}
+
+/// Geometry of one contact at one tick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContactState {
+    /// Index of the touched surface among those passed to the renderer.
+    pub surface: usize,
+    /// Surface point the device is held at (the proxy position).
+    pub point: Vec3,
+    /// Outward unit normal.
+    pub normal: Vec3,
+    /// Depth of the device behind the surface; zero or negative when not touching.
+    pub penetration: Meters,
+    /// Device velocity along the normal; negative while pressing in.
+    pub normal_velocity: MetersPerSecond,
+}
+
+impl ContactState {
+    /// State of a device at `device` moving with `velocity` against the surface
+    /// through `point` with outward `normal`.
+    pub fn new(surface: usize, point: Vec3, normal: Vec3, device: Vec3, velocity: MetersPerSecond3) -> Self {
+        Self {
+            surface,
+            point,
+            normal,
+            penetration: Meters((point - device).dot(normal)),
+            normal_velocity: MetersPerSecond(velocity.0.dot(normal)),
+        }
+    }
+}
+
+// ============================================================================
+// Model
+// ============================================================================
+
+/// Spring-damper force law with saturation and optional pop-through.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContactModel {
+    /// Largest force magnitude produced, per contact and in total.
+    pub max_force: Newtons,
+    /// Penetration beyond which the surface gives way; None never does.
+    pub pop_through: Option<Meters>,
+    /// Penetration at which a new contact engages.
+    pub engage_depth: Meters,
+    /// Penetration below which an engaged contact lets go; at most `engage_depth`.
+    pub release_depth: Meters,
+    /// Time over which a newly engaged contact's force fades in.
+    pub ramp_in: Seconds,
+}
+
+impl ContactModel {
+    pub fn new(max_force: Newtons) -> Self {
+        Self {
+            max_force,
+            pop_through: None,
+            engage_depth: Meters(DEFAULT_CONTACT_ENGAGE_DEPTH_M as f32),
+            release_depth: Meters(DEFAULT_CONTACT_RELEASE_DEPTH_M as f32),
+            ramp_in: Seconds::from(Milliseconds(DEFAULT_CONTACT_RAMP_IN_MS as f32)),
+        }
+    }
+
+    /// Saturates at the device's peak force.
+    pub fn for_device(capabilities: &DeviceCapabilities) -> Self {
+        Self::new(capabilities.max_force)
+    }
+
+    pub fn with_pop_through(mut self, depth: Meters) -> Self {
+        self.pop_through = Some(depth);
+        self
+    }
+
+    /// Engages contacts at `engage` deep and releases them above `release`.
+    pub fn with_engagement(mut self, engage: Meters, release: Meters) -> Self {
+        self.engage_depth = Meters(engage.value().max(0.0));
+        self.release_depth = Meters(release.value().min(self.engage_depth.value()));
+        self
+    }
+
+    /// Fades the force of new contacts in over `ramp`.
+    pub fn with_ramp_in(mut self, ramp: Milliseconds) -> Self {
+        self.ramp_in = Seconds::from(ramp);
+        self
+    }
+
+    /// Takes the dead-band and ramp-in from the `contact.*` keys of `config`; unset,
+    /// negative or non-finite values keep the current ones.
+    pub fn with_config(self, config: &Config) -> Self {
+        let get = |key: &str| config.get_tuning(key);
+        let engage = get(CONTACT_ENGAGE_DEPTH_M).map_or(self.engage_depth, Meters);
+        let release = get(CONTACT_RELEASE_DEPTH_M).map_or(self.release_depth, Meters);
+        let model = self.with_engagement(engage, release);
+        match get(CONTACT_RAMP_IN_MS) {
+            Some(ms) => model.with_ramp_in(Milliseconds(ms)),
+            None => model,
+        }
+    }
+
+    /// Whether the device has pushed deep enough to break through.
+    #[inline]
+    pub fn pops_through(&self, contact: &ContactState) -> bool {
+        self.pop_through.is_some_and(|depth| contact.penetration.value() > depth.value())
+    }
+
+    /// Force of one contact. Zero when not penetrating or popped through; never pulls
+    /// the device into the surface, even while it withdraws quickly.
+    pub fn force(&self, contact: &ContactState, material: &ContactMaterial) -> Newtons3 {
+        if contact.penetration.value() <= 0.0 || self.pops_through(contact) {
+            return Newtons3::ZERO;
+        }
+        let push = material.stiffness * contact.penetration - material.damping * contact.normal_velocity;
+        let magnitude = push.value().clamp(0.0, self.max_force.value());
+        Newtons3(contact.normal * magnitude)
+    }
+
+    /// Clamps a summed force to the saturation limit.
+    #[inline]
+    pub fn saturate(&self, force: Newtons3) -> Newtons3 {
+        force.clamp_length(self.max_force)
+    }
+}
+
+impl Default for ContactModel {
+    fn default() -> Self {
+        Self::for_device(&DeviceCapabilities::KINESTHETIC)
+    }
+}
+
+// ============================================================================
+// Engagement
+// ============================================================================
+
+/// Engagement of one contact, carried between ticks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ContactEngagement {
+    engaged_at_us: Option<u64>,
+}
+
+impl ContactEngagement {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn is_engaged(&self) -> bool {
+        self.engaged_at_us.is_some()
+    }
+
+    /// Advances the dead-band for `contact` at session time `now_us` and returns the
+    /// gain to apply to its force, in 0..=1.
+    pub fn update(&mut self, model: &ContactModel, contact: &ContactState, now_us: u64) -> f32 {
+        let depth = contact.penetration.value();
+        match self.engaged_at_us {
+            None if depth > model.engage_depth.value() => self.engaged_at_us = Some(now_us),
+            Some(_) if depth < model.release_depth.value() => self.engaged_at_us = None,
+            _ => {}
+        }
+        let Some(engaged_at) = self.engaged_at_us else {
+            return 0.0;
+        };
+        let ramp = model.ramp_in.value();
+        if ramp <= 0.0 {
+            return 1.0;
+        }
+        smoothstep(0.0, ramp, now_us.saturating_sub(engaged_at) as f32 * 1e-6)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/contact_tests.rs"]
+mod tests;
@@ -0,0 +1,95 @@
+//! Detents: notches along a widget's travel that the device clicks into.
+//!
+//! A [`Detent`] places notches every `spacing` along a one-dimensional travel (a
+//! slider's track, or the arc length under the fingers of a knob). Within `width`
+//! around a notch the device is pulled toward its center by a force that rises
+//! from zero at the edge to `depth` halfway in and fades back to zero at the
+//! center, so crossing a notch feels like a bump followed by a click into place.
+//! Between notches the travel is free.
+
+use std::f32::consts::TAU;
+
+use crate::core::{Meters, Meters3, Newtons, Newtons3, Vec3};
+
+/// Evenly spaced notches along a travel axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Detent {
+    /// Distance between neighboring notch centers.
+    pub spacing: Meters,
+    /// Peak force pulling toward a notch.
+    pub depth: Newtons,
+    /// Width of the region around each notch that pulls; at most `spacing`.
+    pub width: Meters,
+    /// Travel position of notch 0.
+    pub origin: Meters,
+    /// Number of notches from the origin on; None repeats forever both ways.
+    pub count: Option<u32>,
+}
+
+impl Detent {
+    pub fn new(spacing: Meters, depth: Newtons, width: Meters) -> Self {
+        let spacing = Meters(spacing.value().abs().max(f32::EPSILON));
+        Self {
+            spacing,
+            depth,
+            width: Meters(width.value().clamp(0.0, spacing.value())),
+            origin: Meters::ZERO,
+            count: None,
+        }
+    }
+
+    /// `count` notches spread evenly over `length`, both ends included.
+    pub fn over(length: Meters, count: u32, depth: Newtons, width: Meters) -> Self {
+        let spacing = Meters(length.value() / count.saturating_sub(1).max(1) as f32);
+        Self::new(spacing, depth, width).with_count(count)
+    }
+
+    pub fn with_origin(mut self, origin: Meters) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    pub fn with_count(mut self, count: u32) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    /// Index of the notch closest to `position`, limited to the existing notches.
+    pub fn nearest(&self, position: Meters) -> i64 {
+        let index = ((position.value() - self.origin.value()) / self.spacing.value()).round() as i64;
+        match self.count {
+            Some(0) => 0,
+            Some(count) => index.clamp(0, i64::from(count) - 1),
+            None => index,
+        }
+    }
+
+    /// Travel position of notch `index`.
+    #[inline]
+    pub fn position_of(&self, index: i64) -> Meters {
+        Meters(self.origin.value() + index as f32 * self.spacing.value())
+    }
+
+    /// Force along the travel at `position`; positive pushes toward higher positions.
+    pub fn force(&self, position: Meters) -> Newtons {
+        if self.count == Some(0) || self.width.value() <= 0.0 {
+            return Newtons::ZERO;
+        }
+        let offset = position.value() - self.position_of(self.nearest(position)).value();
+        if offset.abs() >= self.width.value() * 0.5 {
+            return Newtons::ZERO;
+        }
+        Newtons(-self.depth.value() * (TAU * offset / self.width.value()).sin())
+    }
+
+    /// Force on a device at `device` for a travel running through `start` along the
+    /// unit vector `axis`; only the component along the axis is affected.
+    pub fn force_along(&self, start: Vec3, axis: Vec3, device: Meters3) -> Newtons3 {
+        let position = Meters((device.value() - start).dot(axis));
+        Newtons3(axis * self.force(position).value())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/detent_tests.rs"]
+mod tests;
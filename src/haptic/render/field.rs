@@ -0,0 +1,240 @@
+//! Volumetric force fields that act on the tool while it is inside them.
+//!
+//! A [`ForceField`] pairs a [`Region`] (a box, a sphere or any signed distance
+//! function) with a [`FieldKind`]: a constant wind, a radial gravity well, a vortex
+//! around an axis, or a "mud" zone that damps motion. Fields fade in over a
+//! feather distance inside their boundary so entering one never kicks the device.
+//!
+//! Overlapping fields are resolved by priority in a [`ForceFieldSet`]. Fields of
+//! the same priority add up; a higher priority layer covers the ones below it in
+//! proportion to its fade, so a calm zone inside a storm blends in at its edge
+//! and fully replaces the wind within.
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::core::{smoothstep, Meters, Meters3, MetersPerSecond3, NewtonSecondsPerMeter, Newtons, Newtons3, Vec3};
+use crate::geometry::{Aabb, VoxelSdf};
+
+// ============================================================================
+// Regions
+// ============================================================================
+
+/// Volume a field occupies.
+#[derive(Clone)]
+pub enum Region {
+    Box(Aabb),
+    Sphere { center: Vec3, radius: Meters },
+    /// Signed distance function, negative inside.
+    Sdf(Arc<dyn Fn(Vec3) -> f32 + Send + Sync>),
+}
+
+impl Region {
+    /// Region bounded by any signed distance function (negative inside).
+    pub fn sdf(distance: impl Fn(Vec3) -> f32 + Send + Sync + 'static) -> Self {
+        Region::Sdf(Arc::new(distance))
+    }
+
+    /// Region enclosed by a baked mesh field.
+    pub fn voxels(sdf: VoxelSdf) -> Self {
+        Self::sdf(move |p| sdf.sample(p))
+    }
+
+    /// Signed distance from `p` to the boundary, negative inside.
+    pub fn distance(&self, p: Vec3) -> f32 {
+        match self {
+            Region::Box(aabb) => {
+                let q = (p - aabb.center()).abs() - aabb.half_extents();
+                q.max(Vec3::zero()).length() + q.x.max(q.y).max(q.z).min(0.0)
+            }
+            Region::Sphere { center, radius } => (p - *center).length() - radius.value(),
+            Region::Sdf(distance) => distance(p),
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, p: Vec3) -> bool {
+        self.distance(p) <= 0.0
+    }
+}
+
+impl fmt::Debug for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Region::Box(aabb) => f.debug_tuple("Box").field(aabb).finish(),
+            Region::Sphere { center, radius } => {
+                f.debug_struct("Sphere").field("center", center).field("radius", radius).finish()
+            }
+            Region::Sdf(_) => f.write_str("Sdf(..)"),
+        }
+    }
+}
+
+// ============================================================================
+// Fields
+// ============================================================================
+
+/// What a field does to the tool.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldKind {
+    /// The same force everywhere.
+    Wind(Newtons3),
+    /// Pull toward `center`, fading to zero within `core` of it.
+    Gravity { center: Vec3, strength: Newtons, core: Meters },
+    /// Push around the line through `center` along the unit vector `axis`,
+    /// counterclockwise looking down the axis.
+    Vortex { center: Vec3, axis: Vec3, strength: Newtons },
+    /// Resistance proportional to velocity.
+    Mud(NewtonSecondsPerMeter),
+}
+
+impl FieldKind {
+    /// Unweighted force at `position` moving at `velocity`.
+    pub fn force(&self, position: Vec3, velocity: Vec3) -> Vec3 {
+        match *self {
+            FieldKind::Wind(force) => force.value(),
+            FieldKind::Gravity { center, strength, core } => {
+                let offset = center - position;
+                let distance = offset.length();
+                if distance <= f32::EPSILON {
+                    return Vec3::zero();
+                }
+                let fade = if core.value() > 0.0 { (distance / core.value()).min(1.0) } else { 1.0 };
+                offset * (strength.value() * fade / distance)
+            }
+            FieldKind::Vortex { center, axis, strength } => {
+                let tangent = axis.cross(position - center);
+                tangent.try_normalize().map_or(Vec3::zero(), |t| t * strength.value())
+            }
+            FieldKind::Mud(damping) => velocity * -damping.value(),
+        }
+    }
+}
+
+/// A field kind acting within a region.
+#[derive(Debug, Clone)]
+pub struct ForceField {
+    pub region: Region,
+    pub kind: FieldKind,
+    /// Overlapping fields of higher priority cover lower ones.
+    pub priority: i32,
+    /// Depth inside the boundary over which the field fades in.
+    pub feather: Meters,
+}
+
+impl ForceField {
+    pub fn new(region: Region, kind: FieldKind) -> Self {
+        Self { region, kind, priority: 0, feather: Meters::ZERO }
+    }
+
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn with_feather(mut self, feather: Meters) -> Self {
+        self.feather = Meters(feather.value().max(0.0));
+        self
+    }
+
+    /// How strongly the field acts at `p`: 0 outside, 1 deeper than the feather.
+    pub fn weight(&self, p: Vec3) -> f32 {
+        let depth = -self.region.distance(p);
+        if depth < 0.0 {
+            0.0
+        } else if self.feather.value() > 0.0 {
+            smoothstep(0.0, self.feather.value(), depth)
+        } else {
+            1.0
+        }
+    }
+
+    /// Weighted force on the tool at `position` moving at `velocity`.
+    pub fn force(&self, position: Meters3, velocity: MetersPerSecond3) -> Newtons3 {
+        let weight = self.weight(position.value());
+        if weight <= 0.0 {
+            return Newtons3::ZERO;
+        }
+        Newtons3(self.kind.force(position.value(), velocity.value()) * weight)
+    }
+}
+
+// ============================================================================
+// Composition
+// ============================================================================
+
+/// Fields composed by priority.
+#[derive(Debug, Clone, Default)]
+pub struct ForceFieldSet {
+    /// Sorted by descending priority, insertion order within a priority.
+    fields: Vec<ForceField>,
+}
+
+impl ForceFieldSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, field: ForceField) {
+        let at = self.fields.partition_point(|f| f.priority >= field.priority);
+        self.fields.insert(at, field);
+    }
+
+    pub fn with(mut self, field: ForceField) -> Self {
+        self.add(field);
+        self
+    }
+
+    /// Fields from highest to lowest priority.
+    pub fn iter(&self) -> impl Iterator<Item = &ForceField> {
+        self.fields.iter()
+    }
+
+    /// Removes the fields `keep` rejects.
+    pub fn retain(&mut self, keep: impl FnMut(&ForceField) -> bool) {
+        self.fields.retain(keep);
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Fields acting at `position`.
+    pub fn active(&self, position: Meters3) -> impl Iterator<Item = &ForceField> {
+        self.fields.iter().filter(move |f| f.weight(position.value()) > 0.0)
+    }
+
+    /// Total force on the tool: each priority layer sums its fields and covers the
+    /// layers below by its largest weight at `position`.
+    pub fn force(&self, position: Meters3, velocity: MetersPerSecond3) -> Newtons3 {
+        let mut total = Vec3::zero();
+        let mut uncovered = 1.0;
+        for layer in self.fields.chunk_by(|a, b| a.priority == b.priority) {
+            let mut sum = Vec3::zero();
+            let mut coverage: f32 = 0.0;
+            for field in layer {
+                let weight = field.weight(position.value());
+                if weight > 0.0 {
+                    sum += field.kind.force(position.value(), velocity.value()) * weight;
+                    coverage = coverage.max(weight);
+                }
+            }
+            total += sum * uncovered;
+            uncovered *= 1.0 - coverage;
+            if uncovered <= 0.0 {
+                break;
+            }
+        }
+        Newtons3(total)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/field_tests.rs"]
+mod tests;
@@ -0,0 +1,100 @@
+//! Coulomb and viscous friction for a sliding contact.
+//!
+//! Friction is rendered with a stick point: on touching down, an anchor is dropped
+//! at the contact, and a tangential spring pulls the device back toward it. While
+//! that spring force stays within the static cone (`static_friction * normal force`)
+//! the contact sticks. Once it exceeds the cone the contact slips, and the anchor is
+//! dragged along behind the contact so the force drops to the kinetic level. A
+//! viscous term opposes the tangential velocity on top. The anchor is a world point,
+//! but its offset is re-projected onto the current tangent plane every tick, so the
+//! model follows curved surfaces.
+
+use super::contact::{ContactMaterial, ContactState};
+use crate::core::{MetersPerSecond3, Newtons, Newtons3, Vec3};
+
+/// Whether the contact is held or sliding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SlipState {
+    /// Not touching.
+    Free,
+    Stick,
+    Slip,
+}
+
+/// Friction state of one contact.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Friction {
+    anchor: Option<Vec3>,
+    state: SlipState,
+}
+
+impl Default for Friction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Friction {
+    pub const fn new() -> Self {
+        Self { anchor: None, state: SlipState::Free }
+    }
+
+    #[inline]
+    pub fn state(&self) -> SlipState {
+        self.state
+    }
+
+    /// Stick point, while in contact.
+    #[inline]
+    pub fn anchor(&self) -> Option<Vec3> {
+        self.anchor
+    }
+
+    /// Ends the contact; the next one drops a fresh anchor.
+    pub fn release(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Lateral force on the device for `contact` pressing with `normal_force`.
+    /// `velocity` is the device velocity; only its tangential part is used.
+    pub fn update(
+        &mut self,
+        contact: &ContactState,
+        normal_force: Newtons,
+        velocity: MetersPerSecond3,
+        material: &ContactMaterial,
+    ) -> Newtons3 {
+        let normal = contact.normal;
+        let pressing = normal_force.value().max(0.0);
+        if pressing == 0.0 {
+            self.release();
+            return Newtons3::ZERO;
+        }
+        let anchor = *self.anchor.get_or_insert(contact.point);
+        let tangential = |v: Vec3| v - normal * v.dot(normal);
+
+        // Spring from the contact point back to the anchor, in the tangent plane
+        let k = material.stiffness.value();
+        let offset = tangential(contact.point - anchor);
+        let mut spring = -offset * k;
+        let limit = material.static_friction * pressing;
+        if spring.length() > limit {
+            let kinetic = material.kinetic_friction.min(material.static_friction) * pressing;
+            let direction = offset.try_normalize().unwrap_or(Vec3::zero());
+            // Drag the anchor so the spring holds exactly the kinetic force
+            let stretch = if k > 0.0 { kinetic / k } else { 0.0 };
+            self.anchor = Some(contact.point - direction * stretch);
+            spring = -direction * kinetic;
+            self.state = SlipState::Slip;
+        } else {
+            self.state = SlipState::Stick;
+        }
+
+        let viscous = -tangential(velocity.0) * material.viscous_friction.value();
+        Newtons3(spring + viscous)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/friction_tests.rs"]
+mod tests;
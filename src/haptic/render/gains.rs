@@ -0,0 +1,237 @@
+//! Gain scheduling over the device workspace.
+//!
+//! Device dynamics degrade toward the edges of the workspace (linkage singularities,
+//! reduced motor leverage), so the same stiffness that renders a crisp wall at the
+//! center can buzz near the limits. A [`GainSchedule`] describes rendering gain
+//! multipliers as a smooth field over the workspace [`Obb`]: an edge falloff, optional
+//! local zones, and an optional sampled grid, all combined multiplicatively.
+
+use std::ops::{Mul, MulAssign};
+
+use crate::core::{smootherstep, NewtonSecondsPerMeter, NewtonsPerMeter, Vec3};
+use crate::geometry::Obb;
+
+// ============================================================================
+// Gains
+// ============================================================================
+
+/// Multipliers applied to the rendering parameters of a contact.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gains {
+    pub stiffness: f32,
+    pub damping: f32,
+    pub texture: f32,
+}
+
+impl Gains {
+    pub const UNITY: Self = Self::uniform(1.0);
+
+    #[inline]
+    pub const fn new(stiffness: f32, damping: f32, texture: f32) -> Self {
+        Self { stiffness, damping, texture }
+    }
+
+    /// Same multiplier for every parameter.
+    #[inline]
+    pub const fn uniform(gain: f32) -> Self {
+        Self::new(gain, gain, gain)
+    }
+
+    #[inline]
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        Self::new(
+            self.stiffness + (other.stiffness - self.stiffness) * t,
+            self.damping + (other.damping - self.damping) * t,
+            self.texture + (other.texture - self.texture) * t,
+        )
+    }
+
+    #[inline]
+    pub fn apply_stiffness(self, stiffness: NewtonsPerMeter) -> NewtonsPerMeter {
+        stiffness * self.stiffness
+    }
+
+    #[inline]
+    pub fn apply_damping(self, damping: NewtonSecondsPerMeter) -> NewtonSecondsPerMeter {
+        damping * self.damping
+    }
+
+    /// Scales a texture amplitude in whatever unit the caller uses.
+    #[inline]
+    pub fn apply_texture(self, amplitude: f32) -> f32 {
+        amplitude * self.texture
+    }
+}
+
+impl Default for Gains {
+    #[inline]
+    fn default() -> Self {
+        Self::UNITY
+    }
+}
+
+impl Mul for Gains {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(self.stiffness * rhs.stiffness, self.damping * rhs.damping, self.texture * rhs.texture)
+    }
+}
+
+impl MulAssign for Gains {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+// ============================================================================
+// Zones
+// ============================================================================
+
+/// Local region with its own gains, blended out over `falloff` meters outside the box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GainZone {
+    pub region: Obb,
+    pub gains: Gains,
+    pub falloff: f32,
+}
+
+impl GainZone {
+    #[inline]
+    pub fn new(region: Obb, gains: Gains, falloff: f32) -> Self {
+        Self { region, gains, falloff: falloff.max(0.0) }
+    }
+
+    /// Zone gains inside the box, unity beyond the falloff, smooth in between.
+    pub fn gains_at(&self, p: Vec3) -> Gains {
+        let distance = self.region.signed_distance(p);
+        let outside = smootherstep(0.0, self.falloff, distance);
+        self.gains.lerp(Gains::UNITY, outside)
+    }
+}
+
+// ============================================================================
+// Sampled Grid
+// ============================================================================
+
+/// Gains sampled on a regular grid spanning the workspace, interpolated trilinearly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GainGrid {
+    dims: [usize; 3],
+    values: Vec<Gains>,
+}
+
+impl GainGrid {
+    /// Samples `f` at every grid node; `f` receives normalized workspace coordinates in [0, 1]^3.
+    /// Each dimension is raised to at least 2 so the grid spans the whole box.
+    pub fn from_fn(dims: [usize; 3], mut f: impl FnMut(Vec3) -> Gains) -> Self {
+        let dims = dims.map(|d| d.max(2));
+        let step = |i: usize, axis: usize| i as f32 / (dims[axis] - 1) as f32;
+        let mut values = Vec::with_capacity(dims[0] * dims[1] * dims[2]);
+        for z in 0..dims[2] {
+            for y in 0..dims[1] {
+                for x in 0..dims[0] {
+                    values.push(f(Vec3::new(step(x, 0), step(y, 1), step(z, 2))));
+                }
+            }
+        }
+        Self { dims, values }
+    }
+
+    #[inline]
+    pub fn dims(&self) -> [usize; 3] {
+        self.dims
+    }
+
+    /// Trilinear sample at normalized coordinates, clamped to the grid.
+    pub fn sample(&self, normalized: Vec3) -> Gains {
+        let coords = [normalized.x, normalized.y, normalized.z];
+        let mut base = [0usize; 3];
+        let mut frac = [0.0f32; 3];
+        for axis in [0, 1, 2] {
+            let last = self.dims[axis] - 1;
+            let t = coords[axis].clamp(0.0, 1.0) * last as f32;
+            base[axis] = (t.floor() as usize).min(last - 1);
+            frac[axis] = t - base[axis] as f32;
+        }
+
+        let at = |x: usize, y: usize, z: usize| self.values[(z * self.dims[1] + y) * self.dims[0] + x];
+        let [x, y, z] = base;
+        let [fx, fy, fz] = frac;
+        let plane = |z: usize| {
+            let lower = at(x, y, z).lerp(at(x + 1, y, z), fx);
+            let upper = at(x, y + 1, z).lerp(at(x + 1, y + 1, z), fx);
+            lower.lerp(upper, fy)
+        };
+        plane(z).lerp(plane(z + 1), fz)
+    }
+}
+
+// ============================================================================
+// Schedule
+// ============================================================================
+
+/// Rendering gains as a smooth field over the workspace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GainSchedule {
+    workspace: Obb,
+    edge: Option<(f32, Gains)>,
+    zones: Vec<GainZone>,
+    grid: Option<GainGrid>,
+}
+
+impl GainSchedule {
+    /// Unity gains everywhere until configured.
+    pub fn new(workspace: Obb) -> Self {
+        Self { workspace, edge: None, zones: Vec::new(), grid: None }
+    }
+
+    /// Blends toward `at_edge` over the last `width` meters inside the workspace boundary.
+    /// Points outside the workspace get `at_edge`.
+    pub fn edge_falloff(mut self, width: f32, at_edge: Gains) -> Self {
+        self.edge = Some((width.max(0.0), at_edge));
+        self
+    }
+
+    pub fn zone(mut self, zone: GainZone) -> Self {
+        self.zones.push(zone);
+        self
+    }
+
+    pub fn grid(mut self, grid: GainGrid) -> Self {
+        self.grid = Some(grid);
+        self
+    }
+
+    #[inline]
+    pub fn workspace(&self) -> &Obb {
+        &self.workspace
+    }
+
+    #[inline]
+    pub fn zones(&self) -> &[GainZone] {
+        &self.zones
+    }
+
+    /// Combined gains at a device-space position.
+    pub fn gains_at(&self, p: Vec3) -> Gains {
+        let mut gains = Gains::UNITY;
+        if let Some(grid) = &self.grid {
+            gains *= grid.sample(self.workspace.normalized(p));
+        }
+        if let Some((width, at_edge)) = self.edge {
+            let depth = -self.workspace.signed_distance(p);
+            gains *= at_edge.lerp(Gains::UNITY, smootherstep(0.0, width, depth));
+        }
+        for zone in &self.zones {
+            gains *= zone.gains_at(p);
+        }
+        gains
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/gains_tests.rs"]
+mod tests;
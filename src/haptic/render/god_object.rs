@@ -0,0 +1,388 @@
+//! Constraint-based proxy ("god-object") force rendering.
+//!
+//! Penalty rendering pushes the device out along the nearest surface normal, which
+//! breaks down as soon as the device is deep inside a shape: thin objects are
+//! pushed through and the force flips at the medial axis. The god-object
+//! (Zilles and Salisbury, 1995) instead keeps a proxy point that follows the
+//! device but is never allowed through a surface. The proxy moves toward the
+//! device until it meets a surface, then slides along the tangent planes of up
+//! to three contacts to the point nearest the device. The force is a spring
+//! between the two: `stiffness * (proxy - device)`.
+//!
+//! Surfaces are any [`ClosestPoint`] shapes, passed per update so the scene can
+//! change between ticks. [`GodObject::update`] renders every surface with the same
+//! spring; [`GodObject::render`] hands each contact to a [`ContactModel`] with the
+//! material of its surface instead, and adds that surface's friction;
+//! [`GodObject::render_materials`] does the same with [`HapticMaterial`]s, whose
+//! texture also modulates the normal force, and [`GodObject::render_lod`] scales
+//! that texture by a [`LodLevel`] for fast-moving tools. Rendered contacts go
+//! through the model's engagement dead-band and ramp-in, timed by the `dt` each
+//! render is given, so replaying recorded ticks renders the same forces.
+
+use super::contact::{ContactEngagement, ContactMaterial, ContactModel, ContactState};
+use super::friction::{Friction, SlipState};
+use super::lod::LodLevel;
+use super::material::{HapticMaterial, Texture};
+use crate::core::{Meters3, MetersPerSecond3, Newtons3, NewtonsPerMeter, Seconds, Vec3, EPSILON};
+use crate::geometry::{ClosestPoint, Plane, SurfacePoint};
+
+/// Distance the proxy keeps from a surface it rests on.
+const SKIN: f32 = 1e-5;
+
+/// Contact planes from one update; a point is fixed once three are active.
+const MAX_CONTACTS: usize = 3;
+
+/// Longest step taken past a nearby surface the proxy is not moving toward. Bounds
+/// how thin a concave feature can be before sliding can skip over it.
+const SLIDE_STEP: f32 = 5e-4;
+
+/// Advancement steps per segment before the proxy stops short for this tick.
+const MAX_STEPS: usize = 64;
+
+// ============================================================================
+// God-Object
+// ============================================================================
+
+/// A surface constraining the proxy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact {
+    /// Index of the surface among those passed to the update.
+    pub surface: usize,
+    pub point: SurfacePoint,
+}
+
+/// Proxy point constrained to stay outside the rendered surfaces.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GodObject {
+    pub stiffness: NewtonsPerMeter,
+    proxy: Vec3,
+    contacts: Vec<Contact>,
+    /// Surfaces the device has popped through, ignored until it leaves them.
+    popped: Vec<usize>,
+    /// Friction state per touched surface, carried between renders.
+    friction: Vec<(usize, Friction)>,
+    /// Engagement per touched surface, carried between renders.
+    engagement: Vec<(usize, ContactEngagement)>,
+    /// Render time, the sum of every render's `dt`.
+    time_us: u64,
+    initialized: bool,
+}
+
+impl GodObject {
+    pub fn new(stiffness: NewtonsPerMeter) -> Self {
+        Self {
+            stiffness,
+            proxy: Vec3::zero(),
+            contacts: Vec::with_capacity(MAX_CONTACTS),
+            popped: Vec::new(),
+            friction: Vec::new(),
+            engagement: Vec::new(),
+            time_us: 0,
+            initialized: false,
+        }
+    }
+
+    /// Places the proxy at `position`, dropping all contacts. Until the first
+    /// update or reset, the proxy starts wherever the device first reports.
+    pub fn reset(&mut self, position: Meters3) {
+        self.proxy = position.0;
+        self.contacts.clear();
+        self.popped.clear();
+        self.friction.clear();
+        self.engagement.clear();
+        self.initialized = true;
+    }
+
+    #[inline]
+    pub fn proxy(&self) -> Meters3 {
+        Meters3(self.proxy)
+    }
+
+    /// Surfaces constraining the proxy after the last update.
+    #[inline]
+    pub fn contacts(&self) -> &[Contact] {
+        &self.contacts
+    }
+
+    /// Surfaces currently popped through.
+    #[inline]
+    pub fn popped(&self) -> &[usize] {
+        &self.popped
+    }
+
+    /// Whether the contact with `surface` sticks or slips, as of the last render.
+    pub fn slip_state(&self, surface: usize) -> SlipState {
+        self.friction.iter().find(|(s, _)| *s == surface).map_or(SlipState::Free, |(_, f)| f.state())
+    }
+
+    #[inline]
+    pub fn in_contact(&self) -> bool {
+        !self.contacts.is_empty()
+    }
+
+    /// State of each current contact for a device at `device` moving at `velocity`,
+    /// as handed to contact models and force shaders.
+    pub fn contact_states(
+        &self,
+        device: Meters3,
+        velocity: MetersPerSecond3,
+    ) -> impl Iterator<Item = ContactState> + '_ {
+        let proxy = self.proxy;
+        self.contacts.iter().map(move |c| ContactState::new(c.surface, proxy, c.point.normal, device.0, velocity))
+    }
+
+    /// Spring force pulling the device toward the proxy.
+    #[inline]
+    pub fn force(&self, device: Meters3) -> Newtons3 {
+        self.stiffness * (self.proxy() - device)
+    }
+
+    /// Moves the proxy toward `device` against `surfaces` and returns the force.
+    ///
+    /// A proxy found inside a surface (because the surface moved onto it) is first
+    /// pushed out to that surface's nearest point.
+    pub fn update(&mut self, device: Meters3, surfaces: &[&dyn ClosestPoint]) -> Newtons3 {
+        self.solve(device.0, surfaces);
+        self.force(device)
+    }
+
+    /// Like [`update`](Self::update), but each contact pushes with `model` and the
+    /// material of its surface (`materials[i]` for `surfaces[i]`, the default
+    /// material where missing), plus that material's friction. Contacts pressed past
+    /// the pop-through depth release the proxy; that surface is then ignored until
+    /// the device leaves it. `dt` is the time since the previous render, which new
+    /// contacts ramp in over.
+    pub fn render(
+        &mut self,
+        device: Meters3,
+        velocity: MetersPerSecond3,
+        surfaces: &[&dyn ClosestPoint],
+        materials: &[ContactMaterial],
+        model: &ContactModel,
+        dt: Seconds,
+    ) -> Newtons3 {
+        self.render_with(device, velocity, surfaces, model, dt, |surface| {
+            (materials.get(surface).copied().unwrap_or_default(), Texture::NONE)
+        })
+    }
+
+    /// Like [`render`](Self::render) with authored materials, whose texture also
+    /// modulates each contact's normal force at the proxy.
+    pub fn render_materials(
+        &mut self,
+        device: Meters3,
+        velocity: MetersPerSecond3,
+        surfaces: &[&dyn ClosestPoint],
+        materials: &[HapticMaterial],
+        model: &ContactModel,
+        dt: Seconds,
+    ) -> Newtons3 {
+        self.render_lod(device, velocity, surfaces, materials, model, LodLevel::FULL, dt)
+    }
+
+    /// Like [`render_materials`](Self::render_materials) with texture detail
+    /// reduced to `lod`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn render_lod(
+        &mut self,
+        device: Meters3,
+        velocity: MetersPerSecond3,
+        surfaces: &[&dyn ClosestPoint],
+        materials: &[HapticMaterial],
+        model: &ContactModel,
+        lod: LodLevel,
+        dt: Seconds,
+    ) -> Newtons3 {
+        self.render_with(device, velocity, surfaces, model, dt, |surface| {
+            let material = materials.get(surface).copied().unwrap_or_default();
+            (material.into(), lod.texture(material.texture))
+        })
+    }
+
+    fn render_with(
+        &mut self,
+        device: Meters3,
+        velocity: MetersPerSecond3,
+        surfaces: &[&dyn ClosestPoint],
+        model: &ContactModel,
+        dt: Seconds,
+        material: impl Fn(usize) -> (ContactMaterial, Texture),
+    ) -> Newtons3 {
+        self.solve(device.0, surfaces);
+        let state = |proxy: Vec3, c: &Contact| ContactState::new(c.surface, proxy, c.point.normal, device.0, velocity);
+        let proxy = self.proxy;
+        let popped: Vec<usize> = self
+            .contacts
+            .iter()
+            .filter(|c| model.pops_through(&state(proxy, c)))
+            .map(|c| c.surface)
+            .collect();
+        if !popped.is_empty() {
+            self.popped.extend(popped);
+            self.solve(device.0, surfaces);
+        }
+
+        let contacts = &self.contacts;
+        self.friction.retain(|(surface, _)| contacts.iter().any(|c| c.surface == *surface));
+        self.engagement.retain(|(surface, _)| contacts.iter().any(|c| c.surface == *surface));
+        self.time_us += (dt.value().max(0.0) * 1e6).round() as u64;
+        let now_us = self.time_us;
+        let mut force = Newtons3::ZERO;
+        for contact in contacts {
+            let (material, texture) = material(contact.surface);
+            let state = state(self.proxy, contact);
+            let gain = entry(&mut self.engagement, contact.surface).update(model, &state, now_us);
+            let normal = model.force(&state, &material) * (texture.modulation(self.proxy) * gain);
+            let friction = entry(&mut self.friction, contact.surface);
+            force += normal + friction.update(&state, normal.length(), velocity, &material);
+        }
+        model.saturate(force)
+    }
+
+    /// Moves the proxy as far toward `goal` as the surfaces allow.
+    fn solve(&mut self, goal: Vec3, surfaces: &[&dyn ClosestPoint]) {
+        if !self.initialized {
+            self.reset(Meters3(goal));
+        }
+        self.popped.retain(|&i| surfaces.get(i).is_some_and(|s| s.closest_point(goal).signed_distance(goal) < 0.0));
+        for (i, surface) in surfaces.iter().enumerate() {
+            let sp = surface.closest_point(self.proxy);
+            if !self.popped.contains(&i) && !sp.is_none() && sp.signed_distance(self.proxy) < 0.0 {
+                self.proxy = sp.point + sp.normal * SKIN;
+            }
+        }
+
+        self.contacts.clear();
+        let mut planes = [Plane { normal: Vec3::zero(), offset: 0.0 }; MAX_CONTACTS];
+        for _ in 0..=MAX_CONTACTS {
+            let active = &planes[..self.contacts.len()];
+            let Some(target) = constrain(goal, active) else {
+                break;
+            };
+            let (reached, hit) = advance(self.proxy, target, surfaces, &self.popped);
+            self.proxy = reached;
+            match hit {
+                Some(contact) if self.contacts.len() < MAX_CONTACTS => {
+                    let sp = contact.point;
+                    // Offset by the skin so sliding targets stay level with the resting proxy
+                    planes[self.contacts.len()] = Plane::from_point_normal(sp.point + sp.normal * SKIN, sp.normal);
+                    self.contacts.push(contact);
+                }
+                _ => break,
+            }
+        }
+        // Contacts met early on the way may have been slid off since
+        let proxy = self.proxy;
+        self.contacts.retain(|c| c.point.signed_distance(proxy) <= 2.0 * SKIN);
+    }
+}
+
+/// State kept for `surface`, created on first touch.
+fn entry<T: Default>(states: &mut Vec<(usize, T)>, surface: usize) -> &mut T {
+    let index = match states.iter().position(|(s, _)| *s == surface) {
+        Some(index) => index,
+        None => {
+            states.push((surface, T::default()));
+            states.len() - 1
+        }
+    };
+    &mut states[index].1
+}
+
+// ============================================================================
+// Solver
+// ============================================================================
+
+/// Point nearest `target` on the free side of every plane (an active-set search
+/// over at most three planes). None if the planes leave no such point nearby.
+fn constrain(target: Vec3, planes: &[Plane]) -> Option<Vec3> {
+    let feasible = |x: Vec3| planes.iter().all(|p| p.signed_distance(x) >= -0.5 * SKIN);
+    if feasible(target) {
+        return Some(target);
+    }
+
+    let mut best: Option<Vec3> = None;
+    let mut consider = |x: Vec3| {
+        if feasible(x) && best.is_none_or(|b| x.distance_squared_to(target) < b.distance_squared_to(target)) {
+            best = Some(x);
+        }
+    };
+    for plane in planes {
+        consider(plane.project(target));
+    }
+    for (i, a) in planes.iter().enumerate() {
+        for b in &planes[i + 1..] {
+            if let Some(x) = project_onto_line(target, a, b) {
+                consider(x);
+            }
+        }
+    }
+    if let [a, b, c] = planes {
+        if let Some(x) = intersect_three(a, b, c) {
+            consider(x);
+        }
+    }
+    best
+}
+
+/// Projection of `p` onto the intersection line of two planes.
+fn project_onto_line(p: Vec3, a: &Plane, b: &Plane) -> Option<Vec3> {
+    let c = a.normal.dot(b.normal);
+    let det = 1.0 - c * c;
+    if det < EPSILON {
+        return None;
+    }
+    let (da, db) = (a.signed_distance(p), b.signed_distance(p));
+    let ka = (da - c * db) / det;
+    let kb = (db - c * da) / det;
+    Some(p - a.normal * ka - b.normal * kb)
+}
+
+/// The point shared by three planes.
+fn intersect_three(a: &Plane, b: &Plane, c: &Plane) -> Option<Vec3> {
+    let bc = b.normal.cross(c.normal);
+    let det = a.normal.dot(bc);
+    if det.abs() < EPSILON {
+        return None;
+    }
+    Some((bc * a.offset + c.normal.cross(a.normal) * b.offset + a.normal.cross(b.normal) * c.offset) / det)
+}
+
+/// Moves from `from` toward `to` by conservative advancement, stopping at the
+/// first surface approached head-on. Returns where it stopped and what it hit.
+fn advance(from: Vec3, to: Vec3, surfaces: &[&dyn ClosestPoint], skip: &[usize]) -> (Vec3, Option<Contact>) {
+    let Some(direction) = (to - from).try_normalize() else {
+        return (to, None);
+    };
+    let length = from.distance_to(to);
+    let mut travelled = 0.0;
+    for _ in 0..MAX_STEPS {
+        let x = from + direction * travelled;
+        let mut step = length - travelled;
+        for (i, surface) in surfaces.iter().enumerate() {
+            if skip.contains(&i) {
+                continue;
+            }
+            let sp = surface.closest_point(x);
+            if sp.is_none() {
+                continue;
+            }
+            let distance = sp.signed_distance(x);
+            let approaching = direction.dot(sp.normal) < -EPSILON;
+            if approaching && distance <= SKIN {
+                return (x, Some(Contact { surface: i, point: sp }));
+            }
+            let bound = if approaching { distance - 0.5 * SKIN } else { distance.max(SLIDE_STEP) };
+            step = step.min(bound.max(0.5 * SKIN));
+        }
+        travelled += step;
+        if travelled >= length {
+            return (to, None);
+        }
+    }
+    (from + direction * travelled, None)
+}
+
+#[cfg(test)]
+#[path = "tests/god_object_tests.rs"]
+mod tests;
@@ -0,0 +1,169 @@
+//! Velocity-adaptive level of detail for force rendering.
+//!
+//! Fine surface detail is only perceptible while the tool moves slowly; sweeping
+//! across a surface at speed, a texture's grain blurs into vibration that costs
+//! servo time and mostly adds noise. [`HapticLod`] tracks the (smoothed) tool
+//! speed and yields a [`LodLevel`] for each tick: below the slow speed the scene
+//! renders at full fidelity, above the fast speed texture amplitude drops to a
+//! floor, texture lookups move to coarser mip levels, and the output force is
+//! low-pass filtered. Speed is smoothed so the level does not flicker with
+//! device noise, and detail comes back as soon as the user slows down to explore.
+
+use super::material::Texture;
+use crate::core::constants::{
+    DEFAULT_LOD_FAST_CUTOFF_HZ, DEFAULT_LOD_FAST_M_PER_S, DEFAULT_LOD_MIN_DETAIL, DEFAULT_LOD_RESPONSE_S,
+    DEFAULT_LOD_SLOW_M_PER_S, LOD_FAST_CUTOFF_HZ, LOD_FAST_M_PER_S, LOD_MIN_DETAIL, LOD_RESPONSE_S, LOD_SLOW_M_PER_S,
+};
+use crate::core::{smoothstep, Config, Hertz, Meters, MetersPerSecond, MetersPerSecond3, Newtons3, Seconds, Vec3};
+
+/// Detail and filtering to render one tick with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LodLevel {
+    /// Fraction of texture detail rendered, in 0..=1.
+    pub detail: f32,
+    /// Cutoff of the output low-pass; infinite when unfiltered.
+    pub cutoff: Hertz,
+}
+
+impl LodLevel {
+    /// Everything rendered, nothing filtered.
+    pub const FULL: Self = Self { detail: 1.0, cutoff: Hertz(f32::INFINITY) };
+
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.detail >= 1.0 && self.cutoff.value().is_infinite()
+    }
+
+    /// `texture` with its amplitude scaled by the detail.
+    pub fn texture(&self, texture: Texture) -> Texture {
+        Texture { amplitude: texture.amplitude * self.detail, ..texture }
+    }
+
+    /// Contact footprint to sample mip-mapped textures with, widened as detail drops.
+    pub fn footprint(&self, base: Meters) -> Meters {
+        Meters(base.value() / self.detail.max(f32::EPSILON))
+    }
+}
+
+impl Default for LodLevel {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+/// Speeds and limits of a [`HapticLod`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LodConfig {
+    /// At or below this speed the scene renders at full fidelity.
+    pub slow: MetersPerSecond,
+    /// At or above this speed detail is at its floor.
+    pub fast: MetersPerSecond,
+    /// Fraction of detail kept at high speed.
+    pub min_detail: f32,
+    /// Output low-pass cutoff at high speed; it rises without bound toward the slow speed.
+    pub fast_cutoff: Hertz,
+    /// Time constant of the speed smoothing.
+    pub response: Seconds,
+}
+
+impl LodConfig {
+    /// Defaults with the `lod.*` keys of `config`; negative and non-finite values
+    /// are ignored.
+    pub fn from_config(config: &Config) -> Self {
+        let get = |key: &str| config.get_tuning(key);
+        let defaults = Self::default();
+        Self {
+            slow: get(LOD_SLOW_M_PER_S).map_or(defaults.slow, MetersPerSecond),
+            fast: get(LOD_FAST_M_PER_S).map_or(defaults.fast, MetersPerSecond),
+            min_detail: get(LOD_MIN_DETAIL).unwrap_or(defaults.min_detail),
+            fast_cutoff: get(LOD_FAST_CUTOFF_HZ).map_or(defaults.fast_cutoff, Hertz),
+            response: get(LOD_RESPONSE_S).map_or(defaults.response, Seconds),
+        }
+    }
+}
+
+impl Default for LodConfig {
+    fn default() -> Self {
+        Self {
+            slow: MetersPerSecond(DEFAULT_LOD_SLOW_M_PER_S as f32),
+            fast: MetersPerSecond(DEFAULT_LOD_FAST_M_PER_S as f32),
+            min_detail: DEFAULT_LOD_MIN_DETAIL as f32,
+            fast_cutoff: Hertz(DEFAULT_LOD_FAST_CUTOFF_HZ as f32),
+            response: Seconds(DEFAULT_LOD_RESPONSE_S as f32),
+        }
+    }
+}
+
+/// Per-tick LOD state: smoothed speed and the output filter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HapticLod {
+    pub config: LodConfig,
+    speed: f32,
+    level: LodLevel,
+    filtered: Vec3,
+}
+
+impl HapticLod {
+    pub fn new(config: LodConfig) -> Self {
+        Self { config, speed: 0.0, level: LodLevel::FULL, filtered: Vec3::zero() }
+    }
+
+    /// Smoothed tool speed.
+    #[inline]
+    pub fn speed(&self) -> MetersPerSecond {
+        MetersPerSecond(self.speed)
+    }
+
+    /// Level chosen by the last update.
+    #[inline]
+    pub fn level(&self) -> LodLevel {
+        self.level
+    }
+
+    /// Returns to full fidelity, e.g. after the device was re-homed.
+    pub fn reset(&mut self) {
+        self.speed = 0.0;
+        self.level = LodLevel::FULL;
+        self.filtered = Vec3::zero();
+    }
+
+    /// Feeds the tool velocity for a tick of `dt` and picks the level for it.
+    pub fn update(&mut self, velocity: MetersPerSecond3, dt: Seconds) -> LodLevel {
+        let speed = velocity.length().value();
+        let response = self.config.response.value();
+        let alpha = if response > 0.0 { 1.0 - (-dt.value().max(0.0) / response).exp() } else { 1.0 };
+        if speed.is_finite() {
+            self.speed += (speed - self.speed) * alpha;
+        }
+        let t = smoothstep(self.config.slow.value(), self.config.fast.value(), self.speed);
+        let min_detail = self.config.min_detail.clamp(0.0, 1.0);
+        self.level = if t <= 0.0 {
+            LodLevel::FULL
+        } else {
+            LodLevel { detail: 1.0 - t * (1.0 - min_detail), cutoff: Hertz(self.config.fast_cutoff.value() / t) }
+        };
+        self.level
+    }
+
+    /// Low-passes `force` at the current level's cutoff.
+    pub fn filter(&mut self, force: Newtons3, dt: Seconds) -> Newtons3 {
+        let cutoff = self.level.cutoff.value();
+        if !cutoff.is_finite() {
+            self.filtered = force.value();
+            return force;
+        }
+        let alpha = 1.0 - (-std::f32::consts::TAU * cutoff * dt.value().max(0.0)).exp();
+        self.filtered += (force.value() - self.filtered) * alpha;
+        Newtons3(self.filtered)
+    }
+}
+
+impl Default for HapticLod {
+    fn default() -> Self {
+        Self::new(LodConfig::default())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/lod_tests.rs"]
+mod tests;
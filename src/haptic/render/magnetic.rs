@@ -0,0 +1,96 @@
+//! Magnetic wells that pull the device onto snap targets.
+//!
+//! A [`MagneticWell`] attracts the device toward a target point once it comes
+//! within the capture radius. The pull grows smoothly from zero at the capture
+//! radius to full strength at the core radius, then falls linearly to zero at the
+//! target so the device settles there instead of chattering around it. Damping
+//! inside the well keeps the snap from overshooting.
+//!
+//! Buttons, grid points and connector endpoints each get a well; when several
+//! overlap, [`MagneticWell::strongest`] lets only the one pulling hardest act, so
+//! neighboring targets never cancel out.
+
+use crate::core::{smootherstep, Meters, Meters3, MetersPerSecond3, NewtonSecondsPerMeter, Newtons, Newtons3, Vec3};
+
+/// Default core radius as a fraction of the capture radius.
+const CORE_FRACTION: f32 = 0.25;
+
+/// Attraction toward a point within a capture radius.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MagneticWell {
+    pub target: Vec3,
+    /// Distance from the target at which the pull starts.
+    pub radius: Meters,
+    /// Distance within which the pull fades toward zero at the target.
+    pub core: Meters,
+    /// Peak pull, reached at the core radius.
+    pub strength: Newtons,
+    /// Velocity damping applied while captured.
+    pub damping: NewtonSecondsPerMeter,
+}
+
+impl MagneticWell {
+    pub fn new(target: Vec3, radius: Meters, strength: Newtons) -> Self {
+        let radius = Meters(radius.value().max(0.0));
+        Self {
+            target,
+            radius,
+            core: radius * CORE_FRACTION,
+            strength,
+            damping: NewtonSecondsPerMeter::ZERO,
+        }
+    }
+
+    pub fn with_core(mut self, core: Meters) -> Self {
+        self.core = Meters(core.value().clamp(0.0, self.radius.value()));
+        self
+    }
+
+    pub fn with_damping(mut self, damping: NewtonSecondsPerMeter) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Whether `device` is within the capture radius.
+    #[inline]
+    pub fn captures(&self, device: Meters3) -> bool {
+        (self.target - device.value()).length() < self.radius.value()
+    }
+
+    /// Magnitude of the pull at `distance` from the target.
+    pub fn pull(&self, distance: Meters) -> Newtons {
+        let (d, core, radius) = (distance.value(), self.core.value(), self.radius.value());
+        if d >= radius {
+            return Newtons::ZERO;
+        }
+        let center = if core > 0.0 { (d / core).min(1.0) } else { 1.0 };
+        let edge = 1.0 - smootherstep(core, radius, d);
+        self.strength * (center * edge)
+    }
+
+    /// Force on a device at `device` moving at `velocity`; zero outside the well.
+    pub fn force(&self, device: Meters3, velocity: MetersPerSecond3) -> Newtons3 {
+        if !self.captures(device) {
+            return Newtons3::ZERO;
+        }
+        let offset = self.target - device.value();
+        let distance = offset.length();
+        let pull = self.pull(Meters(distance)).value();
+        let pull = if distance > 0.0 { offset * (pull / distance) } else { Vec3::zero() };
+        Newtons3(pull - velocity.value() * self.damping.value())
+    }
+
+    /// The well pulling hardest on `device`, if any captures it.
+    pub fn strongest(wells: &[MagneticWell], device: Meters3) -> Option<&MagneticWell> {
+        wells
+            .iter()
+            .filter(|w| w.captures(device))
+            .map(|w| (w, w.pull(Meters((w.target - device.value()).length())).value()))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(w, _)| w)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/magnetic_tests.rs"]
+mod tests;
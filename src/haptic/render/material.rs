@@ -0,0 +1,204 @@
+//! Haptic materials: how a surface feels, authored like a render material.
+//!
+//! A [`HapticMaterial`] bundles compliance, friction and texture. Materials live in
+//! a [`MaterialLibrary`] under a name and are assigned to scene nodes (a widget or
+//! a mesh) by id; nodes without an assignment fall back to their own
+//! [`NodeHaptics`], so existing scenes keep feeling the same. The contact resolver
+//! looks materials up per touched surface (see `GodObject::render_materials`).
+
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+
+use super::contact::ContactMaterial;
+use crate::core::{Meters, NewtonSecondsPerMeter, NewtonsPerMeter, Vec3};
+use crate::scene::{NodeHaptics, NodeId, Scene};
+
+// ============================================================================
+// Texture
+// ============================================================================
+
+/// A periodic surface grain that modulates the contact force as the device slides.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Texture {
+    /// Relative modulation of the normal force, in 0..=1.
+    pub amplitude: f32,
+    /// Distance between grain ridges.
+    pub period: Meters,
+}
+
+impl Texture {
+    /// Perfectly smooth.
+    pub const NONE: Self = Self { amplitude: 0.0, period: Meters(1.0) };
+
+    pub const fn new(amplitude: f32, period: Meters) -> Self {
+        Self { amplitude, period }
+    }
+
+    #[inline]
+    pub fn is_smooth(&self) -> bool {
+        self.amplitude <= 0.0 || self.period.value() <= 0.0
+    }
+
+    /// Factor applied to the normal force at surface point `point`, in
+    /// `1 - amplitude..=1 + amplitude`.
+    pub fn modulation(&self, point: Vec3) -> f32 {
+        if self.is_smooth() {
+            return 1.0;
+        }
+        let k = TAU / self.period.value();
+        let grain = ((k * point.x).sin() + (k * point.y).sin() + (k * point.z).sin()) / 3.0;
+        1.0 + self.amplitude.clamp(0.0, 1.0) * grain
+    }
+}
+
+impl Default for Texture {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+// ============================================================================
+// Material
+// ============================================================================
+
+/// Compliance, friction and texture of a surface.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HapticMaterial {
+    pub stiffness: NewtonsPerMeter,
+    pub damping: NewtonSecondsPerMeter,
+    /// Coulomb coefficient that must be overcome to start sliding.
+    pub static_friction: f32,
+    /// Coulomb coefficient while sliding; at most `static_friction`.
+    pub dynamic_friction: f32,
+    pub texture: Texture,
+}
+
+impl HapticMaterial {
+    /// Soft and grippy.
+    pub const RUBBER: Self = Self::new(NewtonsPerMeter(300.0), NewtonSecondsPerMeter(2.0), 0.9, 0.7);
+    /// Stiff and slippery.
+    pub const METAL: Self = Self::new(NewtonsPerMeter(1500.0), NewtonSecondsPerMeter(1.0), 0.2, 0.15);
+    /// Firm with a fine grain.
+    pub const WOOD: Self = Self::new(NewtonsPerMeter(900.0), NewtonSecondsPerMeter(1.5), 0.45, 0.35)
+        .with_texture(Texture::new(0.15, Meters(0.002)));
+
+    /// Smooth material.
+    pub const fn new(
+        stiffness: NewtonsPerMeter,
+        damping: NewtonSecondsPerMeter,
+        static_friction: f32,
+        dynamic_friction: f32,
+    ) -> Self {
+        Self { stiffness, damping, static_friction, dynamic_friction, texture: Texture::NONE }
+    }
+
+    pub const fn with_texture(mut self, texture: Texture) -> Self {
+        self.texture = texture;
+        self
+    }
+}
+
+impl Default for HapticMaterial {
+    fn default() -> Self {
+        NodeHaptics::default().into()
+    }
+}
+
+impl From<NodeHaptics> for HapticMaterial {
+    fn from(haptics: NodeHaptics) -> Self {
+        Self::new(haptics.stiffness, haptics.damping, haptics.friction, haptics.friction)
+    }
+}
+
+impl From<HapticMaterial> for ContactMaterial {
+    fn from(material: HapticMaterial) -> Self {
+        ContactMaterial::new(material.stiffness, material.damping)
+            .with_friction(material.static_friction, material.dynamic_friction.min(material.static_friction))
+    }
+}
+
+// ============================================================================
+// Library
+// ============================================================================
+
+/// Index of a material in a [`MaterialLibrary`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MaterialId(u32);
+
+/// Named materials and their assignment to scene nodes.
+#[derive(Debug, Clone, Default)]
+pub struct MaterialLibrary {
+    materials: Vec<(String, HapticMaterial)>,
+    assigned: HashMap<NodeId, MaterialId>,
+}
+
+impl MaterialLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a material, or replaces the one with the same name keeping its id.
+    pub fn add(&mut self, name: impl Into<String>, material: HapticMaterial) -> MaterialId {
+        let name = name.into();
+        if let Some(id) = self.id(&name) {
+            self.materials[id.0 as usize].1 = material;
+            return id;
+        }
+        self.materials.push((name, material));
+        MaterialId(self.materials.len() as u32 - 1)
+    }
+
+    pub fn id(&self, name: &str) -> Option<MaterialId> {
+        self.materials.iter().position(|(n, _)| n == name).map(|i| MaterialId(i as u32))
+    }
+
+    pub fn get(&self, id: MaterialId) -> Option<&HapticMaterial> {
+        self.materials.get(id.0 as usize).map(|(_, m)| m)
+    }
+
+    pub fn get_mut(&mut self, id: MaterialId) -> Option<&mut HapticMaterial> {
+        self.materials.get_mut(id.0 as usize).map(|(_, m)| m)
+    }
+
+    pub fn name(&self, id: MaterialId) -> Option<&str> {
+        self.materials.get(id.0 as usize).map(|(n, _)| n.as_str())
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.materials.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.materials.is_empty()
+    }
+
+    /// Assigns `material` to `node`; ids from another library are not checked.
+    pub fn assign(&mut self, node: NodeId, material: MaterialId) {
+        self.assigned.insert(node, material);
+    }
+
+    pub fn unassign(&mut self, node: NodeId) -> Option<MaterialId> {
+        self.assigned.remove(&node)
+    }
+
+    #[inline]
+    pub fn assigned(&self, node: NodeId) -> Option<MaterialId> {
+        self.assigned.get(&node).copied()
+    }
+
+    /// Material rendered for `node`: its assigned material, else one derived from
+    /// its node haptics. None for intangible nodes and unknown ids.
+    pub fn lookup(&self, scene: &Scene, node: NodeId) -> Option<HapticMaterial> {
+        let haptics = scene.get(node)?.haptics?;
+        match self.assigned(node).and_then(|id| self.get(id)) {
+            Some(material) => Some(*material),
+            None => Some(haptics.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/material_tests.rs"]
+mod tests;
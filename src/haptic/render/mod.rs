@@ -0,0 +1,25 @@
+// src/haptic/render/mod.rs
+pub mod click;
+pub mod contact;
+pub mod detent;
+pub mod field;
+pub mod friction;
+pub mod gains;
+pub mod god_object;
+pub mod lod;
+pub mod magnetic;
+pub mod material;
+pub mod servo;
+pub mod shader;
+pub use click::ClickProfile;
+pub use contact::{ContactEngagement, ContactMaterial, ContactModel, ContactState};
+pub use detent::Detent;
+pub use field::{FieldKind, ForceField, ForceFieldSet, Region};
+pub use friction::{Friction, SlipState};
+pub use gains::{GainGrid, GainSchedule, GainZone, Gains};
+pub use god_object::{Contact, GodObject};
+pub use lod::{HapticLod, LodConfig, LodLevel};
+pub use magnetic::MagneticWell;
+pub use material::{HapticMaterial, MaterialId, MaterialLibrary, Texture};
+pub use servo::{HapticLoop, JitterMeter, LoopConfig, LoopStats, Snapshot, Tick};
+pub use shader::{DirectionalGrain, ForceShader, PulsingAlarm, ShaderRegistry};
@@ -0,0 +1,435 @@
+//! The haptic servo loop.
+//!
+//! Stable force rendering needs a fixed, fast update rate (around 1 kHz) that the
+//! UI thread at 60 FPS cannot provide. [`HapticLoop`] runs a user callback on its
+//! own thread at a fixed rate, asks the OS for real-time scheduling, and measures
+//! how far each tick lands from its deadline. The two threads never share mutable
+//! state directly: each side publishes a [`Snapshot`] that the other reads, so a
+//! slow UI frame can never stall the servo loop. The rate comes from the
+//! `servo.rate_hz` config key and can change while the loop runs.
+
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, TryLockError, Weak};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::core::constants::{DEFAULT_SERVO_RATE_HZ, MAX_SERVO_RATE_HZ, SERVO_RATE_HZ};
+use crate::core::{Config, Hertz, Seconds, SubscriptionId};
+
+// ============================================================================
+// Snapshots
+// ============================================================================
+
+/// Latest value published by one thread for another, double-buffered.
+///
+/// The writer fills the back slot and then flips it to the front, so readers
+/// always see a complete value. Publishing never blocks: if a reader is still
+/// copying out of the back slot, the value is dropped and the next publish
+/// (usually one tick later) supersedes it.
+#[derive(Debug)]
+pub struct Snapshot<T> {
+    slots: [Mutex<T>; 2],
+    front: AtomicUsize,
+    version: AtomicU64,
+}
+
+impl<T: Clone> Snapshot<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            slots: [Mutex::new(value.clone()), Mutex::new(value)],
+            front: AtomicUsize::new(0),
+            version: AtomicU64::new(0),
+        }
+    }
+
+    /// Makes `value` the latest. Returns false if it was dropped because a reader
+    /// held the back slot.
+    pub fn publish(&self, value: T) -> bool {
+        let back = 1 - self.front.load(Ordering::Acquire);
+        let Ok(mut slot) = self.slots[back].try_lock() else {
+            return false;
+        };
+        *slot = value;
+        drop(slot);
+        self.front.store(back, Ordering::Release);
+        self.version.fetch_add(1, Ordering::AcqRel);
+        true
+    }
+
+    /// Copy of the latest value. Never waits on a lock: while a publish refills
+    /// the slot last seen as front, the other slot holds the previous snapshot.
+    pub fn read(&self) -> T {
+        loop {
+            let front = self.front.load(Ordering::Acquire);
+            for slot in [front, 1 - front] {
+                match self.slots[slot].try_lock() {
+                    Ok(value) => return value.clone(),
+                    Err(TryLockError::Poisoned(e)) => return e.into_inner().clone(),
+                    Err(TryLockError::WouldBlock) => {}
+                }
+            }
+            // Both slots busy with other readers and a publish; try again shortly
+            thread::yield_now();
+        }
+    }
+
+    /// Copy of the latest value, or None rather than waiting while a publish
+    /// fills that slot.
+    pub fn try_read(&self) -> Option<T> {
+        let front = self.front.load(Ordering::Acquire);
+        match self.slots[front].try_lock() {
+            Ok(slot) => Some(slot.clone()),
+            Err(TryLockError::Poisoned(e)) => Some(e.into_inner().clone()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+
+    /// Copies the latest value into `out` if it changed since `seen`, returning the
+    /// new version. Avoids cloning every tick when nothing was published.
+    ///
+    /// Never waits: while a publish fills the front slot, `out` keeps the last
+    /// snapshot and None is returned, so the next call picks the value up.
+    pub fn read_if_newer(&self, seen: u64, out: &mut T) -> Option<u64> {
+        let version = self.version.load(Ordering::Acquire);
+        if version == seen {
+            return None;
+        }
+        *out = self.try_read()?;
+        Some(version)
+    }
+
+    /// Number of successful publishes.
+    #[inline]
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+}
+
+// ============================================================================
+// Timing
+// ============================================================================
+
+/// Fastest rate the loop runs at; faster requests are clamped to it.
+pub const MAX_RATE: Hertz = Hertz(MAX_SERVO_RATE_HZ as f32);
+
+/// `rate` clamped to [`MAX_RATE`], or None unless it is positive and finite.
+fn checked_rate(rate: Hertz) -> Option<Hertz> {
+    let hz = rate.value();
+    (hz.is_finite() && hz > 0.0).then(|| Hertz(hz.min(MAX_RATE.value())))
+}
+
+/// Servo loop settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoopConfig {
+    pub rate: Hertz,
+    /// Request real-time scheduling for the loop thread (may need privileges).
+    pub realtime: bool,
+    /// Sleep until this long before a deadline, then spin; trades CPU for jitter.
+    pub spin: Duration,
+    pub thread_name: String,
+}
+
+impl Default for LoopConfig {
+    fn default() -> Self {
+        Self {
+            rate: Hertz(DEFAULT_SERVO_RATE_HZ as f32),
+            realtime: true,
+            spin: Duration::from_micros(200),
+            thread_name: "haptic-servo".into(),
+        }
+    }
+}
+
+impl LoopConfig {
+    /// Defaults at `rate`, clamped to [`MAX_RATE`]; a rate that is not positive
+    /// and finite keeps the default.
+    pub fn with_rate(rate: Hertz) -> Self {
+        let defaults = Self::default();
+        Self { rate: checked_rate(rate).unwrap_or(defaults.rate), ..defaults }
+    }
+
+    /// Defaults with the rate set by `servo.rate_hz`.
+    pub fn from_config(config: &Config) -> Self {
+        match config.get_tuning(SERVO_RATE_HZ) {
+            Some(hz) => Self::with_rate(Hertz(hz)),
+            None => Self::default(),
+        }
+    }
+
+    /// Nominal tick period.
+    pub fn period(&self) -> Duration {
+        period(self.rate)
+    }
+}
+
+/// Tick period at `rate`, or at the default rate if `rate` is unusable.
+fn period(rate: Hertz) -> Duration {
+    let hz = checked_rate(rate).map_or(DEFAULT_SERVO_RATE_HZ as f32, Hertz::value);
+    Duration::from_secs_f64(1.0 / f64::from(hz.max(1.0)))
+}
+
+/// What the callback knows about the current tick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tick {
+    /// Ticks since the loop started.
+    pub index: u64,
+    /// Nominal period, the step to integrate with.
+    pub dt: Seconds,
+    /// Time since the loop started.
+    pub elapsed: Duration,
+    /// How late this tick started relative to its deadline.
+    pub lateness: Duration,
+}
+
+/// Timing quality of the loop so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LoopStats {
+    pub ticks: u64,
+    /// Mean start-to-deadline lateness.
+    pub mean_jitter: Duration,
+    /// Root-mean-square lateness.
+    pub rms_jitter: Duration,
+    pub max_jitter: Duration,
+    /// Deadlines skipped because a tick ran more than a period late.
+    pub missed: u64,
+    /// Whether real-time scheduling was granted.
+    pub realtime: bool,
+}
+
+/// Accumulates per-tick lateness into [`LoopStats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct JitterMeter {
+    ticks: u64,
+    sum_us: f64,
+    sum_sq_us: f64,
+    max_us: f64,
+    missed: u64,
+}
+
+impl JitterMeter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, lateness: Duration) {
+        let us = lateness.as_secs_f64() * 1e6;
+        self.ticks += 1;
+        self.sum_us += us;
+        self.sum_sq_us += us * us;
+        self.max_us = self.max_us.max(us);
+    }
+
+    pub fn record_missed(&mut self, count: u64) {
+        self.missed += count;
+    }
+
+    pub fn stats(&self, realtime: bool) -> LoopStats {
+        let n = self.ticks.max(1) as f64;
+        let micros = |us: f64| Duration::from_secs_f64(us.max(0.0) * 1e-6);
+        LoopStats {
+            ticks: self.ticks,
+            mean_jitter: micros(self.sum_us / n),
+            rms_jitter: micros((self.sum_sq_us / n).sqrt()),
+            max_jitter: micros(self.max_us),
+            missed: self.missed,
+            realtime,
+        }
+    }
+}
+
+// ============================================================================
+// Loop
+// ============================================================================
+
+/// Servo thread exchanging `In` (from the UI) and `Out` (to the UI) snapshots.
+pub struct HapticLoop<In, Out> {
+    input: Arc<Snapshot<In>>,
+    output: Arc<Snapshot<Out>>,
+    stats: Arc<Snapshot<LoopStats>>,
+    /// Bits of the rate in hertz, read by the loop every tick.
+    rate: Arc<AtomicU32>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl<In, Out> HapticLoop<In, Out>
+where
+    In: Clone + Send + 'static,
+    Out: Clone + Send + 'static,
+{
+    /// Starts the loop. Each tick, `callback` sees the latest input published by the
+    /// UI and updates the output state, which is then published back.
+    pub fn spawn(
+        config: LoopConfig,
+        input: In,
+        output: Out,
+        mut callback: impl FnMut(&Tick, &In, &mut Out) + Send + 'static,
+    ) -> io::Result<Self> {
+        let input = Arc::new(Snapshot::new(input));
+        let output = Arc::new(Snapshot::new(output));
+        let stats = Arc::new(Snapshot::new(LoopStats::default()));
+        let initial = checked_rate(config.rate).unwrap_or(Hertz(DEFAULT_SERVO_RATE_HZ as f32));
+        let rate = Arc::new(AtomicU32::new(initial.value().to_bits()));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let (input_rx, output_tx, stats_tx) = (input.clone(), output.clone(), stats.clone());
+        let (rate_rx, run) = (rate.clone(), running.clone());
+        let thread = thread::Builder::new().name(config.thread_name.clone()).spawn(move || {
+            let realtime = config.realtime && promote_current_thread();
+            let mut rate_bits = initial.value().to_bits();
+            let mut period = config.period();
+            let mut dt = Seconds(period.as_secs_f32());
+            let mut local_in = input_rx.read();
+            let mut seen = input_rx.version();
+            let mut local_out = output_tx.read();
+            let mut meter = JitterMeter::new();
+
+            let start = Instant::now();
+            let mut deadline = start;
+            let mut index = 0u64;
+            while run.load(Ordering::Acquire) {
+                wait_until(deadline, config.spin);
+                let bits = rate_rx.load(Ordering::Acquire);
+                if bits != rate_bits {
+                    rate_bits = bits;
+                    period = self::period(Hertz(f32::from_bits(bits)));
+                    dt = Seconds(period.as_secs_f32());
+                }
+                let now = Instant::now();
+                let lateness = now.saturating_duration_since(deadline);
+                meter.record(lateness);
+
+                if let Some(version) = input_rx.read_if_newer(seen, &mut local_in) {
+                    seen = version;
+                }
+                let tick = Tick { index, dt, elapsed: now - start, lateness };
+                callback(&tick, &local_in, &mut local_out);
+                output_tx.publish(local_out.clone());
+                stats_tx.publish(meter.stats(realtime));
+
+                index += 1;
+                deadline += period;
+                // Far behind (debugger, overloaded machine): skip ahead instead of bursting
+                let behind = Instant::now().saturating_duration_since(deadline);
+                if behind > period {
+                    let skipped = (behind.as_nanos() / period.as_nanos().max(1)) as u32;
+                    meter.record_missed(u64::from(skipped));
+                    deadline += period * skipped;
+                }
+            }
+            stats_tx.publish(meter.stats(realtime));
+        })?;
+
+        Ok(Self { input, output, stats, rate, running, thread: Some(thread) })
+    }
+
+    #[inline]
+    pub fn rate(&self) -> Hertz {
+        Hertz(f32::from_bits(self.rate.load(Ordering::Acquire)))
+    }
+
+    /// Switches to `rate`, clamped to [`MAX_RATE`], from the next tick on. A rate
+    /// that is not positive and finite is ignored.
+    pub fn set_rate(&self, rate: Hertz) {
+        if let Some(rate) = checked_rate(rate) {
+            self.rate.store(rate.value().to_bits(), Ordering::Release);
+        }
+    }
+
+    /// Keeps the rate at `servo.rate_hz` as it changes in `config`, returning to the
+    /// default rate if the key is removed. Values are checked like
+    /// [`set_rate`](Self::set_rate)'s: zero, `inf` and `nan` are ignored.
+    ///
+    /// The loop cannot reach `config` to unsubscribe itself: pass the returned id to
+    /// [`Config::unsubscribe`] when the loop goes away. Until then the subscription
+    /// only holds a weak reference, and does nothing once the loop is dropped.
+    pub fn follow_config(&self, config: &mut Config) -> SubscriptionId {
+        if let Some(hz) = config.get_tuning(SERVO_RATE_HZ) {
+            self.set_rate(Hertz(hz));
+        }
+        let rate = Arc::downgrade(&self.rate);
+        config.subscribe(SERVO_RATE_HZ, move |key, value| {
+            let Some(rate) = Weak::upgrade(&rate) else {
+                return;
+            };
+            let hz = match value {
+                Some(value) => value.as_tuning(),
+                None => Some(DEFAULT_SERVO_RATE_HZ as f32),
+            };
+            if let Some(hz) = hz.filter(|_| key == SERVO_RATE_HZ).and_then(|hz| checked_rate(Hertz(hz))) {
+                rate.store(hz.value().to_bits(), Ordering::Release);
+            }
+        })
+    }
+
+    /// Publishes new input for the next tick.
+    pub fn set_input(&self, input: In) -> bool {
+        self.input.publish(input)
+    }
+
+    /// Latest output published by the loop.
+    pub fn output(&self) -> Out {
+        self.output.read()
+    }
+
+    pub fn stats(&self) -> LoopStats {
+        self.stats.read()
+    }
+
+    #[inline]
+    pub fn is_running(&self) -> bool {
+        self.thread.as_ref().is_some_and(|t| !t.is_finished())
+    }
+
+    /// Stops the loop, waits for the current tick to finish and returns the final stats.
+    pub fn stop(&mut self) -> LoopStats {
+        self.running.store(false, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        self.stats.read()
+    }
+}
+
+impl<In, Out> Drop for HapticLoop<In, Out> {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Sleeps until `spin` before `deadline`, then yields until it passes.
+fn wait_until(deadline: Instant, spin: Duration) {
+    let now = Instant::now();
+    if deadline > now + spin {
+        thread::sleep(deadline - now - spin);
+    }
+    while Instant::now() < deadline {
+        thread::yield_now();
+    }
+}
+
+/// Requests FIFO real-time scheduling for the calling thread. Returns false when
+/// the OS refuses (usually missing privileges) or the platform is unsupported.
+#[cfg(unix)]
+fn promote_current_thread() -> bool {
+    // SAFETY: sched_param is plain data, and pthread_self is always a valid
+    // handle for the calling thread.
+    unsafe {
+        let mut param: libc::sched_param = std::mem::zeroed();
+        param.sched_priority = libc::sched_get_priority_max(libc::SCHED_FIFO).min(80);
+        libc::pthread_setschedparam(libc::pthread_self(), libc::SCHED_FIFO, &param) == 0
+    }
+}
+
+#[cfg(not(unix))]
+fn promote_current_thread() -> bool {
+    false
+}
+
+#[cfg(test)]
+#[path = "tests/servo_tests.rs"]
+mod tests;
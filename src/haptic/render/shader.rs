@@ -0,0 +1,169 @@
+//! Programmable per-surface forces.
+//!
+//! A [`ForceShader`] is a small force program run for every contact with the
+//! surfaces it is assigned to, on top of the material's spring and friction; it is
+//! to a haptic surface what a fragment shader is to a rendered one. Shaders run on
+//! the servo thread and may be shared between surfaces, so they take `&self`;
+//! time-varying shaders read a clock rather than counting calls, which keeps every
+//! contact in step however many there are per tick.
+//!
+//! A [`ShaderRegistry`] maps surface indices to shaders; one shader can be shared
+//! by many surfaces. [`PulsingAlarm`] and [`DirectionalGrain`] are ready-made
+//! examples, and any `Fn(&ContactState, f32) -> Vec3` closure is a shader too.
+
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+use std::sync::Arc;
+
+use super::contact::ContactState;
+use crate::core::{Hertz, Meters, Newtons, Newtons3, SessionClock, Vec3};
+
+/// A custom force program for the contacts with a surface.
+pub trait ForceShader: Send + Sync {
+    /// Force in newtons added for `contact`, `dt` seconds after the previous tick.
+    fn shade(&self, contact: &ContactState, dt: f32) -> Vec3;
+}
+
+impl<F> ForceShader for F
+where
+    F: Fn(&ContactState, f32) -> Vec3 + Send + Sync,
+{
+    fn shade(&self, contact: &ContactState, dt: f32) -> Vec3 {
+        self(contact, dt)
+    }
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+/// Which shader runs for which surface.
+#[derive(Clone, Default)]
+pub struct ShaderRegistry {
+    shaders: HashMap<usize, Arc<dyn ForceShader>>,
+}
+
+impl ShaderRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `shader` for contacts with `surface`, replacing any previous one.
+    pub fn assign(&mut self, surface: usize, shader: impl ForceShader + 'static) {
+        self.shaders.insert(surface, Arc::new(shader));
+    }
+
+    /// Runs an already shared shader for contacts with `surface`.
+    pub fn assign_shared(&mut self, surface: usize, shader: Arc<dyn ForceShader>) {
+        self.shaders.insert(surface, shader);
+    }
+
+    pub fn remove(&mut self, surface: usize) -> Option<Arc<dyn ForceShader>> {
+        self.shaders.remove(&surface)
+    }
+
+    pub fn get(&self, surface: usize) -> Option<&Arc<dyn ForceShader>> {
+        self.shaders.get(&surface)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shaders.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.shaders.is_empty()
+    }
+
+    /// Sum of the shader forces over `contacts`; surfaces without a shader add
+    /// nothing. Non-finite shader output is dropped.
+    pub fn shade(&self, contacts: impl IntoIterator<Item = ContactState>, dt: f32) -> Newtons3 {
+        let mut force = Vec3::zero();
+        for contact in contacts {
+            if let Some(shader) = self.shaders.get(&contact.surface) {
+                let shaded = shader.shade(&contact, dt);
+                if shaded.is_finite() {
+                    force += shaded;
+                }
+            }
+        }
+        Newtons3(force)
+    }
+}
+
+// ============================================================================
+// Shaders
+// ============================================================================
+
+/// Pushes back against the device in pulses, like a surface that throbs to warn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PulsingAlarm {
+    pub amplitude: Newtons,
+    pub frequency: Hertz,
+    clock: SessionClock,
+}
+
+impl PulsingAlarm {
+    pub fn new(amplitude: Newtons, frequency: Hertz) -> Self {
+        Self::with_clock(amplitude, frequency, SessionClock::new())
+    }
+
+    /// Pulses in step with everything else timed by `clock`.
+    pub fn with_clock(amplitude: Newtons, frequency: Hertz, clock: SessionClock) -> Self {
+        Self { amplitude, frequency, clock }
+    }
+
+    /// Push at `time_us` on the clock, in 0..=amplitude.
+    pub fn level(&self, time_us: u64) -> Newtons {
+        let phase = (time_us as f64 * 1e-6 * f64::from(self.frequency.value())).fract() as f32;
+        self.amplitude * (0.5 - 0.5 * (TAU * phase).cos())
+    }
+}
+
+impl ForceShader for PulsingAlarm {
+    /// Pulses only while the device presses into the surface.
+    fn shade(&self, contact: &ContactState, _dt: f32) -> Vec3 {
+        if contact.penetration.value() <= 0.0 {
+            return Vec3::zero();
+        }
+        contact.normal * self.level(self.clock.now_us()).value()
+    }
+}
+
+/// Ridges running across `direction`: sliding along it bumps over them, sliding
+/// parallel to the ridges stays smooth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DirectionalGrain {
+    /// Unit vector across the ridges.
+    pub direction: Vec3,
+    pub period: Meters,
+    pub amplitude: Newtons,
+}
+
+impl DirectionalGrain {
+    pub fn new(direction: Vec3, period: Meters, amplitude: Newtons) -> Self {
+        Self { direction: direction.normalize(), period, amplitude }
+    }
+}
+
+impl ForceShader for DirectionalGrain {
+    fn shade(&self, contact: &ContactState, _dt: f32) -> Vec3 {
+        if contact.penetration.value() <= 0.0 || self.period.value() <= 0.0 {
+            return Vec3::zero();
+        }
+        // Ridge direction within the tangent plane of this contact
+        let across = self.direction - contact.normal * self.direction.dot(contact.normal);
+        let length = across.length();
+        if length <= 0.0 {
+            return Vec3::zero();
+        }
+        let across = across / length;
+        let position = contact.point.dot(across) / self.period.value();
+        across * (-self.amplitude.value() * (TAU * position).sin())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/shader_tests.rs"]
+mod tests;
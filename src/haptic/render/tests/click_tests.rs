@@ -0,0 +1,58 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+fn force(displacement_mm: f32) -> f32 {
+    ClickProfile::KEYBOARD.force(Meters(displacement_mm * 1e-3)).value()
+}
+
+#[test]
+fn test_curve_phases() {
+    assert_eq!(force(-1.0), 0.0);
+    assert!((force(1.0) - 0.3).abs() < TEST_EPSILON);
+    assert!((force(2.0) - 0.6).abs() < TEST_EPSILON);
+    // Collapse to the plateau, then climb back to the break force at full travel
+    assert!((force(2.5) - 0.3).abs() < TEST_EPSILON);
+    assert!((force(3.25) - 0.45).abs() < TEST_EPSILON);
+    assert!((force(4.0) - 0.6).abs() < TEST_EPSILON);
+    // Bottom-out is stiff
+    assert!((force(4.1) - 0.9).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_curve_is_continuous() {
+    let samples: Vec<f32> = (0..=5000).map(|i| force(i as f32 * 0.001)).collect();
+    let largest_step = samples.windows(2).map(|w| (w[1] - w[0]).abs()).fold(0.0, f32::max);
+    assert!(largest_step < 0.01, "{}", largest_step);
+    // The collapse is the only falling part
+    let falling: Vec<usize> = samples.windows(2).enumerate().filter(|(_, w)| w[1] < w[0]).map(|(i, _)| i).collect();
+    assert!(falling.iter().all(|&i| (2000..2500).contains(&i)));
+}
+
+#[test]
+fn test_press_hysteresis() {
+    let profile = ClickProfile::default();
+    assert!(!profile.pressed(Meters(0.0019), false));
+    assert!(profile.pressed(Meters(0.002), false));
+    // Rising back past the break does not release yet
+    assert!(profile.pressed(Meters(0.0015), true));
+    assert!(!profile.pressed(Meters(0.001), true));
+}
+
+#[test]
+fn test_force_along_pushes_back_out() {
+    let profile = ClickProfile::KEYBOARD;
+    let axis = -Vec3::unit_z();
+    let force = profile.force_along(Vec3::zero(), axis, Meters3::new(0.01, 0.0, -0.001));
+    assert!((force.0.z - 0.3).abs() < TEST_EPSILON);
+    assert!(force.0.x.abs() < TEST_EPSILON);
+    assert_eq!(profile.force_along(Vec3::zero(), axis, Meters3::new(0.0, 0.0, 0.001)), Newtons3::ZERO);
+}
+
+#[test]
+fn test_with_travel_scales_distances() {
+    let long = ClickProfile::KEYBOARD.with_travel(Meters(0.008));
+    assert!((long.break_travel.value() - 0.004).abs() < TEST_EPSILON);
+    assert!((long.force(Meters(0.004)).value() - 0.6).abs() < TEST_EPSILON);
+    assert!((long.force(Meters(0.005)).value() - 0.3).abs() < TEST_EPSILON);
+}
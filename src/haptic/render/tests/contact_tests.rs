@@ -0,0 +1,114 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+fn material() -> ContactMaterial {
+    ContactMaterial::new(NewtonsPerMeter(1000.0), NewtonSecondsPerMeter(2.0))
+}
+
+fn pressing(depth: f32, normal_speed: f32) -> ContactState {
+    ContactState::new(
+        0,
+        Vec3::zero(),
+        Vec3::unit_z(),
+        Vec3::new(0.0, 0.0, -depth),
+        MetersPerSecond3::new(0.0, 0.0, normal_speed),
+    )
+}
+
+#[test]
+fn test_state_geometry() {
+    let state = pressing(0.002, -0.1);
+    assert!((state.penetration.value() - 0.002).abs() < TEST_EPSILON);
+    assert!((state.normal_velocity.value() + 0.1).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_spring_and_damper() {
+    let model = ContactModel::new(Newtons(100.0));
+    let at_rest = model.force(&pressing(0.002, 0.0), &material());
+    assert!((at_rest.0.z - 2.0).abs() < TEST_EPSILON);
+
+    // Pressing in adds damping force, withdrawing removes it
+    let pressing_in = model.force(&pressing(0.002, -0.5), &material());
+    assert!((pressing_in.0.z - 3.0).abs() < TEST_EPSILON);
+    let withdrawing = model.force(&pressing(0.002, 0.5), &material());
+    assert!((withdrawing.0.z - 1.0).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_never_pulls_in() {
+    let model = ContactModel::new(Newtons(100.0));
+    assert_eq!(model.force(&pressing(0.001, 5.0), &material()), Newtons3::ZERO);
+    assert_eq!(model.force(&pressing(-0.001, 0.0), &material()), Newtons3::ZERO);
+}
+
+#[test]
+fn test_saturation() {
+    let model = ContactModel::for_device(&DeviceCapabilities::KINESTHETIC);
+    let force = model.force(&pressing(0.05, 0.0), &material());
+    assert!((force.length().value() - 3.3).abs() < TEST_EPSILON);
+    assert!((model.saturate(Newtons3::new(10.0, 0.0, 0.0)).0.x - 3.3).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_pop_through() {
+    let model = ContactModel::new(Newtons(100.0)).with_pop_through(Meters(0.01));
+    assert!(!model.pops_through(&pressing(0.005, 0.0)));
+    assert!(model.pops_through(&pressing(0.015, 0.0)));
+    assert_eq!(model.force(&pressing(0.015, 0.0), &material()), Newtons3::ZERO);
+}
+
+#[test]
+fn test_material_from_node_haptics() {
+    let haptics = NodeHaptics::default();
+    let material = ContactMaterial::from(haptics);
+    assert_eq!(material.stiffness, haptics.stiffness);
+    assert_eq!(material.damping, haptics.damping);
+}
+
+#[test]
+fn test_engagement_dead_band() {
+    let model = ContactModel::new(Newtons(100.0)).with_engagement(Meters(0.001), Meters(0.0005));
+    let mut engagement = ContactEngagement::new();
+    assert_eq!(engagement.update(&model, &pressing(0.0008, 0.0), 0), 0.0);
+    assert_eq!(engagement.update(&model, &pressing(0.0012, 0.0), 0), 1.0);
+    // Stays engaged between the release and engage depths
+    assert_eq!(engagement.update(&model, &pressing(0.0008, 0.0), 0), 1.0);
+    assert_eq!(engagement.update(&model, &pressing(0.0004, 0.0), 0), 0.0);
+    assert!(!engagement.is_engaged());
+
+    // Release never exceeds engage
+    let clamped = ContactModel::new(Newtons(100.0)).with_engagement(Meters(0.001), Meters(0.002));
+    assert_eq!(clamped.release_depth, clamped.engage_depth);
+}
+
+#[test]
+fn test_ramp_in() {
+    let model = ContactModel::new(Newtons(100.0)).with_ramp_in(Milliseconds(20.0));
+    let mut engagement = ContactEngagement::new();
+    let contact = pressing(0.002, 0.0);
+    assert_eq!(engagement.update(&model, &contact, 1_000), 0.0);
+    assert!((engagement.update(&model, &contact, 11_000) - 0.5).abs() < TEST_EPSILON);
+    assert_eq!(engagement.update(&model, &contact, 21_000), 1.0);
+    assert_eq!(engagement.update(&model, &contact, 500_000), 1.0);
+
+    // Leaving the surface restarts the ramp
+    assert_eq!(engagement.update(&model, &pressing(-0.001, 0.0), 600_000), 0.0);
+    assert_eq!(engagement.update(&model, &contact, 601_000), 0.0);
+}
+
+#[test]
+fn test_engagement_from_config() {
+    use crate::core::{ConfigValue, Layer};
+
+    let mut config = Config::new();
+    assert_eq!(ContactModel::new(Newtons(5.0)).with_config(&config), ContactModel::new(Newtons(5.0)));
+    config.load_str("[contact]\nengage_depth_m = 0.001\nrelease_depth_m = 0.0005\nramp_in_ms = 20\n").unwrap();
+    config.set(Layer::Cli, "contact.release_depth_m", ConfigValue::Float(-1.0));
+    let model = ContactModel::new(Newtons(5.0)).with_config(&config);
+    assert_eq!(model.engage_depth, Meters(0.001));
+    // A negative value is ignored
+    assert_eq!(model.release_depth, Meters::ZERO);
+    assert!((model.ramp_in.value() - 0.02).abs() < TEST_EPSILON);
+}
@@ -0,0 +1,48 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+fn detent() -> Detent {
+    Detent::new(Meters(0.01), Newtons(1.0), Meters(0.004))
+}
+
+#[test]
+fn test_force_pulls_toward_notch() {
+    let detent = detent();
+    assert!(detent.force(Meters(0.0)).value().abs() < TEST_EPSILON);
+    // Peak pull halfway between the edge and the center, on both sides
+    assert!((detent.force(Meters(0.001)).value() + 1.0).abs() < TEST_EPSILON);
+    assert!((detent.force(Meters(0.019)).value() - 1.0).abs() < TEST_EPSILON);
+    // Free between notches
+    assert_eq!(detent.force(Meters(0.005)), Newtons::ZERO);
+    assert_eq!(detent.force(Meters(-0.0025)), Newtons::ZERO);
+}
+
+#[test]
+fn test_nearest_and_count() {
+    let detent = detent().with_origin(Meters(0.002)).with_count(3);
+    assert_eq!(detent.nearest(Meters(0.0131)), 1);
+    assert_eq!(detent.nearest(Meters(-1.0)), 0);
+    assert_eq!(detent.nearest(Meters(1.0)), 2);
+    assert!((detent.position_of(2).value() - 0.022).abs() < TEST_EPSILON);
+    // Past the last notch the travel is free
+    assert_eq!(detent.force(Meters(0.031)), Newtons::ZERO);
+    assert!(detent.force(Meters(0.021)).value() > 0.0);
+}
+
+#[test]
+fn test_over_length_and_width_limit() {
+    let detent = Detent::over(Meters(0.1), 11, Newtons(0.5), Meters(1.0));
+    assert!((detent.spacing.value() - 0.01).abs() < TEST_EPSILON);
+    assert_eq!(detent.width, detent.spacing);
+    assert_eq!(detent.nearest(Meters(0.1)), 10);
+    assert_eq!(Detent::over(Meters(0.1), 0, Newtons(0.5), Meters(0.01)).force(Meters(0.001)), Newtons::ZERO);
+}
+
+#[test]
+fn test_force_along_axis() {
+    let detent = detent();
+    let force = detent.force_along(Vec3::new(1.0, 0.0, 0.0), Vec3::unit_y(), Meters3::new(1.5, 0.009, 0.3));
+    assert!(force.0.x.abs() < TEST_EPSILON && force.0.z.abs() < TEST_EPSILON);
+    assert!((force.0.y - 1.0).abs() < TEST_EPSILON);
+}
@@ -0,0 +1,83 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+fn at(x: f32, y: f32, z: f32) -> Meters3 {
+    Meters3::new(x, y, z)
+}
+
+fn still() -> MetersPerSecond3 {
+    MetersPerSecond3::ZERO
+}
+
+fn unit_box() -> Region {
+    Region::Box(Aabb::new(Vec3::splat(-1.0), Vec3::splat(1.0)))
+}
+
+#[test]
+fn test_region_distances() {
+    let region = unit_box();
+    assert!((region.distance(Vec3::zero()) + 1.0).abs() < TEST_EPSILON);
+    assert!((region.distance(Vec3::new(2.0, 0.0, 0.0)) - 1.0).abs() < TEST_EPSILON);
+    assert!((region.distance(Vec3::new(2.0, 2.0, 0.0)) - 2f32.sqrt()).abs() < TEST_EPSILON);
+
+    let sphere = Region::Sphere { center: Vec3::unit_x(), radius: Meters(0.5) };
+    assert!(sphere.contains(Vec3::new(1.4, 0.0, 0.0)));
+    assert!(!sphere.contains(Vec3::zero()));
+
+    let slab = Region::sdf(|p| p.z.abs() - 0.1);
+    assert!(slab.contains(Vec3::new(100.0, -5.0, 0.05)));
+    assert!(!slab.contains(Vec3::new(0.0, 0.0, 0.2)));
+}
+
+#[test]
+fn test_field_kinds() {
+    let wind = ForceField::new(unit_box(), FieldKind::Wind(Newtons3::new(0.5, 0.0, 0.0)));
+    assert_eq!(wind.force(at(0.0, 0.0, 0.0), still()), Newtons3::new(0.5, 0.0, 0.0));
+    assert_eq!(wind.force(at(3.0, 0.0, 0.0), still()), Newtons3::ZERO);
+
+    let gravity = FieldKind::Gravity { center: Vec3::zero(), strength: Newtons(2.0), core: Meters(0.1) };
+    let pull = gravity.force(Vec3::new(0.5, 0.0, 0.0), Vec3::zero());
+    assert!((pull.x + 2.0).abs() < TEST_EPSILON);
+    let inner = gravity.force(Vec3::new(0.0, 0.05, 0.0), Vec3::zero());
+    assert!((inner.y + 1.0).abs() < TEST_EPSILON);
+    assert_eq!(gravity.force(Vec3::zero(), Vec3::zero()), Vec3::zero());
+
+    let vortex = FieldKind::Vortex { center: Vec3::zero(), axis: Vec3::unit_z(), strength: Newtons(1.0) };
+    let swirl = vortex.force(Vec3::new(0.3, 0.0, 0.2), Vec3::zero());
+    assert!((swirl.y - 1.0).abs() < TEST_EPSILON);
+    assert!(swirl.x.abs() < TEST_EPSILON && swirl.z.abs() < TEST_EPSILON);
+
+    let mud = FieldKind::Mud(NewtonSecondsPerMeter(4.0));
+    let drag = mud.force(Vec3::zero(), Vec3::new(0.0, 0.0, -0.5));
+    assert!((drag.z - 2.0).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_feather_fades_in() {
+    let field = ForceField::new(unit_box(), FieldKind::Wind(Newtons3::new(1.0, 0.0, 0.0))).with_feather(Meters(0.2));
+    assert_eq!(field.weight(Vec3::new(1.0, 0.0, 0.0)), 0.0);
+    assert!((field.weight(Vec3::new(0.9, 0.0, 0.0)) - 0.5).abs() < TEST_EPSILON);
+    assert_eq!(field.weight(Vec3::new(0.5, 0.0, 0.0)), 1.0);
+}
+
+#[test]
+fn test_priority_covers_lower_layers() {
+    let storm = ForceField::new(
+        Region::Sphere { center: Vec3::zero(), radius: Meters(10.0) },
+        FieldKind::Wind(Newtons3::new(1.0, 0.0, 0.0)),
+    );
+    let gust = ForceField::new(unit_box(), FieldKind::Wind(Newtons3::new(0.0, 1.0, 0.0)));
+    let calm = ForceField::new(unit_box(), FieldKind::Wind(Newtons3::ZERO)).with_priority(1).with_feather(Meters(0.2));
+    let set = ForceFieldSet::new().with(storm).with(gust).with(calm);
+    assert_eq!(set.iter().map(|f| f.priority).collect::<Vec<_>>(), vec![1, 0, 0]);
+
+    // Same priority adds up outside the calm zone's core...
+    let edge = set.force(at(0.9, 0.0, 0.0), still()).value();
+    assert!((edge.x - 0.5).abs() < TEST_EPSILON && (edge.y - 0.5).abs() < TEST_EPSILON);
+    // ...the calm zone replaces both within it...
+    assert_eq!(set.force(at(0.0, 0.0, 0.0), still()), Newtons3::ZERO);
+    assert_eq!(set.active(at(0.0, 0.0, 0.0)).count(), 3);
+    // ...and only the storm blows outside the box
+    assert_eq!(set.force(at(5.0, 0.0, 0.0), still()), Newtons3::new(1.0, 0.0, 0.0));
+}
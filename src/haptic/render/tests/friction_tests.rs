@@ -0,0 +1,73 @@
+use super::*;
+use crate::core::{Meters, MetersPerSecond, NewtonSecondsPerMeter, NewtonsPerMeter};
+
+const TEST_EPSILON: f32 = 1e-4;
+
+fn material() -> ContactMaterial {
+    ContactMaterial::new(NewtonsPerMeter(1000.0), NewtonSecondsPerMeter(0.0)).with_friction(0.5, 0.3)
+}
+
+/// Contact on the floor at `x`, pressing with the given normal force.
+fn contact_at(x: f32) -> ContactState {
+    ContactState {
+        surface: 0,
+        point: Vec3::new(x, 0.0, 0.0),
+        normal: Vec3::unit_z(),
+        penetration: Meters(0.002),
+        normal_velocity: MetersPerSecond(0.0),
+    }
+}
+
+#[test]
+fn test_sticks_within_static_cone() {
+    let mut friction = Friction::new();
+    let still = MetersPerSecond3::ZERO;
+    assert_eq!(friction.update(&contact_at(0.0), Newtons(2.0), still, &material()), Newtons3::ZERO);
+    assert_eq!(friction.state(), SlipState::Stick);
+
+    // 0.5 mm at 1000 N/m is 0.5 N, inside the 1 N static cone
+    let force = friction.update(&contact_at(0.0005), Newtons(2.0), still, &material());
+    assert_eq!(friction.state(), SlipState::Stick);
+    assert!((force.0.x + 0.5).abs() < TEST_EPSILON);
+    assert_eq!(friction.anchor(), Some(Vec3::zero()));
+}
+
+#[test]
+fn test_slips_at_kinetic_level() {
+    let mut friction = Friction::new();
+    let still = MetersPerSecond3::ZERO;
+    friction.update(&contact_at(0.0), Newtons(2.0), still, &material());
+
+    let force = friction.update(&contact_at(0.005), Newtons(2.0), still, &material());
+    assert_eq!(friction.state(), SlipState::Slip);
+    assert!((force.0.x + 0.6).abs() < TEST_EPSILON);
+    // Anchor trails the contact by kinetic force over stiffness
+    assert!((friction.anchor().unwrap().x - 0.0044).abs() < TEST_EPSILON);
+
+    // Holding still afterwards sticks again at the kinetic force
+    friction.update(&contact_at(0.005), Newtons(2.0), still, &material());
+    assert_eq!(friction.state(), SlipState::Stick);
+}
+
+#[test]
+fn test_viscous_opposes_tangential_velocity() {
+    let mut friction = Friction::new();
+    let material = ContactMaterial::new(NewtonsPerMeter(1000.0), NewtonSecondsPerMeter(0.0))
+        .with_viscous_friction(NewtonSecondsPerMeter(2.0));
+    let velocity = MetersPerSecond3::new(0.1, 0.0, -0.5);
+
+    let force = friction.update(&contact_at(0.0), Newtons(1.0), velocity, &material);
+    assert!((force.0.x + 0.2).abs() < TEST_EPSILON);
+    assert!(force.0.z.abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_lifting_off_releases() {
+    let mut friction = Friction::new();
+    let still = MetersPerSecond3::ZERO;
+    friction.update(&contact_at(0.0), Newtons(2.0), still, &material());
+
+    assert_eq!(friction.update(&contact_at(0.001), Newtons::ZERO, still, &material()), Newtons3::ZERO);
+    assert_eq!(friction.state(), SlipState::Free);
+    assert_eq!(friction.anchor(), None);
+}
@@ -0,0 +1,75 @@
+use super::*;
+use crate::core::Quat;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+fn workspace() -> Obb {
+    Obb::new(Vec3::zero(), Vec3::splat(0.1), Quat::identity())
+}
+
+fn assert_gains(actual: Gains, expected: Gains) {
+    assert!((actual.stiffness - expected.stiffness).abs() < TEST_EPSILON, "{actual:?} != {expected:?}");
+    assert!((actual.damping - expected.damping).abs() < TEST_EPSILON, "{actual:?} != {expected:?}");
+    assert!((actual.texture - expected.texture).abs() < TEST_EPSILON, "{actual:?} != {expected:?}");
+}
+
+#[test]
+fn test_unconfigured_is_unity() {
+    let schedule = GainSchedule::new(workspace());
+    assert_gains(schedule.gains_at(Vec3::new(0.05, -0.02, 0.0)), Gains::UNITY);
+    assert_gains(schedule.gains_at(Vec3::splat(1.0)), Gains::UNITY);
+}
+
+#[test]
+fn test_edge_falloff() {
+    let at_edge = Gains::new(0.5, 0.8, 0.0);
+    let schedule = GainSchedule::new(workspace()).edge_falloff(0.02, at_edge);
+
+    assert_gains(schedule.gains_at(Vec3::zero()), Gains::UNITY);
+    assert_gains(schedule.gains_at(Vec3::new(0.1, 0.0, 0.0)), at_edge);
+    assert_gains(schedule.gains_at(Vec3::new(0.3, 0.0, 0.0)), at_edge);
+
+    // Halfway through the band, the quintic blend is exactly half
+    let mid = schedule.gains_at(Vec3::new(0.09, 0.0, 0.0));
+    assert_gains(mid, at_edge.lerp(Gains::UNITY, 0.5));
+
+    // Monotone toward the edge
+    let mut last = 1.0;
+    for i in 0..=20 {
+        let g = schedule.gains_at(Vec3::new(0.08 + 0.001 * i as f32, 0.0, 0.0)).stiffness;
+        assert!(g <= last + TEST_EPSILON);
+        last = g;
+    }
+}
+
+#[test]
+fn test_zones_combine_multiplicatively() {
+    let region = Obb::new(Vec3::new(0.05, 0.0, 0.0), Vec3::splat(0.01), Quat::identity());
+    let schedule = GainSchedule::new(workspace())
+        .zone(GainZone::new(region, Gains::uniform(0.5), 0.01))
+        .zone(GainZone::new(region, Gains::new(1.0, 0.5, 2.0), 0.0));
+
+    assert_gains(schedule.gains_at(Vec3::new(0.05, 0.0, 0.0)), Gains::new(0.5, 0.25, 1.0));
+    assert_gains(schedule.gains_at(Vec3::new(-0.05, 0.0, 0.0)), Gains::UNITY);
+    // Inside the first zone's falloff but outside the hard-edged second
+    assert_gains(schedule.gains_at(Vec3::new(0.065, 0.0, 0.0)), Gains::uniform(0.75));
+}
+
+#[test]
+fn test_grid_interpolation() {
+    let grid = GainGrid::from_fn([3, 1, 1], |n| Gains::uniform(1.0 - n.x * 0.5));
+    assert_eq!(grid.dims(), [3, 2, 2]);
+
+    let schedule = GainSchedule::new(workspace()).grid(grid);
+    assert_gains(schedule.gains_at(Vec3::new(-0.1, 0.0, 0.0)), Gains::UNITY);
+    assert_gains(schedule.gains_at(Vec3::new(0.05, 0.03, -0.07)), Gains::uniform(0.625));
+    assert_gains(schedule.gains_at(Vec3::new(0.5, 0.0, 0.0)), Gains::uniform(0.5));
+}
+
+#[test]
+fn test_apply() {
+    let gains = Gains::new(0.5, 2.0, 0.25);
+    assert_eq!(gains.apply_stiffness(NewtonsPerMeter(800.0)), NewtonsPerMeter(400.0));
+    assert_eq!(gains.apply_damping(NewtonSecondsPerMeter(1.5)), NewtonSecondsPerMeter(3.0));
+    assert_eq!(gains.apply_texture(0.4), 0.1);
+}
@@ -0,0 +1,245 @@
+use super::*;
+use crate::core::{Meters, Milliseconds, NewtonSecondsPerMeter, Newtons};
+use crate::geometry::Aabb;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+/// One servo tick at 1 kHz.
+const TICK: Seconds = Seconds(0.001);
+
+fn floor() -> Plane {
+    Plane::from_point_normal(Vec3::zero(), Vec3::unit_z())
+}
+
+fn assert_near(actual: Vec3, expected: Vec3) {
+    assert!(actual.distance_to(expected) < TEST_EPSILON, "{actual:?} != {expected:?}");
+}
+
+/// Forces carry the proxy's skin offset times the stiffness.
+fn assert_force(actual: Newtons3, expected: Vec3) {
+    assert!(actual.0.distance_to(expected) < 0.05, "{actual:?} != {expected:?}");
+}
+
+#[test]
+fn test_free_space_follows_device() {
+    let mut god = GodObject::new(NewtonsPerMeter(1000.0));
+    let floor = floor();
+    let device = Meters3::new(0.01, 0.02, 0.03);
+
+    let force = god.update(device, &[&floor]);
+    assert_eq!(force, Newtons3::ZERO);
+    assert_eq!(god.proxy(), device);
+    assert!(!god.in_contact());
+}
+
+#[test]
+fn test_penetration_is_spring_to_surface() {
+    let mut god = GodObject::new(NewtonsPerMeter(1000.0));
+    let floor = floor();
+    god.update(Meters3::new(0.0, 0.0, 0.01), &[&floor]);
+
+    let force = god.update(Meters3::new(0.0, 0.0, -0.01), &[&floor]);
+    assert_near(god.proxy().0, Vec3::zero());
+    assert_force(force, Vec3::new(0.0, 0.0, 10.0));
+    assert_eq!(god.contacts().len(), 1);
+}
+
+#[test]
+fn test_slides_along_surface() {
+    let mut god = GodObject::new(NewtonsPerMeter(1000.0));
+    let floor = floor();
+    god.update(Meters3::new(0.0, 0.0, 0.01), &[&floor]);
+    god.update(Meters3::new(0.0, 0.0, -0.01), &[&floor]);
+
+    let force = god.update(Meters3::new(0.02, 0.0, -0.01), &[&floor]);
+    assert_near(god.proxy().0, Vec3::new(0.02, 0.0, 0.0));
+    assert_force(force, Vec3::new(0.0, 0.0, 10.0));
+}
+
+#[test]
+fn test_thin_object_does_not_pop_through() {
+    let mut god = GodObject::new(NewtonsPerMeter(500.0));
+    let slab = Aabb::new(Vec3::new(-0.1, -0.1, -0.005), Vec3::new(0.1, 0.1, 0.0));
+    god.update(Meters3::new(0.0, 0.0, 0.01), &[&slab]);
+
+    // Past the slab in a single tick: penalty rendering would push down here
+    let force = god.update(Meters3::new(0.0, 0.0, -0.02), &[&slab]);
+    assert_near(god.proxy().0, Vec3::zero());
+    assert!(force.0.z > 9.9, "{force:?}");
+}
+
+#[test]
+fn test_corner_uses_two_constraints() {
+    let mut god = GodObject::new(NewtonsPerMeter(1000.0));
+    let floor = floor();
+    let wall = Plane::from_point_normal(Vec3::zero(), Vec3::unit_x());
+    let surfaces: [&dyn ClosestPoint; 2] = [&floor, &wall];
+    god.update(Meters3::new(0.01, 0.0, 0.01), &surfaces);
+
+    let force = god.update(Meters3::new(-0.01, 0.005, -0.01), &surfaces);
+    assert_near(god.proxy().0, Vec3::new(0.0, 0.005, 0.0));
+    assert_force(force, Vec3::new(10.0, 0.0, 10.0));
+    assert_eq!(god.contacts().len(), 2);
+}
+
+#[test]
+fn test_release_leaves_contact() {
+    let mut god = GodObject::new(NewtonsPerMeter(1000.0));
+    let floor = floor();
+    god.update(Meters3::new(0.0, 0.0, 0.01), &[&floor]);
+    god.update(Meters3::new(0.0, 0.0, -0.01), &[&floor]);
+
+    let force = god.update(Meters3::new(0.0, 0.0, 0.02), &[&floor]);
+    assert_eq!(force, Newtons3::ZERO);
+    assert!(!god.in_contact());
+}
+
+#[test]
+fn test_surface_moving_onto_proxy_pushes_it_out() {
+    let mut god = GodObject::new(NewtonsPerMeter(1000.0));
+    god.reset(Meters3::new(0.0, 0.0, -0.01));
+    let floor = floor();
+
+    let force = god.update(Meters3::new(0.0, 0.0, -0.01), &[&floor]);
+    assert_near(god.proxy().0, Vec3::zero());
+    assert!(force.0.z > 9.9);
+}
+
+#[test]
+fn test_constrain_three_planes_meet_at_vertex() {
+    let planes = [
+        Plane::from_point_normal(Vec3::zero(), Vec3::unit_x()),
+        Plane::from_point_normal(Vec3::zero(), Vec3::unit_y()),
+        Plane::from_point_normal(Vec3::zero(), Vec3::unit_z()),
+    ];
+    assert_near(constrain(Vec3::splat(-1.0), &planes).unwrap(), Vec3::zero());
+    assert_near(constrain(Vec3::new(-1.0, 2.0, -1.0), &planes).unwrap(), Vec3::new(0.0, 2.0, 0.0));
+    assert_near(constrain(Vec3::splat(1.0), &planes).unwrap(), Vec3::splat(1.0));
+}
+
+#[test]
+fn test_render_uses_surface_materials() {
+    let mut god = GodObject::new(NewtonsPerMeter(1000.0));
+    let floor = floor();
+    let wall = Plane::from_point_normal(Vec3::zero(), Vec3::unit_x());
+    let surfaces: [&dyn ClosestPoint; 2] = [&floor, &wall];
+    let materials = [
+        ContactMaterial::new(NewtonsPerMeter(1000.0), NewtonSecondsPerMeter(0.0)),
+        ContactMaterial::new(NewtonsPerMeter(200.0), NewtonSecondsPerMeter(0.0)),
+    ];
+    let model = ContactModel::new(Newtons(100.0));
+    god.update(Meters3::new(0.01, 0.0, 0.01), &surfaces);
+
+    let device = Meters3::new(-0.01, 0.0, -0.01);
+    let force = god.render(device, MetersPerSecond3::ZERO, &surfaces, &materials, &model, TICK);
+    assert_force(force, Vec3::new(2.0, 0.0, 10.0));
+}
+
+#[test]
+fn test_render_pops_through_and_recovers() {
+    let mut god = GodObject::new(NewtonsPerMeter(1000.0));
+    let floor = floor();
+    let model = ContactModel::new(Newtons(100.0)).with_pop_through(Meters(0.01));
+    let still = MetersPerSecond3::ZERO;
+    god.update(Meters3::new(0.0, 0.0, 0.01), &[&floor]);
+
+    let force = god.render(Meters3::new(0.0, 0.0, -0.005), still, &[&floor], &[], &model, TICK);
+    assert!(force.0.z > 0.0);
+
+    let force = god.render(Meters3::new(0.0, 0.0, -0.02), still, &[&floor], &[], &model, TICK);
+    assert_eq!(force, Newtons3::ZERO);
+    assert_eq!(god.popped(), &[0]);
+    assert_near(god.proxy().0, Vec3::new(0.0, 0.0, -0.02));
+
+    // Still inside: the surface stays released
+    assert_eq!(god.render(Meters3::new(0.0, 0.0, -0.001), still, &[&floor], &[], &model, TICK), Newtons3::ZERO);
+
+    // Leaving restores it
+    god.render(Meters3::new(0.0, 0.0, 0.01), still, &[&floor], &[], &model, TICK);
+    assert!(god.popped().is_empty());
+    assert!(god.render(Meters3::new(0.0, 0.0, -0.005), still, &[&floor], &[], &model, TICK).0.z > 0.0);
+}
+
+#[test]
+fn test_render_adds_friction_while_sliding() {
+    let mut god = GodObject::new(NewtonsPerMeter(1000.0));
+    let floor = floor();
+    let materials =
+        [ContactMaterial::new(NewtonsPerMeter(1000.0), NewtonSecondsPerMeter(0.0)).with_friction(0.5, 0.5)];
+    let model = ContactModel::new(Newtons(100.0));
+    let still = MetersPerSecond3::ZERO;
+    god.update(Meters3::new(0.0, 0.0, 0.01), &[&floor]);
+    god.render(Meters3::new(0.0, 0.0, -0.004), still, &[&floor], &materials, &model, TICK);
+
+    // Normal force 4 N, so sticking resists up to 2 N of drag
+    let force = god.render(Meters3::new(0.001, 0.0, -0.004), still, &[&floor], &materials, &model, TICK);
+    assert_eq!(god.slip_state(0), SlipState::Stick);
+    assert!((force.0.x + 1.0).abs() < 0.05, "{force:?}");
+
+    let force = god.render(Meters3::new(0.01, 0.0, -0.004), still, &[&floor], &materials, &model, TICK);
+    assert_eq!(god.slip_state(0), SlipState::Slip);
+    assert!((force.0.x + 2.0).abs() < 0.05, "{force:?}");
+
+    god.render(Meters3::new(0.01, 0.0, 0.01), still, &[&floor], &materials, &model, TICK);
+    assert_eq!(god.slip_state(0), SlipState::Free);
+}
+
+#[test]
+fn test_render_materials_applies_texture() {
+    let floor = floor();
+    let model = ContactModel::new(Newtons(100.0));
+    let still = MetersPerSecond3::ZERO;
+    let smooth = [HapticMaterial::new(NewtonsPerMeter(1000.0), NewtonSecondsPerMeter(0.0), 0.0, 0.0)];
+    let grainy = [smooth[0].with_texture(Texture::new(0.3, Meters(0.004)))];
+
+    let mut forces = Vec::new();
+    for materials in [&smooth, &grainy] {
+        let mut god = GodObject::new(NewtonsPerMeter(1000.0));
+        god.update(Meters3::new(0.001, 0.001, 0.01), &[&floor]);
+        let device = Meters3::new(0.001, 0.001, -0.004);
+        forces.push(god.render_materials(device, still, &[&floor], materials, &model, TICK));
+    }
+    assert!((forces[0].0.z - 4.0).abs() < 0.02, "{:?}", forces);
+    // On a ridge along x and y, at zero along z: 1 + 0.3 * 2/3
+    assert!((forces[1].0.z / forces[0].0.z - 1.2).abs() < 5e-3, "{:?}", forces);
+}
+
+#[test]
+fn test_render_lod_flattens_texture() {
+    let floor = floor();
+    let model = ContactModel::new(Newtons(100.0));
+    let still = MetersPerSecond3::ZERO;
+    let grainy = [HapticMaterial::new(NewtonsPerMeter(1000.0), NewtonSecondsPerMeter(0.0), 0.0, 0.0)
+        .with_texture(Texture::new(0.3, Meters(0.004)))];
+    let device = Meters3::new(0.001, 0.001, -0.004);
+
+    let mut forces = Vec::new();
+    for detail in [1.0, 0.0] {
+        let mut god = GodObject::new(NewtonsPerMeter(1000.0));
+        god.update(Meters3::new(0.001, 0.001, 0.01), &[&floor]);
+        let lod = LodLevel { detail, ..LodLevel::FULL };
+        forces.push(god.render_lod(device, still, &[&floor], &grainy, &model, lod, TICK));
+    }
+    assert!((forces[0].0.z / forces[1].0.z - 1.2).abs() < 5e-3, "{:?}", forces);
+    assert!((forces[1].0.z - 4.0).abs() < 0.02, "{:?}", forces);
+}
+
+#[test]
+fn test_render_ramps_in_new_contacts() {
+    let floor = floor();
+    let model = ContactModel::new(Newtons(100.0)).with_ramp_in(Milliseconds(10_000.0));
+    let still = MetersPerSecond3::ZERO;
+    let materials = [ContactMaterial::new(NewtonsPerMeter(1000.0), NewtonSecondsPerMeter(0.0))];
+    let mut god = GodObject::new(NewtonsPerMeter(1000.0));
+    god.update(Meters3::new(0.0, 0.0, 0.01), &[&floor]);
+    // Hitting the surface deep on the first tick does not kick
+    let force = god.render(Meters3::new(0.0, 0.0, -0.004), still, &[&floor], &materials, &model, TICK);
+    assert!(force.0.z < 0.01, "{force:?}");
+    // Halfway through the ramp, however long rendering took, the force is half in
+    let force = god.render(Meters3::new(0.0, 0.0, -0.004), still, &[&floor], &materials, &model, Seconds(5.0));
+    assert!((force.0.z - 2.0).abs() < 0.02, "{force:?}");
+
+    let instant = ContactModel::new(Newtons(100.0));
+    let force = god.render(Meters3::new(0.0, 0.0, -0.004), still, &[&floor], &materials, &instant, TICK);
+    assert!((force.0.z - 4.0).abs() < 0.02, "{force:?}");
+}
@@ -0,0 +1,76 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+const TICK: Seconds = Seconds(0.001);
+
+fn moving(speed: f32) -> MetersPerSecond3 {
+    MetersPerSecond3::new(speed, 0.0, 0.0)
+}
+
+/// Runs `ticks` updates at a constant speed and returns the final level.
+fn settle(lod: &mut HapticLod, speed: f32, ticks: usize) -> LodLevel {
+    (0..ticks).map(|_| lod.update(moving(speed), TICK)).last().unwrap()
+}
+
+#[test]
+fn test_slow_exploration_is_full_fidelity() {
+    let mut lod = HapticLod::default();
+    let level = settle(&mut lod, 0.02, 500);
+    assert!(level.is_full());
+    assert_eq!(lod.filter(Newtons3::new(1.0, 0.0, 0.0), TICK), Newtons3::new(1.0, 0.0, 0.0));
+}
+
+#[test]
+fn test_fast_motion_reduces_detail() {
+    let mut lod = HapticLod::default();
+    let level = settle(&mut lod, 1.0, 500);
+    assert!((level.detail - 0.2).abs() < TEST_EPSILON);
+    assert!((level.cutoff.value() - 80.0).abs() < 0.1);
+    assert!((lod.speed().value() - 1.0).abs() < 1e-3);
+
+    let texture = level.texture(Texture::new(0.5, Meters(0.002)));
+    assert!((texture.amplitude - 0.1).abs() < TEST_EPSILON);
+    assert!((level.footprint(Meters(0.001)).value() - 0.005).abs() < TEST_EPSILON);
+
+    // Intermediate speeds interpolate
+    let middle = settle(&mut lod, 0.175, 1000);
+    assert!((middle.detail - 0.6).abs() < 1e-3, "{:?}", middle);
+}
+
+#[test]
+fn test_speed_is_smoothed() {
+    let mut lod = HapticLod::default();
+    settle(&mut lod, 0.0, 10);
+    // A single noisy sample does not drop the level
+    lod.update(moving(1.0), TICK);
+    assert!(lod.level().detail > 0.99);
+    // Detail returns once the tool slows down
+    settle(&mut lod, 1.0, 300);
+    assert!(lod.level().detail < 0.3);
+    settle(&mut lod, 0.0, 300);
+    assert!(lod.level().is_full());
+}
+
+#[test]
+fn test_filter_smooths_at_speed() {
+    let mut lod = HapticLod::default();
+    settle(&mut lod, 1.0, 500);
+    let first = lod.filter(Newtons3::new(1.0, 0.0, 0.0), TICK);
+    assert!(first.0.x > 0.0 && first.0.x < 0.5);
+    let settled = (0..200).map(|_| lod.filter(Newtons3::new(1.0, 0.0, 0.0), TICK)).last().unwrap();
+    assert!((settled.0.x - 1.0).abs() < 1e-3);
+
+    lod.reset();
+    assert_eq!(lod.level(), LodLevel::FULL);
+}
+
+#[test]
+fn test_config_from_keys() {
+    let mut config = Config::new();
+    assert_eq!(LodConfig::from_config(&config), LodConfig::default());
+    config.load_str("[lod]\nfast_m_per_s = 0.5\nmin_detail = 0.4\n").unwrap();
+    let lod = LodConfig::from_config(&config);
+    assert_eq!((lod.fast, lod.min_detail), (MetersPerSecond(0.5), 0.4));
+    assert_eq!(lod.slow, LodConfig::default().slow);
+}
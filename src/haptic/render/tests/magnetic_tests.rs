@@ -0,0 +1,50 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+fn well() -> MagneticWell {
+    MagneticWell::new(Vec3::zero(), Meters(0.02), Newtons(2.0))
+}
+
+#[test]
+fn test_pull_profile() {
+    let well = well();
+    assert!((well.core.value() - 0.005).abs() < TEST_EPSILON);
+    assert_eq!(well.pull(Meters(0.0)), Newtons::ZERO);
+    assert!((well.pull(Meters(0.0025)).value() - 1.0).abs() < TEST_EPSILON);
+    assert!((well.pull(Meters(0.005)).value() - 2.0).abs() < TEST_EPSILON);
+    assert!(well.pull(Meters(0.0199)).value() < 0.01);
+    assert_eq!(well.pull(Meters(0.03)), Newtons::ZERO);
+    // Monotonic decay between core and capture radius
+    let samples: Vec<f32> = (5..20).map(|mm| well.pull(Meters(mm as f32 * 0.001)).value()).collect();
+    assert!(samples.windows(2).all(|w| w[1] <= w[0]));
+}
+
+#[test]
+fn test_force_points_at_target() {
+    let well = well().with_damping(NewtonSecondsPerMeter(10.0));
+    let force = well.force(Meters3::new(0.005, 0.0, 0.0), MetersPerSecond3::ZERO);
+    assert!((force.0.x + 2.0).abs() < TEST_EPSILON && force.0.y.abs() < TEST_EPSILON);
+    let damped = well.force(Meters3::new(0.005, 0.0, 0.0), MetersPerSecond3::new(0.0, 0.1, 0.0));
+    assert!((damped.0.y + 1.0).abs() < TEST_EPSILON);
+    assert_eq!(well.force(Meters3::new(0.5, 0.0, 0.0), MetersPerSecond3::new(1.0, 0.0, 0.0)), Newtons3::ZERO);
+    assert_eq!(well.force(Meters3::ZERO, MetersPerSecond3::ZERO), Newtons3::ZERO);
+}
+
+#[test]
+fn test_strongest_of_overlapping_wells() {
+    let near = well();
+    let far = MagneticWell::new(Vec3::new(0.02, 0.0, 0.0), Meters(0.02), Newtons(2.0));
+    let wells = [near, far];
+    let device = Meters3::new(0.006, 0.0, 0.0);
+    assert_eq!(MagneticWell::strongest(&wells, device), Some(&near));
+    assert_eq!(MagneticWell::strongest(&wells, Meters3::new(0.015, 0.0, 0.0)), Some(&far));
+    assert_eq!(MagneticWell::strongest(&wells, Meters3::new(1.0, 0.0, 0.0)), None);
+}
+
+#[test]
+fn test_core_is_clamped() {
+    let well = well().with_core(Meters(1.0));
+    assert_eq!(well.core, well.radius);
+    assert!((well.pull(Meters(0.01)).value() - 1.0).abs() < TEST_EPSILON);
+}
@@ -0,0 +1,58 @@
+use super::*;
+use crate::scene::NodeBuilder;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+#[test]
+fn test_texture_modulation() {
+    assert_eq!(Texture::NONE.modulation(Vec3::new(0.3, 0.1, 0.2)), 1.0);
+    let texture = Texture::new(0.3, Meters(0.004));
+    assert!((texture.modulation(Vec3::zero()) - 1.0).abs() < TEST_EPSILON);
+    // Quarter period along every axis is the top of a ridge
+    assert!((texture.modulation(Vec3::splat(0.001)) - 1.3).abs() < TEST_EPSILON);
+    assert!((texture.modulation(Vec3::splat(0.003)) - 0.7).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_contact_material_conversion() {
+    let contact: ContactMaterial = HapticMaterial::RUBBER.into();
+    assert_eq!(contact.stiffness, HapticMaterial::RUBBER.stiffness);
+    assert_eq!((contact.static_friction, contact.kinetic_friction), (0.9, 0.7));
+
+    // Sliding never grips harder than sticking
+    let odd = HapticMaterial::new(NewtonsPerMeter(100.0), NewtonSecondsPerMeter(0.0), 0.2, 0.5);
+    assert_eq!(ContactMaterial::from(odd).kinetic_friction, 0.2);
+    assert_eq!(ContactMaterial::from(HapticMaterial::default()), ContactMaterial::default());
+}
+
+#[test]
+fn test_library_names_and_ids() {
+    let mut library = MaterialLibrary::new();
+    let rubber = library.add("rubber", HapticMaterial::RUBBER);
+    let metal = library.add("metal", HapticMaterial::METAL);
+    assert_ne!(rubber, metal);
+    assert_eq!(library.id("metal"), Some(metal));
+    assert_eq!(library.name(rubber), Some("rubber"));
+    assert_eq!(library.add("rubber", HapticMaterial::WOOD), rubber);
+    assert_eq!(library.get(rubber), Some(&HapticMaterial::WOOD));
+    assert_eq!(library.len(), 2);
+}
+
+#[test]
+fn test_lookup_per_node() {
+    let mut scene = Scene::new();
+    let plain = NodeBuilder::panel().touchable().friction(0.6).build(&mut scene, None).unwrap();
+    let knob = NodeBuilder::panel().touchable().build(&mut scene, None).unwrap();
+    let ghost = NodeBuilder::panel().intangible().build(&mut scene, None).unwrap();
+
+    let mut library = MaterialLibrary::new();
+    let metal = library.add("metal", HapticMaterial::METAL);
+    library.assign(knob, metal);
+    library.assign(ghost, metal);
+
+    assert_eq!(library.lookup(&scene, knob), Some(HapticMaterial::METAL));
+    assert_eq!(library.lookup(&scene, plain).unwrap().dynamic_friction, 0.6);
+    assert_eq!(library.lookup(&scene, ghost), None);
+    assert_eq!(library.unassign(knob), Some(metal));
+    assert_eq!(library.lookup(&scene, knob), Some(HapticMaterial::default()));
+}
@@ -0,0 +1,180 @@
+use super::*;
+
+fn test_config(rate: f32) -> LoopConfig {
+    // Tests run unprivileged and in parallel; do not fight the scheduler
+    LoopConfig { realtime: false, ..LoopConfig::with_rate(Hertz(rate)) }
+}
+
+#[test]
+fn test_snapshot_publish_and_read() {
+    let snapshot = Snapshot::new(1);
+    assert_eq!(snapshot.read(), 1);
+    assert_eq!(snapshot.version(), 0);
+
+    assert!(snapshot.publish(2));
+    assert!(snapshot.publish(3));
+    assert_eq!(snapshot.read(), 3);
+    assert_eq!(snapshot.version(), 2);
+}
+
+#[test]
+fn test_snapshot_read_if_newer() {
+    let snapshot = Snapshot::new(0);
+    let mut local = 0;
+    assert_eq!(snapshot.read_if_newer(0, &mut local), None);
+
+    snapshot.publish(7);
+    assert_eq!(snapshot.read_if_newer(0, &mut local), Some(1));
+    assert_eq!(local, 7);
+    assert_eq!(snapshot.read_if_newer(1, &mut local), None);
+}
+
+#[test]
+fn test_snapshot_try_read_never_waits() {
+    let snapshot = Snapshot::new(1);
+    snapshot.publish(2);
+    assert_eq!(snapshot.try_read(), Some(2));
+
+    // A writer filling the front slot makes the reader give up instead of waiting
+    let front = snapshot.front.load(Ordering::Acquire);
+    let _writing = snapshot.slots[front].lock().unwrap();
+    assert_eq!(snapshot.try_read(), None);
+}
+
+#[test]
+fn test_snapshot_readers_fall_back_to_last_value() {
+    let snapshot = Snapshot::new(1);
+    snapshot.publish(2);
+    let mut local = 0;
+    let front = snapshot.front.load(Ordering::Acquire);
+    let writing = snapshot.slots[front].lock().unwrap();
+
+    // The servo side keeps its copy and tries again later; `read` takes the other slot
+    assert_eq!(snapshot.read_if_newer(0, &mut local), None);
+    assert_eq!(local, 0);
+    assert_eq!(snapshot.read(), 1);
+
+    drop(writing);
+    assert_eq!(snapshot.read_if_newer(0, &mut local), Some(1));
+    assert_eq!(local, 2);
+}
+
+#[test]
+fn test_config_period() {
+    assert_eq!(LoopConfig::default().period(), Duration::from_millis(1));
+    assert_eq!(LoopConfig::with_rate(Hertz(500.0)).period(), Duration::from_millis(2));
+}
+
+#[test]
+fn test_config_rate_from_config() {
+    let mut config = Config::new();
+    assert_eq!(LoopConfig::from_config(&config), LoopConfig::default());
+    config.set(crate::core::Layer::Cli, SERVO_RATE_HZ, crate::core::ConfigValue::Float(500.0));
+    assert_eq!(LoopConfig::from_config(&config).period(), Duration::from_millis(2));
+}
+
+#[test]
+fn test_jitter_meter() {
+    let mut meter = JitterMeter::new();
+    assert_eq!(meter.stats(false), LoopStats::default());
+
+    meter.record(Duration::from_micros(30));
+    meter.record(Duration::from_micros(40));
+    meter.record_missed(2);
+    let stats = meter.stats(true);
+
+    assert_eq!(stats.ticks, 2);
+    assert_eq!(stats.missed, 2);
+    assert!(stats.realtime);
+    assert!((stats.mean_jitter.as_secs_f64() - 35e-6).abs() < 1e-9);
+    assert!((stats.rms_jitter.as_secs_f64() - 1250e-12_f64.sqrt()).abs() < 1e-9);
+    assert!((stats.max_jitter.as_secs_f64() - 40e-6).abs() < 1e-9);
+}
+
+#[test]
+fn test_loop_ticks_and_exchanges_state() {
+    let mut servo = HapticLoop::spawn(test_config(1000.0), 2u64, 0u64, |tick, step, total| {
+        assert!(tick.dt.value() > 0.0);
+        *total += *step;
+    })
+    .unwrap();
+
+    thread::sleep(Duration::from_millis(20));
+    servo.set_input(0);
+    let frozen = {
+        thread::sleep(Duration::from_millis(10));
+        servo.output()
+    };
+    thread::sleep(Duration::from_millis(10));
+    let stats = servo.stop();
+
+    assert!(!servo.is_running());
+    assert!(stats.ticks >= 10, "only {} ticks", stats.ticks);
+    assert!(frozen > 0 && frozen % 2 == 0);
+    assert_eq!(servo.output(), frozen);
+}
+
+#[test]
+fn test_loop_follows_config_rate() {
+    let mut config = Config::new();
+    let servo = HapticLoop::spawn(test_config(1000.0), (), Seconds(0.0), |tick, _, dt| *dt = tick.dt).unwrap();
+    let subscription = servo.follow_config(&mut config);
+    config.set(crate::core::Layer::Cli, SERVO_RATE_HZ, crate::core::ConfigValue::Float(250.0));
+    assert_eq!(servo.rate(), Hertz(250.0));
+
+    thread::sleep(Duration::from_millis(30));
+    assert!((servo.output().value() - 0.004).abs() < 1e-6);
+
+    // Values a file or flag can spell but the loop cannot run at are ignored
+    for raw in ["inf", "nan", "0"] {
+        config.set_raw(crate::core::Layer::Cli, SERVO_RATE_HZ, raw).unwrap();
+        assert_eq!(servo.rate(), Hertz(250.0));
+    }
+
+    // Removing the override falls back to the lower layers
+    config.unset(crate::core::Layer::Cli, SERVO_RATE_HZ);
+    assert_eq!(servo.rate(), Hertz(DEFAULT_SERVO_RATE_HZ as f32));
+
+    // A dropped loop is no longer written to, and its subscription can be removed
+    drop(servo);
+    config.set(crate::core::Layer::Cli, SERVO_RATE_HZ, crate::core::ConfigValue::Float(500.0));
+    assert!(config.unsubscribe(subscription));
+}
+
+#[test]
+fn test_drop_stops_thread() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let servo = HapticLoop::spawn(test_config(500.0), (), (), move |tick, _, _| {
+        let _ = tx.send(tick.index);
+    })
+    .unwrap();
+    assert!(rx.recv_timeout(Duration::from_secs(1)).is_ok());
+    drop(servo);
+
+    // Drop joined the thread, so its sender is gone and the channel closes once drained
+    while rx.try_recv().is_ok() {}
+    assert_eq!(rx.try_recv(), Err(std::sync::mpsc::TryRecvError::Disconnected));
+}
+
+#[test]
+fn test_unusable_rates_are_rejected_or_clamped() {
+    assert_eq!(LoopConfig::with_rate(Hertz(f32::INFINITY)), LoopConfig::default());
+    assert_eq!(LoopConfig::with_rate(Hertz(f32::NAN)), LoopConfig::default());
+    assert_eq!(LoopConfig::with_rate(Hertz(1e9)).rate, MAX_RATE);
+    let raw = LoopConfig { rate: Hertz(f32::INFINITY), ..LoopConfig::default() };
+    assert_eq!(raw.period(), Duration::from_millis(1));
+
+    let servo = HapticLoop::spawn(test_config(1000.0), (), 0u64, |_, _, ticks| *ticks += 1).unwrap();
+    servo.set_rate(Hertz(f32::INFINITY));
+    servo.set_rate(Hertz(f32::NAN));
+    assert_eq!(servo.rate(), Hertz(1000.0));
+    servo.set_rate(Hertz(1e9));
+    assert_eq!(servo.rate(), MAX_RATE);
+
+    // The thread survives the fastest rate and keeps ticking
+    thread::sleep(Duration::from_millis(20));
+    let ticks = servo.output();
+    thread::sleep(Duration::from_millis(20));
+    assert!(servo.is_running());
+    assert!(servo.output() > ticks);
+}
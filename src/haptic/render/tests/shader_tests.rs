@@ -0,0 +1,74 @@
+use super::*;
+use crate::core::MetersPerSecond3;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+/// Device pressed `depth` into the floor under `point`.
+fn pressing(surface: usize, point: Vec3, depth: f32) -> ContactState {
+    ContactState::new(surface, point, Vec3::unit_z(), point - Vec3::unit_z() * depth, MetersPerSecond3::ZERO)
+}
+
+#[test]
+fn test_registry_routes_by_surface() {
+    let mut registry = ShaderRegistry::new();
+    registry.assign(1, |_: &ContactState, _: f32| Vec3::unit_x());
+    let shared: Arc<dyn ForceShader> = Arc::new(|c: &ContactState, dt: f32| c.normal * dt);
+    registry.assign_shared(2, shared.clone());
+    registry.assign_shared(3, shared);
+    assert_eq!(registry.len(), 3);
+
+    let contacts = [pressing(0, Vec3::zero(), 0.001), pressing(1, Vec3::zero(), 0.001), pressing(3, Vec3::zero(), 0.0)];
+    assert_eq!(registry.shade(contacts, 0.5), Newtons3::new(1.0, 0.0, 0.5));
+    assert!(registry.remove(1).is_some());
+    assert_eq!(registry.shade(contacts, 0.0), Newtons3::ZERO);
+}
+
+#[test]
+fn test_non_finite_output_is_dropped() {
+    let mut registry = ShaderRegistry::new();
+    registry.assign(0, |_: &ContactState, _: f32| Vec3::new(f32::NAN, 0.0, 0.0));
+    assert_eq!(registry.shade([pressing(0, Vec3::zero(), 0.001)], 0.001), Newtons3::ZERO);
+}
+
+#[test]
+fn test_pulsing_alarm() {
+    let alarm = PulsingAlarm::new(Newtons(2.0), Hertz(4.0));
+    assert!(alarm.level(0).value().abs() < TEST_EPSILON);
+    assert!((alarm.level(125_000).value() - 2.0).abs() < TEST_EPSILON);
+    assert!(alarm.level(250_000).value().abs() < TEST_EPSILON);
+
+    let force = alarm.shade(&pressing(0, Vec3::zero(), 0.001), 0.001);
+    assert!(force.x == 0.0 && force.y == 0.0 && (0.0..=2.0).contains(&force.z));
+    assert_eq!(alarm.shade(&pressing(0, Vec3::zero(), -0.001), 0.001), Vec3::zero());
+}
+
+#[test]
+fn test_directional_grain() {
+    let grain = DirectionalGrain::new(Vec3::new(2.0, 0.0, 1.0), Meters(0.004), Newtons(0.5));
+    // Projected into the floor, the ridges run along y
+    let bump = grain.shade(&pressing(0, Vec3::new(0.001, 0.3, 0.0), 0.001), 0.001);
+    assert!((bump.x + 0.5).abs() < TEST_EPSILON && bump.y.abs() < TEST_EPSILON && bump.z.abs() < TEST_EPSILON);
+    let along = grain.shade(&pressing(0, Vec3::new(0.0, 0.001, 0.0), 0.001), 0.001);
+    assert!(along.length() < TEST_EPSILON);
+
+    let straight_down = DirectionalGrain::new(Vec3::unit_z(), Meters(0.004), Newtons(0.5));
+    assert_eq!(straight_down.shade(&pressing(0, Vec3::new(0.001, 0.0, 0.0), 0.001), 0.001), Vec3::zero());
+}
+
+#[test]
+fn test_shades_god_object_contacts() {
+    use crate::geometry::{ClosestPoint, Plane};
+    use crate::render::GodObject;
+    use crate::core::{Meters3, NewtonsPerMeter};
+
+    let floor = Plane::from_point_normal(Vec3::zero(), Vec3::unit_z());
+    let surfaces: [&dyn ClosestPoint; 1] = [&floor];
+    let mut god = GodObject::new(NewtonsPerMeter(1000.0));
+    god.update(Meters3::new(0.0, 0.0, 0.01), &surfaces);
+    god.update(Meters3::new(0.0, 0.0, -0.002), &surfaces);
+
+    let mut registry = ShaderRegistry::new();
+    registry.assign(0, |c: &ContactState, _: f32| c.normal * c.penetration.value() * 100.0);
+    let force = registry.shade(god.contact_states(Meters3::new(0.0, 0.0, -0.002), MetersPerSecond3::ZERO), 0.001);
+    assert!((force.0.z - 0.2).abs() < 1e-2, "{:?}", force);
+}
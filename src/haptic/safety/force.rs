@@ -0,0 +1,607 @@
+//! The last stage between force rendering and the motors.
+//!
+//! [`ForceSafety`] limits every commanded force: it clamps the magnitude to what the
+//! device may output, limits how fast the force can change (so a rendering glitch
+//! becomes a ramp rather than a kick), refuses non-finite values, and restarts from
+//! zero when the servo loop has stalled. A gripper force passes the same limiters
+//! on a channel of its own. [`SafeDevice`] wraps a device so that its `set_force`
+//! and `set_gripper_force` go through this stage and only exposes the device
+//! read-only. The [`DeviceManager`](crate::device::DeviceManager) wraps every device
+//! it is given, so the output path through it cannot skip the stage; only a device
+//! the application opens and commands itself, without wrapping it, is unlimited.
+//!
+//! An emergency stop can be raised from any thread through a [`SafetyHandle`], and
+//! [`install_panic_hook`] raises it whenever any thread panics. A stop latches: the
+//! output stays at zero until [`ForceSafety::reset`]. The hook only raises the stop;
+//! the zero force itself comes from [`SafeDevice`]'s drop, which runs as a panicking
+//! servo thread unwinds. Applications built with `panic = "abort"` skip those drops:
+//! the process ends with the hook, and zeroing the motors is then left to the
+//! device's driver or firmware noticing that its host went away, so pair that
+//! strategy with hardware that does. A [`Watchdog`] thread notices a servo loop
+//! that stopped calling in entirely and runs a fallback, such as cutting amplifier
+//! power, from outside that loop.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use super::log::{ReportedEvent, ReportedSnapshot, SafetyReporter};
+use crate::core::constants::{
+    DEFAULT_SAFETY_MAX_FORCE_N, DEFAULT_SAFETY_MAX_FORCE_RATE_N_PER_S, DEFAULT_SERVO_RATE_HZ, MAX_SERVO_RATE_HZ,
+    SAFETY_MAX_FORCE_N, SAFETY_MAX_FORCE_RATE_N_PER_S, SERVO_RATE_HZ,
+};
+use crate::core::{Config, ConfigValue, Hertz, Newtons, Newtons3, NewtonsPerSecond, SessionClock};
+use crate::device::{DeviceCapabilities, DeviceError, DeviceState, GripperState, HapticDevice};
+use crate::effects::HapticSample;
+
+// ============================================================================
+// Limits
+// ============================================================================
+
+/// Caps on every device's force from the `safety.*` config keys, and the rate forces
+/// are commanded at from `servo.rate_hz`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SafetyCaps {
+    /// `safety.max_force_n`.
+    pub max_force: Newtons,
+    /// `safety.max_force_rate_n_per_s`.
+    pub max_slew: NewtonsPerSecond,
+    /// `servo.rate_hz`, which sets the watchdog timeout.
+    pub servo_rate: Hertz,
+}
+
+impl SafetyCaps {
+    /// The keys' built-in defaults.
+    pub const DEFAULT: Self = Self {
+        max_force: Newtons(DEFAULT_SAFETY_MAX_FORCE_N as f32),
+        max_slew: NewtonsPerSecond(DEFAULT_SAFETY_MAX_FORCE_RATE_N_PER_S as f32),
+        servo_rate: Hertz(DEFAULT_SERVO_RATE_HZ as f32),
+    };
+
+    pub fn from_config(config: &Config) -> Self {
+        let mut caps = Self::DEFAULT;
+        for key in [SAFETY_MAX_FORCE_N, SAFETY_MAX_FORCE_RATE_N_PER_S, SERVO_RATE_HZ] {
+            if let Some(value) = config.get(key) {
+                caps.apply(key, Some(value));
+            }
+        }
+        caps
+    }
+
+    /// Takes a changed config value, returning whether it changed the caps; None,
+    /// for a removed key, restores its default. Other keys, non-numbers, negative
+    /// or non-finite values and a zero rate are ignored; rates above
+    /// `MAX_SERVO_RATE_HZ` are clamped to it.
+    pub fn apply(&mut self, key: &str, value: Option<&ConfigValue>) -> bool {
+        let v = match value {
+            Some(value) => value.as_tuning(),
+            None => match key {
+                SAFETY_MAX_FORCE_N => Some(DEFAULT_SAFETY_MAX_FORCE_N as f32),
+                SAFETY_MAX_FORCE_RATE_N_PER_S => Some(DEFAULT_SAFETY_MAX_FORCE_RATE_N_PER_S as f32),
+                SERVO_RATE_HZ => Some(DEFAULT_SERVO_RATE_HZ as f32),
+                _ => None,
+            },
+        };
+        let Some(v) = v else {
+            return false;
+        };
+        let before = *self;
+        match key {
+            SAFETY_MAX_FORCE_N => self.max_force = Newtons(v),
+            SAFETY_MAX_FORCE_RATE_N_PER_S => self.max_slew = NewtonsPerSecond(v),
+            SERVO_RATE_HZ if v > 0.0 => self.servo_rate = Hertz(v.min(MAX_SERVO_RATE_HZ as f32)),
+            _ => {}
+        }
+        *self != before
+    }
+}
+
+impl Default for SafetyCaps {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Output limits enforced by [`ForceSafety`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ForceLimits {
+    pub max_force: Newtons,
+    /// Fastest allowed change of the force vector.
+    pub max_slew: NewtonsPerSecond,
+    /// Gap between two commands after which the loop counts as stalled.
+    pub watchdog_timeout: Duration,
+}
+
+impl ForceLimits {
+    /// [`capped`](Self::capped) by the default [`SafetyCaps`].
+    pub fn for_device(capabilities: &DeviceCapabilities) -> Self {
+        Self::capped(capabilities, &SafetyCaps::DEFAULT)
+    }
+
+    /// The device's peak force, reachable from rest in 10 ms, within `caps`, and a
+    /// watchdog of five periods of the servo rate.
+    ///
+    /// The watchdog follows the rate forces are commanded at, not the device's
+    /// update rate: a device filled by its own faster callback, or commanded once a
+    /// frame, would otherwise trip it on every late tick.
+    pub fn capped(capabilities: &DeviceCapabilities, caps: &SafetyCaps) -> Self {
+        let rate = caps.servo_rate.value();
+        let rate = if rate.is_finite() { rate.clamp(1.0, MAX_SERVO_RATE_HZ as f32) } else { DEFAULT_SERVO_RATE_HZ as f32 };
+        let max_force = capabilities.max_force.min(caps.max_force);
+        Self {
+            max_force,
+            max_slew: NewtonsPerSecond(max_force.value() * 100.0).min(caps.max_slew),
+            watchdog_timeout: Duration::from_secs_f32(5.0 / rate),
+        }
+    }
+
+    /// [`gripper_capped`](Self::gripper_capped) at the default servo rate.
+    pub fn for_gripper(capabilities: &DeviceCapabilities) -> Self {
+        Self::gripper_capped(capabilities, &SafetyCaps::DEFAULT)
+    }
+
+    /// The gripper's rated force, zero without an actuated gripper, reachable from
+    /// rest in 10 ms; only the watchdog comes from `caps`.
+    pub fn gripper_capped(capabilities: &DeviceCapabilities, caps: &SafetyCaps) -> Self {
+        let max_force = capabilities.gripper.map_or(Newtons::ZERO, |g| g.max_force);
+        Self {
+            max_force,
+            max_slew: NewtonsPerSecond(max_force.value() * 100.0),
+            ..Self::capped(capabilities, caps)
+        }
+    }
+}
+
+impl Default for ForceLimits {
+    fn default() -> Self {
+        Self::for_device(&DeviceCapabilities::KINESTHETIC)
+    }
+}
+
+// ============================================================================
+// Shared State
+// ============================================================================
+
+#[derive(Debug)]
+struct Shared {
+    stopped: AtomicBool,
+    heartbeat_us: AtomicU64,
+}
+
+/// Cloneable handle for stopping output and watching the servo loop from any thread.
+#[derive(Debug, Clone)]
+pub struct SafetyHandle {
+    shared: Arc<Shared>,
+}
+
+impl SafetyHandle {
+    fn new() -> Self {
+        Self { shared: Arc::new(Shared { stopped: AtomicBool::new(false), heartbeat_us: AtomicU64::new(0) }) }
+    }
+
+    /// Forces zero output until the owning [`ForceSafety`] is reset.
+    pub fn emergency_stop(&self) {
+        self.shared.stopped.store(true, Ordering::Release);
+    }
+
+    #[inline]
+    pub fn is_stopped(&self) -> bool {
+        self.shared.stopped.load(Ordering::Acquire)
+    }
+
+    /// Timestamp of the last filtered command, in the caller's microsecond clock.
+    #[inline]
+    pub fn heartbeat_us(&self) -> u64 {
+        self.shared.heartbeat_us.load(Ordering::Acquire)
+    }
+}
+
+/// Makes every panic, on any thread, raise an emergency stop on `handle` before the
+/// previously installed hook runs.
+///
+/// The stop keeps every other [`ForceSafety`] sharing `handle` at zero; the
+/// panicking thread's own devices are zeroed by [`SafeDevice`]'s drop as it unwinds,
+/// which only happens when panics unwind rather than abort.
+pub fn install_panic_hook(handle: SafetyHandle) {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        handle.emergency_stop();
+        previous(info);
+    }));
+}
+
+// ============================================================================
+// Force Safety
+// ============================================================================
+
+/// Limiters that report once per activation rather than every tick.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Limiting {
+    magnitude: bool,
+    slew: bool,
+    watchdog: bool,
+}
+
+/// One limited output, the force vector or the gripper force along X.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Channel {
+    output: Newtons3,
+    last_us: Option<u64>,
+    limiting: Limiting,
+}
+
+/// Names a channel's reports go by.
+#[derive(Debug)]
+struct ChannelNames {
+    max_force: &'static str,
+    slew_rate: &'static str,
+    watchdog: &'static str,
+}
+
+const FORCE: ChannelNames = ChannelNames { max_force: "max_force", slew_rate: "slew_rate", watchdog: "servo" };
+const GRIPPER: ChannelNames =
+    ChannelNames { max_force: "gripper_max_force", slew_rate: "gripper_slew_rate", watchdog: "gripper" };
+
+/// Mandatory limiter stage for commanded forces.
+#[derive(Debug)]
+pub struct ForceSafety {
+    limits: ForceLimits,
+    gripper_limits: ForceLimits,
+    handle: SafetyHandle,
+    force: Channel,
+    gripper: Channel,
+    reporter: Option<SafetyReporter>,
+    snapshot: ReportedSnapshot,
+}
+
+impl ForceSafety {
+    /// Limits the force vector; the gripper is held at zero until given limits
+    /// with [`with_gripper_limits`](Self::with_gripper_limits).
+    pub fn new(limits: ForceLimits) -> Self {
+        let gripper_limits = ForceLimits { max_force: Newtons::ZERO, max_slew: NewtonsPerSecond::ZERO, ..limits };
+        Self {
+            limits,
+            gripper_limits,
+            handle: SafetyHandle::new(),
+            force: Channel::default(),
+            gripper: Channel::default(),
+            reporter: None,
+            snapshot: ReportedSnapshot::default(),
+        }
+    }
+
+    /// Replaces the force limits, e.g. when the config changes; the output keeps
+    /// ramping from where it is.
+    pub fn set_limits(&mut self, limits: ForceLimits) {
+        self.limits = limits;
+    }
+
+    pub fn with_gripper_limits(mut self, limits: ForceLimits) -> Self {
+        self.gripper_limits = limits;
+        self
+    }
+
+    /// As [`set_limits`](Self::set_limits), for the gripper channel.
+    pub fn set_gripper_limits(&mut self, limits: ForceLimits) {
+        self.gripper_limits = limits;
+    }
+
+    /// Shares `handle`'s stop and heartbeat with another stage, such as the
+    /// [`SafeDevice`] of the device a servo callback commands, so that a stop raised
+    /// through either one zeroes both.
+    pub fn with_handle(mut self, handle: SafetyHandle) -> Self {
+        self.handle = handle;
+        self
+    }
+
+    /// Reports limiter activations, watchdog trips and faults to a safety log.
+    pub fn with_reporter(mut self, reporter: SafetyReporter) -> Self {
+        self.reporter = Some(reporter);
+        self
+    }
+
+    #[inline]
+    pub fn limits(&self) -> &ForceLimits {
+        &self.limits
+    }
+
+    #[inline]
+    pub fn gripper_limits(&self) -> &ForceLimits {
+        &self.gripper_limits
+    }
+
+    pub fn handle(&self) -> SafetyHandle {
+        self.handle.clone()
+    }
+
+    #[inline]
+    pub fn is_stopped(&self) -> bool {
+        self.handle.is_stopped()
+    }
+
+    /// Last force let through.
+    #[inline]
+    pub fn output(&self) -> Newtons3 {
+        self.force.output
+    }
+
+    /// Last gripper force let through.
+    #[inline]
+    pub fn gripper_output(&self) -> Newtons {
+        Newtons(self.gripper.output.0.x)
+    }
+
+    /// Clears an emergency stop. Output ramps up again from zero.
+    pub fn reset(&mut self, now_us: u64) {
+        let was_stopped = self.handle.shared.stopped.swap(false, Ordering::AcqRel);
+        self.force = Channel::default();
+        self.gripper = Channel::default();
+        if was_stopped {
+            self.report(now_us, ReportedEvent::FaultRecovery { code: "estop", action: "reset" });
+        }
+    }
+
+    /// Records the device state attached to reported events.
+    pub fn observe(&mut self, device: &str, state: &DeviceState) {
+        if *self.snapshot.device != *device {
+            self.snapshot.device = device.into();
+        }
+        self.snapshot.position = state.position.0;
+        self.snapshot.velocity = state.velocity.0;
+    }
+
+    /// The force actually allowed out for `requested` at time `now_us`. The first
+    /// command after creation or a reset outputs zero; one after a stall ramps up
+    /// again from zero.
+    pub fn filter(&mut self, requested: Newtons3, now_us: u64) -> Newtons3 {
+        self.snapshot.force = requested.0;
+        self.limit(false, requested, now_us, ReportedEvent::NonFiniteForce { force: requested.0 })
+    }
+
+    /// The gripper force allowed out for `requested`, limited, ramped and watched
+    /// like [`filter`](Self::filter) with the gripper limits.
+    pub fn filter_gripper(&mut self, requested: Newtons, now_us: u64) -> Newtons {
+        let fault = ReportedEvent::NonFiniteGripperForce { force: requested.value() };
+        Newtons(self.limit(true, Newtons3::new(requested.value(), 0.0, 0.0), now_us, fault).0.x)
+    }
+
+    /// Runs one channel's limiters. A non-finite command raises the stop and reports
+    /// `fault`. Nothing here allocates, so it is safe to call from the servo tick.
+    fn limit(&mut self, gripper: bool, requested: Newtons3, now_us: u64, fault: ReportedEvent) -> Newtons3 {
+        self.handle.shared.heartbeat_us.store(now_us, Ordering::Release);
+        let (channel, limits, names) = if gripper {
+            (&mut self.gripper, &self.gripper_limits, &GRIPPER)
+        } else {
+            (&mut self.force, &self.limits, &FORCE)
+        };
+        let report = |event| {
+            if let Some(reporter) = &self.reporter {
+                reporter.report(now_us, event, &self.snapshot);
+            }
+        };
+        let previous_us = channel.last_us.replace(now_us);
+        if self.handle.is_stopped() {
+            channel.output = Newtons3::ZERO;
+            return channel.output;
+        }
+        let r = requested.0;
+        if !(r.x.is_finite() && r.y.is_finite() && r.z.is_finite()) {
+            self.handle.emergency_stop();
+            channel.output = Newtons3::ZERO;
+            report(fault);
+            return channel.output;
+        }
+
+        let timeout = limits.watchdog_timeout.as_micros() as u64;
+        let mut elapsed = previous_us.map_or(0, |t| now_us.saturating_sub(t));
+        let tripped = elapsed > timeout;
+        if tripped {
+            // The motors may still hold a stale force; ramp again from nothing, by
+            // no more than one timeout's worth however long the stall was
+            channel.output = Newtons3::ZERO;
+            if !channel.limiting.watchdog {
+                let late_by_us = elapsed - timeout;
+                report(ReportedEvent::WatchdogTrip { watchdog: names.watchdog, late_by_us });
+            }
+            elapsed = timeout;
+        }
+        channel.limiting.watchdog = tripped;
+
+        let clamped = requested.clamp_length(limits.max_force);
+        let clamping = clamped != requested;
+        if clamping && !channel.limiting.magnitude {
+            let (requested, applied) = (requested.length().value(), clamped.length().value());
+            report(ReportedEvent::LimiterActivation { limiter: names.max_force, requested, applied });
+        }
+        channel.limiting.magnitude = clamping;
+
+        let dt = elapsed as f32 * 1e-6;
+        let max_step = Newtons(limits.max_slew.value() * dt);
+        let step = clamped - channel.output;
+        let slewing = step.length().value() > max_step.value();
+        if slewing && !channel.limiting.slew {
+            let requested = if dt > 0.0 { step.length().value() / dt } else { f32::INFINITY };
+            let applied = limits.max_slew.value();
+            report(ReportedEvent::LimiterActivation { limiter: names.slew_rate, requested, applied });
+        }
+        channel.limiting.slew = slewing;
+
+        channel.output += step.clamp_length(max_step);
+        channel.output
+    }
+
+    fn report(&self, now_us: u64, event: ReportedEvent) {
+        if let Some(reporter) = &self.reporter {
+            reporter.report(now_us, event, &self.snapshot);
+        }
+    }
+}
+
+// ============================================================================
+// Safe Device
+// ============================================================================
+
+/// A device whose forces all pass through a [`ForceSafety`] stage.
+pub struct SafeDevice<D: HapticDevice> {
+    device: D,
+    safety: ForceSafety,
+    clock: SessionClock,
+}
+
+impl<D: HapticDevice> SafeDevice<D> {
+    /// Wraps `device` with force and gripper limits derived from its capabilities.
+    pub fn new(device: D) -> Self {
+        let capabilities = device.capabilities();
+        let safety = ForceSafety::new(ForceLimits::for_device(&capabilities))
+            .with_gripper_limits(ForceLimits::for_gripper(&capabilities));
+        Self::with_safety(device, safety)
+    }
+
+    pub fn with_safety(device: D, safety: ForceSafety) -> Self {
+        Self { device, safety, clock: SessionClock::new() }
+    }
+
+    #[inline]
+    pub fn device(&self) -> &D {
+        &self.device
+    }
+
+    #[inline]
+    pub fn safety(&self) -> &ForceSafety {
+        &self.safety
+    }
+
+    pub fn handle(&self) -> SafetyHandle {
+        self.safety.handle()
+    }
+
+    /// Microseconds since the device was wrapped; the clock used for filtering.
+    pub fn now_us(&self) -> u64 {
+        self.clock.now_us()
+    }
+
+    /// The clock [`now_us`](Self::now_us) reads, for stages sharing [`handle`](Self::handle).
+    #[inline]
+    pub fn clock(&self) -> SessionClock {
+        self.clock
+    }
+
+    pub fn set_limits(&mut self, limits: ForceLimits) {
+        self.safety.set_limits(limits);
+    }
+
+    pub fn set_gripper_limits(&mut self, limits: ForceLimits) {
+        self.safety.set_gripper_limits(limits);
+    }
+
+    /// Clears an emergency stop.
+    pub fn reset(&mut self) {
+        let now = self.now_us();
+        self.safety.reset(now);
+    }
+}
+
+impl<D: HapticDevice> HapticDevice for SafeDevice<D> {
+    fn name(&self) -> &str {
+        self.device.name()
+    }
+
+    fn capabilities(&self) -> DeviceCapabilities {
+        self.device.capabilities()
+    }
+
+    fn poll(&mut self) -> Result<DeviceState, DeviceError> {
+        let state = self.device.poll()?;
+        self.safety.observe(self.device.name(), &state);
+        Ok(state)
+    }
+
+    fn set_force(&mut self, force: Newtons3) -> Result<(), DeviceError> {
+        let now = self.now_us();
+        let output = self.safety.filter(force, now);
+        self.device.set_force(output)
+    }
+
+    fn poll_gripper(&mut self) -> Result<GripperState, DeviceError> {
+        self.device.poll_gripper()
+    }
+
+    fn set_gripper_force(&mut self, force: Newtons) -> Result<(), DeviceError> {
+        let now = self.now_us();
+        let output = self.safety.filter_gripper(force, now);
+        self.device.set_gripper_force(output)
+    }
+
+    fn set_vibration(&mut self, actuator: usize, sample: HapticSample) -> Result<(), DeviceError> {
+        if self.safety.is_stopped() {
+            return self.device.set_vibration(actuator, HapticSample::SILENT);
+        }
+        self.device.set_vibration(actuator, sample)
+    }
+}
+
+impl<D: HapticDevice> Drop for SafeDevice<D> {
+    fn drop(&mut self) {
+        if self.device.capabilities().renders_force() {
+            let _ = self.device.set_force(Newtons3::ZERO);
+        }
+        if self.device.capabilities().renders_gripper_force() {
+            let _ = self.device.set_gripper_force(Newtons::ZERO);
+        }
+    }
+}
+
+// ============================================================================
+// Watchdog
+// ============================================================================
+
+/// Thread that runs `on_stall` when the servo loop stops filtering forces.
+///
+/// Heartbeats are the timestamps passed to [`ForceSafety::filter`], so `now_us`
+/// must read the same clock (for a [`SafeDevice`], its [`SafeDevice::now_us`]).
+pub struct Watchdog {
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Watchdog {
+    /// Checks every `timeout / 2`. `on_stall` receives how long the loop has been
+    /// silent and runs once per stall; the first heartbeat arms the watchdog.
+    pub fn spawn(
+        handle: SafetyHandle,
+        timeout: Duration,
+        now_us: impl Fn() -> u64 + Send + 'static,
+        mut on_stall: impl FnMut(Duration) + Send + 'static,
+    ) -> std::io::Result<Self> {
+        let running = Arc::new(AtomicBool::new(true));
+        let run = running.clone();
+        let thread = thread::Builder::new().name("haptic-watchdog".into()).spawn(move || {
+            let timeout_us = timeout.as_micros() as u64;
+            let mut tripped_at = None;
+            while run.load(Ordering::Acquire) {
+                thread::sleep(timeout / 2);
+                let beat = handle.heartbeat_us();
+                let silent = now_us().saturating_sub(beat);
+                if beat > 0 && silent > timeout_us && tripped_at != Some(beat) {
+                    tripped_at = Some(beat);
+                    on_stall(Duration::from_micros(silent));
+                }
+            }
+        })?;
+        Ok(Self { running, thread: Some(thread) })
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/force_tests.rs"]
+mod tests;
@@ -0,0 +1,465 @@
+//! Tamper-evident, append-only log of haptic safety events.
+//!
+//! Every limiter activation, watchdog trip, fault and recovery is written as one
+//! text line carrying a sequence number, timestamp, event details and a snapshot of
+//! the device state. Each line ends with a SHA-256 hash over its content and the
+//! previous line's hash, so editing, reordering or removing interior records
+//! breaks the chain and is detected by [`verify`].
+//!
+//! Records cut off the end leave a valid, shorter chain. To catch that, keep the
+//! log's [`head`](SafetyLog::head) (record count and last hash) somewhere the log's
+//! writer cannot alter, and check the log against it with [`verify_anchored`].
+//!
+//! Real-time threads report through a [`SafetyReporter`], which pushes plain-data
+//! [`ReportedEvent`]s onto a bounded queue without allocating or blocking; the owner
+//! of the log turns them into records with [`SafetyLog::flush_pending`]. Reports that
+//! find the queue full are counted and logged as a fault on the next flush.
+
+use crate::core::{Newtons, Newtons3, Vec3};
+use sha2::{Digest, Sha256};
+use std::fmt::{self, Write as _};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::Arc;
+
+/// Hash preceding the first record of a log.
+pub const GENESIS_HASH: [u8; 32] = [0; 32];
+
+// ============================================================================
+// Events
+// ============================================================================
+
+/// A safety-relevant event.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SafetyEvent {
+    /// A limiter reduced a commanded value (force, force rate, velocity...).
+    LimiterActivation { limiter: String, requested: f32, applied: f32 },
+    /// A watchdog missed its deadline.
+    WatchdogTrip { watchdog: String, late_by_us: u64 },
+    /// A fault was detected and output was disabled or reduced.
+    Fault { code: String, message: String },
+    /// The system recovered from a fault.
+    FaultRecovery { code: String, action: String },
+}
+
+impl SafetyEvent {
+    /// Short tag used in the log format.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SafetyEvent::LimiterActivation { .. } => "limiter",
+            SafetyEvent::WatchdogTrip { .. } => "watchdog",
+            SafetyEvent::Fault { .. } => "fault",
+            SafetyEvent::FaultRecovery { .. } => "recovery",
+        }
+    }
+}
+
+/// An event as reported from a real-time thread. Plain data, so reporting it never
+/// allocates; the log spells it out as a [`SafetyEvent`] when writing the record.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportedEvent {
+    LimiterActivation { limiter: &'static str, requested: f32, applied: f32 },
+    WatchdogTrip { watchdog: &'static str, late_by_us: u64 },
+    /// A commanded force vector was not finite.
+    NonFiniteForce { force: Vec3 },
+    /// A commanded gripper force was not finite.
+    NonFiniteGripperForce { force: f32 },
+    FaultRecovery { code: &'static str, action: &'static str },
+}
+
+impl From<ReportedEvent> for SafetyEvent {
+    fn from(event: ReportedEvent) -> Self {
+        match event {
+            ReportedEvent::LimiterActivation { limiter, requested, applied } => {
+                SafetyEvent::LimiterActivation { limiter: limiter.into(), requested, applied }
+            }
+            ReportedEvent::WatchdogTrip { watchdog, late_by_us } => {
+                SafetyEvent::WatchdogTrip { watchdog: watchdog.into(), late_by_us }
+            }
+            ReportedEvent::NonFiniteForce { force } => SafetyEvent::Fault {
+                code: "non_finite_force".into(),
+                message: format!("commanded force {} is not finite", Newtons3(force)),
+            },
+            ReportedEvent::NonFiniteGripperForce { force } => SafetyEvent::Fault {
+                code: "non_finite_gripper_force".into(),
+                message: format!("commanded gripper force {} is not finite", Newtons(force)),
+            },
+            ReportedEvent::FaultRecovery { code, action } => {
+                SafetyEvent::FaultRecovery { code: code.into(), action: action.into() }
+            }
+        }
+    }
+}
+
+/// Device state at the time of an event.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeviceSnapshot {
+    pub device: String,
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub force: Vec3,
+}
+
+/// [`DeviceSnapshot`] as kept by a real-time thread; cloning it never allocates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportedSnapshot {
+    pub device: Arc<str>,
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub force: Vec3,
+}
+
+impl Default for ReportedSnapshot {
+    fn default() -> Self {
+        Self { device: Arc::from(""), position: Vec3::zero(), velocity: Vec3::zero(), force: Vec3::zero() }
+    }
+}
+
+impl From<&ReportedSnapshot> for DeviceSnapshot {
+    fn from(snapshot: &ReportedSnapshot) -> Self {
+        Self {
+            device: snapshot.device.to_string(),
+            position: snapshot.position,
+            velocity: snapshot.velocity,
+            force: snapshot.force,
+        }
+    }
+}
+
+/// A record as written to the log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SafetyRecord {
+    pub sequence: u64,
+    pub timestamp_us: u64,
+    pub event: SafetyEvent,
+    pub snapshot: DeviceSnapshot,
+    pub prev_hash: [u8; 32],
+    pub hash: [u8; 32],
+}
+
+impl SafetyRecord {
+    /// Line content covered by the hash (everything but the trailing hash field).
+    fn body(&self) -> String {
+        let mut line = format!("{}\t{}\t{}\t", self.sequence, self.timestamp_us, self.event.kind());
+        match &self.event {
+            SafetyEvent::LimiterActivation { limiter, requested, applied } => {
+                let _ = write!(line, "limiter={};requested={};applied={}", escape(limiter), requested, applied);
+            }
+            SafetyEvent::WatchdogTrip { watchdog, late_by_us } => {
+                let _ = write!(line, "watchdog={};late_by_us={}", escape(watchdog), late_by_us);
+            }
+            SafetyEvent::Fault { code, message } => {
+                let _ = write!(line, "code={};message={}", escape(code), escape(message));
+            }
+            SafetyEvent::FaultRecovery { code, action } => {
+                let _ = write!(line, "code={};action={}", escape(code), escape(action));
+            }
+        }
+        let s = &self.snapshot;
+        let _ = write!(
+            line,
+            "\tdevice={};p={},{},{};v={},{},{};f={},{},{}\t{}",
+            escape(&s.device),
+            s.position.x, s.position.y, s.position.z,
+            s.velocity.x, s.velocity.y, s.velocity.z,
+            s.force.x, s.force.y, s.force.z,
+            to_hex(&self.prev_hash)
+        );
+        line
+    }
+
+    /// The full log line, without the newline.
+    pub fn to_line(&self) -> String {
+        format!("{}\t{}", self.body(), to_hex(&self.hash))
+    }
+}
+
+/// Escapes the field separators so free text cannot forge extra fields.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            ';' => out.push_str("\\;"),
+            '=' => out.push_str("\\="),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn chain_hash(body: &str) -> [u8; 32] {
+    Sha256::digest(body.as_bytes()).into()
+}
+
+fn to_hex(bytes: &[u8; 32]) -> String {
+    bytes.iter().fold(String::with_capacity(64), |mut s, b| {
+        let _ = write!(s, "{:02x}", b);
+        s
+    })
+}
+
+fn from_hex(text: &str) -> Option<[u8; 32]> {
+    if text.len() != 64 || !text.is_ascii() {
+        return None;
+    }
+    let mut out = [0; 32];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&text[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(out)
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Errors while writing or verifying a safety log.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SafetyLogError {
+    Io(String),
+    /// A line does not have the expected fields. Lines are numbered from 1.
+    Malformed { line: usize },
+    /// The sequence number is not one more than the previous record's.
+    Sequence { line: usize, expected: u64, found: u64 },
+    /// The record does not reference the previous record's hash.
+    BrokenChain { line: usize },
+    /// The record content does not match its hash.
+    HashMismatch { line: usize },
+    /// The log has fewer records than its anchor.
+    Truncated { expected: u64, found: u64 },
+    /// The anchored record is not the one in the log.
+    AnchorMismatch { line: usize },
+}
+
+impl fmt::Display for SafetyLogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SafetyLogError::Io(message) => write!(f, "safety log I/O error: {}", message),
+            SafetyLogError::Malformed { line } => write!(f, "line {}: malformed record", line),
+            SafetyLogError::Sequence { line, expected, found } => {
+                write!(f, "line {}: expected sequence {}, found {}", line, expected, found)
+            }
+            SafetyLogError::BrokenChain { line } => write!(f, "line {}: hash chain broken", line),
+            SafetyLogError::HashMismatch { line } => write!(f, "line {}: record was modified", line),
+            SafetyLogError::Truncated { expected, found } => {
+                write!(f, "log truncated: expected at least {} records, found {}", expected, found)
+            }
+            SafetyLogError::AnchorMismatch { line } => write!(f, "line {}: record differs from the anchor", line),
+        }
+    }
+}
+
+impl std::error::Error for SafetyLogError {}
+
+impl From<io::Error> for SafetyLogError {
+    fn from(e: io::Error) -> Self {
+        SafetyLogError::Io(e.to_string())
+    }
+}
+
+/// Summary of a successfully verified log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyReport {
+    pub records: u64,
+    pub last_hash: [u8; 32],
+}
+
+// ============================================================================
+// Log
+// ============================================================================
+
+/// Reports a log holds until the next flush; further ones are only counted.
+pub const REPORT_QUEUE_LEN: usize = 1024;
+
+type Pending = (u64, ReportedEvent, ReportedSnapshot);
+
+/// Cloneable handle for reporting events from any thread, including real-time ones:
+/// it neither allocates nor blocks.
+#[derive(Debug, Clone)]
+pub struct SafetyReporter {
+    sender: SyncSender<Pending>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl SafetyReporter {
+    /// Queues an event. Returns false if the log has been dropped, or if its queue
+    /// is full, in which case the next flush records how many reports were lost.
+    pub fn report(&self, timestamp_us: u64, event: ReportedEvent, snapshot: &ReportedSnapshot) -> bool {
+        match self.sender.try_send((timestamp_us, event, snapshot.clone())) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                false
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        }
+    }
+}
+
+/// Append-only hash-chained safety log.
+pub struct SafetyLog<W: Write> {
+    writer: W,
+    next_sequence: u64,
+    last_hash: [u8; 32],
+    sender: SyncSender<Pending>,
+    receiver: Receiver<Pending>,
+    /// Reports refused because the queue was full.
+    dropped: Arc<AtomicU64>,
+}
+
+impl<W: Write> SafetyLog<W> {
+    /// Starts a new log on `writer`.
+    pub fn new(writer: W) -> Self {
+        Self::resume(writer, 0, GENESIS_HASH)
+    }
+
+    /// Continues an existing chain whose last record had sequence `next_sequence - 1`.
+    pub fn resume(writer: W, next_sequence: u64, last_hash: [u8; 32]) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(REPORT_QUEUE_LEN);
+        Self { writer, next_sequence, last_hash, sender, receiver, dropped: Arc::default() }
+    }
+
+    /// Handle for reporting events from real-time threads.
+    pub fn reporter(&self) -> SafetyReporter {
+        SafetyReporter { sender: self.sender.clone(), dropped: self.dropped.clone() }
+    }
+
+    /// Appends a record and flushes it to the writer.
+    pub fn record(&mut self, timestamp_us: u64, event: SafetyEvent, snapshot: DeviceSnapshot) -> io::Result<SafetyRecord> {
+        let mut record = SafetyRecord {
+            sequence: self.next_sequence,
+            timestamp_us,
+            event,
+            snapshot,
+            prev_hash: self.last_hash,
+            hash: GENESIS_HASH,
+        };
+        record.hash = chain_hash(&record.body());
+        writeln!(self.writer, "{}", record.to_line())?;
+        self.writer.flush()?;
+        self.next_sequence += 1;
+        self.last_hash = record.hash;
+        Ok(record)
+    }
+
+    /// Writes all events queued by reporters, followed by a `reports_dropped` fault
+    /// if any found the queue full. Returns the number of records written.
+    pub fn flush_pending(&mut self) -> io::Result<usize> {
+        let mut written = 0;
+        let mut last_us = 0;
+        while let Ok((timestamp_us, event, snapshot)) = self.receiver.try_recv() {
+            self.record(timestamp_us, event.into(), DeviceSnapshot::from(&snapshot))?;
+            last_us = timestamp_us;
+            written += 1;
+        }
+        let dropped = self.dropped.swap(0, Ordering::Relaxed);
+        if dropped > 0 {
+            let message = format!("{} reports did not fit the queue of {}", dropped, REPORT_QUEUE_LEN);
+            let event = SafetyEvent::Fault { code: "reports_dropped".into(), message };
+            self.record(last_us, event, DeviceSnapshot::default())?;
+            written += 1;
+        }
+        Ok(written)
+    }
+
+    #[inline]
+    pub fn next_sequence(&self) -> u64 {
+        self.next_sequence
+    }
+
+    #[inline]
+    pub fn last_hash(&self) -> [u8; 32] {
+        self.last_hash
+    }
+
+    /// Record count and last hash, to keep outside the log as an anchor for
+    /// [`verify_anchored`].
+    #[inline]
+    pub fn head(&self) -> VerifyReport {
+        VerifyReport { records: self.next_sequence, last_hash: self.last_hash }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl SafetyLog<File> {
+    /// Opens (or creates) a log file in append mode, verifying the existing chain first.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SafetyLogError> {
+        let path = path.as_ref();
+        let report = match File::open(path) {
+            Ok(file) => verify(BufReader::new(file))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => VerifyReport { records: 0, last_hash: GENESIS_HASH },
+            Err(e) => return Err(e.into()),
+        };
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::resume(file, report.records, report.last_hash))
+    }
+}
+
+/// Checks every record's hash, sequence and link to its predecessor.
+pub fn verify<R: BufRead>(reader: R) -> Result<VerifyReport, SafetyLogError> {
+    verify_each(reader, |_| Ok(()))
+}
+
+/// Like [`verify`], and also checks that the log still holds the records of
+/// `anchor`, a [`head`](SafetyLog::head) kept earlier; records appended since
+/// are allowed.
+pub fn verify_anchored<R: BufRead>(reader: R, anchor: &VerifyReport) -> Result<VerifyReport, SafetyLogError> {
+    let report = verify_each(reader, |report| {
+        if report.records == anchor.records && report.last_hash != anchor.last_hash {
+            return Err(SafetyLogError::AnchorMismatch { line: report.records as usize });
+        }
+        Ok(())
+    })?;
+    if report.records < anchor.records {
+        return Err(SafetyLogError::Truncated { expected: anchor.records, found: report.records });
+    }
+    Ok(report)
+}
+
+/// Verifies the chain, calling `check` with the running report after each record.
+fn verify_each<R: BufRead>(
+    reader: R,
+    mut check: impl FnMut(&VerifyReport) -> Result<(), SafetyLogError>,
+) -> Result<VerifyReport, SafetyLogError> {
+    let mut report = VerifyReport { records: 0, last_hash: GENESIS_HASH };
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let number = i + 1;
+        let (body, hash) = line.rsplit_once('\t').ok_or(SafetyLogError::Malformed { line: number })?;
+        let hash = from_hex(hash).ok_or(SafetyLogError::Malformed { line: number })?;
+        let fields: Vec<&str> = body.split('\t').collect();
+        if fields.len() != 6 {
+            return Err(SafetyLogError::Malformed { line: number });
+        }
+        let sequence: u64 = fields[0].parse().map_err(|_| SafetyLogError::Malformed { line: number })?;
+        let prev = from_hex(fields[5]).ok_or(SafetyLogError::Malformed { line: number })?;
+
+        if chain_hash(body) != hash {
+            return Err(SafetyLogError::HashMismatch { line: number });
+        }
+        if sequence != report.records {
+            return Err(SafetyLogError::Sequence { line: number, expected: report.records, found: sequence });
+        }
+        if prev != report.last_hash {
+            return Err(SafetyLogError::BrokenChain { line: number });
+        }
+        report.records += 1;
+        report.last_hash = hash;
+        check(&report)?;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+#[path = "tests/log_tests.rs"]
+mod tests;
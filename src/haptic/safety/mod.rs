@@ -0,0 +1,9 @@
+// src/haptic/safety/mod.rs
+pub mod force;
+pub mod log;
+pub use force::{install_panic_hook, ForceLimits, ForceSafety, SafeDevice, SafetyCaps, SafetyHandle, Watchdog};
+pub use log::{
+    verify as verify_safety_log, verify_anchored as verify_safety_log_anchored, DeviceSnapshot, ReportedEvent,
+    ReportedSnapshot, SafetyEvent, SafetyLog, SafetyLogError, SafetyRecord, SafetyReporter, VerifyReport, GENESIS_HASH,
+    REPORT_QUEUE_LEN,
+};
@@ -0,0 +1,322 @@
+use super::*;
+use crate::core::{Meters, Meters3};
+use crate::device::{GripperCapabilities, MockDevice};
+use crate::safety::SafetyLog;
+use std::sync::{mpsc, Mutex};
+use std::time::Instant;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+/// Limits with a 1 N/ms slew rate and a 5 ms watchdog.
+fn limits() -> ForceLimits {
+    ForceLimits {
+        max_force: Newtons(3.0),
+        max_slew: NewtonsPerSecond(1000.0),
+        watchdog_timeout: Duration::from_millis(5),
+    }
+}
+
+#[test]
+fn test_limits_within_config_caps() {
+    use crate::core::{Config, ConfigValue, Layer};
+
+    let mut config = Config::new();
+    assert_eq!(SafetyCaps::from_config(&config), SafetyCaps::DEFAULT);
+    config.set(Layer::Cli, "safety.max_force_n", ConfigValue::Float(1.5));
+    let caps = SafetyCaps::from_config(&config);
+    let limits = ForceLimits::capped(&DeviceCapabilities::KINESTHETIC, &caps);
+    assert_eq!(limits.max_force, Newtons(1.5));
+    assert_eq!(limits.max_slew, NewtonsPerSecond(150.0));
+    // A cap above what the device can do leaves the device's limit
+    let weak = DeviceCapabilities { max_force: Newtons(1.0), ..DeviceCapabilities::KINESTHETIC };
+    assert_eq!(ForceLimits::for_device(&weak).max_force, Newtons(1.0));
+    assert_eq!(ForceLimits::for_device(&DeviceCapabilities::KINESTHETIC).max_slew, NewtonsPerSecond(300.0));
+
+    // The watchdog follows the servo rate, not the device's update rate
+    config.set(Layer::Cli, "servo.rate_hz", ConfigValue::Float(250.0));
+    let caps = SafetyCaps::from_config(&config);
+    let fast = DeviceCapabilities { update_rate: Hertz(4000.0), ..DeviceCapabilities::KINESTHETIC };
+    assert_eq!(ForceLimits::capped(&fast, &caps).watchdog_timeout, Duration::from_millis(20));
+    let mut caps = caps;
+    assert!(!caps.apply("servo.rate_hz", Some(&ConfigValue::Float(0.0))));
+    assert!(caps.apply("servo.rate_hz", Some(&ConfigValue::Float(500.0))));
+    assert_eq!(caps.servo_rate, Hertz(500.0));
+    assert!(caps.apply("servo.rate_hz", None));
+    assert_eq!(caps.servo_rate, SafetyCaps::DEFAULT.servo_rate);
+}
+
+/// Filters `force` once per millisecond, `ticks` times, starting at `start_us`.
+fn run(safety: &mut ForceSafety, force: Newtons3, start_us: u64, ticks: u64) -> Newtons3 {
+    (0..ticks).fold(Newtons3::ZERO, |_, i| safety.filter(force, start_us + i * 1000))
+}
+
+#[test]
+fn test_clamps_magnitude() {
+    let mut safety = ForceSafety::new(limits());
+    let output = run(&mut safety, Newtons3::new(0.0, 10.0, 0.0), 0, 10);
+    assert!((output.0.y - 3.0).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_limits_slew_rate() {
+    let mut safety = ForceSafety::new(limits());
+    let step = Newtons3::new(2.5, 0.0, 0.0);
+    assert_eq!(safety.filter(step, 0), Newtons3::ZERO);
+    assert!((safety.filter(step, 1000).0.x - 1.0).abs() < TEST_EPSILON);
+    assert!((safety.filter(step, 2000).0.x - 2.0).abs() < TEST_EPSILON);
+    assert!((safety.filter(step, 3000).0.x - 2.5).abs() < TEST_EPSILON);
+
+    // Releasing is slew limited too
+    assert!((safety.filter(Newtons3::ZERO, 4000).0.x - 1.5).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_non_finite_latches_stop() {
+    let mut safety = ForceSafety::new(limits());
+    run(&mut safety, Newtons3::new(1.0, 0.0, 0.0), 0, 3);
+
+    assert_eq!(safety.filter(Newtons3::new(f32::NAN, 0.0, 0.0), 3000), Newtons3::ZERO);
+    assert!(safety.is_stopped());
+    assert_eq!(run(&mut safety, Newtons3::new(1.0, 0.0, 0.0), 4000, 5), Newtons3::ZERO);
+
+    safety.reset(9000);
+    assert!(!safety.is_stopped());
+    assert!(run(&mut safety, Newtons3::new(1.0, 0.0, 0.0), 10_000, 3).0.x > 0.0);
+}
+
+#[test]
+fn test_emergency_stop_from_other_thread() {
+    let mut safety = ForceSafety::new(limits());
+    run(&mut safety, Newtons3::new(1.0, 0.0, 0.0), 0, 3);
+
+    let handle = safety.handle();
+    thread::spawn(move || handle.emergency_stop()).join().unwrap();
+    assert_eq!(safety.filter(Newtons3::new(1.0, 0.0, 0.0), 3000), Newtons3::ZERO);
+}
+
+#[test]
+fn test_shared_handle_stops_both_stages() {
+    let mut device = ForceSafety::new(limits());
+    let mut servo = ForceSafety::new(limits()).with_handle(device.handle());
+    run(&mut servo, Newtons3::new(1.0, 0.0, 0.0), 0, 3);
+    assert!(servo.output().length().value() > 0.0);
+
+    // A fault in the servo stage stops the device's stage too, and a reset of either clears both
+    assert_eq!(servo.filter(Newtons3::new(f32::NAN, 0.0, 0.0), 3000), Newtons3::ZERO);
+    assert!(device.is_stopped());
+    device.reset(3000);
+    assert!(!servo.is_stopped());
+    device.handle().emergency_stop();
+    assert_eq!(servo.filter(Newtons3::new(1.0, 0.0, 0.0), 4000), Newtons3::ZERO);
+}
+
+#[test]
+fn test_stall_restarts_from_zero() {
+    let mut log = SafetyLog::new(Vec::new());
+    // 0.2 N/ms, so 2 N takes 10 ms to reach
+    let slow = ForceLimits { max_slew: NewtonsPerSecond(200.0), ..limits() };
+    let mut safety = ForceSafety::new(slow).with_reporter(log.reporter());
+    let force = Newtons3::new(2.0, 0.0, 0.0);
+    run(&mut safety, force, 0, 15);
+    assert!((safety.output().0.x - 2.0).abs() < TEST_EPSILON);
+
+    // 20 ms without a command, then the loop resumes from zero, one timeout along
+    assert!((safety.filter(force, 34_000).0.x - 1.0).abs() < TEST_EPSILON);
+    assert!((safety.filter(force, 35_000).0.x - 1.2).abs() < TEST_EPSILON);
+
+    log.flush_pending().unwrap();
+    let text = String::from_utf8(log.into_inner()).unwrap();
+    assert!(text.contains("watchdog=servo;late_by_us=15000"), "{text}");
+}
+
+#[test]
+fn test_frame_rate_commands_still_ramp() {
+    let mut log = SafetyLog::new(Vec::new());
+    let limits = ForceLimits::for_device(&DeviceCapabilities::KINESTHETIC);
+    let mut safety = ForceSafety::new(limits).with_reporter(log.reporter());
+    let force = Newtons3::new(1.0, 0.0, 0.0);
+    // 60 Hz against a 1 kHz servo rate stalls on every call, yet force gets out
+    let outputs: Vec<_> = (0..60).map(|i| safety.filter(force, i * 16_667)).collect();
+    assert!(outputs[1..].iter().all(|f| (f.0.x - 1.0).abs() < TEST_EPSILON));
+    log.flush_pending().unwrap();
+    let text = String::from_utf8(log.into_inner()).unwrap();
+    assert_eq!(text.matches("watchdog=servo").count(), 1, "{text}");
+
+    // At a servo rate matching the commands the watchdog stays quiet
+    let caps = SafetyCaps { servo_rate: Hertz(60.0), ..SafetyCaps::DEFAULT };
+    let limits = ForceLimits::capped(&DeviceCapabilities::KINESTHETIC, &caps);
+    let mut log = SafetyLog::new(Vec::new());
+    let mut safety = ForceSafety::new(limits).with_reporter(log.reporter());
+    let outputs: Vec<_> = (0..60).map(|i| safety.filter(force, i * 16_667)).collect();
+    assert!((outputs[59].0.x - 1.0).abs() < TEST_EPSILON);
+    log.flush_pending().unwrap();
+    let text = String::from_utf8(log.into_inner()).unwrap();
+    assert!(!text.contains("watchdog=servo"), "{text}");
+}
+
+#[test]
+fn test_limiters_report_once_per_activation() {
+    let mut log = SafetyLog::new(Vec::new());
+    let mut safety = ForceSafety::new(limits()).with_reporter(log.reporter());
+    run(&mut safety, Newtons3::new(10.0, 0.0, 0.0), 0, 20);
+    run(&mut safety, Newtons3::new(1.0, 0.0, 0.0), 20_000, 5);
+    run(&mut safety, Newtons3::new(10.0, 0.0, 0.0), 25_000, 5);
+
+    assert_eq!(log.flush_pending().unwrap(), 5);
+    let text = String::from_utf8(log.into_inner()).unwrap();
+    assert_eq!(text.matches("limiter=max_force").count(), 2);
+    assert_eq!(text.matches("limiter=slew_rate").count(), 3);
+}
+
+/// Device recording every commanded force into a shared log.
+struct RecordingDevice {
+    forces: Arc<Mutex<Vec<Newtons3>>>,
+}
+
+impl HapticDevice for RecordingDevice {
+    fn name(&self) -> &str {
+        "recording"
+    }
+
+    fn capabilities(&self) -> DeviceCapabilities {
+        DeviceCapabilities::KINESTHETIC
+    }
+
+    fn poll(&mut self) -> Result<DeviceState, DeviceError> {
+        Ok(DeviceState { position: Meters3::new(0.0, 0.0, 0.01), ..DeviceState::default() })
+    }
+
+    fn set_force(&mut self, force: Newtons3) -> Result<(), DeviceError> {
+        self.forces.lock().unwrap().push(force);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_safe_device_filters_and_zeroes_on_drop() {
+    let forces = Arc::new(Mutex::new(Vec::new()));
+    let mut device = SafeDevice::new(RecordingDevice { forces: forces.clone() });
+    device.poll().unwrap();
+    for _ in 0..5 {
+        device.set_force(Newtons3::new(0.0, 0.0, 50.0)).unwrap();
+        thread::sleep(Duration::from_millis(1));
+    }
+    assert!(forces.lock().unwrap().iter().all(|f| f.length().value() <= 3.3 + TEST_EPSILON));
+
+    drop(device);
+    assert_eq!(forces.lock().unwrap().last(), Some(&Newtons3::ZERO));
+}
+
+#[test]
+fn test_safe_device_zeroes_when_servo_thread_panics() {
+    let forces = Arc::new(Mutex::new(Vec::new()));
+    let device = SafeDevice::new(RecordingDevice { forces: forces.clone() });
+    let servo = thread::spawn(move || {
+        let mut device = device;
+        device.set_force(Newtons3::new(1.0, 0.0, 0.0)).unwrap();
+        panic!("rendering bug");
+    });
+    assert!(servo.join().is_err());
+    assert_eq!(forces.lock().unwrap().last(), Some(&Newtons3::ZERO));
+}
+
+#[test]
+fn test_gripper_is_limited_like_force() {
+    let mut log = SafetyLog::new(Vec::new());
+    let gripper = ForceLimits { max_force: Newtons(2.0), watchdog_timeout: Duration::from_millis(1), ..limits() };
+    let mut safety = ForceSafety::new(limits()).with_gripper_limits(gripper).with_reporter(log.reporter());
+    assert_eq!(safety.filter_gripper(Newtons(-5.0), 0), Newtons::ZERO);
+    assert!((safety.filter_gripper(Newtons(-5.0), 1000).value() + 1.0).abs() < TEST_EPSILON);
+    assert!((safety.filter_gripper(Newtons(-5.0), 2000).value() + 2.0).abs() < TEST_EPSILON);
+    assert!((safety.filter_gripper(Newtons(-5.0), 3000).value() + 2.0).abs() < TEST_EPSILON);
+
+    // A stalled gripper channel restarts from zero, whatever the force channel does
+    run(&mut safety, Newtons3::new(1.0, 0.0, 0.0), 3000, 20);
+    assert!((safety.filter_gripper(Newtons(-5.0), 23_000).value() + 1.0).abs() < TEST_EPSILON);
+    assert!((safety.output().0.x - 1.0).abs() < TEST_EPSILON);
+
+    log.flush_pending().unwrap();
+    let text = String::from_utf8(log.into_inner()).unwrap();
+    assert!(text.contains("limiter=gripper_max_force"), "{text}");
+    assert!(text.contains("limiter=gripper_slew_rate"), "{text}");
+    assert!(text.contains("watchdog=gripper;late_by_us=19000"), "{text}");
+}
+
+#[test]
+fn test_non_finite_gripper_latches_stop() {
+    let mut safety = ForceSafety::new(limits()).with_gripper_limits(limits());
+    run(&mut safety, Newtons3::new(1.0, 0.0, 0.0), 0, 3);
+    assert_eq!(safety.filter_gripper(Newtons(f32::NAN), 3000), Newtons::ZERO);
+    assert!(safety.is_stopped());
+    assert_eq!(safety.filter(Newtons3::new(1.0, 0.0, 0.0), 4000), Newtons3::ZERO);
+    assert_eq!(safety.filter_gripper(Newtons(1.0), 4000), Newtons::ZERO);
+}
+
+#[test]
+fn test_safe_device_limits_gripper_force() {
+    let gripper = GripperCapabilities { max_gap: Meters(0.025), max_force: Newtons(8.0) };
+    let mock = MockDevice::new("sigma.7", DeviceCapabilities::KINESTHETIC.with_gripper(gripper));
+    let mut device = SafeDevice::new(mock);
+    assert_eq!(device.safety().gripper_limits().max_force, Newtons(8.0));
+    for _ in 0..15 {
+        device.set_gripper_force(Newtons(-20.0)).unwrap();
+        thread::sleep(Duration::from_millis(1));
+    }
+    let force = device.device().gripper_force();
+    assert!(force.value() <= 0.0 && force.value() >= -8.0 - TEST_EPSILON, "{force}");
+
+    device.set_gripper_force(Newtons(f32::NAN)).unwrap();
+    assert_eq!(device.device().gripper_force(), Newtons::ZERO);
+    assert!(device.safety().is_stopped());
+    device.set_gripper_force(Newtons(2.0)).unwrap();
+    assert_eq!(device.device().gripper_force(), Newtons::ZERO);
+}
+
+#[test]
+fn test_fast_gripper_tolerates_late_servo_ticks() {
+    let gripper = GripperCapabilities { max_gap: Meters(0.025), max_force: Newtons(8.0) };
+    let fast = DeviceCapabilities { update_rate: Hertz(4000.0), ..DeviceCapabilities::KINESTHETIC };
+    let fast = fast.with_gripper(gripper);
+    let mut log = SafetyLog::new(Vec::new());
+    let mut safety = ForceSafety::new(ForceLimits::for_device(&fast))
+        .with_gripper_limits(ForceLimits::for_gripper(&fast))
+        .with_reporter(log.reporter());
+    // A 1 kHz loop running 1 ms late now and then
+    for i in 0..20u64 {
+        let now_us = i * 1000 + if i % 5 == 0 { 1000 } else { 0 };
+        safety.filter(Newtons3::new(1.0, 0.0, 0.0), now_us);
+        safety.filter_gripper(Newtons(-1.0), now_us);
+    }
+    log.flush_pending().unwrap();
+    let text = String::from_utf8(log.into_inner()).unwrap();
+    assert!(!text.contains("watchdog="), "{text}");
+
+    let caps = SafetyCaps { servo_rate: Hertz(250.0), ..SafetyCaps::DEFAULT };
+    assert_eq!(ForceLimits::gripper_capped(&fast, &caps).watchdog_timeout, Duration::from_millis(20));
+}
+
+#[test]
+fn test_panic_hook_raises_stop() {
+    let safety = ForceSafety::new(limits());
+    install_panic_hook(safety.handle());
+    assert!(thread::spawn(|| panic!("ui thread bug")).join().is_err());
+    assert!(safety.is_stopped());
+}
+
+#[test]
+fn test_watchdog_detects_stall() {
+    let mut safety = ForceSafety::new(limits());
+    let epoch = Instant::now();
+    let clock = move || epoch.elapsed().as_micros() as u64 + 1;
+    let (tx, rx) = mpsc::channel();
+    let _watchdog = Watchdog::spawn(safety.handle(), Duration::from_millis(5), clock, move |silent| {
+        let _ = tx.send(silent);
+    })
+    .unwrap();
+
+    safety.filter(Newtons3::ZERO, clock());
+    let silent = rx.recv_timeout(Duration::from_secs(1)).expect("watchdog did not trip");
+    assert!(silent > Duration::from_millis(5));
+    // One report per stall
+    assert!(rx.recv_timeout(Duration::from_millis(20)).is_err());
+}
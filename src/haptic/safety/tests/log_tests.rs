@@ -0,0 +1,141 @@
+use super::*;
+use std::io::Cursor;
+
+fn snapshot() -> DeviceSnapshot {
+    DeviceSnapshot {
+        device: "stylus-0".to_string(),
+        position: Vec3::new(0.01, 0.02, -0.03),
+        velocity: Vec3::zero(),
+        force: Vec3::new(0.0, 0.0, 2.9),
+    }
+}
+
+fn sample_log() -> String {
+    let mut log = SafetyLog::new(Vec::new());
+    log.record(100, SafetyEvent::LimiterActivation { limiter: "force".into(), requested: 4.2, applied: 3.0 }, snapshot())
+        .unwrap();
+    log.record(250, SafetyEvent::WatchdogTrip { watchdog: "servo".into(), late_by_us: 1800 }, snapshot()).unwrap();
+    log.record(300, SafetyEvent::Fault { code: "E12".into(), message: "encoder\tglitch; count=0".into() }, snapshot())
+        .unwrap();
+    log.record(900, SafetyEvent::FaultRecovery { code: "E12".into(), action: "rehomed".into() }, snapshot()).unwrap();
+    String::from_utf8(log.into_inner()).unwrap()
+}
+
+#[test]
+fn test_records_chain_and_verify() {
+    let text = sample_log();
+    assert_eq!(text.lines().count(), 4);
+    let report = verify(Cursor::new(&text)).unwrap();
+    assert_eq!(report.records, 4);
+    assert_eq!(verify(Cursor::new("")).unwrap().last_hash, GENESIS_HASH);
+}
+
+#[test]
+fn test_tampering_is_detected() {
+    let text = sample_log();
+    let lines: Vec<&str> = text.lines().collect();
+
+    let edited = text.replace("requested=4.2", "requested=3.1");
+    assert_eq!(verify(Cursor::new(edited)), Err(SafetyLogError::HashMismatch { line: 1 }));
+
+    let removed = [lines[0], lines[2], lines[3]].join("\n");
+    assert_eq!(verify(Cursor::new(removed)), Err(SafetyLogError::Sequence { line: 2, expected: 1, found: 2 }));
+
+    let truncated_front = lines[1..].join("\n");
+    assert!(verify(Cursor::new(truncated_front)).is_err());
+
+    assert_eq!(verify(Cursor::new("garbage")), Err(SafetyLogError::Malformed { line: 1 }));
+}
+
+#[test]
+fn test_anchor_catches_truncated_tail() {
+    let mut log = SafetyLog::new(Vec::new());
+    for t in [100, 200] {
+        log.record(t, SafetyEvent::WatchdogTrip { watchdog: "servo".into(), late_by_us: 1500 }, snapshot()).unwrap();
+    }
+    let anchor = log.head();
+    assert_eq!(anchor.records, 2);
+    log.record(300, SafetyEvent::FaultRecovery { code: "W1".into(), action: "resumed".into() }, snapshot()).unwrap();
+    let text = String::from_utf8(log.into_inner()).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+
+    // Records appended after the anchor are fine
+    assert_eq!(verify_anchored(Cursor::new(&text), &anchor).unwrap().records, 3);
+
+    // Dropping the tail still verifies on its own, but not against the anchor
+    let cut = lines[..1].join("\n");
+    assert!(verify(Cursor::new(&cut)).is_ok());
+    assert_eq!(verify_anchored(Cursor::new(&cut), &anchor), Err(SafetyLogError::Truncated { expected: 2, found: 1 }));
+
+    // A rewritten log of the same length does not match the anchored hash
+    let mut forged = SafetyLog::new(Vec::new());
+    for t in [100, 201] {
+        let event = SafetyEvent::WatchdogTrip { watchdog: "servo".into(), late_by_us: 1500 };
+        forged.record(t, event, snapshot()).unwrap();
+    }
+    let forged = String::from_utf8(forged.into_inner()).unwrap();
+    assert_eq!(verify_anchored(Cursor::new(forged), &anchor), Err(SafetyLogError::AnchorMismatch { line: 2 }));
+}
+
+#[test]
+fn test_free_text_cannot_forge_fields() {
+    let text = sample_log();
+    let fault = text.lines().nth(2).unwrap();
+    assert_eq!(fault.split('\t').count(), 7);
+    assert!(fault.contains(r"encoder\tglitch\; count\=0"));
+}
+
+#[test]
+fn test_reporter_queues_until_flushed() {
+    let mut log = SafetyLog::new(Vec::new());
+    let reporter = log.reporter();
+    let worker = std::thread::spawn(move || {
+        for i in 0..3 {
+            reporter.report(i, ReportedEvent::WatchdogTrip { watchdog: "servo", late_by_us: i }, &ReportedSnapshot::default());
+        }
+    });
+    worker.join().unwrap();
+    assert_eq!(log.next_sequence(), 0);
+    assert_eq!(log.flush_pending().unwrap(), 3);
+    assert_eq!(log.next_sequence(), 3);
+    let text = String::from_utf8(log.into_inner()).unwrap();
+    assert_eq!(verify(Cursor::new(text)).unwrap().records, 3);
+}
+
+#[test]
+fn test_full_queue_is_counted_and_logged() {
+    let mut log = SafetyLog::new(Vec::new());
+    let reporter = log.reporter();
+    let snapshot = ReportedSnapshot { device: "touch".into(), ..ReportedSnapshot::default() };
+    let event = ReportedEvent::NonFiniteForce { force: Vec3::new(f32::NAN, 0.0, 0.0) };
+    for i in 0..REPORT_QUEUE_LEN as u64 {
+        assert!(reporter.report(i, event, &snapshot));
+    }
+    assert!(!reporter.report(0, event, &snapshot));
+    assert!(!reporter.report(0, event, &snapshot));
+
+    assert_eq!(log.flush_pending().unwrap(), REPORT_QUEUE_LEN + 1);
+    let text = String::from_utf8(log.into_inner()).unwrap();
+    assert!(text.lines().next().unwrap().contains("non_finite_force"), "{text}");
+    assert!(text.contains("touch"), "{text}");
+    assert!(text.lines().last().unwrap().contains("2 reports did not fit"), "{text}");
+    assert_eq!(verify(Cursor::new(text)).unwrap().records, REPORT_QUEUE_LEN as u64 + 1);
+}
+
+#[test]
+fn test_open_resumes_existing_file() {
+    let path = std::env::temp_dir().join(format!("hapticui-safety-{}.log", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    {
+        let mut log = SafetyLog::open(&path).unwrap();
+        log.record(1, SafetyEvent::Fault { code: "E1".into(), message: "overheat".into() }, snapshot()).unwrap();
+    }
+    {
+        let mut log = SafetyLog::open(&path).unwrap();
+        assert_eq!(log.next_sequence(), 1);
+        log.record(2, SafetyEvent::FaultRecovery { code: "E1".into(), action: "cooled".into() }, snapshot()).unwrap();
+    }
+    let report = verify(BufReader::new(File::open(&path).unwrap())).unwrap();
+    assert_eq!(report.records, 2);
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,134 @@
+//! Camera-facing nodes.
+//!
+//! Scene nodes have no rotation of their own, so a [`Billboards`] component store
+//! keeps, next to the scene, which nodes face the camera and how. Each frame
+//! [`Billboards::update`] computes a [`BillboardPose`] (orientation and scale)
+//! for every such node from the active camera, which the graphics side applies
+//! when drawing the node. Labels and tooltips use it to stay legible from
+//! wherever they are looked at.
+
+use std::collections::HashMap;
+
+use super::camera::CameraPose;
+use super::graph::{NodeId, Scene};
+use crate::core::{Quat, Vec3};
+
+/// How a node turns toward the camera.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BillboardMode {
+    /// Faces the camera fully, with its up along the camera's up.
+    Full,
+    /// Turns about the world Y axis only, staying upright.
+    AxisY,
+    /// Faces the camera fully and scales to stay `pixels` tall on screen.
+    FixedPixelSize { pixels: f32 },
+}
+
+/// Orientation and scale to draw a billboarded node with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BillboardPose {
+    /// Turns the node's +Z toward the camera.
+    pub orientation: Quat,
+    /// Uniform scale; 1 except for fixed pixel size.
+    pub scale: f32,
+}
+
+impl BillboardPose {
+    pub const IDENTITY: Self = Self { orientation: Quat::identity(), scale: 1.0 };
+}
+
+impl BillboardMode {
+    /// Pose of a node at `position`, `height` tall, seen by `camera`.
+    pub fn pose(self, position: Vec3, height: f32, camera: &CameraPose) -> BillboardPose {
+        let toward = camera.position - position;
+        let orientation = match self {
+            BillboardMode::Full | BillboardMode::FixedPixelSize { .. } => {
+                facing(toward, camera.up).or_else(|| facing(-camera.forward, camera.up)).unwrap_or_default()
+            }
+            BillboardMode::AxisY => {
+                let level = Vec3::new(toward.x, 0.0, toward.z);
+                let back = Vec3::new(-camera.forward.x, 0.0, -camera.forward.z);
+                facing(level, Vec3::unit_y()).or_else(|| facing(back, Vec3::unit_y())).unwrap_or_default()
+            }
+        };
+        let scale = match self {
+            BillboardMode::FixedPixelSize { pixels } => {
+                let wanted = pixels * camera.pixel_size(position);
+                if height > 0.0 {
+                    wanted / height
+                } else {
+                    wanted
+                }
+            }
+            _ => 1.0,
+        };
+        BillboardPose { orientation, scale }
+    }
+}
+
+/// Rotation taking +Z to `z` with +Y as close to `up` as possible.
+fn facing(z: Vec3, up: Vec3) -> Option<Quat> {
+    let z = z.try_normalize()?;
+    // Looking along `up`, any roll will do; keep X level with world X
+    let x = up.cross(z).try_normalize().or_else(|| Vec3::unit_x().reject_from(z).try_normalize())?;
+    Some(Quat::from_rotation_axes(x, z.cross(x), z))
+}
+
+/// Which nodes face the camera, and their poses as of the last update.
+#[derive(Debug, Clone, Default)]
+pub struct Billboards {
+    modes: HashMap<NodeId, BillboardMode>,
+    poses: HashMap<NodeId, BillboardPose>,
+}
+
+impl Billboards {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, node: NodeId, mode: BillboardMode) {
+        self.modes.insert(node, mode);
+    }
+
+    pub fn remove(&mut self, node: NodeId) -> Option<BillboardMode> {
+        self.poses.remove(&node);
+        self.modes.remove(&node)
+    }
+
+    #[inline]
+    pub fn get(&self, node: NodeId) -> Option<BillboardMode> {
+        self.modes.get(&node).copied()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.modes.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.modes.is_empty()
+    }
+
+    /// Poses every billboarded node toward `camera`, forgetting nodes that left
+    /// the scene.
+    pub fn update(&mut self, scene: &Scene, camera: &CameraPose) {
+        self.modes.retain(|&node, _| scene.contains(node));
+        self.poses.clear();
+        for (&node, &mode) in &self.modes {
+            if let (Some(position), Some(n)) = (scene.world_position(node), scene.get(node)) {
+                self.poses.insert(node, mode.pose(position, n.size.y, camera));
+            }
+        }
+    }
+
+    /// Pose of `node` as of the last update.
+    #[inline]
+    pub fn pose(&self, node: NodeId) -> Option<BillboardPose> {
+        self.poses.get(&node).copied()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/billboard_tests.rs"]
+mod tests;
@@ -0,0 +1,289 @@
+//! Declarative construction of node hierarchies.
+//!
+//! `NodeBuilder` assembles a subtree with its layout and haptic properties inline;
+//! the `scene!` macro is a thin layer of syntax over it:
+//!
+//! ```ignore
+//! let scene = scene! {
+//!     panel.size(0.3, 0.2, 0.01).column(0.02) {
+//!         label("Volume"),
+//!         button("OK").stiffness(NewtonsPerMeter(800.0)).on_click(|_| println!("ok")),
+//!     }
+//! };
+//! ```
+
+use super::clip::Clip;
+use super::flags::{LayerMask, NodeFlags};
+use super::graph::{Node, NodeHaptics, NodeId, NodeKind, Scene, SceneError};
+use crate::core::{NewtonSecondsPerMeter, NewtonsPerMeter, Vec3};
+
+// ============================================================================
+// Layout
+// ============================================================================
+
+/// How a builder positions its children.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Layout {
+    /// Children keep their own positions.
+    Manual,
+    /// Children are stacked along +X, centered on the parent.
+    Row { spacing: f32 },
+    /// Children are stacked along -Y (top to bottom), centered on the parent.
+    Column { spacing: f32 },
+}
+
+// ============================================================================
+// Node Builder
+// ============================================================================
+
+/// Builder for a node and its subtree.
+#[derive(Debug)]
+pub struct NodeBuilder {
+    node: Node,
+    layout: Layout,
+    children: Vec<NodeBuilder>,
+}
+
+impl NodeBuilder {
+    /// Starts a builder for a node of the given kind.
+    pub fn new(kind: NodeKind) -> Self {
+        Self { node: Node::new(kind), layout: Layout::Manual, children: Vec::new() }
+    }
+
+    pub fn group() -> Self {
+        Self::new(NodeKind::Group)
+    }
+
+    pub fn panel() -> Self {
+        Self::new(NodeKind::Panel).touchable()
+    }
+
+    pub fn button(label: impl Into<String>) -> Self {
+        Self::new(NodeKind::Button { label: label.into() }).touchable()
+    }
+
+    pub fn label(text: impl Into<String>) -> Self {
+        Self::new(NodeKind::Label { text: text.into() })
+    }
+
+    // ============================================================================
+    // Properties
+    // ============================================================================
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.node.name = name.into();
+        self
+    }
+
+    /// Sets the local position relative to the parent.
+    pub fn at(mut self, x: f32, y: f32, z: f32) -> Self {
+        self.node.position = Vec3::new(x, y, z);
+        self
+    }
+
+    pub fn position(mut self, position: Vec3) -> Self {
+        self.node.position = position;
+        self
+    }
+
+    /// Sets the full extents of the node's box.
+    pub fn size(mut self, width: f32, height: f32, depth: f32) -> Self {
+        self.node.size = Vec3::new(width, height, depth);
+        self
+    }
+
+    /// Gives the node default haptic properties.
+    pub fn touchable(mut self) -> Self {
+        self.node.haptics.get_or_insert_with(NodeHaptics::default);
+        self.node.flags.set(NodeFlags::TOUCHABLE, true);
+        self
+    }
+
+    /// Removes haptic properties so the node cannot be felt.
+    pub fn intangible(mut self) -> Self {
+        self.node.haptics = None;
+        self
+    }
+
+    pub fn flags(mut self, flags: NodeFlags) -> Self {
+        self.node.flags = flags;
+        self
+    }
+
+    pub fn visible(mut self, visible: bool) -> Self {
+        self.node.flags.set(NodeFlags::VISIBLE, visible);
+        self
+    }
+
+    pub fn pickable(mut self, pickable: bool) -> Self {
+        self.node.flags.set(NodeFlags::PICKABLE, pickable);
+        self
+    }
+
+    /// Hides the node and its subtree from the graphics renderer.
+    pub fn hidden(self) -> Self {
+        self.visible(false)
+    }
+
+    /// Visible only: cannot be felt or picked (e.g. drag previews).
+    pub fn ghost(mut self) -> Self {
+        self.node.flags = NodeFlags::VISIBLE;
+        self
+    }
+
+    /// Touchable only: felt but not drawn or picked (e.g. guide rails).
+    pub fn guide(mut self) -> Self {
+        self.node.flags = NodeFlags::TOUCHABLE;
+        self.touchable()
+    }
+
+    pub fn layers(mut self, layers: LayerMask) -> Self {
+        self.node.layers = layers;
+        self
+    }
+
+    /// Adds the node to `layer`. Panics if `layer` is not in 0..32.
+    pub fn layer(mut self, layer: u32) -> Self {
+        self.node.layers = self.node.layers.with_layer(layer);
+        self
+    }
+
+    /// Cuts the node's descendants to `clip`, on top of any clips it has.
+    pub fn clip(mut self, clip: Clip) -> Self {
+        self.node.clip.push(clip);
+        self
+    }
+
+    pub fn haptics(mut self, haptics: NodeHaptics) -> Self {
+        self.node.haptics = Some(haptics);
+        self
+    }
+
+    pub fn stiffness(mut self, stiffness: NewtonsPerMeter) -> Self {
+        self.node.haptics.get_or_insert_with(NodeHaptics::default).stiffness = stiffness;
+        self
+    }
+
+    pub fn damping(mut self, damping: NewtonSecondsPerMeter) -> Self {
+        self.node.haptics.get_or_insert_with(NodeHaptics::default).damping = damping;
+        self
+    }
+
+    pub fn friction(mut self, friction: f32) -> Self {
+        self.node.haptics.get_or_insert_with(NodeHaptics::default).friction = friction;
+        self
+    }
+
+    pub fn on_click(mut self, handler: impl FnMut(NodeId) + Send + 'static) -> Self {
+        self.node.set_on_click(handler);
+        self
+    }
+
+    // ============================================================================
+    // Hierarchy and Layout
+    // ============================================================================
+
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    pub fn row(self, spacing: f32) -> Self {
+        self.layout(Layout::Row { spacing })
+    }
+
+    pub fn column(self, spacing: f32) -> Self {
+        self.layout(Layout::Column { spacing })
+    }
+
+    pub fn child(mut self, child: NodeBuilder) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn children(mut self, children: impl IntoIterator<Item = NodeBuilder>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    /// Inserts the subtree into `scene` under `parent` and returns the id of its root.
+    /// Fails, inserting nothing, if `parent` is not in the scene.
+    pub fn build(mut self, scene: &mut Scene, parent: Option<NodeId>) -> Result<NodeId, SceneError> {
+        self.apply_layout();
+        let id = scene.insert(self.node, parent)?;
+        for child in self.children {
+            child.build(scene, Some(id))?;
+        }
+        Ok(id)
+    }
+
+    /// Builds the subtree into a fresh scene.
+    pub fn into_scene(self) -> Scene {
+        let mut scene = Scene::new();
+        self.build(&mut scene, None).expect("a root has no parent to be missing");
+        scene
+    }
+
+    /// Positions children according to the layout, centered on this node.
+    fn apply_layout(&mut self) {
+        let (axis, spacing) = match self.layout {
+            Layout::Manual => return,
+            Layout::Row { spacing } => (Vec3::unit_x(), spacing),
+            Layout::Column { spacing } => (-Vec3::unit_y(), spacing),
+        };
+        let extent = |b: &NodeBuilder| b.node.size.dot(axis).abs();
+        let total: f32 = self.children.iter().map(extent).sum::<f32>()
+            + spacing * self.children.len().saturating_sub(1) as f32;
+
+        let mut cursor = -total * 0.5;
+        for child in &mut self.children {
+            let e = extent(child);
+            // Keep the off-axis offsets the child was authored with
+            let off_axis = child.node.position.reject_from(axis);
+            child.node.position = off_axis + axis * (cursor + e * 0.5);
+            cursor += e + spacing;
+        }
+    }
+}
+
+// ============================================================================
+// Macros
+// ============================================================================
+
+/// Builds a `Scene` from a declarative node tree.
+///
+/// Each node is a `NodeBuilder` constructor (`panel`, `group`, `button("OK")`, `label("Hi")`)
+/// followed by any number of builder method calls and an optional `{ ... }` child list.
+#[macro_export]
+macro_rules! scene {
+    ($($body:tt)*) => {{
+        let mut scene = $crate::scene::Scene::new();
+        for root in $crate::scene_nodes!($($body)*) {
+            root.build(&mut scene, None).expect("a root has no parent to be missing");
+        }
+        scene
+    }};
+}
+
+/// Builds a `Vec<NodeBuilder>` from a comma-separated list of node declarations.
+#[macro_export]
+macro_rules! scene_nodes {
+    ($( $ctor:ident $( ( $($arg:expr),* $(,)? ) )? $( . $method:ident ( $($marg:expr),* $(,)? ) )* $( { $($child:tt)* } )? ),* $(,)?) => {
+        vec![ $( $crate::scene_node!($ctor $( ( $($arg),* ) )? $( . $method ( $($marg),* ) )* $( { $($child)* } )?) ),* ]
+    };
+}
+
+/// Builds a single `NodeBuilder` from a node declaration.
+#[macro_export]
+macro_rules! scene_node {
+    ($ctor:ident $( ( $($arg:expr),* $(,)? ) )? $( . $method:ident ( $($marg:expr),* $(,)? ) )* $( { $($child:tt)* } )?) => {{
+        #[allow(unused_mut)]
+        let mut builder = $crate::scene::NodeBuilder::$ctor( $( $($arg),* )? ) $( .$method( $($marg),* ) )*;
+        $( builder = builder.children($crate::scene_nodes!($($child)*)); )?
+        builder
+    }};
+}
+
+#[cfg(test)]
+#[path = "tests/builder_tests.rs"]
+mod tests;
@@ -0,0 +1,96 @@
+//! The viewpoint the scene is seen from.
+//!
+//! Layout and camera-facing components need to know where the camera is, where it
+//! looks and, to size things in pixels, its vertical field of view and viewport
+//! height. The camera itself belongs to the graphics side; a [`CameraPose`] is the
+//! copy of it handed to the scene each frame.
+
+use crate::core::{Deg, Rad, Vec3};
+use crate::geometry::Ray;
+
+/// Where the camera is, where it looks, and how it projects.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraPose {
+    pub position: Vec3,
+    /// Unit view direction.
+    pub forward: Vec3,
+    /// Unit direction of screen up.
+    pub up: Vec3,
+    /// Vertical field of view.
+    pub fov_y: Rad,
+    /// Viewport height in pixels.
+    pub viewport_height: f32,
+}
+
+impl CameraPose {
+    /// Camera with Y up, a 60° field of view and a 1080 pixel viewport.
+    pub fn new(position: Vec3, forward: Vec3) -> Self {
+        Self {
+            position,
+            forward: forward.try_normalize().unwrap_or(-Vec3::unit_z()),
+            up: Vec3::unit_y(),
+            fov_y: Deg(60.0).into(),
+            viewport_height: 1080.0,
+        }
+    }
+
+    pub fn with_up(mut self, up: Vec3) -> Self {
+        self.up = up.try_normalize().unwrap_or(self.up);
+        self
+    }
+
+    pub fn with_projection(mut self, fov_y: impl Into<Rad>, viewport_height: f32) -> Self {
+        self.fov_y = fov_y.into();
+        self.viewport_height = viewport_height;
+        self
+    }
+
+    /// Distance of `point` in front of the camera, along the view direction.
+    #[inline]
+    pub fn depth(&self, point: Vec3) -> f32 {
+        (point - self.position).dot(self.forward)
+    }
+
+    /// World size of one pixel at the depth of `point`.
+    pub fn pixel_size(&self, point: Vec3) -> f32 {
+        let height = 2.0 * self.depth(point).abs() * (self.fov_y.0 * 0.5).tan();
+        height / self.viewport_height.max(1.0)
+    }
+
+    /// Unit screen right and screen up, at right angles to the view direction.
+    pub fn screen_axes(&self) -> (Vec3, Vec3) {
+        let right = self.forward.cross(self.up).try_normalize().unwrap_or(Vec3::unit_x());
+        (right, right.cross(self.forward))
+    }
+
+    /// Whether `point` is in front of the camera and inside the view of a
+    /// viewport `viewport_width` pixels wide.
+    pub fn sees(&self, point: Vec3, viewport_width: f32) -> bool {
+        let depth = self.depth(point);
+        let (right, up) = self.screen_axes();
+        let half_height = depth * (self.fov_y.0 * 0.5).tan();
+        let half_width = half_height * viewport_width / self.viewport_height.max(1.0);
+        let offset = point - self.position;
+        depth > 0.0 && offset.dot(up).abs() <= half_height && offset.dot(right).abs() <= half_width
+    }
+
+    /// Ray from the camera through pixel (`x`, `y`), counted from the top left of
+    /// a viewport `viewport_width` pixels wide.
+    pub fn screen_ray(&self, x: f32, y: f32, viewport_width: f32) -> Ray {
+        let (right, up) = self.screen_axes();
+        let pixel = 2.0 * (self.fov_y.0 * 0.5).tan() / self.viewport_height.max(1.0);
+        let dx = (x - viewport_width * 0.5) * pixel;
+        let dy = (self.viewport_height * 0.5 - y) * pixel;
+        Ray::new(self.position, self.forward + right * dx + up * dy)
+    }
+}
+
+impl Default for CameraPose {
+    fn default() -> Self {
+        Self::new(Vec3::zero(), -Vec3::unit_z())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/camera_tests.rs"]
+mod tests;
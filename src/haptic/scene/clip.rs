@@ -0,0 +1,174 @@
+//! Clip regions: cutting a container's content off at its edges.
+//!
+//! A container node can carry [`Clip`]s, each a box or a plane placed relative
+//! to the node. Its descendants are cut to the region inside all of them and
+//! inside the clips of containers further up; the container itself is not cut.
+//! Clipping applies wherever the scene is queried, not just to the picture:
+//!
+//! - Nodes wholly outside their region are left out of
+//!   [`Scene::visible_nodes`](super::Scene::visible_nodes),
+//!   [`touchable_nodes`](super::Scene::touchable_nodes) and
+//!   [`pickable_nodes`](super::Scene::pickable_nodes), so they are neither
+//!   drawn, felt nor picked.
+//! - Raycasts only hit what is left of a partly clipped node, so a pointer on
+//!   the cut-off part of a row passes through to whatever is behind it.
+//! - The graphics renderer cuts partly clipped geometry and text against the
+//!   [`ClipRegion::boundary`] planes of
+//!   [`Scene::clip_region`](super::Scene::clip_region).
+
+use crate::core::Vec3;
+use crate::geometry::{Aabb, Plane, Ray};
+
+/// Signed distance within which a point counts as lying on a clip plane.
+const PLANE_EPSILON: f32 = 1e-5;
+
+/// A region a container cuts its descendants to, placed relative to the container.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Clip {
+    /// Keeps what lies inside a box of `size` centered `offset` from the node.
+    Box { offset: Vec3, size: Vec3 },
+    /// Keeps what lies on the side `normal` points to of the plane through
+    /// `point`, relative to the node.
+    Plane { point: Vec3, normal: Vec3 },
+}
+
+impl Clip {
+    /// Box of `size` centered on the node, e.g. the viewport of a scroll view.
+    pub const fn bounds(size: Vec3) -> Self {
+        Clip::Box { offset: Vec3::zero(), size }
+    }
+
+    pub fn plane(point: Vec3, normal: Vec3) -> Self {
+        Clip::Plane { point, normal: normal.try_normalize().unwrap_or_else(Vec3::unit_z) }
+    }
+}
+
+/// Part of world space left by clips: the inside of a box and of half-spaces.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipRegion {
+    /// Box clips and planes along an axis, folded into one box.
+    bounds: Aabb,
+    /// Other planes, keeping the side their normals point to.
+    planes: Vec<Plane>,
+}
+
+impl ClipRegion {
+    /// All of space.
+    pub const UNBOUNDED: Self = Self {
+        bounds: Aabb { min: Vec3::splat(f32::NEG_INFINITY), max: Vec3::splat(f32::INFINITY) },
+        planes: Vec::new(),
+    };
+
+    pub fn new() -> Self {
+        Self::UNBOUNDED
+    }
+
+    /// Whether nothing is clipped.
+    pub fn is_unbounded(&self) -> bool {
+        self.planes.is_empty() && self.bounds == Self::UNBOUNDED.bounds
+    }
+
+    /// Box the region lies in; empty when clips leave nothing.
+    #[inline]
+    pub fn bounds(&self) -> Aabb {
+        self.bounds
+    }
+
+    /// Cuts the region further by `clip` on a container at `origin`.
+    pub fn clip(&mut self, clip: &Clip, origin: Vec3) {
+        match *clip {
+            Clip::Box { offset, size } => {
+                let other = Aabb::from_center_half_extents(origin + offset, size.abs() * 0.5);
+                self.bounds = Aabb { min: self.bounds.min.max(other.min), max: self.bounds.max.min(other.max) };
+            }
+            Clip::Plane { point, normal } => {
+                let point = origin + point;
+                match (0..3).find(|&axis| (normal[axis].abs() - 1.0).abs() <= PLANE_EPSILON) {
+                    Some(axis) if normal[axis] > 0.0 => self.bounds.min[axis] = self.bounds.min[axis].max(point[axis]),
+                    Some(axis) => self.bounds.max[axis] = self.bounds.max[axis].min(point[axis]),
+                    None => self.planes.push(Plane::from_point_normal(point, normal)),
+                }
+            }
+        }
+    }
+
+    /// Builder form of [`clip`](Self::clip).
+    pub fn with(mut self, clip: &Clip, origin: Vec3) -> Self {
+        self.clip(clip, origin);
+        self
+    }
+
+    pub fn contains(&self, p: Vec3) -> bool {
+        self.bounds.contains(p) && self.planes.iter().all(|plane| plane.signed_distance(p) >= -PLANE_EPSILON)
+    }
+
+    /// Part of `bounds` inside the region's box; none if `bounds` lies wholly
+    /// outside the region.
+    pub fn cut(&self, bounds: &Aabb) -> Option<Aabb> {
+        let cut = Aabb { min: bounds.min.max(self.bounds.min), max: bounds.max.min(self.bounds.max) };
+        if cut.is_empty() {
+            return None;
+        }
+        let (center, half) = (cut.center(), cut.half_extents());
+        let behind = self.planes.iter().any(|plane| {
+            plane.signed_distance(center) + plane.normal.abs().dot(half) < -PLANE_EPSILON
+        });
+        (!behind).then_some(cut)
+    }
+
+    /// Part of the stretch from `enter` to `exit` along `ray` that lies in the
+    /// region, as in [`Aabb::ray_interval`]; none if it lies wholly outside.
+    pub fn ray_interval(&self, ray: &Ray, (enter, exit): (f32, f32)) -> Option<(f32, f32)> {
+        let (box_enter, box_exit) = self.bounds.ray_interval(ray)?;
+        let (mut enter, mut exit) = (enter.max(box_enter), exit.min(box_exit));
+        for plane in &self.planes {
+            let distance = plane.signed_distance(ray.origin);
+            let rate = plane.normal.dot(ray.direction);
+            if rate == 0.0 {
+                if distance < -PLANE_EPSILON {
+                    return None;
+                }
+                continue;
+            }
+            let t = -distance / rate;
+            if rate > 0.0 {
+                enter = enter.max(t);
+            } else {
+                exit = exit.min(t);
+            }
+        }
+        (exit >= enter.max(0.0)).then_some((enter, exit))
+    }
+
+    /// Clip plane through `p`, if it lies on one of the planes not along an axis.
+    pub fn plane_at(&self, p: Vec3) -> Option<&Plane> {
+        self.planes.iter().find(|plane| plane.signed_distance(p).abs() <= PLANE_EPSILON)
+    }
+
+    /// Planes bounding the region, facing in, for clipping in a shader.
+    pub fn boundary(&self) -> Vec<Plane> {
+        let mut planes = Vec::new();
+        for axis in 0..3 {
+            let mut normal = Vec3::zero();
+            normal[axis] = 1.0;
+            if self.bounds.min[axis].is_finite() {
+                planes.push(Plane { normal, offset: self.bounds.min[axis] });
+            }
+            if self.bounds.max[axis].is_finite() {
+                planes.push(Plane { normal: -normal, offset: -self.bounds.max[axis] });
+            }
+        }
+        planes.extend_from_slice(&self.planes);
+        planes
+    }
+}
+
+impl Default for ClipRegion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/clip_tests.rs"]
+mod tests;
@@ -0,0 +1,180 @@
+//! Per-node visibility, pickability and touchability flags and layer masks.
+//!
+//! The three flags are independent, so a node can be seen but not felt (ghost
+//! previews) or felt but not seen (guide rails). Hidden, unpickable or
+//! untouchable parents hide their whole subtree for that aspect. Layers are not
+//! inherited: each renderer queries the scene with its own mask.
+
+use std::fmt;
+use std::ops::{BitAnd, BitOr, BitOrAssign, Not};
+
+// ============================================================================
+// Node Flags
+// ============================================================================
+
+/// Set of per-node behaviour flags.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeFlags(u8);
+
+impl NodeFlags {
+    pub const NONE: Self = Self(0);
+    /// Drawn by the graphics renderer.
+    pub const VISIBLE: Self = Self(1 << 0);
+    /// Hit by pointer rays and cursor picking.
+    pub const PICKABLE: Self = Self(1 << 1);
+    /// Rendered by the haptic renderer (requires surface properties as well).
+    pub const TOUCHABLE: Self = Self(1 << 2);
+    pub const ALL: Self = Self(0b111);
+
+    #[inline]
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// True if every flag in `other` is set.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    #[inline]
+    pub fn set(&mut self, flags: Self, enabled: bool) {
+        if enabled {
+            self.0 |= flags.0;
+        } else {
+            self.0 &= !flags.0;
+        }
+    }
+
+    #[inline]
+    pub const fn with(self, flags: Self, enabled: bool) -> Self {
+        if enabled {
+            Self(self.0 | flags.0)
+        } else {
+            Self(self.0 & !flags.0)
+        }
+    }
+}
+
+impl Default for NodeFlags {
+    #[inline]
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl BitOr for NodeFlags {
+    type Output = Self;
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for NodeFlags {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl BitAnd for NodeFlags {
+    type Output = Self;
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+impl Not for NodeFlags {
+    type Output = Self;
+    #[inline]
+    fn not(self) -> Self {
+        Self(!self.0 & Self::ALL.0)
+    }
+}
+
+impl fmt::Debug for NodeFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = [(Self::VISIBLE, "VISIBLE"), (Self::PICKABLE, "PICKABLE"), (Self::TOUCHABLE, "TOUCHABLE")];
+        let set: Vec<&str> = names.iter().filter(|(flag, _)| self.contains(*flag)).map(|(_, name)| *name).collect();
+        if set.is_empty() {
+            write!(f, "NodeFlags(NONE)")
+        } else {
+            write!(f, "NodeFlags({})", set.join(" | "))
+        }
+    }
+}
+
+// ============================================================================
+// Layer Masks
+// ============================================================================
+
+/// Up to 32 layers a node belongs to, matched against a renderer's query mask.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LayerMask(pub u32);
+
+impl LayerMask {
+    pub const NONE: Self = Self(0);
+    /// Layer 0, which new nodes belong to.
+    pub const DEFAULT: Self = Self(1);
+    pub const ALL: Self = Self(u32::MAX);
+
+    /// Mask containing only `layer`. Panics if `layer` is not in 0..32.
+    #[inline]
+    pub const fn layer(layer: u32) -> Self {
+        Self(bit(layer))
+    }
+
+    /// True if the masks share at least one layer.
+    #[inline]
+    pub const fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Panics if `layer` is not in 0..32.
+    #[inline]
+    pub const fn with_layer(self, layer: u32) -> Self {
+        Self(self.0 | bit(layer))
+    }
+
+    /// Panics if `layer` is not in 0..32.
+    #[inline]
+    pub const fn without_layer(self, layer: u32) -> Self {
+        Self(self.0 & !bit(layer))
+    }
+}
+
+/// The bit for `layer`, checked in release builds too rather than wrapping.
+#[inline]
+const fn bit(layer: u32) -> u32 {
+    assert!(layer < 32, "layer index out of range: layer masks hold layers 0..32");
+    1 << layer
+}
+
+impl Default for LayerMask {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl BitOr for LayerMask {
+    type Output = Self;
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl BitAnd for LayerMask {
+    type Output = Self;
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/flags_tests.rs"]
+mod tests;
@@ -0,0 +1,564 @@
+//! Scene graph storing the node hierarchy of a HapticGUI scene.
+//!
+//! Nodes carry a local position relative to their parent, a box size used for
+//! layout and touch volumes, and optional haptic surface properties. Flags and
+//! layer masks decide which renderers see each node.
+//!
+//! Node ids are generational handles: removing a node bumps the generation of its
+//! slot, so an id held across frames never resolves to whatever reuses the slot
+//! later. A slot whose generation runs out is retired rather than wrapped. A node can be detached from the hierarchy and attached elsewhere; while
+//! detached it is an orphan that no query reaches, and [`Scene::remove_orphans`]
+//! drops the orphans nobody reattached.
+//!
+//! Containers can cut their descendants to [`Clip`] regions; queries leave out
+//! nodes clipped away entirely.
+
+use super::clip::{Clip, ClipRegion};
+use super::flags::{LayerMask, NodeFlags};
+use crate::core::{NewtonSecondsPerMeter, NewtonsPerMeter, Vec3};
+use crate::geometry::Aabb;
+use std::collections::HashSet;
+use std::fmt;
+
+// ============================================================================
+// Node Types
+// ============================================================================
+
+/// Handle to a node inside a `Scene`; stale once the node is removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId {
+    index: u32,
+    generation: u32,
+}
+
+impl NodeId {
+    /// Storage slot, shared with earlier nodes that were removed.
+    #[inline]
+    pub fn index(self) -> usize {
+        self.index as usize
+    }
+
+    /// How many nodes used the slot before this one.
+    #[inline]
+    pub fn generation(self) -> u32 {
+        self.generation
+    }
+}
+
+/// Why a hierarchy edit was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SceneError {
+    /// The id was removed or never belonged to this scene.
+    InvalidNode(NodeId),
+    /// Attaching requires a detached node.
+    AlreadyAttached(NodeId),
+    /// The new parent lies inside the node's own subtree.
+    Cycle { node: NodeId, parent: NodeId },
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneError::InvalidNode(id) => write!(f, "node #{} is not in the scene", id.index()),
+            SceneError::AlreadyAttached(id) => write!(f, "node #{} is already attached; detach it first", id.index()),
+            SceneError::Cycle { node, parent } => {
+                write!(f, "cannot attach node #{} under its own descendant #{}", node.index(), parent.index())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SceneError {}
+
+/// What a node represents.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeKind {
+    /// Invisible grouping node.
+    Group,
+    /// Backing surface for other widgets.
+    Panel,
+    /// Pressable button with a caption.
+    Button { label: String },
+    /// Static text.
+    Label { text: String },
+}
+
+/// Haptic surface properties of a node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NodeHaptics {
+    pub stiffness: NewtonsPerMeter,
+    pub damping: NewtonSecondsPerMeter,
+    /// Coulomb friction coefficient.
+    pub friction: f32,
+}
+
+impl Default for NodeHaptics {
+    fn default() -> Self {
+        Self { stiffness: NewtonsPerMeter(500.0), damping: NewtonSecondsPerMeter(1.0), friction: 0.3 }
+    }
+}
+
+/// Callback invoked when a node is clicked.
+pub type ClickHandler = Box<dyn FnMut(NodeId) + Send>;
+
+/// A single scene node.
+pub struct Node {
+    pub name: String,
+    pub kind: NodeKind,
+    /// Position of the node center relative to its parent.
+    pub position: Vec3,
+    /// Full extents of the node's box.
+    pub size: Vec3,
+    /// Surface properties; None makes the node intangible.
+    pub haptics: Option<NodeHaptics>,
+    /// Visibility, pickability and touchability, inherited by descendants.
+    pub flags: NodeFlags,
+    /// Layers the node belongs to (not inherited).
+    pub layers: LayerMask,
+    /// Regions the node's descendants are cut to, relative to the node.
+    pub clip: Vec<Clip>,
+    pub(crate) on_click: Option<ClickHandler>,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+}
+
+impl Node {
+    /// Creates an unnamed node of the given kind at the origin.
+    pub fn new(kind: NodeKind) -> Self {
+        Self {
+            name: String::new(),
+            kind,
+            position: Vec3::zero(),
+            size: Vec3::zero(),
+            haptics: None,
+            flags: NodeFlags::ALL,
+            layers: LayerMask::DEFAULT,
+            clip: Vec::new(),
+            on_click: None,
+            parent: None,
+            children: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    #[inline]
+    pub fn children(&self) -> &[NodeId] {
+        &self.children
+    }
+
+    #[inline]
+    pub fn is_visible(&self) -> bool {
+        self.flags.contains(NodeFlags::VISIBLE)
+    }
+
+    #[inline]
+    pub fn is_pickable(&self) -> bool {
+        self.flags.contains(NodeFlags::PICKABLE)
+    }
+
+    /// True if the touchable flag is set and the node has surface properties.
+    #[inline]
+    pub fn is_touchable(&self) -> bool {
+        self.flags.contains(NodeFlags::TOUCHABLE) && self.haptics.is_some()
+    }
+
+    /// True if the node itself has `flags` and belongs to a layer in `mask`.
+    /// Ignores ancestors; see [`Scene::is_effectively`].
+    #[inline]
+    pub fn matches(&self, flags: NodeFlags, mask: LayerMask) -> bool {
+        self.flags.contains(flags)
+            && self.layers.intersects(mask)
+            && (!flags.contains(NodeFlags::TOUCHABLE) || self.haptics.is_some())
+    }
+
+    /// Returns true if the node has a click handler attached.
+    #[inline]
+    pub fn is_clickable(&self) -> bool {
+        self.on_click.is_some()
+    }
+
+    /// Replaces the click handler.
+    pub fn set_on_click(&mut self, handler: impl FnMut(NodeId) + Send + 'static) {
+        self.on_click = Some(Box::new(handler));
+    }
+}
+
+impl fmt::Debug for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Node")
+            .field("name", &self.name)
+            .field("kind", &self.kind)
+            .field("position", &self.position)
+            .field("size", &self.size)
+            .field("haptics", &self.haptics)
+            .field("flags", &self.flags)
+            .field("layers", &self.layers)
+            .field("clip", &self.clip)
+            .field("clickable", &self.on_click.is_some())
+            .field("parent", &self.parent)
+            .field("children", &self.children)
+            .finish()
+    }
+}
+
+// ============================================================================
+// Scene
+// ============================================================================
+
+/// Storage for one node, reused with a new generation once it is removed, or
+/// never again once the generation reaches `u32::MAX`.
+#[derive(Debug, Default)]
+struct Slot {
+    generation: u32,
+    node: Option<Node>,
+}
+
+/// Owner of all nodes of a scene.
+#[derive(Debug, Default)]
+pub struct Scene {
+    slots: Vec<Slot>,
+    free: Vec<u32>,
+    roots: Vec<NodeId>,
+    len: usize,
+}
+
+impl Scene {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a node under `parent` (or as a root) and returns its id.
+    ///
+    /// Fails if `parent` was removed or belongs to another scene; a root always
+    /// goes in.
+    pub fn insert(&mut self, mut node: Node, parent: Option<NodeId>) -> Result<NodeId, SceneError> {
+        if let Some(parent) = parent.filter(|&p| !self.contains(p)) {
+            return Err(SceneError::InvalidNode(parent));
+        }
+        node.parent = None;
+        node.children.clear();
+        let id = match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.node = Some(node);
+                NodeId { index, generation: slot.generation }
+            }
+            None => {
+                self.slots.push(Slot { generation: 0, node: Some(node) });
+                NodeId { index: self.slots.len() as u32 - 1, generation: 0 }
+            }
+        };
+        self.len += 1;
+        self.link(id, parent);
+        Ok(id)
+    }
+
+    /// Whether `id` still refers to a node of this scene.
+    #[inline]
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.get(id).is_some()
+    }
+
+    #[inline]
+    pub fn get(&self, id: NodeId) -> Option<&Node> {
+        self.slots.get(id.index()).filter(|s| s.generation == id.generation)?.node.as_ref()
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+        self.slots.get_mut(id.index()).filter(|s| s.generation == id.generation)?.node.as_mut()
+    }
+
+    #[inline]
+    pub fn roots(&self) -> &[NodeId] {
+        &self.roots
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Iterates over all nodes, orphans included, in slot order.
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &Node)> {
+        self.slots.iter().enumerate().filter_map(|(i, slot)| {
+            let id = NodeId { index: i as u32, generation: slot.generation };
+            slot.node.as_ref().map(|node| (id, node))
+        })
+    }
+
+    /// Finds the first node with the given name.
+    pub fn find(&self, name: &str) -> Option<NodeId> {
+        self.iter().find(|(_, n)| n.name == name).map(|(id, _)| id)
+    }
+
+    /// Position of the node in scene space (sum of local positions up to the root).
+    pub fn world_position(&self, id: NodeId) -> Option<Vec3> {
+        let mut node = self.get(id)?;
+        let mut position = node.position;
+        while let Some(parent) = node.parent {
+            node = self.node(parent);
+            position += node.position;
+        }
+        Some(position)
+    }
+
+    /// Live node behind an id taken from the hierarchy, which is kept consistent.
+    #[inline]
+    fn node(&self, id: NodeId) -> &Node {
+        self.get(id).expect("hierarchy refers to a removed node")
+    }
+
+    // ============================================================================
+    // Hierarchy Edits
+    // ============================================================================
+
+    /// True if `id` is live but detached from the hierarchy.
+    pub fn is_orphan(&self, id: NodeId) -> bool {
+        self.get(id).is_some_and(|n| n.parent.is_none()) && !self.roots.contains(&id)
+    }
+
+    /// True if `ancestor` is a parent of `id`, directly or further up.
+    pub fn is_ancestor(&self, ancestor: NodeId, id: NodeId) -> bool {
+        let mut current = self.get(id).and_then(|n| n.parent);
+        while let Some(node) = current {
+            if node == ancestor {
+                return true;
+            }
+            current = self.get(node).and_then(|n| n.parent);
+        }
+        false
+    }
+
+    /// Takes `id` and its subtree out of the hierarchy, leaving it an orphan that
+    /// keeps its local position. Detaching an orphan does nothing.
+    pub fn detach(&mut self, id: NodeId) -> Result<(), SceneError> {
+        let parent = self.get(id).ok_or(SceneError::InvalidNode(id))?.parent;
+        match parent {
+            Some(parent) => {
+                let parent = self.get_mut(parent).expect("hierarchy refers to a removed node");
+                parent.children.retain(|&c| c != id);
+            }
+            None => self.roots.retain(|&r| r != id),
+        }
+        if let Some(node) = self.get_mut(id) {
+            node.parent = None;
+        }
+        Ok(())
+    }
+
+    /// Attaches the orphan `id` under `parent`, or as a root.
+    pub fn attach(&mut self, id: NodeId, parent: Option<NodeId>) -> Result<(), SceneError> {
+        if !self.contains(id) {
+            return Err(SceneError::InvalidNode(id));
+        }
+        if !self.is_orphan(id) {
+            return Err(SceneError::AlreadyAttached(id));
+        }
+        if let Some(parent) = parent {
+            if !self.contains(parent) {
+                return Err(SceneError::InvalidNode(parent));
+            }
+            if parent == id || self.is_ancestor(id, parent) {
+                return Err(SceneError::Cycle { node: id, parent });
+            }
+        }
+        self.link(id, parent);
+        Ok(())
+    }
+
+    /// Moves `id` with its subtree under `parent` (or to the roots), keeping its
+    /// local position. Fails without changing anything if that would form a cycle.
+    pub fn reparent(&mut self, id: NodeId, parent: Option<NodeId>) -> Result<(), SceneError> {
+        if !self.contains(id) {
+            return Err(SceneError::InvalidNode(id));
+        }
+        if let Some(parent) = parent {
+            if !self.contains(parent) {
+                return Err(SceneError::InvalidNode(parent));
+            }
+            if parent == id || self.is_ancestor(id, parent) {
+                return Err(SceneError::Cycle { node: id, parent });
+            }
+        }
+        self.detach(id)?;
+        self.link(id, parent);
+        Ok(())
+    }
+
+    /// Removes `id` and its subtree, invalidating their ids. Returns how many
+    /// nodes were removed.
+    pub fn remove(&mut self, id: NodeId) -> Result<usize, SceneError> {
+        self.detach(id)?;
+        Ok(self.free_subtree(id))
+    }
+
+    /// Removes every orphan subtree. Returns how many nodes were removed.
+    pub fn remove_orphans(&mut self) -> usize {
+        let roots: HashSet<NodeId> = self.roots.iter().copied().collect();
+        let orphans: Vec<NodeId> =
+            self.iter().filter(|(id, node)| node.parent.is_none() && !roots.contains(id)).map(|(id, _)| id).collect();
+        // Orphans are already out of the hierarchy, so there is nothing to detach
+        orphans.into_iter().map(|id| self.free_subtree(id)).sum()
+    }
+
+    /// Frees the slots of the detached `id` and its subtree; returns how many.
+    fn free_subtree(&mut self, id: NodeId) -> usize {
+        let mut removed = 0;
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            let slot = &mut self.slots[id.index()];
+            if let Some(node) = slot.node.take() {
+                stack.extend(node.children);
+                // A slot out of generations is retired, so no id can ever match it again
+                if let Some(generation) = slot.generation.checked_add(1) {
+                    slot.generation = generation;
+                    self.free.push(id.index);
+                }
+                removed += 1;
+            }
+        }
+        self.len -= removed;
+        removed
+    }
+
+    fn link(&mut self, id: NodeId, parent: Option<NodeId>) {
+        match parent {
+            Some(p) => self.get_mut(p).expect("parent is not in the scene").children.push(id),
+            None => self.roots.push(id),
+        }
+        if let Some(node) = self.get_mut(id) {
+            node.parent = parent;
+        }
+    }
+
+    // ============================================================================
+    // Flag Queries
+    // ============================================================================
+
+    /// True if the node and all its ancestors have `flags` set.
+    pub fn is_effectively(&self, id: NodeId, flags: NodeFlags) -> bool {
+        let mut current = Some(id);
+        while let Some(id) = current {
+            match self.get(id) {
+                Some(node) if node.flags.contains(flags) => current = node.parent,
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// Collects nodes that effectively have `flags` and belong to a layer in
+    /// `mask`, in depth-first order, leaving out nodes wholly clipped away.
+    /// Subtrees whose root lacks a flag are skipped without being visited.
+    /// `out` is cleared first so renderers can reuse the allocation every frame.
+    pub fn query_into(&self, flags: NodeFlags, mask: LayerMask, out: &mut Vec<NodeId>) {
+        out.clear();
+        // Clip regions in effect, the first cutting nothing
+        let mut regions = vec![ClipRegion::UNBOUNDED];
+        let mut stack: Vec<(NodeId, Vec3, usize)> = self.roots.iter().rev().map(|&id| (id, Vec3::zero(), 0)).collect();
+        while let Some((id, origin, region)) = stack.pop() {
+            let node = self.node(id);
+            if !node.flags.contains(flags) {
+                continue;
+            }
+            let center = origin + node.position;
+            if node.matches(flags, mask) && (region == 0 || regions[region].cut(&node_bounds(node, center)).is_some()) {
+                out.push(id);
+            }
+            let inner = if node.clip.is_empty() {
+                region
+            } else {
+                let clipped = node.clip.iter().fold(regions[region].clone(), |r, clip| r.with(clip, center));
+                regions.push(clipped);
+                regions.len() - 1
+            };
+            stack.extend(node.children.iter().rev().map(|&child| (child, center, inner)));
+        }
+    }
+
+    /// Allocating form of [`query_into`](Self::query_into).
+    pub fn query(&self, flags: NodeFlags, mask: LayerMask) -> Vec<NodeId> {
+        let mut out = Vec::new();
+        self.query_into(flags, mask, &mut out);
+        out
+    }
+
+    /// Nodes the graphics renderer should draw.
+    #[inline]
+    pub fn visible_nodes(&self, mask: LayerMask) -> Vec<NodeId> {
+        self.query(NodeFlags::VISIBLE, mask)
+    }
+
+    /// Nodes the haptic renderer should render.
+    #[inline]
+    pub fn touchable_nodes(&self, mask: LayerMask) -> Vec<NodeId> {
+        self.query(NodeFlags::TOUCHABLE, mask)
+    }
+
+    /// Nodes pointer picking should test.
+    #[inline]
+    pub fn pickable_nodes(&self, mask: LayerMask) -> Vec<NodeId> {
+        self.query(NodeFlags::PICKABLE, mask)
+    }
+
+    // ============================================================================
+    // Clipping
+    // ============================================================================
+
+    /// Region the clips of `id`'s ancestors cut it to.
+    pub fn clip_region(&self, id: NodeId) -> Option<ClipRegion> {
+        let mut ancestors = Vec::new();
+        let mut current = self.get(id)?.parent;
+        while let Some(parent) = current {
+            ancestors.push(parent);
+            current = self.node(parent).parent;
+        }
+        let mut region = ClipRegion::UNBOUNDED;
+        let mut origin = Vec3::zero();
+        for &ancestor in ancestors.iter().rev() {
+            let node = self.node(ancestor);
+            origin += node.position;
+            for clip in &node.clip {
+                region.clip(clip, origin);
+            }
+        }
+        Some(region)
+    }
+
+    /// World box of `id` cut to its clip region's box; none if the node is
+    /// missing or clipped away entirely.
+    pub fn clipped_bounds(&self, id: NodeId) -> Option<Aabb> {
+        let bounds = node_bounds(self.get(id)?, self.world_position(id)?);
+        self.clip_region(id)?.cut(&bounds)
+    }
+
+    /// Invokes the node's click handler. Returns false if the node has none.
+    pub fn click(&mut self, id: NodeId) -> bool {
+        match self.get_mut(id).and_then(|n| n.on_click.as_mut()) {
+            Some(handler) => {
+                handler(id);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// World box of `node` centered at `center`.
+fn node_bounds(node: &Node, center: Vec3) -> Aabb {
+    Aabb::from_center_half_extents(center, node.size.abs() * 0.5)
+}
+
+#[cfg(test)]
+#[path = "tests/graph_tests.rs"]
+mod tests;
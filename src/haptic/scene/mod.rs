@@ -0,0 +1,17 @@
+// src/haptic/scene/mod.rs
+pub mod billboard;
+pub mod builder;
+pub mod camera;
+pub mod clip;
+pub mod flags;
+pub mod graph;
+pub mod test_scenes;
+pub mod validate;
+pub use billboard::{BillboardMode, BillboardPose, Billboards};
+pub use builder::{Layout, NodeBuilder};
+pub use camera::CameraPose;
+pub use clip::{Clip, ClipRegion};
+pub use flags::{LayerMask, NodeFlags};
+pub use graph::{ClickHandler, Node, NodeHaptics, NodeId, NodeKind, Scene, SceneError};
+pub use test_scenes::TestScene;
+pub use validate::{validate, Diagnostic, SceneValidator, Severity, ValidationReport};
@@ -0,0 +1,194 @@
+//! Standard benchmark scenes for validating device backends and rendering algorithms.
+//!
+//! Every scene is built in meters with its contact surfaces facing +Z, centered on
+//! the origin so it fits a desktop device workspace. Each comes with a probe path:
+//! waypoints a scripted or recorded tool follows (approach, contact, slide,
+//! retract), so runs on different backends and algorithms are directly comparable.
+
+use super::builder::NodeBuilder;
+use super::graph::Scene;
+use crate::core::{NewtonsPerMeter, Vec3};
+
+/// Thickness of walls and plates.
+const SLAB: f32 = 0.01;
+
+/// A generated scene with its reference probe path.
+#[derive(Debug)]
+pub struct TestScene {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub scene: Scene,
+    /// Waypoints of the reference probe path, in scene coordinates.
+    pub path: Vec<Vec3>,
+}
+
+impl TestScene {
+    /// Point at fraction `t` (0..=1) along the path, by arc length.
+    pub fn path_point(&self, t: f32) -> Vec3 {
+        let lengths: Vec<f32> = self.path.windows(2).map(|w| (w[1] - w[0]).length()).collect();
+        let total: f32 = lengths.iter().sum();
+        let mut remaining = t.clamp(0.0, 1.0) * total;
+        for (segment, length) in self.path.windows(2).zip(lengths) {
+            if remaining <= length && length > 0.0 {
+                return segment[0].lerp(segment[1], remaining / length);
+            }
+            remaining -= length;
+        }
+        self.path.last().copied().unwrap_or_else(Vec3::zero)
+    }
+}
+
+/// All scenes with default parameters.
+pub fn gallery() -> Vec<TestScene> {
+    vec![
+        stiff_wall(NewtonsPerMeter(2000.0)),
+        corner_crevice(0.004),
+        friction_plane(0.6),
+        textured_plate(0.004, 0.001),
+        button_wall(3, 4),
+        peg_in_hole(0.001),
+    ]
+}
+
+/// Looks up a default scene by name.
+pub fn by_name(name: &str) -> Option<TestScene> {
+    gallery().into_iter().find(|s| s.name == name)
+}
+
+// ============================================================================
+// Scenes
+// ============================================================================
+
+/// Flat wall of the given stiffness; the probe presses 5 mm into it and releases.
+/// The classic test for stability at high stiffness.
+pub fn stiff_wall(stiffness: NewtonsPerMeter) -> TestScene {
+    let scene = NodeBuilder::panel().name("wall").size(0.15, 0.15, SLAB).at(0.0, 0.0, -SLAB * 0.5).stiffness(stiffness).into_scene();
+    TestScene {
+        name: "stiff_wall",
+        description: "flat wall for stability at high stiffness",
+        scene,
+        path: vec![Vec3::new(0.0, 0.0, 0.03), Vec3::new(0.0, 0.0, -0.005), Vec3::new(0.0, 0.0, 0.03)],
+    }
+}
+
+/// Floor and two walls meeting in a corner, with a `gap`-wide crevice cut into the
+/// floor. Exercises multi-constraint contacts and proxies squeezing into narrow gaps.
+pub fn corner_crevice(gap: f32) -> TestScene {
+    let half = 0.05;
+    let floor_half = (half - gap * 0.5) * 0.5;
+    let floor = |name: &str, x: f32| NodeBuilder::panel().name(name).size(floor_half * 2.0, 0.1, SLAB).at(x, 0.0, -SLAB * 0.5);
+    let scene = NodeBuilder::group()
+        .name("corner")
+        .child(floor("floor_left", -gap * 0.5 - floor_half))
+        .child(floor("floor_right", gap * 0.5 + floor_half))
+        .child(NodeBuilder::panel().name("wall_back").size(0.1, SLAB, 0.1).at(0.0, half + SLAB * 0.5, 0.05))
+        .child(NodeBuilder::panel().name("wall_side").size(SLAB, 0.1, 0.1).at(half + SLAB * 0.5, 0.0, 0.05))
+        .into_scene();
+    TestScene {
+        name: "corner_crevice",
+        description: "three-plane corner and a narrow crevice",
+        scene,
+        path: vec![
+            Vec3::new(0.0, -0.03, 0.03),
+            Vec3::new(0.0, -0.03, -0.002),
+            Vec3::new(0.0, 0.045, -0.002),
+            Vec3::new(0.045, 0.045, -0.002),
+            Vec3::new(0.045, 0.045, 0.03),
+        ],
+    }
+}
+
+/// Horizontal plate with Coulomb friction `mu`; the probe presses in and slides
+/// across it for stick-slip behavior.
+pub fn friction_plane(mu: f32) -> TestScene {
+    let scene = NodeBuilder::panel().name("plane").size(0.15, 0.15, SLAB).at(0.0, 0.0, -SLAB * 0.5).friction(mu).into_scene();
+    TestScene {
+        name: "friction_plane",
+        description: "frictional plate for stick-slip",
+        scene,
+        path: vec![
+            Vec3::new(-0.06, 0.0, 0.02),
+            Vec3::new(-0.06, 0.0, -0.003),
+            Vec3::new(0.06, 0.0, -0.003),
+            Vec3::new(0.06, 0.0, 0.02),
+        ],
+    }
+}
+
+/// Plate with parallel ridges across X every `pitch` meters, each `height` tall,
+/// for geometric texture rendering. The probe strokes across the ridges.
+pub fn textured_plate(pitch: f32, height: f32) -> TestScene {
+    let width = 0.1;
+    let count = (width / pitch).floor().max(1.0) as usize;
+    let ridges = (0..count).map(|i| {
+        let x = -width * 0.5 + pitch * (i as f32 + 0.5);
+        NodeBuilder::panel().name(format!("ridge_{i}")).size(pitch * 0.5, width, height).at(x, 0.0, height * 0.5)
+    });
+    let scene = NodeBuilder::panel()
+        .name("plate")
+        .size(width, width, SLAB)
+        .at(0.0, 0.0, -SLAB * 0.5)
+        .child(NodeBuilder::group().at(0.0, 0.0, SLAB * 0.5).children(ridges))
+        .into_scene();
+    TestScene {
+        name: "textured_plate",
+        description: "periodic ridges for texture rendering",
+        scene,
+        path: vec![
+            Vec3::new(-0.045, 0.0, 0.02),
+            Vec3::new(-0.045, 0.0, height * 0.5),
+            Vec3::new(0.045, 0.0, height * 0.5),
+            Vec3::new(0.045, 0.0, 0.02),
+        ],
+    }
+}
+
+/// Panel with a `rows` × `cols` grid of 2 cm buttons; the probe presses each in turn.
+pub fn button_wall(rows: usize, cols: usize) -> TestScene {
+    let pitch = 0.03;
+    let center = |i: usize, n: usize| (i as f32 - (n as f32 - 1.0) * 0.5) * pitch;
+    let positions: Vec<(usize, usize, f32, f32)> =
+        (0..rows).flat_map(|r| (0..cols).map(move |c| (r, c, center(c, cols), -center(r, rows)))).collect();
+    let buttons = positions.iter().map(|&(r, c, x, y)| {
+        NodeBuilder::button(format!("{}", r * cols + c + 1)).name(format!("button_{r}_{c}")).size(0.02, 0.02, 0.005).at(x, y, 0.0025)
+    });
+    let scene = NodeBuilder::panel()
+        .name("panel")
+        .size(pitch * cols as f32 + 0.01, pitch * rows as f32 + 0.01, SLAB)
+        .at(0.0, 0.0, -SLAB * 0.5)
+        .child(NodeBuilder::group().at(0.0, 0.0, SLAB * 0.5).children(buttons))
+        .into_scene();
+    let path = positions
+        .iter()
+        .flat_map(|&(_, _, x, y)| [Vec3::new(x, y, 0.015), Vec3::new(x, y, 0.002), Vec3::new(x, y, 0.015)])
+        .collect();
+    TestScene { name: "button_wall", description: "grid of buttons for press targeting", scene, path }
+}
+
+/// Plate with a 1 cm square hole, made of four blocks, sized for a 1 cm peg with
+/// `clearance` on each side. The probe descends into the hole.
+pub fn peg_in_hole(clearance: f32) -> TestScene {
+    let hole = 0.01 + clearance * 2.0;
+    let (outer, depth) = (0.08, 0.02);
+    let side = (outer - hole) * 0.5;
+    let offset = (hole + side) * 0.5;
+    let block = |name: &str, w: f32, h: f32, x: f32, y: f32| NodeBuilder::panel().name(name).size(w, h, depth).at(x, y, -depth * 0.5);
+    let scene = NodeBuilder::group()
+        .name("fixture")
+        .child(block("left", side, outer, -offset, 0.0))
+        .child(block("right", side, outer, offset, 0.0))
+        .child(block("front", hole, side, 0.0, -offset))
+        .child(block("back", hole, side, 0.0, offset))
+        .child(NodeBuilder::panel().name("bottom").size(outer, outer, SLAB).at(0.0, 0.0, -depth - SLAB * 0.5))
+        .into_scene();
+    TestScene {
+        name: "peg_in_hole",
+        description: "square hole for insertion with tight clearance",
+        scene,
+        path: vec![Vec3::new(0.0, 0.0, 0.03), Vec3::new(0.0, 0.0, -depth + 0.001), Vec3::new(0.0, 0.0, 0.03)],
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/test_scenes_tests.rs"]
+mod tests;
@@ -0,0 +1,75 @@
+use super::*;
+use crate::core::Deg;
+use crate::scene::{Node, NodeKind};
+
+const TEST_EPSILON: f32 = 1e-4;
+
+fn close(a: Vec3, b: Vec3) -> bool {
+    a.distance_to(b) < TEST_EPSILON
+}
+
+#[test]
+fn test_full_faces_the_camera() {
+    let camera = CameraPose::new(Vec3::new(1.0, 1.0, 0.0), -Vec3::unit_x());
+    let pose = BillboardMode::Full.pose(Vec3::zero(), 0.1, &camera);
+    assert_eq!(pose.scale, 1.0);
+    assert!(close(pose.orientation.rotate(Vec3::unit_z()), Vec3::new(1.0, 1.0, 0.0).normalize()));
+    // Screen up stays in the plane of the camera up
+    assert!(pose.orientation.rotate(Vec3::unit_x()).y.abs() < TEST_EPSILON);
+
+    // Straight above, with the camera up along the view, lines stay along X
+    let above = CameraPose::new(Vec3::new(0.0, 1.0, 0.0), -Vec3::unit_y());
+    let pose = BillboardMode::Full.pose(Vec3::zero(), 0.1, &above);
+    assert!(close(pose.orientation.rotate(Vec3::unit_z()), Vec3::unit_y()));
+    assert!(close(pose.orientation.rotate(Vec3::unit_x()), Vec3::unit_x()));
+}
+
+#[test]
+fn test_axis_y_stays_upright() {
+    let camera = CameraPose::new(Vec3::new(1.0, 1.0, 0.0), -Vec3::unit_x());
+    let pose = BillboardMode::AxisY.pose(Vec3::zero(), 0.1, &camera);
+    assert!(close(pose.orientation.rotate(Vec3::unit_z()), Vec3::unit_x()));
+    assert!(close(pose.orientation.rotate(Vec3::unit_y()), Vec3::unit_y()));
+
+    // Straight overhead it falls back to facing against the view direction
+    let above = CameraPose::new(Vec3::new(0.0, 2.0, 0.0), Vec3::new(0.0, -1.0, -1.0));
+    let pose = BillboardMode::AxisY.pose(Vec3::zero(), 0.1, &above);
+    assert!(close(pose.orientation.rotate(Vec3::unit_z()), Vec3::unit_z()));
+}
+
+#[test]
+fn test_fixed_pixel_size_scales_with_distance() {
+    let camera = CameraPose::default().with_projection(Deg(90.0), 1000.0);
+    let mode = BillboardMode::FixedPixelSize { pixels: 50.0 };
+    // 50 px is 10 cm at 1 m, twice a 5 cm node
+    let near = mode.pose(Vec3::new(0.0, 0.0, -1.0), 0.05, &camera);
+    let far = mode.pose(Vec3::new(0.0, 0.0, -3.0), 0.05, &camera);
+    assert!((near.scale - 2.0).abs() < TEST_EPSILON);
+    assert!((far.scale - 6.0).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_store_updates_and_forgets_removed_nodes() {
+    let mut scene = Scene::new();
+    let mut node = Node::new(NodeKind::Label { text: "hi".into() });
+    node.position = Vec3::new(0.0, 0.0, -1.0);
+    let label = scene.insert(node, None).unwrap();
+    let other = scene.insert(Node::new(NodeKind::Group), None).unwrap();
+
+    let mut billboards = Billboards::new();
+    billboards.insert(label, BillboardMode::Full);
+    billboards.insert(other, BillboardMode::AxisY);
+    assert_eq!(billboards.get(label), Some(BillboardMode::Full));
+    assert!(billboards.pose(label).is_none());
+
+    billboards.update(&scene, &CameraPose::default());
+    let pose = billboards.pose(label).unwrap();
+    assert!(close(pose.orientation.rotate(Vec3::unit_z()), Vec3::unit_z()));
+
+    scene.remove(other).unwrap();
+    billboards.update(&scene, &CameraPose::default());
+    assert_eq!(billboards.len(), 1);
+    assert!(billboards.pose(other).is_none());
+    assert_eq!(billboards.remove(label), Some(BillboardMode::Full));
+    assert!(billboards.is_empty());
+}
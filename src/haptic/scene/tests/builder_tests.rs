@@ -0,0 +1,70 @@
+use super::*;
+use crate::core::NewtonsPerMeter;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+#[test]
+fn test_builder_inserts_hierarchy() {
+    let scene = NodeBuilder::panel()
+        .name("root")
+        .child(NodeBuilder::button("OK").name("ok").at(0.0, 0.0, 0.01))
+        .child(NodeBuilder::label("Title"))
+        .into_scene();
+
+    assert_eq!(scene.len(), 3);
+    let root = scene.find("root").unwrap();
+    let ok = scene.find("ok").unwrap();
+    assert_eq!(scene.roots(), &[root]);
+    assert_eq!(scene.get(root).unwrap().children().len(), 2);
+    assert_eq!(scene.get(ok).unwrap().parent(), Some(root));
+    assert!(scene.get(ok).unwrap().haptics.is_some());
+}
+
+#[test]
+fn test_scene_macro_with_handlers_and_properties() {
+    let clicks = Arc::new(AtomicUsize::new(0));
+    let counter = clicks.clone();
+
+    let mut scene = crate::scene! {
+        panel.name("main").at(0.0, 1.0, 0.0) {
+            button("OK").name("ok").stiffness(NewtonsPerMeter(800.0)).on_click(move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }),
+            group {
+                label("Hint").name("hint"),
+            },
+        },
+        panel.name("side").intangible(),
+    };
+
+    assert_eq!(scene.roots().len(), 2);
+    let ok = scene.find("ok").unwrap();
+    assert_eq!(scene.get(ok).unwrap().haptics.unwrap().stiffness, NewtonsPerMeter(800.0));
+    assert_eq!(scene.get(ok).unwrap().kind, NodeKind::Button { label: "OK".into() });
+    assert!(scene.get(scene.find("side").unwrap()).unwrap().haptics.is_none());
+    assert_eq!(scene.world_position(scene.find("hint").unwrap()), Some(Vec3::new(0.0, 1.0, 0.0)));
+
+    assert!(scene.click(ok));
+    assert!(scene.click(ok));
+    assert!(!scene.click(scene.find("hint").unwrap()));
+    assert_eq!(clicks.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_column_layout_stacks_children() {
+    let scene = crate::scene! {
+        group.column(0.1) {
+            button("A").name("a").size(0.2, 0.2, 0.05),
+            button("B").name("b").size(0.2, 0.2, 0.05).at(0.3, 0.0, 0.0),
+        }
+    };
+
+    let a = scene.get(scene.find("a").unwrap()).unwrap().position;
+    let b = scene.get(scene.find("b").unwrap()).unwrap().position;
+    assert!((a.y - 0.15).abs() < TEST_EPSILON);
+    assert!((b.y + 0.15).abs() < TEST_EPSILON);
+    // Off-axis offsets are preserved
+    assert!((b.x - 0.3).abs() < TEST_EPSILON);
+}
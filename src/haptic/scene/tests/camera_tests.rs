@@ -0,0 +1,42 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+#[test]
+fn test_new_normalizes_forward() {
+    let camera = CameraPose::new(Vec3::zero(), Vec3::new(0.0, 0.0, -3.0));
+    assert!((camera.forward.length() - 1.0).abs() < TEST_EPSILON);
+    assert_eq!(CameraPose::new(Vec3::zero(), Vec3::zero()).forward, -Vec3::unit_z());
+}
+
+#[test]
+fn test_pixel_size_grows_with_depth() {
+    let camera = CameraPose::default().with_projection(Deg(90.0), 1000.0);
+    assert!((camera.depth(Vec3::new(0.3, 0.0, -2.0)) - 2.0).abs() < TEST_EPSILON);
+    // A 90° view is 2 m tall at 1 m
+    assert!((camera.pixel_size(Vec3::new(0.0, 0.0, -1.0)) - 0.002).abs() < TEST_EPSILON);
+    assert!((camera.pixel_size(Vec3::new(0.0, 0.0, -2.0)) - 0.004).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_screen_ray_through_pixels() {
+    let camera = CameraPose::default().with_projection(Deg(90.0), 1000.0);
+    let center = camera.screen_ray(500.0, 500.0, 1000.0);
+    assert!(center.direction.distance_to(-Vec3::unit_z()) < TEST_EPSILON);
+    // The top right corner of a square 90° view is 45° off along both axes
+    let corner = camera.screen_ray(1000.0, 0.0, 1000.0);
+    assert!(corner.direction.distance_to(Vec3::new(1.0, 1.0, -1.0).normalize()) < TEST_EPSILON);
+}
+
+#[test]
+fn test_sees_points_inside_the_frustum() {
+    // 90° vertical view of a square viewport looking down -Z
+    let camera = CameraPose::default().with_projection(Deg(90.0), 1000.0);
+    assert!(camera.sees(Vec3::new(0.0, 0.0, -1.0), 1000.0));
+    assert!(camera.sees(Vec3::new(0.99, -0.99, -1.0), 1000.0));
+    assert!(!camera.sees(Vec3::new(0.0, 1.01, -1.0), 1000.0));
+    assert!(!camera.sees(Vec3::new(1.5, 0.0, -1.0), 1000.0));
+    // Twice as wide
+    assert!(camera.sees(Vec3::new(1.5, 0.0, -1.0), 2000.0));
+    assert!(!camera.sees(Vec3::new(0.0, 0.0, 1.0), 1000.0));
+}
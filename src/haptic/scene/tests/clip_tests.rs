@@ -0,0 +1,81 @@
+use super::*;
+use crate::scene::{LayerMask, NodeBuilder, NodeId, Scene};
+
+const TEST_EPSILON: f32 = 1e-5;
+
+/// A 1 m scroll view at z = -1 clipping a content group with a row inside it,
+/// a row half out of its top, a row and a caption above it.
+fn scroll_view() -> (Scene, NodeId) {
+    let scene = NodeBuilder::panel()
+        .name("view")
+        .at(0.0, 0.0, -1.0)
+        .size(1.0, 1.0, 0.1)
+        .clip(Clip::bounds(Vec3::new(1.0, 1.0, 1.0)))
+        .child(
+            NodeBuilder::group()
+                .name("content")
+                .child(NodeBuilder::button("inside").name("inside").at(0.0, 0.0, 0.1).size(0.8, 0.3, 0.02))
+                .child(NodeBuilder::button("half").name("half").at(0.0, 0.5, 0.1).size(0.8, 0.3, 0.02))
+                .child(NodeBuilder::button("outside").name("outside").at(0.0, 1.0, 0.1).size(0.8, 0.3, 0.02))
+                .child(NodeBuilder::label("caption").name("caption").at(0.0, 0.8, 0.1)),
+        )
+        .into_scene();
+    let content = scene.find("content").unwrap();
+    (scene, content)
+}
+
+fn names(scene: &Scene, nodes: &[NodeId]) -> Vec<String> {
+    nodes.iter().map(|&id| scene.get(id).unwrap().name.clone()).collect()
+}
+
+#[test]
+fn test_region_folds_axis_planes_into_its_box() {
+    assert!(ClipRegion::default().is_unbounded());
+    let region = ClipRegion::new()
+        .with(&Clip::bounds(Vec3::new(1.0, 1.0, 1.0)), Vec3::zero())
+        .with(&Clip::plane(Vec3::new(0.0, 0.25, 0.0), Vec3::unit_y()), Vec3::zero())
+        .with(&Clip::plane(Vec3::zero(), Vec3::new(1.0, 1.0, 0.0)), Vec3::zero());
+    assert!(!region.is_unbounded());
+    assert_eq!(region.bounds(), Aabb::new(Vec3::new(-0.5, 0.25, -0.5), Vec3::splat(0.5)));
+    assert_eq!(region.boundary().len(), 7);
+
+    assert!(region.contains(Vec3::new(0.4, 0.3, 0.0)));
+    assert!(!region.contains(Vec3::new(0.4, 0.2, 0.0)));
+    assert!(!region.contains(Vec3::new(-0.4, 0.3, 0.0)));
+    let corner = Aabb::from_center_half_extents(Vec3::new(-0.45, 0.3, 0.0), Vec3::splat(0.02));
+    assert_eq!(region.cut(&corner), None);
+    let edge = Aabb::from_center_half_extents(Vec3::new(0.5, 0.5, 0.0), Vec3::splat(0.1));
+    assert_eq!(region.cut(&edge), Some(Aabb::new(Vec3::new(0.4, 0.4, -0.1), Vec3::new(0.5, 0.5, 0.1))));
+
+    // A ray along +X enters through the slanted plane
+    let ray = Ray::new(Vec3::new(-0.4, 0.3, 0.0), Vec3::unit_x());
+    let (enter, exit) = region.ray_interval(&ray, (f32::NEG_INFINITY, f32::INFINITY)).unwrap();
+    assert!((enter - 0.1).abs() < TEST_EPSILON && (exit - 0.9).abs() < TEST_EPSILON);
+    assert!(region.plane_at(ray.at(enter)).is_some());
+    assert_eq!(region.ray_interval(&ray, (0.0, 0.05)), None);
+}
+
+#[test]
+fn test_queries_leave_out_clipped_nodes() {
+    let (mut scene, content) = scroll_view();
+    let view = scene.find("view").unwrap();
+    let half = scene.find("half").unwrap();
+    let expected = ["view", "content", "inside", "half"];
+    assert_eq!(names(&scene, &scene.visible_nodes(LayerMask::ALL)), expected);
+    assert_eq!(names(&scene, &scene.pickable_nodes(LayerMask::ALL)), expected);
+    assert_eq!(names(&scene, &scene.touchable_nodes(LayerMask::ALL)), ["view", "inside", "half"]);
+
+    // The view's own clip does not cut the view
+    assert!(scene.clip_region(view).unwrap().is_unbounded());
+    assert_eq!(scene.clip_region(half).unwrap().bounds().max.y, 0.5);
+    let cut = scene.clipped_bounds(half).unwrap();
+    assert!((cut.min.y - 0.35).abs() < TEST_EPSILON && (cut.max.y - 0.5).abs() < TEST_EPSILON);
+    assert_eq!(scene.clipped_bounds(scene.find("outside").unwrap()), None);
+
+    // Scrolling brings the top row in and pushes the caption to the edge
+    scene.get_mut(content).unwrap().position.y = -0.5;
+    let visible = names(&scene, &scene.visible_nodes(LayerMask::ALL));
+    assert_eq!(visible, ["view", "content", "inside", "half", "outside", "caption"]);
+    scene.get_mut(content).unwrap().position.y = -1.0;
+    assert!(!names(&scene, &scene.visible_nodes(LayerMask::ALL)).contains(&"inside".to_string()));
+}
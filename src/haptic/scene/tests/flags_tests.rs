@@ -0,0 +1,74 @@
+use super::*;
+use crate::scene::{NodeBuilder, NodeId, Scene};
+
+#[test]
+fn test_flag_operations() {
+    let mut flags = NodeFlags::default();
+    assert_eq!(flags, NodeFlags::ALL);
+    flags.set(NodeFlags::TOUCHABLE, false);
+    assert!(flags.contains(NodeFlags::VISIBLE | NodeFlags::PICKABLE));
+    assert!(!flags.contains(NodeFlags::TOUCHABLE));
+    assert_eq!(!flags, NodeFlags::TOUCHABLE);
+    assert_eq!(NodeFlags::NONE.with(NodeFlags::VISIBLE, true), NodeFlags::VISIBLE);
+    assert_eq!(format!("{:?}", flags), "NodeFlags(VISIBLE | PICKABLE)");
+    assert_eq!(format!("{:?}", NodeFlags::NONE), "NodeFlags(NONE)");
+}
+
+#[test]
+fn test_layer_masks() {
+    let ui = LayerMask::layer(3);
+    let mask = LayerMask::DEFAULT | ui;
+    assert!(mask.intersects(ui));
+    assert!(!LayerMask::DEFAULT.intersects(ui));
+    assert_eq!(mask.without_layer(0), ui);
+    assert_eq!(LayerMask::NONE.with_layer(3), ui);
+    assert!(LayerMask::ALL.intersects(ui));
+}
+
+#[test]
+#[should_panic(expected = "layer index out of range")]
+fn test_layer_out_of_range_panics() {
+    let _ = LayerMask::DEFAULT.with_layer(32);
+}
+
+fn sample_scene() -> Scene {
+    NodeBuilder::group()
+        .name("root")
+        .child(NodeBuilder::panel().name("panel").child(NodeBuilder::button("OK").name("ok")))
+        .child(NodeBuilder::panel().name("preview").ghost())
+        .child(NodeBuilder::panel().name("rail").guide().layers(LayerMask::layer(2)))
+        .child(NodeBuilder::group().name("hidden").hidden().child(NodeBuilder::button("X").name("x")))
+        .into_scene()
+}
+
+#[test]
+fn test_ghost_and_guide() {
+    let scene = sample_scene();
+    let preview = scene.get(scene.find("preview").unwrap()).unwrap();
+    assert!(preview.is_visible() && !preview.is_touchable() && !preview.is_pickable());
+    let rail = scene.get(scene.find("rail").unwrap()).unwrap();
+    assert!(!rail.is_visible() && rail.is_touchable() && !rail.is_pickable());
+    // Groups carry the flag but have no surface, so they are never touched
+    assert!(!scene.get(scene.find("root").unwrap()).unwrap().is_touchable());
+}
+
+#[test]
+fn test_queries_respect_hierarchy_and_layers() {
+    let scene = sample_scene();
+    let names = |ids: Vec<NodeId>| ids.iter().map(|id| scene.get(*id).unwrap().name.clone()).collect::<Vec<_>>();
+
+    assert_eq!(names(scene.visible_nodes(LayerMask::ALL)), ["root", "panel", "ok", "preview"]);
+    assert_eq!(names(scene.touchable_nodes(LayerMask::ALL)), ["panel", "ok", "rail", "x"]);
+    assert_eq!(names(scene.touchable_nodes(LayerMask::DEFAULT)), ["panel", "ok", "x"]);
+    assert_eq!(names(scene.touchable_nodes(LayerMask::layer(2))), ["rail"]);
+    assert_eq!(names(scene.pickable_nodes(LayerMask::ALL)), ["root", "panel", "ok", "hidden", "x"]);
+
+    let x = scene.find("x").unwrap();
+    assert!(scene.get(x).unwrap().is_visible());
+    assert!(!scene.is_effectively(x, NodeFlags::VISIBLE));
+    assert!(scene.is_effectively(x, NodeFlags::PICKABLE | NodeFlags::TOUCHABLE));
+
+    let mut reused = vec![x];
+    scene.query_into(NodeFlags::VISIBLE | NodeFlags::TOUCHABLE, LayerMask::ALL, &mut reused);
+    assert_eq!(names(reused), ["panel", "ok"]);
+}
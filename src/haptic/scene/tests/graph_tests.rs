@@ -0,0 +1,90 @@
+use super::*;
+
+/// root(a(b), c)
+fn tree() -> (Scene, [NodeId; 4]) {
+    let mut scene = Scene::new();
+    let root = scene.insert(Node::new(NodeKind::Panel), None).unwrap();
+    let a = scene.insert(Node::new(NodeKind::Group), Some(root)).unwrap();
+    let b = scene.insert(Node::new(NodeKind::Group), Some(a)).unwrap();
+    let c = scene.insert(Node::new(NodeKind::Group), Some(root)).unwrap();
+    (scene, [root, a, b, c])
+}
+
+#[test]
+fn test_removed_ids_stay_invalid() {
+    let (mut scene, [root, a, b, c]) = tree();
+    assert_eq!(scene.remove(a), Ok(2));
+    assert_eq!(scene.len(), 2);
+    assert!(!scene.contains(a) && !scene.contains(b));
+    assert_eq!(scene.get(root).unwrap().children(), &[c]);
+
+    // The slot is reused under a new generation
+    let d = scene.insert(Node::new(NodeKind::Group), None).unwrap();
+    assert!(d.index() == a.index() || d.index() == b.index());
+    assert_ne!(d, a);
+    assert!(scene.get(a).is_none() && scene.get(b).is_none());
+    assert_eq!(scene.remove(a), Err(SceneError::InvalidNode(a)));
+    assert_eq!(scene.insert(Node::new(NodeKind::Group), Some(a)), Err(SceneError::InvalidNode(a)));
+    assert_eq!(scene.iter().count(), 3);
+}
+
+#[test]
+fn test_slot_out_of_generations_is_retired() {
+    let mut scene = Scene::new();
+    let first = scene.insert(Node::new(NodeKind::Group), None).unwrap();
+    scene.slots[first.index()].generation = u32::MAX - 1;
+    let last = NodeId { index: first.index, generation: u32::MAX - 1 };
+    scene.remove(last).unwrap();
+
+    // The last generation is still handed out, then the slot is never reused
+    let final_id = scene.insert(Node::new(NodeKind::Group), None).unwrap();
+    assert_eq!((final_id.index(), final_id.generation()), (first.index(), u32::MAX));
+    scene.remove(final_id).unwrap();
+    let fresh = scene.insert(Node::new(NodeKind::Group), None).unwrap();
+    assert_ne!(fresh.index(), first.index());
+    assert!(!scene.contains(final_id) && !scene.contains(first));
+    assert_eq!((scene.len(), scene.iter().count()), (1, 1));
+}
+
+#[test]
+fn test_reparent_moves_subtree() {
+    let (mut scene, [root, a, b, c]) = tree();
+    scene.get_mut(c).unwrap().position = Vec3::new(1.0, 0.0, 0.0);
+    scene.get_mut(a).unwrap().position = Vec3::new(0.0, 2.0, 0.0);
+    scene.reparent(a, Some(c)).unwrap();
+    assert_eq!(scene.get(root).unwrap().children(), &[c]);
+    assert_eq!(scene.get(c).unwrap().children(), &[a]);
+    assert_eq!(scene.world_position(b), Some(Vec3::new(1.0, 2.0, 0.0)));
+    assert!(scene.is_ancestor(root, b) && !scene.is_ancestor(b, b));
+
+    scene.reparent(c, None).unwrap();
+    assert_eq!(scene.roots(), &[root, c]);
+}
+
+#[test]
+fn test_reparent_rejects_cycles() {
+    let (mut scene, [root, a, b, _]) = tree();
+    assert_eq!(scene.reparent(a, Some(b)), Err(SceneError::Cycle { node: a, parent: b }));
+    assert_eq!(scene.reparent(a, Some(a)), Err(SceneError::Cycle { node: a, parent: a }));
+    assert_eq!(scene.get(b).unwrap().parent(), Some(a));
+    assert_eq!(scene.get(a).unwrap().parent(), Some(root));
+}
+
+#[test]
+fn test_detach_attach_and_orphans() {
+    let (mut scene, [root, a, b, c]) = tree();
+    scene.detach(a).unwrap();
+    assert!(scene.is_orphan(a) && !scene.is_orphan(b));
+    assert_eq!(scene.query(NodeFlags::VISIBLE, LayerMask::ALL), vec![root, c]);
+    assert_eq!(scene.attach(c, None), Err(SceneError::AlreadyAttached(c)));
+
+    scene.attach(a, Some(c)).unwrap();
+    assert!(!scene.is_orphan(a));
+    assert_eq!(scene.query(NodeFlags::VISIBLE, LayerMask::ALL), vec![root, c, a, b]);
+
+    scene.detach(a).unwrap();
+    scene.detach(root).unwrap();
+    assert!(scene.roots().is_empty());
+    assert_eq!(scene.remove_orphans(), 4);
+    assert!(scene.is_empty());
+}
@@ -0,0 +1,69 @@
+use super::*;
+use crate::geometry::Aabb;
+use crate::scene::NodeFlags;
+
+/// World boxes of the touchable nodes of a scene.
+fn touchable_boxes(scene: &Scene) -> Vec<Aabb> {
+    scene
+        .touchable_nodes(crate::scene::LayerMask::ALL)
+        .into_iter()
+        .map(|id| Aabb::from_center_half_extents(scene.world_position(id).unwrap(), scene.get(id).unwrap().size * 0.5))
+        .collect()
+}
+
+#[test]
+fn test_gallery_scenes_are_well_formed() {
+    let gallery = gallery();
+    assert_eq!(gallery.len(), 6);
+    for test in &gallery {
+        assert!(by_name(test.name).is_some(), "{}", test.name);
+        assert!(test.path.len() >= 3, "{}", test.name);
+        let boxes = touchable_boxes(&test.scene);
+        assert!(!boxes.is_empty(), "{}", test.name);
+        // Everything fits a 16 cm desktop workspace
+        let bounds = boxes.iter().fold(Aabb::EMPTY, |b, x| b.union(*x));
+        assert!(bounds.size().max_component() <= 0.16, "{}: {:?}", test.name, bounds);
+        // The path starts and ends clear of every surface
+        for p in [test.path[0], *test.path.last().unwrap()] {
+            assert!(boxes.iter().all(|b| !b.contains(p)), "{}", test.name);
+        }
+    }
+    assert!(by_name("trampoline").is_none());
+}
+
+#[test]
+fn test_scene_parameters() {
+    let wall = stiff_wall(NewtonsPerMeter(3000.0));
+    let node = wall.scene.get(wall.scene.find("wall").unwrap()).unwrap();
+    assert_eq!(node.haptics.unwrap().stiffness, NewtonsPerMeter(3000.0));
+    assert!(node.flags.contains(NodeFlags::TOUCHABLE));
+
+    let buttons = button_wall(2, 5);
+    assert!(buttons.scene.find("button_1_4").is_some());
+    assert_eq!(buttons.path.len(), 2 * 5 * 3);
+
+    // The probe fits through the hole only with clearance
+    let peg = peg_in_hole(0.001);
+    let boxes = touchable_boxes(&peg.scene);
+    let probe = Aabb::from_center_half_extents(Vec3::new(0.0, 0.0, -0.01), Vec3::new(0.005, 0.005, 0.005));
+    assert!(boxes.iter().all(|b| !b.intersects(&probe)));
+    let tight = peg_in_hole(0.0);
+    let fat = probe.expand(0.0005);
+    assert!(touchable_boxes(&tight.scene).iter().any(|b| b.intersects(&fat)));
+
+    // Crevice gap between the two floor halves
+    let corner = corner_crevice(0.004);
+    let left = corner.scene.find("floor_left").unwrap();
+    let right = corner.scene.find("floor_right").unwrap();
+    let edge = |id, sign: f32| corner.scene.world_position(id).unwrap().x + sign * corner.scene.get(id).unwrap().size.x * 0.5;
+    assert!((edge(right, -1.0) - edge(left, 1.0) - 0.004).abs() < 1e-6);
+}
+
+#[test]
+fn test_path_point_by_arc_length() {
+    let wall = stiff_wall(NewtonsPerMeter(1000.0));
+    assert_eq!(wall.path_point(0.0), wall.path[0]);
+    assert!((wall.path_point(0.5) - Vec3::new(0.0, 0.0, -0.005)).length() < 1e-6);
+    assert!((wall.path_point(0.25) - Vec3::new(0.0, 0.0, 0.0125)).length() < 1e-6);
+    assert!((wall.path_point(2.0) - wall.path[2]).length() < 1e-6);
+}
@@ -0,0 +1,117 @@
+use super::*;
+use crate::core::{NewtonsPerMeter, Quat, Vec3};
+use crate::scene::{test_scenes, NodeBuilder};
+
+fn panel() -> NodeBuilder {
+    NodeBuilder::panel().name("panel").size(0.2, 0.1, 0.01)
+}
+
+fn kinds(report: &ValidationReport) -> Vec<&'static str> {
+    report
+        .diagnostics
+        .iter()
+        .map(|d| match d {
+            Diagnostic::Unreachable { .. } => "unreachable",
+            Diagnostic::ZeroSize { .. } => "zero-size",
+            Diagnostic::OutsideWorkspace { .. } => "outside",
+            Diagnostic::Overlap { .. } => "overlap",
+            Diagnostic::MissingMaterial { .. } => "material",
+            Diagnostic::StiffnessExceedsDevice { .. } => "stiffness",
+        })
+        .collect()
+}
+
+#[test]
+fn test_clean_scene() {
+    let scene = panel()
+        .child(NodeBuilder::button("A").name("a").at(-0.05, 0.0, 0.01).size(0.04, 0.03, 0.01))
+        .child(NodeBuilder::button("B").name("b").at(0.05, 0.0, 0.01).size(0.04, 0.03, 0.01))
+        .child(NodeBuilder::label("caption").at(0.0, 0.04, 0.0))
+        .into_scene();
+    let report = validate(&scene);
+    assert!(report.diagnostics.is_empty(), "{}", report);
+    assert!(report.into_result().is_ok());
+}
+
+#[test]
+fn test_gallery_scenes_are_clean() {
+    for scene in test_scenes::gallery() {
+        // The stiff wall deliberately exceeds desktop devices, so no device limits here
+        let report = validate(&scene.scene);
+        assert!(report.diagnostics.is_empty(), "{}: {}", scene.name, report);
+    }
+}
+
+#[test]
+fn test_unreachable_click_handler() {
+    let scene = panel()
+        .pickable(false)
+        .flags(NodeFlags::VISIBLE)
+        .child(NodeBuilder::button("ok").name("ok").size(0.04, 0.03, 0.01).on_click(|_| {}))
+        .into_scene();
+    let report = validate(&scene);
+    assert_eq!(kinds(&report), vec!["unreachable"]);
+    assert_eq!(report.diagnostics[0].node(), scene.find("ok").unwrap());
+    assert!(report.to_string().contains("'ok' has a click handler"));
+    assert!(report.clone().into_result().is_err());
+}
+
+#[test]
+fn test_missing_material_and_zero_size() {
+    let scene = panel()
+        .child(NodeBuilder::button("flat").name("flat").at(-0.05, 0.0, 0.01).size(0.04, 0.03, 0.0))
+        .child(NodeBuilder::button("ghost").name("ghost").at(0.05, 0.0, 0.01).size(0.04, 0.03, 0.01).intangible())
+        .into_scene();
+    let report = validate(&scene);
+    assert_eq!(kinds(&report), vec!["zero-size", "material"]);
+    assert_eq!(report.errors().count(), 1);
+    assert_eq!(report.warnings().count(), 1);
+    // Untouchable on purpose: no warning
+    let scene = NodeBuilder::button("ghost").size(0.04, 0.03, 0.01).ghost().into_scene();
+    assert!(validate(&scene).diagnostics.is_empty());
+}
+
+#[test]
+fn test_overlapping_siblings() {
+    let scene = panel()
+        .child(NodeBuilder::button("A").name("a").at(-0.01, 0.0, 0.01).size(0.04, 0.03, 0.01))
+        .child(NodeBuilder::button("B").name("b").at(0.02, 0.0, 0.01).size(0.04, 0.03, 0.01))
+        .child(NodeBuilder::button("C").name("c").at(0.06, 0.0, 0.01).size(0.04, 0.03, 0.01))
+        .into_scene();
+    let report = validate(&scene);
+    assert_eq!(kinds(&report), vec!["overlap"]);
+    let Diagnostic::Overlap { a_name, b_name, depth, .. } = &report.diagnostics[0] else { unreachable!() };
+    assert_eq!((a_name.as_str(), b_name.as_str()), ("a", "b"));
+    assert!((depth - 0.01).abs() < 1e-4);
+    assert!(report.is_ok());
+}
+
+#[test]
+fn test_device_limits() {
+    let scene = panel()
+        .child(
+            NodeBuilder::button("hard").name("hard").at(0.0, 0.0, 0.01).size(0.04, 0.03, 0.01).stiffness(NewtonsPerMeter(2500.0)),
+        )
+        .into_scene();
+    assert!(validate(&scene).diagnostics.is_empty());
+
+    let caps = crate::device::DeviceCapabilities::KINESTHETIC;
+    let report = SceneValidator::for_device(caps).validate(&scene);
+    assert_eq!(kinds(&report), vec!["stiffness"]);
+    assert!(report.to_string().contains("2500 N/m"));
+    // Vibration-only devices render no stiffness at all
+    assert!(SceneValidator::for_device(crate::device::DeviceCapabilities::GAMEPAD).validate(&scene).is_ok());
+}
+
+#[test]
+fn test_workspace() {
+    let scene = panel()
+        .child(NodeBuilder::button("near").name("near").at(0.0, 0.0, 0.01).size(0.04, 0.03, 0.01))
+        .child(NodeBuilder::button("far").name("far").at(0.5, 0.0, 0.01).size(0.04, 0.03, 0.01))
+        .child(NodeBuilder::label("far label").at(0.5, 0.1, 0.0))
+        .into_scene();
+    let workspace = Obb::new(Vec3::zero(), Vec3::new(0.12, 0.12, 0.12), Quat::identity());
+    let report = SceneValidator::new().with_workspace(workspace).validate(&scene);
+    assert_eq!(kinds(&report), vec!["outside"]);
+    assert_eq!(report.diagnostics[0].node(), scene.find("far").unwrap());
+}
@@ -0,0 +1,270 @@
+//! Static checks that catch scene mistakes before anyone touches the scene.
+//!
+//! The checks need nothing but the scene and, optionally, the target device and
+//! its workspace, so they can run in unit tests or CI against every shipped scene.
+//! Each finding names the node and says what to change.
+
+use std::fmt;
+
+use super::flags::NodeFlags;
+use super::graph::{NodeId, NodeKind, Scene};
+use crate::device::DeviceCapabilities;
+use crate::geometry::{obb_overlap, Aabb, Obb};
+
+/// Overlaps thinner than this are treated as widgets merely touching.
+const OVERLAP_TOLERANCE: f32 = 1e-5;
+
+// ============================================================================
+// Diagnostics
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// One problem found in a scene.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Diagnostic {
+    /// Has a click handler but its own or inherited flags make it neither pickable nor touchable.
+    Unreachable { node: NodeId, name: String },
+    /// Touchable but with no extent along some axis, so there is nothing to feel.
+    ZeroSize { node: NodeId, name: String },
+    /// Touchable or clickable but entirely outside the device workspace.
+    OutsideWorkspace { node: NodeId, name: String },
+    /// Two touchable widgets, neither an ancestor of the other, share volume.
+    Overlap { a: NodeId, a_name: String, b: NodeId, b_name: String, depth: f32 },
+    /// Panel, button or clickable node with the touchable flag but no surface properties.
+    MissingMaterial { node: NodeId, name: String },
+    /// Surface stiffness above what the device renders stably.
+    StiffnessExceedsDevice { node: NodeId, name: String, stiffness: f32, max: f32 },
+}
+
+impl Diagnostic {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Diagnostic::Overlap { .. } | Diagnostic::MissingMaterial { .. } => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+
+    /// The node the diagnostic is about (the first of an overlapping pair).
+    pub fn node(&self) -> NodeId {
+        match self {
+            Diagnostic::Unreachable { node, .. }
+            | Diagnostic::ZeroSize { node, .. }
+            | Diagnostic::OutsideWorkspace { node, .. }
+            | Diagnostic::MissingMaterial { node, .. }
+            | Diagnostic::StiffnessExceedsDevice { node, .. } => *node,
+            Diagnostic::Overlap { a, .. } => *a,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.severity() {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{}: ", level)?;
+        match self {
+            Diagnostic::Unreachable { name, .. } => write!(
+                f,
+                "'{}' has a click handler but cannot be picked or touched; \
+                 enable PICKABLE or TOUCHABLE on it and its ancestors",
+                name
+            ),
+            Diagnostic::ZeroSize { name, .. } => {
+                write!(f, "'{}' is touchable but has zero size; give it a nonzero width, height and depth", name)
+            }
+            Diagnostic::OutsideWorkspace { name, .. } => {
+                write!(f, "'{}' lies outside the device workspace; move it or scale the workspace mapping", name)
+            }
+            Diagnostic::Overlap { a_name, b_name, depth, .. } => write!(
+                f,
+                "'{}' and '{}' overlap by {:.1} mm; the user cannot tell which one they touch, so separate them",
+                a_name,
+                b_name,
+                depth * 1000.0
+            ),
+            Diagnostic::MissingMaterial { name, .. } => {
+                write!(f, "'{}' is marked touchable but has no haptic material; add one or make it intangible", name)
+            }
+            Diagnostic::StiffnessExceedsDevice { name, stiffness, max, .. } => write!(
+                f,
+                "'{}' has stiffness {} N/m but the device renders at most {} N/m; lower it to avoid instability",
+                name, stiffness, max
+            ),
+        }
+    }
+}
+
+/// All diagnostics of one validation run, errors first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidationReport {
+    /// True when there are no errors (warnings are allowed).
+    pub fn is_ok(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter().filter(|d| d.severity() == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter().filter(|d| d.severity() == Severity::Warning)
+    }
+
+    /// Ok if there are no errors, so tests and build checks can use `?`.
+    pub fn into_result(self) -> Result<Self, Self> {
+        if self.is_ok() {
+            Ok(self)
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for d in &self.diagnostics {
+            writeln!(f, "{}", d)?;
+        }
+        let errors = self.errors().count();
+        write!(f, "{} error(s), {} warning(s)", errors, self.diagnostics.len() - errors)
+    }
+}
+
+impl std::error::Error for ValidationReport {}
+
+// ============================================================================
+// Validator
+// ============================================================================
+
+/// Configurable validation pass.
+#[derive(Debug, Clone, Default)]
+pub struct SceneValidator {
+    capabilities: Option<DeviceCapabilities>,
+    workspace: Option<Obb>,
+}
+
+impl SceneValidator {
+    /// Checks that need no device information.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also checks surface stiffness against the device limits.
+    pub fn for_device(capabilities: DeviceCapabilities) -> Self {
+        Self { capabilities: Some(capabilities), workspace: None }
+    }
+
+    /// Also checks that interactive widgets lie within `workspace` (scene space).
+    pub fn with_workspace(mut self, workspace: Obb) -> Self {
+        self.workspace = Some(workspace);
+        self
+    }
+
+    pub fn validate(&self, scene: &Scene) -> ValidationReport {
+        let mut diagnostics = Vec::new();
+        let mut volumes: Vec<(NodeId, Aabb)> = Vec::new();
+
+        for (id, node) in scene.iter() {
+            let name = display_name(scene, id);
+            let touchable = scene.is_effectively(id, NodeFlags::TOUCHABLE);
+            let pickable = scene.is_effectively(id, NodeFlags::PICKABLE);
+            let bounds = scene.world_position(id).map(|c| Aabb::from_center_half_extents(c, node.size.abs() * 0.5));
+            let needs_material = matches!(node.kind, NodeKind::Panel | NodeKind::Button { .. }) || node.is_clickable();
+
+            if node.is_clickable() && !pickable && !(touchable && node.haptics.is_some()) {
+                diagnostics.push(Diagnostic::Unreachable { node: id, name: name.clone() });
+            }
+            if touchable && node.haptics.is_none() && needs_material {
+                diagnostics.push(Diagnostic::MissingMaterial { node: id, name: name.clone() });
+            }
+            let felt = touchable && node.haptics.is_some();
+            if felt && (node.size.x == 0.0 || node.size.y == 0.0 || node.size.z == 0.0) {
+                diagnostics.push(Diagnostic::ZeroSize { node: id, name: name.clone() });
+            } else if felt {
+                volumes.extend(bounds.map(|b| (id, b)));
+            }
+            if let (Some(workspace), Some(bounds)) = (&self.workspace, bounds) {
+                let interactive = felt || (pickable && node.is_clickable());
+                // Padding keeps flat widgets (zero depth) from counting as outside
+                let inflated = Obb::from_aabb(&bounds.expand(OVERLAP_TOLERANCE));
+                if interactive && obb_overlap(&inflated, workspace).is_none() {
+                    diagnostics.push(Diagnostic::OutsideWorkspace { node: id, name: name.clone() });
+                }
+            }
+            if let (Some(caps), Some(haptics)) = (&self.capabilities, &node.haptics) {
+                if caps.renders_force() && felt && haptics.stiffness.value() > caps.max_stiffness.value() {
+                    diagnostics.push(Diagnostic::StiffnessExceedsDevice {
+                        node: id,
+                        name,
+                        stiffness: haptics.stiffness.value(),
+                        max: caps.max_stiffness.value(),
+                    });
+                }
+            }
+        }
+
+        // Offline check, so all pairs; volumes are sorted by min X to prune early.
+        volumes.sort_by(|(_, a), (_, b)| a.min.x.total_cmp(&b.min.x));
+        for (i, (a, box_a)) in volumes.iter().enumerate() {
+            for (b, box_b) in &volumes[i + 1..] {
+                if box_b.min.x >= box_a.max.x {
+                    break;
+                }
+                let depth = [0, 1, 2]
+                    .map(|axis| box_a.max[axis].min(box_b.max[axis]) - box_a.min[axis].max(box_b.min[axis]))
+                    .into_iter()
+                    .fold(f32::INFINITY, f32::min);
+                if depth > OVERLAP_TOLERANCE && !scene.is_ancestor(*a, *b) && !scene.is_ancestor(*b, *a) {
+                    let (a, b) = if a < b { (*a, *b) } else { (*b, *a) };
+                    diagnostics.push(Diagnostic::Overlap {
+                        a,
+                        a_name: display_name(scene, a),
+                        b,
+                        b_name: display_name(scene, b),
+                        depth,
+                    });
+                }
+            }
+        }
+
+        diagnostics.sort_by(|x, y| y.severity().cmp(&x.severity()).then(x.node().cmp(&y.node())));
+        ValidationReport { diagnostics }
+    }
+}
+
+/// Validates with no device information.
+pub fn validate(scene: &Scene) -> ValidationReport {
+    SceneValidator::new().validate(scene)
+}
+
+/// Node name, or its kind and index when unnamed.
+fn display_name(scene: &Scene, id: NodeId) -> String {
+    match scene.get(id) {
+        Some(node) if !node.name.is_empty() => node.name.clone(),
+        Some(node) => {
+            let kind = match node.kind {
+                NodeKind::Group => "group",
+                NodeKind::Panel => "panel",
+                NodeKind::Button { .. } => "button",
+                NodeKind::Label { .. } => "label",
+            };
+            format!("{} #{}", kind, id.index())
+        }
+        None => format!("#{}", id.index()),
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/validate_tests.rs"]
+mod tests;
@@ -0,0 +1,290 @@
+//! Embedded rhai scripts for interaction logic.
+//!
+//! Designers write event handlers in rhai and reload them without recompiling:
+//!
+//! ```text
+//! let presses = 0;
+//! fn on_click(node) {
+//!     presses += 1;
+//!     if node == "ok" { play_effect("confirm"); }
+//!     set_stiffness(node, 400.0 + presses * 50.0);
+//! }
+//! fn on_contact_begin(node, force) { set_effect_param("buzz", "gain", force / 3.0); }
+//! ```
+//!
+//! Handlers do not touch the scene or effects directly: the functions they call
+//! queue [`ScriptCommand`]s, which the UI thread applies with [`ScriptHost::apply`]
+//! between frames. The host is deliberately not `Send`, so it cannot be moved onto
+//! the servo thread, and every run is bounded by an operation limit so a runaway
+//! script stalls one frame at worst rather than hanging the UI.
+
+use crate::core::{NewtonSecondsPerMeter, NewtonsPerMeter};
+use crate::effects::{HapticSignal, ParameterError};
+use crate::scene::{NodeHaptics, Scene};
+use rhai::{Engine, ImmutableString, Scope, AST};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+/// Operations one load or handler call may execute before it is aborted.
+pub const DEFAULT_MAX_OPERATIONS: u64 = 100_000;
+
+// ============================================================================
+// Events and Commands
+// ============================================================================
+
+/// A UI or haptic event forwarded to scripts. Nodes are identified by name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptEvent {
+    /// Calls `on_click(node)`.
+    Click { node: String },
+    /// Calls `on_contact_begin(node, force)` with the contact force in newtons.
+    ContactBegin { node: String, force: f32 },
+    /// Calls `on_contact_end(node)`.
+    ContactEnd { node: String },
+    /// Calls `on_value_changed(node, value)` for sliders, knobs and toggles.
+    ValueChanged { node: String, value: f32 },
+    /// Calls `on_event(name, value)` for application-defined events.
+    Custom { name: String, value: f32 },
+}
+
+impl ScriptEvent {
+    /// Name of the script function handling this event.
+    pub fn handler(&self) -> &'static str {
+        match self {
+            ScriptEvent::Click { .. } => "on_click",
+            ScriptEvent::ContactBegin { .. } => "on_contact_begin",
+            ScriptEvent::ContactEnd { .. } => "on_contact_end",
+            ScriptEvent::ValueChanged { .. } => "on_value_changed",
+            ScriptEvent::Custom { .. } => "on_event",
+        }
+    }
+}
+
+/// A change requested by a script, applied outside the script run.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptCommand {
+    /// `set_stiffness(node, n_per_m)`
+    SetStiffness { node: String, stiffness: NewtonsPerMeter },
+    /// `set_damping(node, ns_per_m)`
+    SetDamping { node: String, damping: NewtonSecondsPerMeter },
+    /// `set_friction(node, coefficient)`
+    SetFriction { node: String, friction: f32 },
+    /// `set_effect_param(effect, parameter, value)`
+    SetEffectParameter { effect: String, parameter: String, value: f32 },
+    /// `play_effect(effect)`; left to the application, which owns the players.
+    PlayEffect { effect: String },
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Errors from loading, running or applying scripts.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptError {
+    /// The script does not parse.
+    Compile(String),
+    /// The script failed or exceeded its operation limit.
+    Runtime(String),
+    /// A command named a node that is not in the scene.
+    UnknownNode(String),
+    /// A command named an effect that is not registered.
+    UnknownEffect(String),
+    /// A node without haptic surface properties cannot take material commands.
+    Intangible(String),
+    Parameter(ParameterError),
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScriptError::Compile(message) => write!(f, "script does not compile: {}", message),
+            ScriptError::Runtime(message) => write!(f, "script error: {}", message),
+            ScriptError::UnknownNode(name) => write!(f, "no node named '{}'", name),
+            ScriptError::UnknownEffect(name) => write!(f, "no effect named '{}'", name),
+            ScriptError::Intangible(name) => write!(f, "node '{}' has no haptic material", name),
+            ScriptError::Parameter(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+impl From<ParameterError> for ScriptError {
+    fn from(e: ParameterError) -> Self {
+        ScriptError::Parameter(e)
+    }
+}
+
+// ============================================================================
+// Host
+// ============================================================================
+
+type Queue = Rc<RefCell<Vec<ScriptCommand>>>;
+
+/// Runs one script's event handlers on the UI thread.
+pub struct ScriptHost {
+    engine: Engine,
+    ast: AST,
+    scope: Scope<'static>,
+    queue: Queue,
+    /// Keeps the host on the thread that created it.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl ScriptHost {
+    pub fn new() -> Self {
+        let queue = Queue::default();
+        let mut engine = Engine::new();
+        engine.set_max_operations(DEFAULT_MAX_OPERATIONS);
+        register_commands(&mut engine, &queue);
+        Self { engine, ast: AST::empty(), scope: Scope::new(), queue, _not_send: PhantomData }
+    }
+
+    /// Changes the per-run operation limit (0 disables it).
+    pub fn set_max_operations(&mut self, operations: u64) {
+        self.engine.set_max_operations(operations);
+    }
+
+    /// Compiles a script and runs its top-level statements, replacing the previous
+    /// script and its variables. On error the previous script stays loaded.
+    pub fn load(&mut self, source: &str) -> Result<(), ScriptError> {
+        let ast = self.engine.compile(source).map_err(|e| ScriptError::Compile(e.to_string()))?;
+        let mut scope = Scope::new();
+        self.engine.run_ast_with_scope(&mut scope, &ast).map_err(|e| ScriptError::Runtime(e.to_string()))?;
+        self.ast = ast;
+        self.scope = scope;
+        Ok(())
+    }
+
+    /// True if the loaded script defines a handler for the event.
+    pub fn handles(&self, event: &ScriptEvent) -> bool {
+        let arity = match event {
+            ScriptEvent::Click { .. } | ScriptEvent::ContactEnd { .. } => 1,
+            _ => 2,
+        };
+        self.ast.iter_functions().any(|f| f.name == event.handler() && f.params.len() == arity)
+    }
+
+    /// Calls the event's handler. Returns false if the script does not define one.
+    pub fn handle(&mut self, event: &ScriptEvent) -> Result<bool, ScriptError> {
+        if !self.handles(event) {
+            return Ok(false);
+        }
+        let name = event.handler();
+        let (engine, scope, ast) = (&self.engine, &mut self.scope, &self.ast);
+        let result = match event {
+            ScriptEvent::Click { node } | ScriptEvent::ContactEnd { node } => {
+                engine.call_fn::<rhai::Dynamic>(scope, ast, name, (node.clone(),))
+            }
+            ScriptEvent::ContactBegin { node, force: value }
+            | ScriptEvent::ValueChanged { node, value }
+            | ScriptEvent::Custom { name: node, value } => {
+                engine.call_fn::<rhai::Dynamic>(scope, ast, name, (node.clone(), f64::from(*value)))
+            }
+        };
+        result.map(|_| true).map_err(|e| ScriptError::Runtime(e.to_string()))
+    }
+
+    /// Commands queued since the last call.
+    pub fn take_commands(&mut self) -> Vec<ScriptCommand> {
+        std::mem::take(&mut *self.queue.borrow_mut())
+    }
+
+    /// Applies queued commands to the scene and named effects. `PlayEffect` commands
+    /// are returned for the application; commands that fail are skipped and reported.
+    pub fn apply(
+        &mut self,
+        scene: &mut Scene,
+        effects: &mut HashMap<String, Box<dyn HapticSignal>>,
+    ) -> (Vec<ScriptCommand>, Vec<ScriptError>) {
+        let mut unhandled = Vec::new();
+        let mut errors = Vec::new();
+        for command in self.take_commands() {
+            let result = match command {
+                ScriptCommand::SetStiffness { ref node, stiffness } => {
+                    with_haptics(scene, node, |h| h.stiffness = stiffness)
+                }
+                ScriptCommand::SetDamping { ref node, damping } => with_haptics(scene, node, |h| h.damping = damping),
+                ScriptCommand::SetFriction { ref node, friction } => with_haptics(scene, node, |h| h.friction = friction),
+                ScriptCommand::SetEffectParameter { ref effect, ref parameter, value } => match effects.get_mut(effect) {
+                    Some(signal) => signal.set_parameter(parameter, value).map_err(ScriptError::from),
+                    None => Err(ScriptError::UnknownEffect(effect.clone())),
+                },
+                ScriptCommand::PlayEffect { .. } => {
+                    unhandled.push(command);
+                    Ok(())
+                }
+            };
+            if let Err(e) = result {
+                errors.push(e);
+            }
+        }
+        (unhandled, errors)
+    }
+}
+
+impl Default for ScriptHost {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn with_haptics(
+    scene: &mut Scene,
+    name: &str,
+    change: impl FnOnce(&mut NodeHaptics),
+) -> Result<(), ScriptError> {
+    let id = scene.find(name).ok_or_else(|| ScriptError::UnknownNode(name.to_string()))?;
+    let node = scene.get_mut(id).ok_or_else(|| ScriptError::UnknownNode(name.to_string()))?;
+    let haptics = node.haptics.as_mut().ok_or_else(|| ScriptError::Intangible(name.to_string()))?;
+    change(haptics);
+    Ok(())
+}
+
+/// Registers the command functions, with integer overloads so `set_stiffness(n, 800)` works.
+fn register_commands(engine: &mut Engine, queue: &Queue) {
+    fn push(queue: &Queue, command: ScriptCommand) {
+        queue.borrow_mut().push(command);
+    }
+
+    let q = queue.clone();
+    let set_stiffness = move |node: ImmutableString, value: f64| {
+        push(&q, ScriptCommand::SetStiffness { node: node.into(), stiffness: NewtonsPerMeter(value as f32) })
+    };
+    let q = queue.clone();
+    let set_damping = move |node: ImmutableString, value: f64| {
+        push(&q, ScriptCommand::SetDamping { node: node.into(), damping: NewtonSecondsPerMeter(value as f32) })
+    };
+    let q = queue.clone();
+    let set_friction = move |node: ImmutableString, value: f64| {
+        push(&q, ScriptCommand::SetFriction { node: node.into(), friction: value as f32 })
+    };
+    let q = queue.clone();
+    let set_effect_param = move |effect: ImmutableString, parameter: ImmutableString, value: f64| {
+        push(&q, ScriptCommand::SetEffectParameter { effect: effect.into(), parameter: parameter.into(), value: value as f32 })
+    };
+    let q = queue.clone();
+    engine.register_fn("play_effect", move |effect: ImmutableString| {
+        push(&q, ScriptCommand::PlayEffect { effect: effect.into() })
+    });
+
+    macro_rules! register_numeric {
+        ($name:literal, $f:ident, ($($arg:ident: $ty:ty),*)) => {{
+            let f = $f.clone();
+            engine.register_fn($name, move |$($arg: $ty,)* value: i64| f($($arg,)* value as f64));
+            engine.register_fn($name, $f);
+        }};
+    }
+    register_numeric!("set_stiffness", set_stiffness, (node: ImmutableString));
+    register_numeric!("set_damping", set_damping, (node: ImmutableString));
+    register_numeric!("set_friction", set_friction, (node: ImmutableString));
+    register_numeric!("set_effect_param", set_effect_param, (effect: ImmutableString, parameter: ImmutableString));
+}
+
+#[cfg(test)]
+#[path = "tests/host_tests.rs"]
+mod tests;
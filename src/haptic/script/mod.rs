@@ -0,0 +1,3 @@
+// src/haptic/script/mod.rs
+pub mod host;
+pub use host::{ScriptCommand, ScriptError, ScriptEvent, ScriptHost};
@@ -0,0 +1,89 @@
+use super::*;
+use crate::effects::{Keyframe, KeyframeEffect};
+use crate::scene::NodeBuilder;
+
+const SCRIPT: &str = r#"
+let presses = 0;
+fn on_click(node) {
+    presses += 1;
+    if node == "ok" { play_effect("confirm"); }
+    set_stiffness(node, 400 + presses * 50);
+}
+fn on_contact_begin(node, force) {
+    set_effect_param("buzz", "gain", force / 2.0);
+    set_friction(node, 0.1);
+}
+"#;
+
+fn scene() -> Scene {
+    NodeBuilder::panel()
+        .name("panel")
+        .child(NodeBuilder::button("OK").name("ok"))
+        .child(NodeBuilder::label("Title").name("title"))
+        .into_scene()
+}
+
+fn effects() -> HashMap<String, Box<dyn HapticSignal>> {
+    let key = |time| Keyframe { time, intensity: 1.0, sharpness: 0.5 };
+    let buzz: Box<dyn HapticSignal> = Box::new(KeyframeEffect::new(vec![key(0.0), key(0.1)], None));
+    HashMap::from([("buzz".to_string(), buzz)])
+}
+
+#[test]
+fn test_handlers_queue_commands_and_keep_state() {
+    let mut host = ScriptHost::new();
+    host.load(SCRIPT).unwrap();
+    assert!(host.handles(&ScriptEvent::Click { node: "ok".into() }));
+    assert!(!host.handle(&ScriptEvent::ContactEnd { node: "ok".into() }).unwrap());
+
+    host.handle(&ScriptEvent::Click { node: "ok".into() }).unwrap();
+    host.handle(&ScriptEvent::Click { node: "panel".into() }).unwrap();
+    assert_eq!(
+        host.take_commands(),
+        vec![
+            ScriptCommand::PlayEffect { effect: "confirm".into() },
+            ScriptCommand::SetStiffness { node: "ok".into(), stiffness: NewtonsPerMeter(450.0) },
+            ScriptCommand::SetStiffness { node: "panel".into(), stiffness: NewtonsPerMeter(500.0) },
+        ]
+    );
+    assert!(host.take_commands().is_empty());
+}
+
+#[test]
+fn test_apply_updates_scene_and_effects() {
+    let mut scene = scene();
+    let mut effects = effects();
+    let mut host = ScriptHost::new();
+    host.load(SCRIPT).unwrap();
+
+    host.handle(&ScriptEvent::Click { node: "ok".into() }).unwrap();
+    host.handle(&ScriptEvent::ContactBegin { node: "ok".into(), force: 1.5 }).unwrap();
+    host.handle(&ScriptEvent::ContactBegin { node: "title".into(), force: 9.0 }).unwrap();
+    let (unhandled, errors) = host.apply(&mut scene, &mut effects);
+
+    assert_eq!(unhandled, vec![ScriptCommand::PlayEffect { effect: "confirm".into() }]);
+    let haptics = scene.get(scene.find("ok").unwrap()).unwrap().haptics.unwrap();
+    assert_eq!(haptics.stiffness, NewtonsPerMeter(450.0));
+    assert_eq!(haptics.friction, 0.1);
+    let gain = effects["buzz"].parameters().into_iter().find(|(n, _)| n == "gain").unwrap().1;
+    assert_eq!(gain, 0.75);
+
+    // The label is intangible, and a gain of 4.5 is out of range
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(errors[0], ScriptError::Parameter(ParameterError::OutOfRange { .. })));
+    assert_eq!(errors[1], ScriptError::Intangible("title".into()));
+}
+
+#[test]
+fn test_errors_keep_previous_script() {
+    let mut host = ScriptHost::new();
+    host.load(SCRIPT).unwrap();
+    assert!(matches!(host.load("fn on_click(node) {"), Err(ScriptError::Compile(_))));
+    assert!(host.handles(&ScriptEvent::Click { node: "ok".into() }));
+
+    host.load("fn on_event(name, value) { loop { } }").unwrap();
+    host.set_max_operations(1_000);
+    let result = host.handle(&ScriptEvent::Custom { name: "tick".into(), value: 0.0 });
+    assert!(matches!(result, Err(ScriptError::Runtime(_))));
+    assert!(!host.handles(&ScriptEvent::Click { node: "ok".into() }));
+}
@@ -0,0 +1,65 @@
+//! Frustum culling of scene nodes.
+//!
+//! The [`FrustumCuller`] indexes the drawable nodes of a [`Scene`] (everything but
+//! groups) in an [`Octree`] and collects the ones a camera can see. Octree nodes
+//! wholly inside the frustum accept their subtree without per-node tests, so the
+//! cost scales with the frustum's boundary rather than with the scene.
+//!
+//! Nodes are indexed with their boxes cut by their clip regions, so nodes
+//! clipped away entirely are never returned.
+
+use super::octree::Octree;
+use super::raycaster::{build_index, collect, Entry};
+use crate::geometry::Frustum;
+use crate::scene::{LayerMask, NodeFlags, NodeId, NodeKind, Scene};
+
+/// Spatial index of drawable nodes for visibility queries.
+pub struct FrustumCuller {
+    index: Octree<Entry>,
+}
+
+impl FrustumCuller {
+    /// Indexes every node except groups; unsized nodes such as labels are indexed as points.
+    pub fn from_scene(scene: &Scene) -> Self {
+        Self { index: build_index(collect(scene, |node| node.kind != NodeKind::Group).0) }
+    }
+
+    /// Re-indexes the scene after nodes moved, resized or changed flags.
+    pub fn rebuild(&mut self, scene: &Scene) {
+        *self = Self::from_scene(scene);
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Collects the effectively visible nodes on a layer in `mask` that intersect
+    /// the frustum, in scene order. `out` is cleared first so it can be reused
+    /// every frame.
+    pub fn cull_into(&self, frustum: &Frustum, mask: LayerMask, out: &mut Vec<NodeId>) {
+        out.clear();
+        self.index.for_each_in_frustum(frustum, |_, _, entry| {
+            if entry.flags.contains(NodeFlags::VISIBLE) && entry.layers.intersects(mask) {
+                out.push(entry.node);
+            }
+        });
+        out.sort_unstable();
+    }
+
+    /// Allocating form of [`cull_into`](Self::cull_into).
+    pub fn cull(&self, frustum: &Frustum, mask: LayerMask) -> Vec<NodeId> {
+        let mut out = Vec::new();
+        self.cull_into(frustum, mask, &mut out);
+        out
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/culling_tests.rs"]
+mod tests;
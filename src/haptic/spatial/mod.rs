@@ -0,0 +1,9 @@
+// src/haptic/spatial/mod.rs
+pub mod bvh;
+pub mod culling;
+pub mod octree;
+pub mod raycaster;
+pub use bvh::{MeshBvh, TriangleHit};
+pub use culling::FrustumCuller;
+pub use octree::{Octree, OctreeKey, RayHit};
+pub use raycaster::{RaycastFilter, RaycastHit, Raycaster};
@@ -0,0 +1,510 @@
+//! Octree over bounding boxes for widgets and colliders.
+//!
+//! Each item is stored in the deepest node whose bounds fully contain it, so an
+//! item straddling a split plane stays in the parent. Items outside the root
+//! bounds are kept in the root. Queries allocate nothing but the result (and a
+//! small traversal stack), which keeps them usable from the haptic loop.
+
+use crate::core::Vec3;
+use crate::geometry::{Aabb, Containment, Frustum, Ray};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Stable handle to an item. Keys of removed items are never reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct OctreeKey {
+    index: u32,
+    generation: u32,
+}
+
+/// An item hit by a ray, at the distance where the ray enters its bounds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    pub key: OctreeKey,
+    pub distance: f32,
+}
+
+struct Item<T> {
+    bounds: Aabb,
+    value: T,
+    node: u32,
+}
+
+struct Slot<T> {
+    generation: u32,
+    item: Option<Item<T>>,
+}
+
+struct Node {
+    bounds: Aabb,
+    /// Index of the first of eight consecutive children; 0 for a leaf (the root
+    /// is node 0 and never anyone's child).
+    first_child: u32,
+    depth: u32,
+    items: Vec<u32>,
+}
+
+/// Octree mapping bounding boxes to values of type `T`.
+pub struct Octree<T> {
+    nodes: Vec<Node>,
+    slots: Vec<Slot<T>>,
+    free: Vec<u32>,
+    len: usize,
+    /// Items a leaf holds before it splits.
+    pub capacity: usize,
+    pub max_depth: u32,
+}
+
+/// Non-negative f32 distances compare like their bit patterns, which gives the
+/// heaps a total order without a float wrapper.
+#[inline]
+fn order_key(distance: f32) -> u32 {
+    distance.max(0.0).to_bits()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Candidate {
+    // Items sort before nodes at equal distance so results are emitted early
+    Item(u32),
+    Node(u32),
+}
+
+impl<T> Octree<T> {
+    pub const DEFAULT_CAPACITY: usize = 8;
+    pub const DEFAULT_MAX_DEPTH: u32 = 8;
+
+    /// Empty tree covering `bounds`.
+    pub fn new(bounds: Aabb) -> Self {
+        Self::with_limits(bounds, Self::DEFAULT_CAPACITY, Self::DEFAULT_MAX_DEPTH)
+    }
+
+    pub fn with_limits(bounds: Aabb, capacity: usize, max_depth: u32) -> Self {
+        Self {
+            nodes: vec![Node { bounds, first_child: 0, depth: 0, items: Vec::new() }],
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+            capacity: capacity.max(1),
+            max_depth,
+        }
+    }
+
+    #[inline]
+    pub fn bounds(&self) -> Aabb {
+        self.nodes[0].bounds
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of tree nodes, for diagnostics.
+    #[inline]
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    // ============================================================================
+    // Items
+    // ============================================================================
+
+    pub fn insert(&mut self, bounds: Aabb, value: T) -> OctreeKey {
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                self.slots.push(Slot { generation: 0, item: None });
+                (self.slots.len() - 1) as u32
+            }
+        };
+        let slot = &mut self.slots[index as usize];
+        slot.item = Some(Item { bounds, value, node: 0 });
+        let key = OctreeKey { index, generation: slot.generation };
+        self.len += 1;
+        self.place(index, bounds);
+        key
+    }
+
+    pub fn remove(&mut self, key: OctreeKey) -> Option<T> {
+        let slot = self.slots.get_mut(key.index as usize).filter(|s| s.generation == key.generation)?;
+        let item = slot.item.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.unlink(key.index, item.node);
+        self.free.push(key.index);
+        self.len -= 1;
+        Some(item.value)
+    }
+
+    /// Moves an item to new bounds. Returns false for a stale key.
+    pub fn update(&mut self, key: OctreeKey, bounds: Aabb) -> bool {
+        let Some(item) = self.item_mut(key) else {
+            return false;
+        };
+        let current = item.node;
+        item.bounds = bounds;
+        if self.locate(&bounds) != current {
+            self.unlink(key.index, current);
+            self.place(key.index, bounds);
+        }
+        true
+    }
+
+    pub fn get(&self, key: OctreeKey) -> Option<(&Aabb, &T)> {
+        self.item(key).map(|item| (&item.bounds, &item.value))
+    }
+
+    pub fn get_mut(&mut self, key: OctreeKey) -> Option<&mut T> {
+        self.item_mut(key).map(|item| &mut item.value)
+    }
+
+    #[inline]
+    pub fn contains_key(&self, key: OctreeKey) -> bool {
+        self.item(key).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (OctreeKey, &Aabb, &T)> {
+        self.slots.iter().enumerate().filter_map(|(i, slot)| {
+            let item = slot.item.as_ref()?;
+            Some((OctreeKey { index: i as u32, generation: slot.generation }, &item.bounds, &item.value))
+        })
+    }
+
+    pub fn clear(&mut self) {
+        let bounds = self.bounds();
+        self.nodes.truncate(1);
+        self.nodes[0] = Node { bounds, first_child: 0, depth: 0, items: Vec::new() };
+        for (i, slot) in self.slots.iter_mut().enumerate() {
+            if slot.item.take().is_some() {
+                slot.generation = slot.generation.wrapping_add(1);
+                self.free.push(i as u32);
+            }
+        }
+        self.len = 0;
+    }
+
+    fn item(&self, key: OctreeKey) -> Option<&Item<T>> {
+        self.slots.get(key.index as usize).filter(|s| s.generation == key.generation)?.item.as_ref()
+    }
+
+    fn item_mut(&mut self, key: OctreeKey) -> Option<&mut Item<T>> {
+        self.slots.get_mut(key.index as usize).filter(|s| s.generation == key.generation)?.item.as_mut()
+    }
+
+    #[inline]
+    fn key_of(&self, index: u32) -> OctreeKey {
+        OctreeKey { index, generation: self.slots[index as usize].generation }
+    }
+
+    #[inline]
+    fn bounds_of(&self, index: u32) -> Aabb {
+        self.slots[index as usize].item.as_ref().map_or(Aabb::EMPTY, |item| item.bounds)
+    }
+
+    // ============================================================================
+    // Structure
+    // ============================================================================
+
+    /// Deepest existing node whose bounds fully contain `bounds`.
+    fn locate(&self, bounds: &Aabb) -> u32 {
+        let mut node = 0u32;
+        loop {
+            let first = self.nodes[node as usize].first_child;
+            if first == 0 {
+                return node;
+            }
+            match (first..first + 8).find(|&c| contains_box(&self.nodes[c as usize].bounds, bounds)) {
+                Some(child) => node = child,
+                None => return node,
+            }
+        }
+    }
+
+    fn place(&mut self, index: u32, bounds: Aabb) {
+        let node = self.locate(&bounds);
+        self.link(index, node);
+        let n = &self.nodes[node as usize];
+        if n.first_child == 0 && n.items.len() > self.capacity && n.depth < self.max_depth {
+            self.split(node);
+        }
+    }
+
+    fn link(&mut self, index: u32, node: u32) {
+        self.nodes[node as usize].items.push(index);
+        if let Some(item) = self.slots[index as usize].item.as_mut() {
+            item.node = node;
+        }
+    }
+
+    fn unlink(&mut self, index: u32, node: u32) {
+        let items = &mut self.nodes[node as usize].items;
+        if let Some(pos) = items.iter().position(|&i| i == index) {
+            items.swap_remove(pos);
+        }
+    }
+
+    /// Creates eight children and pushes down the items that fit in one.
+    fn split(&mut self, node: u32) {
+        let Node { bounds, depth, .. } = self.nodes[node as usize];
+        let center = bounds.center();
+        let first = self.nodes.len() as u32;
+        for octant in 0..8 {
+            let pick = |bit: usize, axis: usize| if octant & bit != 0 { (center[axis], bounds.max[axis]) } else { (bounds.min[axis], center[axis]) };
+            let (x, y, z) = (pick(1, 0), pick(2, 1), pick(4, 2));
+            let child = Aabb::new(Vec3::new(x.0, y.0, z.0), Vec3::new(x.1, y.1, z.1));
+            self.nodes.push(Node { bounds: child, first_child: 0, depth: depth + 1, items: Vec::new() });
+        }
+        self.nodes[node as usize].first_child = first;
+
+        let items = std::mem::take(&mut self.nodes[node as usize].items);
+        for index in items {
+            let item_bounds = self.bounds_of(index);
+            let target = (first..first + 8).find(|&c| contains_box(&self.nodes[c as usize].bounds, &item_bounds)).unwrap_or(node);
+            self.link(index, target);
+        }
+    }
+
+    // ============================================================================
+    // Queries
+    // ============================================================================
+
+    /// Calls `visit` for every item whose bounds intersect `region`.
+    pub fn for_each_in(&self, region: &Aabb, mut visit: impl FnMut(OctreeKey, &Aabb, &T)) {
+        let mut stack = vec![0u32];
+        while let Some(node) = stack.pop() {
+            let n = &self.nodes[node as usize];
+            for &index in &n.items {
+                if let Some(item) = &self.slots[index as usize].item {
+                    if item.bounds.intersects(region) {
+                        visit(self.key_of(index), &item.bounds, &item.value);
+                    }
+                }
+            }
+            if n.first_child != 0 {
+                stack.extend((n.first_child..n.first_child + 8).filter(|&c| self.nodes[c as usize].bounds.intersects(region)));
+            }
+        }
+    }
+
+    /// Calls `visit` for every item whose bounds intersect `frustum`. Nodes entirely
+    /// inside the frustum have their whole subtree accepted without further tests,
+    /// and nodes entirely outside are skipped with theirs.
+    pub fn for_each_in_frustum(&self, frustum: &Frustum, mut visit: impl FnMut(OctreeKey, &Aabb, &T)) {
+        // The root also holds items outside its bounds, so it is never accepted wholesale
+        let mut stack = vec![(0u32, false)];
+        while let Some((node, inside)) = stack.pop() {
+            let n = &self.nodes[node as usize];
+            for &index in &n.items {
+                if let Some(item) = &self.slots[index as usize].item {
+                    if inside || frustum.intersects(&item.bounds) {
+                        visit(self.key_of(index), &item.bounds, &item.value);
+                    }
+                }
+            }
+            if n.first_child == 0 {
+                continue;
+            }
+            for c in n.first_child..n.first_child + 8 {
+                let child = &self.nodes[c as usize];
+                if child.items.is_empty() && child.first_child == 0 {
+                    continue;
+                }
+                match if inside { Containment::Inside } else { frustum.classify(&child.bounds) } {
+                    Containment::Outside => {}
+                    Containment::Intersects => stack.push((c, false)),
+                    Containment::Inside => stack.push((c, true)),
+                }
+            }
+        }
+    }
+
+    /// Keys of items whose bounds intersect `frustum`.
+    pub fn query_frustum(&self, frustum: &Frustum) -> Vec<OctreeKey> {
+        let mut out = Vec::new();
+        self.for_each_in_frustum(frustum, |key, _, _| out.push(key));
+        out
+    }
+
+    /// Keys of items whose bounds intersect `region`.
+    pub fn query_aabb(&self, region: &Aabb) -> Vec<OctreeKey> {
+        let mut out = Vec::new();
+        self.for_each_in(region, |key, _, _| out.push(key));
+        out
+    }
+
+    /// Keys of items whose bounds come within `radius` of `center`.
+    pub fn query_sphere(&self, center: Vec3, radius: f32) -> Vec<OctreeKey> {
+        let region = Aabb::from_center_half_extents(center, Vec3::splat(radius));
+        let mut out = Vec::new();
+        self.for_each_in(&region, |key, bounds, _| {
+            if bounds.distance_squared(center) <= radius * radius {
+                out.push(key);
+            }
+        });
+        out
+    }
+
+    /// Up to `k` items closest to `p` by bounding-box distance, nearest first.
+    pub fn nearest(&self, p: Vec3, k: usize) -> Vec<(OctreeKey, f32)> {
+        let mut out = Vec::with_capacity(k);
+        if k == 0 {
+            return out;
+        }
+        let mut heap = BinaryHeap::new();
+        heap.push(Reverse((0u32, Candidate::Node(0))));
+        while let Some(Reverse((_, candidate))) = heap.pop() {
+            match candidate {
+                Candidate::Item(index) => {
+                    out.push((self.key_of(index), self.bounds_of(index).distance_squared(p).sqrt()));
+                    if out.len() == k {
+                        break;
+                    }
+                }
+                Candidate::Node(node) => {
+                    let n = &self.nodes[node as usize];
+                    for &index in &n.items {
+                        heap.push(Reverse((order_key(self.bounds_of(index).distance_squared(p)), Candidate::Item(index))));
+                    }
+                    if n.first_child != 0 {
+                        for c in n.first_child..n.first_child + 8 {
+                            let d = self.nodes[c as usize].bounds.distance_squared(p);
+                            heap.push(Reverse((order_key(d), Candidate::Node(c))));
+                        }
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// All items whose bounds the ray enters within `max_distance`, nearest first.
+    pub fn raycast(&self, ray: &Ray, max_distance: f32) -> Vec<RayHit> {
+        let mut hits = Vec::new();
+        self.traverse_ray(ray, max_distance, |key, bounds, _| {
+            if let Some(distance) = bounds.ray_distance(ray, max_distance) {
+                hits.push(RayHit { key, distance });
+            }
+        });
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    /// First item along the ray, where `test` refines the bounding-box hit into an
+    /// exact distance (or None for a miss). Traversal is front to back and stops
+    /// once no unvisited node can be closer than the best hit.
+    pub fn raycast_first_by(
+        &self,
+        ray: &Ray,
+        max_distance: f32,
+        test: impl FnMut(OctreeKey, &Aabb, &T) -> Option<f32>,
+    ) -> Option<RayHit> {
+        self.first_along(ray, 0.0, max_distance, test)
+    }
+
+    /// First item hit by a shape of bounding radius `radius` swept along the ray.
+    /// Candidates are items whose bounds, grown by `radius`, the ray enters; `test`
+    /// computes the exact time of impact (e.g. with [`sweep_sphere`](crate::geometry::sweep_sphere)).
+    pub fn sweep_first_by(
+        &self,
+        ray: &Ray,
+        radius: f32,
+        max_distance: f32,
+        test: impl FnMut(OctreeKey, &Aabb, &T) -> Option<f32>,
+    ) -> Option<RayHit> {
+        self.first_along(ray, radius.max(0.0), max_distance, test)
+    }
+
+    /// Front-to-back traversal shared by ray and sweep queries, with all bounds
+    /// grown by `margin`.
+    fn first_along(
+        &self,
+        ray: &Ray,
+        margin: f32,
+        max_distance: f32,
+        mut test: impl FnMut(OctreeKey, &Aabb, &T) -> Option<f32>,
+    ) -> Option<RayHit> {
+        let mut best: Option<RayHit> = None;
+        let mut heap = BinaryHeap::new();
+        if let Some(t) = self.node_ray_distance(0, ray, margin, max_distance) {
+            heap.push(Reverse((order_key(t), 0u32)));
+        }
+        while let Some(Reverse((t, node))) = heap.pop() {
+            if best.is_some_and(|b| order_key(b.distance) < t) {
+                break;
+            }
+            let n = &self.nodes[node as usize];
+            for &index in &n.items {
+                let Some(item) = &self.slots[index as usize].item else { continue };
+                let limit = best.map_or(max_distance, |b| b.distance);
+                if item.bounds.expand(margin).ray_distance(ray, limit).is_none() {
+                    continue;
+                }
+                let key = self.key_of(index);
+                if let Some(distance) = test(key, &item.bounds, &item.value).filter(|d| *d <= limit) {
+                    best = Some(RayHit { key, distance });
+                }
+            }
+            if n.first_child != 0 {
+                for c in n.first_child..n.first_child + 8 {
+                    if let Some(t) = self.node_ray_distance(c, ray, margin, max_distance) {
+                        heap.push(Reverse((order_key(t), c)));
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    /// First item whose bounding box the ray enters.
+    #[inline]
+    pub fn raycast_first(&self, ray: &Ray, max_distance: f32) -> Option<RayHit> {
+        self.raycast_first_by(ray, max_distance, |_, bounds, _| bounds.ray_distance(ray, max_distance))
+    }
+
+    /// Root bounds do not limit the items it holds, so the root is always entered.
+    fn node_ray_distance(&self, node: u32, ray: &Ray, margin: f32, max_distance: f32) -> Option<f32> {
+        if node == 0 {
+            return Some(0.0);
+        }
+        self.nodes[node as usize].bounds.expand(margin).ray_distance(ray, max_distance)
+    }
+
+    /// Depth-first visit of items in nodes the ray passes through.
+    fn traverse_ray(&self, ray: &Ray, max_distance: f32, mut visit: impl FnMut(OctreeKey, &Aabb, &T)) {
+        let mut stack = vec![0u32];
+        while let Some(node) = stack.pop() {
+            let n = &self.nodes[node as usize];
+            for &index in &n.items {
+                if let Some(item) = &self.slots[index as usize].item {
+                    visit(self.key_of(index), &item.bounds, &item.value);
+                }
+            }
+            if n.first_child != 0 {
+                stack.extend((n.first_child..n.first_child + 8).filter(|&c| self.node_ray_distance(c, ray, 0.0, max_distance).is_some()));
+            }
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Octree<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Octree")
+            .field("bounds", &self.bounds())
+            .field("len", &self.len)
+            .field("nodes", &self.nodes.len())
+            .finish()
+    }
+}
+
+#[inline]
+fn contains_box(outer: &Aabb, inner: &Aabb) -> bool {
+    outer.contains(inner.min) && outer.contains(inner.max)
+}
+
+#[cfg(test)]
+#[path = "tests/octree_tests.rs"]
+mod tests;
@@ -0,0 +1,294 @@
+//! Scene-wide raycasts over an octree of node boxes.
+//!
+//! The [`Raycaster`] snapshots the world-space box, effective flags and layers of
+//! every sized node in a [`Scene`] into an [`Octree`]. Pointer picking and
+//! line-of-sight checks both go through it with a [`RaycastFilter`] selecting which
+//! nodes count. Rebuild after the scene changes; queries never touch the scene.
+//!
+//! Nodes are indexed as cut by their [`ClipRegion`], so rays only hit what is
+//! left of a clipped node; sweeps test the cut box, ignoring clip planes that
+//! are not along an axis.
+
+use super::octree::Octree;
+use crate::core::Vec3;
+use crate::geometry::{sweep_capsule, sweep_sphere, Aabb, Capsule, Ray, SweepHit};
+use crate::scene::{ClipRegion, LayerMask, Node, NodeFlags, NodeId, Scene};
+
+/// Distance kept clear of the target in line-of-sight checks.
+const LINE_OF_SIGHT_EPSILON: f32 = 1e-4;
+
+// ============================================================================
+// Filter and Hits
+// ============================================================================
+
+/// Which nodes a raycast considers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RaycastFilter {
+    /// Flags the node must effectively have (including its ancestors).
+    pub flags: NodeFlags,
+    /// Layers, of which the node must belong to at least one.
+    pub mask: LayerMask,
+    pub max_distance: f32,
+}
+
+impl RaycastFilter {
+    pub fn new(flags: NodeFlags, mask: LayerMask) -> Self {
+        Self { flags, mask, max_distance: f32::INFINITY }
+    }
+
+    /// Pickable nodes on any layer, for pointer picking.
+    pub fn picking() -> Self {
+        Self::new(NodeFlags::PICKABLE, LayerMask::ALL)
+    }
+
+    /// Visible nodes on any layer, for occlusion and line of sight.
+    pub fn occluders() -> Self {
+        Self::new(NodeFlags::VISIBLE, LayerMask::ALL)
+    }
+
+    pub fn with_max_distance(mut self, max_distance: f32) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    #[inline]
+    fn accepts(&self, entry: &Entry) -> bool {
+        entry.flags.contains(self.flags) && entry.layers.intersects(self.mask)
+    }
+}
+
+impl Default for RaycastFilter {
+    fn default() -> Self {
+        Self::new(NodeFlags::NONE, LayerMask::ALL)
+    }
+}
+
+/// A node hit by a ray.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RaycastHit {
+    pub node: NodeId,
+    pub point: Vec3,
+    /// Outward normal of the box face that was hit.
+    pub normal: Vec3,
+    pub distance: f32,
+}
+
+/// Indexed node with its effective flags; shared with the frustum culler.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Entry {
+    pub(super) node: NodeId,
+    pub(super) flags: NodeFlags,
+    pub(super) layers: LayerMask,
+    /// Index of the clip region cutting the node.
+    pub(super) clip: usize,
+}
+
+// ============================================================================
+// Raycaster
+// ============================================================================
+
+/// Spatial index of scene nodes for ray queries.
+pub struct Raycaster {
+    index: Octree<Entry>,
+    clips: Vec<ClipRegion>,
+}
+
+impl Raycaster {
+    /// Indexes every node with a non-zero size.
+    pub fn from_scene(scene: &Scene) -> Self {
+        let (entries, clips) = collect(scene, |node| node.size.max_component() > 0.0);
+        Self { index: build_index(entries), clips }
+    }
+
+    /// Re-indexes the scene after nodes moved, resized or changed flags.
+    pub fn rebuild(&mut self, scene: &Scene) {
+        *self = Self::from_scene(scene);
+    }
+
+    /// Number of indexed nodes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// All accepted nodes along the ray, nearest first.
+    pub fn cast(&self, ray: &Ray, filter: &RaycastFilter) -> Vec<RaycastHit> {
+        let mut hits: Vec<RaycastHit> = self
+            .index
+            .raycast(ray, filter.max_distance)
+            .into_iter()
+            .filter_map(|hit| {
+                let (bounds, entry) = self.index.get(hit.key)?;
+                if !filter.accepts(entry) {
+                    return None;
+                }
+                let distance = self.ray_distance(ray, bounds, entry, filter.max_distance)?;
+                Some(self.make_hit(ray, bounds, entry, distance))
+            })
+            .collect();
+        // Clipping can push a hit back past the next one
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    /// Nearest accepted node along the ray.
+    pub fn cast_first(&self, ray: &Ray, filter: &RaycastFilter) -> Option<RaycastHit> {
+        self.cast_first_where(ray, filter, |_| true)
+    }
+
+    /// Nearest node accepted by both the filter and `keep`.
+    pub fn cast_first_where(
+        &self,
+        ray: &Ray,
+        filter: &RaycastFilter,
+        mut keep: impl FnMut(NodeId) -> bool,
+    ) -> Option<RaycastHit> {
+        let hit = self.index.raycast_first_by(ray, filter.max_distance, |_, bounds, entry| {
+            if filter.accepts(entry) && keep(entry.node) {
+                self.ray_distance(ray, bounds, entry, filter.max_distance)
+            } else {
+                None
+            }
+        })?;
+        let (bounds, entry) = self.index.get(hit.key)?;
+        Some(self.make_hit(ray, bounds, entry, hit.distance))
+    }
+
+    /// Nearest accepted node touched by a sphere of `radius` swept along the ray.
+    /// `point` is the contact point on the node's box and `distance` the travel before contact.
+    pub fn sphere_cast(&self, ray: &Ray, radius: f32, filter: &RaycastFilter) -> Option<RaycastHit> {
+        self.sweep(ray, radius, filter, |bounds, limit| sweep_sphere(ray, radius, limit, |p| bounds.closest_point(p)))
+    }
+
+    /// Nearest accepted node touched by `capsule` swept along the unit vector `direction`.
+    pub fn capsule_cast(&self, capsule: &Capsule, direction: Vec3, filter: &RaycastFilter) -> Option<RaycastHit> {
+        let ray = Ray { origin: capsule.center(), direction };
+        self.sweep(&ray, capsule.bounding_radius(), filter, |bounds, limit| {
+            sweep_capsule(capsule, direction, limit, |p| bounds.closest_point(p))
+        })
+    }
+
+    fn sweep(
+        &self,
+        ray: &Ray,
+        margin: f32,
+        filter: &RaycastFilter,
+        test: impl Fn(&Aabb, f32) -> Option<SweepHit>,
+    ) -> Option<RaycastHit> {
+        let mut contact = None;
+        let hit = self.index.sweep_first_by(ray, margin, filter.max_distance, |key, bounds, entry| {
+            if !filter.accepts(entry) {
+                return None;
+            }
+            let hit = test(bounds, filter.max_distance)?;
+            if contact.is_none_or(|(_, best): (_, SweepHit)| hit.distance <= best.distance) {
+                contact = Some((key, hit));
+            }
+            Some(hit.distance)
+        })?;
+        let (key, contact) = contact.filter(|(key, _)| *key == hit.key)?;
+        let (_, entry) = self.index.get(key)?;
+        Some(RaycastHit { node: entry.node, point: contact.point, normal: contact.normal, distance: contact.distance })
+    }
+
+    /// True if no accepted node other than those in `ignore` lies between the two points.
+    /// Pass the nodes at either end in `ignore` so they do not block themselves.
+    pub fn line_of_sight(&self, from: Vec3, to: Vec3, filter: &RaycastFilter, ignore: &[NodeId]) -> bool {
+        let distance = from.distance_to(to);
+        if distance <= LINE_OF_SIGHT_EPSILON {
+            return true;
+        }
+        let filter = filter.with_max_distance(distance - LINE_OF_SIGHT_EPSILON);
+        self.cast_first_where(&Ray::between(from, to), &filter, |node| !ignore.contains(&node)).is_none()
+    }
+
+    /// Distance along `ray` into the part of `bounds` the entry's clip region
+    /// leaves, up to `max_distance`.
+    fn ray_distance(&self, ray: &Ray, bounds: &Aabb, entry: &Entry, max_distance: f32) -> Option<f32> {
+        let mut interval = bounds.ray_interval(ray)?;
+        if entry.clip != 0 {
+            interval = self.clips[entry.clip].ray_interval(ray, interval)?;
+        }
+        let t = interval.0.max(0.0);
+        (t <= max_distance).then_some(t)
+    }
+
+    /// Hit at `distance`, facing out of the clip plane when it lies on one.
+    fn make_hit(&self, ray: &Ray, bounds: &Aabb, entry: &Entry, distance: f32) -> RaycastHit {
+        let point = ray.at(distance);
+        let normal = match self.clips[entry.clip].plane_at(point) {
+            Some(plane) => -plane.normal,
+            None => face_normal(bounds, point),
+        };
+        RaycastHit { node: entry.node, point, normal, distance }
+    }
+}
+
+/// World boxes, cut by their clip regions, and effective flags of the nodes
+/// accepted by `keep`, in depth-first order, with the clip regions the entries
+/// index. Nodes clipped away entirely are left out.
+pub(super) fn collect(scene: &Scene, keep: impl Fn(&Node) -> bool) -> (Vec<(Aabb, Entry)>, Vec<ClipRegion>) {
+    let mut out = Vec::new();
+    let mut clips = vec![ClipRegion::UNBOUNDED];
+    let mut stack: Vec<(NodeId, Vec3, NodeFlags, usize)> =
+        scene.roots().iter().rev().map(|&id| (id, Vec3::zero(), NodeFlags::ALL, 0)).collect();
+    while let Some((id, origin, inherited, clip)) = stack.pop() {
+        let Some(node) = scene.get(id) else { continue };
+        let center = origin + node.position;
+        let mut flags = inherited & node.flags;
+        if node.haptics.is_none() {
+            flags.set(NodeFlags::TOUCHABLE, false);
+        }
+        if keep(node) {
+            let bounds = Aabb::from_center_half_extents(center, node.size.abs() * 0.5);
+            if let Some(bounds) = clips[clip].cut(&bounds) {
+                out.push((bounds, Entry { node: id, flags, layers: node.layers, clip }));
+            }
+        }
+        // Untouchable only because of missing haptics is not inherited
+        let inherited = inherited & node.flags;
+        let inner = if node.clip.is_empty() {
+            clip
+        } else {
+            clips.push(node.clip.iter().fold(clips[clip].clone(), |region, c| region.with(c, center)));
+            clips.len() - 1
+        };
+        stack.extend(node.children().iter().rev().map(|&child| (child, center, inherited, inner)));
+    }
+    (out, clips)
+}
+
+/// Octree over collected entries, with root bounds fitted to them.
+pub(super) fn build_index(entries: Vec<(Aabb, Entry)>) -> Octree<Entry> {
+    let bounds = entries.iter().fold(Aabb::EMPTY, |b, (bounds, _)| b.union(*bounds));
+    let mut index = Octree::new(if bounds.is_empty() { Aabb::new(Vec3::zero(), Vec3::zero()) } else { bounds });
+    for (bounds, entry) in entries {
+        index.insert(bounds, entry);
+    }
+    index
+}
+
+/// Outward normal of the face of `bounds` nearest to `p`.
+fn face_normal(bounds: &Aabb, p: Vec3) -> Vec3 {
+    let half = bounds.half_extents();
+    let local = p - bounds.center();
+    let mut best = (0, f32::INFINITY);
+    for axis in [0, 1, 2] {
+        let gap = half[axis] - local[axis].abs();
+        if gap < best.1 {
+            best = (axis, gap);
+        }
+    }
+    let mut normal = Vec3::zero();
+    normal[best.0] = if local[best.0] < 0.0 { -1.0 } else { 1.0 };
+    normal
+}
+
+#[cfg(test)]
+#[path = "tests/raycaster_tests.rs"]
+mod tests;
@@ -0,0 +1,122 @@
+use super::*;
+
+/// UV sphere of radius 1 with `rings * segments * 2` triangles (minus the poles).
+fn sphere(rings: u32, segments: u32) -> TriMesh {
+    let mut positions = Vec::new();
+    for r in 0..=rings {
+        let theta = std::f32::consts::PI * r as f32 / rings as f32;
+        for s in 0..segments {
+            let phi = std::f32::consts::TAU * s as f32 / segments as f32;
+            positions.push(Vec3::new(theta.sin() * phi.cos(), theta.cos(), theta.sin() * phi.sin()));
+        }
+    }
+    let mut indices = Vec::new();
+    for r in 0..rings {
+        for s in 0..segments {
+            let a = r * segments + s;
+            let b = r * segments + (s + 1) % segments;
+            let (c, d) = (a + segments, b + segments);
+            indices.extend([a, b, c, b, d, c]);
+        }
+    }
+    TriMesh::new(positions, indices)
+}
+
+#[test]
+fn test_build_structure() {
+    let mesh = sphere(16, 24);
+    let bvh = MeshBvh::build(&mesh);
+    assert_eq!(bvh.triangle_count(), mesh.triangle_count());
+    assert!(bvh.node_count() > 1);
+    assert!((bvh.bounds().min - Vec3::splat(-1.0)).length() < 1e-3);
+    assert!(MeshBvh::build(&TriMesh::default()).raycast(&Ray::new(Vec3::zero(), Vec3::unit_x()), 1.0).is_none());
+}
+
+#[test]
+fn test_raycast_matches_brute_force() {
+    let mesh = sphere(16, 24);
+    let bvh = MeshBvh::build(&mesh);
+    for i in 0..20 {
+        let angle = i as f32 * 0.7;
+        let origin = Vec3::new(angle.cos() * 3.0, (i as f32 * 0.13).sin(), angle.sin() * 3.0);
+        let ray = Ray::between(origin, Vec3::new(0.1, -0.2, 0.05));
+        let expected = mesh.triangles().filter_map(|t| ray_triangle(&ray, t, 10.0)).fold(f32::INFINITY, f32::min);
+        let hit = bvh.raycast(&ray, 10.0).unwrap();
+        assert!((hit.distance - expected).abs() < 1e-5);
+        assert!((hit.point.length() - 1.0).abs() < 0.05);
+        assert_eq!(ray_triangle(&ray, mesh.triangle(hit.triangle as usize), 10.0), Some(hit.distance));
+    }
+    assert!(bvh.raycast(&Ray::new(Vec3::new(0.0, 5.0, 0.0), Vec3::unit_x()), 10.0).is_none());
+    assert!(bvh.raycast(&Ray::new(Vec3::new(3.0, 0.0, 0.0), -Vec3::unit_x()), 1.5).is_none());
+}
+
+#[test]
+fn test_closest_point_and_overlap() {
+    let mesh = sphere(16, 24);
+    let bvh = MeshBvh::build(&mesh);
+    for p in [Vec3::new(2.0, 0.3, -0.4), Vec3::new(0.1, 0.2, 0.05), Vec3::new(-0.5, 1.5, 0.9)] {
+        let hit = bvh.closest_point(p, f32::INFINITY).unwrap();
+        assert!((hit.distance - mesh.distance(p)).abs() < 1e-5);
+        assert!(((hit.point - p).length() - hit.distance).abs() < 1e-5);
+    }
+    assert!(bvh.closest_point(Vec3::new(5.0, 0.0, 0.0), 1.0).is_none());
+
+    let center = Vec3::new(1.0, 0.0, 0.0);
+    let mut found = Vec::new();
+    bvh.overlap_sphere(center, 0.3, |t| found.push(t));
+    found.sort_unstable();
+    let expected: Vec<u32> = (0..mesh.triangle_count() as u32)
+        .filter(|&t| (closest_point_on_triangle(center, mesh.triangle(t as usize)) - center).length() <= 0.3)
+        .collect();
+    assert!(!expected.is_empty());
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn test_sphere_cast_against_mesh() {
+    let bvh = MeshBvh::build(&TriMesh::cuboid(Vec3::new(1.0, 1.0, 0.001)));
+    let down = Ray::new(Vec3::new(0.3, 0.2, 0.5), Vec3::new(0.0, 0.0, -1.0));
+    let hit = bvh.sphere_cast(&down, 0.05, 1.0).unwrap();
+    assert!((hit.distance - 0.449).abs() < 1e-3);
+    assert!((hit.normal - Vec3::unit_z()).length() < 1e-3);
+    assert!((hit.point - Vec3::new(0.3, 0.2, 0.001)).length() < 1e-3);
+    assert!(bvh.sphere_cast(&down, 0.05, 0.4).is_none());
+
+    // A sphere passing just outside the plate's edge clips the rim
+    let past = Ray::new(Vec3::new(1.03, 0.0, 0.5), Vec3::new(0.0, 0.0, -1.0));
+    assert!(bvh.raycast(&past, 1.0).is_none());
+    assert!(bvh.sphere_cast(&past, 0.05, 1.0).is_some());
+
+    let capsule = Capsule::new(Vec3::new(-2.0, 0.0, 0.3), Vec3::new(2.0, 0.0, 0.3), 0.02);
+    let hit = bvh.capsule_cast(&capsule, Vec3::new(0.0, 0.0, -1.0), 1.0).unwrap();
+    assert!((hit.distance - 0.279).abs() < 1e-3);
+    assert!(MeshBvh::build(&TriMesh::default()).sphere_cast(&down, 0.05, 1.0).is_none());
+}
+
+#[test]
+fn test_closest_point_trait() {
+    let bvh = MeshBvh::build(&sphere(16, 24));
+    let p = Vec3::new(0.3, 2.0, -0.2);
+    let surface = ClosestPoint::closest_point(&bvh, p);
+    let hit = bvh.closest_point(p, f32::INFINITY).unwrap();
+    assert!((surface.point - hit.point).length() < 1e-5);
+    assert!((surface.signed_distance(p) - hit.distance).abs() < 1e-4);
+    let triangle = match surface.feature {
+        Feature::Face(t) => t,
+        Feature::Edge(e) | Feature::Vertex(e) => e / 3,
+    };
+    assert_eq!(triangle, hit.triangle);
+    assert!(ClosestPoint::closest_point(&MeshBvh::build(&TriMesh::default()), p).is_none());
+}
+
+#[test]
+fn test_closest_to_capsule() {
+    let bvh = MeshBvh::build(&sphere(16, 24));
+    // Vertical capsule beside the unit sphere: nearest mesh point faces +X
+    let capsule = Capsule::new(Vec3::new(2.0, -1.0, 0.0), Vec3::new(2.0, 1.0, 0.0), 0.25);
+    let (on_capsule, surface) = bvh.closest_to_capsule(&capsule, 10.0).unwrap();
+    assert!((on_capsule - Vec3::new(1.75, 0.0, 0.0)).length() < 1e-3);
+    assert!((surface.point - Vec3::new(1.0, 0.0, 0.0)).length() < 0.02);
+    assert!(surface.normal.x > 0.95);
+    assert!(bvh.closest_to_capsule(&capsule, 0.5).is_none());
+}
@@ -0,0 +1,70 @@
+use super::*;
+use crate::core::{Deg, DepthRange, Mat4, Vec3};
+use crate::geometry::Aabb;
+use crate::scene::NodeBuilder;
+
+/// Camera at (0, 0, 5) looking down -Z, 90° field of view, depth 1..10.
+fn frustum() -> Frustum {
+    let projection = Mat4::perspective(Deg(90.0), 1.0, 1.0, 10.0);
+    let view = Mat4::look_at(Vec3::new(0.0, 0.0, 5.0), Vec3::zero(), Vec3::unit_y());
+    Frustum::from_matrix(&(projection * view), DepthRange::ZeroToOne)
+}
+
+/// 21 × 21 wall of panels at z = 0 spanning x, y in -20..20, a hidden group of
+/// buttons, a label and a panel on another layer.
+fn scene() -> Scene {
+    let tiles = (-10..=10).flat_map(|i| {
+        (-10..=10).map(move |j| NodeBuilder::panel().name(format!("{i},{j}")).at(i as f32 * 2.0, j as f32 * 2.0, 0.0).size(1.0, 1.0, 0.1))
+    });
+    NodeBuilder::group()
+        .child(NodeBuilder::group().children(tiles))
+        .child(NodeBuilder::group().hidden().child(NodeBuilder::button("X").name("hidden").size(1.0, 1.0, 1.0)))
+        .child(NodeBuilder::label("Title").name("title").at(0.0, 3.0, 1.0))
+        .child(NodeBuilder::panel().name("overlay").size(1.0, 1.0, 0.1).at(0.0, 0.0, 2.0).layers(LayerMask::layer(5)))
+        .into_scene()
+}
+
+#[test]
+fn test_cull_matches_brute_force() {
+    let scene = scene();
+    let culler = FrustumCuller::from_scene(&scene);
+    assert_eq!(culler.len(), 21 * 21 + 3);
+
+    let frustum = frustum();
+    let visible = culler.cull(&frustum, LayerMask::DEFAULT);
+    let expected: Vec<NodeId> = scene
+        .iter()
+        .filter(|(id, node)| {
+            let bounds = Aabb::from_center_half_extents(scene.world_position(*id).unwrap(), node.size * 0.5);
+            node.kind != NodeKind::Group
+                && scene.is_effectively(*id, NodeFlags::VISIBLE)
+                && node.layers.intersects(LayerMask::DEFAULT)
+                && frustum.intersects(&bounds)
+        })
+        .map(|(id, _)| id)
+        .collect();
+    assert_eq!(visible, expected);
+
+    // The view is 10 wide at z = 0, so tiles with |x|, |y| <= 4 plus the rim at ±5.5
+    assert!(visible.contains(&scene.find("0,0").unwrap()));
+    assert!(visible.contains(&scene.find("2,-2").unwrap()));
+    assert!(!visible.contains(&scene.find("3,0").unwrap()));
+    assert!(visible.contains(&scene.find("title").unwrap()));
+    assert!(!visible.contains(&scene.find("hidden").unwrap()));
+    assert!(!visible.contains(&scene.find("overlay").unwrap()));
+    assert_eq!(culler.cull(&frustum, LayerMask::layer(5)), vec![scene.find("overlay").unwrap()]);
+}
+
+#[test]
+fn test_cull_into_reuses_buffer() {
+    let mut scene = scene();
+    let mut culler = FrustumCuller::from_scene(&scene);
+    let mut out = vec![scene.find("10,10").unwrap()];
+    culler.cull_into(&frustum(), LayerMask::DEFAULT, &mut out);
+    assert!(!out.contains(&scene.find("10,10").unwrap()));
+
+    let title = scene.find("title").unwrap();
+    scene.get_mut(title).unwrap().position = Vec3::new(0.0, 0.0, 20.0);
+    culler.rebuild(&scene);
+    assert!(!culler.cull(&frustum(), LayerMask::DEFAULT).contains(&title));
+}
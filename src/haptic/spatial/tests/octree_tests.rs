@@ -0,0 +1,139 @@
+use super::*;
+
+/// Deterministic pseudo-random boxes scattered over [-10, 10]^3.
+fn scattered(count: usize) -> Vec<Aabb> {
+    let mut state = 0x2545_f491_u32;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state as f32 / u32::MAX as f32
+    };
+    (0..count)
+        .map(|_| {
+            let center = Vec3::new(next(), next(), next()) * 20.0 - Vec3::splat(10.0);
+            Aabb::from_center_half_extents(center, Vec3::splat(0.05 + next() * 0.3))
+        })
+        .collect()
+}
+
+fn tree_of(boxes: &[Aabb]) -> (Octree<usize>, Vec<OctreeKey>) {
+    let mut tree = Octree::with_limits(Aabb::from_center_half_extents(Vec3::zero(), Vec3::splat(10.0)), 4, 6);
+    let keys = boxes.iter().enumerate().map(|(i, b)| tree.insert(*b, i)).collect();
+    (tree, keys)
+}
+
+fn sorted_values(tree: &Octree<usize>, keys: &[OctreeKey]) -> Vec<usize> {
+    let mut values: Vec<usize> = keys.iter().map(|k| *tree.get(*k).unwrap().1).collect();
+    values.sort_unstable();
+    values
+}
+
+#[test]
+fn test_insert_remove_and_stale_keys() {
+    let boxes = scattered(200);
+    let (mut tree, keys) = tree_of(&boxes);
+    assert_eq!(tree.len(), 200);
+    assert!(tree.node_count() > 1);
+    assert_eq!(tree.remove(keys[7]), Some(7));
+    assert_eq!(tree.remove(keys[7]), None);
+    assert!(!tree.contains_key(keys[7]));
+
+    // The freed slot is reused but the old key stays dead
+    let reused = tree.insert(boxes[7], 1000);
+    assert!(tree.get(keys[7]).is_none());
+    assert_eq!(tree.get(reused).map(|(_, v)| *v), Some(1000));
+    *tree.get_mut(reused).unwrap() = 7;
+    assert_eq!(tree.iter().count(), 200);
+
+    tree.clear();
+    assert!(tree.is_empty() && tree.get(reused).is_none());
+}
+
+#[test]
+fn test_range_queries_match_brute_force() {
+    let boxes = scattered(300);
+    let (tree, _) = tree_of(&boxes);
+    let region = Aabb::new(Vec3::new(-3.0, -2.0, -5.0), Vec3::new(4.0, 6.0, 1.0));
+    let expected: Vec<usize> = (0..boxes.len()).filter(|&i| boxes[i].intersects(&region)).collect();
+    assert!(!expected.is_empty());
+    assert_eq!(sorted_values(&tree, &tree.query_aabb(&region)), expected);
+
+    let center = Vec3::new(1.0, 1.0, 1.0);
+    let expected: Vec<usize> = (0..boxes.len()).filter(|&i| boxes[i].distance_squared(center) <= 9.0).collect();
+    assert_eq!(sorted_values(&tree, &tree.query_sphere(center, 3.0)), expected);
+}
+
+#[test]
+fn test_k_nearest() {
+    let boxes = scattered(300);
+    let (tree, _) = tree_of(&boxes);
+    let p = Vec3::new(2.0, -1.0, 0.5);
+    let mut expected: Vec<(usize, f32)> = boxes.iter().enumerate().map(|(i, b)| (i, b.distance_squared(p).sqrt())).collect();
+    expected.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let nearest = tree.nearest(p, 5);
+    assert_eq!(nearest.len(), 5);
+    for ((key, distance), (_, expected_distance)) in nearest.iter().zip(&expected) {
+        assert!((distance - expected_distance).abs() < 1e-5);
+        assert!((boxes[*tree.get(*key).unwrap().1].distance_squared(p).sqrt() - distance).abs() < 1e-5);
+    }
+    assert!(tree.nearest(p, 0).is_empty());
+}
+
+#[test]
+fn test_update_moves_items() {
+    let boxes = scattered(100);
+    let (mut tree, keys) = tree_of(&boxes);
+    let far = Aabb::from_center_half_extents(Vec3::new(9.5, 9.5, 9.5), Vec3::splat(0.1));
+    assert!(tree.update(keys[3], far));
+    assert_eq!(tree.nearest(Vec3::splat(9.5), 1)[0].0, keys[3]);
+    assert!(!tree.query_aabb(&boxes[3]).contains(&keys[3]));
+
+    // Items may leave the root bounds entirely
+    let outside = Aabb::from_center_half_extents(Vec3::new(50.0, 0.0, 0.0), Vec3::one());
+    assert!(tree.update(keys[4], outside));
+    assert_eq!(tree.query_sphere(Vec3::new(50.0, 0.0, 0.0), 0.5), vec![keys[4]]);
+    tree.remove(keys[5]);
+    assert!(!tree.update(keys[5], far));
+}
+
+#[test]
+fn test_raycasts() {
+    let boxes = scattered(300);
+    let (tree, _) = tree_of(&boxes);
+    let ray = Ray::new(Vec3::new(-12.0, 0.3, -0.2), Vec3::new(1.0, 0.05, 0.02));
+    let expected: Vec<f32> = {
+        let mut d: Vec<f32> = boxes.iter().filter_map(|b| b.ray_distance(&ray, 30.0)).collect();
+        d.sort_by(f32::total_cmp);
+        d
+    };
+    let hits = tree.raycast(&ray, 30.0);
+    assert_eq!(hits.iter().map(|h| h.distance).collect::<Vec<_>>(), expected);
+
+    let first = tree.raycast_first(&ray, 30.0);
+    assert_eq!(first.map(|h| h.distance), expected.first().copied());
+
+    // A custom test can reject items, e.g. everything with an even value
+    let odd = tree.raycast_first_by(&ray, 30.0, |_, b, v| if v % 2 == 1 { b.ray_distance(&ray, 30.0) } else { None });
+    let expected_odd = hits.iter().find(|h| tree.get(h.key).unwrap().1 % 2 == 1);
+    assert_eq!(odd.map(|h| h.key), expected_odd.map(|h| h.key));
+}
+
+#[test]
+fn test_frustum_query_matches_brute_force() {
+    use crate::core::{Deg, DepthRange, Mat4};
+    use crate::geometry::Frustum;
+
+    let boxes = scattered(500);
+    let (tree, _) = tree_of(&boxes);
+    let projection = Mat4::perspective(Deg(60.0), 1.5, 0.5, 12.0);
+    let view = Mat4::look_at(Vec3::new(2.0, 1.0, 9.0), Vec3::new(-1.0, 0.0, 0.0), Vec3::unit_y());
+    let frustum = Frustum::from_matrix(&(projection * view), DepthRange::ZeroToOne);
+
+    let mut found = sorted_values(&tree, &tree.query_frustum(&frustum));
+    found.dedup();
+    let expected: Vec<usize> = (0..boxes.len()).filter(|&i| frustum.intersects(&boxes[i])).collect();
+    assert_eq!(found, expected);
+    assert!(!expected.is_empty() && expected.len() < boxes.len());
+}
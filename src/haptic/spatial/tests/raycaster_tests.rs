@@ -0,0 +1,140 @@
+use super::*;
+use crate::scene::NodeBuilder;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+/// Three panels stacked along -Z in front of a ray from the origin, plus a hidden
+/// group and a panel on a separate layer.
+fn scene() -> Scene {
+    NodeBuilder::group()
+        .name("root")
+        .child(NodeBuilder::panel().name("near").at(0.0, 0.0, -1.0).size(1.0, 1.0, 0.1))
+        .child(NodeBuilder::panel().name("ghost").at(0.0, 0.0, -2.0).size(1.0, 1.0, 0.1).ghost())
+        .child(
+            NodeBuilder::group()
+                .at(0.0, 0.0, -3.0)
+                .hidden()
+                .child(NodeBuilder::button("hidden").name("hidden").size(1.0, 1.0, 0.1)),
+        )
+        .child(NodeBuilder::panel().name("far").at(0.0, 0.0, -4.0).size(1.0, 1.0, 0.1).layers(LayerMask::layer(4)))
+        .child(NodeBuilder::panel().name("aside").at(5.0, 0.0, -1.0).size(1.0, 1.0, 0.1))
+        .into_scene()
+}
+
+fn forward() -> Ray {
+    Ray::new(Vec3::zero(), Vec3::new(0.0, 0.0, -1.0))
+}
+
+#[test]
+fn test_hits_are_sorted_with_face_normals() {
+    let scene = scene();
+    let raycaster = Raycaster::from_scene(&scene);
+    assert_eq!(raycaster.len(), 5);
+
+    let hits = raycaster.cast(&forward(), &RaycastFilter::default());
+    let names: Vec<&str> = hits.iter().map(|h| scene.get(h.node).unwrap().name.as_str()).collect();
+    assert_eq!(names, ["near", "ghost", "hidden", "far"]);
+    assert!((hits[0].distance - 0.95).abs() < TEST_EPSILON);
+    assert!((hits[0].point - Vec3::new(0.0, 0.0, -0.95)).length() < TEST_EPSILON);
+    assert_eq!(hits[0].normal, Vec3::new(0.0, 0.0, 1.0));
+
+    let side = raycaster.cast_first(&Ray::new(Vec3::new(7.0, 0.0, -1.0), Vec3::new(-1.0, 0.0, 0.0)), &RaycastFilter::default());
+    assert_eq!(side.map(|h| (h.node, h.normal)), Some((scene.find("aside").unwrap(), Vec3::unit_x())));
+}
+
+#[test]
+fn test_flags_layers_and_distance_filter_hits() {
+    let scene = scene();
+    let raycaster = Raycaster::from_scene(&scene);
+    let name = |hit: Option<RaycastHit>| hit.map(|h| scene.get(h.node).unwrap().name.clone());
+
+    // The ghost is visible but not pickable; hiding a group leaves its children pickable
+    let picked: Vec<NodeId> = raycaster.cast(&forward(), &RaycastFilter::picking()).iter().map(|h| h.node).collect();
+    assert_eq!(picked, ["near", "hidden", "far"].map(|n| scene.find(n).unwrap()));
+    let seen: Vec<NodeId> = raycaster.cast(&forward(), &RaycastFilter::occluders()).iter().map(|h| h.node).collect();
+    assert_eq!(seen, ["near", "ghost", "far"].map(|n| scene.find(n).unwrap()));
+
+    let near = scene.find("near").unwrap();
+    assert_eq!(name(raycaster.cast_first_where(&forward(), &RaycastFilter::picking(), |n| n != near)), Some("hidden".into()));
+
+    let far_layer = RaycastFilter::new(NodeFlags::NONE, LayerMask::layer(4));
+    assert_eq!(name(raycaster.cast_first(&forward(), &far_layer)), Some("far".into()));
+    assert_eq!(raycaster.cast_first(&forward(), &far_layer.with_max_distance(3.0)), None);
+    // The ghost has no TOUCHABLE flag
+    assert_eq!(raycaster.cast(&forward(), &RaycastFilter::new(NodeFlags::TOUCHABLE, LayerMask::ALL)).len(), 3);
+}
+
+#[test]
+fn test_line_of_sight() {
+    let scene = scene();
+    let mut raycaster = Raycaster::from_scene(&scene);
+    let occluders = RaycastFilter::occluders();
+    let near = scene.find("near").unwrap();
+
+    assert!(!raycaster.line_of_sight(Vec3::zero(), Vec3::new(0.0, 0.0, -2.0), &occluders, &[]));
+    assert!(raycaster.line_of_sight(Vec3::zero(), Vec3::new(0.0, 0.0, -1.5), &occluders, &[near]));
+    // Stopping at the near panel's face does not count as blocked
+    assert!(raycaster.line_of_sight(Vec3::zero(), Vec3::new(0.0, 0.0, -0.95), &occluders, &[]));
+
+    let mut scene = scene;
+    scene.get_mut(near).unwrap().position = Vec3::new(0.0, 3.0, -1.0);
+    raycaster.rebuild(&scene);
+    assert!(raycaster.line_of_sight(Vec3::zero(), Vec3::new(0.0, 0.0, -1.5), &occluders, &[]));
+}
+
+#[test]
+fn test_sphere_and_capsule_casts() {
+    let scene = scene();
+    let raycaster = Raycaster::from_scene(&scene);
+    let aside = scene.find("aside").unwrap();
+
+    // A thin ray between the panels misses, a fat sphere clips the edge of "aside"
+    let between = Ray::new(Vec3::new(4.4, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0));
+    let filter = RaycastFilter::picking();
+    assert_eq!(raycaster.cast_first(&between, &filter), None);
+    let hit = raycaster.sphere_cast(&between, 0.2, &filter).unwrap();
+    assert_eq!(hit.node, aside);
+    assert!((hit.point - Vec3::new(4.5, 0.0, -0.95)).length() < 1e-3);
+    assert!(hit.normal.z > 0.0 && hit.normal.x < 0.0);
+    assert!(raycaster.sphere_cast(&between, 0.2, &filter.with_max_distance(0.5)).is_none());
+
+    let capsule = Capsule::new(Vec3::new(-0.2, 0.0, 0.0), Vec3::new(0.2, 0.0, 0.0), 0.05);
+    let hit = raycaster.capsule_cast(&capsule, Vec3::new(0.0, 0.0, -1.0), &filter).unwrap();
+    assert_eq!(hit.node, scene.find("near").unwrap());
+    assert!((hit.distance - 0.9).abs() < 1e-3);
+    assert!((hit.normal - Vec3::unit_z()).length() < 1e-3);
+}
+
+#[test]
+fn test_rays_pass_through_clipped_parts() {
+    use crate::scene::Clip;
+
+    // A row sticking out of the top of a clipped view, in front of a wall
+    let scene = NodeBuilder::group()
+        .child(
+            NodeBuilder::group()
+                .at(0.0, 0.0, -1.0)
+                .clip(Clip::bounds(Vec3::new(1.0, 1.0, 1.0)))
+                .child(NodeBuilder::panel().name("row").at(0.0, 0.5, 0.0).size(1.0, 0.4, 0.1)),
+        )
+        .child(
+            NodeBuilder::group()
+                .clip(Clip::plane(Vec3::new(0.0, 0.0, -3.0), Vec3::new(0.0, 1.0, -1.0)))
+                .child(NodeBuilder::panel().name("wall").at(0.0, 0.0, -3.0).size(2.0, 2.0, 0.1)),
+        )
+        .into_scene();
+    let (row, wall) = (scene.find("row").unwrap(), scene.find("wall").unwrap());
+    let raycaster = Raycaster::from_scene(&scene);
+    let filter = RaycastFilter::picking();
+    let ray = |y: f32| Ray::new(Vec3::new(0.0, y, 0.0), Vec3::new(0.0, 0.0, -1.0));
+
+    assert_eq!(raycaster.cast_first(&ray(0.4), &filter).map(|h| h.node), Some(row));
+    let through = raycaster.cast(&ray(0.6), &filter);
+    assert_eq!(through.iter().map(|h| h.node).collect::<Vec<_>>(), [wall]);
+
+    // The wall is cut on a slant: a ray meets the cut face, facing back along the plane normal
+    let hit = raycaster.cast_first(&ray(0.02), &filter).unwrap();
+    assert!((hit.distance - 2.98).abs() < TEST_EPSILON);
+    assert!((hit.normal - Vec3::new(0.0, -1.0, 1.0).normalize()).length() < TEST_EPSILON);
+    assert!(raycaster.cast_first(&ray(-0.2), &filter).is_none());
+}
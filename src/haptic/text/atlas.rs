@@ -0,0 +1,104 @@
+//! Glyph atlas allocation for signed-distance-field text.
+//!
+//! A [`GlyphAtlas`] hands out square cells of one texture, one per glyph of one
+//! font, the first time the glyph is needed. The atlas only does the bookkeeping:
+//! glyphs allocated since the last [`GlyphAtlas::take_new`] are reported so the
+//! renderer can rasterize their distance fields into the texture. Once full, new
+//! glyphs get no cell until the atlas is cleared.
+
+use std::collections::HashMap;
+
+/// A cell of the atlas texture, in texels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AtlasRect {
+    pub x: u32,
+    pub y: u32,
+    pub size: u32,
+}
+
+impl AtlasRect {
+    /// Texture coordinates `[u0, v0, u1, v1]` in an atlas of `atlas_size` texels square.
+    pub fn uv(&self, atlas_size: u32) -> [f32; 4] {
+        let scale = 1.0 / atlas_size.max(1) as f32;
+        [
+            self.x as f32 * scale,
+            self.y as f32 * scale,
+            (self.x + self.size) as f32 * scale,
+            (self.y + self.size) as f32 * scale,
+        ]
+    }
+}
+
+/// Square texture divided into glyph cells, filled row by row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlyphAtlas {
+    size: u32,
+    cell: u32,
+    cells: HashMap<(usize, char), AtlasRect>,
+    new: Vec<(usize, char, AtlasRect)>,
+}
+
+impl GlyphAtlas {
+    /// Atlas of `size` texels square with cells of `cell` texels.
+    pub fn new(size: u32, cell: u32) -> Self {
+        Self { size, cell: cell.clamp(1, size.max(1)), cells: HashMap::new(), new: Vec::new() }
+    }
+
+    #[inline]
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        let per_row = (self.size / self.cell) as usize;
+        per_row * per_row
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Cell of `ch` in font `font`, if allocated.
+    pub fn get(&self, font: usize, ch: char) -> Option<AtlasRect> {
+        self.cells.get(&(font, ch)).copied()
+    }
+
+    /// Cell of `ch` in font `font`, allocating it if needed; None once full.
+    pub fn allocate(&mut self, font: usize, ch: char) -> Option<AtlasRect> {
+        if let Some(rect) = self.get(font, ch) {
+            return Some(rect);
+        }
+        let index = self.cells.len();
+        if index >= self.capacity() {
+            return None;
+        }
+        let per_row = self.size / self.cell;
+        let index = index as u32;
+        let rect = AtlasRect { x: index % per_row * self.cell, y: index / per_row * self.cell, size: self.cell };
+        self.cells.insert((font, ch), rect);
+        self.new.push((font, ch, rect));
+        Some(rect)
+    }
+
+    /// Glyphs allocated since the last call, to rasterize into the texture.
+    pub fn take_new(&mut self) -> Vec<(usize, char, AtlasRect)> {
+        std::mem::take(&mut self.new)
+    }
+
+    /// Frees every cell, e.g. when the atlas filled up with glyphs no longer shown.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.new.clear();
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/atlas_tests.rs"]
+mod tests;
@@ -0,0 +1,237 @@
+//! Direction-aware line layout with font fallback.
+//!
+//! Produces positioned glyphs in a 2D text plane (x to the right, lines going down),
+//! which labels then place in 3D. Bidirectional text uses a simplified reordering:
+//! runs of strong characters are reversed as units in RTL paragraphs, and RTL runs
+//! are mirrored inside LTR paragraphs. This covers UI strings (labels, numbers in
+//! Arabic/Hebrew sentences) without a full UAX #9 implementation.
+
+use super::locale::Direction;
+use std::ops::RangeInclusive;
+
+// ============================================================================
+// Fonts
+// ============================================================================
+
+/// A font face and the character ranges it covers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontFace {
+    pub name: String,
+    pub coverage: Vec<RangeInclusive<char>>,
+    /// Advance width of one em-relative glyph, as a fraction of the font size.
+    pub advance: f32,
+}
+
+impl FontFace {
+    pub fn new(name: impl Into<String>, coverage: Vec<RangeInclusive<char>>, advance: f32) -> Self {
+        Self { name: name.into(), coverage, advance }
+    }
+
+    #[inline]
+    pub fn covers(&self, c: char) -> bool {
+        self.coverage.iter().any(|r| r.contains(&c))
+    }
+}
+
+/// Ordered list of fonts; each character uses the first font that covers it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FontFallback {
+    fonts: Vec<FontFace>,
+}
+
+impl FontFallback {
+    pub fn new(fonts: Vec<FontFace>) -> Self {
+        Self { fonts }
+    }
+
+    pub fn push(&mut self, font: FontFace) {
+        self.fonts.push(font);
+    }
+
+    pub fn fonts(&self) -> &[FontFace] {
+        &self.fonts
+    }
+
+    /// Index of the font used for `c`. Uncovered characters use the last font (tofu).
+    pub fn font_for(&self, c: char) -> usize {
+        self.fonts.iter().position(|f| f.covers(c)).unwrap_or(self.fonts.len().saturating_sub(1))
+    }
+
+    fn advance(&self, c: char) -> f32 {
+        self.fonts.get(self.font_for(c)).map_or(0.6, |f| f.advance)
+    }
+}
+
+// ============================================================================
+// Layout
+// ============================================================================
+
+/// Horizontal alignment within the layout width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextAlign {
+    /// Left for LTR, right for RTL paragraphs.
+    Start,
+    Center,
+    /// Right for LTR, left for RTL paragraphs.
+    End,
+}
+
+/// Layout parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextStyle {
+    pub font_size: f32,
+    pub line_height: f32,
+    /// Maximum line width; lines wrap at spaces beyond it. None disables wrapping.
+    pub max_width: Option<f32>,
+    pub align: TextAlign,
+    /// Paragraph direction; None detects it from the first strong character.
+    pub direction: Option<Direction>,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self { font_size: 1.0, line_height: 1.2, max_width: None, align: TextAlign::Start, direction: None }
+    }
+}
+
+/// A laid-out glyph.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionedGlyph {
+    pub ch: char,
+    /// Index into the fallback chain.
+    pub font: usize,
+    /// Left edge of the glyph.
+    pub x: f32,
+    /// Baseline offset (0 for the first line, negative downwards).
+    pub y: f32,
+    pub advance: f32,
+    pub line: usize,
+}
+
+/// Result of laying out a string.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayout {
+    pub glyphs: Vec<PositionedGlyph>,
+    pub direction: Direction,
+    pub line_count: usize,
+    /// Width of the widest line.
+    pub width: f32,
+    pub height: f32,
+}
+
+impl TextLayout {
+    /// Lays out `text` with the given fonts and style.
+    pub fn new(text: &str, fonts: &FontFallback, style: &TextStyle) -> Self {
+        let direction = style.direction.or_else(|| Direction::detect(text)).unwrap_or(Direction::Ltr);
+        let advance = |c: char| fonts.advance(c) * style.font_size;
+
+        let mut lines: Vec<Vec<char>> = Vec::new();
+        for paragraph in text.split('\n') {
+            wrap_paragraph(paragraph, style.max_width, &advance, &mut lines);
+        }
+
+        let widths: Vec<f32> = lines.iter().map(|l| l.iter().map(|&c| advance(c)).sum()).collect();
+        let width = widths.iter().copied().fold(0.0, f32::max);
+        let box_width = style.max_width.unwrap_or(width);
+
+        let mut glyphs = Vec::new();
+        for (line_index, line) in lines.iter().enumerate() {
+            let visual = reorder_visual(line, direction);
+            let slack = box_width - widths[line_index];
+            let mut x = match (style.align, direction) {
+                (TextAlign::Center, _) => slack * 0.5,
+                (TextAlign::Start, Direction::Ltr) | (TextAlign::End, Direction::Rtl) => 0.0,
+                (TextAlign::End, Direction::Ltr) | (TextAlign::Start, Direction::Rtl) => slack,
+            };
+            let y = -(line_index as f32) * style.line_height * style.font_size;
+            for c in visual {
+                let a = advance(c);
+                glyphs.push(PositionedGlyph { ch: c, font: fonts.font_for(c), x, y, advance: a, line: line_index });
+                x += a;
+            }
+        }
+
+        Self {
+            glyphs,
+            direction,
+            line_count: lines.len(),
+            width,
+            height: lines.len() as f32 * style.line_height * style.font_size,
+        }
+    }
+
+    /// The characters of one line in visual (left-to-right) order.
+    pub fn line_text(&self, line: usize) -> String {
+        self.glyphs.iter().filter(|g| g.line == line).map(|g| g.ch).collect()
+    }
+}
+
+/// Greedy word wrap in logical order. Words longer than the width are broken by character.
+fn wrap_paragraph(text: &str, max_width: Option<f32>, advance: &dyn Fn(char) -> f32, lines: &mut Vec<Vec<char>>) {
+    let Some(max_width) = max_width else {
+        lines.push(text.chars().collect());
+        return;
+    };
+
+    let mut line: Vec<char> = Vec::new();
+    let mut line_width = 0.0;
+    for word in text.split(' ') {
+        let word_width: f32 = word.chars().map(advance).sum();
+        let space = if line.is_empty() { 0.0 } else { advance(' ') };
+        if !line.is_empty() && line_width + space + word_width > max_width {
+            lines.push(std::mem::take(&mut line));
+            line_width = 0.0;
+        } else if !line.is_empty() {
+            line.push(' ');
+            line_width += space;
+        }
+        for c in word.chars() {
+            let a = advance(c);
+            if !line.is_empty() && line_width + a > max_width && word_width > max_width {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0.0;
+            }
+            line.push(c);
+            line_width += a;
+        }
+    }
+    lines.push(line);
+}
+
+/// Reorders one line from logical to visual order.
+fn reorder_visual(line: &[char], paragraph: Direction) -> Vec<char> {
+    // Digits are treated as LTR so numbers keep their order inside RTL text
+    let strong = |c: char| Direction::of_char(c).or(c.is_ascii_digit().then_some(Direction::Ltr));
+    let classes: Vec<Option<Direction>> = line.iter().map(|&c| strong(c)).collect();
+
+    // Neutrals between two characters of the same direction take that direction,
+    // otherwise the paragraph direction (UAX #9 rules N1/N2)
+    let mut runs: Vec<(Direction, Vec<char>)> = Vec::new();
+    for (i, &c) in line.iter().enumerate() {
+        let dir = classes[i].unwrap_or_else(|| {
+            let before = classes[..i].iter().rev().find_map(|d| *d).unwrap_or(paragraph);
+            let after = classes[i + 1..].iter().find_map(|d| *d).unwrap_or(paragraph);
+            if before == after { before } else { paragraph }
+        });
+        match runs.last_mut() {
+            Some((d, run)) if *d == dir => run.push(c),
+            _ => runs.push((dir, vec![c])),
+        }
+    }
+
+    if paragraph == Direction::Rtl {
+        runs.reverse();
+    }
+    runs.into_iter()
+        .flat_map(|(dir, mut run)| {
+            if dir == Direction::Rtl {
+                run.reverse();
+            }
+            run
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "tests/layout_tests.rs"]
+mod tests;
@@ -0,0 +1,213 @@
+//! Locales, writing direction, and pluralized message catalogs.
+
+use std::collections::HashMap;
+use std::fmt;
+
+// ============================================================================
+// Locale
+// ============================================================================
+
+/// Language and optional region, e.g. `ar-EG` or `en`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Locale {
+    pub language: String,
+    pub region: Option<String>,
+}
+
+impl Locale {
+    /// Parses a BCP 47-style tag (`en`, `en-US`, `pt_BR`). Only language and region are kept.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let mut parts = tag.split(['-', '_']);
+        let language = parts.next()?.to_ascii_lowercase();
+        if language.len() < 2 || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        let region = parts.find(|p| p.len() == 2 || p.len() == 3).map(|r| r.to_ascii_uppercase());
+        Some(Self { language, region })
+    }
+
+    /// Base writing direction of the locale's script.
+    pub fn direction(&self) -> Direction {
+        match self.language.as_str() {
+            "ar" | "he" | "fa" | "ur" | "ps" | "sd" | "yi" | "dv" | "ug" | "ckb" => Direction::Rtl,
+            _ => Direction::Ltr,
+        }
+    }
+
+    /// Fallback chain: `pt-BR` → `pt`.
+    pub fn fallbacks(&self) -> Vec<Locale> {
+        let mut chain = vec![self.clone()];
+        if self.region.is_some() {
+            chain.push(Locale { language: self.language.clone(), region: None });
+        }
+        chain
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.region {
+            Some(region) => write!(f, "{}-{}", self.language, region),
+            None => f.write_str(&self.language),
+        }
+    }
+}
+
+// ============================================================================
+// Direction
+// ============================================================================
+
+/// Writing direction of a paragraph or run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Ltr,
+    Rtl,
+}
+
+impl Direction {
+    /// Strong direction of a character, or None for neutrals (digits, spaces, punctuation).
+    pub fn of_char(c: char) -> Option<Direction> {
+        match c as u32 {
+            0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF | 0x10800..=0x10FFF | 0x1E800..=0x1EFFF => {
+                Some(Direction::Rtl)
+            }
+            _ if c.is_alphabetic() => Some(Direction::Ltr),
+            _ => None,
+        }
+    }
+
+    /// Direction of the first strong character (Unicode rule P2), or None if there is none.
+    pub fn detect(text: &str) -> Option<Direction> {
+        text.chars().find_map(Direction::of_char)
+    }
+}
+
+// ============================================================================
+// Plural Rules
+// ============================================================================
+
+/// CLDR plural categories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+/// Maps a count to its plural category for one language.
+pub type PluralRule = fn(u64) -> PluralCategory;
+
+/// Built-in plural rules for a language; unknown languages fall back to the English rule.
+pub fn plural_rule(language: &str) -> PluralRule {
+    match language {
+        "ja" | "zh" | "ko" | "th" | "vi" | "id" | "ms" => |_| PluralCategory::Other,
+        "fr" | "pt" => |n| if n <= 1 { PluralCategory::One } else { PluralCategory::Other },
+        "ru" | "uk" | "be" | "sr" | "hr" | "bs" => |n| match (n % 10, n % 100) {
+            (1, r) if r != 11 => PluralCategory::One,
+            (2..=4, r) if !(12..=14).contains(&r) => PluralCategory::Few,
+            _ => PluralCategory::Many,
+        },
+        "pl" => |n| match (n, n % 10, n % 100) {
+            (1, _, _) => PluralCategory::One,
+            (_, 2..=4, r) if !(12..=14).contains(&r) => PluralCategory::Few,
+            _ => PluralCategory::Many,
+        },
+        "ar" => |n| match (n, n % 100) {
+            (0, _) => PluralCategory::Zero,
+            (1, _) => PluralCategory::One,
+            (2, _) => PluralCategory::Two,
+            (_, 3..=10) => PluralCategory::Few,
+            (_, 11..=99) => PluralCategory::Many,
+            _ => PluralCategory::Other,
+        },
+        _ => |n| if n == 1 { PluralCategory::One } else { PluralCategory::Other },
+    }
+}
+
+// ============================================================================
+// Message Catalog
+// ============================================================================
+
+/// A translatable message, optionally with plural variants.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    Text(String),
+    Plural(HashMap<PluralCategory, String>),
+}
+
+/// Translated messages for several locales, with per-language plural rules.
+///
+/// Lookups walk the locale fallback chain, then the default locale, and finally
+/// return the key itself so missing translations are visible but never fatal.
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    default_locale: Locale,
+    messages: HashMap<(Locale, String), Message>,
+    plural_overrides: HashMap<String, PluralRule>,
+}
+
+impl Catalog {
+    pub fn new(default_locale: Locale) -> Self {
+        Self { default_locale, messages: HashMap::new(), plural_overrides: HashMap::new() }
+    }
+
+    pub fn insert(&mut self, locale: &Locale, key: impl Into<String>, text: impl Into<String>) {
+        self.messages.insert((locale.clone(), key.into()), Message::Text(text.into()));
+    }
+
+    /// Adds plural variants; `Other` should always be present.
+    pub fn insert_plural(&mut self, locale: &Locale, key: impl Into<String>, forms: &[(PluralCategory, &str)]) {
+        let forms = forms.iter().map(|(c, s)| (*c, s.to_string())).collect();
+        self.messages.insert((locale.clone(), key.into()), Message::Plural(forms));
+    }
+
+    /// Pluralization hook: overrides the built-in rule for a language.
+    pub fn set_plural_rule(&mut self, language: impl Into<String>, rule: PluralRule) {
+        self.plural_overrides.insert(language.into(), rule);
+    }
+
+    fn rule_for(&self, language: &str) -> PluralRule {
+        self.plural_overrides.get(language).copied().unwrap_or_else(|| plural_rule(language))
+    }
+
+    fn find(&self, locale: &Locale, key: &str) -> Option<(&Locale, &Message)> {
+        locale
+            .fallbacks()
+            .into_iter()
+            .chain(self.default_locale.fallbacks())
+            .find_map(|l| self.messages.get_key_value(&(l, key.to_string())).map(|((l, _), m)| (l, m)))
+    }
+
+    /// Looks up a message for `locale`.
+    pub fn get(&self, locale: &Locale, key: &str) -> String {
+        match self.find(locale, key) {
+            Some((_, Message::Text(text))) => text.clone(),
+            Some((_, Message::Plural(forms))) => forms.get(&PluralCategory::Other).cloned().unwrap_or_default(),
+            None => key.to_string(),
+        }
+    }
+
+    /// Looks up the plural form for `count` and substitutes `{count}`.
+    pub fn get_plural(&self, locale: &Locale, key: &str, count: u64) -> String {
+        let text = match self.find(locale, key) {
+            Some((found, Message::Plural(forms))) => {
+                let category = self.rule_for(&found.language)(count);
+                forms
+                    .get(&category)
+                    .or_else(|| forms.get(&PluralCategory::Other))
+                    .cloned()
+                    .unwrap_or_default()
+            }
+            Some((_, Message::Text(text))) => text.clone(),
+            None => key.to_string(),
+        };
+        text.replace("{count}", &count.to_string())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/locale_tests.rs"]
+mod tests;
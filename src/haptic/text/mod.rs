@@ -0,0 +1,7 @@
+// src/haptic/text/mod.rs
+pub mod atlas;
+pub mod layout;
+pub mod locale;
+pub use atlas::{AtlasRect, GlyphAtlas};
+pub use layout::{FontFace, FontFallback, PositionedGlyph, TextAlign, TextLayout, TextStyle};
+pub use locale::{plural_rule, Catalog, Direction, Locale, Message, PluralCategory, PluralRule};
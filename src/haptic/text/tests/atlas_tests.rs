@@ -0,0 +1,54 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-6;
+
+#[test]
+fn test_cells_fill_row_by_row() {
+    let mut atlas = GlyphAtlas::new(128, 32);
+    assert_eq!(atlas.capacity(), 16);
+    let a = atlas.allocate(0, 'a').unwrap();
+    let b = atlas.allocate(0, 'b').unwrap();
+    assert_eq!(a, AtlasRect { x: 0, y: 0, size: 32 });
+    assert_eq!(b, AtlasRect { x: 32, y: 0, size: 32 });
+    for c in 'c'..='e' {
+        atlas.allocate(0, c);
+    }
+    assert_eq!(atlas.get(0, 'e'), Some(AtlasRect { x: 0, y: 32, size: 32 }));
+
+    // The same glyph in another font gets its own cell; a repeat does not
+    assert_ne!(atlas.allocate(1, 'a'), Some(a));
+    assert_eq!(atlas.allocate(0, 'a'), Some(a));
+    assert_eq!(atlas.len(), 6);
+}
+
+#[test]
+fn test_new_glyphs_are_reported_once() {
+    let mut atlas = GlyphAtlas::new(64, 32);
+    atlas.allocate(0, 'x');
+    atlas.allocate(0, 'x');
+    atlas.allocate(0, 'y');
+    let new = atlas.take_new();
+    assert_eq!(new.iter().map(|&(_, c, _)| c).collect::<String>(), "xy");
+    assert!(atlas.take_new().is_empty());
+}
+
+#[test]
+fn test_full_atlas_and_clear() {
+    let mut atlas = GlyphAtlas::new(64, 32);
+    for c in 'a'..='d' {
+        assert!(atlas.allocate(0, c).is_some());
+    }
+    assert!(atlas.allocate(0, 'e').is_none());
+    atlas.clear();
+    assert!(atlas.is_empty());
+    assert!(atlas.allocate(0, 'e').is_some());
+}
+
+#[test]
+fn test_uv() {
+    let uv = AtlasRect { x: 32, y: 64, size: 32 }.uv(128);
+    let expected = [0.25, 0.5, 0.5, 0.75];
+    for (a, b) in uv.iter().zip(expected) {
+        assert!((a - b).abs() < TEST_EPSILON);
+    }
+}
@@ -0,0 +1,49 @@
+use super::*;
+
+fn fonts() -> FontFallback {
+    FontFallback::new(vec![
+        FontFace::new("Latin", vec![' '..='\u{024F}'], 0.5),
+        FontFace::new("Hebrew", vec!['\u{0590}'..='\u{05FF}'], 0.6),
+        FontFace::new("Fallback", vec![], 1.0),
+    ])
+}
+
+#[test]
+fn test_font_fallback_selection() {
+    let fonts = fonts();
+    assert_eq!(fonts.font_for('a'), 0);
+    assert_eq!(fonts.font_for('ש'), 1);
+    assert_eq!(fonts.font_for('漢'), 2);
+}
+
+#[test]
+fn test_wrapping_within_width() {
+    let style = TextStyle { max_width: Some(3.0), ..TextStyle::default() };
+    let layout = TextLayout::new("one two three", &fonts(), &style);
+    assert_eq!(layout.line_count, 3);
+    assert_eq!(layout.line_text(0), "one");
+    assert_eq!(layout.line_text(2), "three");
+    assert!(layout.glyphs.iter().all(|g| g.x + g.advance <= 3.0 + 1e-5));
+    assert!(layout.glyphs.iter().filter(|g| g.line == 1).all(|g| g.y < 0.0));
+}
+
+#[test]
+fn test_rtl_paragraph_is_reversed_and_right_aligned() {
+    let style = TextStyle { max_width: Some(10.0), ..TextStyle::default() };
+    let layout = TextLayout::new("שלום", &fonts(), &style);
+    assert_eq!(layout.direction, Direction::Rtl);
+    assert_eq!(layout.line_text(0), "םולש");
+    // Start alignment in RTL means flush right
+    let last = layout.glyphs.last().unwrap();
+    assert!((last.x + last.advance - 10.0).abs() < 1e-5);
+}
+
+#[test]
+fn test_mixed_direction_keeps_numbers_and_latin_in_order() {
+    let style = TextStyle { direction: Some(Direction::Rtl), ..TextStyle::default() };
+    let layout = TextLayout::new("שלום 42", &fonts(), &style);
+    assert_eq!(layout.line_text(0), "42 םולש");
+
+    let ltr = TextLayout::new("go שלום now", &fonts(), &TextStyle::default());
+    assert_eq!(ltr.line_text(0), "go םולש now");
+}
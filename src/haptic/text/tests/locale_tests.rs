@@ -0,0 +1,56 @@
+use super::*;
+
+#[test]
+fn test_locale_parsing_and_direction() {
+    let locale = Locale::parse("ar_eg").unwrap();
+    assert_eq!(locale.to_string(), "ar-EG");
+    assert_eq!(locale.direction(), Direction::Rtl);
+    assert_eq!(Locale::parse("en-US").unwrap().direction(), Direction::Ltr);
+    assert!(Locale::parse("1").is_none());
+}
+
+#[test]
+fn test_direction_detection() {
+    assert_eq!(Direction::detect("123 שלום"), Some(Direction::Rtl));
+    assert_eq!(Direction::detect("  Hello"), Some(Direction::Ltr));
+    assert_eq!(Direction::detect("42 %"), None);
+}
+
+#[test]
+fn test_plural_rules() {
+    let ru = plural_rule("ru");
+    assert_eq!(ru(1), PluralCategory::One);
+    assert_eq!(ru(3), PluralCategory::Few);
+    assert_eq!(ru(11), PluralCategory::Many);
+    assert_eq!(ru(21), PluralCategory::One);
+
+    let ar = plural_rule("ar");
+    assert_eq!(ar(0), PluralCategory::Zero);
+    assert_eq!(ar(2), PluralCategory::Two);
+    assert_eq!(ar(105), PluralCategory::Few);
+
+    assert_eq!(plural_rule("ja")(1), PluralCategory::Other);
+    assert_eq!(plural_rule("xx")(1), PluralCategory::One);
+}
+
+#[test]
+fn test_catalog_fallback_and_plurals() {
+    let en = Locale::parse("en").unwrap();
+    let de = Locale::parse("de").unwrap();
+    let mut catalog = Catalog::new(en.clone());
+    catalog.insert(&en, "ok", "OK");
+    catalog.insert(&en, "cancel", "Cancel");
+    catalog.insert(&de, "cancel", "Abbrechen");
+    catalog.insert_plural(&en, "items", &[(PluralCategory::One, "{count} item"), (PluralCategory::Other, "{count} items")]);
+
+    let de_at = Locale::parse("de-AT").unwrap();
+    assert_eq!(catalog.get(&de_at, "cancel"), "Abbrechen");
+    assert_eq!(catalog.get(&de_at, "ok"), "OK");
+    assert_eq!(catalog.get(&de_at, "missing.key"), "missing.key");
+    assert_eq!(catalog.get_plural(&en, "items", 1), "1 item");
+    assert_eq!(catalog.get_plural(&en, "items", 5), "5 items");
+
+    // Pluralization hook
+    catalog.set_plural_rule("en", |_| PluralCategory::Other);
+    assert_eq!(catalog.get_plural(&en, "items", 1), "1 items");
+}
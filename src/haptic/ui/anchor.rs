@@ -0,0 +1,277 @@
+//! Anchors: placing widgets relative to other widgets and the camera.
+//!
+//! An [`AnchorLayout`] holds constraints on scene nodes and moves the nodes to
+//! satisfy them on each layout pass ([`AnchorLayout::solve`]). A node can be
+//!
+//! - aligned on one axis to a side of another node, plus an offset ("left edge at
+//!   the right edge of A + 2 cm"),
+//! - placed between the centers of two nodes ("centered between B and C"),
+//! - held in front of the camera ("always 30 cm ahead").
+//!
+//! Aligning on different axes composes, so a node can take its X from one node
+//! and its Y from another. The solver orders the anchors so every node is placed
+//! after the nodes it depends on (and their anchored ancestors), which places a
+//! chain of anchors in one pass; circular anchors are reported instead of solved.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::core::Vec3;
+use crate::scene::{CameraPose, NodeId, Scene};
+
+// ============================================================================
+// Anchors
+// ============================================================================
+
+/// A side or center plane of a node's box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+    Bottom,
+    Top,
+    Back,
+    Front,
+    CenterX,
+    CenterY,
+    CenterZ,
+}
+
+impl Side {
+    /// Axis the side is across: 0 for X, 1 for Y, 2 for Z.
+    pub fn axis(self) -> usize {
+        match self {
+            Side::Left | Side::Right | Side::CenterX => 0,
+            Side::Bottom | Side::Top | Side::CenterY => 1,
+            Side::Back | Side::Front | Side::CenterZ => 2,
+        }
+    }
+
+    /// Position of the side along its axis, as a fraction of the box size from
+    /// the center.
+    fn fraction(self) -> f32 {
+        match self {
+            Side::Left | Side::Bottom | Side::Back => -0.5,
+            Side::Right | Side::Top | Side::Front => 0.5,
+            Side::CenterX | Side::CenterY | Side::CenterZ => 0.0,
+        }
+    }
+}
+
+/// A constraint on the position of one node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Anchor {
+    /// Puts `side` of the node at `target_side` of `target`, plus `offset` along
+    /// their common axis.
+    Align { side: Side, target: NodeId, target_side: Side, offset: f32 },
+    /// Centers the node at `fraction` of the way from the center of `from` to the
+    /// center of `to`.
+    Between { from: NodeId, to: NodeId, fraction: f32 },
+    /// Centers the node `distance` ahead of the camera.
+    FrontOfCamera { distance: f32 },
+}
+
+impl Anchor {
+    pub const fn align(side: Side, target: NodeId, target_side: Side, offset: f32) -> Self {
+        Anchor::Align { side, target, target_side, offset }
+    }
+
+    /// Halfway between the centers of `from` and `to`.
+    pub const fn between(from: NodeId, to: NodeId) -> Self {
+        Anchor::Between { from, to, fraction: 0.5 }
+    }
+
+    pub const fn front_of_camera(distance: f32) -> Self {
+        Anchor::FrontOfCamera { distance }
+    }
+
+    /// Nodes whose placement this anchor reads.
+    fn targets(&self) -> impl Iterator<Item = NodeId> {
+        let (a, b) = match *self {
+            Anchor::Align { target, .. } => (Some(target), None),
+            Anchor::Between { from, to, .. } => (Some(from), Some(to)),
+            Anchor::FrontOfCamera { .. } => (None, None),
+        };
+        a.into_iter().chain(b)
+    }
+}
+
+/// Errors from adding or solving anchors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnchorError {
+    /// The node is not in the scene.
+    InvalidNode(NodeId),
+    /// An alignment between sides across different axes.
+    AxisMismatch { side: Side, target_side: Side },
+    /// Anchors depend on each other in a loop through this node.
+    Cycle(NodeId),
+}
+
+impl fmt::Display for AnchorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnchorError::InvalidNode(id) => write!(f, "node {:?} is not in the scene", id),
+            AnchorError::AxisMismatch { side, target_side } => {
+                write!(f, "cannot align {:?} to {:?}: they are across different axes", side, target_side)
+            }
+            AnchorError::Cycle(id) => write!(f, "anchors of node {:?} depend on themselves", id),
+        }
+    }
+}
+
+impl std::error::Error for AnchorError {}
+
+// ============================================================================
+// Solver
+// ============================================================================
+
+/// Anchors on scene nodes, solved on each layout pass.
+#[derive(Debug, Clone, Default)]
+pub struct AnchorLayout {
+    anchors: Vec<(NodeId, Anchor)>,
+}
+
+impl AnchorLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Anchors `node`. Anchors are applied in the order added, so a later anchor
+    /// on the same axis wins.
+    pub fn add(&mut self, node: NodeId, anchor: Anchor) -> Result<(), AnchorError> {
+        if let Anchor::Align { side, target_side, .. } = anchor {
+            if side.axis() != target_side.axis() {
+                return Err(AnchorError::AxisMismatch { side, target_side });
+            }
+        }
+        self.anchors.push((node, anchor));
+        Ok(())
+    }
+
+    pub fn with(mut self, node: NodeId, anchor: Anchor) -> Result<Self, AnchorError> {
+        self.add(node, anchor)?;
+        Ok(self)
+    }
+
+    /// Drops every anchor of `node`; returns how many.
+    pub fn remove(&mut self, node: NodeId) -> usize {
+        let before = self.anchors.len();
+        self.anchors.retain(|(n, _)| *n != node);
+        before - self.anchors.len()
+    }
+
+    /// Anchors of `node`, in the order applied.
+    pub fn anchors_of(&self, node: NodeId) -> impl Iterator<Item = &Anchor> + '_ {
+        self.anchors.iter().filter(move |(n, _)| *n == node).map(|(_, a)| a)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.anchors.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty()
+    }
+
+    /// Moves every anchored node to satisfy its anchors. Nothing moves when a node
+    /// is missing or the anchors are circular.
+    pub fn solve(&self, scene: &mut Scene, camera: &CameraPose) -> Result<(), AnchorError> {
+        for node in self.order(scene)? {
+            for anchor in self.anchors_of(node) {
+                let (world, size) = frame(scene, node)?;
+                let target = match *anchor {
+                    Anchor::Align { side, target, target_side, offset } => {
+                        let axis = side.axis();
+                        let (center, extent) = frame(scene, target)?;
+                        let plane = center[axis] + extent[axis] * target_side.fraction() + offset;
+                        let mut moved = world;
+                        moved[axis] = plane - size[axis] * side.fraction();
+                        moved
+                    }
+                    Anchor::Between { from, to, fraction } => {
+                        let from = frame(scene, from)?.0;
+                        from + (frame(scene, to)?.0 - from) * fraction
+                    }
+                    Anchor::FrontOfCamera { distance } => camera.position + camera.forward * distance,
+                };
+                if let Some(n) = scene.get_mut(node) {
+                    n.position += target - world;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Anchored nodes, each after every anchored node it depends on.
+    fn order(&self, scene: &Scene) -> Result<Vec<NodeId>, AnchorError> {
+        let mut nodes: Vec<NodeId> = Vec::new();
+        for &(node, _) in &self.anchors {
+            if !nodes.contains(&node) {
+                nodes.push(node);
+            }
+        }
+
+        // A node depends on the anchored nodes placing its targets or itself,
+        // which includes their anchored ancestors
+        let anchored = |id: NodeId| nodes.contains(&id);
+        let mut depends: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        for &(node, anchor) in &self.anchors {
+            let placed = std::iter::once(node).chain(anchor.targets());
+            for id in placed {
+                let mut current = scene.get(id).ok_or(AnchorError::InvalidNode(id))?;
+                let entry = depends.entry(node).or_default();
+                if id != node && anchored(id) {
+                    entry.push(id);
+                }
+                while let Some(parent) = current.parent() {
+                    if anchored(parent) {
+                        entry.push(parent);
+                    }
+                    current = scene.get(parent).ok_or(AnchorError::InvalidNode(parent))?;
+                }
+            }
+        }
+
+        // Depth-first topological sort; a node met again while open is a cycle
+        let mut order = Vec::with_capacity(nodes.len());
+        let mut state: HashMap<NodeId, bool> = HashMap::new();
+        for &node in &nodes {
+            visit(node, &depends, &mut state, &mut order)?;
+        }
+        Ok(order)
+    }
+}
+
+/// Appends `node` to `order` after its dependencies. `state` is false while a
+/// node's dependencies are being visited and true once it is placed.
+fn visit(
+    node: NodeId,
+    depends: &HashMap<NodeId, Vec<NodeId>>,
+    state: &mut HashMap<NodeId, bool>,
+    order: &mut Vec<NodeId>,
+) -> Result<(), AnchorError> {
+    match state.get(&node) {
+        Some(true) => return Ok(()),
+        Some(false) => return Err(AnchorError::Cycle(node)),
+        None => {}
+    }
+    state.insert(node, false);
+    for &dependency in depends.get(&node).into_iter().flatten() {
+        visit(dependency, depends, state, order)?;
+    }
+    state.insert(node, true);
+    order.push(node);
+    Ok(())
+}
+
+/// World center and size of `node`.
+fn frame(scene: &Scene, node: NodeId) -> Result<(Vec3, Vec3), AnchorError> {
+    let center = scene.world_position(node).ok_or(AnchorError::InvalidNode(node))?;
+    Ok((center, scene.get(node).map_or(Vec3::zero(), |n| n.size)))
+}
+
+#[cfg(test)]
+#[path = "tests/anchor_tests.rs"]
+mod tests;
@@ -0,0 +1,139 @@
+//! The tool avatar: how the haptic cursor is drawn.
+//!
+//! The device goes wherever the hand moves it, into surfaces included, but the
+//! god-object proxy stays outside them. Drawing the proxy makes surfaces look
+//! as solid as they feel; drawing only that hides how hard the user is
+//! pushing. A [`ToolAvatar`] therefore describes, each frame, the tool model
+//! ([`ToolModel`]: a sphere, a stylus or a hand) at the proxy and, once the
+//! device has sunk in past a threshold, a faded ghost of it at the device with
+//! a tether between the two. The [`AvatarFrame`] also carries the penetration
+//! depth and a 0 to 1 strain for tinting or squashing the model.
+
+use crate::core::{Meters, Meters3, Quat, Vec3};
+use crate::render::GodObject;
+
+/// Shape drawn for the tool.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToolModel {
+    /// Sphere the size of the proxy, centered on the tool point.
+    Sphere { radius: Meters },
+    /// Pen with its tip on the tool point, its body along the tool's +Z.
+    Stylus { length: Meters, radius: Meters },
+    /// Hand with the index fingertip on the tool point, `scale` times life size.
+    Hand { scale: f32 },
+}
+
+impl Default for ToolModel {
+    /// A 5 mm sphere.
+    fn default() -> Self {
+        ToolModel::Sphere { radius: Meters(0.005) }
+    }
+}
+
+/// Where a model is drawn: its tool point and orientation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AvatarPose {
+    pub position: Vec3,
+    pub orientation: Quat,
+}
+
+/// What to draw for the tool this frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AvatarFrame {
+    pub model: ToolModel,
+    /// The tool as felt: at the proxy.
+    pub tool: AvatarPose,
+    /// The device, while it is in deeper than the ghost threshold.
+    pub ghost: Option<AvatarPose>,
+    /// Opacity of the ghost, growing with depth up to the full-strain depth.
+    pub ghost_opacity: f32,
+    /// Line from the proxy to the device, shown with the ghost if enabled.
+    pub tether: Option<(Vec3, Vec3)>,
+    /// How far the device is in past the proxy.
+    pub depth: Meters,
+    /// Depth as a fraction of the full-strain depth, 0 to 1.
+    pub strain: f32,
+    /// Unit direction the surface pushes back along, while the device is in.
+    pub push: Option<Vec3>,
+}
+
+/// Draws the haptic tool from the proxy and device positions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolAvatar {
+    pub model: ToolModel,
+    /// Depth beyond which the device ghost is shown.
+    pub ghost_threshold: Meters,
+    /// Depth at which strain reaches 1 and the ghost is fully opaque.
+    pub full_strain_depth: Meters,
+    pub tether: bool,
+}
+
+impl ToolAvatar {
+    /// Ghost past 1 mm, full strain at 1 cm, with a tether.
+    pub fn new(model: ToolModel) -> Self {
+        Self { model, ghost_threshold: Meters(0.001), full_strain_depth: Meters(0.01), tether: true }
+    }
+
+    pub fn with_ghost_threshold(mut self, threshold: Meters) -> Self {
+        self.ghost_threshold = Meters(threshold.value().max(0.0));
+        self
+    }
+
+    pub fn with_full_strain_depth(mut self, depth: Meters) -> Self {
+        self.full_strain_depth = depth;
+        self
+    }
+
+    pub fn with_tether(mut self, tether: bool) -> Self {
+        self.tether = tether;
+        self
+    }
+
+    /// Frame for a proxy at `proxy` and the device at `device`, both turned
+    /// by the device's `orientation`.
+    pub fn frame(&self, proxy: Meters3, device: Meters3, orientation: Quat) -> AvatarFrame {
+        let offset = proxy.value() - device.value();
+        let depth = offset.length();
+        let strain = if self.full_strain_depth.value() > 0.0 {
+            (depth / self.full_strain_depth.value()).clamp(0.0, 1.0)
+        } else if depth > 0.0 {
+            1.0
+        } else {
+            0.0
+        };
+        let deep = depth > self.ghost_threshold.value();
+        let ghost_span = self.full_strain_depth.value() - self.ghost_threshold.value();
+        let ghost_opacity = if !deep {
+            0.0
+        } else if ghost_span > 0.0 {
+            ((depth - self.ghost_threshold.value()) / ghost_span).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        AvatarFrame {
+            model: self.model,
+            tool: AvatarPose { position: proxy.value(), orientation },
+            ghost: deep.then_some(AvatarPose { position: device.value(), orientation }),
+            ghost_opacity,
+            tether: (deep && self.tether).then_some((proxy.value(), device.value())),
+            depth: Meters(depth),
+            strain,
+            push: offset.try_normalize(),
+        }
+    }
+
+    /// Frame for `god_object`'s proxy and the device at `device`.
+    pub fn frame_for(&self, god_object: &GodObject, device: Meters3, orientation: Quat) -> AvatarFrame {
+        self.frame(god_object.proxy(), device, orientation)
+    }
+}
+
+impl Default for ToolAvatar {
+    fn default() -> Self {
+        Self::new(ToolModel::default())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/avatar_tests.rs"]
+mod tests;
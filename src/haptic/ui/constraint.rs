@@ -0,0 +1,187 @@
+//! Attachment constraints: widgets that follow moving objects.
+//!
+//! Anchors place widgets once per layout pass; constraints keep them attached
+//! to things that move every frame, like tracked hands or vehicles. A
+//! [`ConstraintSolver`] holds [`Constraint`]s on scene nodes and applies them
+//! after layout with [`ConstraintSolver::solve`], given the frame time. A node
+//! can be
+//!
+//! - kept within a range of distances from another node,
+//! - turned to face another node (its rotation, in [`AnimatedProps`]),
+//! - locked onto a plane,
+//! - made to follow another node at an offset, catching up with a lag.
+//!
+//! Constraints apply in the order added, so a node following another should
+//! be constrained after it; a later constraint on the same node wins.
+
+use std::fmt;
+
+use crate::anim::AnimatedProps;
+use crate::core::{Quat, Seconds, Vec3};
+use crate::scene::{NodeId, Scene};
+
+/// A constraint on the placement of one node, in world space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Constraint {
+    /// Keeps the node between `min` and `max` from `target`'s center.
+    Distance { target: NodeId, min: f32, max: f32 },
+    /// Turns the node's +Z toward `target`, with its +Y as close to `up` as possible.
+    LookAt { target: NodeId, up: Vec3 },
+    /// Keeps the node on the plane through `point` with unit `normal`.
+    PlaneLock { point: Vec3, normal: Vec3 },
+    /// Moves the node toward `target` plus `offset`, closing about 63% of the
+    /// gap every `lag`; no lag follows rigidly.
+    Follow { target: NodeId, offset: Vec3, lag: Seconds },
+}
+
+impl Constraint {
+    pub const fn distance(target: NodeId, min: f32, max: f32) -> Self {
+        Constraint::Distance { target, min, max }
+    }
+
+    /// Facing `target`, upright along +Y.
+    pub fn look_at(target: NodeId) -> Self {
+        Constraint::LookAt { target, up: Vec3::unit_y() }
+    }
+
+    pub fn plane_lock(point: Vec3, normal: Vec3) -> Self {
+        Constraint::PlaneLock { point, normal: normal.try_normalize().unwrap_or_else(Vec3::unit_z) }
+    }
+
+    pub const fn follow(target: NodeId, offset: Vec3, lag: Seconds) -> Self {
+        Constraint::Follow { target, offset, lag }
+    }
+
+    /// Node the constraint reads, if any.
+    fn target(&self) -> Option<NodeId> {
+        match *self {
+            Constraint::Distance { target, .. }
+            | Constraint::LookAt { target, .. }
+            | Constraint::Follow { target, .. } => Some(target),
+            Constraint::PlaneLock { .. } => None,
+        }
+    }
+}
+
+/// Errors from solving constraints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintError {
+    /// The node is not in the scene.
+    InvalidNode(NodeId),
+}
+
+impl fmt::Display for ConstraintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConstraintError::InvalidNode(id) => write!(f, "node {:?} is not in the scene", id),
+        }
+    }
+}
+
+impl std::error::Error for ConstraintError {}
+
+/// Constraints on scene nodes, solved every frame.
+#[derive(Debug, Clone, Default)]
+pub struct ConstraintSolver {
+    constraints: Vec<(NodeId, Constraint)>,
+}
+
+impl ConstraintSolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, node: NodeId, constraint: Constraint) {
+        self.constraints.push((node, constraint));
+    }
+
+    pub fn with(mut self, node: NodeId, constraint: Constraint) -> Self {
+        self.add(node, constraint);
+        self
+    }
+
+    /// Drops every constraint of `node`; returns how many.
+    pub fn remove(&mut self, node: NodeId) -> usize {
+        let before = self.constraints.len();
+        self.constraints.retain(|(n, _)| *n != node);
+        before - self.constraints.len()
+    }
+
+    /// Drops constraints on or toward nodes that left the scene; returns how many.
+    pub fn retain_in(&mut self, scene: &Scene) -> usize {
+        let before = self.constraints.len();
+        self.constraints.retain(|(n, c)| scene.contains(*n) && c.target().is_none_or(|t| scene.contains(t)));
+        before - self.constraints.len()
+    }
+
+    /// Constraints of `node`, in the order applied.
+    pub fn constraints_of(&self, node: NodeId) -> impl Iterator<Item = &Constraint> + '_ {
+        self.constraints.iter().filter(move |(n, _)| *n == node).map(|(_, c)| c)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.constraints.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.constraints.is_empty()
+    }
+
+    /// Applies every constraint for a frame lasting `dt`. Nothing moves when a
+    /// constrained or target node is missing.
+    pub fn solve(&self, scene: &mut Scene, props: &mut AnimatedProps, dt: Seconds) -> Result<(), ConstraintError> {
+        for (node, constraint) in &self.constraints {
+            for id in std::iter::once(*node).chain(constraint.target()) {
+                if !scene.contains(id) {
+                    return Err(ConstraintError::InvalidNode(id));
+                }
+            }
+        }
+        for &(node, constraint) in &self.constraints {
+            let here = world(scene, node)?;
+            let placed = match constraint {
+                Constraint::Distance { target, min, max } => {
+                    let center = world(scene, target)?;
+                    let away = here - center;
+                    let direction = away.try_normalize().unwrap_or_else(Vec3::unit_z);
+                    center + direction * away.length().clamp(min.min(max), max.max(min))
+                }
+                Constraint::LookAt { target, up } => {
+                    if let Some(rotation) = facing(world(scene, target)? - here, up) {
+                        props.get_mut(node).rotation = rotation;
+                    }
+                    here
+                }
+                Constraint::PlaneLock { point, normal } => here - normal * (here - point).dot(normal),
+                Constraint::Follow { target, offset, lag } => {
+                    let goal = world(scene, target)? + offset;
+                    let lag = lag.value();
+                    let blend = if lag > 0.0 { 1.0 - (-dt.value().max(0.0) / lag).exp() } else { 1.0 };
+                    here + (goal - here) * blend
+                }
+            };
+            if let Some(n) = scene.get_mut(node) {
+                n.position += placed - here;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// World position of `node`.
+fn world(scene: &Scene, node: NodeId) -> Result<Vec3, ConstraintError> {
+    scene.world_position(node).ok_or(ConstraintError::InvalidNode(node))
+}
+
+/// Rotation taking +Z along `z` with +Y as close to `up` as possible.
+fn facing(z: Vec3, up: Vec3) -> Option<Quat> {
+    let z = z.try_normalize()?;
+    let x = up.cross(z).try_normalize().or_else(|| Vec3::unit_x().reject_from(z).try_normalize())?;
+    Some(Quat::from_rotation_axes(x, z.cross(x), z))
+}
+
+#[cfg(test)]
+#[path = "tests/constraint_tests.rs"]
+mod tests;
@@ -0,0 +1,394 @@
+//! Drag and drop between widgets.
+//!
+//! Widgets take part through two traits: a [`DragSource`] hands out a
+//! [`DragPayload`] when a drag starts on it, and a [`DropTarget`] says which
+//! payloads it accepts and takes them when dropped. [`DragDrop`] runs the
+//! [`DragSession`] in between:
+//!
+//! - The dragged node follows the pointer and stops being pickable, so picking
+//!   finds the target underneath (rebuild the picker after starting a drag).
+//! - Hovering a target that accepts the payload plays an accept pulse, collected
+//!   with [`DragDrop::take_feedback`].
+//! - Dropping on an accepting target hands it the payload and leaves the node
+//!   where it was dropped. Any other drop, or a cancel, springs the node back to
+//!   where it started over the next [`DragDrop::animate`] calls; grabbing it on
+//!   the way back starts a new drag from its original home.
+//! - With a [`Snapper`], the dragged node snaps to the grid and to other nodes'
+//!   corners, edges and faces; snapped to a face, it rests on it.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+
+use super::event::PointerId;
+use super::snap::{Snap, Snapper};
+use crate::core::{Hertz, Seconds, Vec3};
+use crate::effects::Waveform;
+use crate::scene::{NodeFlags, NodeId, Scene};
+
+/// Distance from home at which a returning node snaps into place.
+const SETTLE_DISTANCE: f32 = 1e-4;
+
+// ============================================================================
+// Payloads and Roles
+// ============================================================================
+
+/// A value of any type carried by a drag.
+pub struct DragPayload {
+    value: Box<dyn Any + Send>,
+    type_name: &'static str,
+}
+
+impl DragPayload {
+    pub fn new<T: Any + Send>(value: T) -> Self {
+        Self { value: Box::new(value), type_name: std::any::type_name::<T>() }
+    }
+
+    /// Name of the carried type, for diagnostics.
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    #[inline]
+    pub fn is<T: Any>(&self) -> bool {
+        self.value.is::<T>()
+    }
+
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.value.downcast_ref()
+    }
+
+    /// The carried value, or the payload back if it holds another type.
+    pub fn take<T: Any>(self) -> Result<T, Self> {
+        let type_name = self.type_name;
+        self.value.downcast().map(|value| *value).map_err(|value| Self { value, type_name })
+    }
+}
+
+impl fmt::Debug for DragPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DragPayload").field("type_name", &self.type_name).finish()
+    }
+}
+
+/// How a drag ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropOutcome {
+    /// Taken by this target.
+    Dropped(NodeId),
+    /// Dropped nowhere that accepted it, or cancelled.
+    Cancelled,
+}
+
+/// A widget things can be dragged out of.
+pub trait DragSource: Send {
+    /// Payload for a drag starting on `node`; None refuses the drag.
+    fn payload(&mut self, node: NodeId) -> Option<DragPayload>;
+
+    /// Called once the drag from `node` has ended.
+    fn finished(&mut self, _node: NodeId, _outcome: DropOutcome) {}
+}
+
+/// A widget things can be dropped on.
+pub trait DropTarget: Send {
+    fn accepts(&self, payload: &DragPayload) -> bool;
+
+    /// Takes a payload `accepts` approved, dropped at `position`.
+    fn receive(&mut self, node: NodeId, payload: DragPayload, position: Vec3);
+}
+
+// ============================================================================
+// Session
+// ============================================================================
+
+/// A drag in progress.
+#[derive(Debug)]
+pub struct DragSession {
+    pub pointer: PointerId,
+    /// Source node being dragged.
+    pub source: NodeId,
+    payload: DragPayload,
+    target: Option<NodeId>,
+    accepted: bool,
+    /// Pointer position when the drag started.
+    grab: Vec3,
+    /// Local position of the source before the drag.
+    home: Vec3,
+    flags: NodeFlags,
+}
+
+impl DragSession {
+    #[inline]
+    pub fn payload(&self) -> &DragPayload {
+        &self.payload
+    }
+
+    /// Drop target under the pointer.
+    #[inline]
+    pub fn target(&self) -> Option<NodeId> {
+        self.target
+    }
+
+    /// Whether the target under the pointer accepts the payload.
+    #[inline]
+    pub fn is_accepted(&self) -> bool {
+        self.accepted
+    }
+
+    /// Local position the source springs back to.
+    #[inline]
+    pub fn home(&self) -> Vec3 {
+        self.home
+    }
+}
+
+/// A node springing back home after a cancelled drop.
+#[derive(Debug, Clone, Copy)]
+struct Return {
+    node: NodeId,
+    home: Vec3,
+    velocity: Vec3,
+}
+
+/// Drag sources, drop targets and the drag between them.
+pub struct DragDrop {
+    sources: HashMap<NodeId, Box<dyn DragSource>>,
+    targets: HashMap<NodeId, Box<dyn DropTarget>>,
+    session: Option<DragSession>,
+    returning: Vec<Return>,
+    feedback: Vec<Waveform>,
+    snapper: Option<Snapper>,
+    /// Time a cancelled drop takes to settle, roughly.
+    spring_time: Seconds,
+    accept_pulse: Waveform,
+}
+
+impl DragDrop {
+    /// Springs back in about 0.15 s; accepts with a short 150 Hz pulse.
+    pub fn new() -> Self {
+        Self {
+            sources: HashMap::new(),
+            targets: HashMap::new(),
+            session: None,
+            returning: Vec::new(),
+            feedback: Vec::new(),
+            snapper: None,
+            spring_time: Seconds(0.15),
+            accept_pulse: Waveform::sine(Hertz(150.0), Some(0.04)).with_intensity(0.6),
+        }
+    }
+
+    pub fn with_spring_time(mut self, spring_time: Seconds) -> Self {
+        self.spring_time = spring_time;
+        self
+    }
+
+    pub fn with_accept_pulse(mut self, pulse: Waveform) -> Self {
+        self.accept_pulse = pulse;
+        self
+    }
+
+    /// Snaps dragged nodes with `snapper`.
+    pub fn with_snapper(mut self, snapper: Snapper) -> Self {
+        self.snapper = Some(snapper);
+        self
+    }
+
+    /// The snapper, whose current snap gives the guide to draw.
+    #[inline]
+    pub fn snapper(&self) -> Option<&Snapper> {
+        self.snapper.as_ref()
+    }
+
+    pub fn add_source(&mut self, node: NodeId, source: impl DragSource + 'static) {
+        self.sources.insert(node, Box::new(source));
+    }
+
+    pub fn add_target(&mut self, node: NodeId, target: impl DropTarget + 'static) {
+        self.targets.insert(node, Box::new(target));
+    }
+
+    /// Stops `node` being a source or target; returns whether it was either.
+    pub fn remove(&mut self, node: NodeId) -> bool {
+        let source = self.sources.remove(&node).is_some();
+        self.targets.remove(&node).is_some() || source
+    }
+
+    #[inline]
+    pub fn session(&self) -> Option<&DragSession> {
+        self.session.as_ref()
+    }
+
+    #[inline]
+    pub fn is_dragging(&self) -> bool {
+        self.session.is_some()
+    }
+
+    /// Whether `node` is springing back home.
+    pub fn is_returning(&self, node: NodeId) -> bool {
+        self.returning.iter().any(|r| r.node == node)
+    }
+
+    /// Starts dragging the nearest source at or above `node`, grabbed by
+    /// `pointer` at `position`. False if a drag is running or no source gives a
+    /// payload.
+    pub fn begin(&mut self, scene: &mut Scene, pointer: PointerId, node: NodeId, position: Vec3) -> bool {
+        if self.session.is_some() {
+            return false;
+        }
+        let mut current = Some(node);
+        let (source, payload) = loop {
+            let Some(id) = current else { return false };
+            if let Some(payload) = self.sources.get_mut(&id).and_then(|s| s.payload(id)) {
+                break (id, payload);
+            }
+            current = scene.get(id).and_then(|n| n.parent());
+        };
+        let Some(n) = scene.get_mut(source) else { return false };
+
+        // Caught on its way back: home is still where it came from
+        let home = match self.returning.iter().position(|r| r.node == source) {
+            Some(index) => self.returning.swap_remove(index).home,
+            None => n.position,
+        };
+        let flags = n.flags;
+        n.flags.set(NodeFlags::PICKABLE, false);
+        let grab = position - (n.position - home);
+        self.session = Some(DragSession { pointer, source, payload, target: None, accepted: false, grab, home, flags });
+        true
+    }
+
+    /// Moves the dragged node with the pointer, now at `position` over `hit`.
+    /// Returns the drop target under the pointer, accepting or not.
+    pub fn update(&mut self, scene: &mut Scene, position: Vec3, hit: Option<NodeId>) -> Option<NodeId> {
+        let session = self.session.as_mut()?;
+        let mut local = session.home + (position - session.grab);
+        if let (Some(snapper), Some(world), Some(n)) =
+            (self.snapper.as_mut(), scene.world_position(session.source), scene.get(session.source))
+        {
+            let parent = world - n.position;
+            let half = n.size.abs() * 0.5;
+            let mut snapped = snapper.snap(scene, parent + local, &[session.source]);
+            if let Some(normal) = snapper.current().and_then(Snap::normal) {
+                snapped += normal * half.dot(normal.abs());
+            }
+            local = snapped - parent;
+        }
+        if let Some(n) = scene.get_mut(session.source) {
+            n.position = local;
+        }
+
+        let mut current = hit;
+        let target = loop {
+            let Some(id) = current else { break None };
+            if id == session.source {
+                break None;
+            }
+            if self.targets.contains_key(&id) {
+                break Some(id);
+            }
+            current = scene.get(id).and_then(|n| n.parent());
+        };
+        if target != session.target {
+            let accepted = target.and_then(|id| self.targets.get(&id)).is_some_and(|t| t.accepts(&session.payload));
+            if accepted {
+                self.feedback.push(self.accept_pulse);
+            }
+            session.target = target;
+            session.accepted = accepted;
+        }
+        target
+    }
+
+    /// Drops the payload on the accepting target under the pointer, or cancels
+    /// the drag when there is none.
+    pub fn end(&mut self, scene: &mut Scene) -> Option<DropOutcome> {
+        let session = self.session.take()?;
+        if let Some(snapper) = self.snapper.as_mut() {
+            snapper.release();
+        }
+        let target = session.target.filter(|_| session.accepted);
+        match target.and_then(|id| self.targets.get_mut(&id).map(|t| (id, t))) {
+            Some((id, target)) => {
+                let position = scene.world_position(session.source).unwrap_or_default();
+                if let Some(n) = scene.get_mut(session.source) {
+                    n.flags = session.flags;
+                }
+                target.receive(id, session.payload, position);
+                let outcome = DropOutcome::Dropped(id);
+                if let Some(source) = self.sources.get_mut(&session.source) {
+                    source.finished(session.source, outcome);
+                }
+                Some(outcome)
+            }
+            None => Some(self.spring_back(scene, session)),
+        }
+    }
+
+    /// Abandons the drag, springing the node back home.
+    pub fn cancel(&mut self, scene: &mut Scene) -> Option<DropOutcome> {
+        let session = self.session.take()?;
+        if let Some(snapper) = self.snapper.as_mut() {
+            snapper.release();
+        }
+        Some(self.spring_back(scene, session))
+    }
+
+    /// Moves returning nodes toward home; returns whether any are still moving.
+    pub fn animate(&mut self, scene: &mut Scene, dt: Seconds) -> bool {
+        let smooth_time = self.spring_time.value().max(f32::EPSILON);
+        self.returning.retain_mut(|r| {
+            let Some(n) = scene.get_mut(r.node) else { return false };
+            n.position = Vec3::smooth_damp(n.position, r.home, &mut r.velocity, smooth_time, dt.value());
+            if n.position.distance_to(r.home) > SETTLE_DISTANCE {
+                return true;
+            }
+            n.position = r.home;
+            false
+        });
+        !self.returning.is_empty()
+    }
+
+    /// Accept pulses and snap clicks played since the last call.
+    pub fn take_feedback(&mut self) -> Vec<Waveform> {
+        let mut feedback = std::mem::take(&mut self.feedback);
+        if let Some(snapper) = self.snapper.as_mut() {
+            feedback.extend(snapper.take_feedback());
+        }
+        feedback
+    }
+
+    fn spring_back(&mut self, scene: &mut Scene, session: DragSession) -> DropOutcome {
+        if let Some(n) = scene.get_mut(session.source) {
+            n.flags = session.flags;
+            self.returning.push(Return { node: session.source, home: session.home, velocity: Vec3::zero() });
+        }
+        if let Some(source) = self.sources.get_mut(&session.source) {
+            source.finished(session.source, DropOutcome::Cancelled);
+        }
+        DropOutcome::Cancelled
+    }
+}
+
+impl Default for DragDrop {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for DragDrop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DragDrop")
+            .field("sources", &self.sources.len())
+            .field("targets", &self.targets.len())
+            .field("session", &self.session)
+            .field("returning", &self.returning)
+            .field("snapper", &self.snapper)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/drag_tests.rs"]
+mod tests;
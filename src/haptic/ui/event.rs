@@ -0,0 +1,273 @@
+//! Pointer events: enter, exit, press, release, move and click.
+//!
+//! An [`EventDispatcher`] turns raw pointer samples (where a pointer is, which
+//! node it is over, which buttons are down) into typed [`PointerEvent`]s and
+//! delivers them to handlers registered on scene nodes:
+//!
+//! - Enter and exit go to the node the pointer starts or stops hovering only.
+//! - Press, release, move and click bubble from the target node up through its
+//!   ancestors until a handler returns [`Propagation::Stop`]; the other handlers
+//!   of the node that stopped still run.
+//! - Pressing captures the pointer for the pressed node: until every button is
+//!   up again, moves and the release go to that node even if the pointer has
+//!   left it, so a drag that overshoots a slider keeps dragging it. A release
+//!   over the pressed node also clicks it.
+//!
+//! Several pointers (two hands, a stylus and a mouse) are tracked separately.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::core::Vec3;
+use crate::scene::{NodeId, Scene};
+
+/// Identifies an input source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PointerId(pub u32);
+
+/// What happened to a pointer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerEventKind {
+    Enter,
+    Exit,
+    /// Button `button` (a bit index) went down.
+    Press { button: u32 },
+    /// Button `button` went up.
+    Release { button: u32 },
+    Move,
+    /// Button `button` went down and up on the same node.
+    Click { button: u32 },
+}
+
+impl PointerEventKind {
+    /// Whether the event travels up to the target's ancestors.
+    #[inline]
+    pub fn bubbles(self) -> bool {
+        !matches!(self, PointerEventKind::Enter | PointerEventKind::Exit)
+    }
+}
+
+/// An event as delivered to a handler.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointerEvent {
+    pub kind: PointerEventKind,
+    pub pointer: PointerId,
+    pub position: Vec3,
+    /// Buttons down after the event, one bit each.
+    pub buttons: u32,
+    /// Node the event was dispatched to.
+    pub target: NodeId,
+    /// Node whose handler is running; an ancestor of `target` while bubbling.
+    pub current: NodeId,
+    pub time_us: u64,
+}
+
+/// Whether an event goes on to the next ancestor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Propagation {
+    Continue,
+    Stop,
+}
+
+/// One reading of a pointer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointerSample {
+    pub pointer: PointerId,
+    pub position: Vec3,
+    /// Node under the pointer, as found by picking.
+    pub hit: Option<NodeId>,
+    /// Buttons down, one bit each.
+    pub buttons: u32,
+    pub time_us: u64,
+}
+
+impl PointerSample {
+    pub fn new(pointer: PointerId, position: Vec3, hit: Option<NodeId>, buttons: u32, time_us: u64) -> Self {
+        Self { pointer, position, hit, buttons, time_us }
+    }
+}
+
+/// Handler for events reaching a node.
+pub type PointerHandler = Box<dyn FnMut(&PointerEvent) -> Propagation + Send>;
+
+/// What the dispatcher remembers about a pointer between samples.
+#[derive(Debug, Clone, Copy, Default)]
+struct PointerTrack {
+    position: Option<Vec3>,
+    hovered: Option<NodeId>,
+    buttons: u32,
+    capture: Option<NodeId>,
+    /// Node each button went down on.
+    pressed: [Option<NodeId>; 32],
+}
+
+/// Routes pointer samples to node handlers as events.
+#[derive(Default)]
+pub struct EventDispatcher {
+    handlers: HashMap<NodeId, Vec<PointerHandler>>,
+    pointers: HashMap<PointerId, PointerTrack>,
+}
+
+impl EventDispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a handler to `node`; handlers of a node run in the order added.
+    pub fn add_handler(
+        &mut self,
+        node: NodeId,
+        handler: impl FnMut(&PointerEvent) -> Propagation + Send + 'static,
+    ) {
+        self.handlers.entry(node).or_default().push(Box::new(handler));
+    }
+
+    /// Drops every handler of `node`; returns how many.
+    pub fn remove_handlers(&mut self, node: NodeId) -> usize {
+        self.handlers.remove(&node).map_or(0, |h| h.len())
+    }
+
+    /// Node the pointer is over, as of the last sample.
+    pub fn hovered(&self, pointer: PointerId) -> Option<NodeId> {
+        self.pointers.get(&pointer).and_then(|p| p.hovered)
+    }
+
+    /// Node holding the pointer's capture.
+    pub fn captured(&self, pointer: PointerId) -> Option<NodeId> {
+        self.pointers.get(&pointer).and_then(|p| p.capture)
+    }
+
+    /// Sends the pointer's moves and releases to `node` until every button is up
+    /// or the capture is released.
+    pub fn capture(&mut self, pointer: PointerId, node: NodeId) {
+        self.pointers.entry(pointer).or_default().capture = Some(node);
+    }
+
+    pub fn release_capture(&mut self, pointer: PointerId) {
+        if let Some(track) = self.pointers.get_mut(&pointer) {
+            track.capture = None;
+        }
+    }
+
+    /// Forgets a pointer that went away, exiting the node it hovered.
+    pub fn remove_pointer(&mut self, scene: &Scene, pointer: PointerId, time_us: u64) -> Vec<PointerEvent> {
+        let mut events = Vec::new();
+        if let Some(track) = self.pointers.remove(&pointer) {
+            if let Some(node) = track.hovered.filter(|&node| scene.contains(node)) {
+                let event = PointerEvent {
+                    kind: PointerEventKind::Exit,
+                    pointer,
+                    position: track.position.unwrap_or_default(),
+                    buttons: 0,
+                    target: node,
+                    current: node,
+                    time_us,
+                };
+                self.deliver(scene, event, &mut events);
+            }
+        }
+        events
+    }
+
+    /// Dispatches the events following from `sample`; returns them in the order
+    /// delivered, as sent to their targets.
+    pub fn dispatch(&mut self, scene: &Scene, sample: PointerSample) -> Vec<PointerEvent> {
+        self.handlers.retain(|node, _| scene.contains(*node));
+        let mut events = Vec::new();
+        let mut track = self.pointers.get(&sample.pointer).copied().unwrap_or_default();
+        let hit = sample.hit.filter(|&node| scene.contains(node));
+        if track.capture.is_some_and(|node| !scene.contains(node)) {
+            track.capture = None;
+        }
+        let event = |kind, buttons, target| PointerEvent {
+            kind,
+            pointer: sample.pointer,
+            position: sample.position,
+            buttons,
+            target,
+            current: target,
+            time_us: sample.time_us,
+        };
+
+        // Hover follows the pointer even while captured
+        if track.hovered != hit {
+            if let Some(node) = track.hovered.filter(|&node| scene.contains(node)) {
+                self.deliver(scene, event(PointerEventKind::Exit, track.buttons, node), &mut events);
+            }
+            if let Some(node) = hit {
+                self.deliver(scene, event(PointerEventKind::Enter, track.buttons, node), &mut events);
+            }
+            track.hovered = hit;
+        }
+
+        if track.position.is_some_and(|p| p != sample.position) {
+            if let Some(node) = track.capture.or(hit) {
+                self.deliver(scene, event(PointerEventKind::Move, track.buttons, node), &mut events);
+            }
+        }
+        track.position = Some(sample.position);
+
+        let mut buttons = track.buttons;
+        for button in 0..32 {
+            let bit = 1 << button;
+            let down = sample.buttons & bit != 0;
+            if down == (buttons & bit != 0) {
+                continue;
+            }
+            buttons ^= bit;
+            if down {
+                track.pressed[button as usize] = hit;
+                if let Some(node) = hit {
+                    track.capture = track.capture.or(Some(node));
+                    self.deliver(scene, event(PointerEventKind::Press { button }, buttons, node), &mut events);
+                }
+            } else {
+                let pressed = track.pressed[button as usize].take();
+                if let Some(node) = track.capture.or(hit) {
+                    self.deliver(scene, event(PointerEventKind::Release { button }, buttons, node), &mut events);
+                }
+                if let Some(node) = pressed.filter(|&node| hit == Some(node)) {
+                    self.deliver(scene, event(PointerEventKind::Click { button }, buttons, node), &mut events);
+                }
+                if buttons == 0 {
+                    track.capture = None;
+                }
+            }
+        }
+        track.buttons = buttons;
+        self.pointers.insert(sample.pointer, track);
+        events
+    }
+
+    /// Runs the handlers of the target and, for bubbling events, its ancestors.
+    fn deliver(&mut self, scene: &Scene, event: PointerEvent, delivered: &mut Vec<PointerEvent>) {
+        delivered.push(event);
+        let mut current = Some(event.target);
+        while let Some(node) = current {
+            let mut stop = false;
+            if let Some(handlers) = self.handlers.get_mut(&node) {
+                let event = PointerEvent { current: node, ..event };
+                for handler in handlers.iter_mut() {
+                    stop |= handler(&event) == Propagation::Stop;
+                }
+            }
+            if stop || !event.kind.bubbles() {
+                break;
+            }
+            current = scene.get(node).and_then(|n| n.parent());
+        }
+    }
+}
+
+impl fmt::Debug for EventDispatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventDispatcher")
+            .field("handlers", &self.handlers.values().map(Vec::len).sum::<usize>())
+            .field("pointers", &self.pointers.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/event_tests.rs"]
+mod tests;
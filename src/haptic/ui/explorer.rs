@@ -0,0 +1,341 @@
+//! Touch-only exploration of a scene, the haptic counterpart of a screen reader.
+//!
+//! While exploring, the cursor slides freely across the UI plane. Every widget
+//! boundary is felt as a ridge (a lateral bump force that resists crossing it),
+//! each widget kind has its own vibrotactile texture plus a short tacton played on
+//! entry, and the label of the widget under the cursor is handed to a text-to-speech
+//! hook. A UI that can be operated this way is usable without looking at it.
+
+use crate::core::Vec3;
+use crate::effects::HapticSample;
+use crate::geometry::Aabb;
+use crate::scene::{NodeFlags, NodeId, NodeKind, Scene};
+
+// ============================================================================
+// Roles and Style
+// ============================================================================
+
+/// Kind of widget as announced to the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WidgetRole {
+    Panel,
+    Button,
+    Label,
+}
+
+impl WidgetRole {
+    /// Role of a node kind; groups are not explorable.
+    pub fn of(kind: &NodeKind) -> Option<Self> {
+        match kind {
+            NodeKind::Group => None,
+            NodeKind::Panel => Some(WidgetRole::Panel),
+            NodeKind::Button { .. } => Some(WidgetRole::Button),
+            NodeKind::Label { .. } => Some(WidgetRole::Label),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WidgetRole::Panel => "panel",
+            WidgetRole::Button => "button",
+            WidgetRole::Label => "label",
+        }
+    }
+}
+
+/// Texture and entry tacton identifying a role by touch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoleTexture {
+    /// Spatial period of the grating felt while sliding across the widget (meters).
+    pub pitch: f32,
+    /// Vibration at the grating peaks.
+    pub texture: HapticSample,
+    /// Pulse played when the cursor enters the widget.
+    pub tacton: HapticSample,
+    /// Length of the entry pulse in seconds.
+    pub tacton_duration: f32,
+}
+
+impl RoleTexture {
+    /// Vibration at `p`: a grating along X and Y with period `pitch`.
+    pub fn sample(&self, p: Vec3) -> HapticSample {
+        if self.pitch <= 0.0 {
+            return self.texture;
+        }
+        let phase = std::f32::consts::TAU / self.pitch;
+        let grating = 0.25 * (2.0 + (p.x * phase).sin() + (p.y * phase).sin());
+        HapticSample::new(self.texture.intensity * grating, self.texture.sharpness)
+    }
+}
+
+/// Ridge and texture parameters of exploration mode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExplorerStyle {
+    /// Width of the band around each boundary in which the ridge is felt (meters).
+    pub ridge_width: f32,
+    /// Peak lateral force of a ridge (newtons).
+    pub ridge_force: f32,
+    pub panel: RoleTexture,
+    pub button: RoleTexture,
+    pub label: RoleTexture,
+}
+
+impl Default for ExplorerStyle {
+    fn default() -> Self {
+        Self {
+            ridge_width: 0.004,
+            ridge_force: 1.0,
+            // Smooth and faint: the background everything else sits on.
+            panel: RoleTexture {
+                pitch: 0.0,
+                texture: HapticSample::new(0.05, 0.1),
+                tacton: HapticSample::new(0.3, 0.2),
+                tacton_duration: 0.03,
+            },
+            // Coarse and sharp, with a crisp click on entry.
+            button: RoleTexture {
+                pitch: 0.004,
+                texture: HapticSample::new(0.4, 0.8),
+                tacton: HapticSample::new(1.0, 1.0),
+                tacton_duration: 0.02,
+            },
+            // Fine and soft, like running a finger over print.
+            label: RoleTexture {
+                pitch: 0.0015,
+                texture: HapticSample::new(0.25, 0.3),
+                tacton: HapticSample::new(0.6, 0.4),
+                tacton_duration: 0.05,
+            },
+        }
+    }
+}
+
+impl ExplorerStyle {
+    pub fn texture(&self, role: WidgetRole) -> &RoleTexture {
+        match role {
+            WidgetRole::Panel => &self.panel,
+            WidgetRole::Button => &self.button,
+            WidgetRole::Label => &self.label,
+        }
+    }
+}
+
+// ============================================================================
+// Explorer
+// ============================================================================
+
+/// Receives text to speak, usually forwarded to the platform TTS engine.
+pub type SpeechHook = Box<dyn FnMut(&str) + Send>;
+
+/// A widget as seen by the explorer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExploredWidget {
+    pub node: NodeId,
+    pub role: WidgetRole,
+    /// Scene-space box of the widget.
+    pub bounds: Aabb,
+    /// Text spoken when the cursor enters it.
+    pub description: String,
+}
+
+/// Force, vibration and focus for one update.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExplorerFrame {
+    /// Lateral ridge force in newtons, in the UI plane.
+    pub force: Vec3,
+    pub vibration: HapticSample,
+    /// Widget under the cursor.
+    pub focus: Option<NodeId>,
+}
+
+impl ExplorerFrame {
+    pub const IDLE: Self = Self { force: Vec3::zero(), vibration: HapticSample::SILENT, focus: None };
+}
+
+/// Exploration mode over the visible widgets of a scene.
+pub struct Explorer {
+    pub style: ExplorerStyle,
+    widgets: Vec<ExploredWidget>,
+    active: bool,
+    focus: Option<usize>,
+    since_entry: f32,
+    speech: Option<SpeechHook>,
+}
+
+impl Explorer {
+    pub fn from_scene(scene: &Scene) -> Self {
+        let mut explorer = Self {
+            style: ExplorerStyle::default(),
+            widgets: Vec::new(),
+            active: false,
+            focus: None,
+            since_entry: 0.0,
+            speech: None,
+        };
+        explorer.rebuild(scene);
+        explorer
+    }
+
+    /// Sends widget descriptions to `speak` as the cursor enters them.
+    pub fn with_speech(mut self, speak: impl FnMut(&str) + Send + 'static) -> Self {
+        self.speech = Some(Box::new(speak));
+        self
+    }
+
+    pub fn with_style(mut self, style: ExplorerStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Re-reads widgets after the scene changed. Hidden widgets are skipped.
+    pub fn rebuild(&mut self, scene: &Scene) {
+        let focused = self.focused();
+        self.widgets = scene
+            .iter()
+            .filter(|&(id, _)| scene.is_effectively(id, NodeFlags::VISIBLE))
+            .filter_map(|(id, node)| {
+                let role = WidgetRole::of(&node.kind)?;
+                let center = scene.world_position(id)?;
+                Some(ExploredWidget {
+                    node: id,
+                    role,
+                    bounds: Aabb::from_center_half_extents(center, node.size.abs() * 0.5),
+                    description: describe(role, &node.kind, &node.name),
+                })
+            })
+            .collect();
+        self.focus = focused.and_then(|id| self.widgets.iter().position(|w| w.node == id));
+    }
+
+    #[inline]
+    pub fn widgets(&self) -> &[ExploredWidget] {
+        &self.widgets
+    }
+
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn activate(&mut self) {
+        self.active = true;
+    }
+
+    /// Leaves exploration mode and forgets the focus, so re-entering announces again.
+    pub fn deactivate(&mut self) {
+        self.active = false;
+        self.focus = None;
+    }
+
+    pub fn focused(&self) -> Option<NodeId> {
+        self.focus.map(|i| self.widgets[i].node)
+    }
+
+    /// Innermost widget whose footprint in the UI (XY) plane contains `p`.
+    pub fn widget_at(&self, p: Vec3) -> Option<&ExploredWidget> {
+        self.index_at(p).map(|i| &self.widgets[i])
+    }
+
+    /// Speaks the focused widget again.
+    pub fn repeat(&mut self) {
+        if let (Some(i), Some(speak)) = (self.focus, &mut self.speech) {
+            speak(&self.widgets[i].description);
+        }
+    }
+
+    /// Advances by `dt` seconds with the cursor at `cursor`.
+    pub fn update(&mut self, cursor: Vec3, dt: f32) -> ExplorerFrame {
+        if !self.active {
+            return ExplorerFrame::IDLE;
+        }
+        let under = self.index_at(cursor);
+        if under != self.focus {
+            self.focus = under;
+            self.since_entry = 0.0;
+            self.repeat();
+        } else {
+            self.since_entry += dt;
+        }
+
+        let vibration = self.focus.map_or(HapticSample::SILENT, |i| {
+            let texture = self.style.texture(self.widgets[i].role);
+            if self.since_entry < texture.tacton_duration {
+                texture.tacton
+            } else {
+                texture.sample(cursor)
+            }
+        });
+        ExplorerFrame { force: self.ridge_force(cursor), vibration, focus: self.focused() }
+    }
+
+    fn index_at(&self, p: Vec3) -> Option<usize> {
+        self.widgets
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| footprint_contains(&w.bounds, p, 0.0))
+            .min_by(|(_, a), (_, b)| footprint_area(&a.bounds).total_cmp(&footprint_area(&b.bounds)))
+            .map(|(i, _)| i)
+    }
+
+    /// Virtual bump over the nearest boundary: zero at the crest and at the band
+    /// edge, pushing away from the crest in between.
+    fn ridge_force(&self, p: Vec3) -> Vec3 {
+        let width = self.style.ridge_width;
+        if width <= 0.0 {
+            return Vec3::zero();
+        }
+        let nearest = self
+            .widgets
+            .iter()
+            .filter(|w| footprint_contains(&w.bounds, p, width))
+            .filter_map(|w| nearest_edge(&w.bounds, p))
+            .filter(|(d, _)| d.abs() < width)
+            .min_by(|(a, _), (b, _)| a.abs().total_cmp(&b.abs()));
+        match nearest {
+            Some((d, normal)) => normal * (self.style.ridge_force * (std::f32::consts::PI * d / width).sin()),
+            None => Vec3::zero(),
+        }
+    }
+}
+
+/// Spoken text for a widget: role followed by its caption, text or name.
+fn describe(role: WidgetRole, kind: &NodeKind, name: &str) -> String {
+    let text = match kind {
+        NodeKind::Button { label } => label.as_str(),
+        NodeKind::Label { text } => text.as_str(),
+        _ => name,
+    };
+    if text.is_empty() {
+        role.name().to_string()
+    } else {
+        format!("{} {}", role.name(), text)
+    }
+}
+
+fn footprint_contains(bounds: &Aabb, p: Vec3, margin: f32) -> bool {
+    p.x >= bounds.min.x - margin
+        && p.x <= bounds.max.x + margin
+        && p.y >= bounds.min.y - margin
+        && p.y <= bounds.max.y + margin
+}
+
+fn footprint_area(bounds: &Aabb) -> f32 {
+    (bounds.max.x - bounds.min.x) * (bounds.max.y - bounds.min.y)
+}
+
+/// Signed distance to the nearest footprint edge (positive inside) and that edge's
+/// inward normal.
+fn nearest_edge(bounds: &Aabb, p: Vec3) -> Option<(f32, Vec3)> {
+    [
+        (p.x - bounds.min.x, Vec3::new(1.0, 0.0, 0.0)),
+        (bounds.max.x - p.x, Vec3::new(-1.0, 0.0, 0.0)),
+        (p.y - bounds.min.y, Vec3::new(0.0, 1.0, 0.0)),
+        (bounds.max.y - p.y, Vec3::new(0.0, -1.0, 0.0)),
+    ]
+    .into_iter()
+    .min_by(|(a, _), (b, _)| a.abs().total_cmp(&b.abs()))
+}
+
+#[cfg(test)]
+#[path = "tests/explorer_tests.rs"]
+mod tests;
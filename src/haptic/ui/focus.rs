@@ -0,0 +1,305 @@
+//! Keyboard and thumbstick focus.
+//!
+//! A [`FocusManager`] keeps one focused widget among the nodes registered as
+//! focusable, so the UI works without precise pointing:
+//!
+//! - Next and previous step through a tab order read off the layout: rows from
+//!   top to bottom, left to right within a row, then back to front. A node joins
+//!   a row when its center lies within the height of the row's first node.
+//! - Directional keys move to the nearest widget whose center lies within a cone
+//!   around the direction (45° either side by default). Directions are in world
+//!   space; [`FocusManager::navigate`] takes any direction, e.g. a thumbstick
+//!   turned into camera right and up.
+//! - Activating presses the focused widget through the [`EventDispatcher`] as the
+//!   reserved [`FOCUS_POINTER`], so widgets handle it like any other press.
+//! - [`FocusManager::ring`] gives the outline to draw around the focused widget.
+//!
+//! Hidden or unpickable widgets, and removed nodes, are skipped. Focus can be
+//! trapped inside a subtree, such as an open dialog, with
+//! [`FocusManager::set_trap`]; widgets outside it are skipped too.
+
+use super::event::{EventDispatcher, PointerEvent, PointerId, PointerSample};
+use crate::core::{Deg, Rad, Vec3};
+use crate::scene::{NodeFlags, NodeId, Scene};
+
+/// Pointer that focus activation presses with.
+pub const FOCUS_POINTER: PointerId = PointerId(u32::MAX);
+
+/// A navigation key, from a keyboard, gamepad or thumbstick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusKey {
+    Next,
+    Previous,
+    Left,
+    Right,
+    Up,
+    Down,
+    /// Away from the viewer, along -Z.
+    Forward,
+    /// Toward the viewer, along +Z.
+    Back,
+    /// Presses the focused widget while held.
+    Activate,
+}
+
+impl FocusKey {
+    /// World direction of a directional key.
+    pub fn direction(self) -> Option<Vec3> {
+        match self {
+            FocusKey::Left => Some(-Vec3::unit_x()),
+            FocusKey::Right => Some(Vec3::unit_x()),
+            FocusKey::Up => Some(Vec3::unit_y()),
+            FocusKey::Down => Some(-Vec3::unit_y()),
+            FocusKey::Forward => Some(-Vec3::unit_z()),
+            FocusKey::Back => Some(Vec3::unit_z()),
+            FocusKey::Next | FocusKey::Previous | FocusKey::Activate => None,
+        }
+    }
+}
+
+/// Outline drawn around the focused widget.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FocusRing {
+    pub node: NodeId,
+    pub center: Vec3,
+    /// Size of the widget plus the padding on each side.
+    pub size: Vec3,
+}
+
+impl FocusRing {
+    /// Bottom left, bottom right, top right and top left of the front face.
+    pub fn corners(&self) -> [Vec3; 4] {
+        let half = self.size * 0.5;
+        let front = self.center.z + half.z;
+        [
+            Vec3::new(self.center.x - half.x, self.center.y - half.y, front),
+            Vec3::new(self.center.x + half.x, self.center.y - half.y, front),
+            Vec3::new(self.center.x + half.x, self.center.y + half.y, front),
+            Vec3::new(self.center.x - half.x, self.center.y + half.y, front),
+        ]
+    }
+}
+
+/// Tracks which focusable widget has focus.
+#[derive(Debug, Clone)]
+pub struct FocusManager {
+    focusable: Vec<NodeId>,
+    focused: Option<NodeId>,
+    /// Subtree focus is kept inside.
+    trap: Option<NodeId>,
+    /// Half angle of the cone directional navigation searches.
+    cone: Rad,
+    /// Gap between a widget and its focus ring.
+    ring_padding: f32,
+}
+
+impl FocusManager {
+    /// No focusable widgets, a 45° search cone and a 2 mm focus ring gap.
+    pub fn new() -> Self {
+        Self { focusable: Vec::new(), focused: None, trap: None, cone: Deg(45.0).into(), ring_padding: 0.002 }
+    }
+
+    pub fn with_cone(mut self, half_angle: impl Into<Rad>) -> Self {
+        self.cone = half_angle.into();
+        self
+    }
+
+    pub fn with_ring_padding(mut self, padding: f32) -> Self {
+        self.ring_padding = padding;
+        self
+    }
+
+    /// Makes `node` focusable.
+    pub fn add(&mut self, node: NodeId) {
+        if !self.focusable.contains(&node) {
+            self.focusable.push(node);
+        }
+    }
+
+    /// Stops `node` being focusable, dropping focus if it had it.
+    pub fn remove(&mut self, node: NodeId) -> bool {
+        if self.focused == Some(node) {
+            self.focused = None;
+        }
+        let before = self.focusable.len();
+        self.focusable.retain(|&n| n != node);
+        self.focusable.len() != before
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.focusable.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.focusable.is_empty()
+    }
+
+    /// Focused widget, if it can still take focus.
+    pub fn focused(&self, scene: &Scene) -> Option<NodeId> {
+        self.focused.filter(|&node| self.can_focus(scene, node))
+    }
+
+    /// Focuses `node`; false if it is not focusable.
+    pub fn focus(&mut self, scene: &Scene, node: NodeId) -> bool {
+        if self.focusable.contains(&node) && self.can_focus(scene, node) {
+            self.focused = Some(node);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn blur(&mut self) {
+        self.focused = None;
+    }
+
+    /// Keeps focus on `root` and its descendants, or anywhere with `None`.
+    pub fn set_trap(&mut self, root: Option<NodeId>) {
+        self.trap = root;
+    }
+
+    /// Subtree focus is trapped in.
+    #[inline]
+    pub fn trap(&self) -> Option<NodeId> {
+        self.trap
+    }
+
+    /// Focusable widgets in reading order.
+    pub fn tab_order(&self, scene: &Scene) -> Vec<NodeId> {
+        let mut nodes: Vec<(NodeId, Vec3, f32)> = self
+            .focusable
+            .iter()
+            .filter(|&&node| self.can_focus(scene, node))
+            .filter_map(|&node| Some((node, scene.world_position(node)?, scene.get(node)?.size.y.abs())))
+            .collect();
+        nodes.sort_by(|a, b| b.1.y.total_cmp(&a.1.y));
+
+        let mut order = Vec::with_capacity(nodes.len());
+        let mut rest = nodes.as_slice();
+        while let Some(&(_, first, height)) = rest.first() {
+            let len = rest.iter().take_while(|(_, p, _)| first.y - p.y <= height * 0.5).count().max(1);
+            let mut row = rest[..len].to_vec();
+            row.sort_by(|a, b| a.1.x.total_cmp(&b.1.x).then(a.1.z.total_cmp(&b.1.z)));
+            order.extend(row.into_iter().map(|(node, _, _)| node));
+            rest = &rest[len..];
+        }
+        order
+    }
+
+    /// Focuses the next widget in tab order, wrapping around; the first one if
+    /// nothing had focus.
+    pub fn next(&mut self, scene: &Scene) -> Option<NodeId> {
+        self.step(scene, 1)
+    }
+
+    /// Focuses the previous widget in tab order, wrapping around; the last one if
+    /// nothing had focus.
+    pub fn previous(&mut self, scene: &Scene) -> Option<NodeId> {
+        self.step(scene, -1)
+    }
+
+    /// Focuses the nearest widget within the cone around `direction` from the
+    /// focused one. Focus stays put when there is none; with nothing focused the
+    /// first widget in tab order takes it.
+    pub fn navigate(&mut self, scene: &Scene, direction: Vec3) -> Option<NodeId> {
+        let Some(current) = self.focused(scene) else {
+            return self.next(scene);
+        };
+        let (Some(origin), Some(direction)) = (scene.world_position(current), direction.try_normalize()) else {
+            return Some(current);
+        };
+        let cos = self.cone.0.cos();
+        let target = self
+            .focusable
+            .iter()
+            .filter(|&&node| node != current && self.can_focus(scene, node))
+            .filter_map(|&node| {
+                let offset = scene.world_position(node)? - origin;
+                let distance = offset.length();
+                (distance > 0.0 && offset.dot(direction) >= distance * cos).then_some((node, distance))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(node, _)| node);
+        if let Some(node) = target {
+            self.focused = Some(node);
+        }
+        self.focused
+    }
+
+    /// Handles a key going down (`pressed`) or up. Navigation keys act on press;
+    /// activation presses the focused widget and releases it with the key.
+    /// Returns the events activation dispatched.
+    pub fn key(
+        &mut self,
+        scene: &Scene,
+        dispatcher: &mut EventDispatcher,
+        key: FocusKey,
+        pressed: bool,
+        time_us: u64,
+    ) -> Vec<PointerEvent> {
+        match key {
+            FocusKey::Activate => {
+                let buttons = u32::from(pressed);
+                let hit = self.focused(scene).or(dispatcher.captured(FOCUS_POINTER));
+                let position = hit.and_then(|node| scene.world_position(node)).unwrap_or_default();
+                dispatcher.dispatch(scene, PointerSample::new(FOCUS_POINTER, position, hit, buttons, time_us))
+            }
+            _ if !pressed => Vec::new(),
+            FocusKey::Next => {
+                self.next(scene);
+                Vec::new()
+            }
+            FocusKey::Previous => {
+                self.previous(scene);
+                Vec::new()
+            }
+            _ => {
+                if let Some(direction) = key.direction() {
+                    self.navigate(scene, direction);
+                }
+                Vec::new()
+            }
+        }
+    }
+
+    /// Outline around the focused widget.
+    pub fn ring(&self, scene: &Scene) -> Option<FocusRing> {
+        let node = self.focused(scene)?;
+        let center = scene.world_position(node)?;
+        let size = scene.get(node)?.size.abs() + Vec3::splat(self.ring_padding * 2.0);
+        Some(FocusRing { node, center, size })
+    }
+
+    fn step(&mut self, scene: &Scene, by: isize) -> Option<NodeId> {
+        let order = self.tab_order(scene);
+        if order.is_empty() {
+            self.focused = None;
+            return None;
+        }
+        let len = order.len() as isize;
+        let index = match self.focused(scene).and_then(|node| order.iter().position(|&n| n == node)) {
+            Some(index) => (index as isize + by).rem_euclid(len),
+            None if by > 0 => 0,
+            None => len - 1,
+        };
+        self.focused = Some(order[index as usize]);
+        self.focused
+    }
+
+    fn can_focus(&self, scene: &Scene, node: NodeId) -> bool {
+        scene.is_effectively(node, NodeFlags::VISIBLE | NodeFlags::PICKABLE)
+            && self.trap.is_none_or(|root| node == root || scene.is_ancestor(root, node))
+    }
+}
+
+impl Default for FocusManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/focus_tests.rs"]
+mod tests;
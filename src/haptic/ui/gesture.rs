@@ -0,0 +1,290 @@
+//! Gestures recognized from tool and finger trajectories.
+//!
+//! A stroke is the trajectory of one pointer from press to release, as
+//! timestamped positions; the source does not matter, so stylus tips and
+//! tracked fingertips both work. [`GestureRecognizer::classify`] reads one stroke
+//! as a tap, hold, swipe or circle and [`GestureRecognizer::classify_pinch`]
+//! reads two simultaneous strokes as a pinch. Every [`GestureEvent`] carries a
+//! confidence from 0.5 (barely passed the thresholds) to 1 (textbook).
+//!
+//! For live input, [`GestureRecognizer::press`], [`GestureRecognizer::motion`]
+//! and [`GestureRecognizer::release`] record strokes per pointer. A hold fires
+//! while still pressed, once the pointer has stayed put long enough; two
+//! strokes overlapping in time are checked for a pinch when the first ends.
+
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+
+use super::event::PointerId;
+use crate::core::{Meters, Seconds, Vec3};
+
+/// Least net travel over path length for a swipe.
+const SWIPE_STRAIGHTNESS: f32 = 0.8;
+
+/// Most spread of distances from the center, relative to the radius, for a circle.
+const CIRCLE_SPREAD: f32 = 0.3;
+
+/// Least angle a circle sweeps around its center.
+const CIRCLE_SWEEP: f32 = 0.8 * TAU;
+
+/// One point of a trajectory.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrackPoint {
+    pub position: Vec3,
+    pub time_us: u64,
+}
+
+impl TrackPoint {
+    pub const fn new(position: Vec3, time_us: u64) -> Self {
+        Self { position, time_us }
+    }
+}
+
+/// A recognized gesture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    Tap { position: Vec3 },
+    /// Pressed in place for `duration`.
+    Hold { position: Vec3, duration: Seconds },
+    /// A quick straight stroke along the unit `direction`, at `speed` m/s.
+    Swipe { direction: Vec3, speed: f32 },
+    /// Two points moving apart (`scale` > 1) or together (< 1), as the ratio of
+    /// their final to initial separation.
+    Pinch { center: Vec3, scale: f32 },
+    /// A loop around `center`; `normal` is the unit axis the stroke turns
+    /// counterclockwise around, and `turns` how many times it went round.
+    Circle { center: Vec3, radius: f32, normal: Vec3, turns: f32 },
+}
+
+/// A gesture with how sure the recognizer is of it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureEvent {
+    pub gesture: Gesture,
+    /// From 0.5 to 1.
+    pub confidence: f32,
+    /// When the gesture was recognized.
+    pub time_us: u64,
+}
+
+/// Thresholds separating the gestures.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureConfig {
+    /// Longest press that is a tap.
+    pub tap_time: Seconds,
+    /// Farthest a tap or hold may wander from where it started.
+    pub slop: Meters,
+    /// Shortest press that is a hold.
+    pub hold_time: Seconds,
+    /// Shortest net travel of a swipe.
+    pub swipe_distance: Meters,
+    /// Slowest average speed of a swipe, in m/s.
+    pub swipe_speed: f32,
+    /// Smallest circle radius.
+    pub circle_radius: Meters,
+    /// Smallest relative change of separation that is a pinch.
+    pub pinch_change: f32,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            tap_time: Seconds(0.25),
+            slop: Meters(0.01),
+            hold_time: Seconds(0.6),
+            swipe_distance: Meters(0.05),
+            swipe_speed: 0.3,
+            circle_radius: Meters(0.01),
+            pinch_change: 0.2,
+        }
+    }
+}
+
+/// A stroke being recorded.
+#[derive(Debug, Clone, Default)]
+struct Stroke {
+    points: Vec<TrackPoint>,
+    /// A hold already fired for this press.
+    held: bool,
+    /// Used up by a pinch; its release yields nothing.
+    consumed: bool,
+}
+
+/// Turns strokes into gestures.
+#[derive(Debug, Clone, Default)]
+pub struct GestureRecognizer {
+    pub config: GestureConfig,
+    strokes: HashMap<PointerId, Stroke>,
+}
+
+impl GestureRecognizer {
+    pub fn new(config: GestureConfig) -> Self {
+        Self { config, strokes: HashMap::new() }
+    }
+
+    /// Whether `pointer` is mid-stroke.
+    pub fn is_pressed(&self, pointer: PointerId) -> bool {
+        self.strokes.contains_key(&pointer)
+    }
+
+    /// Starts a stroke for `pointer`, dropping any unfinished one.
+    pub fn press(&mut self, pointer: PointerId, point: TrackPoint) {
+        self.strokes.insert(pointer, Stroke { points: vec![point], ..Stroke::default() });
+    }
+
+    /// Extends the stroke of `pointer`; returns a hold once it has been pressed
+    /// in place long enough.
+    pub fn motion(&mut self, pointer: PointerId, point: TrackPoint) -> Option<GestureEvent> {
+        let stroke = self.strokes.get_mut(&pointer)?;
+        stroke.points.push(point);
+        if stroke.held || stroke.consumed {
+            return None;
+        }
+        let event = hold(&self.config, &stroke.points)?;
+        stroke.held = true;
+        Some(event)
+    }
+
+    /// Ends the stroke of `pointer` and classifies it, or the pinch it made with
+    /// another pointer still down.
+    pub fn release(&mut self, pointer: PointerId, point: TrackPoint) -> Option<GestureEvent> {
+        let mut stroke = self.strokes.remove(&pointer)?;
+        stroke.points.push(point);
+        if stroke.consumed || stroke.held {
+            return None;
+        }
+        let config = self.config;
+        for other in self.strokes.values_mut().filter(|s| !s.consumed) {
+            let start = stroke.points[0].time_us.max(other.points[0].time_us);
+            let overlap = |points: &[TrackPoint]| -> Vec<TrackPoint> {
+                points.iter().copied().filter(|p| p.time_us >= start).collect()
+            };
+            if let Some(event) = pinch(&config, &overlap(&stroke.points), &overlap(&other.points)) {
+                other.consumed = true;
+                return Some(event);
+            }
+        }
+        self.classify(&stroke.points)
+    }
+
+    /// Reads a complete stroke as a tap, hold, circle or swipe.
+    pub fn classify(&self, stroke: &[TrackPoint]) -> Option<GestureEvent> {
+        let config = &self.config;
+        tap(config, stroke)
+            .or_else(|| hold(config, stroke))
+            .or_else(|| circle(config, stroke))
+            .or_else(|| swipe(config, stroke))
+    }
+
+    /// Reads two simultaneous strokes as a pinch.
+    pub fn classify_pinch(&self, a: &[TrackPoint], b: &[TrackPoint]) -> Option<GestureEvent> {
+        pinch(&self.config, a, b)
+    }
+}
+
+fn event(gesture: Gesture, confidence: f32, time_us: u64) -> GestureEvent {
+    GestureEvent { gesture, confidence: confidence.clamp(0.5, 1.0), time_us }
+}
+
+/// Seconds from the first to the last point.
+fn duration(stroke: &[TrackPoint]) -> f32 {
+    match (stroke.first(), stroke.last()) {
+        (Some(first), Some(last)) => last.time_us.saturating_sub(first.time_us) as f32 * 1e-6,
+        _ => 0.0,
+    }
+}
+
+/// Farthest any point got from the first.
+fn wander(stroke: &[TrackPoint]) -> f32 {
+    let start = stroke[0].position;
+    stroke.iter().map(|p| p.position.distance_to(start)).fold(0.0, f32::max)
+}
+
+fn path_length(stroke: &[TrackPoint]) -> f32 {
+    stroke.windows(2).map(|w| w[0].position.distance_to(w[1].position)).sum()
+}
+
+fn tap(config: &GestureConfig, stroke: &[TrackPoint]) -> Option<GestureEvent> {
+    let last = stroke.last()?;
+    let (time, travel) = (duration(stroke) / config.tap_time.value(), wander(stroke) / config.slop.value());
+    (time <= 1.0 && travel <= 1.0).then(|| {
+        event(Gesture::Tap { position: stroke[0].position }, 1.0 - 0.5 * time.max(travel), last.time_us)
+    })
+}
+
+fn hold(config: &GestureConfig, stroke: &[TrackPoint]) -> Option<GestureEvent> {
+    let last = stroke.last()?;
+    let time = duration(stroke);
+    let travel = wander(stroke) / config.slop.value();
+    (time >= config.hold_time.value() && travel <= 1.0).then(|| {
+        let gesture = Gesture::Hold { position: stroke[0].position, duration: Seconds(time) };
+        event(gesture, 1.0 - 0.5 * travel, last.time_us)
+    })
+}
+
+fn swipe(config: &GestureConfig, stroke: &[TrackPoint]) -> Option<GestureEvent> {
+    let (first, last) = (stroke.first()?, stroke.last()?);
+    let offset = last.position - first.position;
+    let distance = offset.length();
+    let speed = distance / duration(stroke).max(f32::EPSILON);
+    let straightness = distance / path_length(stroke).max(f32::EPSILON);
+    if distance < config.swipe_distance.value() || speed < config.swipe_speed || straightness < SWIPE_STRAIGHTNESS {
+        return None;
+    }
+    let direction = offset / distance;
+    let confidence = 0.5 + 0.5 * (straightness - SWIPE_STRAIGHTNESS) / (1.0 - SWIPE_STRAIGHTNESS);
+    Some(event(Gesture::Swipe { direction, speed }, confidence, last.time_us))
+}
+
+fn circle(config: &GestureConfig, stroke: &[TrackPoint]) -> Option<GestureEvent> {
+    let last = stroke.last()?;
+    if stroke.len() < 8 {
+        return None;
+    }
+    let center = stroke.iter().fold(Vec3::zero(), |sum, p| sum + p.position) / stroke.len() as f32;
+    let distances: Vec<f32> = stroke.iter().map(|p| p.position.distance_to(center)).collect();
+    let radius = distances.iter().sum::<f32>() / distances.len() as f32;
+    if radius < config.circle_radius.value() {
+        return None;
+    }
+    let variance = distances.iter().map(|d| (d - radius) * (d - radius)).sum::<f32>() / distances.len() as f32;
+    let spread = variance.sqrt() / radius;
+
+    // Signed angle swept around the mean turning axis
+    let area = stroke.windows(2).fold(Vec3::zero(), |sum, w| {
+        sum + (w[0].position - center).cross(w[1].position - center)
+    });
+    let normal = area.try_normalize()?;
+    let sweep: f32 = stroke
+        .windows(2)
+        .map(|w| {
+            let (a, b) = (w[0].position - center, w[1].position - center);
+            a.cross(b).dot(normal).atan2(a.dot(b))
+        })
+        .sum();
+    if spread > CIRCLE_SPREAD || sweep < CIRCLE_SWEEP {
+        return None;
+    }
+    let gesture = Gesture::Circle { center, radius, normal, turns: sweep / TAU };
+    Some(event(gesture, 1.0 - 0.5 * spread / CIRCLE_SPREAD, last.time_us))
+}
+
+fn pinch(config: &GestureConfig, a: &[TrackPoint], b: &[TrackPoint]) -> Option<GestureEvent> {
+    let (a0, a1, b0, b1) = (a.first()?, a.last()?, b.first()?, b.last()?);
+    let before = a0.position.distance_to(b0.position);
+    let after = a1.position.distance_to(b1.position);
+    if before <= f32::EPSILON {
+        return None;
+    }
+    let scale = after / before;
+    let change = (scale - 1.0).abs().max((1.0 / scale.max(f32::EPSILON) - 1.0).abs());
+    if change < config.pinch_change {
+        return None;
+    }
+    let center = (a1.position + b1.position) * 0.5;
+    let confidence = 0.5 * change / config.pinch_change;
+    Some(event(Gesture::Pinch { center, scale }, confidence, a1.time_us.max(b1.time_us)))
+}
+
+#[cfg(test)]
+#[path = "tests/gesture_tests.rs"]
+mod tests;
@@ -0,0 +1,244 @@
+//! Immediate-mode facade for tools and debug overlays.
+//!
+//! Quick tools should not have to keep widget handles around. [`ImmediateUi`]
+//! lets them declare widgets inline every frame, egui style, but placed in 3D:
+//!
+//! ```ignore
+//! ui.begin(&device_state);
+//! if ui.button_3d("Reset", Vec3::new(0.0, 0.05, 0.0))?.clicked {
+//!     reset();
+//! }
+//! ui.end(&mut scene)?;
+//! ```
+//!
+//! Each widget is identified by hashing its label with the enclosing
+//! [`push_id`](ImmediateUi::push_id) scopes. The facade turns the frame's calls
+//! into [`WidgetDesc`]s keyed by those hashes and hands them to a retained
+//! [`WidgetTree`], so the widgets are real, touchable scene nodes whose
+//! interaction state carries over between frames. Two widgets hashing to the same
+//! id in one frame are an error, since they would share that state.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use super::property::{PropertyError, WidgetState};
+use super::widget::{WidgetChange, WidgetDesc, WidgetError, WidgetTree};
+use crate::core::{NewtonSecondsPerMeter, NewtonsPerMeter, Vec3};
+use crate::device::DeviceState;
+use crate::effects::Waveform;
+use crate::scene::{NodeHaptics, NodeId, Scene, SceneError};
+
+/// Full extents of an immediate-mode button.
+pub const BUTTON_SIZE: Vec3 = Vec3::new(0.03, 0.015, 0.008);
+
+/// Distance around a widget's box within which the cursor hovers it.
+const HOVER_MARGIN: f32 = 0.005;
+
+/// Surface of immediate-mode buttons: firm enough to rest a finger on.
+const BUTTON_HAPTICS: NodeHaptics =
+    NodeHaptics { stiffness: NewtonsPerMeter(800.0), damping: NewtonSecondsPerMeter(1.0), friction: 0.4 };
+
+crate::haptic_widget! {
+    /// Interaction state of an immediate-mode button.
+    #[derive(Debug, Clone, Default)]
+    pub struct ImmediateButton {
+        pub label: String,
+        pub hovered: bool,
+        pub pressed: bool,
+    }
+}
+
+// ============================================================================
+// Ids and Responses
+// ============================================================================
+
+/// Hash identifying an immediate-mode widget across frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WidgetId(pub u64);
+
+impl WidgetId {
+    fn key(self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
+
+/// Errors from an immediate-mode frame.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImmediateError {
+    /// Another widget in this frame has the same id; wrap one in `push_id`.
+    IdClash { label: String },
+    Property(PropertyError),
+    Scene(SceneError),
+}
+
+impl fmt::Display for ImmediateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImmediateError::IdClash { label } => {
+                write!(f, "two widgets labelled '{}' share an id; give one its own push_id scope", label)
+            }
+            ImmediateError::Property(error) => write!(f, "{}", error),
+            ImmediateError::Scene(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for ImmediateError {}
+
+impl From<PropertyError> for ImmediateError {
+    fn from(error: PropertyError) -> Self {
+        ImmediateError::Property(error)
+    }
+}
+
+impl From<WidgetError> for ImmediateError {
+    fn from(error: WidgetError) -> Self {
+        match error {
+            WidgetError::Property(error) => ImmediateError::Property(error),
+            WidgetError::Scene(error) => ImmediateError::Scene(error),
+        }
+    }
+}
+
+/// What happened to a widget this frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Response {
+    pub id: WidgetId,
+    /// Scene node, once the widget has been through an `end`.
+    pub node: Option<NodeId>,
+    pub hovered: bool,
+    /// Held down: pressed while hovered and not yet released.
+    pub pressed: bool,
+    /// Released while still hovered this frame.
+    pub clicked: bool,
+}
+
+// ============================================================================
+// Immediate UI
+// ============================================================================
+
+/// Immediate-mode widgets backed by a retained widget tree.
+#[derive(Default)]
+pub struct ImmediateUi {
+    tree: WidgetTree,
+    /// Device button that presses widgets.
+    pub button: u32,
+    cursor: Vec3,
+    down: bool,
+    was_down: bool,
+    ids: Vec<u64>,
+    seen: HashSet<WidgetId>,
+    frame: Vec<WidgetDesc>,
+    feedback: Vec<Waveform>,
+}
+
+impl ImmediateUi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The retained widgets behind the facade.
+    #[inline]
+    pub fn tree(&self) -> &WidgetTree {
+        &self.tree
+    }
+
+    /// Starts a frame with the latest device reading.
+    pub fn begin(&mut self, device: &DeviceState) {
+        self.was_down = self.down;
+        self.down = device.is_pressed(self.button);
+        self.cursor = device.position.value();
+        self.ids.clear();
+        self.seen.clear();
+        self.frame.clear();
+    }
+
+    /// Scopes the ids of the widgets that follow by `id`, e.g. a loop index.
+    pub fn push_id(&mut self, id: impl Hash) {
+        let hash = self.hash(id);
+        self.ids.push(hash);
+    }
+
+    pub fn pop_id(&mut self) {
+        self.ids.pop();
+    }
+
+    /// A touchable push button centered at `position`.
+    pub fn button_3d(&mut self, label: &str, position: Vec3) -> Result<Response, ImmediateError> {
+        let id = self.claim(label)?;
+        let node = self.tree.find(&[&id.key()]);
+        let was_pressed = node.and_then(|n| self.tree.get::<bool>(n, "pressed")).unwrap_or(false);
+
+        let offset = (self.cursor - position).abs();
+        let reach = BUTTON_SIZE * 0.5 + Vec3::splat(HOVER_MARGIN);
+        let hovered = offset.x <= reach.x && offset.y <= reach.y && offset.z <= reach.z;
+        let pressed = if was_pressed { self.down } else { hovered && self.down && !self.was_down };
+        let clicked = was_pressed && !self.down && hovered;
+        if pressed != was_pressed {
+            self.feedback.push(Waveform::click().with_intensity(if pressed { 1.0 } else { 0.5 }));
+        }
+        if let Some(node) = node {
+            self.tree.set(node, "hovered", hovered)?;
+            self.tree.set(node, "pressed", pressed)?;
+        }
+
+        self.frame.push(
+            WidgetDesc::button(label)
+                .key(id.key())
+                .name(label)
+                .at(position.x, position.y, position.z)
+                .size(BUTTON_SIZE.x, BUTTON_SIZE.y, BUTTON_SIZE.z)
+                .haptics(BUTTON_HAPTICS)
+                .state::<ImmediateButton>()
+                .prop("label", label.to_string()),
+        );
+        Ok(Response { id, node, hovered, pressed, clicked })
+    }
+
+    /// Static text centered at `position`; never hovered or pressed.
+    pub fn label_3d(&mut self, text: &str, position: Vec3) -> Result<Response, ImmediateError> {
+        let id = self.claim(text)?;
+        let node = self.tree.find(&[&id.key()]);
+        self.frame.push(WidgetDesc::label(text).key(id.key()).name(text).at(position.x, position.y, position.z));
+        Ok(Response { id, node, hovered: false, pressed: false, clicked: false })
+    }
+
+    /// Ends the frame: widgets declared in it are created or updated in `scene`,
+    /// and the ones not declared again are removed.
+    pub fn end(&mut self, scene: &mut Scene) -> Result<Vec<WidgetChange>, ImmediateError> {
+        let frame = std::mem::take(&mut self.frame);
+        Ok(self.tree.update(scene, &frame)?)
+    }
+
+    /// Clicks to play for presses and releases since the last call.
+    pub fn take_feedback(&mut self) -> Vec<Waveform> {
+        std::mem::take(&mut self.feedback)
+    }
+
+    /// Interaction state of the widget `id`, as of its last declaration.
+    pub fn state(&self, id: WidgetId) -> Option<&dyn WidgetState> {
+        self.tree.state(self.tree.find(&[&id.key()])?)
+    }
+
+    fn hash(&self, value: impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.ids.last().hash(&mut hasher);
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Id of `label` in the current scope, unless it was already used this frame.
+    fn claim(&mut self, label: &str) -> Result<WidgetId, ImmediateError> {
+        let id = WidgetId(self.hash(label));
+        if !self.seen.insert(id) {
+            return Err(ImmediateError::IdClash { label: label.to_string() });
+        }
+        Ok(id)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/immediate_tests.rs"]
+mod tests;
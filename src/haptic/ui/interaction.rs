@@ -0,0 +1,212 @@
+//! Interaction state machine shared by interactive widgets.
+//!
+//! Every interactive widget moves through the same states: idle, hovered by a
+//! pointer, engaged by the tool touching it, pressed, and dragged while
+//! pressed. An [`InteractionMachine`] holds one widget's [`InteractionState`]
+//! and moves it on [`InteractionInput`]s following one fixed table, so a
+//! custom widget gets the same behaviour as the built-in ones by feeding it.
+//!
+//! - Hooks run on entering or leaving a state and on every transition, in the
+//!   order exit, transition, entry. Haptic cues, visuals and events hung on
+//!   them stay in step with the state.
+//! - The machine remembers whether the widget is hovered and touched, so a
+//!   release, cancel or re-enable returns to whichever of idle, hover or
+//!   engaged still holds.
+//! - Leaving the widget while pressed or dragged does not end the press; the
+//!   release does.
+
+use std::fmt;
+
+use super::event::PointerEventKind;
+
+/// Where a widget is in an interaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum InteractionState {
+    #[default]
+    Idle,
+    /// A pointer is over the widget.
+    Hover,
+    /// The tool is touching the widget.
+    Engaged,
+    Pressed,
+    /// Moved while pressed.
+    Dragged,
+    /// Ignores everything but being enabled again.
+    Disabled,
+}
+
+impl InteractionState {
+    /// Whether a press is held: pressed or dragged.
+    #[inline]
+    pub fn is_active(self) -> bool {
+        matches!(self, InteractionState::Pressed | InteractionState::Dragged)
+    }
+}
+
+/// Something that happened to a widget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InteractionInput {
+    PointerEnter,
+    PointerExit,
+    /// The tool touched the widget.
+    Contact,
+    /// The tool left the widget's surface.
+    ContactLost,
+    Press,
+    Release,
+    /// The press moved far enough to count as a drag.
+    DragStart,
+    /// Abandons a press without a release, e.g. when focus is lost.
+    Cancel,
+    Disable,
+    Enable,
+}
+
+impl InteractionInput {
+    /// The input a dispatched pointer event stands for; clicks have none.
+    pub fn from_pointer(kind: &PointerEventKind) -> Option<Self> {
+        match kind {
+            PointerEventKind::Enter => Some(InteractionInput::PointerEnter),
+            PointerEventKind::Exit => Some(InteractionInput::PointerExit),
+            PointerEventKind::Press { .. } => Some(InteractionInput::Press),
+            PointerEventKind::Release { .. } => Some(InteractionInput::Release),
+            PointerEventKind::Move | PointerEventKind::Click { .. } => None,
+        }
+    }
+}
+
+/// A state change and the input that caused it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    pub from: InteractionState,
+    pub to: InteractionState,
+    pub input: InteractionInput,
+}
+
+/// Called with the transition that triggered it.
+pub type InteractionHook = Box<dyn FnMut(&Transition) + Send>;
+
+#[derive(PartialEq)]
+enum HookKind {
+    Enter(InteractionState),
+    Exit(InteractionState),
+    Transition,
+}
+
+/// Interaction state of one widget, with hooks on its changes.
+#[derive(Default)]
+pub struct InteractionMachine {
+    state: InteractionState,
+    hovered: bool,
+    contact: bool,
+    hooks: Vec<(HookKind, InteractionHook)>,
+}
+
+impl InteractionMachine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `hook` whenever the machine enters `state`.
+    pub fn on_enter(mut self, state: InteractionState, hook: impl FnMut(&Transition) + Send + 'static) -> Self {
+        self.hooks.push((HookKind::Enter(state), Box::new(hook)));
+        self
+    }
+
+    /// Runs `hook` whenever the machine leaves `state`.
+    pub fn on_exit(mut self, state: InteractionState, hook: impl FnMut(&Transition) + Send + 'static) -> Self {
+        self.hooks.push((HookKind::Exit(state), Box::new(hook)));
+        self
+    }
+
+    /// Runs `hook` on every transition.
+    pub fn on_transition(mut self, hook: impl FnMut(&Transition) + Send + 'static) -> Self {
+        self.hooks.push((HookKind::Transition, Box::new(hook)));
+        self
+    }
+
+    #[inline]
+    pub fn state(&self) -> InteractionState {
+        self.state
+    }
+
+    #[inline]
+    pub fn is_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    #[inline]
+    pub fn is_touched(&self) -> bool {
+        self.contact
+    }
+
+    /// Applies `input`; returns the transition if the state changed.
+    pub fn handle(&mut self, input: InteractionInput) -> Option<Transition> {
+        use InteractionInput as I;
+        use InteractionState as S;
+        match input {
+            I::PointerEnter => self.hovered = true,
+            I::PointerExit => self.hovered = false,
+            I::Contact => self.contact = true,
+            I::ContactLost => self.contact = false,
+            _ => {}
+        }
+        let to = match (self.state, input) {
+            (S::Disabled, I::Enable) => self.resting(),
+            (S::Disabled, _) => return None,
+            (_, I::Disable) => S::Disabled,
+            (S::Pressed, I::DragStart) => S::Dragged,
+            (S::Pressed | S::Dragged, I::Release | I::Cancel) => self.resting(),
+            (S::Pressed | S::Dragged, _) => return None,
+            (S::Idle | S::Hover | S::Engaged, I::Press) => S::Pressed,
+            (S::Idle | S::Hover | S::Engaged, _) => self.resting(),
+        };
+        if to == self.state {
+            return None;
+        }
+        let transition = Transition { from: self.state, to, input };
+        self.state = to;
+        self.run_hooks(&transition);
+        Some(transition)
+    }
+
+    /// Disables or enables the widget.
+    pub fn set_enabled(&mut self, enabled: bool) -> Option<Transition> {
+        self.handle(if enabled { InteractionInput::Enable } else { InteractionInput::Disable })
+    }
+
+    /// Idle, hover or engaged, from what the pointer and tool are doing.
+    fn resting(&self) -> InteractionState {
+        if self.contact {
+            InteractionState::Engaged
+        } else if self.hovered {
+            InteractionState::Hover
+        } else {
+            InteractionState::Idle
+        }
+    }
+
+    fn run_hooks(&mut self, transition: &Transition) {
+        let order = [HookKind::Exit(transition.from), HookKind::Transition, HookKind::Enter(transition.to)];
+        for wanted in &order {
+            for (_, hook) in self.hooks.iter_mut().filter(|(kind, _)| kind == wanted) {
+                hook(transition);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for InteractionMachine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InteractionMachine")
+            .field("state", &self.state)
+            .field("hovered", &self.hovered)
+            .field("contact", &self.contact)
+            .field("hooks", &self.hooks.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/interaction_tests.rs"]
+mod tests;
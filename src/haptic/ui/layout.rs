@@ -0,0 +1,321 @@
+//! Grid layout for control panels.
+//!
+//! A [`GridLayout`] places widget descriptions into cells of a 3D grid: columns
+//! run along +X, rows down -Y and layers out along +Z. A child can span several
+//! tracks, and is aligned within its cell per axis (start, center, end, or
+//! stretched to fill it). Tracks are either a fixed size or sized to fit the
+//! largest child sitting in that track alone. The grid is centered on the origin
+//! of the group it produces, so moving the panel means moving one node:
+//!
+//! ```text
+//! GridLayout::new(3, 2)
+//!     .with_spacing(0.005, 0.005, 0.0)
+//!     .with(WidgetDesc::button("A").size(0.02, 0.02, 0.005), Cell::at(0, 0))
+//!     .with(WidgetDesc::button("B").size(0.02, 0.02, 0.005), Cell::at(1, 0).span(2, 1))
+//!     .desc()
+//! ```
+
+use super::widget::WidgetDesc;
+use crate::core::Vec3;
+
+// ============================================================================
+// Cells
+// ============================================================================
+
+/// Placement of a child along one axis of its cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    /// Left, top or back.
+    Start,
+    #[default]
+    Center,
+    /// Right, bottom or front.
+    End,
+    /// Resized to fill the cell.
+    Stretch,
+}
+
+/// Alignment along each axis of a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CellAlign {
+    pub x: Align,
+    pub y: Align,
+    pub z: Align,
+}
+
+impl CellAlign {
+    pub const CENTER: Self = Self::all(Align::Center);
+    pub const STRETCH: Self = Self::all(Align::Stretch);
+
+    pub const fn all(align: Align) -> Self {
+        Self { x: align, y: align, z: align }
+    }
+
+    pub const fn new(x: Align, y: Align, z: Align) -> Self {
+        Self { x, y, z }
+    }
+}
+
+/// Where a child sits in the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub column: usize,
+    pub row: usize,
+    pub layer: usize,
+    pub column_span: usize,
+    pub row_span: usize,
+    pub layer_span: usize,
+    /// Overrides the grid's alignment for this child.
+    pub align: Option<CellAlign>,
+}
+
+impl Cell {
+    /// Single cell in the first layer.
+    pub const fn at(column: usize, row: usize) -> Self {
+        Self { column, row, layer: 0, column_span: 1, row_span: 1, layer_span: 1, align: None }
+    }
+
+    pub const fn layer(mut self, layer: usize) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Spans `columns` and `rows` tracks from the cell on.
+    pub const fn span(mut self, columns: usize, rows: usize) -> Self {
+        self.column_span = columns;
+        self.row_span = rows;
+        self
+    }
+
+    pub const fn layer_span(mut self, layers: usize) -> Self {
+        self.layer_span = layers;
+        self
+    }
+
+    pub const fn align(mut self, align: CellAlign) -> Self {
+        self.align = Some(align);
+        self
+    }
+}
+
+/// Size of a row, column or layer.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Track {
+    /// Fits the largest child that sits in this track alone.
+    #[default]
+    Auto,
+    Fixed(f32),
+}
+
+// ============================================================================
+// Grid
+// ============================================================================
+
+/// Children arranged in columns, rows and layers.
+#[derive(Debug, Clone, Default)]
+pub struct GridLayout {
+    pub columns: Vec<Track>,
+    pub rows: Vec<Track>,
+    pub layers: Vec<Track>,
+    /// Gap between neighboring tracks along each axis.
+    pub spacing: Vec3,
+    /// Alignment of children whose cell does not set one.
+    pub align: CellAlign,
+    children: Vec<(WidgetDesc, Cell)>,
+}
+
+impl GridLayout {
+    /// Grid of auto-sized tracks, one layer deep.
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Self {
+            columns: vec![Track::Auto; columns],
+            rows: vec![Track::Auto; rows],
+            layers: vec![Track::Auto],
+            ..Self::default()
+        }
+    }
+
+    pub fn with_layers(mut self, layers: usize) -> Self {
+        self.layers = vec![Track::Auto; layers];
+        self
+    }
+
+    pub fn with_spacing(mut self, x: f32, y: f32, z: f32) -> Self {
+        self.spacing = Vec3::new(x, y, z);
+        self
+    }
+
+    pub fn with_align(mut self, align: CellAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Sets the size of column `index`, adding columns up to it if needed.
+    pub fn with_column(mut self, index: usize, track: Track) -> Self {
+        set_track(&mut self.columns, index, track);
+        self
+    }
+
+    /// Sets the size of row `index`, adding rows up to it if needed.
+    pub fn with_row(mut self, index: usize, track: Track) -> Self {
+        set_track(&mut self.rows, index, track);
+        self
+    }
+
+    /// Sets the size of layer `index`, adding layers up to it if needed.
+    pub fn with_layer(mut self, index: usize, track: Track) -> Self {
+        set_track(&mut self.layers, index, track);
+        self
+    }
+
+    pub fn with(mut self, child: WidgetDesc, cell: Cell) -> Self {
+        self.add(child, cell);
+        self
+    }
+
+    /// Adds a child; tracks are added as needed to hold its cell.
+    pub fn add(&mut self, child: WidgetDesc, cell: Cell) {
+        let cell = Cell {
+            column_span: cell.column_span.max(1),
+            row_span: cell.row_span.max(1),
+            layer_span: cell.layer_span.max(1),
+            ..cell
+        };
+        grow(&mut self.columns, cell.column + cell.column_span);
+        grow(&mut self.rows, cell.row + cell.row_span);
+        grow(&mut self.layers, cell.layer + cell.layer_span);
+        self.children.push((child, cell));
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Resolved sizes of the columns, rows and layers.
+    pub fn track_sizes(&self) -> [Vec<f32>; 3] {
+        [0, 1, 2].map(|axis| {
+            let tracks = [&self.columns, &self.rows, &self.layers][axis];
+            tracks
+                .iter()
+                .enumerate()
+                .map(|(index, track)| match *track {
+                    Track::Fixed(size) => size.max(0.0),
+                    Track::Auto => self
+                        .children
+                        .iter()
+                        .filter(|(_, cell)| span(cell, axis) == (index, 1))
+                        .map(|(child, _)| component(child.size, axis))
+                        .fold(0.0, f32::max),
+                })
+                .collect()
+        })
+    }
+
+    /// Outer size of the whole grid.
+    pub fn size(&self) -> Vec3 {
+        let [columns, rows, layers] = self.track_sizes();
+        Vec3::new(
+            total(&columns, 0, columns.len(), self.spacing.x),
+            total(&rows, 0, rows.len(), self.spacing.y),
+            total(&layers, 0, layers.len(), self.spacing.z),
+        )
+    }
+
+    /// Center and size of each child once placed, in the order they were added,
+    /// relative to the grid center.
+    pub fn frames(&self) -> Vec<(Vec3, Vec3)> {
+        let sizes = self.track_sizes();
+        let outer = self.size();
+        let spacing = [self.spacing.x, self.spacing.y, self.spacing.z];
+        self.children
+            .iter()
+            .map(|(child, cell)| {
+                let align = cell.align.unwrap_or(self.align);
+                let mut center = [0.0; 3];
+                let mut size = [0.0; 3];
+                for axis in 0..3 {
+                    let (first, count) = span(cell, axis);
+                    // Offset from the start edge of the grid along the axis
+                    let start = sizes[axis][..first].iter().sum::<f32>() + first as f32 * spacing[axis];
+                    let extent = total(&sizes[axis], first, count, spacing[axis]);
+                    let wanted = component(child.size, axis);
+                    let (offset, length) = match [align.x, align.y, align.z][axis] {
+                        Align::Start => (wanted * 0.5, wanted),
+                        Align::Center => (extent * 0.5, wanted),
+                        Align::End => (extent - wanted * 0.5, wanted),
+                        Align::Stretch => (extent * 0.5, extent),
+                    };
+                    let along = start + offset - component(outer, axis) * 0.5;
+                    // Rows run down, unlike columns and layers
+                    center[axis] = if axis == 1 { -along } else { along };
+                    size[axis] = length;
+                }
+                (Vec3::new(center[0], center[1], center[2]), Vec3::new(size[0], size[1], size[2]))
+            })
+            .collect()
+    }
+
+    /// The children, placed.
+    pub fn children(&self) -> Vec<WidgetDesc> {
+        self.children
+            .iter()
+            .zip(self.frames())
+            .map(|((child, _), (center, size))| {
+                let mut child = child.clone();
+                child.position = center;
+                child.size = size;
+                child
+            })
+            .collect()
+    }
+
+    /// A group holding the placed children, sized to the grid.
+    pub fn desc(&self) -> WidgetDesc {
+        let size = self.size();
+        WidgetDesc::group().size(size.x, size.y, size.z).children(self.children())
+    }
+}
+
+fn set_track(tracks: &mut Vec<Track>, index: usize, track: Track) {
+    grow(tracks, index + 1);
+    tracks[index] = track;
+}
+
+fn grow(tracks: &mut Vec<Track>, len: usize) {
+    if tracks.len() < len {
+        tracks.resize(len, Track::Auto);
+    }
+}
+
+/// First track and track count of `cell` along `axis`.
+fn span(cell: &Cell, axis: usize) -> (usize, usize) {
+    match axis {
+        0 => (cell.column, cell.column_span),
+        1 => (cell.row, cell.row_span),
+        _ => (cell.layer, cell.layer_span),
+    }
+}
+
+fn component(v: Vec3, axis: usize) -> f32 {
+    match axis {
+        0 => v.x,
+        1 => v.y,
+        _ => v.z,
+    }
+}
+
+/// Size of `count` tracks from `first` on, with the gaps between them.
+fn total(sizes: &[f32], first: usize, count: usize, spacing: f32) -> f32 {
+    let tracks = sizes.iter().skip(first).take(count);
+    tracks.sum::<f32>() + count.saturating_sub(1) as f32 * spacing
+}
+
+#[cfg(test)]
+#[path = "tests/layout_tests.rs"]
+mod tests;
@@ -0,0 +1,187 @@
+//! Widget lifecycle hooks and update scheduling.
+//!
+//! A [`WidgetDesc`](super::WidgetDesc) can carry [`WidgetHooks`], run by the
+//! [`WidgetTree`](super::WidgetTree) holding the widget:
+//!
+//! - `on_mount` once the widget is created and its children are mounted,
+//! - `on_unmount` just before it is removed, after its children,
+//! - `on_update` on the frames the tree's [`UpdateScheduler`] has it due, with
+//!   the frame's delta time.
+//!
+//! The scheduler keeps idle scenes cheap: a widget is only due on the next
+//! [`WidgetTree::tick`](super::WidgetTree::tick) if it asked for an update, for
+//! instance to step an animation, or while something interacts with it. A
+//! widget that wants to keep animating asks again from its `on_update`. With
+//! nothing due, a tick does not even walk the tree.
+//!
+//! Hooks are shared by every copy of a description, so they are `Fn`; a
+//! widget's mutable data belongs in its state, which hooks are handed.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use super::event::{PointerEvent, PointerEventKind, PointerId};
+use super::property::WidgetState;
+use crate::core::Seconds;
+use crate::scene::{NodeId, Scene};
+
+/// A lifecycle hook.
+pub type WidgetHook = Arc<dyn Fn(&mut WidgetContext<'_>) + Send + Sync>;
+
+/// What a hook runs with.
+pub struct WidgetContext<'a> {
+    pub node: NodeId,
+    pub scene: &'a mut Scene,
+    /// The widget's state, if it has one.
+    pub state: Option<&'a mut (dyn WidgetState + Send + 'static)>,
+    /// Time since the last frame; zero when mounting and unmounting.
+    pub dt: Seconds,
+    update: bool,
+}
+
+impl<'a> WidgetContext<'a> {
+    pub(super) fn new(
+        node: NodeId,
+        scene: &'a mut Scene,
+        state: Option<&'a mut (dyn WidgetState + Send + 'static)>,
+        dt: Seconds,
+    ) -> Self {
+        Self { node, scene, state, dt, update: false }
+    }
+
+    /// Has the widget updated on the next tick, e.g. for the next step of an
+    /// animation. Ignored when unmounting.
+    pub fn request_update(&mut self) {
+        self.update = true;
+    }
+
+    #[inline]
+    pub(super) fn update_requested(&self) -> bool {
+        self.update
+    }
+}
+
+/// Lifecycle hooks of a widget.
+#[derive(Clone, Default)]
+pub struct WidgetHooks {
+    pub on_mount: Option<WidgetHook>,
+    pub on_unmount: Option<WidgetHook>,
+    pub on_update: Option<WidgetHook>,
+}
+
+impl fmt::Debug for WidgetHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WidgetHooks")
+            .field("on_mount", &self.on_mount.is_some())
+            .field("on_unmount", &self.on_unmount.is_some())
+            .field("on_update", &self.on_update.is_some())
+            .finish()
+    }
+}
+
+/// Decides which widgets update on each tick.
+#[derive(Debug, Clone, Default)]
+pub struct UpdateScheduler {
+    /// Widgets that asked to update on the next tick.
+    requested: HashSet<NodeId>,
+    /// Widgets marked interacting by the app, e.g. while the tool touches them.
+    held: HashSet<NodeId>,
+    /// Pointers over each widget.
+    hovers: HashSet<(NodeId, PointerId)>,
+    /// Pointers pressed on each widget.
+    presses: HashSet<(NodeId, PointerId)>,
+}
+
+impl UpdateScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Has `node` updated on the next tick.
+    pub fn request_update(&mut self, node: NodeId) {
+        self.requested.insert(node);
+    }
+
+    /// Marks `node` interacting or not; an interacting widget updates on every
+    /// tick.
+    pub fn set_interacting(&mut self, node: NodeId, interacting: bool) {
+        if interacting {
+            self.held.insert(node);
+        } else {
+            self.held.remove(&node);
+        }
+    }
+
+    /// Follows pointers over and pressing widgets from a dispatched event. A
+    /// widget is interacting while a pointer is over it or pressed on it.
+    pub fn observe(&mut self, event: &PointerEvent) {
+        let entry = (event.target, event.pointer);
+        match event.kind {
+            PointerEventKind::Enter => {
+                self.hovers.insert(entry);
+            }
+            PointerEventKind::Exit => {
+                self.hovers.remove(&entry);
+            }
+            PointerEventKind::Press { .. } => {
+                self.presses.insert(entry);
+            }
+            // The release may land elsewhere than the press
+            PointerEventKind::Release { .. } => self.presses.retain(|(_, pointer)| *pointer != event.pointer),
+            PointerEventKind::Move | PointerEventKind::Click { .. } => {}
+        }
+    }
+
+    pub fn is_interacting(&self, node: NodeId) -> bool {
+        self.held.contains(&node)
+            || self.hovers.iter().any(|(n, _)| *n == node)
+            || self.presses.iter().any(|(n, _)| *n == node)
+    }
+
+    /// Whether `node` updates on the next tick.
+    pub fn is_due(&self, node: NodeId) -> bool {
+        self.requested.contains(&node) || self.is_interacting(node)
+    }
+
+    /// Whether no widget updates on the next tick.
+    pub fn is_idle(&self) -> bool {
+        self.requested.is_empty() && self.held.is_empty() && self.hovers.is_empty() && self.presses.is_empty()
+    }
+
+    /// Drops everything about `node`, e.g. once it is unmounted.
+    pub fn forget(&mut self, node: NodeId) {
+        self.requested.remove(&node);
+        self.held.remove(&node);
+        self.hovers.retain(|(n, _)| *n != node);
+        self.presses.retain(|(n, _)| *n != node);
+    }
+
+    /// Forgets widgets whose node left the scene; returns how many.
+    pub fn retain_in(&mut self, scene: &Scene) -> usize {
+        let gone: HashSet<NodeId> = self
+            .requested
+            .iter()
+            .chain(&self.held)
+            .chain(self.hovers.iter().chain(&self.presses).map(|(n, _)| n))
+            .filter(|&&n| !scene.contains(n))
+            .copied()
+            .collect();
+        for &node in &gone {
+            self.forget(node);
+        }
+        gone.len()
+    }
+
+    /// Widgets due on this tick, clearing the requests.
+    pub(super) fn take_due(&mut self) -> HashSet<NodeId> {
+        let mut due = std::mem::take(&mut self.requested);
+        due.extend(&self.held);
+        due.extend(self.hovers.iter().chain(&self.presses).map(|(n, _)| *n));
+        due
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/lifecycle_tests.rs"]
+mod tests;
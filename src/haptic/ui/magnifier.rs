@@ -0,0 +1,151 @@
+//! Picture-in-picture magnifier with matching motion and force scaling.
+//!
+//! While active, the area around the cursor is shown zoomed in an inset, device
+//! motion is scaled down by the zoom factor (finer positioning), and rendered forces
+//! are scaled down by the same factor so magnified surfaces do not feel stiffer.
+//! Scaling ramps in and out over a short transition and the device→world mapping is
+//! incremental, so toggling the magnifier never makes the cursor jump.
+
+use crate::core::Vec3;
+
+/// What the renderer should draw in the inset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MagnifierView {
+    /// World-space center of the magnified region.
+    pub center: Vec3,
+    /// World-space radius of the region shown in the inset.
+    pub source_radius: f32,
+    /// Current zoom factor (ramps from 1 to `zoom` during the transition).
+    pub zoom: f32,
+    /// Inset opacity in 0..=1.
+    pub opacity: f32,
+}
+
+/// Magnifier state and the workspace mapping it controls.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Magnifier {
+    /// Zoom factor at full activation (> 1).
+    pub zoom: f32,
+    /// Radius of the inset in world units; the source region is `radius / zoom`.
+    pub radius: f32,
+    /// Seconds to ramp scaling in or out.
+    pub transition: f32,
+    /// Rate (1/s) at which the cursor offset built up while magnified decays back
+    /// to the absolute mapping after deactivation.
+    pub recenter_rate: f32,
+    active: bool,
+    blend: f32,
+    last_device: Option<Vec3>,
+    cursor: Vec3,
+}
+
+impl Magnifier {
+    pub fn new(zoom: f32, radius: f32) -> Self {
+        Self {
+            zoom: zoom.max(1.0),
+            radius,
+            transition: 0.2,
+            recenter_rate: 2.0,
+            active: false,
+            blend: 0.0,
+            last_device: None,
+            cursor: Vec3::zero(),
+        }
+    }
+
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn activate(&mut self) {
+        self.active = true;
+    }
+
+    pub fn deactivate(&mut self) {
+        self.active = false;
+    }
+
+    pub fn toggle(&mut self) {
+        self.active = !self.active;
+    }
+
+    /// Activation progress in 0..=1.
+    #[inline]
+    pub fn blend(&self) -> f32 {
+        self.blend
+    }
+
+    /// Effective zoom for the current blend.
+    #[inline]
+    pub fn current_zoom(&self) -> f32 {
+        1.0 + (self.zoom - 1.0) * self.blend
+    }
+
+    /// World distance moved per unit of device motion.
+    #[inline]
+    pub fn motion_scale(&self) -> f32 {
+        1.0 / self.current_zoom()
+    }
+
+    /// Factor applied to forces sent to the device.
+    #[inline]
+    pub fn force_scale(&self) -> f32 {
+        1.0 / self.current_zoom()
+    }
+
+    /// Advances the activation ramp.
+    pub fn update(&mut self, dt: f32) {
+        let step = if self.transition > 0.0 { dt / self.transition } else { 1.0 };
+        let target = if self.active { 1.0 } else { 0.0 };
+        self.blend = if self.blend < target { (self.blend + step).min(1.0) } else { (self.blend - step).max(0.0) };
+
+        // Once fully off, drift back to the absolute mapping
+        if self.blend == 0.0 {
+            if let Some(device) = self.last_device {
+                let decay = 1.0 - (-self.recenter_rate * dt).exp();
+                self.cursor = self.cursor.lerp(device, decay);
+            }
+        }
+    }
+
+    /// Maps a device position to the world cursor position.
+    pub fn map_device(&mut self, device: Vec3) -> Vec3 {
+        match self.last_device {
+            Some(last) => self.cursor += (device - last) * self.motion_scale(),
+            None => self.cursor = device,
+        }
+        self.last_device = Some(device);
+        self.cursor
+    }
+
+    /// Current world cursor position.
+    #[inline]
+    pub fn cursor(&self) -> Vec3 {
+        self.cursor
+    }
+
+    /// Scales a world-space force for output on the device.
+    #[inline]
+    pub fn scale_force(&self, force: Vec3) -> Vec3 {
+        force * self.force_scale()
+    }
+
+    /// Inset to render, or None while fully inactive.
+    pub fn view(&self) -> Option<MagnifierView> {
+        (self.blend > 0.0).then(|| {
+            let zoom = self.current_zoom();
+            MagnifierView { center: self.cursor, source_radius: self.radius / zoom, zoom, opacity: self.blend }
+        })
+    }
+}
+
+impl Default for Magnifier {
+    fn default() -> Self {
+        Self::new(4.0, 0.05)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/magnifier_tests.rs"]
+mod tests;
@@ -0,0 +1,47 @@
+// src/haptic/ui/mod.rs
+pub mod anchor;
+pub mod avatar;
+pub mod constraint;
+pub mod drag;
+pub mod event;
+pub mod explorer;
+pub mod focus;
+pub mod gesture;
+pub mod immediate;
+pub mod interaction;
+pub mod layout;
+pub mod lifecycle;
+pub mod magnifier;
+pub mod modal;
+pub mod picking;
+pub mod property;
+pub mod proximity;
+pub mod scroll;
+pub mod signal;
+pub mod snap;
+pub mod tooltip;
+pub mod tour;
+pub mod widget;
+pub use anchor::{Anchor, AnchorError, AnchorLayout, Side};
+pub use avatar::{AvatarFrame, AvatarPose, ToolAvatar, ToolModel};
+pub use constraint::{Constraint, ConstraintError, ConstraintSolver};
+pub use drag::{DragDrop, DragPayload, DragSession, DragSource, DropOutcome, DropTarget};
+pub use event::{EventDispatcher, PointerEvent, PointerEventKind, PointerHandler, PointerId, PointerSample, Propagation};
+pub use explorer::{ExploredWidget, Explorer, ExplorerFrame, ExplorerStyle, RoleTexture, SpeechHook, WidgetRole};
+pub use focus::{FocusKey, FocusManager, FocusRing, FOCUS_POINTER};
+pub use gesture::{Gesture, GestureConfig, GestureEvent, GestureRecognizer, TrackPoint};
+pub use immediate::{ImmediateButton, ImmediateError, ImmediateUi, Response, WidgetId, BUTTON_SIZE};
+pub use interaction::{InteractionHook, InteractionInput, InteractionMachine, InteractionState, Transition};
+pub use layout::{Align, Cell, CellAlign, GridLayout, Track};
+pub use lifecycle::{UpdateScheduler, WidgetContext, WidgetHook, WidgetHooks};
+pub use magnifier::{Magnifier, MagnifierView};
+pub use modal::ModalStack;
+pub use picking::{controller_ray, Picker};
+pub use property::{Property, PropertyError, PropertyInfo, PropertyKind, PropertyValue, WidgetState};
+pub use proximity::{Proximity, ProximityChange, ProximityTracker};
+pub use scroll::KineticScroll;
+pub use signal::{BindingId, Bindings, Signal};
+pub use snap::{Snap, SnapGuide, SnapKind, Snapper};
+pub use tooltip::{Tooltip, TooltipFrame, TooltipPlacement, TooltipStyle, Tooltips};
+pub use tour::{CursorConstraint, Tour, TourEvent, TourFrame, TourPlayer, Waypoint};
+pub use widget::{WidgetChange, WidgetDesc, WidgetError, WidgetTree};
@@ -0,0 +1,149 @@
+//! Modal dialogs.
+//!
+//! A [`ModalStack`] holds the open dialogs, each a scene subtree, the last
+//! opened on top. While one is open everything outside the top dialog is
+//! behind it:
+//!
+//! - Pointer samples are passed through [`ModalStack::filter`], which drops
+//!   hits behind the dialog, so those widgets see neither hover nor presses. A
+//!   press that began before the dialog opened still ends with its release.
+//! - [`ModalStack::touchable_nodes`] leaves out nodes behind the dialog, so the
+//!   haptic renderer lets the tool pass through them.
+//! - [`ModalStack::dim`] gives how much to darken each node behind it.
+//! - Focus is trapped in the dialog, starting on its first widget in tab
+//!   order. Closing the dialog gives focus back to the widget that had it.
+
+use super::event::PointerSample;
+use super::focus::FocusManager;
+use crate::scene::{LayerMask, NodeId, Scene};
+
+/// One open dialog.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Modal {
+    root: NodeId,
+    /// Focus when the dialog opened.
+    restore: Option<NodeId>,
+}
+
+/// Open dialogs, blocking input to everything behind the top one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModalStack {
+    modals: Vec<Modal>,
+    /// Darkening of what is behind the top dialog, 0 to 1.
+    dim: f32,
+}
+
+impl ModalStack {
+    /// No dialogs, dimming the background by half.
+    pub fn new() -> Self {
+        Self { modals: Vec::new(), dim: 0.5 }
+    }
+
+    pub fn with_dim(mut self, dim: f32) -> Self {
+        self.dim = dim.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Opens the dialog rooted at `root` on top, trapping focus in it; returns
+    /// the widget focused in the dialog. Reopening a dialog moves it to the top.
+    pub fn open(&mut self, scene: &Scene, focus: &mut FocusManager, root: NodeId) -> Option<NodeId> {
+        let restore = match self.modals.iter().position(|m| m.root == root) {
+            Some(index) => self.modals.remove(index).restore,
+            None => focus.focused(scene),
+        };
+        self.modals.push(Modal { root, restore });
+        focus.set_trap(Some(root));
+        focus.blur();
+        focus.next(scene)
+    }
+
+    /// Closes the dialog rooted at `root` and every dialog opened above it,
+    /// giving focus back to whatever had it when `root` opened. False if
+    /// `root` is not open.
+    pub fn close(&mut self, scene: &Scene, focus: &mut FocusManager, root: NodeId) -> bool {
+        let Some(index) = self.modals.iter().position(|m| m.root == root) else {
+            return false;
+        };
+        let restore = self.modals[index].restore;
+        self.modals.truncate(index);
+        focus.set_trap(self.top());
+        match restore {
+            Some(node) if focus.focus(scene, node) => {}
+            _ => focus.blur(),
+        }
+        true
+    }
+
+    /// Closes the top dialog; returns its root.
+    pub fn close_top(&mut self, scene: &Scene, focus: &mut FocusManager) -> Option<NodeId> {
+        let root = self.top()?;
+        self.close(scene, focus, root);
+        Some(root)
+    }
+
+    /// Closes dialogs whose root left the scene; returns how many.
+    pub fn retain_in(&mut self, scene: &Scene, focus: &mut FocusManager) -> usize {
+        let before = self.modals.len();
+        while let Some(root) = self.modals.iter().map(|m| m.root).find(|&root| !scene.contains(root)) {
+            self.close(scene, focus, root);
+        }
+        before - self.modals.len()
+    }
+
+    /// Root of the dialog on top.
+    #[inline]
+    pub fn top(&self) -> Option<NodeId> {
+        self.modals.last().map(|m| m.root)
+    }
+
+    #[inline]
+    pub fn is_open(&self, root: NodeId) -> bool {
+        self.modals.iter().any(|m| m.root == root)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.modals.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.modals.is_empty()
+    }
+
+    /// Whether `node` takes input: it is in the top dialog, or none is open.
+    pub fn allows(&self, scene: &Scene, node: NodeId) -> bool {
+        self.top().is_none_or(|root| node == root || scene.is_ancestor(root, node))
+    }
+
+    /// `sample` with its hit dropped if the hit node is behind the top dialog.
+    pub fn filter(&self, scene: &Scene, sample: PointerSample) -> PointerSample {
+        PointerSample { hit: sample.hit.filter(|&node| self.allows(scene, node)), ..sample }
+    }
+
+    /// Touchable nodes on `mask` that are not behind the top dialog.
+    pub fn touchable_nodes(&self, scene: &Scene, mask: LayerMask) -> Vec<NodeId> {
+        let mut nodes = scene.touchable_nodes(mask);
+        nodes.retain(|&node| self.allows(scene, node));
+        nodes
+    }
+
+    /// How much to darken `node`: 0 unless it is behind the top dialog.
+    pub fn dim(&self, scene: &Scene, node: NodeId) -> f32 {
+        if self.allows(scene, node) {
+            0.0
+        } else {
+            self.dim
+        }
+    }
+}
+
+impl Default for ModalStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/modal_tests.rs"]
+mod tests;
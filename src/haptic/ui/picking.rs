@@ -0,0 +1,111 @@
+//! Picking: finding the widget a pointer ray points at.
+//!
+//! Input arrives as a [`Ray`]: through a pixel of the screen
+//! ([`CameraPose::screen_ray`](crate::scene::CameraPose::screen_ray)) or out of a tracked controller
+//! ([`controller_ray`]). A [`Picker`] casts it against a [`Raycaster`] snapshot
+//! of the scene and resolves the widget the pointer is on:
+//!
+//! - Only pickable nodes are hit.
+//! - Layers are searched in priority order, so an overlay layer in front of the
+//!   world wins even when a world node is nearer.
+//! - Disabled nodes, and everything under them, still block the ray but pick
+//!   nothing, so a greyed out button does not let clicks through to what is
+//!   behind it.
+//!
+//! [`Picker::sample`] turns the result into a [`PointerSample`] for the
+//! [`EventDispatcher`](super::EventDispatcher).
+
+use std::collections::HashSet;
+
+use super::event::{PointerId, PointerSample};
+use crate::core::{Quat, Vec3};
+use crate::geometry::Ray;
+use crate::scene::{LayerMask, NodeFlags, NodeId, Scene};
+use crate::spatial::{RaycastFilter, RaycastHit, Raycaster};
+
+/// Ray pointing out of a controller held at `position`, along its local -Z.
+pub fn controller_ray(position: Vec3, orientation: Quat) -> Ray {
+    Ray::new(position, orientation.rotate(-Vec3::unit_z()))
+}
+
+/// Resolves pointer rays to the widgets they point at.
+pub struct Picker {
+    raycaster: Raycaster,
+    /// Layer masks searched in order until one has a hit.
+    layers: Vec<LayerMask>,
+    disabled: HashSet<NodeId>,
+    max_distance: f32,
+}
+
+impl Picker {
+    /// Picker over every layer at once, with unlimited reach.
+    pub fn new(scene: &Scene) -> Self {
+        Self {
+            raycaster: Raycaster::from_scene(scene),
+            layers: vec![LayerMask::ALL],
+            disabled: HashSet::new(),
+            max_distance: f32::INFINITY,
+        }
+    }
+
+    /// Searches `layers` in order; nodes on none of them are never picked.
+    pub fn with_layers(mut self, layers: impl IntoIterator<Item = LayerMask>) -> Self {
+        self.layers = layers.into_iter().collect();
+        self
+    }
+
+    pub fn with_max_distance(mut self, max_distance: f32) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    /// Re-indexes the scene after nodes moved, resized or changed flags.
+    pub fn rebuild(&mut self, scene: &Scene) {
+        self.raycaster.rebuild(scene);
+    }
+
+    /// Enables or disables `node` and its subtree.
+    pub fn set_enabled(&mut self, node: NodeId, enabled: bool) {
+        if enabled {
+            self.disabled.remove(&node);
+        } else {
+            self.disabled.insert(node);
+        }
+    }
+
+    /// True unless `node` or one of its ancestors is disabled.
+    pub fn is_enabled(&self, scene: &Scene, node: NodeId) -> bool {
+        let mut current = Some(node);
+        while let Some(id) = current {
+            if self.disabled.contains(&id) {
+                return false;
+            }
+            current = scene.get(id).and_then(|n| n.parent());
+        }
+        true
+    }
+
+    /// Nearest pickable node along the ray on the first layer with a hit, unless
+    /// it is disabled.
+    pub fn pick(&self, scene: &Scene, ray: &Ray) -> Option<RaycastHit> {
+        for &mask in &self.layers {
+            let filter = RaycastFilter::new(NodeFlags::PICKABLE, mask).with_max_distance(self.max_distance);
+            if let Some(hit) = self.raycaster.cast_first(ray, &filter) {
+                return self.is_enabled(scene, hit.node).then_some(hit);
+            }
+        }
+        None
+    }
+
+    /// Sample for the event dispatcher: the pointer sits where the ray hits, or
+    /// at the ray origin when it hits nothing.
+    pub fn sample(&self, scene: &Scene, pointer: PointerId, ray: &Ray, buttons: u32, time_us: u64) -> PointerSample {
+        let hit = self.pick(scene, ray);
+        let position = hit.map_or(ray.origin, |h| h.point);
+        PointerSample::new(pointer, position, hit.map(|h| h.node), buttons, time_us)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/picking_tests.rs"]
+mod tests;
@@ -0,0 +1,315 @@
+//! Typed property access for widget state structs.
+//!
+//! The `haptic_widget!` macro plays the role of a `#[derive(HapticWidget)]`: wrapped
+//! around a plain struct definition it generates property registration, by-name
+//! get/set used by bindings, and text serialization. It is a declarative macro so the
+//! crate does not need a separate proc-macro package.
+
+use crate::core::Vec3;
+use std::fmt;
+
+// ============================================================================
+// Property Values
+// ============================================================================
+
+/// Type tag of a widget property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PropertyKind {
+    Bool,
+    F32,
+    Vec3,
+    Text,
+}
+
+/// Dynamically typed property value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+    Bool(bool),
+    F32(f32),
+    Vec3(Vec3),
+    Text(String),
+}
+
+impl PropertyValue {
+    #[inline]
+    pub fn kind(&self) -> PropertyKind {
+        match self {
+            PropertyValue::Bool(_) => PropertyKind::Bool,
+            PropertyValue::F32(_) => PropertyKind::F32,
+            PropertyValue::Vec3(_) => PropertyKind::Vec3,
+            PropertyValue::Text(_) => PropertyKind::Text,
+        }
+    }
+
+    /// Parses the serialized form produced by `Display`.
+    pub fn parse(kind: PropertyKind, text: &str) -> Option<Self> {
+        match kind {
+            PropertyKind::Bool => text.parse().ok().map(PropertyValue::Bool),
+            PropertyKind::F32 => text.parse().ok().map(PropertyValue::F32),
+            PropertyKind::Vec3 => {
+                let mut parts = text.split(',').map(|p| p.trim().parse::<f32>());
+                match (parts.next(), parts.next(), parts.next(), parts.next()) {
+                    (Some(Ok(x)), Some(Ok(y)), Some(Ok(z)), None) => Some(PropertyValue::Vec3(Vec3::new(x, y, z))),
+                    _ => None,
+                }
+            }
+            PropertyKind::Text => Some(PropertyValue::Text(unescape(text))),
+        }
+    }
+}
+
+impl fmt::Display for PropertyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyValue::Bool(b) => write!(f, "{}", b),
+            PropertyValue::F32(v) => write!(f, "{}", v),
+            PropertyValue::Vec3(v) => write!(f, "{},{},{}", v.x, v.y, v.z),
+            PropertyValue::Text(s) => f.write_str(&escape(s)),
+        }
+    }
+}
+
+/// Escapes newlines and backslashes so text values fit on one line.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Rust types that can be exposed as widget properties.
+pub trait Property: Sized {
+    const KIND: PropertyKind;
+    fn to_value(&self) -> PropertyValue;
+    fn from_value(value: PropertyValue) -> Option<Self>;
+}
+
+impl Property for bool {
+    const KIND: PropertyKind = PropertyKind::Bool;
+    fn to_value(&self) -> PropertyValue {
+        PropertyValue::Bool(*self)
+    }
+    fn from_value(value: PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Bool(b) => Some(b),
+            _ => None,
+        }
+    }
+}
+
+impl Property for f32 {
+    const KIND: PropertyKind = PropertyKind::F32;
+    fn to_value(&self) -> PropertyValue {
+        PropertyValue::F32(*self)
+    }
+    fn from_value(value: PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::F32(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl Property for Vec3 {
+    const KIND: PropertyKind = PropertyKind::Vec3;
+    fn to_value(&self) -> PropertyValue {
+        PropertyValue::Vec3(*self)
+    }
+    fn from_value(value: PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Vec3(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl Property for String {
+    const KIND: PropertyKind = PropertyKind::Text;
+    fn to_value(&self) -> PropertyValue {
+        PropertyValue::Text(self.clone())
+    }
+    fn from_value(value: PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Text(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// Widget State
+// ============================================================================
+
+/// Registration record of one property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PropertyInfo {
+    pub name: &'static str,
+    pub kind: PropertyKind,
+}
+
+/// Errors from by-name property access.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropertyError {
+    Unknown(String),
+    TypeMismatch { name: &'static str, expected: PropertyKind },
+    Parse { line: usize },
+}
+
+impl fmt::Display for PropertyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyError::Unknown(name) => write!(f, "unknown property '{}'", name),
+            PropertyError::TypeMismatch { name, expected } => {
+                write!(f, "property '{}' expects a {:?} value", name, expected)
+            }
+            PropertyError::Parse { line } => write!(f, "malformed property on line {}", line),
+        }
+    }
+}
+
+impl std::error::Error for PropertyError {}
+
+/// Reflection interface of widget state structs; usually generated by `haptic_widget!`.
+pub trait WidgetState {
+    /// Name of the widget type.
+    fn type_name() -> &'static str
+    where
+        Self: Sized;
+
+    /// All registered properties in declaration order.
+    fn properties(&self) -> &'static [PropertyInfo];
+
+    fn get(&self, name: &str) -> Option<PropertyValue>;
+
+    fn set(&mut self, name: &str, value: PropertyValue) -> Result<(), PropertyError>;
+
+    /// Properties whose values differ from `other`, in declaration order.
+    fn diff(&self, other: &dyn WidgetState) -> Vec<(&'static str, PropertyValue)> {
+        self.properties()
+            .iter()
+            .filter_map(|p| {
+                let value = self.get(p.name)?;
+                (other.get(p.name).as_ref() != Some(&value)).then_some((p.name, value))
+            })
+            .collect()
+    }
+
+    /// Serializes all properties as `name = value` lines.
+    fn serialize(&self) -> String {
+        let mut out = String::new();
+        for p in self.properties() {
+            if let Some(value) = self.get(p.name) {
+                out.push_str(p.name);
+                out.push_str(" = ");
+                out.push_str(&value.to_string());
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// Applies `name = value` lines produced by `serialize`. Blank lines and `#` comments are skipped.
+    fn deserialize(&mut self, text: &str) -> Result<(), PropertyError> {
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim_start();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, raw) = line.split_once(" = ").ok_or(PropertyError::Parse { line: i + 1 })?;
+            let info = self
+                .properties()
+                .iter()
+                .find(|p| p.name == name.trim())
+                .ok_or_else(|| PropertyError::Unknown(name.trim().to_string()))?;
+            let value = PropertyValue::parse(info.kind, raw).ok_or(PropertyError::Parse { line: i + 1 })?;
+            self.set(info.name, value)?;
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Macro
+// ============================================================================
+
+/// Defines a widget state struct and implements `WidgetState` for it.
+///
+/// ```ignore
+/// haptic_widget! {
+///     #[derive(Debug, Clone, Default)]
+///     pub struct DialState {
+///         pub value: f32,
+///         pub caption: String,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! haptic_widget {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $( $(#[$fmeta:meta])* $fvis:vis $field:ident : $ty:ty ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $( $(#[$fmeta])* $fvis $field: $ty ),*
+        }
+
+        impl $crate::ui::WidgetState for $name {
+            fn type_name() -> &'static str {
+                stringify!($name)
+            }
+
+            fn properties(&self) -> &'static [$crate::ui::PropertyInfo] {
+                const PROPERTIES: &[$crate::ui::PropertyInfo] = &[
+                    $( $crate::ui::PropertyInfo {
+                        name: stringify!($field),
+                        kind: <$ty as $crate::ui::Property>::KIND,
+                    } ),*
+                ];
+                PROPERTIES
+            }
+
+            fn get(&self, name: &str) -> Option<$crate::ui::PropertyValue> {
+                match name {
+                    $( stringify!($field) => Some($crate::ui::Property::to_value(&self.$field)), )*
+                    _ => None,
+                }
+            }
+
+            fn set(&mut self, name: &str, value: $crate::ui::PropertyValue) -> Result<(), $crate::ui::PropertyError> {
+                match name {
+                    $( stringify!($field) => {
+                        self.$field = <$ty as $crate::ui::Property>::from_value(value).ok_or(
+                            $crate::ui::PropertyError::TypeMismatch {
+                                name: stringify!($field),
+                                expected: <$ty as $crate::ui::Property>::KIND,
+                            },
+                        )?;
+                        Ok(())
+                    } )*
+                    _ => Err($crate::ui::PropertyError::Unknown(name.to_string())),
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+#[path = "tests/property_tests.rs"]
+mod tests;
@@ -0,0 +1,159 @@
+//! Hover and proximity from cursor distance, with a pre-contact cue.
+//!
+//! Pointer hover only knows what a ray crosses; a haptic tool also needs to know
+//! what it is close to. A [`ProximityTracker`] measures the distance from the
+//! cursor to the box of every pickable widget and keeps a [`Proximity`] per
+//! widget: far, near (within the near distance) or hovering (within the hover
+//! distance, or inside). Leaving a state takes a little more distance than
+//! entering it, so a cursor resting on a boundary does not flicker.
+//!
+//! While the tool is near a widget but not yet on it, [`ProximityTracker::cue`]
+//! gives a faint vibration that grows as the gap closes, so targets can be found
+//! without looking. It stops on contact, where the surface itself is felt.
+
+use std::collections::HashMap;
+
+use crate::core::{Hertz, Meters, Vec3};
+use crate::effects::Waveform;
+use crate::scene::{LayerMask, NodeId, Scene};
+
+/// Factor on a distance threshold for leaving the state it bounds.
+const HYSTERESIS: f32 = 1.1;
+
+/// How close the cursor is to a widget, ordered from far to hovering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Proximity {
+    #[default]
+    Far,
+    Near,
+    Hover,
+}
+
+/// A widget whose proximity changed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProximityChange {
+    pub node: NodeId,
+    pub from: Proximity,
+    pub to: Proximity,
+    /// Distance from the cursor to the widget's box.
+    pub distance: f32,
+}
+
+/// Proximity of the cursor to every pickable widget.
+#[derive(Debug, Clone)]
+pub struct ProximityTracker {
+    pub near_distance: Meters,
+    pub hover_distance: Meters,
+    /// Layers whose widgets are tracked.
+    pub mask: LayerMask,
+    /// Cue played at full strength, just before contact.
+    cue: Waveform,
+    states: HashMap<NodeId, (Proximity, f32)>,
+}
+
+impl ProximityTracker {
+    /// Near within 3 cm, hovering within 2 mm, cueing with a 250 Hz hum at 0.25.
+    pub fn new() -> Self {
+        Self {
+            near_distance: Meters(0.03),
+            hover_distance: Meters(0.002),
+            mask: LayerMask::ALL,
+            cue: Waveform::sine(Hertz(250.0), None).with_intensity(0.25),
+            states: HashMap::new(),
+        }
+    }
+
+    pub fn with_distances(mut self, near: Meters, hover: Meters) -> Self {
+        self.near_distance = near;
+        self.hover_distance = hover;
+        self
+    }
+
+    pub fn with_mask(mut self, mask: LayerMask) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    pub fn with_cue(mut self, cue: Waveform) -> Self {
+        self.cue = cue;
+        self
+    }
+
+    /// Proximity of `node` as of the last update.
+    pub fn state(&self, node: NodeId) -> Proximity {
+        self.states.get(&node).map_or(Proximity::Far, |&(state, _)| state)
+    }
+
+    /// Distance from the cursor to `node` as of the last update, if it is near.
+    pub fn distance(&self, node: NodeId) -> Option<f32> {
+        self.states.get(&node).map(|&(_, distance)| distance)
+    }
+
+    /// Widgets the cursor is hovering.
+    pub fn hovered(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.states.iter().filter(|(_, (state, _))| *state == Proximity::Hover).map(|(&node, _)| node)
+    }
+
+    /// Re-measures every widget against the cursor at `cursor`; returns the
+    /// widgets whose state changed. Removed or hidden widgets go far.
+    pub fn update(&mut self, scene: &Scene, cursor: Vec3) -> Vec<ProximityChange> {
+        let near = self.near_distance.value();
+        let hover = self.hover_distance.value().min(near);
+        let mut changes = Vec::new();
+        let mut states = HashMap::new();
+        for node in scene.pickable_nodes(self.mask) {
+            if scene.get(node).is_none_or(|n| n.size.max_component() <= 0.0) {
+                continue;
+            }
+            // Measured to what clipping leaves of the widget
+            let Some(bounds) = scene.clipped_bounds(node) else { continue };
+            let distance = bounds.distance_squared(cursor).sqrt();
+            let from = self.state(node);
+            let slack = |state| if from >= state { HYSTERESIS } else { 1.0 };
+            let to = if distance <= hover * slack(Proximity::Hover) {
+                Proximity::Hover
+            } else if distance <= near * slack(Proximity::Near) {
+                Proximity::Near
+            } else {
+                Proximity::Far
+            };
+            if to != from {
+                changes.push(ProximityChange { node, from, to, distance });
+            }
+            if to != Proximity::Far {
+                states.insert(node, (to, distance));
+            }
+        }
+        for (&node, &(from, distance)) in &self.states {
+            if !states.contains_key(&node) && !changes.iter().any(|c| c.node == node) {
+                changes.push(ProximityChange { node, from, to: Proximity::Far, distance });
+            }
+        }
+        self.states = states;
+        changes
+    }
+
+    /// Pre-contact cue for this frame: the cue waveform scaled by how far the
+    /// nearest widget is between the near and hover distances, squared. None
+    /// when nothing is near, and on contact with anything.
+    pub fn cue(&self) -> Option<Waveform> {
+        if self.states.values().any(|&(state, _)| state == Proximity::Hover) {
+            return None;
+        }
+        let nearest = self.states.values().map(|&(_, distance)| distance).min_by(f32::total_cmp)?;
+        let (near, hover) = (self.near_distance.value(), self.hover_distance.value());
+        let closeness = (1.0 - (nearest - hover) / (near - hover).max(f32::EPSILON)).clamp(0.0, 1.0);
+        let intensity = self.cue.intensity * closeness * closeness;
+        (intensity > 0.0).then(|| self.cue.with_intensity(intensity))
+    }
+}
+
+impl Default for ProximityTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/proximity_tests.rs"]
+mod tests;
@@ -0,0 +1,319 @@
+//! Kinetic scrolling for scrollable containers.
+//!
+//! A [`KineticScroll`] holds how far content is scrolled along one axis,
+//! between 0 and an extent, and moves it like a physical sheet:
+//!
+//! - Dragged, the content follows the drag. Past either end it stretches on a
+//!   rubber band, moving less the further out it is pulled.
+//! - Released, it keeps the flick velocity, measured over the last 50 ms of
+//!   the drag, and coasts, slowing down with friction.
+//! - Content out past an end springs back, critically damped, so coasting into
+//!   an end overshoots a little and settles without wobbling.
+//! - Each time the content reaches an end it plays a dull thunk, stronger the
+//!   faster it hit. Content resting at an end does not thunk again until it
+//!   has left it.
+//!
+//! Positions are in meters of content; a container calls [`press`],
+//! [`drag`] and [`release`] from its input and [`advance`] once a frame.
+//!
+//! [`press`]: KineticScroll::press
+//! [`drag`]: KineticScroll::drag
+//! [`release`]: KineticScroll::release
+//! [`advance`]: KineticScroll::advance
+
+use std::collections::VecDeque;
+
+use crate::core::Seconds;
+use crate::effects::Waveform;
+
+/// Span of the drag the flick velocity is measured over, in seconds.
+const FLICK_WINDOW: f32 = 0.05;
+
+/// Sharpness of the end-stop thunk; low, so it lands as a knock, not a tick.
+const THUNK_SHARPNESS: f32 = 0.1;
+
+/// Weakest thunk, for an end reached very slowly.
+const MIN_THUNK: f32 = 0.1;
+
+/// Overscroll closer than this to the end, while slower than the rest speed, settles.
+const SETTLE_DISTANCE: f32 = 1e-5;
+
+/// Scroll position of content with flick, friction and rubber-band ends.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KineticScroll {
+    /// Rate at which coasting slows down, per second.
+    pub friction: f32,
+    /// Coasting slower than this, in meters per second, stops.
+    pub rest_speed: f32,
+    /// How far the content moves per meter dragged just past an end, 0 to 1.
+    pub rubber_band: f32,
+    /// Farthest the content can be pulled or thrown past an end.
+    pub max_overscroll: f32,
+    /// Angular frequency of the spring pulling content back from past an end,
+    /// in radians per second.
+    pub spring: f32,
+    /// Speed at which reaching an end gives a full-strength thunk, in meters
+    /// per second.
+    pub thunk_speed: f32,
+    extent: f32,
+    position: f32,
+    /// In meters per second, positive scrolling further into the content.
+    velocity: f32,
+    /// Position the drag would have reached with no rubber band, while dragging.
+    drag: Option<f32>,
+    /// Recent drag positions and when they were reached, oldest first.
+    samples: VecDeque<(f32, f32)>,
+    /// Time dragged since the press.
+    time: f32,
+    /// Whether the content was at or past an end at the last step.
+    at_end: bool,
+    feedback: Vec<Waveform>,
+}
+
+impl KineticScroll {
+    /// Content scrolling from 0 to `extent`, slowing at 4/s and stretching at
+    /// most 2 cm past the ends.
+    pub fn new(extent: f32) -> Self {
+        Self {
+            friction: 4.0,
+            rest_speed: 0.002,
+            rubber_band: 0.55,
+            max_overscroll: 0.02,
+            spring: 25.0,
+            thunk_speed: 0.2,
+            extent: extent.max(0.0),
+            position: 0.0,
+            velocity: 0.0,
+            drag: None,
+            samples: VecDeque::new(),
+            time: 0.0,
+            at_end: true,
+            feedback: Vec::new(),
+        }
+    }
+
+    pub fn with_friction(mut self, friction: f32) -> Self {
+        self.friction = friction.max(0.0);
+        self
+    }
+
+    /// Rubber band giving `resistance` of the drag just past an end and
+    /// stretching at most `max_overscroll`; zero for hard ends.
+    pub fn with_rubber_band(mut self, resistance: f32, max_overscroll: f32) -> Self {
+        self.rubber_band = resistance.clamp(0.0, 1.0);
+        self.max_overscroll = max_overscroll.max(0.0);
+        self
+    }
+
+    pub fn with_spring(mut self, angular_frequency: f32) -> Self {
+        self.spring = angular_frequency.max(0.0);
+        self
+    }
+
+    /// Scrollable length.
+    #[inline]
+    pub fn extent(&self) -> f32 {
+        self.extent
+    }
+
+    /// Changes the scrollable length, moving the content back inside it and
+    /// stopping it if it no longer fits.
+    pub fn set_extent(&mut self, extent: f32) {
+        self.extent = extent.max(0.0);
+        if self.drag.is_none() && self.position > self.extent {
+            self.position = self.extent;
+            self.velocity = 0.0;
+        }
+        self.at_end = self.is_at_end();
+    }
+
+    /// How far the content is scrolled, past the ends while stretched.
+    #[inline]
+    pub fn position(&self) -> f32 {
+        self.position
+    }
+
+    #[inline]
+    pub fn velocity(&self) -> f32 {
+        self.velocity
+    }
+
+    /// Distance past the nearer end, negative before the start; 0 within.
+    pub fn overscroll(&self) -> f32 {
+        if self.position < 0.0 {
+            self.position
+        } else {
+            (self.position - self.extent).max(0.0)
+        }
+    }
+
+    #[inline]
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// Whether the content is coasting or springing back.
+    pub fn is_moving(&self) -> bool {
+        self.drag.is_none() && (self.velocity != 0.0 || self.overscroll() != 0.0)
+    }
+
+    /// Starts a drag, catching the content where it is.
+    pub fn press(&mut self) {
+        let over = self.overscroll();
+        let end = self.position - over;
+        let raw = end + over.signum() * self.unstretch(over.abs());
+        self.drag = Some(raw);
+        self.velocity = 0.0;
+        self.time = 0.0;
+        self.samples.clear();
+        self.samples.push_back((0.0, raw));
+    }
+
+    /// Drags the content on by `delta` over `dt`. Starts a drag if none is held.
+    pub fn drag(&mut self, delta: f32, dt: Seconds) {
+        if self.drag.is_none() {
+            self.press();
+        }
+        let dt = dt.value().max(0.0);
+        let raw = self.drag.unwrap_or(self.position) + delta;
+        self.drag = Some(raw);
+        self.time += dt;
+        self.samples.push_back((self.time, raw));
+        while self.samples.len() > 2 && self.time - self.samples[1].0 >= FLICK_WINDOW {
+            self.samples.pop_front();
+        }
+
+        self.position = if raw < 0.0 {
+            -self.stretch(-raw)
+        } else if raw > self.extent {
+            self.extent + self.stretch(raw - self.extent)
+        } else {
+            raw
+        };
+        let speed = if dt > 0.0 { delta.abs() / dt } else { 0.0 };
+        self.hit_end(speed);
+    }
+
+    /// Lets go, leaving the content coasting at the flick velocity.
+    pub fn release(&mut self) {
+        if self.drag.take().is_none() {
+            return;
+        }
+        let (Some(&(t0, p0)), Some(&(t1, p1))) = (self.samples.front(), self.samples.back()) else {
+            return;
+        };
+        self.velocity = if t1 > t0 { (p1 - p0) / (t1 - t0) } else { 0.0 };
+        if self.velocity.abs() < self.rest_speed {
+            self.velocity = 0.0;
+        }
+        self.samples.clear();
+    }
+
+    /// Ends any drag or coasting where the content is; stretched content still
+    /// springs back.
+    pub fn stop(&mut self) {
+        self.drag = None;
+        self.velocity = 0.0;
+        self.samples.clear();
+    }
+
+    /// Jumps to `position` within the ends, stopping.
+    pub fn scroll_to(&mut self, position: f32) {
+        self.stop();
+        self.position = position.clamp(0.0, self.extent);
+        self.at_end = self.is_at_end();
+    }
+
+    /// Coasts or springs back over `dt`; does nothing while dragged.
+    pub fn advance(&mut self, dt: Seconds) {
+        let dt = dt.value().max(0.0);
+        if self.drag.is_some() || dt == 0.0 {
+            return;
+        }
+        let over = self.overscroll();
+        if over != 0.0 {
+            // Critically damped spring toward the end
+            let end = self.position - over;
+            let w = self.spring;
+            let decay = (-w * dt).exp();
+            let moved = (over + (self.velocity + w * over) * dt) * decay;
+            let velocity = (self.velocity - w * (self.velocity + w * over) * dt) * decay;
+            if moved * over <= 0.0 || (moved.abs() < SETTLE_DISTANCE && velocity.abs() < self.rest_speed) {
+                self.position = end;
+                self.velocity = 0.0;
+            } else {
+                self.position = end + moved;
+                self.velocity = velocity;
+            }
+        } else if self.velocity != 0.0 {
+            let speed = self.velocity.abs();
+            self.position += self.velocity * dt;
+            self.velocity *= (-self.friction * dt).exp();
+            if self.velocity.abs() < self.rest_speed {
+                self.velocity = 0.0;
+            }
+            self.hit_end(speed);
+        }
+        self.limit_overscroll();
+    }
+
+    /// Thunks played since the last call.
+    pub fn take_feedback(&mut self) -> Vec<Waveform> {
+        std::mem::take(&mut self.feedback)
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.position <= 0.0 || self.position >= self.extent
+    }
+
+    /// Thunks if the content just reached an end moving at `speed`.
+    fn hit_end(&mut self, speed: f32) {
+        let at_end = self.is_at_end();
+        if at_end && !self.at_end {
+            let strength = if self.thunk_speed > 0.0 { speed / self.thunk_speed } else { 1.0 };
+            let intensity = strength.clamp(MIN_THUNK, 1.0);
+            self.feedback.push(Waveform::click().with_intensity(intensity).with_sharpness(THUNK_SHARPNESS));
+        }
+        self.at_end = at_end;
+    }
+
+    /// Keeps coasting content within the farthest stretch.
+    fn limit_overscroll(&mut self) {
+        let over = self.overscroll();
+        if over.abs() > self.max_overscroll {
+            self.position -= over - over.signum() * self.max_overscroll;
+            if self.velocity * over > 0.0 {
+                self.velocity = 0.0;
+            }
+        }
+    }
+
+    /// How far content dragged `distance` past an end is stretched.
+    fn stretch(&self, distance: f32) -> f32 {
+        let limit = self.max_overscroll;
+        if limit <= 0.0 {
+            return 0.0;
+        }
+        limit * (1.0 - 1.0 / (distance * self.rubber_band / limit + 1.0))
+    }
+
+    /// Drag distance past an end that stretches the content by `stretched`.
+    fn unstretch(&self, stretched: f32) -> f32 {
+        let limit = self.max_overscroll;
+        if limit <= 0.0 || self.rubber_band <= 0.0 || stretched <= 0.0 {
+            return 0.0;
+        }
+        let stretched = stretched.min(limit * 0.999);
+        stretched * limit / (self.rubber_band * (limit - stretched))
+    }
+}
+
+impl Default for KineticScroll {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/scroll_tests.rs"]
+mod tests;
@@ -0,0 +1,210 @@
+//! Signals: application state bound to widget properties.
+//!
+//! A [`Signal`] is a shared value the application writes, such as a measured
+//! temperature; clones share it. [`Bindings`] tie signals to properties of
+//! widgets in a [`WidgetTree`], directly or through a mapping (the temperature
+//! as label text, or as a slider position).
+//!
+//! Writing a signal only marks it changed. [`Bindings::flush`], called once per
+//! frame, pushes the latest value of each changed signal into the properties
+//! bound to it, so any number of writes in a frame cost one update per
+//! property. Bindings to widgets that left the tree are dropped on flush.
+
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use super::property::{Property, PropertyError};
+use super::widget::WidgetTree;
+use crate::scene::NodeId;
+
+// ============================================================================
+// Signals
+// ============================================================================
+
+struct Slot<T> {
+    value: T,
+    /// Bumped on every write.
+    version: u64,
+}
+
+/// A shared value that widget properties can follow.
+pub struct Signal<T> {
+    slot: Arc<Mutex<Slot<T>>>,
+}
+
+impl<T> Signal<T> {
+    pub fn new(value: T) -> Self {
+        Self { slot: Arc::new(Mutex::new(Slot { value, version: 0 })) }
+    }
+
+    pub fn set(&self, value: T) {
+        let mut slot = self.lock();
+        slot.value = value;
+        slot.version += 1;
+    }
+
+    /// Changes the value in place.
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        let mut slot = self.lock();
+        f(&mut slot.value);
+        slot.version += 1;
+    }
+
+    /// Calls `f` with the current value.
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&self.lock().value)
+    }
+
+    /// Number of writes so far.
+    pub fn version(&self) -> u64 {
+        self.lock().version
+    }
+
+    /// The slot, even if a panicking `update` left it poisoned.
+    fn lock(&self) -> MutexGuard<'_, Slot<T>> {
+        self.slot.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<T: Clone> Signal<T> {
+    pub fn get(&self) -> T {
+        self.with(T::clone)
+    }
+}
+
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        Self { slot: Arc::clone(&self.slot) }
+    }
+}
+
+impl<T: Default> Default for Signal<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Signal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let slot = self.lock();
+        f.debug_struct("Signal").field("value", &slot.value).field("version", &slot.version).finish()
+    }
+}
+
+// ============================================================================
+// Bindings
+// ============================================================================
+
+/// Identifies a binding made with [`Bindings`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BindingId(u32);
+
+/// Pushes a signal into one property if it changed; reports whether it did.
+type Push = Box<dyn FnMut(&mut WidgetTree, NodeId) -> Result<bool, PropertyError> + Send>;
+
+struct Binding {
+    id: BindingId,
+    node: NodeId,
+    push: Push,
+}
+
+/// Signals bound to widget properties.
+#[derive(Default)]
+pub struct Bindings {
+    bindings: Vec<Binding>,
+    next_id: u32,
+}
+
+impl Bindings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keeps property `name` of the widget on `node` equal to `signal`.
+    pub fn bind<T>(&mut self, signal: &Signal<T>, node: NodeId, name: &'static str) -> BindingId
+    where
+        T: Property + Clone + Send + 'static,
+    {
+        self.bind_map(signal, node, name, T::clone)
+    }
+
+    /// Keeps property `name` of the widget on `node` equal to `map` of `signal`.
+    pub fn bind_map<T, U, F>(&mut self, signal: &Signal<T>, node: NodeId, name: &'static str, map: F) -> BindingId
+    where
+        T: Send + 'static,
+        U: Property,
+        F: Fn(&T) -> U + Send + 'static,
+    {
+        let signal = signal.clone();
+        let mut pushed: Option<u64> = None;
+        let push = move |tree: &mut WidgetTree, node: NodeId| {
+            let (version, value) = {
+                let slot = signal.lock();
+                if pushed == Some(slot.version) {
+                    return Ok(false);
+                }
+                (slot.version, map(&slot.value))
+            };
+            tree.set(node, name, value)?;
+            pushed = Some(version);
+            Ok(true)
+        };
+        let id = BindingId(self.next_id);
+        self.next_id += 1;
+        self.bindings.push(Binding { id, node, push: Box::new(push) });
+        id
+    }
+
+    pub fn unbind(&mut self, id: BindingId) -> bool {
+        let before = self.bindings.len();
+        self.bindings.retain(|b| b.id != id);
+        self.bindings.len() < before
+    }
+
+    /// Removes every binding to the widget on `node`; returns how many there were.
+    pub fn unbind_node(&mut self, node: NodeId) -> usize {
+        let before = self.bindings.len();
+        self.bindings.retain(|b| b.node != node);
+        before - self.bindings.len()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bindings.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+
+    /// Pushes every signal written since the last flush (or never pushed)
+    /// into its properties; returns how many properties were set.
+    ///
+    /// Bindings that fail, such as to a property of the wrong type, are skipped
+    /// and reported with their errors; the rest still update. A failed binding
+    /// is retried on the next flush.
+    pub fn flush(&mut self, tree: &mut WidgetTree) -> (usize, Vec<(BindingId, PropertyError)>) {
+        self.bindings.retain(|b| tree.state(b.node).is_some());
+        let mut count = 0;
+        let mut errors = Vec::new();
+        for binding in &mut self.bindings {
+            match (binding.push)(tree, binding.node) {
+                Ok(true) => count += 1,
+                Ok(false) => {}
+                Err(e) => errors.push((binding.id, e)),
+            }
+        }
+        (count, errors)
+    }
+}
+
+impl fmt::Debug for Bindings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bindings").field("len", &self.bindings.len()).field("next_id", &self.next_id).finish()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/signal_tests.rs"]
+mod tests;
@@ -0,0 +1,312 @@
+//! Snapping for placing and turning objects.
+//!
+//! A [`Snapper`] pulls a position being placed onto something nearby, in
+//! order of precedence (nodes being cut to their clip regions):
+//!
+//! - a corner of a node's box within the snap radius,
+//! - the nearest point of one of its edges within the radius,
+//! - the nearest point of one of its faces within the radius,
+//! - the nearest point of the grid, when a grid spacing is set.
+//!
+//! Angles snap to multiples of the angle step. [`DragDrop`](super::DragDrop)
+//! and [`Gizmo`](crate::widgets::Gizmo) take a snapper for their drags.
+//!
+//! The snap in effect is kept as a [`Snap`] whose [`SnapGuide`] describes what
+//! to draw: the grid point, corner, edge or face snapped to, or the angle. A
+//! click plays each time a snap engages on a new feature (another corner, edge,
+//! face, grid point or angle step), collected with [`Snapper::take_feedback`].
+
+use crate::core::{Rad, Vec3};
+use crate::effects::Waveform;
+use crate::geometry::{closest_point_on_segment, Aabb};
+use crate::scene::{LayerMask, NodeId, Scene};
+
+/// Intensity of the click played as a snap engages.
+const CLICK_INTENSITY: f32 = 0.5;
+
+/// What a position or angle snapped to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SnapKind {
+    Grid,
+    Surface,
+    Vertex,
+    Edge,
+    Angle,
+}
+
+/// What to draw to show a snap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SnapGuide {
+    /// Crosshair on a grid point, `step` apart from its neighbours.
+    Grid { point: Vec3, step: f32 },
+    /// Contact disc on a face.
+    Surface { point: Vec3, normal: Vec3 },
+    /// Marker on a corner.
+    Vertex { point: Vec3 },
+    /// Highlighted edge from `from` to `to`, snapped at `point`.
+    Edge { from: Vec3, to: Vec3, point: Vec3 },
+    /// Arc of `angle` about `axis`.
+    Angle { axis: Vec3, angle: Rad },
+}
+
+/// Feature a snap engaged on; a click plays when it changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Feature {
+    Cell([i32; 3]),
+    Vertex(u8),
+    Edge(u8),
+    Face(u8),
+    Step(i32),
+}
+
+/// The snap in effect.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Snap {
+    pub kind: SnapKind,
+    /// Node snapped to; none for the grid and angles.
+    pub node: Option<NodeId>,
+    pub guide: SnapGuide,
+    feature: Feature,
+}
+
+impl Snap {
+    /// Snapped position; none for angles.
+    pub fn position(&self) -> Option<Vec3> {
+        match self.guide {
+            SnapGuide::Grid { point, .. }
+            | SnapGuide::Surface { point, .. }
+            | SnapGuide::Vertex { point }
+            | SnapGuide::Edge { point, .. } => Some(point),
+            SnapGuide::Angle { .. } => None,
+        }
+    }
+
+    /// Outward normal of the face snapped to.
+    pub fn normal(&self) -> Option<Vec3> {
+        match self.guide {
+            SnapGuide::Surface { normal, .. } => Some(normal),
+            _ => None,
+        }
+    }
+}
+
+/// Snaps positions to nodes and a grid, and angles to steps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapper {
+    /// Grid spacing; no grid when unset.
+    pub grid: Option<f32>,
+    /// A point of the grid.
+    pub grid_origin: Vec3,
+    pub angle_step: Option<Rad>,
+    /// Distance within which corners, edges and faces attract.
+    pub radius: f32,
+    pub vertices: bool,
+    pub edges: bool,
+    pub surfaces: bool,
+    /// Layers whose nodes can be snapped to.
+    pub layers: LayerMask,
+    /// Played as a snap engages.
+    pub click: Waveform,
+    current: Option<Snap>,
+    feedback: Vec<Waveform>,
+}
+
+impl Snapper {
+    /// Snaps to corners, edges and faces within 1 cm on every layer, with no
+    /// grid and no angle step.
+    pub fn new() -> Self {
+        Self {
+            grid: None,
+            grid_origin: Vec3::zero(),
+            angle_step: None,
+            radius: 0.01,
+            vertices: true,
+            edges: true,
+            surfaces: true,
+            layers: LayerMask::ALL,
+            click: Waveform::click().with_intensity(CLICK_INTENSITY),
+            current: None,
+            feedback: Vec::new(),
+        }
+    }
+
+    pub fn with_grid(mut self, step: Option<f32>) -> Self {
+        self.grid = step.filter(|s| *s > 0.0);
+        self
+    }
+
+    pub fn with_grid_origin(mut self, origin: Vec3) -> Self {
+        self.grid_origin = origin;
+        self
+    }
+
+    pub fn with_angle_step(mut self, step: Option<Rad>) -> Self {
+        self.angle_step = step.filter(|s| s.0 > 0.0);
+        self
+    }
+
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius.max(0.0);
+        self
+    }
+
+    /// Turns snapping to corners, edges and faces on or off.
+    pub fn with_features(mut self, vertices: bool, edges: bool, surfaces: bool) -> Self {
+        self.vertices = vertices;
+        self.edges = edges;
+        self.surfaces = surfaces;
+        self
+    }
+
+    pub fn with_layers(mut self, layers: LayerMask) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    pub fn with_click(mut self, click: Waveform) -> Self {
+        self.click = click;
+        self
+    }
+
+    /// Snap in effect, for drawing its guide.
+    #[inline]
+    pub fn current(&self) -> Option<&Snap> {
+        self.current.as_ref()
+    }
+
+    /// `position` snapped to the nodes on the snapper's layers, other than
+    /// `ignore` and their descendants, or to the grid; unchanged if nothing is
+    /// in reach.
+    pub fn snap(&mut self, scene: &Scene, position: Vec3, ignore: &[NodeId]) -> Vec3 {
+        let snap = self.snap_to_nodes(scene, position, ignore).or_else(|| self.snap_to_grid(position));
+        self.engage(snap);
+        snap.and_then(|s| s.position()).unwrap_or(position)
+    }
+
+    /// `angle` about `axis` rounded to the angle step; unchanged without one.
+    pub fn snap_angle(&mut self, axis: Vec3, angle: Rad) -> Rad {
+        let Some(step) = self.angle_step else {
+            self.engage(None);
+            return angle;
+        };
+        let index = (angle.0 / step.0).round();
+        let snapped = Rad(index * step.0);
+        self.engage(Some(Snap {
+            kind: SnapKind::Angle,
+            node: None,
+            guide: SnapGuide::Angle { axis, angle: snapped },
+            feature: Feature::Step(index as i32),
+        }));
+        snapped
+    }
+
+    /// Ends the snap in effect, e.g. when the drag ends.
+    pub fn release(&mut self) {
+        self.current = None;
+    }
+
+    /// Clicks played since the last call.
+    pub fn take_feedback(&mut self) -> Vec<Waveform> {
+        std::mem::take(&mut self.feedback)
+    }
+
+    fn engage(&mut self, snap: Option<Snap>) {
+        let key = |s: &Snap| (s.node, s.feature);
+        if let Some(new) = &snap {
+            if self.current.as_ref().map(key) != Some(key(new)) {
+                self.feedback.push(self.click);
+            }
+        }
+        self.current = snap;
+    }
+
+    fn snap_to_grid(&self, position: Vec3) -> Option<Snap> {
+        let step = self.grid?;
+        let cell = ((position - self.grid_origin) / step).round();
+        Some(Snap {
+            kind: SnapKind::Grid,
+            node: None,
+            guide: SnapGuide::Grid { point: self.grid_origin + cell * step, step },
+            feature: Feature::Cell([cell.x as i32, cell.y as i32, cell.z as i32]),
+        })
+    }
+
+    /// Nearest corner in reach; failing that the nearest edge, then face.
+    fn snap_to_nodes(&self, scene: &Scene, position: Vec3, ignore: &[NodeId]) -> Option<Snap> {
+        let mut vertex: Option<(f32, Snap)> = None;
+        let mut edge = None;
+        let mut face = None;
+        let keep = |best: &mut Option<(f32, Snap)>, distance: f32, snap: Snap| {
+            if distance <= self.radius && best.is_none_or(|(d, _)| distance < d) {
+                *best = Some((distance, snap));
+            }
+        };
+        for node in scene.visible_nodes(self.layers) {
+            if ignore.iter().any(|&i| i == node || scene.is_ancestor(i, node)) {
+                continue;
+            }
+            if scene.get(node).is_none_or(|n| n.size.max_component() <= 0.0) {
+                continue;
+            }
+            let Some(bounds) = scene.clipped_bounds(node) else { continue };
+            if !bounds.expand(self.radius).contains(position) {
+                continue;
+            }
+            let corners = corners(&bounds);
+            let snap = |kind, guide, feature| Snap { kind, node: Some(node), guide, feature };
+            if self.vertices {
+                for (i, &point) in corners.iter().enumerate() {
+                    let guide = SnapGuide::Vertex { point };
+                    let feature = Feature::Vertex(i as u8);
+                    keep(&mut vertex, point.distance_to(position), snap(SnapKind::Vertex, guide, feature));
+                }
+            }
+            if self.edges {
+                for (i, (a, b)) in edges().enumerate() {
+                    let (from, to) = (corners[a], corners[b]);
+                    let point = closest_point_on_segment(position, from, to);
+                    let guide = SnapGuide::Edge { from, to, point };
+                    let feature = Feature::Edge(i as u8);
+                    keep(&mut edge, point.distance_to(position), snap(SnapKind::Edge, guide, feature));
+                }
+            }
+            if self.surfaces {
+                for axis in 0..3 {
+                    for (side, bound) in [(0u8, bounds.min[axis]), (1u8, bounds.max[axis])] {
+                        let mut point = bounds.closest_point(position);
+                        point[axis] = bound;
+                        let mut normal = Vec3::zero();
+                        normal[axis] = if side == 0 { -1.0 } else { 1.0 };
+                        let guide = SnapGuide::Surface { point, normal };
+                        let feature = Feature::Face(axis as u8 * 2 + side);
+                        keep(&mut face, point.distance_to(position), snap(SnapKind::Surface, guide, feature));
+                    }
+                }
+            }
+        }
+        vertex.or(edge).or(face).map(|(_, snap)| snap)
+    }
+}
+
+impl Default for Snapper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Corners of `bounds`, bit 0 of the index choosing max x, bit 1 max y, bit 2 max z.
+fn corners(bounds: &Aabb) -> [Vec3; 8] {
+    std::array::from_fn(|i| {
+        let pick = |bit: usize, axis: usize| if i & bit == 0 { bounds.min[axis] } else { bounds.max[axis] };
+        Vec3::new(pick(1, 0), pick(2, 1), pick(4, 2))
+    })
+}
+
+/// The twelve edges of a box as pairs of corner indices.
+fn edges() -> impl Iterator<Item = (usize, usize)> {
+    (0..8).flat_map(|i| [1, 2, 4].into_iter().filter(move |bit| i & bit == 0).map(move |bit| (i, i | bit)))
+}
+
+#[cfg(test)]
+#[path = "tests/snap_tests.rs"]
+mod tests;
@@ -0,0 +1,113 @@
+use super::*;
+use crate::scene::{CameraPose, Node, NodeKind};
+
+const TEST_EPSILON: f32 = 1e-5;
+
+fn close(a: Vec3, b: Vec3) -> bool {
+    a.distance_to(b) < TEST_EPSILON
+}
+
+/// A 2 cm cube panel at `position`.
+fn add(scene: &mut Scene, position: Vec3, parent: Option<NodeId>) -> NodeId {
+    let mut node = Node::new(NodeKind::Panel);
+    node.position = position;
+    node.size = Vec3::splat(0.02);
+    scene.insert(node, parent).unwrap()
+}
+
+#[test]
+fn test_align_to_a_side_with_offset() {
+    let mut scene = Scene::new();
+    let a = add(&mut scene, Vec3::new(0.1, 0.05, 0.0), None);
+    let b = add(&mut scene, Vec3::zero(), None);
+    let layout = AnchorLayout::new()
+        .with(b, Anchor::align(Side::Left, a, Side::Right, 0.02))
+        .unwrap()
+        .with(b, Anchor::align(Side::Top, a, Side::Top, 0.0))
+        .unwrap();
+    layout.solve(&mut scene, &CameraPose::default()).unwrap();
+    // Left edge at 0.11 + 0.02, tops level, depth untouched
+    assert!(close(scene.world_position(b).unwrap(), Vec3::new(0.14, 0.05, 0.0)));
+}
+
+#[test]
+fn test_between_and_camera() {
+    let mut scene = Scene::new();
+    let b = add(&mut scene, Vec3::new(-0.1, 0.0, 0.0), None);
+    let c = add(&mut scene, Vec3::new(0.1, 0.2, 0.0), None);
+    let middle = add(&mut scene, Vec3::zero(), None);
+    let hud = add(&mut scene, Vec3::zero(), None);
+    let mut layout = AnchorLayout::new();
+    layout.add(middle, Anchor::between(b, c)).unwrap();
+    layout.add(hud, Anchor::front_of_camera(0.3)).unwrap();
+
+    let camera = CameraPose::new(Vec3::new(0.0, 1.5, 0.0), Vec3::new(0.0, 0.0, 2.0));
+    layout.solve(&mut scene, &camera).unwrap();
+    assert!(close(scene.world_position(middle).unwrap(), Vec3::new(0.0, 0.1, 0.0)));
+    assert!(close(scene.world_position(hud).unwrap(), Vec3::new(0.0, 1.5, 0.3)));
+}
+
+#[test]
+fn test_chains_solve_in_one_pass() {
+    let mut scene = Scene::new();
+    let a = add(&mut scene, Vec3::zero(), None);
+    let b = add(&mut scene, Vec3::zero(), None);
+    let c = add(&mut scene, Vec3::zero(), None);
+    // Added out of dependency order: c follows b, which follows a
+    let mut layout = AnchorLayout::new();
+    layout.add(c, Anchor::align(Side::Left, b, Side::Right, 0.0)).unwrap();
+    layout.add(b, Anchor::align(Side::Left, a, Side::Right, 0.0)).unwrap();
+    layout.add(a, Anchor::front_of_camera(1.0)).unwrap();
+    layout.solve(&mut scene, &CameraPose::default()).unwrap();
+    assert!(close(scene.world_position(b).unwrap(), Vec3::new(0.02, 0.0, 0.0)));
+    assert!(close(scene.world_position(c).unwrap(), Vec3::new(0.04, 0.0, 0.0)));
+}
+
+#[test]
+fn test_children_are_placed_in_world_space() {
+    let mut scene = Scene::new();
+    let panel = add(&mut scene, Vec3::zero(), None);
+    let child = add(&mut scene, Vec3::zero(), Some(panel));
+    let target = add(&mut scene, Vec3::new(0.5, 0.0, 0.0), None);
+    let layout = AnchorLayout::new()
+        .with(child, Anchor::align(Side::CenterX, target, Side::CenterX, 0.0))
+        .unwrap()
+        .with(panel, Anchor::front_of_camera(0.2))
+        .unwrap();
+    layout.solve(&mut scene, &CameraPose::default()).unwrap();
+    // The panel moved first, so the child lands on the target regardless
+    assert!(close(scene.world_position(child).unwrap(), Vec3::new(0.5, 0.0, -0.2)));
+    assert!(close(scene.get(child).unwrap().position, Vec3::new(0.5, 0.0, 0.0)));
+}
+
+#[test]
+fn test_errors() {
+    let mut scene = Scene::new();
+    let a = add(&mut scene, Vec3::zero(), None);
+    let b = add(&mut scene, Vec3::new(0.1, 0.0, 0.0), None);
+    let child = add(&mut scene, Vec3::zero(), Some(a));
+
+    let mut layout = AnchorLayout::new();
+    assert_eq!(
+        layout.add(a, Anchor::align(Side::Left, b, Side::Top, 0.0)),
+        Err(AnchorError::AxisMismatch { side: Side::Left, target_side: Side::Top })
+    );
+    assert!(layout.is_empty());
+
+    layout.add(a, Anchor::align(Side::Left, b, Side::Right, 0.0)).unwrap();
+    layout.add(b, Anchor::align(Side::Right, a, Side::Left, 0.0)).unwrap();
+    assert!(matches!(layout.solve(&mut scene, &CameraPose::default()), Err(AnchorError::Cycle(_))));
+    assert!(close(scene.world_position(a).unwrap(), Vec3::zero()));
+
+    // Following one's own child is circular too
+    assert_eq!(layout.remove(b), 1);
+    layout.remove(a);
+    layout.add(a, Anchor::align(Side::CenterX, child, Side::Right, 0.0)).unwrap();
+    layout.add(child, Anchor::front_of_camera(0.1)).unwrap();
+    assert!(matches!(layout.solve(&mut scene, &CameraPose::default()), Err(AnchorError::Cycle(_))));
+
+    scene.remove(b).unwrap();
+    let mut layout = AnchorLayout::new();
+    layout.add(a, Anchor::between(a, b)).unwrap();
+    assert_eq!(layout.solve(&mut scene, &CameraPose::default()), Err(AnchorError::InvalidNode(b)));
+}
@@ -0,0 +1,54 @@
+use super::*;
+use crate::core::NewtonsPerMeter;
+use crate::geometry::Plane;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+#[test]
+fn test_free_space_draws_only_the_tool() {
+    let avatar = ToolAvatar::default();
+    let at = Meters3::new(0.0, 0.1, 0.0);
+    let frame = avatar.frame(at, at, Quat::identity());
+    assert_eq!(frame.tool.position, at.value());
+    assert_eq!((frame.ghost, frame.tether, frame.push), (None, None, None));
+    assert_eq!((frame.depth, frame.strain, frame.ghost_opacity), (Meters(0.0), 0.0, 0.0));
+}
+
+#[test]
+fn test_penetration_shows_ghost_and_strain() {
+    let avatar = ToolAvatar::new(ToolModel::Stylus { length: Meters(0.12), radius: Meters(0.004) });
+    let proxy = Meters3::new(0.0, 0.0, 0.0);
+
+    // Shallow contact stays below the ghost threshold
+    let shallow = avatar.frame(proxy, Meters3::new(0.0, 0.0, -0.0005), Quat::identity());
+    assert!(shallow.ghost.is_none());
+    assert!((shallow.strain - 0.05).abs() < TEST_EPSILON);
+
+    let device = Meters3::new(0.0, 0.0, -0.0055);
+    let frame = avatar.frame(proxy, device, Quat::identity());
+    assert_eq!(frame.ghost.map(|g| g.position), Some(device.value()));
+    assert_eq!(frame.tether, Some((proxy.value(), device.value())));
+    assert!((frame.depth.value() - 0.0055).abs() < TEST_EPSILON);
+    assert!((frame.strain - 0.55).abs() < TEST_EPSILON);
+    assert!((frame.ghost_opacity - 0.5).abs() < TEST_EPSILON);
+    assert!((frame.push.unwrap() - Vec3::unit_z()).length() < TEST_EPSILON);
+
+    let deep = avatar.with_tether(false).frame(proxy, Meters3::new(0.0, 0.0, -0.05), Quat::identity());
+    assert_eq!((deep.strain, deep.ghost_opacity), (1.0, 1.0));
+    assert!(deep.ghost.is_some() && deep.tether.is_none());
+}
+
+#[test]
+fn test_frame_follows_god_object() {
+    let mut god = GodObject::new(NewtonsPerMeter(1000.0));
+    let floor = Plane::from_point_normal(Vec3::zero(), Vec3::unit_z());
+    god.update(Meters3::new(0.0, 0.0, 0.01), &[&floor]);
+    let device = Meters3::new(0.0, 0.0, -0.004);
+    god.update(device, &[&floor]);
+
+    let avatar = ToolAvatar::new(ToolModel::Hand { scale: 1.0 });
+    let frame = avatar.frame_for(&god, device, Quat::identity());
+    assert!(frame.tool.position.z.abs() < 1e-4);
+    assert_eq!(frame.ghost.map(|g| g.position), Some(device.value()));
+    assert_eq!(frame.model, ToolModel::Hand { scale: 1.0 });
+}
@@ -0,0 +1,103 @@
+use super::*;
+use crate::scene::{Node, NodeKind};
+
+const TEST_EPSILON: f32 = 1e-5;
+
+fn close(a: Vec3, b: Vec3) -> bool {
+    a.distance_to(b) < TEST_EPSILON
+}
+
+fn add(scene: &mut Scene, position: Vec3, parent: Option<NodeId>) -> NodeId {
+    let mut node = Node::new(NodeKind::Panel);
+    node.position = position;
+    scene.insert(node, parent).unwrap()
+}
+
+#[test]
+fn test_distance_clamps_range() {
+    let mut scene = Scene::new();
+    let mut props = AnimatedProps::new();
+    let hand = add(&mut scene, Vec3::zero(), None);
+    let near = add(&mut scene, Vec3::new(0.05, 0.0, 0.0), None);
+    let far = add(&mut scene, Vec3::new(0.0, 0.5, 0.0), None);
+    let inside = add(&mut scene, Vec3::new(0.0, 0.0, 0.15), None);
+    let solver = ConstraintSolver::new()
+        .with(near, Constraint::distance(hand, 0.1, 0.3))
+        .with(far, Constraint::distance(hand, 0.1, 0.3))
+        .with(inside, Constraint::distance(hand, 0.1, 0.3));
+    solver.solve(&mut scene, &mut props, Seconds(0.016)).unwrap();
+    assert!(close(scene.world_position(near).unwrap(), Vec3::new(0.1, 0.0, 0.0)));
+    assert!(close(scene.world_position(far).unwrap(), Vec3::new(0.0, 0.3, 0.0)));
+    assert!(close(scene.world_position(inside).unwrap(), Vec3::new(0.0, 0.0, 0.15)));
+}
+
+#[test]
+fn test_look_at_turns_toward_target() {
+    let mut scene = Scene::new();
+    let mut props = AnimatedProps::new();
+    let head = add(&mut scene, Vec3::new(1.0, 0.0, 0.0), None);
+    let panel = add(&mut scene, Vec3::zero(), None);
+    ConstraintSolver::new().with(panel, Constraint::look_at(head)).solve(&mut scene, &mut props, Seconds(0.0)).unwrap();
+    let rotation = props.get(panel).rotation;
+    assert!(close(rotation.rotate(Vec3::unit_z()), Vec3::unit_x()));
+    assert!(close(rotation.rotate(Vec3::unit_y()), Vec3::unit_y()));
+    assert_eq!(scene.world_position(panel), Some(Vec3::zero()));
+}
+
+#[test]
+fn test_plane_lock_projects_world_position() {
+    let mut scene = Scene::new();
+    let mut props = AnimatedProps::new();
+    let parent = add(&mut scene, Vec3::new(0.0, 1.0, 0.0), None);
+    let child = add(&mut scene, Vec3::new(0.2, 0.3, 0.1), Some(parent));
+    let solver = ConstraintSolver::new().with(child, Constraint::plane_lock(Vec3::new(0.0, 0.5, 0.0), Vec3::unit_y()));
+    solver.solve(&mut scene, &mut props, Seconds(0.0)).unwrap();
+    assert!(close(scene.world_position(child).unwrap(), Vec3::new(0.2, 0.5, 0.1)));
+    assert!(close(scene.get(child).unwrap().position, Vec3::new(0.2, -0.5, 0.1)));
+}
+
+#[test]
+fn test_follow_lags_behind_and_chains() {
+    let mut scene = Scene::new();
+    let mut props = AnimatedProps::new();
+    let vehicle = add(&mut scene, Vec3::new(1.0, 0.0, 0.0), None);
+    let dash = add(&mut scene, Vec3::zero(), None);
+    let label = add(&mut scene, Vec3::zero(), None);
+    let offset = Vec3::new(0.0, 0.1, 0.0);
+    let solver = ConstraintSolver::new()
+        .with(dash, Constraint::follow(vehicle, offset, Seconds(0.5)))
+        .with(label, Constraint::follow(dash, offset, Seconds(0.0)));
+
+    solver.solve(&mut scene, &mut props, Seconds(0.5)).unwrap();
+    let dash_at = scene.world_position(dash).unwrap();
+    let expected = 1.0 - (-1.0f32).exp();
+    assert!(close(dash_at, Vec3::new(expected, 0.1 * expected, 0.0)));
+    // Rigid follow after the lagging one, in the same pass
+    assert!(close(scene.world_position(label).unwrap(), dash_at + offset));
+
+    for _ in 0..100 {
+        solver.solve(&mut scene, &mut props, Seconds(0.1)).unwrap();
+    }
+    assert!(close(scene.world_position(dash).unwrap(), Vec3::new(1.0, 0.1, 0.0)));
+}
+
+#[test]
+fn test_missing_nodes() {
+    let mut scene = Scene::new();
+    let mut props = AnimatedProps::new();
+    let hand = add(&mut scene, Vec3::zero(), None);
+    let panel = add(&mut scene, Vec3::new(1.0, 0.0, 0.0), None);
+    let mut solver = ConstraintSolver::new()
+        .with(panel, Constraint::plane_lock(Vec3::zero(), Vec3::unit_x()))
+        .with(panel, Constraint::distance(hand, 0.0, 0.1));
+    assert_eq!(solver.constraints_of(panel).count(), 2);
+    scene.remove(hand).unwrap();
+
+    assert_eq!(solver.solve(&mut scene, &mut props, Seconds(0.0)), Err(ConstraintError::InvalidNode(hand)));
+    assert_eq!(scene.world_position(panel), Some(Vec3::new(1.0, 0.0, 0.0)));
+    assert_eq!(solver.retain_in(&scene), 1);
+    solver.solve(&mut scene, &mut props, Seconds(0.0)).unwrap();
+    assert_eq!(scene.world_position(panel), Some(Vec3::zero()));
+    assert_eq!(solver.remove(panel), 1);
+    assert!(solver.is_empty());
+}
@@ -0,0 +1,199 @@
+use super::*;
+use std::sync::{Arc, Mutex};
+
+use crate::scene::NodeBuilder;
+use crate::ui::SnapKind;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+const POINTER: PointerId = PointerId(0);
+
+/// Hands out its name as a `String` payload.
+struct Card {
+    ended: Arc<Mutex<Vec<DropOutcome>>>,
+}
+
+impl DragSource for Card {
+    fn payload(&mut self, _node: NodeId) -> Option<DragPayload> {
+        Some(DragPayload::new(String::from("ace")))
+    }
+
+    fn finished(&mut self, _node: NodeId, outcome: DropOutcome) {
+        self.ended.lock().unwrap().push(outcome);
+    }
+}
+
+/// Takes payloads of type `T`.
+struct Bin<T> {
+    received: Arc<Mutex<Vec<T>>>,
+}
+
+impl<T: Any + Send> DropTarget for Bin<T> {
+    fn accepts(&self, payload: &DragPayload) -> bool {
+        payload.is::<T>()
+    }
+
+    fn receive(&mut self, _node: NodeId, payload: DragPayload, _position: Vec3) {
+        self.received.lock().unwrap().extend(payload.take::<T>().ok());
+    }
+}
+
+struct Table {
+    scene: Scene,
+    dnd: DragDrop,
+    card: NodeId,
+    /// Takes strings; a label sits on it.
+    bin: NodeId,
+    label: NodeId,
+    /// Takes numbers only.
+    shelf: NodeId,
+    ended: Arc<Mutex<Vec<DropOutcome>>>,
+    received: Arc<Mutex<Vec<String>>>,
+}
+
+fn table() -> Table {
+    let scene = NodeBuilder::group()
+        .child(NodeBuilder::panel().name("card").size(0.02, 0.03, 0.002))
+        .child(
+            NodeBuilder::panel()
+                .name("bin")
+                .at(0.1, 0.0, 0.0)
+                .size(0.05, 0.05, 0.01)
+                .child(NodeBuilder::label("Bin").name("label")),
+        )
+        .child(NodeBuilder::panel().name("shelf").at(-0.1, 0.0, 0.0).size(0.05, 0.05, 0.01))
+        .into_scene();
+    let [card, bin, label, shelf] = ["card", "bin", "label", "shelf"].map(|name| scene.find(name).unwrap());
+    let ended = Arc::new(Mutex::new(Vec::new()));
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let mut dnd = DragDrop::new();
+    dnd.add_source(card, Card { ended: ended.clone() });
+    dnd.add_target(bin, Bin { received: received.clone() });
+    dnd.add_target(shelf, Bin::<u32> { received: Arc::default() });
+    Table { scene, dnd, card, bin, label, shelf, ended, received }
+}
+
+#[test]
+fn test_payload_is_typed() {
+    let payload = DragPayload::new(7u32);
+    assert!(payload.is::<u32>());
+    assert_eq!(payload.get::<u32>(), Some(&7));
+    assert_eq!(payload.get::<i32>(), None);
+    let payload = payload.take::<String>().unwrap_err();
+    assert_eq!(payload.take::<u32>().unwrap(), 7);
+}
+
+#[test]
+fn test_node_follows_pointer_and_stops_being_pickable() {
+    let mut t = table();
+    assert!(t.dnd.begin(&mut t.scene, POINTER, t.card, Vec3::new(0.005, 0.0, 0.001)));
+    assert!(!t.scene.get(t.card).unwrap().is_pickable());
+    assert!(!t.dnd.begin(&mut t.scene, POINTER, t.card, Vec3::zero()));
+
+    t.dnd.update(&mut t.scene, Vec3::new(0.035, 0.01, 0.001), None);
+    let position = t.scene.get(t.card).unwrap().position;
+    assert!(position.distance_to(Vec3::new(0.03, 0.01, 0.0)) < TEST_EPSILON);
+    assert_eq!(t.dnd.session().unwrap().payload().get::<String>().unwrap(), "ace");
+}
+
+#[test]
+fn test_hovering_an_accepting_target_pulses_once() {
+    let mut t = table();
+    t.dnd.begin(&mut t.scene, POINTER, t.card, Vec3::zero());
+
+    // Over the label on the bin: the bin is the target
+    assert_eq!(t.dnd.update(&mut t.scene, Vec3::new(0.1, 0.0, 0.0), Some(t.label)), Some(t.bin));
+    assert!(t.dnd.session().unwrap().is_accepted());
+    t.dnd.update(&mut t.scene, Vec3::new(0.11, 0.0, 0.0), Some(t.bin));
+    assert_eq!(t.dnd.take_feedback().len(), 1);
+
+    // The shelf refuses strings: no pulse
+    assert_eq!(t.dnd.update(&mut t.scene, Vec3::new(-0.1, 0.0, 0.0), Some(t.shelf)), Some(t.shelf));
+    assert!(!t.dnd.session().unwrap().is_accepted());
+    assert!(t.dnd.take_feedback().is_empty());
+}
+
+#[test]
+fn test_drop_on_accepting_target() {
+    let mut t = table();
+    t.dnd.begin(&mut t.scene, POINTER, t.card, Vec3::zero());
+    t.dnd.update(&mut t.scene, Vec3::new(0.1, 0.0, 0.01), Some(t.bin));
+    assert_eq!(t.dnd.end(&mut t.scene), Some(DropOutcome::Dropped(t.bin)));
+
+    assert_eq!(*t.received.lock().unwrap(), vec![String::from("ace")]);
+    assert_eq!(*t.ended.lock().unwrap(), vec![DropOutcome::Dropped(t.bin)]);
+    // Left where it was dropped, pickable again
+    let card = t.scene.get(t.card).unwrap();
+    assert!(card.is_pickable());
+    assert!(card.position.distance_to(Vec3::new(0.1, 0.0, 0.01)) < TEST_EPSILON);
+    assert!(!t.dnd.is_dragging());
+    assert_eq!(t.dnd.end(&mut t.scene), None);
+}
+
+#[test]
+fn test_rejected_drop_springs_back() {
+    let mut t = table();
+    t.dnd.begin(&mut t.scene, POINTER, t.card, Vec3::zero());
+    t.dnd.update(&mut t.scene, Vec3::new(-0.1, 0.0, 0.0), Some(t.shelf));
+    assert_eq!(t.dnd.end(&mut t.scene), Some(DropOutcome::Cancelled));
+    assert_eq!(*t.ended.lock().unwrap(), vec![DropOutcome::Cancelled]);
+    assert!(t.dnd.is_returning(t.card));
+
+    let mut last = t.scene.get(t.card).unwrap().position.length();
+    let mut frames = 0;
+    while t.dnd.animate(&mut t.scene, Seconds(0.01)) {
+        let distance = t.scene.get(t.card).unwrap().position.length();
+        assert!(distance < last, "approaches home without overshooting");
+        last = distance;
+        frames += 1;
+        assert!(frames < 200);
+    }
+    assert_eq!(t.scene.get(t.card).unwrap().position, Vec3::zero());
+    assert!(!t.dnd.is_returning(t.card));
+}
+
+#[test]
+fn test_regrab_while_returning_keeps_home() {
+    let mut t = table();
+    t.dnd.begin(&mut t.scene, POINTER, t.card, Vec3::zero());
+    t.dnd.update(&mut t.scene, Vec3::new(0.05, 0.0, 0.0), None);
+    t.dnd.cancel(&mut t.scene);
+    t.dnd.animate(&mut t.scene, Seconds(0.05));
+    let midway = t.scene.get(t.card).unwrap().position;
+    assert!(midway.x > 0.0 && midway.x < 0.05);
+
+    // Grabbed where it is; it stays put until the pointer moves
+    assert!(t.dnd.begin(&mut t.scene, POINTER, t.card, midway));
+    assert!(!t.dnd.is_returning(t.card));
+    assert_eq!(t.dnd.session().unwrap().home(), Vec3::zero());
+    t.dnd.update(&mut t.scene, midway, None);
+    assert!(t.scene.get(t.card).unwrap().position.distance_to(midway) < TEST_EPSILON);
+}
+
+#[test]
+fn test_begin_needs_a_source() {
+    let mut t = table();
+    assert!(!t.dnd.begin(&mut t.scene, POINTER, t.bin, Vec3::zero()));
+    assert!(t.dnd.remove(t.card));
+    assert!(!t.dnd.begin(&mut t.scene, POINTER, t.card, Vec3::zero()));
+}
+
+#[test]
+fn test_snapped_drag_rests_on_faces() {
+    let Table { mut scene, dnd, card, bin, .. } = table();
+    let mut dnd = dnd.with_snapper(Snapper::new().with_grid(Some(0.01)));
+    assert!(dnd.begin(&mut scene, POINTER, card, Vec3::zero()));
+
+    // Near the front of the bin the card rests on it, its own half depth out
+    dnd.update(&mut scene, Vec3::new(0.103, 0.004, 0.012), None);
+    assert!(scene.get(card).unwrap().position.distance_to(Vec3::new(0.103, 0.004, 0.006)) < TEST_EPSILON);
+    let snap = dnd.snapper().and_then(|s| s.current()).unwrap();
+    assert_eq!((snap.kind, snap.node), (SnapKind::Surface, Some(bin)));
+    // Out in the open it snaps to the grid
+    dnd.update(&mut scene, Vec3::new(-0.104, 0.052, 0.0), None);
+    assert!(scene.get(card).unwrap().position.distance_to(Vec3::new(-0.1, 0.05, 0.0)) < TEST_EPSILON);
+    assert_eq!(dnd.take_feedback().len(), 2);
+
+    dnd.end(&mut scene);
+    assert!(dnd.snapper().unwrap().current().is_none());
+}
@@ -0,0 +1,181 @@
+use super::*;
+use std::sync::{Arc, Mutex};
+
+use crate::scene::{Node, NodeKind};
+
+const POINTER: PointerId = PointerId(0);
+
+type Log = Arc<Mutex<Vec<(NodeId, PointerEventKind)>>>;
+
+/// Records every event reaching `node`, answering with `propagation`.
+fn record(dispatcher: &mut EventDispatcher, node: NodeId, log: &Log, propagation: Propagation) {
+    let log = log.clone();
+    dispatcher.add_handler(node, move |event| {
+        log.lock().unwrap().push((event.current, event.kind));
+        propagation
+    });
+}
+
+fn sample(x: f32, hit: Option<NodeId>, buttons: u32) -> PointerSample {
+    PointerSample::new(POINTER, Vec3::new(x, 0.0, 0.0), hit, buttons, 0)
+}
+
+/// Panel holding a button.
+fn scene() -> (Scene, NodeId, NodeId) {
+    let mut scene = Scene::new();
+    let panel = scene.insert(Node::new(NodeKind::Panel), None).unwrap();
+    let button = scene.insert(Node::new(NodeKind::Panel), Some(panel)).unwrap();
+    (scene, panel, button)
+}
+
+#[test]
+fn test_enter_and_exit_do_not_bubble() {
+    let (scene, panel, button) = scene();
+    let mut dispatcher = EventDispatcher::new();
+    let log = Log::default();
+    record(&mut dispatcher, panel, &log, Propagation::Continue);
+    record(&mut dispatcher, button, &log, Propagation::Continue);
+
+    dispatcher.dispatch(&scene, sample(0.0, Some(button), 0));
+    assert_eq!(dispatcher.hovered(POINTER), Some(button));
+    dispatcher.dispatch(&scene, sample(0.0, Some(panel), 0));
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            (button, PointerEventKind::Enter),
+            (button, PointerEventKind::Exit),
+            (panel, PointerEventKind::Enter),
+        ]
+    );
+}
+
+#[test]
+fn test_press_bubbles_to_ancestors() {
+    let (scene, panel, button) = scene();
+    let mut dispatcher = EventDispatcher::new();
+    let log = Log::default();
+    record(&mut dispatcher, panel, &log, Propagation::Continue);
+    record(&mut dispatcher, button, &log, Propagation::Continue);
+
+    dispatcher.dispatch(&scene, sample(0.0, Some(button), 0));
+    log.lock().unwrap().clear();
+    let events = dispatcher.dispatch(&scene, sample(0.0, Some(button), 1));
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].target, button);
+    assert_eq!(events[0].buttons, 1);
+    let press = PointerEventKind::Press { button: 0 };
+    assert_eq!(*log.lock().unwrap(), vec![(button, press), (panel, press)]);
+}
+
+#[test]
+fn test_stop_propagation_finishes_the_node() {
+    let (scene, panel, button) = scene();
+    let mut dispatcher = EventDispatcher::new();
+    let log = Log::default();
+    record(&mut dispatcher, panel, &log, Propagation::Continue);
+    record(&mut dispatcher, button, &log, Propagation::Stop);
+    record(&mut dispatcher, button, &log, Propagation::Continue);
+
+    dispatcher.dispatch(&scene, sample(0.0, Some(button), 1));
+    let nodes: Vec<NodeId> = log.lock().unwrap().iter().filter(|(_, k)| k.bubbles()).map(|(n, _)| *n).collect();
+    // Both handlers of the button run, the panel never hears of it
+    assert_eq!(nodes, vec![button, button]);
+}
+
+#[test]
+fn test_press_captures_until_release() {
+    let (scene, panel, button) = scene();
+    let mut dispatcher = EventDispatcher::new();
+
+    dispatcher.dispatch(&scene, sample(0.0, Some(button), 1));
+    assert_eq!(dispatcher.captured(POINTER), Some(button));
+
+    // Dragging off the button still moves it, though hover follows the pointer
+    let events = dispatcher.dispatch(&scene, sample(0.1, Some(panel), 1));
+    let moved = events.iter().find(|e| e.kind == PointerEventKind::Move).unwrap();
+    assert_eq!(moved.target, button);
+    assert_eq!(dispatcher.hovered(POINTER), Some(panel));
+
+    let events = dispatcher.dispatch(&scene, sample(0.1, Some(panel), 0));
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, PointerEventKind::Release { button: 0 });
+    assert_eq!(events[0].target, button);
+    assert_eq!(dispatcher.captured(POINTER), None);
+}
+
+#[test]
+fn test_click_needs_release_on_the_pressed_node() {
+    let (scene, panel, button) = scene();
+    let mut dispatcher = EventDispatcher::new();
+    let clicks = |events: Vec<PointerEvent>| {
+        events.iter().filter(|e| matches!(e.kind, PointerEventKind::Click { .. })).map(|e| e.target).collect::<Vec<_>>()
+    };
+
+    dispatcher.dispatch(&scene, sample(0.0, Some(button), 1));
+    assert_eq!(clicks(dispatcher.dispatch(&scene, sample(0.0, Some(button), 0))), vec![button]);
+
+    dispatcher.dispatch(&scene, sample(0.0, Some(button), 1));
+    dispatcher.dispatch(&scene, sample(0.1, Some(panel), 1));
+    assert!(clicks(dispatcher.dispatch(&scene, sample(0.1, Some(panel), 0))).is_empty());
+}
+
+#[test]
+fn test_capture_holds_while_any_button_is_down() {
+    let (scene, panel, button) = scene();
+    let mut dispatcher = EventDispatcher::new();
+
+    dispatcher.dispatch(&scene, sample(0.0, Some(button), 0b01));
+    dispatcher.dispatch(&scene, sample(0.0, Some(panel), 0b11));
+    dispatcher.dispatch(&scene, sample(0.0, Some(panel), 0b10));
+    assert_eq!(dispatcher.captured(POINTER), Some(button));
+    dispatcher.dispatch(&scene, sample(0.0, Some(panel), 0));
+    assert_eq!(dispatcher.captured(POINTER), None);
+}
+
+#[test]
+fn test_explicit_capture_and_release() {
+    let (scene, panel, button) = scene();
+    let mut dispatcher = EventDispatcher::new();
+
+    dispatcher.dispatch(&scene, sample(0.0, Some(button), 1));
+    dispatcher.capture(POINTER, panel);
+    let events = dispatcher.dispatch(&scene, sample(0.1, Some(button), 1));
+    assert_eq!(events[0].target, panel);
+
+    dispatcher.release_capture(POINTER);
+    let events = dispatcher.dispatch(&scene, sample(0.2, Some(button), 1));
+    assert_eq!(events[0].target, button);
+}
+
+#[test]
+fn test_pointers_are_independent() {
+    let (scene, panel, button) = scene();
+    let mut dispatcher = EventDispatcher::new();
+    let other = PointerId(1);
+
+    dispatcher.dispatch(&scene, sample(0.0, Some(button), 1));
+    dispatcher.dispatch(&scene, PointerSample::new(other, Vec3::zero(), Some(panel), 0, 0));
+    assert_eq!(dispatcher.captured(POINTER), Some(button));
+    assert_eq!(dispatcher.captured(other), None);
+    assert_eq!(dispatcher.hovered(other), Some(panel));
+
+    let events = dispatcher.remove_pointer(&scene, other, 0);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, PointerEventKind::Exit);
+    assert_eq!(dispatcher.hovered(other), None);
+}
+
+#[test]
+fn test_removed_nodes_drop_capture_and_handlers() {
+    let (mut scene, panel, button) = scene();
+    let mut dispatcher = EventDispatcher::new();
+    let log = Log::default();
+    record(&mut dispatcher, button, &log, Propagation::Continue);
+
+    dispatcher.dispatch(&scene, sample(0.0, Some(button), 1));
+    scene.remove(button).unwrap();
+    let events = dispatcher.dispatch(&scene, sample(0.1, Some(panel), 1));
+    assert_eq!(dispatcher.captured(POINTER), None);
+    assert!(events.iter().all(|e| e.target == panel));
+    assert_eq!(dispatcher.remove_handlers(button), 0);
+}
@@ -0,0 +1,109 @@
+use super::*;
+use crate::scene::NodeBuilder;
+use std::sync::{Arc, Mutex};
+
+const TEST_EPSILON: f32 = 1e-5;
+
+/// 20 x 10 cm panel with a button on the left and a label on the right.
+fn scene() -> Scene {
+    NodeBuilder::panel()
+        .name("settings")
+        .size(0.2, 0.1, 0.01)
+        .child(NodeBuilder::button("OK").name("ok").at(-0.05, 0.0, 0.0).size(0.04, 0.03, 0.01))
+        .child(NodeBuilder::label("Volume").name("volume").at(0.05, 0.0, 0.0).size(0.06, 0.02, 0.0))
+        .into_scene()
+}
+
+fn spoken() -> (Arc<Mutex<Vec<String>>>, impl FnMut(&str) + Send + 'static) {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&log);
+    (log, move |text: &str| sink.lock().unwrap().push(text.to_string()))
+}
+
+#[test]
+fn test_collects_widgets() {
+    let scene = scene();
+    let explorer = Explorer::from_scene(&scene);
+    assert_eq!(explorer.widgets().len(), 3);
+    let descriptions: Vec<&str> = explorer.widgets().iter().map(|w| w.description.as_str()).collect();
+    assert_eq!(descriptions, vec!["panel settings", "button OK", "label Volume"]);
+    assert_eq!(explorer.widget_at(Vec3::new(-0.05, 0.01, 0.0)).unwrap().role, WidgetRole::Button);
+    assert_eq!(explorer.widget_at(Vec3::new(0.0, 0.04, 0.0)).unwrap().role, WidgetRole::Panel);
+    assert!(explorer.widget_at(Vec3::new(0.2, 0.0, 0.0)).is_none());
+}
+
+#[test]
+fn test_hidden_widgets_are_skipped() {
+    let mut scene = scene();
+    let ok = scene.find("ok").unwrap();
+    scene.get_mut(ok).unwrap().flags.set(NodeFlags::VISIBLE, false);
+    let explorer = Explorer::from_scene(&scene);
+    assert_eq!(explorer.widgets().len(), 2);
+    assert_eq!(explorer.widget_at(Vec3::new(-0.05, 0.0, 0.0)).unwrap().role, WidgetRole::Panel);
+}
+
+#[test]
+fn test_announces_on_entry() {
+    let scene = scene();
+    let (log, speak) = spoken();
+    let mut explorer = Explorer::from_scene(&scene).with_speech(speak);
+    assert_eq!(explorer.update(Vec3::new(-0.05, 0.0, 0.0), 0.001), ExplorerFrame::IDLE);
+
+    explorer.activate();
+    let frame = explorer.update(Vec3::new(-0.05, 0.0, 0.0), 0.001);
+    assert_eq!(frame.focus, scene.find("ok"));
+    assert_eq!(frame.vibration, explorer.style.button.tacton);
+    explorer.update(Vec3::new(-0.051, 0.0, 0.0), 0.001);
+    explorer.update(Vec3::new(0.05, 0.0, 0.0), 0.001);
+    explorer.repeat();
+    assert_eq!(*log.lock().unwrap(), vec!["button OK", "label Volume", "label Volume"]);
+
+    explorer.deactivate();
+    assert!(explorer.focused().is_none());
+}
+
+#[test]
+fn test_texture_after_tacton() {
+    let scene = scene();
+    let mut explorer = Explorer::from_scene(&scene);
+    explorer.activate();
+    let p = Vec3::new(0.05, 0.0, 0.0);
+    explorer.update(p, 0.001);
+    let frame = explorer.update(p, 0.1);
+    assert_eq!(frame.vibration, explorer.style.label.sample(p));
+    assert!(frame.vibration.intensity <= explorer.style.label.texture.intensity);
+}
+
+#[test]
+fn test_ridge_resists_crossing() {
+    let scene = scene();
+    let mut explorer = Explorer::from_scene(&scene);
+    explorer.activate();
+    let edge = -0.03; // right edge of the OK button
+    let w = explorer.style.ridge_width;
+
+    // Inside the button, approaching its right edge: pushed back inward (-X).
+    let inside = explorer.update(Vec3::new(edge - w * 0.5, 0.0, 0.0), 0.001);
+    assert!((inside.force - Vec3::new(-1.0, 0.0, 0.0)).length() < TEST_EPSILON);
+    // Just past the crest: pushed onward, away from the button.
+    let outside = explorer.update(Vec3::new(edge + w * 0.5, 0.0, 0.0), 0.001);
+    assert!((outside.force - Vec3::new(1.0, 0.0, 0.0)).length() < TEST_EPSILON);
+    // On the crest and away from any boundary: no force.
+    assert!(explorer.update(Vec3::new(edge, 0.0, 0.0), 0.001).force.length() < TEST_EPSILON);
+    assert_eq!(explorer.update(Vec3::new(-0.05, 0.0, 0.0), 0.001).force, Vec3::zero());
+}
+
+#[test]
+fn test_rebuild_keeps_focus() {
+    let mut scene = scene();
+    let (log, speak) = spoken();
+    let mut explorer = Explorer::from_scene(&scene).with_speech(speak);
+    explorer.activate();
+    explorer.update(Vec3::new(-0.05, 0.0, 0.0), 0.001);
+    let ok = scene.find("ok").unwrap();
+    scene.get_mut(ok).unwrap().position.x = -0.049;
+    explorer.rebuild(&scene);
+    explorer.update(Vec3::new(-0.05, 0.0, 0.0), 0.001);
+    assert_eq!(explorer.focused(), Some(ok));
+    assert_eq!(log.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,110 @@
+use super::*;
+use crate::scene::{NodeBuilder, NodeKind};
+use crate::ui::PointerEventKind;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+/// A 2 by 2 keypad of 2 cm keys with a slightly lower key in the top row, and a
+/// wide key below.
+fn keypad() -> (Scene, FocusManager, [NodeId; 5]) {
+    let key = |name: &str, x: f32, y: f32| NodeBuilder::button(name).name(name).at(x, y, 0.0).size(0.02, 0.02, 0.005);
+    let scene = NodeBuilder::group()
+        .child(key("b", 0.03, 0.032))
+        .child(key("a", 0.0, 0.03))
+        .child(key("c", 0.0, 0.0))
+        .child(key("d", 0.03, 0.0))
+        .child(NodeBuilder::button("e").name("e").at(0.015, -0.03, 0.0).size(0.05, 0.02, 0.005))
+        .into_scene();
+    let keys = ["a", "b", "c", "d", "e"].map(|name| scene.find(name).unwrap());
+    let mut focus = FocusManager::new();
+    // Registered out of order; tab order comes from the layout
+    for &node in keys.iter().rev() {
+        focus.add(node);
+    }
+    (scene, focus, keys)
+}
+
+#[test]
+fn test_tab_order_reads_rows() {
+    let (scene, focus, keys) = keypad();
+    assert_eq!(focus.tab_order(&scene), keys.to_vec());
+}
+
+#[test]
+fn test_next_and_previous_wrap() {
+    let (scene, mut focus, [a, b, _, _, e]) = keypad();
+    assert_eq!(focus.focused(&scene), None);
+    assert_eq!(focus.next(&scene), Some(a));
+    assert_eq!(focus.next(&scene), Some(b));
+    assert_eq!(focus.previous(&scene), Some(a));
+    assert_eq!(focus.previous(&scene), Some(e));
+    assert_eq!(focus.next(&scene), Some(a));
+}
+
+#[test]
+fn test_directional_navigation_uses_the_cone() {
+    let (scene, mut focus, [a, b, c, d, e]) = keypad();
+    assert!(focus.focus(&scene, a));
+    assert_eq!(focus.navigate(&scene, Vec3::unit_x()), Some(b));
+    assert_eq!(focus.navigate(&scene, -Vec3::unit_y()), Some(d));
+    assert_eq!(focus.navigate(&scene, -Vec3::unit_x()), Some(c));
+    assert_eq!(focus.navigate(&scene, -Vec3::unit_y()), Some(e));
+    // Nothing below: focus stays
+    assert_eq!(focus.navigate(&scene, -Vec3::unit_y()), Some(e));
+
+    // A narrow cone reaches past the keys off to the side to the top row
+    let mut narrow = focus.clone().with_cone(Deg(20.0));
+    assert_eq!(narrow.navigate(&scene, Vec3::unit_y()), Some(a));
+    assert!(focus.focus(&scene, c));
+    assert_eq!(focus.navigate(&scene, Vec3::unit_y()), Some(a));
+}
+
+#[test]
+fn test_hidden_and_removed_widgets_are_skipped() {
+    let (mut scene, mut focus, [a, b, c, d, e]) = keypad();
+    scene.get_mut(b).unwrap().flags.set(NodeFlags::VISIBLE, false);
+    assert_eq!(focus.tab_order(&scene), vec![a, c, d, e]);
+    assert!(!focus.focus(&scene, b));
+
+    focus.focus(&scene, a);
+    assert!(focus.remove(a));
+    assert_eq!(focus.focused(&scene), None);
+    assert_eq!(focus.len(), 4);
+
+    let lone = scene.insert(crate::scene::Node::new(NodeKind::Panel), None).unwrap();
+    assert!(!focus.focus(&scene, lone));
+}
+
+#[test]
+fn test_ring_surrounds_the_focused_widget() {
+    let (scene, mut focus, [_, _, _, d, _]) = keypad();
+    assert_eq!(focus.ring(&scene), None);
+    focus.focus(&scene, d);
+    let ring = focus.ring(&scene).unwrap();
+    assert_eq!(ring.node, d);
+    assert!((ring.size.x - 0.024).abs() < TEST_EPSILON);
+    let [bottom_left, _, top_right, _] = ring.corners();
+    assert!(bottom_left.distance_to(Vec3::new(0.018, -0.012, 0.0045)) < TEST_EPSILON);
+    assert!(top_right.distance_to(Vec3::new(0.042, 0.012, 0.0045)) < TEST_EPSILON);
+}
+
+#[test]
+fn test_keys_navigate_and_activate() {
+    let (scene, mut focus, [a, b, ..]) = keypad();
+    let mut dispatcher = EventDispatcher::new();
+
+    assert!(focus.key(&scene, &mut dispatcher, FocusKey::Next, true, 0).is_empty());
+    focus.key(&scene, &mut dispatcher, FocusKey::Next, false, 0);
+    focus.key(&scene, &mut dispatcher, FocusKey::Right, true, 0);
+    assert_eq!(focus.focused(&scene), Some(b));
+    focus.key(&scene, &mut dispatcher, FocusKey::Left, true, 0);
+    assert_eq!(focus.focused(&scene), Some(a));
+
+    let kinds = |events: Vec<PointerEvent>| events.into_iter().map(|e| (e.target, e.kind)).collect::<Vec<_>>();
+    let pressed = kinds(focus.key(&scene, &mut dispatcher, FocusKey::Activate, true, 10));
+    assert!(pressed.contains(&(a, PointerEventKind::Press { button: 0 })));
+    assert_eq!(dispatcher.captured(FOCUS_POINTER), Some(a));
+    let released = kinds(focus.key(&scene, &mut dispatcher, FocusKey::Activate, false, 20));
+    let release = PointerEventKind::Release { button: 0 };
+    assert_eq!(released, vec![(a, release), (a, PointerEventKind::Click { button: 0 })]);
+}
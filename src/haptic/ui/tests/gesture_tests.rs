@@ -0,0 +1,135 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+/// `count` points from `from` to `to`, evenly over `seconds` from `start_us`.
+fn line(from: Vec3, to: Vec3, seconds: f32, count: usize, start_us: u64) -> Vec<TrackPoint> {
+    (0..count)
+        .map(|i| {
+            let t = i as f32 / (count - 1) as f32;
+            TrackPoint::new(from.lerp(to, t), start_us + (t * seconds * 1e6) as u64)
+        })
+        .collect()
+}
+
+/// One loop of radius `radius` in the XY plane over a second, counterclockwise
+/// unless `clockwise`.
+fn circle_stroke(radius: f32, clockwise: bool) -> Vec<TrackPoint> {
+    let sign = if clockwise { -1.0 } else { 1.0 };
+    (0..=32)
+        .map(|i| {
+            let angle = sign * TAU * i as f32 / 32.0;
+            TrackPoint::new(Vec3::new(angle.cos(), angle.sin(), 0.0) * radius, i * 31_250)
+        })
+        .collect()
+}
+
+fn recognizer() -> GestureRecognizer {
+    GestureRecognizer::default()
+}
+
+#[test]
+fn test_tap() {
+    let stroke = line(Vec3::zero(), Vec3::new(0.001, 0.0, 0.0), 0.1, 3, 0);
+    let event = recognizer().classify(&stroke).unwrap();
+    assert_eq!(event.gesture, Gesture::Tap { position: Vec3::zero() });
+    assert!((0.5..=1.0).contains(&event.confidence));
+    assert_eq!(event.time_us, 100_000);
+}
+
+#[test]
+fn test_hold_fires_while_pressed() {
+    let mut gestures = recognizer();
+    let pointer = PointerId(0);
+    gestures.press(pointer, TrackPoint::new(Vec3::zero(), 0));
+    let mut fired = Vec::new();
+    for i in 1..=10 {
+        let point = TrackPoint::new(Vec3::new(0.0005, 0.0, 0.0), i * 100_000);
+        fired.extend(gestures.motion(pointer, point).map(|e| (i, e.gesture)));
+    }
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0], (6, Gesture::Hold { position: Vec3::zero(), duration: Seconds(0.6) }));
+    // The release after a hold is not a tap too
+    assert_eq!(gestures.release(pointer, TrackPoint::new(Vec3::zero(), 1_100_000)), None);
+    assert!(!gestures.is_pressed(pointer));
+}
+
+#[test]
+fn test_swipe_needs_speed_and_straightness() {
+    let gestures = recognizer();
+    let fast = line(Vec3::zero(), Vec3::new(0.1, 0.0, 0.0), 0.2, 10, 0);
+    match gestures.classify(&fast).unwrap().gesture {
+        Gesture::Swipe { direction, speed } => {
+            assert!(direction.distance_to(Vec3::unit_x()) < TEST_EPSILON);
+            assert!((speed - 0.5).abs() < TEST_EPSILON);
+        }
+        other => panic!("expected a swipe, got {:?}", other),
+    }
+
+    let slow = line(Vec3::zero(), Vec3::new(0.1, 0.0, 0.0), 2.0, 10, 0);
+    assert_eq!(gestures.classify(&slow), None);
+
+    // Out and back is not straight
+    let mut zigzag = line(Vec3::zero(), Vec3::new(0.1, 0.0, 0.0), 0.2, 5, 0);
+    zigzag.extend(line(Vec3::new(0.1, 0.0, 0.0), Vec3::new(0.05, 0.0, 0.0), 0.1, 5, 200_000));
+    assert_eq!(gestures.classify(&zigzag), None);
+}
+
+#[test]
+fn test_circle_reports_center_radius_and_direction() {
+    let gestures = recognizer();
+    let event = gestures.classify(&circle_stroke(0.03, false)).unwrap();
+    let Gesture::Circle { center, radius, normal, turns } = event.gesture else {
+        panic!("expected a circle, got {:?}", event.gesture);
+    };
+    assert!(center.length() < 0.002);
+    assert!((radius - 0.03).abs() < 0.001);
+    assert!(normal.distance_to(Vec3::unit_z()) < TEST_EPSILON);
+    assert!((turns - 1.0).abs() < 0.01);
+    assert!(event.confidence > 0.9);
+
+    let Gesture::Circle { normal, .. } = gestures.classify(&circle_stroke(0.03, true)).unwrap().gesture else {
+        panic!("expected a circle");
+    };
+    assert!(normal.distance_to(-Vec3::unit_z()) < TEST_EPSILON);
+
+    // Too small to be deliberate
+    let small = gestures.classify(&circle_stroke(0.005, false)).map(|e| e.gesture);
+    assert!(!matches!(small, Some(Gesture::Circle { .. })));
+}
+
+#[test]
+fn test_pinch_from_two_strokes() {
+    let gestures = recognizer();
+    let a = line(Vec3::new(-0.05, 0.0, 0.0), Vec3::new(-0.02, 0.0, 0.0), 0.3, 5, 0);
+    let b = line(Vec3::new(0.05, 0.0, 0.0), Vec3::new(0.02, 0.0, 0.0), 0.3, 5, 0);
+    let event = gestures.classify_pinch(&a, &b).unwrap();
+    let Gesture::Pinch { center, scale } = event.gesture else { panic!("expected a pinch") };
+    assert!(center.length() < TEST_EPSILON);
+    assert!((scale - 0.4).abs() < TEST_EPSILON);
+    assert_eq!(event.confidence, 1.0);
+
+    // Both moving the same way is no pinch
+    let c = line(Vec3::new(0.05, 0.0, 0.0), Vec3::new(0.08, 0.0, 0.0), 0.3, 5, 0);
+    let d = line(Vec3::new(-0.05, 0.0, 0.0), Vec3::new(-0.02, 0.0, 0.0), 0.3, 5, 0);
+    assert_eq!(gestures.classify_pinch(&c, &d), None);
+}
+
+#[test]
+fn test_live_pinch_consumes_both_strokes() {
+    let mut gestures = recognizer();
+    let (thumb, finger) = (PointerId(0), PointerId(1));
+    let a = line(Vec3::new(-0.02, 0.0, 0.0), Vec3::new(-0.06, 0.0, 0.0), 0.3, 4, 0);
+    let b = line(Vec3::new(0.02, 0.0, 0.0), Vec3::new(0.06, 0.0, 0.0), 0.3, 4, 0);
+    gestures.press(thumb, a[0]);
+    gestures.press(finger, b[0]);
+    for i in 1..3 {
+        assert_eq!(gestures.motion(thumb, a[i]), None);
+        assert_eq!(gestures.motion(finger, b[i]), None);
+    }
+    gestures.motion(finger, b[3]);
+    let event = gestures.release(thumb, a[3]).unwrap();
+    let Gesture::Pinch { scale, .. } = event.gesture else { panic!("expected a pinch") };
+    assert!((scale - 3.0).abs() < TEST_EPSILON);
+    assert_eq!(gestures.release(finger, b[3]), None);
+}
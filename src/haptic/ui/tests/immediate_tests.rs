@@ -0,0 +1,85 @@
+use super::*;
+use crate::core::Meters3;
+
+fn device(position: Vec3, down: bool) -> DeviceState {
+    DeviceState { position: Meters3(position), buttons: u32::from(down), ..DeviceState::default() }
+}
+
+/// Runs one frame with a single "Reset" button at the origin.
+fn frame(ui: &mut ImmediateUi, scene: &mut Scene, cursor: Vec3, down: bool) -> Response {
+    ui.begin(&device(cursor, down));
+    let response = ui.button_3d("Reset", Vec3::zero()).unwrap();
+    ui.end(scene).unwrap();
+    response
+}
+
+#[test]
+fn test_button_press_and_click() {
+    let mut scene = Scene::new();
+    let mut ui = ImmediateUi::new();
+    let away = Vec3::new(0.1, 0.0, 0.0);
+    let on = Vec3::new(0.0, 0.0, 0.006);
+
+    let first = frame(&mut ui, &mut scene, away, false);
+    assert_eq!(first.node, None);
+    let node = scene.find("Reset").unwrap();
+    assert!(scene.get(node).unwrap().is_touchable());
+
+    let hover = frame(&mut ui, &mut scene, on, false);
+    assert_eq!(hover.node, Some(node));
+    assert!(hover.hovered && !hover.pressed);
+
+    let press = frame(&mut ui, &mut scene, on, true);
+    assert!(press.pressed && !press.clicked);
+    assert_eq!(ui.tree().get::<bool>(node, "pressed"), Some(true));
+    let held = frame(&mut ui, &mut scene, on, true);
+    assert!(held.pressed);
+
+    let release = frame(&mut ui, &mut scene, on, false);
+    assert!(release.clicked && !release.pressed);
+    assert_eq!(ui.take_feedback().len(), 2);
+    assert_eq!(scene.len(), 1);
+}
+
+#[test]
+fn test_release_outside_does_not_click() {
+    let mut scene = Scene::new();
+    let mut ui = ImmediateUi::new();
+    let on = Vec3::zero();
+    frame(&mut ui, &mut scene, on, false);
+    frame(&mut ui, &mut scene, on, true);
+    let dragged = frame(&mut ui, &mut scene, Vec3::new(0.1, 0.0, 0.0), true);
+    assert!(dragged.pressed && !dragged.hovered);
+    assert!(!frame(&mut ui, &mut scene, Vec3::new(0.1, 0.0, 0.0), false).clicked);
+
+    // Pressing elsewhere and sliding on does not press either
+    frame(&mut ui, &mut scene, Vec3::new(0.1, 0.0, 0.0), true);
+    assert!(!frame(&mut ui, &mut scene, on, true).pressed);
+}
+
+#[test]
+fn test_ids_and_scopes() {
+    let mut scene = Scene::new();
+    let mut ui = ImmediateUi::new();
+    ui.begin(&DeviceState::default());
+    let first = ui.button_3d("Delete", Vec3::zero()).unwrap();
+    assert_eq!(
+        ui.button_3d("Delete", Vec3::unit_x()).unwrap_err(),
+        ImmediateError::IdClash { label: "Delete".into() }
+    );
+    ui.push_id(1);
+    let scoped = ui.button_3d("Delete", Vec3::unit_x()).unwrap();
+    ui.pop_id();
+    assert_ne!(first.id, scoped.id);
+    ui.label_3d("Status", Vec3::unit_y()).unwrap();
+    ui.end(&mut scene).unwrap();
+    assert_eq!(scene.len(), 3);
+
+    // Ids are stable across frames, and widgets not declared again disappear
+    ui.begin(&DeviceState::default());
+    assert_eq!(ui.button_3d("Delete", Vec3::zero()).unwrap().id, first.id);
+    let changes = ui.end(&mut scene).unwrap();
+    assert_eq!(changes.iter().filter(|c| matches!(c, WidgetChange::Removed(_))).count(), 2);
+    assert_eq!(scene.len(), 1);
+    assert_eq!(ui.state(first.id).and_then(|s| s.get("label")), Some(crate::ui::PropertyValue::Text("Delete".into())));
+}
@@ -0,0 +1,83 @@
+use super::*;
+use std::sync::{Arc, Mutex};
+
+use InteractionInput as I;
+use InteractionState as S;
+
+fn run(machine: &mut InteractionMachine, inputs: &[InteractionInput]) -> Vec<InteractionState> {
+    inputs
+        .iter()
+        .map(|&input| {
+            machine.handle(input);
+            machine.state()
+        })
+        .collect()
+}
+
+#[test]
+fn test_full_interaction_path() {
+    let mut machine = InteractionMachine::new();
+    let inputs = [I::PointerEnter, I::Contact, I::Press, I::DragStart, I::Release, I::ContactLost, I::PointerExit];
+    let states = run(&mut machine, &inputs);
+    assert_eq!(states, vec![S::Hover, S::Engaged, S::Pressed, S::Dragged, S::Engaged, S::Hover, S::Idle]);
+}
+
+#[test]
+fn test_press_survives_leaving_until_release() {
+    let mut machine = InteractionMachine::new();
+    run(&mut machine, &[I::PointerEnter, I::Press]);
+    assert_eq!(machine.handle(I::PointerExit), None);
+    assert_eq!(machine.state(), S::Pressed);
+    assert!(machine.state().is_active());
+    assert!(!machine.is_hovered());
+
+    let released = machine.handle(I::Release).unwrap();
+    assert_eq!(released, Transition { from: S::Pressed, to: S::Idle, input: I::Release });
+
+    run(&mut machine, &[I::Contact, I::Press, I::DragStart]);
+    assert_eq!(machine.handle(I::Cancel).map(|t| t.to), Some(S::Engaged));
+    assert!(machine.is_touched());
+}
+
+#[test]
+fn test_ignored_inputs_do_not_transition() {
+    let mut machine = InteractionMachine::new();
+    assert_eq!(machine.handle(I::Release), None);
+    assert_eq!(machine.handle(I::DragStart), None);
+    run(&mut machine, &[I::PointerEnter, I::Contact]);
+    // Hover going away while touching stays engaged
+    assert_eq!(machine.handle(I::PointerExit), None);
+    assert_eq!(machine.state(), S::Engaged);
+}
+
+#[test]
+fn test_disabled_ignores_input_but_tracks_pointer() {
+    let mut machine = InteractionMachine::new();
+    run(&mut machine, &[I::PointerEnter, I::Press]);
+    assert_eq!(machine.set_enabled(false).map(|t| t.to), Some(S::Disabled));
+    assert_eq!(machine.handle(I::Press), None);
+    assert_eq!(machine.handle(I::Contact), None);
+    assert_eq!(machine.state(), S::Disabled);
+    assert_eq!(machine.set_enabled(true).map(|t| t.to), Some(S::Engaged));
+}
+
+#[test]
+fn test_hooks_run_exit_transition_enter() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let (exit, any, enter) = (log.clone(), log.clone(), log.clone());
+    let mut machine = InteractionMachine::new()
+        .on_enter(S::Pressed, move |t| enter.lock().unwrap().push(format!("enter {:?}", t.to)))
+        .on_exit(S::Hover, move |t| exit.lock().unwrap().push(format!("exit {:?}", t.from)))
+        .on_transition(move |t| any.lock().unwrap().push(format!("{:?}", t.input)));
+
+    run(&mut machine, &[I::PointerEnter, I::Press, I::Press]);
+    assert_eq!(*log.lock().unwrap(), vec!["PointerEnter", "exit Hover", "Press", "enter Pressed"]);
+}
+
+#[test]
+fn test_from_pointer_events() {
+    assert_eq!(InteractionInput::from_pointer(&PointerEventKind::Enter), Some(I::PointerEnter));
+    assert_eq!(InteractionInput::from_pointer(&PointerEventKind::Press { button: 0 }), Some(I::Press));
+    assert_eq!(InteractionInput::from_pointer(&PointerEventKind::Click { button: 0 }), None);
+    assert_eq!(InteractionInput::from_pointer(&PointerEventKind::Move), None);
+}
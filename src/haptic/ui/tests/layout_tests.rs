@@ -0,0 +1,91 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+fn close(a: Vec3, b: Vec3) -> bool {
+    a.distance_to(b) < TEST_EPSILON
+}
+
+fn key(size: f32) -> WidgetDesc {
+    WidgetDesc::button("k").size(size, size, 0.005)
+}
+
+#[test]
+fn test_auto_tracks_fit_children() {
+    let grid = GridLayout::new(2, 2)
+        .with_spacing(0.01, 0.01, 0.0)
+        .with(key(0.02), Cell::at(0, 0))
+        .with(key(0.04), Cell::at(1, 1));
+    let [columns, rows, layers] = grid.track_sizes();
+    assert_eq!(columns, vec![0.02, 0.04]);
+    assert_eq!(rows, vec![0.02, 0.04]);
+    assert_eq!(layers, vec![0.005]);
+    assert!(close(grid.size(), Vec3::new(0.07, 0.07, 0.005)));
+
+    let frames = grid.frames();
+    // Top-left cell, centered on the grid
+    assert!(close(frames[0].0, Vec3::new(-0.025, 0.025, 0.0)));
+    assert!(close(frames[1].0, Vec3::new(0.015, -0.015, 0.0)));
+}
+
+#[test]
+fn test_spans_cover_tracks_and_gaps() {
+    let grid = GridLayout::new(3, 1)
+        .with_spacing(0.01, 0.0, 0.0)
+        .with_column(0, Track::Fixed(0.02))
+        .with_column(1, Track::Fixed(0.02))
+        .with_column(2, Track::Fixed(0.02))
+        .with_align(CellAlign::STRETCH)
+        .with(key(0.01), Cell::at(1, 0).span(2, 1));
+    let (center, size) = grid.frames()[0];
+    assert!((size.x - 0.05).abs() < TEST_EPSILON);
+    assert!((center.x - 0.015).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_per_cell_alignment() {
+    let start = CellAlign::new(Align::Start, Align::Start, Align::Center);
+    let end = CellAlign::new(Align::End, Align::End, Align::Center);
+    let grid = GridLayout::new(1, 1)
+        .with_column(0, Track::Fixed(0.1))
+        .with_row(0, Track::Fixed(0.1))
+        .with(key(0.02), Cell::at(0, 0).align(start))
+        .with(key(0.02), Cell::at(0, 0).align(end))
+        .with(key(0.02), Cell::at(0, 0));
+    let frames = grid.frames();
+    assert!(close(frames[0].0, Vec3::new(-0.04, 0.04, 0.0)));
+    assert!(close(frames[1].0, Vec3::new(0.04, -0.04, 0.0)));
+    assert!(close(frames[2].0, Vec3::zero()));
+    assert!(close(frames[2].1, Vec3::new(0.02, 0.02, 0.005)));
+}
+
+#[test]
+fn test_layers_and_growth() {
+    let mut grid = GridLayout::new(1, 1).with_spacing(0.0, 0.0, 0.01);
+    grid.add(key(0.02), Cell::at(0, 0));
+    grid.add(key(0.02), Cell::at(2, 0).layer(1));
+    assert_eq!(grid.columns.len(), 3);
+    assert_eq!(grid.layers.len(), 2);
+    assert_eq!(grid.len(), 2);
+
+    let frames = grid.frames();
+    // Layer 1 sits in front of layer 0
+    assert!((frames[1].0.z - frames[0].0.z - 0.015).abs() < TEST_EPSILON);
+    // The empty middle column collapses
+    assert!((frames[1].0.x - frames[0].0.x - 0.02).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_desc_places_children() {
+    let grid = GridLayout::new(2, 1).with(key(0.02), Cell::at(0, 0)).with(key(0.02), Cell::at(1, 0));
+    let children = grid.children();
+    assert!(close(children[0].position, Vec3::new(-0.01, 0.0, 0.0)));
+    assert!(close(children[1].position, Vec3::new(0.01, 0.0, 0.0)));
+
+    let mut scene = crate::scene::Scene::new();
+    let mut tree = super::super::WidgetTree::new();
+    tree.update(&mut scene, &[grid.desc()]).unwrap();
+    assert_eq!(tree.len(), 3);
+    let root = tree.roots().next().unwrap();
+    assert!(close(scene.get(root).unwrap().size, Vec3::new(0.04, 0.02, 0.005)));
+}
@@ -0,0 +1,58 @@
+use super::*;
+use crate::core::Vec3;
+use crate::scene::{Node, NodeKind};
+
+fn event(kind: PointerEventKind, pointer: u32, target: NodeId) -> PointerEvent {
+    PointerEvent {
+        kind,
+        pointer: PointerId(pointer),
+        position: Vec3::zero(),
+        buttons: 0,
+        target,
+        current: target,
+        time_us: 0,
+    }
+}
+
+#[test]
+fn test_requests_last_one_tick() {
+    let mut scene = Scene::new();
+    let node = scene.insert(Node::new(NodeKind::Panel), None).unwrap();
+    let mut scheduler = UpdateScheduler::new();
+    assert!(scheduler.is_idle() && !scheduler.is_due(node));
+
+    scheduler.request_update(node);
+    assert!(scheduler.is_due(node) && !scheduler.is_interacting(node));
+    assert_eq!(scheduler.take_due(), HashSet::from([node]));
+    assert!(scheduler.is_idle());
+    assert!(scheduler.take_due().is_empty());
+}
+
+#[test]
+fn test_interactions_keep_widgets_due() {
+    let mut scene = Scene::new();
+    let (a, b) =
+        (scene.insert(Node::new(NodeKind::Panel), None).unwrap(), scene.insert(Node::new(NodeKind::Panel), None).unwrap());
+    let mut scheduler = UpdateScheduler::new();
+
+    // Hovered by two pointers, then pressed by one which releases over another widget
+    scheduler.observe(&event(PointerEventKind::Enter, 0, a));
+    scheduler.observe(&event(PointerEventKind::Enter, 1, a));
+    scheduler.observe(&event(PointerEventKind::Press { button: 0 }, 0, a));
+    scheduler.observe(&event(PointerEventKind::Exit, 0, a));
+    scheduler.observe(&event(PointerEventKind::Exit, 1, a));
+    assert!(scheduler.is_interacting(a));
+    assert_eq!(scheduler.take_due(), HashSet::from([a]));
+    assert!(scheduler.is_due(a));
+    scheduler.observe(&event(PointerEventKind::Release { button: 0 }, 0, b));
+    assert!(scheduler.is_idle());
+
+    // Held by the app until let go, or until the node goes
+    scheduler.set_interacting(a, true);
+    scheduler.set_interacting(b, true);
+    scheduler.set_interacting(a, false);
+    assert_eq!(scheduler.take_due(), HashSet::from([b]));
+    scene.remove(b).unwrap();
+    assert_eq!(scheduler.retain_in(&scene), 1);
+    assert!(scheduler.is_idle());
+}
@@ -0,0 +1,64 @@
+use super::*;
+
+fn settle(m: &mut Magnifier) {
+    for _ in 0..100 {
+        m.update(0.01);
+    }
+}
+
+#[test]
+fn test_inactive_mapping_is_identity() {
+    let mut m = Magnifier::new(4.0, 0.05);
+    assert_eq!(m.map_device(Vec3::new(0.1, 0.2, 0.3)), Vec3::new(0.1, 0.2, 0.3));
+    assert_eq!(m.map_device(Vec3::new(0.2, 0.2, 0.3)), Vec3::new(0.2, 0.2, 0.3));
+    assert_eq!(m.scale_force(Vec3::unit_y()), Vec3::unit_y());
+    assert!(m.view().is_none());
+}
+
+#[test]
+fn test_active_scales_motion_and_force() {
+    let mut m = Magnifier::new(4.0, 0.05);
+    m.map_device(Vec3::zero());
+    m.activate();
+    settle(&mut m);
+    assert_eq!(m.blend(), 1.0);
+    let cursor = m.map_device(Vec3::new(0.04, 0.0, 0.0));
+    assert!((cursor.x - 0.01).abs() < 1e-6);
+    assert_eq!(m.scale_force(Vec3::new(0.0, 2.0, 0.0)), Vec3::new(0.0, 0.5, 0.0));
+
+    let view = m.view().unwrap();
+    assert_eq!(view.center, cursor);
+    assert!((view.source_radius - 0.0125).abs() < 1e-6);
+    assert_eq!(view.zoom, 4.0);
+}
+
+#[test]
+fn test_transition_is_continuous() {
+    let mut m = Magnifier::new(4.0, 0.05);
+    m.map_device(Vec3::zero());
+    m.activate();
+    m.update(0.1);
+    assert!((m.blend() - 0.5).abs() < 1e-6);
+    assert!((m.current_zoom() - 2.5).abs() < 1e-6);
+
+    // Toggling never moves the cursor by itself
+    let before = m.map_device(Vec3::new(0.01, 0.0, 0.0));
+    m.deactivate();
+    assert_eq!(m.map_device(Vec3::new(0.01, 0.0, 0.0)), before);
+}
+
+#[test]
+fn test_recenters_after_deactivation() {
+    let mut m = Magnifier::new(4.0, 0.05);
+    m.map_device(Vec3::zero());
+    m.activate();
+    settle(&mut m);
+    m.map_device(Vec3::new(0.08, 0.0, 0.0));
+    assert!((m.cursor().x - 0.02).abs() < 1e-6);
+
+    m.deactivate();
+    for _ in 0..500 {
+        m.update(0.01);
+    }
+    assert!((m.cursor().x - 0.08).abs() < 1e-3);
+}
@@ -0,0 +1,120 @@
+use super::*;
+use crate::core::Vec3;
+use crate::scene::NodeBuilder;
+use crate::ui::{EventDispatcher, PointerEventKind, PointerId};
+
+const TEST_EPSILON: f32 = 1e-6;
+
+/// A page with two buttons and two dialogs of two buttons each, all focusable.
+fn page() -> (Scene, FocusManager, [NodeId; 8]) {
+    let key = |name: &str, x: f32, y: f32| NodeBuilder::button(name).name(name).at(x, y, 0.0).size(0.02, 0.02, 0.005);
+    let dialog = |name: &str, ok: &str, cancel: &str| {
+        NodeBuilder::panel().name(name).at(0.0, 0.0, 0.05).child(key(ok, -0.02, 0.0)).child(key(cancel, 0.02, 0.0))
+    };
+    let scene = NodeBuilder::group()
+        .child(key("open", 0.0, 0.1))
+        .child(key("save", 0.03, 0.1))
+        .child(dialog("dialog", "ok", "cancel"))
+        .child(dialog("confirm", "yes", "no"))
+        .into_scene();
+    let nodes = ["open", "save", "dialog", "ok", "cancel", "confirm", "yes", "no"].map(|n| scene.find(n).unwrap());
+    let mut focus = FocusManager::new();
+    for node in [0, 1, 3, 4, 6, 7] {
+        focus.add(nodes[node]);
+    }
+    (scene, focus, nodes)
+}
+
+#[test]
+fn test_open_traps_focus_and_close_restores_it() {
+    let (scene, mut focus, [open, save, dialog, ok, cancel, ..]) = page();
+    let mut modals = ModalStack::new();
+    assert!(focus.focus(&scene, save));
+
+    assert_eq!(modals.open(&scene, &mut focus, dialog), Some(ok));
+    assert_eq!(modals.top(), Some(dialog));
+    assert_eq!(focus.tab_order(&scene), vec![ok, cancel]);
+    assert_eq!(focus.next(&scene), Some(cancel));
+    assert_eq!(focus.next(&scene), Some(ok));
+    assert!(!focus.focus(&scene, open));
+    assert_eq!(focus.navigate(&scene, Vec3::unit_y()), Some(ok));
+
+    assert!(modals.close(&scene, &mut focus, dialog));
+    assert!(modals.is_empty());
+    assert_eq!(focus.trap(), None);
+    assert_eq!(focus.focused(&scene), Some(save));
+    assert!(!modals.close(&scene, &mut focus, dialog));
+}
+
+#[test]
+fn test_nested_dialogs() {
+    let (scene, mut focus, [open, _, dialog, ok, cancel, confirm, yes, _]) = page();
+    let mut modals = ModalStack::new();
+    assert!(focus.focus(&scene, open));
+    modals.open(&scene, &mut focus, dialog);
+    assert!(focus.focus(&scene, cancel));
+    assert_eq!(modals.open(&scene, &mut focus, confirm), Some(yes));
+    assert_eq!(modals.len(), 2);
+    assert!(!modals.allows(&scene, ok));
+
+    assert_eq!(modals.close_top(&scene, &mut focus), Some(confirm));
+    assert_eq!(focus.trap(), Some(dialog));
+    assert_eq!(focus.focused(&scene), Some(cancel));
+
+    // Closing a dialog closes the ones opened above it
+    modals.open(&scene, &mut focus, confirm);
+    assert!(modals.close(&scene, &mut focus, dialog));
+    assert!(modals.is_empty());
+    assert_eq!(focus.focused(&scene), Some(open));
+}
+
+#[test]
+fn test_background_pointer_and_haptics_are_blocked() {
+    let (scene, mut focus, [open, save, dialog, ok, cancel, ..]) = page();
+    let mut modals = ModalStack::new();
+    let mut dispatcher = EventDispatcher::new();
+    let pointer = PointerId(1);
+    modals.open(&scene, &mut focus, dialog);
+
+    let sample = |hit, buttons| PointerSample::new(pointer, Vec3::zero(), Some(hit), buttons, 0);
+    let events = dispatcher.dispatch(&scene, modals.filter(&scene, sample(open, 1)));
+    assert!(events.is_empty());
+    let events = dispatcher.dispatch(&scene, modals.filter(&scene, sample(ok, 0)));
+    let kinds: Vec<_> = events.iter().map(|e| (e.kind, e.target)).collect();
+    // The blocked press clicks nothing when released over the dialog
+    assert_eq!(kinds, vec![(PointerEventKind::Enter, ok), (PointerEventKind::Release { button: 0 }, ok)]);
+
+    let touchable = modals.touchable_nodes(&scene, LayerMask::ALL);
+    assert_eq!(touchable, vec![dialog, ok, cancel]);
+    assert!(!touchable.contains(&save));
+
+    modals.close_top(&scene, &mut focus);
+    assert_eq!(modals.filter(&scene, sample(open, 0)).hit, Some(open));
+    assert_eq!(modals.touchable_nodes(&scene, LayerMask::ALL), scene.touchable_nodes(LayerMask::ALL));
+}
+
+#[test]
+fn test_dim_applies_behind_the_top_dialog() {
+    let (scene, mut focus, [open, _, dialog, ok, _, confirm, yes, _]) = page();
+    let mut modals = ModalStack::new().with_dim(0.7);
+    assert_eq!(modals.dim(&scene, open), 0.0);
+    modals.open(&scene, &mut focus, dialog);
+    assert!((modals.dim(&scene, open) - 0.7).abs() < TEST_EPSILON);
+    assert_eq!(modals.dim(&scene, ok), 0.0);
+    modals.open(&scene, &mut focus, confirm);
+    assert!((modals.dim(&scene, ok) - 0.7).abs() < TEST_EPSILON);
+    assert_eq!(modals.dim(&scene, yes), 0.0);
+    assert_eq!(ModalStack::new().with_dim(2.0), ModalStack::new().with_dim(1.0));
+}
+
+#[test]
+fn test_removed_dialog_closes() {
+    let (mut scene, mut focus, [_, save, dialog, ..]) = page();
+    let mut modals = ModalStack::new();
+    assert!(focus.focus(&scene, save));
+    modals.open(&scene, &mut focus, dialog);
+    scene.remove(dialog).unwrap();
+    assert_eq!(modals.retain_in(&scene, &mut focus), 1);
+    assert!(!modals.is_open(dialog));
+    assert_eq!(focus.focused(&scene), Some(save));
+}
@@ -0,0 +1,122 @@
+use super::*;
+use crate::core::Deg;
+use crate::scene::NodeBuilder;
+use crate::ui::EventDispatcher;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+/// A pane of glass, a menu with a button on it and a wall, stacked along -Z, plus
+/// a small overlay panel on layer 1 behind them all.
+fn scene() -> Scene {
+    NodeBuilder::group()
+        .child(NodeBuilder::panel().name("glass").at(0.0, 0.0, -0.5).size(1.0, 1.0, 0.01).pickable(false))
+        .child(
+            NodeBuilder::panel()
+                .name("menu")
+                .at(0.0, 0.0, -1.0)
+                .size(1.0, 1.0, 0.1)
+                .child(NodeBuilder::button("ok").name("ok").at(0.0, 0.0, 0.06).size(0.2, 0.2, 0.02)),
+        )
+        .child(NodeBuilder::panel().name("wall").at(0.0, 0.0, -2.0).size(4.0, 4.0, 0.1))
+        .child(NodeBuilder::panel().name("overlay").at(0.0, 0.0, -3.0).size(0.5, 0.5, 0.1).layer(1))
+        .into_scene()
+}
+
+fn forward(x: f32) -> Ray {
+    Ray::new(Vec3::new(x, 0.0, 0.0), -Vec3::unit_z())
+}
+
+fn picked(picker: &Picker, scene: &Scene, ray: &Ray) -> Option<NodeId> {
+    picker.pick(scene, ray).map(|hit| hit.node)
+}
+
+#[test]
+fn test_picks_nearest_pickable_node() {
+    let scene = scene();
+    let picker = Picker::new(&scene);
+    // The glass is not pickable, the button sits in front of its menu
+    assert_eq!(picked(&picker, &scene, &forward(0.0)), scene.find("ok"));
+    assert_eq!(picked(&picker, &scene, &forward(0.3)), scene.find("menu"));
+    assert_eq!(picked(&picker, &scene, &forward(1.5)), scene.find("wall"));
+    assert_eq!(picked(&picker, &scene, &forward(3.0)), None);
+}
+
+#[test]
+fn test_layers_are_searched_in_priority_order() {
+    let scene = scene();
+    let picker = Picker::new(&scene).with_layers([LayerMask::layer(1), LayerMask::DEFAULT]);
+    assert_eq!(picked(&picker, &scene, &forward(0.0)), scene.find("overlay"));
+    // Off the overlay, the world layer is next
+    assert_eq!(picked(&picker, &scene, &forward(0.4)), scene.find("menu"));
+
+    let world_only = Picker::new(&scene).with_layers([LayerMask::DEFAULT]);
+    assert_eq!(picked(&world_only, &scene, &forward(0.0)), scene.find("ok"));
+}
+
+#[test]
+fn test_disabled_subtrees_block_but_pick_nothing() {
+    let scene = scene();
+    let menu = scene.find("menu").unwrap();
+    let ok = scene.find("ok").unwrap();
+    let mut picker = Picker::new(&scene);
+
+    picker.set_enabled(menu, false);
+    assert!(!picker.is_enabled(&scene, ok));
+    // Neither the button nor the menu reacts, and the wall behind stays covered
+    assert_eq!(picked(&picker, &scene, &forward(0.0)), None);
+    assert_eq!(picked(&picker, &scene, &forward(0.3)), None);
+    assert_eq!(picked(&picker, &scene, &forward(1.5)), scene.find("wall"));
+
+    picker.set_enabled(menu, true);
+    assert_eq!(picked(&picker, &scene, &forward(0.0)), Some(ok));
+}
+
+#[test]
+fn test_max_distance_limits_reach() {
+    let scene = scene();
+    let picker = Picker::new(&scene).with_max_distance(1.5);
+    assert_eq!(picked(&picker, &scene, &forward(0.3)), scene.find("menu"));
+    assert_eq!(picked(&picker, &scene, &forward(1.5)), None);
+}
+
+#[test]
+fn test_rebuild_sees_moved_nodes() {
+    let mut scene = scene();
+    let mut picker = Picker::new(&scene);
+    let wall = scene.find("wall").unwrap();
+    scene.get_mut(wall).unwrap().position.x = 10.0;
+    assert_eq!(picked(&picker, &scene, &forward(1.5)), Some(wall));
+    picker.rebuild(&scene);
+    assert_eq!(picked(&picker, &scene, &forward(1.5)), None);
+}
+
+#[test]
+fn test_sample_feeds_the_dispatcher() {
+    let scene = scene();
+    let picker = Picker::new(&scene);
+    let pointer = PointerId(3);
+
+    let sample = picker.sample(&scene, pointer, &forward(0.0), 1, 42);
+    assert_eq!(sample.hit, scene.find("ok"));
+    assert!(sample.position.distance_to(Vec3::new(0.0, 0.0, -0.93)) < TEST_EPSILON);
+    assert_eq!((sample.pointer, sample.buttons, sample.time_us), (pointer, 1, 42));
+
+    let mut dispatcher = EventDispatcher::new();
+    dispatcher.dispatch(&scene, sample);
+    assert_eq!(dispatcher.captured(pointer), scene.find("ok"));
+
+    // A miss leaves the pointer at the ray origin
+    let miss = picker.sample(&scene, pointer, &forward(3.0), 0, 43);
+    assert_eq!(miss.hit, None);
+    assert_eq!(miss.position, Vec3::new(3.0, 0.0, 0.0));
+}
+
+#[test]
+fn test_controller_ray_points_along_local_forward() {
+    let ray = controller_ray(Vec3::unit_y(), Quat::identity());
+    assert_eq!(ray.origin, Vec3::unit_y());
+    assert!(ray.direction.distance_to(-Vec3::unit_z()) < TEST_EPSILON);
+
+    let turned = controller_ray(Vec3::zero(), Quat::from_axis_angle(Vec3::unit_y(), Deg(90.0)));
+    assert!(turned.direction.distance_to(-Vec3::unit_x()) < TEST_EPSILON);
+}
@@ -0,0 +1,70 @@
+use super::*;
+
+crate::haptic_widget! {
+    /// Test widget with one property of each kind.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct DialState {
+        pub value: f32,
+        pub enabled: bool,
+        pub anchor: Vec3,
+        pub caption: String,
+    }
+}
+
+fn dial() -> DialState {
+    DialState { value: 0.5, enabled: true, anchor: Vec3::new(1.0, 2.0, 3.0), caption: "Gain".into() }
+}
+
+#[test]
+fn test_properties_are_registered_in_order() {
+    let names: Vec<_> = dial().properties().iter().map(|p| (p.name, p.kind)).collect();
+    assert_eq!(
+        names,
+        vec![
+            ("value", PropertyKind::F32),
+            ("enabled", PropertyKind::Bool),
+            ("anchor", PropertyKind::Vec3),
+            ("caption", PropertyKind::Text),
+        ]
+    );
+    assert_eq!(DialState::type_name(), "DialState");
+}
+
+#[test]
+fn test_get_and_set_by_name() {
+    let mut state = dial();
+    assert_eq!(state.get("value"), Some(PropertyValue::F32(0.5)));
+    state.set("value", PropertyValue::F32(0.75)).unwrap();
+    assert_eq!(state.value, 0.75);
+
+    assert_eq!(
+        state.set("value", PropertyValue::Bool(true)),
+        Err(PropertyError::TypeMismatch { name: "value", expected: PropertyKind::F32 })
+    );
+    assert_eq!(state.set("missing", PropertyValue::F32(1.0)), Err(PropertyError::Unknown("missing".into())));
+}
+
+#[test]
+fn test_serialization_round_trip() {
+    let mut original = dial();
+    original.caption = "Line one\nback\\slash".into();
+    let text = original.serialize();
+
+    let mut restored = DialState { value: 0.0, enabled: false, anchor: Vec3::zero(), caption: String::new() };
+    restored.deserialize(&text).unwrap();
+    assert_eq!(restored, original);
+
+    assert_eq!(restored.deserialize("value 3"), Err(PropertyError::Parse { line: 1 }));
+}
+
+#[test]
+fn test_diff_reports_changed_properties() {
+    let a = dial();
+    let mut b = dial();
+    b.enabled = false;
+    b.caption = "Trim".into();
+    assert_eq!(
+        b.diff(&a),
+        vec![("enabled", PropertyValue::Bool(false)), ("caption", PropertyValue::Text("Trim".into()))]
+    );
+}
@@ -0,0 +1,95 @@
+use super::*;
+use crate::scene::{NodeBuilder, NodeFlags};
+
+const TEST_EPSILON: f32 = 1e-5;
+
+/// Two 2 cm buttons 10 cm apart, facing +Z with their fronts at z = 0.005.
+fn scene() -> (Scene, NodeId, NodeId) {
+    let scene = NodeBuilder::group()
+        .child(NodeBuilder::button("a").name("a").size(0.02, 0.02, 0.01))
+        .child(NodeBuilder::button("b").name("b").at(0.1, 0.0, 0.0).size(0.02, 0.02, 0.01))
+        .into_scene();
+    let (a, b) = (scene.find("a").unwrap(), scene.find("b").unwrap());
+    (scene, a, b)
+}
+
+/// Cursor `gap` in front of button a.
+fn above_a(gap: f32) -> Vec3 {
+    Vec3::new(0.0, 0.0, 0.005 + gap)
+}
+
+#[test]
+fn test_states_follow_distance() {
+    let (scene, a, b) = scene();
+    let mut tracker = ProximityTracker::new();
+
+    assert!(tracker.update(&scene, above_a(0.1)).is_empty());
+    assert_eq!(tracker.state(a), Proximity::Far);
+
+    let changes = tracker.update(&scene, above_a(0.02));
+    assert_eq!(changes.len(), 1);
+    assert_eq!((changes[0].node, changes[0].from, changes[0].to), (a, Proximity::Far, Proximity::Near));
+    assert!((changes[0].distance - 0.02).abs() < TEST_EPSILON);
+    assert!((tracker.distance(a).unwrap() - 0.02).abs() < TEST_EPSILON);
+    assert_eq!(tracker.state(b), Proximity::Far);
+
+    tracker.update(&scene, above_a(0.001));
+    assert_eq!(tracker.state(a), Proximity::Hover);
+    assert_eq!(tracker.hovered().collect::<Vec<_>>(), vec![a]);
+
+    // Inside the box counts as hovering too
+    assert!(tracker.update(&scene, Vec3::zero()).is_empty());
+    let changes = tracker.update(&scene, above_a(0.1));
+    assert_eq!((changes[0].from, changes[0].to), (Proximity::Hover, Proximity::Far));
+    assert_eq!(tracker.distance(a), None);
+}
+
+#[test]
+fn test_leaving_takes_extra_distance() {
+    let (scene, a, _) = scene();
+    let mut tracker = ProximityTracker::new();
+    tracker.update(&scene, above_a(0.029));
+    assert_eq!(tracker.state(a), Proximity::Near);
+    // Just past 3 cm still near, past 3.3 cm far
+    tracker.update(&scene, above_a(0.031));
+    assert_eq!(tracker.state(a), Proximity::Near);
+    tracker.update(&scene, above_a(0.034));
+    assert_eq!(tracker.state(a), Proximity::Far);
+    // Coming back needs the plain threshold
+    tracker.update(&scene, above_a(0.031));
+    assert_eq!(tracker.state(a), Proximity::Far);
+}
+
+#[test]
+fn test_cue_grows_until_contact() {
+    let (scene, _, _) = scene();
+    let mut tracker = ProximityTracker::new();
+    tracker.update(&scene, above_a(0.1));
+    assert_eq!(tracker.cue(), None);
+
+    let mut last = 0.0;
+    for gap in [0.025, 0.015, 0.005, 0.0025] {
+        tracker.update(&scene, above_a(gap));
+        let intensity = tracker.cue().unwrap().intensity;
+        assert!(intensity > last && intensity <= 0.25);
+        last = intensity;
+    }
+    // Felt directly once touching
+    tracker.update(&scene, above_a(0.001));
+    assert_eq!(tracker.cue(), None);
+}
+
+#[test]
+fn test_hidden_and_removed_widgets_go_far() {
+    let (mut scene, a, b) = scene();
+    let mut tracker = ProximityTracker::new().with_distances(Meters(0.2), Meters(0.002));
+    tracker.update(&scene, above_a(0.01));
+    assert_eq!((tracker.state(a), tracker.state(b)), (Proximity::Near, Proximity::Near));
+
+    scene.get_mut(a).unwrap().flags.set(NodeFlags::PICKABLE, false);
+    scene.remove(b).unwrap();
+    let mut changes = tracker.update(&scene, above_a(0.01));
+    changes.sort_by_key(|c| c.node != a);
+    let changes: Vec<_> = changes.iter().map(|c| (c.node, c.to)).collect();
+    assert_eq!(changes, vec![(a, Proximity::Far), (b, Proximity::Far)]);
+}
@@ -0,0 +1,136 @@
+use super::*;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+const FRAME: Seconds = Seconds(0.01);
+
+/// Drags `scroll` by `step` every frame, `frames` times.
+fn drag(scroll: &mut KineticScroll, step: f32, frames: usize) {
+    for _ in 0..frames {
+        scroll.drag(step, FRAME);
+    }
+}
+
+fn settle(scroll: &mut KineticScroll) -> usize {
+    let mut frames = 0;
+    while scroll.is_moving() && frames < 10_000 {
+        scroll.advance(FRAME);
+        frames += 1;
+    }
+    frames
+}
+
+#[test]
+fn test_flick_coasts_with_friction() {
+    let mut scroll = KineticScroll::new(1.0);
+    scroll.press();
+    // Slow at first; only the last 50 ms count
+    drag(&mut scroll, 0.0001, 10);
+    drag(&mut scroll, 0.002, 5);
+    let held = scroll.position();
+    scroll.release();
+    assert!((scroll.velocity() - 0.2).abs() < TEST_EPSILON);
+    assert!(scroll.is_moving());
+
+    settle(&mut scroll);
+    assert!(!scroll.is_moving());
+    // 0.2 m/s decaying at 4/s coasts about 5 cm
+    let coasted = scroll.position() - held;
+    assert!((coasted - 0.05).abs() < 2e-3, "{coasted}");
+    assert!(scroll.take_feedback().is_empty());
+}
+
+#[test]
+fn test_holding_still_before_release_does_not_flick() {
+    let mut scroll = KineticScroll::new(1.0);
+    scroll.press();
+    drag(&mut scroll, 0.002, 5);
+    drag(&mut scroll, 0.0, 6);
+    scroll.release();
+    assert_eq!(scroll.velocity(), 0.0);
+    assert!(!scroll.is_moving());
+}
+
+#[test]
+fn test_rubber_band_resists_and_springs_back() {
+    let mut scroll = KineticScroll::new(0.1);
+    scroll.press();
+    drag(&mut scroll, -0.01, 1);
+    // Just past the end the content moves about half as far as the drag
+    let first = -scroll.position();
+    assert!(first > 0.0 && first < 0.0055, "{first}");
+    drag(&mut scroll, -0.01, 20);
+    assert!(scroll.overscroll() < 0.0);
+    assert!(scroll.overscroll() > -scroll.max_overscroll);
+    // Further out each step moves it less
+    let before = scroll.position();
+    drag(&mut scroll, -0.01, 1);
+    assert!(before - scroll.position() < first);
+
+    // Let go after holding still; catching it again picks it up where it is
+    drag(&mut scroll, 0.0, 6);
+    let stretched = scroll.position();
+    scroll.release();
+    scroll.advance(FRAME);
+    assert!(scroll.position() > stretched);
+    scroll.press();
+    let caught = scroll.position();
+    drag(&mut scroll, 0.0, 1);
+    assert!((scroll.position() - caught).abs() < TEST_EPSILON);
+    scroll.release();
+
+    let frames = settle(&mut scroll);
+    assert!(frames < 100, "{frames}");
+    assert_eq!(scroll.position(), 0.0);
+    assert_eq!(scroll.overscroll(), 0.0);
+
+    let mut hard = KineticScroll::new(0.1).with_rubber_band(0.0, 0.0);
+    hard.drag(-0.01, FRAME);
+    assert_eq!(hard.position(), 0.0);
+}
+
+#[test]
+fn test_hitting_an_end_thunks_once() {
+    let mut scroll = KineticScroll::new(0.02);
+    scroll.scroll_to(0.01);
+    scroll.press();
+    drag(&mut scroll, 0.005, 5);
+    scroll.release();
+    // Reached the end while dragging at 0.5 m/s: full strength
+    let thunks = scroll.take_feedback();
+    assert_eq!(thunks.len(), 1);
+    assert_eq!(thunks[0].intensity, 1.0);
+    assert!(thunks[0].sharpness < 0.5);
+
+    // Coasting on into the end overshoots and settles back without thunking again
+    let mut peak: f32 = 0.0;
+    while scroll.is_moving() {
+        scroll.advance(FRAME);
+        peak = peak.max(scroll.overscroll());
+    }
+    assert!(peak > 0.0 && peak <= scroll.max_overscroll + TEST_EPSILON);
+    assert!((scroll.position() - 0.02).abs() < TEST_EPSILON);
+    assert!(scroll.take_feedback().is_empty());
+
+    // Thrown gently back into the start: a weaker thunk
+    scroll.press();
+    drag(&mut scroll, -0.002, 5);
+    scroll.release();
+    settle(&mut scroll);
+    let thunks = scroll.take_feedback();
+    assert_eq!(thunks.len(), 1);
+    assert!(thunks[0].intensity < 1.0);
+    assert_eq!(scroll.position(), 0.0);
+}
+
+#[test]
+fn test_extent_changes_keep_content_inside() {
+    let mut scroll = KineticScroll::new(0.5);
+    scroll.scroll_to(0.4);
+    scroll.set_extent(0.2);
+    assert_eq!(scroll.position(), 0.2);
+    scroll.scroll_to(-1.0);
+    assert_eq!(scroll.position(), 0.0);
+    scroll.set_extent(-1.0);
+    assert_eq!(scroll.extent(), 0.0);
+}
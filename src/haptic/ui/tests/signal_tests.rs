@@ -0,0 +1,104 @@
+use super::*;
+use crate::scene::Scene;
+use crate::ui::WidgetDesc;
+
+crate::haptic_widget! {
+    /// Readout with a caption and a needle position.
+    #[derive(Debug, Clone, Default)]
+    pub struct GaugeState {
+        pub caption: String,
+        pub value: f32,
+    }
+}
+
+fn gauge(scene: &mut Scene, tree: &mut WidgetTree) -> NodeId {
+    tree.update(scene, &[WidgetDesc::panel().key("gauge").state::<GaugeState>()]).unwrap();
+    tree.find(&["gauge"]).unwrap()
+}
+
+#[test]
+fn test_signal_shares_value_and_counts_writes() {
+    let signal = Signal::new(1.0_f32);
+    let shared = signal.clone();
+    assert_eq!(signal.version(), 0);
+    shared.set(2.0);
+    signal.update(|v| *v *= 3.0);
+    assert_eq!(signal.get(), 6.0);
+    assert_eq!(shared.version(), 2);
+    assert!(signal.with(|v| *v > 5.0));
+}
+
+#[test]
+fn test_flush_pushes_changes_once_per_frame() {
+    let mut scene = Scene::new();
+    let mut tree = WidgetTree::new();
+    let node = gauge(&mut scene, &mut tree);
+    let temperature = Signal::new(20.0_f32);
+    let mut bindings = Bindings::new();
+    bindings.bind(&temperature, node, "value");
+    bindings.bind_map(&temperature, node, "caption", |t| format!("{:.1} °C", t));
+
+    // New bindings push on their first flush
+    assert_eq!(bindings.flush(&mut tree), (2, Vec::new()));
+    assert_eq!(tree.get::<String>(node, "caption"), Some("20.0 °C".to_string()));
+    assert_eq!(bindings.flush(&mut tree), (0, Vec::new()));
+
+    // Several writes in one frame arrive as one update with the latest value
+    temperature.set(21.0);
+    temperature.set(22.5);
+    assert_eq!(tree.get::<f32>(node, "value"), Some(20.0));
+    assert_eq!(bindings.flush(&mut tree), (2, Vec::new()));
+    assert_eq!(tree.get::<f32>(node, "value"), Some(22.5));
+    assert_eq!(tree.get::<String>(node, "caption"), Some("22.5 °C".to_string()));
+}
+
+#[test]
+fn test_unbind_and_removed_widgets() {
+    let mut scene = Scene::new();
+    let mut tree = WidgetTree::new();
+    let node = gauge(&mut scene, &mut tree);
+    let level = Signal::new(0.5_f32);
+    let mut bindings = Bindings::new();
+    let id = bindings.bind(&level, node, "value");
+    bindings.bind(&level, node, "value");
+    assert_eq!(bindings.len(), 2);
+    assert!(bindings.unbind(id));
+    assert!(!bindings.unbind(id));
+
+    tree.clear(&mut scene);
+    level.set(0.75);
+    assert_eq!(bindings.flush(&mut tree), (0, Vec::new()));
+    assert!(bindings.is_empty());
+    assert_eq!(bindings.unbind_node(node), 0);
+}
+
+#[test]
+fn test_type_mismatch_is_reported_and_retried() {
+    let mut scene = Scene::new();
+    let mut tree = WidgetTree::new();
+    let node = gauge(&mut scene, &mut tree);
+    let label = Signal::new(String::from("hot"));
+    let needle = Signal::new(0.5_f32);
+    let mut bindings = Bindings::new();
+    let bad = bindings.bind(&label, node, "value");
+    bindings.bind(&needle, node, "value");
+
+    // The bad binding does not hold back the ones after it
+    let (count, errors) = bindings.flush(&mut tree);
+    assert_eq!(count, 1);
+    assert!(matches!(errors[..], [(id, PropertyError::TypeMismatch { name: "value", .. })] if id == bad));
+    assert_eq!(tree.get::<f32>(node, "value"), Some(0.5));
+    assert_eq!(bindings.flush(&mut tree).1.len(), 1);
+    assert_eq!(bindings.unbind_node(node), 2);
+}
+
+#[test]
+fn test_signal_survives_panicking_update() {
+    let signal = Signal::new(1_i32);
+    let shared = signal.clone();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| shared.update(|_| panic!("writer failed"))));
+    assert!(result.is_err());
+    signal.set(4);
+    assert_eq!(signal.get(), 4);
+    assert_eq!(signal.version(), 1);
+}
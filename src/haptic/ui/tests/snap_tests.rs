@@ -0,0 +1,111 @@
+use super::*;
+use crate::core::Deg;
+use crate::scene::NodeBuilder;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+/// A 10 cm crate centered on the origin.
+fn crate_scene() -> (Scene, NodeId) {
+    let scene = NodeBuilder::group().child(NodeBuilder::panel().name("crate").size(0.1, 0.1, 0.1)).into_scene();
+    let id = scene.find("crate").unwrap();
+    (scene, id)
+}
+
+fn close(a: Vec3, b: Vec3) -> bool {
+    a.distance_to(b) < TEST_EPSILON
+}
+
+#[test]
+fn test_corners_edges_then_faces() {
+    let (scene, id) = crate_scene();
+    let mut snapper = Snapper::new();
+
+    let p = snapper.snap(&scene, Vec3::new(0.052, 0.049, 0.055), &[]);
+    assert!(close(p, Vec3::splat(0.05)));
+    let snap = *snapper.current().unwrap();
+    assert_eq!((snap.kind, snap.node), (SnapKind::Vertex, Some(id)));
+    assert_eq!(snap.guide, SnapGuide::Vertex { point: p });
+
+    let p = snapper.snap(&scene, Vec3::new(0.0, 0.053, 0.055), &[]);
+    assert!(close(p, Vec3::new(0.0, 0.05, 0.05)));
+    let Some(SnapGuide::Edge { from, to, .. }) = snapper.current().map(|s| s.guide) else {
+        panic!("expected an edge snap");
+    };
+    assert!(close(from, Vec3::new(-0.05, 0.05, 0.05)) && close(to, Vec3::splat(0.05)));
+
+    let p = snapper.snap(&scene, Vec3::new(0.01, 0.02, 0.056), &[]);
+    assert!(close(p, Vec3::new(0.01, 0.02, 0.05)));
+    assert_eq!(snapper.current().unwrap().kind, SnapKind::Surface);
+    assert_eq!(snapper.current().unwrap().normal(), Some(Vec3::unit_z()));
+    // From inside, out to the nearest face
+    let p = snapper.snap(&scene, Vec3::new(0.0, -0.046, 0.0), &[]);
+    assert!(close(p, Vec3::new(0.0, -0.05, 0.0)));
+    assert_eq!(snapper.current().unwrap().normal(), Some(-Vec3::unit_y()));
+
+    // Only faces
+    let mut faces = Snapper::new().with_features(false, false, true);
+    faces.snap(&scene, Vec3::new(0.052, 0.049, 0.055), &[]);
+    assert_eq!(faces.current().unwrap().kind, SnapKind::Surface);
+}
+
+#[test]
+fn test_grid_when_nothing_is_in_reach() {
+    let (scene, id) = crate_scene();
+    let mut snapper = Snapper::new();
+    let free = Vec3::new(0.2, 0.31, -0.011);
+    assert_eq!(snapper.snap(&scene, free, &[]), free);
+    assert!(snapper.current().is_none());
+
+    let mut snapper = snapper.with_grid(Some(0.025)).with_grid_origin(Vec3::new(0.0, 0.0, 0.005));
+    let p = snapper.snap(&scene, free, &[]);
+    assert!(close(p, Vec3::new(0.2, 0.3, -0.02)), "{p:?}");
+    assert_eq!(snapper.current().unwrap().guide, SnapGuide::Grid { point: p, step: 0.025 });
+
+    // Ignored nodes are not snapped to
+    let p = snapper.snap(&scene, Vec3::new(0.052, 0.049, 0.055), &[id]);
+    assert_eq!(snapper.current().unwrap().kind, SnapKind::Grid);
+    assert!(close(p, Vec3::new(0.05, 0.05, 0.055)));
+}
+
+#[test]
+fn test_clicks_when_a_snap_engages() {
+    let (scene, _) = crate_scene();
+    let mut snapper = Snapper::new().with_grid(Some(0.1));
+    // Sliding over one face clicks once
+    snapper.snap(&scene, Vec3::new(0.01, 0.02, 0.056), &[]);
+    snapper.snap(&scene, Vec3::new(0.0, 0.0, 0.054), &[]);
+    assert_eq!(snapper.take_feedback().len(), 1);
+    // Off into the grid, a new cell, then back onto the face
+    snapper.snap(&scene, Vec3::new(0.0, 0.0, 0.2), &[]);
+    snapper.snap(&scene, Vec3::new(0.0, 0.0, 0.21), &[]);
+    snapper.snap(&scene, Vec3::new(0.0, 0.0, 0.3), &[]);
+    snapper.snap(&scene, Vec3::new(0.0, 0.0, 0.054), &[]);
+    let clicks = snapper.take_feedback();
+    assert_eq!(clicks.len(), 3);
+    assert_eq!(clicks[0], snapper.click);
+    // A new drag engages afresh
+    snapper.release();
+    assert!(snapper.current().is_none());
+    snapper.snap(&scene, Vec3::new(0.0, 0.0, 0.054), &[]);
+    assert_eq!(snapper.take_feedback().len(), 1);
+}
+
+#[test]
+fn test_angles_snap_to_steps() {
+    let mut snapper = Snapper::new();
+    let angle = Rad::from(Deg(22.0));
+    assert_eq!(snapper.snap_angle(Vec3::unit_z(), angle), angle);
+    assert!(snapper.current().is_none());
+
+    let mut snapper = snapper.with_angle_step(Some(Deg(15.0).into()));
+    let snapped = snapper.snap_angle(Vec3::unit_z(), angle);
+    assert!((snapped.0 - 15f32.to_radians()).abs() < TEST_EPSILON);
+    snapper.snap_angle(Vec3::unit_z(), Rad::from(Deg(20.0)));
+    let snapped = snapper.snap_angle(Vec3::unit_z(), Rad::from(Deg(-23.0)));
+    assert!((snapped.0 + 30f32.to_radians()).abs() < TEST_EPSILON);
+    assert_eq!(snapper.take_feedback().len(), 2);
+    let snap = snapper.current().unwrap();
+    assert_eq!(snap.kind, SnapKind::Angle);
+    assert_eq!(snap.position(), None);
+    assert_eq!(snap.guide, SnapGuide::Angle { axis: Vec3::unit_z(), angle: snapped });
+}
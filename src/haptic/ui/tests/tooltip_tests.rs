@@ -0,0 +1,114 @@
+use super::*;
+use crate::scene::NodeBuilder;
+
+const TEST_EPSILON: f32 = 1e-5;
+
+const FRAME: Seconds = Seconds(0.1);
+
+/// Two 4 by 2 cm buttons half a meter in front of the default camera, one in
+/// the middle of the view and one near its top edge.
+fn toolbar() -> (Scene, Tooltips, NodeId, NodeId) {
+    let button = |name: &str, y: f32| NodeBuilder::button(name).name(name).at(0.0, y, -0.5).size(0.04, 0.02, 0.005);
+    let scene = NodeBuilder::group().child(button("save", 0.0)).child(button("open", 0.27)).into_scene();
+    let (save, open) = (scene.find("save").unwrap(), scene.find("open").unwrap());
+    let style = TooltipStyle { ease: Ease::Linear, ..TooltipStyle::default() };
+    let tooltips = Tooltips::new().with_style(style).with(save, "Save").with(open, String::from("Open"));
+    (scene, tooltips, save, open)
+}
+
+fn hover(tooltips: &mut Tooltips, scene: &Scene, node: Option<NodeId>, cursor: Vec3) -> Option<TooltipFrame> {
+    tooltips.update(scene, &CameraPose::default(), 1920.0, node, cursor, FRAME)
+}
+
+#[test]
+fn test_appears_after_dwell_and_fades() {
+    let (scene, mut tooltips, save, _) = toolbar();
+    let cursor = Vec3::new(0.0, 0.0, -0.5);
+    for _ in 0..4 {
+        assert_eq!(hover(&mut tooltips, &scene, Some(save), cursor), None);
+    }
+    let frame = hover(&mut tooltips, &scene, Some(save), cursor).unwrap();
+    assert_eq!(frame.node, save);
+    assert_eq!(frame.text, "Save");
+    assert!((frame.opacity - 0.1 / 0.12).abs() < TEST_EPSILON);
+    // Still sliding out from the widget
+    assert!(frame.position.y < 0.028 - TEST_EPSILON);
+
+    let frame = hover(&mut tooltips, &scene, Some(save), cursor).unwrap();
+    assert_eq!(frame.placement, TooltipPlacement::Above);
+    assert_eq!(frame.opacity, 1.0);
+    // Above the button: half its height, the gap and half the tooltip's
+    assert!(frame.position.distance_to(Vec3::new(0.0, 0.028, -0.5)) < TEST_EPSILON);
+    assert!(frame.size.approx_eq(Vec2::new(0.032, 0.024), TEST_EPSILON));
+    assert!(frame.rotation.rotate(Vec3::unit_z()).distance_to(Vec3::unit_z()) < TEST_EPSILON);
+
+    let out = tooltips.update(&scene, &CameraPose::default(), 1920.0, None, cursor, Seconds(0.05)).unwrap();
+    assert!((out.opacity - 0.375).abs() < TEST_EPSILON);
+    assert_eq!(hover(&mut tooltips, &scene, None, cursor), None);
+    assert_eq!(tooltips.shown(), None);
+}
+
+#[test]
+fn test_placement_avoids_cursor_and_view_edge() {
+    let (scene, mut tooltips, save, open) = toolbar();
+    // Cursor just above the button, where the tooltip would go
+    let above = Vec3::new(0.0, 0.03, -0.5);
+    let frame = (0..5).filter_map(|_| hover(&mut tooltips, &scene, Some(save), above)).last().unwrap();
+    assert_eq!(frame.placement, TooltipPlacement::Right);
+    assert!(frame.position.x > 0.02);
+
+    // Near the top of the view there is no room above
+    let mut tooltips = Tooltips::new().with(open, "Open");
+    let cursor = Vec3::new(0.0, 0.27, -0.5);
+    let frame = (0..5).filter_map(|_| hover(&mut tooltips, &scene, Some(open), cursor)).last().unwrap();
+    assert_eq!(frame.placement, TooltipPlacement::Right);
+}
+
+#[test]
+fn test_switches_at_once_while_showing() {
+    let (scene, mut tooltips, save, open) = toolbar();
+    let cursor = Vec3::new(0.0, 0.0, -0.5);
+    for _ in 0..6 {
+        hover(&mut tooltips, &scene, Some(save), cursor);
+    }
+    let frame = hover(&mut tooltips, &scene, Some(open), cursor).unwrap();
+    assert_eq!(frame.node, open);
+    assert_eq!(frame.text, "Open");
+    assert_eq!(frame.opacity, 1.0);
+}
+
+#[test]
+fn test_dismiss_holds_until_pointer_leaves() {
+    let (scene, mut tooltips, save, _) = toolbar();
+    let cursor = Vec3::new(0.0, 0.0, -0.5);
+    for _ in 0..6 {
+        hover(&mut tooltips, &scene, Some(save), cursor);
+    }
+    tooltips.dismiss();
+    for _ in 0..10 {
+        assert_eq!(hover(&mut tooltips, &scene, Some(save), cursor), None);
+    }
+    hover(&mut tooltips, &scene, None, cursor);
+    for _ in 0..4 {
+        assert_eq!(hover(&mut tooltips, &scene, Some(save), cursor), None);
+    }
+    assert!(hover(&mut tooltips, &scene, Some(save), cursor).is_some());
+}
+
+#[test]
+fn test_widgets_without_tooltips_and_removed_widgets() {
+    let (mut scene, mut tooltips, save, _) = toolbar();
+    let cursor = Vec3::new(0.0, 0.0, -0.5);
+    assert_eq!(tooltips.len(), 2);
+    let root = scene.get(save).unwrap().parent().unwrap();
+    for _ in 0..10 {
+        assert_eq!(hover(&mut tooltips, &scene, Some(root), cursor), None);
+    }
+    let multiline = Tooltip::new("Save\nCtrl+S");
+    assert!(multiline.size_in(tooltips.style()).approx_eq(Vec2::new(0.044, 0.040), TEST_EPSILON));
+
+    scene.remove(save).unwrap();
+    assert_eq!(hover(&mut tooltips, &scene, Some(save), cursor), None);
+    assert_eq!(tooltips.len(), 1);
+    assert!(tooltips.get(save).is_none());
+}
@@ -0,0 +1,80 @@
+use super::*;
+
+fn tour() -> Tour {
+    Tour::new(vec![
+        Waypoint::new(Vec3::new(0.0, 0.0, 0.0), 0.0).label("start").hold(1.0).look_at(Vec3::new(0.0, 0.0, -1.0)),
+        Waypoint::new(Vec3::new(1.0, 0.0, 0.0), 3.0).label("exhibit").hold(2.0),
+        Waypoint::new(Vec3::new(2.0, 1.0, 0.0), 6.0).label("end"),
+    ])
+}
+
+#[test]
+fn test_timing_holds_and_easing() {
+    let tour = tour();
+    assert_eq!(tour.duration(), 6.0);
+    assert_eq!(tour.parameter_at(0.5), 0.0);
+    assert_eq!(tour.parameter_at(1.0), 0.0);
+    assert!((tour.parameter_at(2.0) - 0.5).abs() < 1e-6);
+    assert_eq!(tour.parameter_at(4.0), 1.0);
+    assert_eq!(tour.parameter_at(10.0), 2.0);
+    // Eased: slow near the departure
+    assert!(tour.parameter_at(1.1) < 0.05);
+}
+
+#[test]
+fn test_camera_targets() {
+    let tour = tour();
+    let (position, target) = tour.camera_at(0.5);
+    assert_eq!(position, Vec3::zero());
+    assert_eq!(target, Vec3::new(0.0, 0.0, -1.0));
+
+    // Without an authored target the camera looks ahead along the path
+    let (position, target) = tour.camera_at(3.5);
+    assert!((position - Vec3::new(1.0, 0.0, 0.0)).length() < 1e-4);
+    assert!(target.x > position.x);
+}
+
+#[test]
+fn test_player_events() {
+    let mut player = TourPlayer::new(tour());
+    let mut events = Vec::new();
+    player.update(0.0, &mut events);
+    assert_eq!(events, vec![TourEvent::WaypointReached { index: 0, label: "start".into() }]);
+
+    events.clear();
+    for _ in 0..70 {
+        player.update(0.1, &mut events);
+    }
+    assert_eq!(
+        events,
+        vec![
+            TourEvent::WaypointReached { index: 1, label: "exhibit".into() },
+            TourEvent::WaypointReached { index: 2, label: "end".into() },
+            TourEvent::Finished,
+        ]
+    );
+    assert!(!player.is_playing());
+
+    player.seek(3.5);
+    player.resume();
+    events.clear();
+    player.update(0.1, &mut events);
+    assert!(events.is_empty());
+}
+
+#[test]
+fn test_cursor_constraints() {
+    let free = TourPlayer::new(tour());
+    assert_eq!(free.cursor_force(Vec3::one()), Vec3::zero());
+
+    let mut guided = TourPlayer::new(tour().with_constraint(CursorConstraint::Guide { stiffness: 10.0, max_force: 2.0 }));
+    guided.seek(4.0);
+    let force = guided.cursor_force(Vec3::new(1.0, 0.1, 0.0));
+    assert!((force - Vec3::new(0.0, -1.0, 0.0)).length() < 1e-3);
+    assert!((guided.cursor_force(Vec3::new(1.0, 5.0, 0.0)).length() - 2.0).abs() < 1e-4);
+
+    let on_path = TourPlayer::new(tour().with_constraint(CursorConstraint::Path { stiffness: 100.0, max_force: 50.0 }));
+    let point = on_path.tour().path().sample(0.4);
+    let force = on_path.cursor_force(point + Vec3::new(0.0, 0.0, 0.05));
+    assert!((force - Vec3::new(0.0, 0.0, -5.0)).length() < 0.05, "{}", force);
+}
@@ -0,0 +1,203 @@
+use super::*;
+use crate::ui::PropertyKind;
+use std::sync::{Arc, Mutex};
+
+crate::haptic_widget! {
+    /// Toggle with an app-driven caption and widget-owned interaction state.
+    #[derive(Debug, Clone, Default)]
+    pub struct ToggleState {
+        pub caption: String,
+        pub pressed: bool,
+    }
+}
+
+fn toggle(key: &str, caption: &str) -> WidgetDesc {
+    WidgetDesc::button(caption).key(key).state::<ToggleState>().prop("caption", caption.to_string())
+}
+
+fn menu(items: &[(&str, &str)]) -> Vec<WidgetDesc> {
+    vec![WidgetDesc::panel().key("menu").children(items.iter().map(|(key, caption)| toggle(key, caption)))]
+}
+
+#[test]
+fn test_first_update_creates_widgets() {
+    let mut scene = Scene::new();
+    let mut tree = WidgetTree::new();
+    let changes = tree.update(&mut scene, &menu(&[("a", "Alpha"), ("b", "Beta")])).unwrap();
+    assert_eq!(changes.len(), 3);
+    assert!(changes.iter().all(|c| matches!(c, WidgetChange::Created(_))));
+    assert_eq!((tree.len(), scene.len()), (3, 3));
+
+    let a = tree.find(&["menu", "a"]).unwrap();
+    assert_eq!(scene.get(tree.find(&["menu"]).unwrap()).unwrap().children()[0], a);
+    assert_eq!(tree.find(&["a"]), None);
+    assert_eq!(tree.get::<String>(a, "caption"), Some("Alpha".to_string()));
+    assert_eq!(scene.get(a).unwrap().kind, NodeKind::Button { label: "Alpha".into() });
+}
+
+#[test]
+fn test_rebuild_keeps_nodes_and_interaction_state() {
+    let mut scene = Scene::new();
+    let mut tree = WidgetTree::new();
+    tree.update(&mut scene, &menu(&[("a", "Alpha"), ("b", "Beta")])).unwrap();
+    let menu_node = tree.roots().next().unwrap();
+    let b = scene.get(menu_node).unwrap().children()[1];
+    tree.set(b, "pressed", true).unwrap();
+
+    // An identical description changes nothing
+    assert_eq!(tree.update(&mut scene, &menu(&[("a", "Alpha"), ("b", "Beta")])).unwrap(), vec![]);
+
+    // Only the difference is applied, and the press survives
+    let changes = tree.update(&mut scene, &menu(&[("a", "Alpha"), ("b", "Bravo")])).unwrap();
+    assert_eq!(
+        changes,
+        vec![
+            WidgetChange::Node(b),
+            WidgetChange::Property { node: b, name: "caption", value: PropertyValue::Text("Bravo".into()) },
+        ]
+    );
+    assert_eq!(tree.get::<bool>(b, "pressed"), Some(true));
+    assert_eq!(tree.roots().next(), Some(menu_node));
+}
+
+#[test]
+fn test_keyed_widgets_follow_reorders() {
+    let mut scene = Scene::new();
+    let mut tree = WidgetTree::new();
+    tree.update(&mut scene, &menu(&[("a", "Alpha"), ("b", "Beta"), ("c", "Gamma")])).unwrap();
+    let menu_node = tree.roots().next().unwrap();
+    let before = scene.get(menu_node).unwrap().children().to_vec();
+
+    let changes = tree.update(&mut scene, &menu(&[("c", "Gamma"), ("a", "Alpha")])).unwrap();
+    assert_eq!(changes, vec![WidgetChange::Removed(before[1])]);
+    assert_eq!(scene.get(menu_node).unwrap().children(), &[before[2], before[0]]);
+    assert!(!scene.contains(before[1]));
+    assert_eq!(tree.len(), 3);
+}
+
+#[test]
+fn test_mismatched_widget_is_replaced() {
+    let mut scene = Scene::new();
+    let mut tree = WidgetTree::new();
+    tree.update(&mut scene, &[toggle("x", "On")]).unwrap();
+    let old = tree.roots().next().unwrap();
+
+    let changes = tree.update(&mut scene, &[WidgetDesc::label("On").key("x")]).unwrap();
+    let new = tree.roots().next().unwrap();
+    assert_eq!(changes, vec![WidgetChange::Removed(old), WidgetChange::Created(new)]);
+    assert!(tree.state(new).is_none());
+    assert_eq!(scene.len(), 1);
+
+    tree.clear(&mut scene);
+    assert!(tree.is_empty() && scene.is_empty());
+}
+
+#[test]
+fn test_bad_property_is_reported() {
+    let mut scene = Scene::new();
+    let mut tree = WidgetTree::new();
+    let result = tree.update(&mut scene, &[WidgetDesc::group().state::<ToggleState>().prop("caption", 1.0f32)]);
+    let error = PropertyError::TypeMismatch { name: "caption", expected: PropertyKind::Text };
+    assert_eq!(result, Err(WidgetError::Property(error)));
+    assert_eq!(tree.len(), scene.len());
+}
+
+#[test]
+fn test_hook_removing_the_parent_stops_the_update() {
+    let mut scene = Scene::new();
+    let mut tree = WidgetTree::new();
+    let first = toggle("a", "Alpha").on_mount(|cx| {
+        let parent = cx.scene.get(cx.node).and_then(|n| n.parent()).unwrap();
+        cx.scene.remove(parent).unwrap();
+    });
+    let descs = [WidgetDesc::panel().key("menu").children([first, toggle("b", "Beta")])];
+
+    let result = tree.update(&mut scene, &descs);
+    assert!(matches!(result, Err(WidgetError::Scene(SceneError::InvalidNode(_)))));
+    assert!(scene.is_empty());
+
+    // The next update rebuilds what the hook removed
+    let result = tree.update(&mut scene, &menu(&[("a", "Alpha"), ("b", "Beta")]));
+    assert!(result.is_ok());
+    assert_eq!((tree.len(), scene.len()), (3, 3));
+}
+
+/// Toggle logging its lifecycle as "<hook> <key>"; it asks for an update when
+/// mounted and keeps updating while pressed.
+fn hooked(key: &'static str, log: &Arc<Mutex<Vec<String>>>) -> WidgetDesc {
+    let (mount, unmount, update) = (log.clone(), log.clone(), log.clone());
+    toggle(key, key)
+        .on_mount(move |cx| {
+            mount.lock().unwrap().push(format!("mount {key}"));
+            cx.request_update();
+        })
+        .on_unmount(move |_| unmount.lock().unwrap().push(format!("unmount {key}")))
+        .on_update(move |cx| {
+            update.lock().unwrap().push(format!("update {key} {}", cx.dt.value()));
+            if cx.state.as_ref().and_then(|s| s.get("pressed")) == Some(PropertyValue::Bool(true)) {
+                cx.request_update();
+            }
+        })
+}
+
+#[test]
+fn test_hooks_run_on_mount_unmount_and_due_ticks() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let taken = || std::mem::take(&mut *log.lock().unwrap());
+    let menu_log = log.clone();
+    let menu = |keys: &[&'static str]| {
+        let menu_log = menu_log.clone();
+        vec![WidgetDesc::panel()
+            .key("menu")
+            .on_unmount(move |_| menu_log.lock().unwrap().push("unmount menu".into()))
+            .children(keys.iter().map(|key| hooked(key, &log)))]
+    };
+    let mut scene = Scene::new();
+    let mut tree = WidgetTree::new();
+    let dt = Seconds(0.5);
+
+    // Mounting asks for one update each; then the tree idles
+    tree.update(&mut scene, &menu(&["a", "b"])).unwrap();
+    assert_eq!(taken(), ["mount a", "mount b"]);
+    assert_eq!(tree.tick(&mut scene, dt), 2);
+    assert_eq!(taken(), ["update a 0.5", "update b 0.5"]);
+    assert!(tree.scheduler().is_idle());
+    assert_eq!(tree.tick(&mut scene, dt), 0);
+
+    // An animation keeps itself going until it stops asking
+    let a = tree.find(&["menu", "a"]).unwrap();
+    tree.set(a, "pressed", true).unwrap();
+    tree.scheduler_mut().request_update(a);
+    assert_eq!((tree.tick(&mut scene, dt), tree.tick(&mut scene, dt)), (1, 1));
+    tree.set(a, "pressed", false).unwrap();
+    assert_eq!((tree.tick(&mut scene, dt), tree.tick(&mut scene, dt)), (1, 0));
+    taken();
+
+    // Interacting widgets update every tick until they are unmounted
+    let b = tree.find(&["menu", "b"]).unwrap();
+    tree.scheduler_mut().set_interacting(b, true);
+    assert_eq!((tree.tick(&mut scene, dt), tree.tick(&mut scene, dt)), (1, 1));
+    tree.update(&mut scene, &menu(&["a"])).unwrap();
+    assert!(!tree.scheduler().is_due(b));
+    assert_eq!(tree.tick(&mut scene, dt), 0);
+    assert_eq!(taken(), ["update b 0.5", "update b 0.5", "unmount b"]);
+
+    // Children unmount before their parent
+    tree.clear(&mut scene);
+    assert_eq!(taken(), ["unmount a", "unmount menu"]);
+}
+
+#[test]
+fn test_widgets_removed_from_scene_are_rebuilt() {
+    let mut scene = Scene::new();
+    let mut tree = WidgetTree::new();
+    tree.update(&mut scene, &menu(&[("a", "Alpha")])).unwrap();
+    let old = tree.find(&["menu"]).unwrap();
+    scene.remove(old).unwrap();
+
+    let changes = tree.update(&mut scene, &menu(&[("a", "Alpha")])).unwrap();
+    assert_eq!(changes[0], WidgetChange::Removed(old));
+    let rebuilt = tree.find(&["menu", "a"]).unwrap();
+    assert_eq!(scene.get(rebuilt).unwrap().parent(), tree.find(&["menu"]));
+    assert_eq!((tree.len(), scene.len()), (2, 2));
+}
@@ -0,0 +1,338 @@
+//! Tooltips: short help shown next to a widget after hovering it.
+//!
+//! [`Tooltips`] holds the text of each widget that has a tooltip and, fed the
+//! hovered node every frame, decides which one to show:
+//!
+//! - A tooltip appears once its widget has been hovered for the dwell delay.
+//!   While one is showing, moving onto another widget with a tooltip switches
+//!   to it at once. Pressing should [`dismiss`](Tooltips::dismiss) it until the
+//!   pointer leaves the widget.
+//! - It is placed beside the widget, facing the camera, on the first side
+//!   (above, right, below, left) where it neither covers the cursor nor pokes
+//!   out of the camera's view. The side is kept while it shows so it does not
+//!   jump around.
+//! - It fades and slides in and out as set by the [`TooltipStyle`] shared by
+//!   every tooltip, so they all animate alike.
+
+use std::collections::HashMap;
+
+use crate::anim::Ease;
+use crate::core::{Quat, Seconds, Vec2, Vec3};
+use crate::scene::{CameraPose, NodeId, Scene};
+
+/// Side of its widget a tooltip sits on, as seen from the camera.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TooltipPlacement {
+    Above,
+    Right,
+    Below,
+    Left,
+}
+
+impl TooltipPlacement {
+    /// In order of preference.
+    pub const ALL: [TooltipPlacement; 4] =
+        [TooltipPlacement::Above, TooltipPlacement::Right, TooltipPlacement::Below, TooltipPlacement::Left];
+
+    /// Direction from the widget to the tooltip, given the camera's right and up.
+    fn direction(self, right: Vec3, up: Vec3) -> Vec3 {
+        match self {
+            TooltipPlacement::Above => up,
+            TooltipPlacement::Right => right,
+            TooltipPlacement::Below => -up,
+            TooltipPlacement::Left => -right,
+        }
+    }
+}
+
+/// Timing, motion and metrics shared by all tooltips.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TooltipStyle {
+    /// Hover time before a tooltip appears.
+    pub delay: Seconds,
+    pub fade_in: Seconds,
+    pub fade_out: Seconds,
+    /// Curve of the fade and slide.
+    pub ease: Ease,
+    /// Distance a tooltip slides out from its widget while fading in.
+    pub slide: f32,
+    /// Space between the widget and the tooltip, and kept around the cursor.
+    pub gap: f32,
+    /// Width of a character and height of a line, for sizing from the text.
+    pub char_size: Vec2,
+    /// Space around the text.
+    pub padding: f32,
+}
+
+impl Default for TooltipStyle {
+    /// Half a second's dwell, quick eased fades and 6 by 16 mm characters.
+    fn default() -> Self {
+        Self {
+            delay: Seconds(0.5),
+            fade_in: Seconds(0.12),
+            fade_out: Seconds(0.08),
+            ease: Ease::OutCubic,
+            slide: 0.004,
+            gap: 0.006,
+            char_size: Vec2::new(0.006, 0.016),
+            padding: 0.004,
+        }
+    }
+}
+
+/// Text of one tooltip.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tooltip {
+    pub text: String,
+    /// Width and height; estimated from the text when unset.
+    pub size: Option<Vec2>,
+}
+
+impl Tooltip {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into(), size: None }
+    }
+
+    /// Uses a measured size, e.g. from a [`TextLayout`](crate::text::TextLayout), instead of the estimate.
+    pub fn with_size(mut self, size: Vec2) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Size of the tooltip in `style`.
+    pub fn size_in(&self, style: &TooltipStyle) -> Vec2 {
+        self.size.unwrap_or_else(|| {
+            let columns = self.text.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+            let rows = self.text.lines().count().max(1);
+            Vec2::new(
+                columns as f32 * style.char_size.x + 2.0 * style.padding,
+                rows as f32 * style.char_size.y + 2.0 * style.padding,
+            )
+        })
+    }
+}
+
+impl From<&str> for Tooltip {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for Tooltip {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+/// What to draw for the tooltip showing this frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TooltipFrame {
+    pub node: NodeId,
+    pub text: String,
+    pub placement: TooltipPlacement,
+    /// Center of the tooltip.
+    pub position: Vec3,
+    /// Turns the tooltip's +Z toward the camera, upright on screen.
+    pub rotation: Quat,
+    pub size: Vec2,
+    pub opacity: f32,
+}
+
+/// A tooltip on its way in, showing, or on its way out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Shown {
+    node: NodeId,
+    placement: TooltipPlacement,
+    /// Linear fade progress, 0 to 1.
+    fade: f32,
+}
+
+/// Tooltips of widgets, and the one showing.
+#[derive(Debug, Clone, Default)]
+pub struct Tooltips {
+    tips: HashMap<NodeId, Tooltip>,
+    style: TooltipStyle,
+    hovered: Option<NodeId>,
+    dwell: f32,
+    /// Hidden until the pointer leaves the hovered widget.
+    dismissed: bool,
+    shown: Option<Shown>,
+}
+
+impl Tooltips {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_style(mut self, style: TooltipStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    #[inline]
+    pub fn style(&self) -> &TooltipStyle {
+        &self.style
+    }
+
+    /// Gives `node` a tooltip, replacing any it had.
+    pub fn add(&mut self, node: NodeId, tooltip: impl Into<Tooltip>) {
+        self.tips.insert(node, tooltip.into());
+    }
+
+    pub fn with(mut self, node: NodeId, tooltip: impl Into<Tooltip>) -> Self {
+        self.add(node, tooltip);
+        self
+    }
+
+    pub fn remove(&mut self, node: NodeId) -> Option<Tooltip> {
+        self.tips.remove(&node)
+    }
+
+    pub fn get(&self, node: NodeId) -> Option<&Tooltip> {
+        self.tips.get(&node)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tips.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tips.is_empty()
+    }
+
+    /// Widget whose tooltip is showing or fading.
+    pub fn shown(&self) -> Option<NodeId> {
+        self.shown.map(|s| s.node)
+    }
+
+    /// Fades the tooltip out and keeps it hidden until the pointer leaves its
+    /// widget.
+    pub fn dismiss(&mut self) {
+        self.dismissed = true;
+    }
+
+    /// Advances by `dt` with the pointer over `hovered` (as picked) at
+    /// `cursor`; returns the tooltip to draw, if any. `viewport_width` is in
+    /// pixels, for keeping the tooltip in view.
+    pub fn update(
+        &mut self,
+        scene: &Scene,
+        camera: &CameraPose,
+        viewport_width: f32,
+        hovered: Option<NodeId>,
+        cursor: Vec3,
+        dt: Seconds,
+    ) -> Option<TooltipFrame> {
+        self.tips.retain(|&node, _| scene.contains(node));
+        let hovered = hovered.filter(|node| self.tips.contains_key(node));
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            self.dwell = 0.0;
+            self.dismissed = false;
+        }
+        let dt = dt.value().max(0.0);
+        self.dwell += dt;
+
+        let showing = self.shown.filter(|s| s.fade > 0.0 && scene.contains(s.node));
+        let wanted = hovered.filter(|_| !self.dismissed);
+        match (wanted, showing) {
+            (Some(node), Some(shown)) if shown.node == node => {
+                self.shown = Some(Shown { fade: step(shown.fade, dt, self.style.fade_in), ..shown });
+            }
+            (Some(node), Some(shown)) => {
+                let placement = self.place(scene, camera, viewport_width, node, cursor);
+                self.shown = Some(Shown { node, placement, fade: shown.fade });
+            }
+            (Some(node), None) if self.dwell >= self.style.delay.value() => {
+                let placement = self.place(scene, camera, viewport_width, node, cursor);
+                self.shown = Some(Shown { node, placement, fade: step(0.0, dt, self.style.fade_in) });
+            }
+            (_, Some(shown)) => {
+                let fade = step(shown.fade, -dt, self.style.fade_out);
+                self.shown = (fade > 0.0).then_some(Shown { fade, ..shown });
+            }
+            (_, None) => self.shown = None,
+        }
+        self.frame(scene, camera)
+    }
+
+    /// First side of `node` where its tooltip stays in view and off the
+    /// cursor; failing that, in view; failing that, above.
+    fn place(
+        &self,
+        scene: &Scene,
+        camera: &CameraPose,
+        viewport_width: f32,
+        node: NodeId,
+        cursor: Vec3,
+    ) -> TooltipPlacement {
+        let (right, up) = camera.screen_axes();
+        let size = self.tips[&node].size_in(&self.style);
+        let half = size * 0.5;
+        let fits = |placement: TooltipPlacement| {
+            let Some(center) = self.center(scene, camera, node, placement) else {
+                return (false, false);
+            };
+            let in_view = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]
+                .iter()
+                .all(|&(x, y)| camera.sees(center + right * (x * half.x) + up * (y * half.y), viewport_width));
+            let offset = cursor - center;
+            let covers = offset.dot(right).abs() <= half.x + self.style.gap
+                && offset.dot(up).abs() <= half.y + self.style.gap;
+            (in_view, !covers)
+        };
+        let checked: Vec<_> = TooltipPlacement::ALL.into_iter().map(|p| (p, fits(p))).collect();
+        checked
+            .iter()
+            .find(|(_, (in_view, clear))| *in_view && *clear)
+            .or_else(|| checked.iter().find(|(_, (in_view, _))| *in_view))
+            .map_or(TooltipPlacement::Above, |(p, _)| *p)
+    }
+
+    /// Center of `node`'s tooltip on `placement`, fully faded in.
+    fn center(&self, scene: &Scene, camera: &CameraPose, node: NodeId, placement: TooltipPlacement) -> Option<Vec3> {
+        let (right, up) = camera.screen_axes();
+        let direction = placement.direction(right, up);
+        let widget = scene.get(node)?.size;
+        let reach =
+            0.5 * ((widget.x * direction.x).abs() + (widget.y * direction.y).abs() + (widget.z * direction.z).abs());
+        let size = self.tips.get(&node)?.size_in(&self.style);
+        let half = match placement {
+            TooltipPlacement::Above | TooltipPlacement::Below => size.y * 0.5,
+            TooltipPlacement::Right | TooltipPlacement::Left => size.x * 0.5,
+        };
+        Some(scene.world_position(node)? + direction * (reach + self.style.gap + half))
+    }
+
+    fn frame(&self, scene: &Scene, camera: &CameraPose) -> Option<TooltipFrame> {
+        let shown = self.shown?;
+        let tooltip = self.tips.get(&shown.node)?;
+        let (right, up) = camera.screen_axes();
+        let eased = self.style.ease.apply(shown.fade);
+        let direction = shown.placement.direction(right, up);
+        let center = self.center(scene, camera, shown.node, shown.placement)?;
+        Some(TooltipFrame {
+            node: shown.node,
+            text: tooltip.text.clone(),
+            placement: shown.placement,
+            position: center - direction * (self.style.slide * (1.0 - eased)),
+            rotation: Quat::from_rotation_axes(right, up, -camera.forward),
+            size: tooltip.size_in(&self.style),
+            opacity: eased.clamp(0.0, 1.0),
+        })
+    }
+}
+
+/// `fade` moved by `dt` over a fade lasting `duration`, within 0 to 1.
+fn step(fade: f32, dt: f32, duration: Seconds) -> f32 {
+    if duration.value() <= 0.0 {
+        return if dt < 0.0 { 0.0 } else { 1.0 };
+    }
+    (fade + dt / duration.value()).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+#[path = "tests/tooltip_tests.rs"]
+mod tests;
@@ -0,0 +1,245 @@
+//! Guided tours along authored spline paths.
+//!
+//! A tour moves the camera through timed waypoints for onboarding sequences and
+//! kiosk demos. While it plays, the haptic cursor can be left free, pulled toward
+//! the path, or pulled toward the moving guide point, so the user feels where the
+//! tour is heading.
+
+use crate::core::{CatmullRom, Vec3};
+
+// ============================================================================
+// Authoring
+// ============================================================================
+
+/// A point the camera passes through.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Waypoint {
+    pub position: Vec3,
+    /// Where the camera looks while at this waypoint; None looks along the path.
+    pub look_at: Option<Vec3>,
+    /// Seconds from tour start at which the camera arrives here.
+    pub arrive: f32,
+    /// Seconds the camera stays before moving on.
+    pub hold: f32,
+    pub label: String,
+}
+
+impl Waypoint {
+    pub fn new(position: Vec3, arrive: f32) -> Self {
+        Self { position, look_at: None, arrive, hold: 0.0, label: String::new() }
+    }
+
+    pub fn look_at(mut self, target: Vec3) -> Self {
+        self.look_at = Some(target);
+        self
+    }
+
+    pub fn hold(mut self, seconds: f32) -> Self {
+        self.hold = seconds;
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+}
+
+/// How the haptic cursor is guided while a tour plays.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CursorConstraint {
+    Free,
+    /// Spring toward the nearest point of the path.
+    Path { stiffness: f32, max_force: f32 },
+    /// Spring toward the camera's current position on the path.
+    Guide { stiffness: f32, max_force: f32 },
+}
+
+/// Authored tour: waypoints sorted by arrival time and the spline through them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tour {
+    waypoints: Vec<Waypoint>,
+    path: CatmullRom,
+    /// Seconds of path the camera looks ahead when a waypoint has no `look_at`.
+    pub look_ahead: f32,
+    pub constraint: CursorConstraint,
+}
+
+impl Tour {
+    pub fn new(mut waypoints: Vec<Waypoint>) -> Self {
+        waypoints.sort_by(|a, b| a.arrive.total_cmp(&b.arrive));
+        let path = CatmullRom::new(waypoints.iter().map(|w| w.position).collect());
+        Self { waypoints, path, look_ahead: 0.5, constraint: CursorConstraint::Free }
+    }
+
+    pub fn with_constraint(mut self, constraint: CursorConstraint) -> Self {
+        self.constraint = constraint;
+        self
+    }
+
+    #[inline]
+    pub fn waypoints(&self) -> &[Waypoint] {
+        &self.waypoints
+    }
+
+    #[inline]
+    pub fn path(&self) -> &CatmullRom {
+        &self.path
+    }
+
+    /// Time at which the camera leaves the last waypoint.
+    pub fn duration(&self) -> f32 {
+        self.waypoints.last().map_or(0.0, |w| w.arrive + w.hold)
+    }
+
+    /// Spline parameter at `time`. Each segment eases in and out between holds.
+    pub fn parameter_at(&self, time: f32) -> f32 {
+        let w = &self.waypoints;
+        if w.len() < 2 {
+            return 0.0;
+        }
+        let segment = w.partition_point(|wp| wp.arrive <= time).saturating_sub(1).min(w.len() - 2);
+        let depart = w[segment].arrive + w[segment].hold;
+        let arrive = w[segment + 1].arrive;
+        if time <= depart {
+            return segment as f32;
+        }
+        let u = ((time - depart) / (arrive - depart).max(f32::EPSILON)).clamp(0.0, 1.0);
+        segment as f32 + crate::core::smoothstep(0.0, 1.0, u)
+    }
+
+    /// Camera position and look target at `time`.
+    pub fn camera_at(&self, time: f32) -> (Vec3, Vec3) {
+        let t = self.parameter_at(time);
+        let position = self.path.sample(t);
+        let segment = (t.floor() as usize).min(self.waypoints.len().saturating_sub(1));
+        let u = t - segment as f32;
+        let look = |i: usize| self.waypoints.get(i).and_then(|w| w.look_at);
+        let ahead = || {
+            let p = self.path.sample(self.parameter_at(time + self.look_ahead));
+            if (p - position).length_squared() > 1e-8 {
+                p
+            } else {
+                position + self.path.tangent(t)
+            }
+        };
+        let target = match (look(segment), look(segment + 1)) {
+            (Some(a), Some(b)) => a.lerp(b, u),
+            (Some(a), None) if u <= 0.0 => a,
+            (None, Some(b)) if u >= 1.0 => b,
+            _ => ahead(),
+        };
+        (position, target)
+    }
+}
+
+// ============================================================================
+// Playback
+// ============================================================================
+
+/// Notifications emitted while a tour plays.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TourEvent {
+    WaypointReached { index: usize, label: String },
+    Finished,
+}
+
+/// Camera pose and cursor force for one frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TourFrame {
+    pub camera_position: Vec3,
+    pub camera_target: Vec3,
+    /// Point on the path the guide is at.
+    pub guide: Vec3,
+}
+
+/// Plays a tour in real time.
+#[derive(Debug, Clone)]
+pub struct TourPlayer {
+    tour: Tour,
+    time: f32,
+    playing: bool,
+    next_waypoint: usize,
+}
+
+impl TourPlayer {
+    pub fn new(tour: Tour) -> Self {
+        Self { tour, time: 0.0, playing: true, next_waypoint: 0 }
+    }
+
+    #[inline]
+    pub fn tour(&self) -> &Tour {
+        &self.tour
+    }
+
+    #[inline]
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    pub fn resume(&mut self) {
+        self.playing = self.time < self.tour.duration();
+    }
+
+    /// Jumps to `time`; waypoints before it are not reported again.
+    pub fn seek(&mut self, time: f32) {
+        self.time = time.clamp(0.0, self.tour.duration());
+        self.next_waypoint = self.tour.waypoints.partition_point(|w| w.arrive < self.time);
+    }
+
+    /// Advances by `dt` seconds, appending events to `events`.
+    pub fn update(&mut self, dt: f32, events: &mut Vec<TourEvent>) -> TourFrame {
+        if self.playing {
+            self.time = (self.time + dt).min(self.tour.duration());
+            while let Some(w) = self.tour.waypoints.get(self.next_waypoint).filter(|w| w.arrive <= self.time) {
+                events.push(TourEvent::WaypointReached { index: self.next_waypoint, label: w.label.clone() });
+                self.next_waypoint += 1;
+            }
+            if self.time >= self.tour.duration() {
+                self.playing = false;
+                events.push(TourEvent::Finished);
+            }
+        }
+        self.frame()
+    }
+
+    /// Current camera pose without advancing time.
+    pub fn frame(&self) -> TourFrame {
+        let (camera_position, camera_target) = self.tour.camera_at(self.time);
+        TourFrame { camera_position, camera_target, guide: camera_position }
+    }
+
+    /// Force pulling the cursor according to the tour's constraint.
+    pub fn cursor_force(&self, cursor: Vec3) -> Vec3 {
+        let (anchor, stiffness, max_force) = match self.tour.constraint {
+            CursorConstraint::Free => return Vec3::zero(),
+            CursorConstraint::Guide { stiffness, max_force } => (self.frame().guide, stiffness, max_force),
+            CursorConstraint::Path { stiffness, max_force } => {
+                // Search near the current progress so the pull does not jump to a
+                // distant part of a path that loops back on itself
+                let t = self.tour.parameter_at(self.time);
+                (self.tour.path.closest_point_in(cursor, t - 1.0, t + 1.0).1, stiffness, max_force)
+            }
+        };
+        let force = (anchor - cursor) * stiffness;
+        let magnitude = force.length();
+        if magnitude > max_force {
+            force * (max_force / magnitude)
+        } else {
+            force
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/tour_tests.rs"]
+mod tests;
@@ -0,0 +1,521 @@
+//! Retained widgets updated from declarative descriptions.
+//!
+//! Apps describe their whole UI every frame as a tree of [`WidgetDesc`]s and hand
+//! it to [`WidgetTree::update`]. The tree reconciles the description against the
+//! widgets it already holds: a description matches the widget at the same place
+//! with the same key (or, for unkeyed ones, the same position among unkeyed
+//! siblings), the same node kind and the same state type. Matched widgets keep
+//! their scene node and their state and only receive the differences; everything
+//! else is created or removed. Since node ids and state survive a rebuild, so does
+//! interaction state such as a held press or a hover highlight.
+//!
+//! Widget state is any [`WidgetState`] (usually a `haptic_widget!` struct). A
+//! description only sets the properties it names; the rest belong to the widget,
+//! and are read and written through the typed [`WidgetTree::get`] and
+//! [`WidgetTree::set`].
+//!
+//! Widgets can hook their mounting, unmounting and per-frame updates; the tree
+//! only runs the update hooks of widgets its [`UpdateScheduler`] has due (see
+//! [`lifecycle`](super::lifecycle)).
+
+use std::fmt;
+use std::mem;
+use std::sync::Arc;
+
+use super::lifecycle::{UpdateScheduler, WidgetContext, WidgetHook, WidgetHooks};
+use super::property::{Property, PropertyError, PropertyValue, WidgetState};
+use crate::core::{Seconds, Vec3};
+use crate::scene::{Node, NodeFlags, NodeHaptics, NodeId, NodeKind, Scene, SceneError};
+
+/// Creates the state of a new widget.
+type StateFactory = fn() -> Box<dyn WidgetState + Send>;
+
+// ============================================================================
+// Descriptions
+// ============================================================================
+
+/// What a widget should look like this frame.
+#[derive(Debug, Clone)]
+pub struct WidgetDesc {
+    pub key: Option<String>,
+    pub name: String,
+    pub kind: NodeKind,
+    pub position: Vec3,
+    pub size: Vec3,
+    pub haptics: Option<NodeHaptics>,
+    pub flags: NodeFlags,
+    state: Option<(&'static str, StateFactory)>,
+    props: Vec<(&'static str, PropertyValue)>,
+    hooks: WidgetHooks,
+    children: Vec<WidgetDesc>,
+}
+
+impl WidgetDesc {
+    pub fn new(kind: NodeKind) -> Self {
+        Self {
+            key: None,
+            name: String::new(),
+            kind,
+            position: Vec3::zero(),
+            size: Vec3::zero(),
+            haptics: None,
+            flags: NodeFlags::ALL,
+            state: None,
+            props: Vec::new(),
+            hooks: WidgetHooks::default(),
+            children: Vec::new(),
+        }
+    }
+
+    pub fn group() -> Self {
+        Self::new(NodeKind::Group)
+    }
+
+    pub fn panel() -> Self {
+        Self::new(NodeKind::Panel).haptics(NodeHaptics::default())
+    }
+
+    pub fn button(label: impl Into<String>) -> Self {
+        Self::new(NodeKind::Button { label: label.into() }).haptics(NodeHaptics::default())
+    }
+
+    pub fn label(text: impl Into<String>) -> Self {
+        Self::new(NodeKind::Label { text: text.into() })
+    }
+
+    /// Identifies the widget among its siblings, so it keeps its state when
+    /// siblings are inserted, removed or reordered.
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn at(mut self, x: f32, y: f32, z: f32) -> Self {
+        self.position = Vec3::new(x, y, z);
+        self
+    }
+
+    pub fn size(mut self, width: f32, height: f32, depth: f32) -> Self {
+        self.size = Vec3::new(width, height, depth);
+        self
+    }
+
+    pub fn haptics(mut self, haptics: NodeHaptics) -> Self {
+        self.haptics = Some(haptics);
+        self
+    }
+
+    pub fn flags(mut self, flags: NodeFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Gives the widget state of type `S`, created with its default.
+    pub fn state<S: WidgetState + Default + Send + 'static>(mut self) -> Self {
+        self.state = Some((S::type_name(), || Box::new(S::default())));
+        self
+    }
+
+    /// Sets property `name` of the widget state on every update.
+    pub fn prop(mut self, name: &'static str, value: impl Property) -> Self {
+        self.props.retain(|(n, _)| *n != name);
+        self.props.push((name, value.to_value()));
+        self
+    }
+
+    /// Runs `hook` once the widget and its children are created.
+    pub fn on_mount(mut self, hook: impl Fn(&mut WidgetContext<'_>) + Send + Sync + 'static) -> Self {
+        self.hooks.on_mount = Some(Arc::new(hook));
+        self
+    }
+
+    /// Runs `hook` just before the widget is removed.
+    pub fn on_unmount(mut self, hook: impl Fn(&mut WidgetContext<'_>) + Send + Sync + 'static) -> Self {
+        self.hooks.on_unmount = Some(Arc::new(hook));
+        self
+    }
+
+    /// Runs `hook` on every tick the widget is due.
+    pub fn on_update(mut self, hook: impl Fn(&mut WidgetContext<'_>) + Send + Sync + 'static) -> Self {
+        self.hooks.on_update = Some(Arc::new(hook));
+        self
+    }
+
+    pub fn child(mut self, child: WidgetDesc) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn children(mut self, children: impl IntoIterator<Item = WidgetDesc>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    /// Whether a widget built from `other` can be updated to this description.
+    fn matches(&self, other: &Mounted) -> bool {
+        self.key == other.key
+            && mem::discriminant(&self.kind) == other.kind
+            && self.state.map(|(name, _)| name) == other.state_type
+    }
+}
+
+// ============================================================================
+// Retained Tree
+// ============================================================================
+
+/// What an update changed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WidgetChange {
+    Created(NodeId),
+    Removed(NodeId),
+    /// Scene node fields (name, kind, placement, haptics or flags) changed.
+    Node(NodeId),
+    Property { node: NodeId, name: &'static str, value: PropertyValue },
+}
+
+/// Why an update stopped.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WidgetError {
+    Property(PropertyError),
+    /// A widget's node could not be inserted, e.g. a hook removed its parent.
+    Scene(SceneError),
+}
+
+impl fmt::Display for WidgetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WidgetError::Property(error) => write!(f, "{}", error),
+            WidgetError::Scene(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for WidgetError {}
+
+impl From<PropertyError> for WidgetError {
+    fn from(error: PropertyError) -> Self {
+        WidgetError::Property(error)
+    }
+}
+
+impl From<SceneError> for WidgetError {
+    fn from(error: SceneError) -> Self {
+        WidgetError::Scene(error)
+    }
+}
+
+/// A widget held by the tree.
+struct Mounted {
+    key: Option<String>,
+    kind: mem::Discriminant<NodeKind>,
+    node: NodeId,
+    state_type: Option<&'static str>,
+    state: Option<Box<dyn WidgetState + Send>>,
+    hooks: WidgetHooks,
+    children: Vec<Mounted>,
+}
+
+/// Retained widgets mirrored into a scene.
+#[derive(Default)]
+pub struct WidgetTree {
+    roots: Vec<Mounted>,
+    scheduler: UpdateScheduler,
+}
+
+impl WidgetTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Brings the widgets and `scene` in line with `roots`, returning the changes.
+    ///
+    /// A property the widget state does not have, or of the wrong type, stops the
+    /// update at that widget; widgets reconciled before it keep their update. So
+    /// does a widget whose parent a hook removed from the scene during the update.
+    pub fn update(&mut self, scene: &mut Scene, roots: &[WidgetDesc]) -> Result<Vec<WidgetChange>, WidgetError> {
+        let mut changes = Vec::new();
+        let old = mem::take(&mut self.roots);
+        let mut cx = Reconcile { scene, scheduler: &mut self.scheduler, changes: &mut changes };
+        let result = reconcile(&mut cx, None, old, roots, &mut self.roots);
+        result.map(|_| changes)
+    }
+
+    /// Removes every widget from `scene`.
+    pub fn clear(&mut self, scene: &mut Scene) {
+        let mut changes = Vec::new();
+        let mut cx = Reconcile { scene, scheduler: &mut self.scheduler, changes: &mut changes };
+        for widget in self.roots.drain(..) {
+            remove(&mut cx, widget);
+        }
+    }
+
+    /// Runs the update hooks of the widgets due, over a frame of `dt`; returns
+    /// how many ran.
+    pub fn tick(&mut self, scene: &mut Scene, dt: Seconds) -> usize {
+        if self.scheduler.is_idle() {
+            return 0;
+        }
+        let due = self.scheduler.take_due();
+        let mut ticked = 0;
+        let mut stack: Vec<&mut Mounted> = self.roots.iter_mut().rev().collect();
+        while let Some(widget) = stack.pop() {
+            if due.contains(&widget.node) {
+                if let Some(hook) = widget.hooks.on_update.clone() {
+                    if run(&hook, scene, widget, dt) {
+                        self.scheduler.request_update(widget.node);
+                    }
+                    ticked += 1;
+                }
+            }
+            stack.extend(widget.children.iter_mut().rev());
+        }
+        ticked
+    }
+
+    #[inline]
+    pub fn scheduler(&self) -> &UpdateScheduler {
+        &self.scheduler
+    }
+
+    /// Scheduler to request updates and report interactions to.
+    #[inline]
+    pub fn scheduler_mut(&mut self) -> &mut UpdateScheduler {
+        &mut self.scheduler
+    }
+
+    /// Scene nodes of the root widgets, in description order.
+    pub fn roots(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.roots.iter().map(|w| w.node)
+    }
+
+    /// Number of widgets, nested ones included.
+    pub fn len(&self) -> usize {
+        let mut stack: Vec<&Mounted> = self.roots.iter().collect();
+        let mut count = 0;
+        while let Some(widget) = stack.pop() {
+            count += 1;
+            stack.extend(&widget.children);
+        }
+        count
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Node of the widget reached by following `keys` from the roots.
+    pub fn find(&self, keys: &[&str]) -> Option<NodeId> {
+        let mut level = &self.roots;
+        let mut found = None;
+        for key in keys {
+            let widget = level.iter().find(|w| w.key.as_deref() == Some(*key))?;
+            found = Some(widget.node);
+            level = &widget.children;
+        }
+        found
+    }
+
+    pub fn state(&self, node: NodeId) -> Option<&dyn WidgetState> {
+        let widget = self.mounted(node)?;
+        widget.state.as_deref().map(|s| s as &dyn WidgetState)
+    }
+
+    pub fn state_mut(&mut self, node: NodeId) -> Option<&mut (dyn WidgetState + Send + 'static)> {
+        self.mounted_mut(node)?.state.as_deref_mut()
+    }
+
+    /// Typed value of property `name` of the widget on `node`.
+    pub fn get<T: Property>(&self, node: NodeId, name: &str) -> Option<T> {
+        T::from_value(self.state(node)?.get(name)?)
+    }
+
+    /// Sets property `name` of the widget on `node`.
+    pub fn set<T: Property>(&mut self, node: NodeId, name: &str, value: T) -> Result<(), PropertyError> {
+        let state = self.state_mut(node).ok_or_else(|| PropertyError::Unknown(name.to_string()))?;
+        state.set(name, value.to_value())
+    }
+
+    fn mounted(&self, node: NodeId) -> Option<&Mounted> {
+        let mut stack: Vec<&Mounted> = self.roots.iter().collect();
+        while let Some(widget) = stack.pop() {
+            if widget.node == node {
+                return Some(widget);
+            }
+            stack.extend(&widget.children);
+        }
+        None
+    }
+
+    fn mounted_mut(&mut self, node: NodeId) -> Option<&mut Mounted> {
+        let mut stack: Vec<&mut Mounted> = self.roots.iter_mut().collect();
+        while let Some(widget) = stack.pop() {
+            if widget.node == node {
+                return Some(widget);
+            }
+            stack.extend(&mut widget.children);
+        }
+        None
+    }
+}
+
+// ============================================================================
+// Reconciliation
+// ============================================================================
+
+/// What reconciling works on.
+struct Reconcile<'a> {
+    scene: &'a mut Scene,
+    scheduler: &'a mut UpdateScheduler,
+    changes: &'a mut Vec<WidgetChange>,
+}
+
+/// Reconciles the `old` widgets under `parent` with `descs` into `out`.
+fn reconcile(
+    cx: &mut Reconcile<'_>,
+    parent: Option<NodeId>,
+    mut old: Vec<Mounted>,
+    descs: &[WidgetDesc],
+    out: &mut Vec<Mounted>,
+) -> Result<(), WidgetError> {
+    let mut failure = None;
+    for desc in descs {
+        // Keyed widgets match by key, unkeyed ones by their order among unkeyed siblings
+        let index = old.iter().position(|w| w.key == desc.key);
+        let mounted = match index.map(|i| old.remove(i)) {
+            // A widget whose node was removed from the scene behind the tree's back is rebuilt
+            Some(widget) if desc.matches(&widget) && cx.scene.contains(widget.node) => Ok((widget, false)),
+            Some(widget) => {
+                remove(cx, widget);
+                create(cx, parent, desc).map(|widget| (widget, true))
+            }
+            None => create(cx, parent, desc).map(|widget| (widget, true)),
+        };
+        // A hook earlier in this update may have removed the parent
+        let (mut widget, created) = match mounted {
+            Ok(mounted) => mounted,
+            Err(error) => {
+                failure = Some(error.into());
+                break;
+            }
+        };
+        let children = mem::take(&mut widget.children);
+        let result = apply(cx, &mut widget, desc, created)
+            .map_err(WidgetError::from)
+            .and_then(|_| reconcile(cx, Some(widget.node), children, &desc.children, &mut widget.children));
+        if created && result.is_ok() {
+            if let Some(hook) = widget.hooks.on_mount.clone() {
+                if run(&hook, cx.scene, &mut widget, Seconds(0.0)) {
+                    cx.scheduler.request_update(widget.node);
+                }
+            }
+        }
+        out.push(widget);
+        if let Err(error) = result {
+            failure = Some(error);
+            break;
+        }
+    }
+    if failure.is_some() {
+        // Keep what was not reached so the tree stays in step with the scene
+        out.append(&mut old);
+    } else {
+        for widget in old {
+            remove(cx, widget);
+        }
+    }
+
+    // Match the scene's sibling order to the description
+    let order: Vec<NodeId> = out.iter().map(|w| w.node).collect();
+    let siblings = match parent {
+        Some(parent) => cx.scene.get(parent).map_or(&[][..], |n| n.children()),
+        None => cx.scene.roots(),
+    };
+    if !siblings.iter().filter(|id| order.contains(id)).eq(order.iter()) {
+        for &node in &order {
+            let _ = cx.scene.reparent(node, parent);
+        }
+    }
+    failure.map_or(Ok(()), Err)
+}
+
+fn create(cx: &mut Reconcile<'_>, parent: Option<NodeId>, desc: &WidgetDesc) -> Result<Mounted, SceneError> {
+    let node = cx.scene.insert(Node::new(desc.kind.clone()), parent)?;
+    cx.changes.push(WidgetChange::Created(node));
+    Ok(Mounted {
+        key: desc.key.clone(),
+        kind: mem::discriminant(&desc.kind),
+        node,
+        state_type: desc.state.map(|(name, _)| name),
+        state: desc.state.map(|(_, factory)| factory()),
+        hooks: WidgetHooks::default(),
+        children: Vec::new(),
+    })
+}
+
+fn remove(cx: &mut Reconcile<'_>, mut widget: Mounted) {
+    unmount(cx, &mut widget);
+    let _ = cx.scene.remove(widget.node);
+    cx.changes.push(WidgetChange::Removed(widget.node));
+}
+
+/// Runs the unmount hooks of `widget`'s subtree, children first.
+fn unmount(cx: &mut Reconcile<'_>, widget: &mut Mounted) {
+    for child in &mut widget.children {
+        unmount(cx, child);
+    }
+    if let Some(hook) = widget.hooks.on_unmount.clone() {
+        run(&hook, cx.scene, widget, Seconds(0.0));
+    }
+    cx.scheduler.forget(widget.node);
+}
+
+/// Runs `hook` on `widget`; returns whether it asked for an update.
+fn run(hook: &WidgetHook, scene: &mut Scene, widget: &mut Mounted, dt: Seconds) -> bool {
+    let mut context = WidgetContext::new(widget.node, scene, widget.state.as_deref_mut(), dt);
+    hook(&mut context);
+    context.update_requested()
+}
+
+/// Copies the description onto the widget's node and state.
+fn apply(cx: &mut Reconcile<'_>, widget: &mut Mounted, desc: &WidgetDesc, created: bool) -> Result<(), PropertyError> {
+    widget.hooks.clone_from(&desc.hooks);
+    if let Some(node) = cx.scene.get_mut(widget.node) {
+        let changed = node.name != desc.name
+            || node.kind != desc.kind
+            || node.position != desc.position
+            || node.size != desc.size
+            || node.haptics != desc.haptics
+            || node.flags != desc.flags;
+        if changed {
+            node.name.clone_from(&desc.name);
+            node.kind.clone_from(&desc.kind);
+            node.position = desc.position;
+            node.size = desc.size;
+            node.haptics = desc.haptics;
+            node.flags = desc.flags;
+            if !created {
+                cx.changes.push(WidgetChange::Node(widget.node));
+            }
+        }
+    }
+    for (name, value) in &desc.props {
+        let state = widget.state.as_deref_mut().ok_or_else(|| PropertyError::Unknown(name.to_string()))?;
+        if state.get(name).as_ref() != Some(value) {
+            state.set(name, value.clone())?;
+            if !created {
+                cx.changes.push(WidgetChange::Property { node: widget.node, name, value: value.clone() });
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/widget_tests.rs"]
+mod tests;
@@ -0,0 +1,223 @@
+//! Push buttons that feel like physical keys.
+//!
+//! A [`Button3D`] is a box or cylinder whose cap sits at `position` and travels
+//! along `axis` when pushed. While the device is over the cap, the button renders
+//! its [`ClickProfile`] against the push (ramp, click, bottom-out) instead of a
+//! plain surface spring, reports hover, press and release through its event
+//! callback, and exposes the cap displacement so the graphics can move the cap
+//! with the finger. The button renders its own force, so the node it describes for
+//! the scene is intangible.
+
+use std::fmt;
+
+use crate::core::{Meters, Meters3, Newtons3, Vec3};
+use crate::render::ClickProfile;
+use crate::scene::NodeKind;
+use crate::ui::WidgetDesc;
+
+/// Height above the cap within which the device hovers the button.
+const HOVER_HEIGHT: Meters = Meters(0.01);
+
+/// Outline of a button's cap; the button extends `travel` below it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ButtonShape {
+    /// Rectangular key, `width` and `height` across the axis.
+    Box { width: Meters, height: Meters },
+    Cylinder { radius: Meters },
+}
+
+impl ButtonShape {
+    /// Whether a point at `lateral` offset from the cap center (across the axis) is
+    /// over the cap.
+    fn covers(&self, lateral: Vec3, axis: Vec3) -> bool {
+        match *self {
+            ButtonShape::Box { width, height } => {
+                // Width runs along the X axis projected onto the cap plane
+                let across = Vec3::unit_x().reject_from(axis).try_normalize().unwrap_or_else(Vec3::unit_y);
+                let up = axis.cross(across);
+                lateral.dot(across).abs() <= width.value() * 0.5 && lateral.dot(up).abs() <= height.value() * 0.5
+            }
+            ButtonShape::Cylinder { radius } => lateral.length() <= radius.value(),
+        }
+    }
+}
+
+/// Interaction state of a button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ButtonPhase {
+    #[default]
+    Idle,
+    Hovered,
+    Pressed,
+}
+
+/// Something that happened to a button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonEvent {
+    HoverEnter,
+    HoverExit,
+    /// The cap went past the click point.
+    Press,
+    /// The cap came back up past the release point.
+    Release,
+}
+
+/// A push button with a buckling-spring feel.
+pub struct Button3D {
+    pub label: String,
+    /// Center of the cap face at rest.
+    pub position: Vec3,
+    /// Unit direction the cap travels when pushed.
+    pub axis: Vec3,
+    pub shape: ButtonShape,
+    profile: ClickProfile,
+    phase: ButtonPhase,
+    displacement: Meters,
+    on_event: Option<Box<dyn FnMut(ButtonEvent) + Send>>,
+}
+
+impl Button3D {
+    /// Keyboard-feel box button facing +Z, pushed along -Z.
+    pub fn new(label: impl Into<String>, position: Vec3) -> Self {
+        Self {
+            label: label.into(),
+            position,
+            axis: -Vec3::unit_z(),
+            shape: ButtonShape::Box { width: Meters(0.02), height: Meters(0.02) },
+            profile: ClickProfile::KEYBOARD,
+            phase: ButtonPhase::Idle,
+            displacement: Meters::ZERO,
+            on_event: None,
+        }
+    }
+
+    pub fn with_shape(mut self, shape: ButtonShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Pushed along the unit vector `axis` (pointing into the button).
+    pub fn with_axis(mut self, axis: Vec3) -> Self {
+        self.axis = axis.try_normalize().unwrap_or(self.axis);
+        self
+    }
+
+    pub fn with_profile(mut self, profile: ClickProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Keeps the profile's feel over a different full travel.
+    pub fn with_travel(mut self, travel: Meters) -> Self {
+        self.profile = self.profile.with_travel(travel);
+        self
+    }
+
+    pub fn on_event(mut self, handler: impl FnMut(ButtonEvent) + Send + 'static) -> Self {
+        self.on_event = Some(Box::new(handler));
+        self
+    }
+
+    #[inline]
+    pub fn profile(&self) -> &ClickProfile {
+        &self.profile
+    }
+
+    #[inline]
+    pub fn travel(&self) -> Meters {
+        self.profile.travel
+    }
+
+    #[inline]
+    pub fn phase(&self) -> ButtonPhase {
+        self.phase
+    }
+
+    #[inline]
+    pub fn is_pressed(&self) -> bool {
+        self.phase == ButtonPhase::Pressed
+    }
+
+    /// How far the cap is pushed in, within its travel; for drawing the cap.
+    #[inline]
+    pub fn displacement(&self) -> Meters {
+        self.displacement
+    }
+
+    /// Follows the device at `device` and returns the force the button pushes back with.
+    pub fn update(&mut self, device: Meters3) -> Newtons3 {
+        let offset = device.value() - self.position;
+        let depth = offset.dot(self.axis);
+        let over = self.shape.covers(offset - self.axis * depth, self.axis);
+        let pressed = self.phase == ButtonPhase::Pressed;
+
+        // A held press follows the device even if it slips off the cap edge
+        let engaged = (over || pressed) && depth > 0.0;
+        self.displacement = Meters(if engaged { depth.min(self.travel().value()) } else { 0.0 });
+        let phase = if self.profile.pressed(Meters(if engaged { depth } else { 0.0 }), pressed) {
+            ButtonPhase::Pressed
+        } else if over && depth >= -HOVER_HEIGHT.value() {
+            ButtonPhase::Hovered
+        } else {
+            ButtonPhase::Idle
+        };
+        self.transition(phase);
+
+        if engaged {
+            self.profile.force_along(self.position, self.axis, device)
+        } else {
+            Newtons3::ZERO
+        }
+    }
+
+    /// Description of the button for a widget tree, with the cap drawn pushed in.
+    pub fn desc(&self) -> WidgetDesc {
+        let (width, height) = match self.shape {
+            ButtonShape::Box { width, height } => (width.value(), height.value()),
+            ButtonShape::Cylinder { radius } => (radius.value() * 2.0, radius.value() * 2.0),
+        };
+        let depth = self.travel().value();
+        let center = self.position + self.axis * (self.displacement.value() + depth * 0.5);
+        WidgetDesc::new(NodeKind::Button { label: self.label.clone() })
+            .name(self.label.clone())
+            .at(center.x, center.y, center.z)
+            .size(width, height, depth)
+    }
+
+    fn transition(&mut self, phase: ButtonPhase) {
+        use ButtonPhase::*;
+        let events: &[ButtonEvent] = match (self.phase, phase) {
+            (Idle, Hovered) => &[ButtonEvent::HoverEnter],
+            (Idle, Pressed) => &[ButtonEvent::HoverEnter, ButtonEvent::Press],
+            (Hovered, Idle) => &[ButtonEvent::HoverExit],
+            (Hovered, Pressed) => &[ButtonEvent::Press],
+            (Pressed, Hovered) => &[ButtonEvent::Release],
+            (Pressed, Idle) => &[ButtonEvent::Release, ButtonEvent::HoverExit],
+            _ => &[],
+        };
+        self.phase = phase;
+        if let Some(handler) = self.on_event.as_mut() {
+            for &event in events {
+                handler(event);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Button3D {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Button3D")
+            .field("label", &self.label)
+            .field("position", &self.position)
+            .field("axis", &self.axis)
+            .field("shape", &self.shape)
+            .field("profile", &self.profile)
+            .field("phase", &self.phase)
+            .field("displacement", &self.displacement)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/button_tests.rs"]
+mod tests;
@@ -0,0 +1,411 @@
+//! Manipulation gizmos for moving, turning and resizing scene nodes.
+//!
+//! A [`Gizmo`] attaches to a node and shows three handles, one per world axis,
+//! around the node's world position. What they do depends on the mode:
+//!
+//! - Translate: arrows along the axes; dragging one moves the node along it.
+//! - Rotate: rings about the axes; dragging around one turns the node about it.
+//! - Scale: arrows whose drag stretches the node along their axis.
+//!
+//! A grabbed handle holds the device on its arrow or ring with a spring, so the
+//! drag is constrained to the handle's one degree of freedom. Each mode snaps
+//! to an optional step (15° for rotation by default) and clicks with a detent
+//! at every step. A [`Snapper`] can take over: arrows then stop where it
+//! snaps along them, and rings turn in its angle steps, with its clicks.
+//! Changes are written to the node's position in the scene or to its rotation
+//! and scale in [`AnimatedProps`], and reported as [`GizmoEvent`]s.
+
+use std::fmt;
+
+use crate::anim::AnimatedProps;
+use crate::core::{Deg, Meters, Meters3, Newtons, Newtons3, NewtonsPerMeter, Quat, Rad, Vec3};
+use crate::effects::Waveform;
+use crate::render::Detent;
+use crate::scene::{NodeId, Scene};
+use crate::ui::Snapper;
+
+/// Distance from a handle within which pressing grabs it.
+const GRAB_RADIUS: Meters = Meters(0.01);
+
+/// Stiffness of the spring holding a grabbed device on its handle.
+const RAIL_STIFFNESS: NewtonsPerMeter = NewtonsPerMeter(600.0);
+
+/// Smallest scale factor a drag can shrink a node to.
+const MIN_SCALE: f32 = 0.01;
+
+/// What dragging a handle does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GizmoMode {
+    #[default]
+    Translate,
+    Rotate,
+    Scale,
+}
+
+/// World axis of a handle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GizmoAxis {
+    X,
+    Y,
+    Z,
+}
+
+impl GizmoAxis {
+    pub const ALL: [GizmoAxis; 3] = [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z];
+
+    pub fn vector(self) -> Vec3 {
+        match self {
+            GizmoAxis::X => Vec3::unit_x(),
+            GizmoAxis::Y => Vec3::unit_y(),
+            GizmoAxis::Z => Vec3::unit_z(),
+        }
+    }
+
+    /// Two unit vectors spanning the plane about the axis, counterclockwise.
+    fn plane(self) -> (Vec3, Vec3) {
+        match self {
+            GizmoAxis::X => (Vec3::unit_y(), Vec3::unit_z()),
+            GizmoAxis::Y => (Vec3::unit_z(), Vec3::unit_x()),
+            GizmoAxis::Z => (Vec3::unit_x(), Vec3::unit_y()),
+        }
+    }
+}
+
+/// Something that happened to a gizmo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GizmoEvent {
+    Grabbed(GizmoAxis),
+    Released(GizmoAxis),
+    /// The node moved to this local position.
+    Moved(Vec3),
+    /// The node turned to this rotation.
+    Rotated(Quat),
+    /// The node was resized to this scale.
+    Scaled(Vec3),
+}
+
+/// State of a drag, captured when the handle was grabbed.
+#[derive(Debug, Clone, Copy)]
+struct Grab {
+    axis: GizmoAxis,
+    center: Vec3,
+    position: Vec3,
+    rotation: Quat,
+    scale: Vec3,
+    /// Travel along the axis at the grab; for rings, the angle at the last update.
+    start: f32,
+    /// Ring angle turned since the grab.
+    turned: f32,
+}
+
+/// Translate, rotate and scale handles on one node.
+pub struct Gizmo {
+    pub target: NodeId,
+    pub mode: GizmoMode,
+    /// Length of the arrows and radius of the rings.
+    pub size: Meters,
+    pub translate_snap: Option<Meters>,
+    pub rotate_snap: Option<Rad>,
+    /// Step of the scale factor.
+    pub scale_snap: Option<f32>,
+    /// Peak force of the detent clicking at each snap step.
+    pub detent_depth: Newtons,
+    snapper: Option<Snapper>,
+    grab: Option<Grab>,
+    hovered: Option<GizmoAxis>,
+    on_event: Option<Box<dyn FnMut(GizmoEvent) + Send>>,
+}
+
+impl Gizmo {
+    /// Translate gizmo with 5 cm handles, snapping rotation to 15°.
+    pub fn new(target: NodeId) -> Self {
+        Self {
+            target,
+            mode: GizmoMode::Translate,
+            size: Meters(0.05),
+            translate_snap: None,
+            rotate_snap: Some(Deg(15.0).into()),
+            scale_snap: None,
+            detent_depth: Newtons(0.5),
+            snapper: None,
+            grab: None,
+            hovered: None,
+            on_event: None,
+        }
+    }
+
+    pub fn with_mode(mut self, mode: GizmoMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_size(mut self, size: Meters) -> Self {
+        self.size = Meters(size.value().abs().max(f32::EPSILON));
+        self
+    }
+
+    pub fn with_translate_snap(mut self, step: Option<Meters>) -> Self {
+        self.translate_snap = step;
+        self
+    }
+
+    pub fn with_rotate_snap(mut self, step: Option<Rad>) -> Self {
+        self.rotate_snap = step;
+        self
+    }
+
+    pub fn with_scale_snap(mut self, step: Option<f32>) -> Self {
+        self.scale_snap = step;
+        self
+    }
+
+    pub fn with_detent_depth(mut self, depth: Newtons) -> Self {
+        self.detent_depth = depth;
+        self
+    }
+
+    /// Snaps translation and rotation with `snapper`.
+    pub fn with_snapper(mut self, snapper: Snapper) -> Self {
+        self.snapper = Some(snapper);
+        self
+    }
+
+    /// The snapper, whose current snap gives the guide to draw.
+    #[inline]
+    pub fn snapper(&self) -> Option<&Snapper> {
+        self.snapper.as_ref()
+    }
+
+    pub fn on_event(mut self, handler: impl FnMut(GizmoEvent) + Send + 'static) -> Self {
+        self.on_event = Some(Box::new(handler));
+        self
+    }
+
+    /// Switches mode, dropping any drag in progress.
+    pub fn set_mode(&mut self, mode: GizmoMode) {
+        self.release();
+        self.mode = mode;
+    }
+
+    /// Handle being dragged.
+    #[inline]
+    pub fn grabbed(&self) -> Option<GizmoAxis> {
+        self.grab.map(|g| g.axis)
+    }
+
+    /// Handle within reach of the device as of the last update, while not dragging.
+    #[inline]
+    pub fn hovered(&self) -> Option<GizmoAxis> {
+        self.hovered
+    }
+
+    /// Snap clicks played since the last call.
+    pub fn take_feedback(&mut self) -> Vec<Waveform> {
+        self.snapper.as_mut().map_or_else(Vec::new, Snapper::take_feedback)
+    }
+
+    /// Follows the device at `device` with `grab` held down, applies the drag to
+    /// the target and returns the force on the device. Only a grabbed handle
+    /// pushes back.
+    pub fn update(&mut self, scene: &mut Scene, props: &mut AnimatedProps, device: Meters3, grab: bool) -> Newtons3 {
+        let Some(center) = scene.world_position(self.target) else {
+            self.release();
+            self.hovered = None;
+            return Newtons3::ZERO;
+        };
+        let device = device.value();
+        if !grab {
+            self.release();
+            self.hovered = self.handle_at(center, device);
+            return Newtons3::ZERO;
+        }
+        let grabbed = match self.grab {
+            Some(grabbed) => grabbed,
+            None => {
+                let Some(axis) = self.handle_at(center, device) else {
+                    return Newtons3::ZERO;
+                };
+                let node_props = props.get(self.target);
+                let grabbed = Grab {
+                    axis,
+                    center,
+                    position: scene.get(self.target).map_or(Vec3::zero(), |n| n.position),
+                    rotation: node_props.rotation,
+                    scale: node_props.scale,
+                    start: self.travel(axis, center, device),
+                    turned: 0.0,
+                };
+                self.grab = Some(grabbed);
+                self.hovered = None;
+                self.emit(GizmoEvent::Grabbed(axis));
+                grabbed
+            }
+        };
+        match self.mode {
+            GizmoMode::Translate | GizmoMode::Scale => self.drag_along(scene, props, grabbed, device),
+            GizmoMode::Rotate => self.drag_around(props, grabbed, device),
+        }
+    }
+
+    /// Drag along an arrow: moving or scaling.
+    fn drag_along(&mut self, scene: &mut Scene, props: &mut AnimatedProps, grab: Grab, device: Vec3) -> Newtons3 {
+        let axis = grab.axis.vector();
+        let offset = device - grab.center;
+        let delta = offset.dot(axis) - grab.start;
+        let step = match self.mode {
+            GizmoMode::Translate => {
+                let moved = match self.snap_along(scene, grab.center, axis, delta) {
+                    Some(moved) => moved,
+                    None => snap(delta, self.translate_snap.map(|s| s.value())),
+                };
+                let position = grab.position + axis * moved;
+                if let Some(node) = scene.get_mut(self.target).filter(|n| n.position != position) {
+                    node.position = position;
+                    self.emit(GizmoEvent::Moved(position));
+                }
+                self.translate_snap.map(|s| s.value())
+            }
+            _ => {
+                let factor = snap(1.0 + delta / self.size.value(), self.scale_snap).max(MIN_SCALE);
+                let mut scale = grab.scale;
+                scale[grab.axis as usize] *= factor;
+                let node_props = props.get_mut(self.target);
+                if node_props.scale != scale {
+                    node_props.scale = scale;
+                    self.emit(GizmoEvent::Scaled(scale));
+                }
+                self.scale_snap.map(|s| s * self.size.value())
+            }
+        };
+        let rail = -offset.reject_from(axis) * RAIL_STIFFNESS.value();
+        let click = self.detent(step).map_or(0.0, |d| d.force(Meters(delta)).value());
+        Newtons3(rail + axis * click)
+    }
+
+    /// Drag around a ring: rotating.
+    fn drag_around(&mut self, props: &mut AnimatedProps, grab: Grab, device: Vec3) -> Newtons3 {
+        let axis = grab.axis.vector();
+        let offset = device - grab.center;
+        let radial = offset.reject_from(axis);
+        let lift = -axis * (offset.dot(axis) * RAIL_STIFFNESS.value());
+        let Some(out) = radial.try_normalize() else {
+            return Newtons3(lift);
+        };
+
+        let angle = self.travel(grab.axis, grab.center, device);
+        let turned = grab.turned + Rad(angle - grab.start).wrap().0;
+        self.grab = Some(Grab { start: angle, turned, ..grab });
+
+        let step = self.snapper.as_ref().and_then(|s| s.angle_step).or(self.rotate_snap);
+        let snapped = match self.snapper.as_mut().filter(|s| s.angle_step.is_some()) {
+            Some(snapper) => snapper.snap_angle(axis, Rad(turned)).0,
+            None => snap(turned, step.map(|s| s.0)),
+        };
+        let rotation = Quat::from_axis_angle(axis, Rad(snapped)) * grab.rotation;
+        let node_props = props.get_mut(self.target);
+        if node_props.rotation != rotation {
+            node_props.rotation = rotation;
+            self.emit(GizmoEvent::Rotated(rotation));
+        }
+
+        let radius = self.size.value();
+        let rail = out * (RAIL_STIFFNESS.value() * (radius - radial.length()));
+        let spacing = step.map(|s| s.0 * radius);
+        let click = self.detent(spacing).map_or(0.0, |d| d.force(Meters(turned * radius)).value());
+        Newtons3(rail + lift + axis.cross(out) * click)
+    }
+
+    /// Travel along the arrow `axis` to where the snapper pulls the target, if
+    /// it snapped.
+    fn snap_along(&mut self, scene: &Scene, center: Vec3, axis: Vec3, delta: f32) -> Option<f32> {
+        let snapper = self.snapper.as_mut()?;
+        let snapped = snapper.snap(scene, center + axis * delta, &[self.target]);
+        snapper.current().map(|_| (snapped - center).dot(axis))
+    }
+
+    /// Travel along `axis` for arrows, or the angle about it for rings.
+    fn travel(&self, axis: GizmoAxis, center: Vec3, device: Vec3) -> f32 {
+        let offset = device - center;
+        match self.mode {
+            GizmoMode::Translate | GizmoMode::Scale => offset.dot(axis.vector()),
+            GizmoMode::Rotate => {
+                let (u, v) = axis.plane();
+                Rad::atan2(offset.dot(v), offset.dot(u)).0
+            }
+        }
+    }
+
+    /// Nearest handle within grabbing distance of `device`.
+    fn handle_at(&self, center: Vec3, device: Vec3) -> Option<GizmoAxis> {
+        let offset = device - center;
+        let size = self.size.value();
+        let distance = |axis: GizmoAxis| {
+            let a = axis.vector();
+            match self.mode {
+                GizmoMode::Translate | GizmoMode::Scale => {
+                    (offset - a * offset.dot(a).clamp(0.0, size)).length()
+                }
+                GizmoMode::Rotate => {
+                    let radial = offset.reject_from(a).length() - size;
+                    (radial * radial + offset.dot(a).powi(2)).sqrt()
+                }
+            }
+        };
+        GizmoAxis::ALL
+            .into_iter()
+            .map(|axis| (axis, distance(axis)))
+            .filter(|&(_, d)| d <= GRAB_RADIUS.value())
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(axis, _)| axis)
+    }
+
+    /// Notches every `spacing` of travel from the grab.
+    fn detent(&self, spacing: Option<f32>) -> Option<Detent> {
+        let spacing = spacing.filter(|s| *s > 0.0)?;
+        Some(Detent::new(Meters(spacing), self.detent_depth, Meters(spacing * 0.5)))
+    }
+
+    fn release(&mut self) {
+        if let Some(snapper) = self.snapper.as_mut() {
+            snapper.release();
+        }
+        if let Some(grab) = self.grab.take() {
+            self.emit(GizmoEvent::Released(grab.axis));
+        }
+    }
+
+    fn emit(&mut self, event: GizmoEvent) {
+        if let Some(handler) = self.on_event.as_mut() {
+            handler(event);
+        }
+    }
+}
+
+/// `value` rounded to a multiple of `step`, if any.
+fn snap(value: f32, step: Option<f32>) -> f32 {
+    match step.filter(|s| *s > 0.0) {
+        Some(step) => (value / step).round() * step,
+        None => value,
+    }
+}
+
+impl fmt::Debug for Gizmo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Gizmo")
+            .field("target", &self.target)
+            .field("mode", &self.mode)
+            .field("size", &self.size)
+            .field("translate_snap", &self.translate_snap)
+            .field("rotate_snap", &self.rotate_snap)
+            .field("scale_snap", &self.scale_snap)
+            .field("detent_depth", &self.detent_depth)
+            .field("snapper", &self.snapper)
+            .field("grab", &self.grab)
+            .field("hovered", &self.hovered)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/gizmo_tests.rs"]
+mod tests;
@@ -0,0 +1,359 @@
+//! Rotary knobs with angular detents and end stops.
+//!
+//! A [`Knob3D`] maps a turn about its axis to a value between `min` and `max`,
+//! spread over a sweep of less than a full turn. It can be turned two ways:
+//! by dragging the device around the grip ring ([`Knob3D::update`]) or by
+//! twisting the tool about the axis ([`Knob3D::update_twist`]). Either way the
+//! knob clicks at every step and stops hard at both ends of its sweep.
+//!
+//! Pushing the knob in switches to fine mode, where the knob turns by only a
+//! fraction of the hand's rotation. The forces are geared down by the same
+//! fraction, so detents feel wider and softer rather than stronger.
+
+use std::fmt;
+
+use crate::core::{Meters, Meters3, Newtons, Newtons3, NewtonsPerMeter, Quat, Rad, Vec3};
+use crate::render::Detent;
+
+/// Stiffness of the spring holding a grabbed device on the grip ring.
+const RAIL_STIFFNESS: NewtonsPerMeter = NewtonsPerMeter(600.0);
+
+/// Stiffness of the end stops, along the arc of the grip ring.
+const WALL_STIFFNESS: NewtonsPerMeter = NewtonsPerMeter(2000.0);
+
+/// Stiffness resisting a push along the axis.
+const PUSH_STIFFNESS: NewtonsPerMeter = NewtonsPerMeter(300.0);
+
+/// How far the knob must be pushed in for fine mode.
+const PUSH_DEPTH: Meters = Meters(0.004);
+
+/// Distance from the grip ring within which pressing grabs the knob.
+const GRAB_RADIUS: Meters = Meters(0.01);
+
+/// Most decimals a value is displayed with.
+const MAX_DECIMALS: usize = 6;
+
+/// Decimals needed to write multiples of `step` exactly: 0 for 5, 1 for 0.5, 2 for 0.25.
+fn step_decimals(step: f32) -> usize {
+    (0..MAX_DECIMALS)
+        .find(|&d| {
+            let scaled = step.abs() * 10f32.powi(d as i32);
+            (scaled - scaled.round()).abs() <= 1e-4 * scaled.max(1.0)
+        })
+        .unwrap_or(MAX_DECIMALS)
+}
+
+/// Something that happened to a knob.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KnobEvent {
+    Grabbed,
+    Released,
+    /// The value moved to the given one while held.
+    Changed(f32),
+    /// Fine mode was entered (true) or left (false).
+    Fine(bool),
+}
+
+/// A knob turning about an axis through its center.
+pub struct Knob3D {
+    pub center: Vec3,
+    /// Unit axis pointing out of the knob face; turning counterclockwise about it
+    /// raises the value.
+    pub axis: Vec3,
+    /// Radius of the grip ring the device drags around.
+    pub radius: Meters,
+    /// Value at the start of the sweep.
+    pub min: f32,
+    /// Value at the end of the sweep.
+    pub max: f32,
+    /// Angle between the two end stops.
+    pub sweep: Rad,
+    /// Values snap to multiples of `step` from `min`; None is continuous.
+    pub step: Option<f32>,
+    /// Knob rotation per hand rotation in fine mode.
+    pub fine_scale: f32,
+    value: f32,
+    /// Knob angle from the start of the sweep; runs past the stops while held, by
+    /// the hand's own travel beyond them.
+    angle: f32,
+    /// Raw input angle of the previous update while held.
+    last: Option<f32>,
+    detent: Option<Detent>,
+    grabbed: bool,
+    fine: bool,
+    formatter: Option<Box<dyn Fn(f32) -> String + Send>>,
+    on_event: Option<Box<dyn FnMut(KnobEvent) + Send>>,
+}
+
+impl Knob3D {
+    /// Knob with a 270° sweep and a 2 cm grip ring, facing `axis`.
+    pub fn new(center: Vec3, axis: Vec3, min: f32, max: f32) -> Self {
+        Self {
+            center,
+            axis: axis.try_normalize().unwrap_or_else(Vec3::unit_z),
+            radius: Meters(0.02),
+            min,
+            max,
+            sweep: Rad(270f32.to_radians()),
+            step: None,
+            fine_scale: 0.1,
+            value: min,
+            angle: 0.0,
+            last: None,
+            detent: None,
+            grabbed: false,
+            fine: false,
+            formatter: None,
+            on_event: None,
+        }
+    }
+
+    pub fn with_radius(mut self, radius: Meters) -> Self {
+        self.radius = Meters(radius.value().abs().max(f32::EPSILON));
+        self
+    }
+
+    pub fn with_sweep(mut self, sweep: impl Into<Rad>) -> Self {
+        self.sweep = Rad(sweep.into().0.abs().max(f32::EPSILON));
+        self.angle = self.angle_of(self.value);
+        self
+    }
+
+    pub fn with_step(mut self, step: f32) -> Self {
+        self.step = (step > 0.0).then_some(step);
+        self.set_value(self.value);
+        self
+    }
+
+    pub fn with_fine_scale(mut self, scale: f32) -> Self {
+        self.fine_scale = scale.clamp(f32::EPSILON, 1.0);
+        self
+    }
+
+    pub fn with_value(mut self, value: f32) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Notches of `depth` at every step, felt at the grip ring; without a step
+    /// there are none. Call after setting the radius, sweep and step.
+    pub fn with_detents(mut self, depth: Newtons) -> Self {
+        self.detent = self.step.and_then(|step| {
+            let steps = ((self.max - self.min) / step).abs().round() as u32;
+            let arc = self.radius.value() * self.sweep.0;
+            let spacing = Meters(arc * step / (self.max - self.min).abs().max(f32::EPSILON));
+            (steps > 0).then(|| Detent::new(spacing, depth, spacing * 0.5).with_count(steps + 1))
+        });
+        self
+    }
+
+    /// Formats the value for [`Knob3D::display`].
+    pub fn with_formatter(mut self, formatter: impl Fn(f32) -> String + Send + 'static) -> Self {
+        self.formatter = Some(Box::new(formatter));
+        self
+    }
+
+    pub fn on_event(mut self, handler: impl FnMut(KnobEvent) + Send + 'static) -> Self {
+        self.on_event = Some(Box::new(handler));
+        self
+    }
+
+    #[inline]
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Sets the value without reporting a change.
+    pub fn set_value(&mut self, value: f32) {
+        self.value = self.snap(value);
+        self.angle = self.angle_of(self.value);
+    }
+
+    #[inline]
+    pub fn is_grabbed(&self) -> bool {
+        self.grabbed
+    }
+
+    #[inline]
+    pub fn is_fine(&self) -> bool {
+        self.fine
+    }
+
+    /// Knob angle from the start of the sweep for the current value.
+    #[inline]
+    pub fn angle(&self) -> Rad {
+        Rad(self.angle_of(self.value))
+    }
+
+    /// The value as text: through the formatter if set, else with as many
+    /// decimals as the step needs.
+    pub fn display(&self) -> String {
+        match &self.formatter {
+            Some(formatter) => formatter(self.value),
+            None => {
+                let decimals = self.step.map_or(2, step_decimals);
+                format!("{:.*}", decimals, self.value)
+            }
+        }
+    }
+
+    /// Follows the device at `device` dragging around the grip ring, with `grab`
+    /// held down, and returns the force on it. Pressing past the face of the knob
+    /// pushes it in. Only a grabbed knob pushes back.
+    pub fn update(&mut self, device: Meters3, grab: bool) -> Newtons3 {
+        let offset = device.value() - self.center;
+        let depth = -offset.dot(self.axis);
+        let radial = offset.reject_from(self.axis);
+        let reach = (radial.length() - self.radius.value()).abs() <= GRAB_RADIUS.value()
+            && depth.abs() <= GRAB_RADIUS.value();
+        if !self.hold(grab, reach) {
+            return Newtons3::ZERO;
+        }
+
+        let (u, v) = self.plane();
+        let Some(out) = radial.try_normalize() else {
+            return Newtons3(self.axis * (PUSH_STIFFNESS.value() * depth.max(0.0)));
+        };
+        let tangent = self.axis.cross(out);
+        let turn = self.turn(Rad::atan2(radial.dot(v), radial.dot(u)).0, depth > PUSH_DEPTH.value());
+
+        let rail = out * (RAIL_STIFFNESS.value() * (self.radius.value() - radial.length()));
+        let push = self.axis * (PUSH_STIFFNESS.value() * depth.max(0.0));
+        Newtons3(tangent * turn.value() + rail + push)
+    }
+
+    /// Follows a tool twisted to `orientation` about the knob axis, with `grab`
+    /// held down and the knob `pushed` in or not, and returns the torque on the
+    /// tool about the axis in newton-meters.
+    pub fn update_twist(&mut self, orientation: Quat, pushed: bool, grab: bool) -> f32 {
+        if !self.hold(grab, true) {
+            return 0.0;
+        }
+        let twist = 2.0 * orientation.vector().dot(self.axis).atan2(orientation.w);
+        self.turn(twist, pushed).value() * self.radius.value()
+    }
+
+    /// Tracks grabbing and releasing, grabbing only within `reach`; returns
+    /// whether the knob is held.
+    fn hold(&mut self, grab: bool, reach: bool) -> bool {
+        if !grab {
+            if self.grabbed {
+                self.grabbed = false;
+                self.last = None;
+                self.angle = self.angle_of(self.value);
+                self.set_fine(false);
+                self.emit(KnobEvent::Released);
+            }
+            return false;
+        }
+        if !self.grabbed {
+            if !reach {
+                return false;
+            }
+            self.grabbed = true;
+            self.emit(KnobEvent::Grabbed);
+        }
+        true
+    }
+
+    /// Turns the knob by the change of the raw input angle since the last update
+    /// and returns the tangential force at the grip ring. Detents are geared like
+    /// the turn; the end stops are not, so they feel as hard in fine mode.
+    fn turn(&mut self, raw: f32, pushed: bool) -> Newtons {
+        self.set_fine(pushed);
+        let gear = if self.fine { self.fine_scale } else { 1.0 };
+        if let Some(last) = self.last {
+            self.angle = self.advance(Rad(raw - last).wrap().0, gear);
+        }
+        self.last = Some(raw);
+
+        let clamped = self.angle.clamp(0.0, self.sweep.0);
+        let value = self.snap(self.min + (self.max - self.min) * clamped / self.sweep.0);
+        if value != self.value {
+            self.value = value;
+            self.emit(KnobEvent::Changed(value));
+        }
+
+        let radius = self.radius.value();
+        let wall = WALL_STIFFNESS.value() * (clamped - self.angle) * radius;
+        let detent = self.detent.map_or(0.0, |d| d.force(Meters(self.angle * radius)).value());
+        Newtons(wall + detent * gear)
+    }
+
+    /// The angle after the hand turns by `delta`: through the gear within the
+    /// sweep, one to one past a stop, where the hand presses on the wall.
+    fn advance(&self, delta: f32, gear: f32) -> f32 {
+        let stop = self.angle.clamp(0.0, self.sweep.0);
+        let mut from = self.angle;
+        let mut delta = delta;
+        if from != stop {
+            let next = from + delta;
+            if (next - stop) * (from - stop) >= 0.0 {
+                return next;
+            }
+            // Back inside: the rest of the turn goes through the gear
+            (from, delta) = (stop, next - stop);
+        }
+        let next = from + delta * gear;
+        let clamped = next.clamp(0.0, self.sweep.0);
+        clamped + (next - clamped) / gear
+    }
+
+    /// Two unit vectors spanning the plane of the knob face.
+    fn plane(&self) -> (Vec3, Vec3) {
+        let helper = if self.axis.x.abs() < 0.9 { Vec3::unit_x() } else { Vec3::unit_y() };
+        let u = helper.reject_from(self.axis).normalize();
+        (u, self.axis.cross(u))
+    }
+
+    fn angle_of(&self, value: f32) -> f32 {
+        let range = self.max - self.min;
+        let fraction = if range != 0.0 { (value - self.min) / range } else { 0.0 };
+        fraction * self.sweep.0
+    }
+
+    fn set_fine(&mut self, fine: bool) {
+        if self.fine != fine {
+            self.fine = fine;
+            self.emit(KnobEvent::Fine(fine));
+        }
+    }
+
+    /// Clamps `value` to the range and snaps it to the step.
+    fn snap(&self, value: f32) -> f32 {
+        let (low, high) = if self.min <= self.max { (self.min, self.max) } else { (self.max, self.min) };
+        let snapped = match self.step {
+            Some(step) => self.min + ((value - self.min) / step).round() * step,
+            None => value,
+        };
+        snapped.clamp(low, high)
+    }
+
+    fn emit(&mut self, event: KnobEvent) {
+        if let Some(handler) = self.on_event.as_mut() {
+            handler(event);
+        }
+    }
+}
+
+impl fmt::Debug for Knob3D {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Knob3D")
+            .field("center", &self.center)
+            .field("axis", &self.axis)
+            .field("radius", &self.radius)
+            .field("min", &self.min)
+            .field("max", &self.max)
+            .field("sweep", &self.sweep)
+            .field("step", &self.step)
+            .field("value", &self.value)
+            .field("detent", &self.detent)
+            .field("grabbed", &self.grabbed)
+            .field("fine", &self.fine)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/knob_tests.rs"]
+mod tests;
@@ -0,0 +1,184 @@
+//! Text labels and value readouts placed in 3D.
+//!
+//! A [`Label3D`] lays its text out with [`TextLayout`] (alignment, wrapping within
+//! a width, bidirectional text) and turns the glyphs into quads textured from a
+//! signed-distance-field [`GlyphAtlas`]. The text block is centered on `position`
+//! in the plane given by `orientation`, or turned toward the camera by a
+//! [`BillboardMode`] when the label is a billboard. Changing the text relays it
+//! out; setting the same text again is free, so readouts can set their value
+//! every frame.
+
+use crate::core::{Meters, Quat, Vec3};
+use crate::scene::{BillboardMode, CameraPose, NodeKind};
+use crate::text::{FontFallback, GlyphAtlas, TextAlign, TextLayout, TextStyle};
+use crate::ui::WidgetDesc;
+
+/// Height of glyphs above the baseline, as a fraction of the font size.
+const ASCENT: f32 = 0.8;
+
+/// Depth of glyphs below the baseline, as a fraction of the font size.
+const DESCENT: f32 = 0.2;
+
+/// One glyph as a textured quad.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlyphQuad {
+    pub ch: char,
+    /// Index into the label's fallback chain.
+    pub font: usize,
+    /// Bottom left, bottom right, top right and top left, in world space.
+    pub corners: [Vec3; 4],
+    /// Atlas texture coordinates `[u0, v0, u1, v1]`.
+    pub uv: [f32; 4],
+}
+
+/// A block of text in the scene.
+#[derive(Debug, Clone)]
+pub struct Label3D {
+    /// Center of the text block.
+    pub position: Vec3,
+    /// Turns the text plane (text along X, lines down -Y, facing +Z) into place.
+    pub orientation: Quat,
+    /// Faces the camera instead of following `orientation`.
+    pub billboard: Option<BillboardMode>,
+    text: String,
+    fonts: FontFallback,
+    style: TextStyle,
+    layout: TextLayout,
+}
+
+impl Label3D {
+    /// Centered 1 cm text facing +Z.
+    pub fn new(text: impl Into<String>, position: Vec3) -> Self {
+        let text = text.into();
+        let fonts = FontFallback::default();
+        let style = TextStyle { font_size: 0.01, align: TextAlign::Center, ..TextStyle::default() };
+        let layout = TextLayout::new(&text, &fonts, &style);
+        Self { position, orientation: Quat::identity(), billboard: None, text, fonts, style, layout }
+    }
+
+    pub fn with_fonts(mut self, fonts: FontFallback) -> Self {
+        self.fonts = fonts;
+        self.relayout();
+        self
+    }
+
+    pub fn with_size(mut self, size: Meters) -> Self {
+        self.style.font_size = size.value();
+        self.relayout();
+        self
+    }
+
+    pub fn with_align(mut self, align: TextAlign) -> Self {
+        self.style.align = align;
+        self.relayout();
+        self
+    }
+
+    /// Wraps lines at spaces to stay within `width`.
+    pub fn with_wrap(mut self, width: Meters) -> Self {
+        self.style.max_width = Some(width.value());
+        self.relayout();
+        self
+    }
+
+    pub fn with_orientation(mut self, orientation: Quat) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn with_billboard(mut self, mode: BillboardMode) -> Self {
+        self.billboard = Some(mode);
+        self
+    }
+
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replaces the text; returns whether it changed.
+    pub fn set_text(&mut self, text: impl AsRef<str>) -> bool {
+        let text = text.as_ref();
+        if self.text == text {
+            return false;
+        }
+        self.text.clear();
+        self.text.push_str(text);
+        self.relayout();
+        true
+    }
+
+    #[inline]
+    pub fn style(&self) -> &TextStyle {
+        &self.style
+    }
+
+    #[inline]
+    pub fn layout(&self) -> &TextLayout {
+        &self.layout
+    }
+
+    /// Width and height of the text block.
+    pub fn extent(&self) -> (Meters, Meters) {
+        (Meters(self.block_width()), Meters(self.layout.height))
+    }
+
+    /// Orientation and scale the text is drawn with, seen by `camera`.
+    pub fn pose_for(&self, camera: &CameraPose) -> (Quat, f32) {
+        match self.billboard {
+            Some(mode) => {
+                let pose = mode.pose(self.position, self.layout.height, camera);
+                (pose.orientation, pose.scale)
+            }
+            None => (self.orientation, 1.0),
+        }
+    }
+
+    /// Quads for every visible glyph seen by `camera`, allocating atlas cells for
+    /// new glyphs. Glyphs that do not fit in the atlas are left out.
+    pub fn quads(&self, camera: &CameraPose, atlas: &mut GlyphAtlas) -> Vec<GlyphQuad> {
+        let (rotation, scale) = self.pose_for(camera);
+        let size = self.style.font_size;
+        let left = -self.block_width() * 0.5;
+        let middle = ASCENT * size - self.layout.height * 0.5;
+        let place = |x: f32, y: f32| self.position + rotation.rotate(Vec3::new(left + x, y - middle, 0.0) * scale);
+
+        self.layout
+            .glyphs
+            .iter()
+            .filter(|g| !g.ch.is_whitespace())
+            .filter_map(|g| {
+                let rect = atlas.allocate(g.font, g.ch)?;
+                let (x0, x1) = (g.x, g.x + g.advance);
+                let (y0, y1) = (g.y - DESCENT * size, g.y + ASCENT * size);
+                Some(GlyphQuad {
+                    ch: g.ch,
+                    font: g.font,
+                    corners: [place(x0, y0), place(x1, y0), place(x1, y1), place(x0, y1)],
+                    uv: rect.uv(atlas.size()),
+                })
+            })
+            .collect()
+    }
+
+    /// Description of the label for a widget tree.
+    pub fn desc(&self) -> WidgetDesc {
+        let (width, height) = self.extent();
+        WidgetDesc::new(NodeKind::Label { text: self.text.clone() })
+            .at(self.position.x, self.position.y, self.position.z)
+            .size(width.value(), height.value(), 0.0)
+    }
+
+    /// Width the text is aligned within.
+    fn block_width(&self) -> f32 {
+        self.style.max_width.unwrap_or(self.layout.width)
+    }
+
+    fn relayout(&mut self) {
+        self.layout = TextLayout::new(&self.text, &self.fonts, &self.style);
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/label_tests.rs"]
+mod tests;
@@ -0,0 +1,273 @@
+//! Scrollable lists that tick as rows pass.
+//!
+//! A [`ListView3D`] shows a column of text rows on a panel facing +Z. Pressing
+//! into the panel and dragging up or down scrolls the rows; lifting off keeps
+//! them coasting with the flick velocity until friction stops them. Past
+//! either end the rows stretch on a rubber band and spring back, with a thunk
+//! as they reach the end (see [`KineticScroll`]). A light click plays each
+//! time a new row crosses the selection line across the middle of the panel,
+//! and a press that lifts off without scrolling selects the row under it. Rows
+//! are clipped to the panel, so [`ListView3D::desc`] only describes what is
+//! visible. Like the other widgets the list renders its own surface force, so
+//! the panel it describes is intangible.
+
+use std::fmt;
+
+use crate::core::{Meters, Meters3, Newtons3, NewtonsPerMeter, Seconds, Vec3};
+use crate::effects::Waveform;
+use crate::scene::NodeKind;
+use crate::ui::{KineticScroll, WidgetDesc};
+
+/// Stiffness of the panel surface.
+const SURFACE_STIFFNESS: NewtonsPerMeter = NewtonsPerMeter(500.0);
+
+/// Thickness of the panel behind its face.
+const PANEL_DEPTH: Meters = Meters(0.005);
+
+/// How far a press may drag and still select rather than scroll.
+const TAP_SLOP: Meters = Meters(0.003);
+
+/// Intensity of the click played as a row crosses the selection line.
+const TICK_INTENSITY: f32 = 0.3;
+
+/// Something that happened to a list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListEvent {
+    /// The row at the selection line changed to the given one.
+    Crossed(usize),
+    /// The given row was tapped.
+    Selected(usize),
+}
+
+/// A scrolling column of rows on a panel.
+pub struct ListView3D {
+    /// Center of the panel face.
+    pub position: Vec3,
+    pub width: Meters,
+    pub height: Meters,
+    pub row_height: Meters,
+    items: Vec<String>,
+    /// Distance the rows are scrolled up from the first row at the top.
+    scroll: KineticScroll,
+    /// Device height at the previous update while pressed.
+    last: Option<f32>,
+    /// Distance dragged during the current press.
+    dragged: f32,
+    current: Option<usize>,
+    selected: Option<usize>,
+    feedback: Vec<Waveform>,
+    on_event: Option<Box<dyn FnMut(ListEvent) + Send>>,
+}
+
+impl ListView3D {
+    pub fn new(position: Vec3, width: Meters, height: Meters, row_height: Meters) -> Self {
+        Self {
+            position,
+            width,
+            height,
+            row_height: Meters(row_height.value().abs().max(f32::EPSILON)),
+            items: Vec::new(),
+            scroll: KineticScroll::new(0.0),
+            last: None,
+            dragged: 0.0,
+            current: None,
+            selected: None,
+            feedback: Vec::new(),
+            on_event: None,
+        }
+    }
+
+    pub fn with_items<S: Into<String>>(mut self, items: impl IntoIterator<Item = S>) -> Self {
+        self.set_items(items);
+        self
+    }
+
+    /// Scrolls with the friction, rubber band and spring of `scroll`.
+    pub fn with_scroll(mut self, scroll: KineticScroll) -> Self {
+        let position = self.scroll.position();
+        self.scroll = scroll;
+        self.scroll.set_extent(self.max_offset());
+        self.scroll.scroll_to(position);
+        self
+    }
+
+    pub fn on_event(mut self, handler: impl FnMut(ListEvent) + Send + 'static) -> Self {
+        self.on_event = Some(Box::new(handler));
+        self
+    }
+
+    /// Replaces the rows, keeping the scroll position where it still fits.
+    pub fn set_items<S: Into<String>>(&mut self, items: impl IntoIterator<Item = S>) {
+        self.items = items.into_iter().map(Into::into).collect();
+        self.scroll.set_extent(self.max_offset());
+        self.selected = self.selected.filter(|&i| i < self.items.len());
+        self.current = self.row_at_line();
+    }
+
+    #[inline]
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// How far the rows are scrolled up; past the ends while stretched.
+    #[inline]
+    pub fn offset(&self) -> Meters {
+        Meters(self.scroll.position())
+    }
+
+    /// Whether the rows are coasting or springing back.
+    #[inline]
+    pub fn is_scrolling(&self) -> bool {
+        self.scroll.is_moving()
+    }
+
+    /// Row at the selection line.
+    #[inline]
+    pub fn current(&self) -> Option<usize> {
+        self.current
+    }
+
+    /// Row selected last.
+    #[inline]
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Scrolls row `index` as close to the selection line as the ends allow,
+    /// stopping any coasting. Does not tick or report the crossing.
+    pub fn scroll_to(&mut self, index: usize) {
+        let center = (index as f32 + 0.5) * self.row_height.value();
+        self.scroll.scroll_to(center - self.height.value() * 0.5);
+        self.current = self.row_at_line();
+    }
+
+    /// Follows the device at `device` over a frame of `dt` and returns the force
+    /// of the panel surface on it.
+    pub fn update(&mut self, device: Meters3, dt: Seconds) -> Newtons3 {
+        let p = device.value();
+        let local = p - self.position;
+        let depth = -local.z;
+        let over = local.x.abs() <= self.width.value() * 0.5 && local.y.abs() <= self.height.value() * 0.5;
+
+        if over && depth > 0.0 {
+            if let Some(last) = self.last {
+                let delta = p.y - last;
+                self.dragged += delta.abs();
+                self.scroll.drag(delta, dt);
+            } else {
+                self.scroll.press();
+                self.dragged = 0.0;
+            }
+            self.last = Some(p.y);
+        } else {
+            if self.last.take().is_some() {
+                if self.dragged <= TAP_SLOP.value() {
+                    self.scroll.stop();
+                    if let Some(row) = self.row_at(local.y) {
+                        self.selected = Some(row);
+                        self.emit(ListEvent::Selected(row));
+                    }
+                } else {
+                    self.scroll.release();
+                }
+            }
+            self.scroll.advance(dt);
+        }
+        self.feedback.extend(self.scroll.take_feedback());
+        self.cross();
+
+        if over && depth > 0.0 {
+            Newtons3(Vec3::unit_z() * (SURFACE_STIFFNESS.value() * depth))
+        } else {
+            Newtons3::ZERO
+        }
+    }
+
+    /// Clicks and end-stop thunks played since the last call.
+    pub fn take_feedback(&mut self) -> Vec<Waveform> {
+        std::mem::take(&mut self.feedback)
+    }
+
+    /// Rows showing on the panel as `(index, top, bottom)`, in panel coordinates
+    /// from the center up and clipped to the panel.
+    pub fn visible_rows(&self) -> impl Iterator<Item = (usize, f32, f32)> + '_ {
+        let half = self.height.value() * 0.5;
+        let row = self.row_height.value();
+        let offset = self.scroll.position();
+        let first = (offset / row).floor().max(0.0) as usize;
+        (first..self.items.len())
+            .map(move |i| {
+                let top = half + offset - i as f32 * row;
+                (i, top.min(half), (top - row).max(-half))
+            })
+            .take_while(|&(_, top, bottom)| top > bottom)
+    }
+
+    /// Description of the panel with a label for each visible row.
+    pub fn desc(&self) -> WidgetDesc {
+        let depth = PANEL_DEPTH.value();
+        let center = self.position - Vec3::unit_z() * (depth * 0.5);
+        let rows = self.visible_rows().map(|(i, top, bottom)| {
+            WidgetDesc::label(self.items[i].clone())
+                .key(i.to_string())
+                .at(0.0, (top + bottom) * 0.5, depth * 0.5)
+                .size(self.width.value(), top - bottom, 0.0)
+        });
+        WidgetDesc::new(NodeKind::Panel)
+            .at(center.x, center.y, center.z)
+            .size(self.width.value(), self.height.value(), depth)
+            .children(rows)
+    }
+
+    fn max_offset(&self) -> f32 {
+        (self.items.len() as f32 * self.row_height.value() - self.height.value()).max(0.0)
+    }
+
+    /// Row at panel height `y`, measured from the center up.
+    fn row_at(&self, y: f32) -> Option<usize> {
+        let content = self.scroll.position() + self.height.value() * 0.5 - y;
+        let index = (content / self.row_height.value()).floor();
+        (index >= 0.0 && (index as usize) < self.items.len()).then_some(index as usize)
+    }
+
+    fn row_at_line(&self) -> Option<usize> {
+        self.row_at(0.0)
+    }
+
+    /// Ticks and reports a new row at the selection line.
+    fn cross(&mut self) {
+        let row = self.row_at_line();
+        if row != self.current {
+            self.current = row;
+            if let Some(row) = row {
+                self.feedback.push(Waveform::click().with_intensity(TICK_INTENSITY));
+                self.emit(ListEvent::Crossed(row));
+            }
+        }
+    }
+
+    fn emit(&mut self, event: ListEvent) {
+        if let Some(handler) = self.on_event.as_mut() {
+            handler(event);
+        }
+    }
+}
+
+impl fmt::Debug for ListView3D {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ListView3D")
+            .field("position", &self.position)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("row_height", &self.row_height)
+            .field("items", &self.items.len())
+            .field("scroll", &self.scroll)
+            .field("current", &self.current)
+            .field("selected", &self.selected)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/list_tests.rs"]
+mod tests;
@@ -0,0 +1,15 @@
+// src/haptic/widgets/mod.rs
+pub mod button;
+pub mod gizmo;
+pub mod knob;
+pub mod label;
+pub mod list;
+pub mod slider;
+pub mod toggle;
+pub use button::{Button3D, ButtonEvent, ButtonPhase, ButtonShape};
+pub use gizmo::{Gizmo, GizmoAxis, GizmoEvent, GizmoMode};
+pub use knob::{Knob3D, KnobEvent};
+pub use label::{GlyphQuad, Label3D};
+pub use list::{ListEvent, ListView3D};
+pub use slider::{Slider3D, SliderEvent};
+pub use toggle::{Checkbox3D, OverCenter, Toggle3D};
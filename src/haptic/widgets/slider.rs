@@ -0,0 +1,178 @@
+//! Linear sliders with a haptically rendered track.
+//!
+//! A [`Slider3D`] maps the position of a handle along a track segment to a value
+//! between `min` and `max`. Grabbing the handle (pressing the grab button within
+//! reach of it) binds the device to the track: a rail spring pulls it back onto the
+//! segment, stiff walls stop it at either end, and optional detents click at every
+//! step. While held, each change of the value is reported to the event callback.
+
+use std::fmt;
+
+use crate::core::{Meters, Meters3, Newtons, Newtons3, NewtonsPerMeter, Vec3};
+use crate::render::Detent;
+
+/// Stiffness of the spring holding a grabbed handle on its track.
+const RAIL_STIFFNESS: NewtonsPerMeter = NewtonsPerMeter(600.0);
+
+/// Stiffness of the end stops.
+const WALL_STIFFNESS: NewtonsPerMeter = NewtonsPerMeter(2000.0);
+
+/// Distance from the handle within which pressing grabs it.
+const GRAB_RADIUS: Meters = Meters(0.01);
+
+/// Something that happened to a slider.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SliderEvent {
+    Grabbed,
+    Released,
+    /// The value moved to the given one while held.
+    Changed(f32),
+}
+
+/// A handle sliding along a straight track.
+pub struct Slider3D {
+    pub start: Vec3,
+    pub end: Vec3,
+    /// Value at `start`.
+    pub min: f32,
+    /// Value at `end`.
+    pub max: f32,
+    /// Values snap to multiples of `step` from `min`; None is continuous.
+    pub step: Option<f32>,
+    value: f32,
+    detent: Option<Detent>,
+    grabbed: bool,
+    on_event: Option<Box<dyn FnMut(SliderEvent) + Send>>,
+}
+
+impl Slider3D {
+    pub fn new(start: Vec3, end: Vec3, min: f32, max: f32) -> Self {
+        Self { start, end, min, max, step: None, value: min, detent: None, grabbed: false, on_event: None }
+    }
+
+    pub fn with_step(mut self, step: f32) -> Self {
+        self.step = (step > 0.0).then_some(step);
+        self.value = self.snap(self.value);
+        self
+    }
+
+    pub fn with_value(mut self, value: f32) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Notches of `depth` at every step; without a step there are none.
+    pub fn with_detents(mut self, depth: Newtons) -> Self {
+        self.detent = self.step.and_then(|step| {
+            let steps = ((self.max - self.min) / step).abs().round() as u32;
+            let spacing = Meters(self.length() * step / (self.max - self.min).abs().max(f32::EPSILON));
+            (steps > 0).then(|| Detent::new(spacing, depth, spacing * 0.5).with_count(steps + 1))
+        });
+        self
+    }
+
+    pub fn on_event(mut self, handler: impl FnMut(SliderEvent) + Send + 'static) -> Self {
+        self.on_event = Some(Box::new(handler));
+        self
+    }
+
+    #[inline]
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Sets the value without reporting a change.
+    pub fn set_value(&mut self, value: f32) {
+        self.value = self.snap(value);
+    }
+
+    #[inline]
+    pub fn is_grabbed(&self) -> bool {
+        self.grabbed
+    }
+
+    #[inline]
+    pub fn length(&self) -> f32 {
+        self.start.distance_to(self.end)
+    }
+
+    /// Where the handle sits for the current value.
+    pub fn handle_position(&self) -> Vec3 {
+        let range = self.max - self.min;
+        let fraction = if range != 0.0 { (self.value - self.min) / range } else { 0.0 };
+        self.start + (self.end - self.start) * fraction
+    }
+
+    /// Follows the device at `device`, with `grab` held down, and returns the force
+    /// on it. Only a grabbed handle pushes back.
+    pub fn update(&mut self, device: Meters3, grab: bool) -> Newtons3 {
+        let p = device.value();
+        if !grab {
+            if self.grabbed {
+                self.grabbed = false;
+                self.emit(SliderEvent::Released);
+            }
+            return Newtons3::ZERO;
+        }
+        if !self.grabbed {
+            if p.distance_to(self.handle_position()) > GRAB_RADIUS.value() {
+                return Newtons3::ZERO;
+            }
+            self.grabbed = true;
+            self.emit(SliderEvent::Grabbed);
+        }
+
+        let length = self.length();
+        let Some(axis) = (self.end - self.start).try_normalize() else {
+            return RAIL_STIFFNESS * (Meters3(self.start) - device);
+        };
+        let along = (p - self.start).dot(axis);
+        let clamped = along.clamp(0.0, length);
+        let value = self.snap(self.min + (self.max - self.min) * clamped / length);
+        if value != self.value {
+            self.value = value;
+            self.emit(SliderEvent::Changed(value));
+        }
+
+        let lateral = (p - self.start).reject_from(axis);
+        let rail = lateral * -RAIL_STIFFNESS.value();
+        let wall = axis * (WALL_STIFFNESS.value() * (clamped - along));
+        let detent = self.detent.map_or(Newtons3::ZERO, |d| d.force_along(self.start, axis, device));
+        Newtons3(rail + wall) + detent
+    }
+
+    /// Clamps `value` to the range and snaps it to the step.
+    fn snap(&self, value: f32) -> f32 {
+        let (low, high) = if self.min <= self.max { (self.min, self.max) } else { (self.max, self.min) };
+        let snapped = match self.step {
+            Some(step) => self.min + ((value - self.min) / step).round() * step,
+            None => value,
+        };
+        snapped.clamp(low, high)
+    }
+
+    fn emit(&mut self, event: SliderEvent) {
+        if let Some(handler) = self.on_event.as_mut() {
+            handler(event);
+        }
+    }
+}
+
+impl fmt::Debug for Slider3D {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Slider3D")
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .field("min", &self.min)
+            .field("max", &self.max)
+            .field("step", &self.step)
+            .field("value", &self.value)
+            .field("detent", &self.detent)
+            .field("grabbed", &self.grabbed)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/slider_tests.rs"]
+mod tests;
@@ -0,0 +1,82 @@
+use super::*;
+use std::sync::{Arc, Mutex};
+
+const TEST_EPSILON: f32 = 1e-4;
+
+fn at(x: f32, y: f32, z: f32) -> Meters3 {
+    Meters3::new(x, y, z)
+}
+
+fn recording_button() -> (Button3D, Arc<Mutex<Vec<ButtonEvent>>>) {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let button = Button3D::new("OK", Vec3::zero()).on_event(move |e| sink.lock().unwrap().push(e));
+    (button, events)
+}
+
+#[test]
+fn test_press_cycle_events() {
+    let (mut button, events) = recording_button();
+    button.update(at(0.0, 0.0, 0.05));
+    assert_eq!(button.phase(), ButtonPhase::Idle);
+    button.update(at(0.0, 0.0, 0.005));
+    assert_eq!(button.phase(), ButtonPhase::Hovered);
+    button.update(at(0.0, 0.0, -0.001));
+    assert_eq!(button.phase(), ButtonPhase::Hovered);
+    button.update(at(0.0, 0.0, -0.0025));
+    assert!(button.is_pressed());
+    // Rising past the break keeps the press until the release point
+    button.update(at(0.0, 0.0, -0.0015));
+    assert!(button.is_pressed());
+    button.update(at(0.0, 0.0, -0.0005));
+    button.update(at(0.0, 0.0, 0.05));
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![ButtonEvent::HoverEnter, ButtonEvent::Press, ButtonEvent::Release, ButtonEvent::HoverExit]
+    );
+}
+
+#[test]
+fn test_force_follows_click_profile() {
+    let mut button = Button3D::new("OK", Vec3::new(0.1, 0.0, 0.0));
+    assert_eq!(button.update(at(0.1, 0.0, 0.001)), Newtons3::ZERO);
+    let force = button.update(at(0.1, 0.0, -0.002));
+    assert!((force.0.z - 0.6).abs() < TEST_EPSILON);
+    assert!((button.displacement().value() - 0.002).abs() < TEST_EPSILON);
+    // Off the cap there is nothing to push
+    button.update(at(0.1, 0.0, 0.05));
+    assert_eq!(button.update(at(0.2, 0.0, -0.002)), Newtons3::ZERO);
+
+    // Displacement stops at the travel while the bottom-out pushes back hard
+    let force = button.update(at(0.1, 0.0, 0.0)).0.z;
+    assert_eq!(force, 0.0);
+    button.update(at(0.1, 0.0, -0.002));
+    let bottomed = button.update(at(0.1, 0.0, -0.0045)).0.z;
+    assert!(bottomed > 1.5, "{bottomed}");
+    assert!((button.displacement().value() - 0.004).abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_cylinder_and_axis() {
+    let mut button = Button3D::new("Go", Vec3::zero())
+        .with_shape(ButtonShape::Cylinder { radius: Meters(0.01) })
+        .with_axis(Vec3::unit_x())
+        .with_travel(Meters(0.008));
+    assert!((button.travel().value() - 0.008).abs() < TEST_EPSILON);
+    button.update(at(0.003, 0.009, 0.0));
+    assert_eq!(button.phase(), ButtonPhase::Hovered);
+    button.update(at(0.003, 0.008, 0.008));
+    assert_eq!(button.phase(), ButtonPhase::Idle);
+    let force = button.update(at(0.004, 0.0, 0.0));
+    assert!(force.0.x < 0.0 && button.is_pressed());
+}
+
+#[test]
+fn test_desc_moves_cap() {
+    let mut button = Button3D::new("OK", Vec3::zero());
+    button.update(at(0.0, 0.0, -0.001));
+    let desc = button.desc();
+    assert_eq!(desc.kind, NodeKind::Button { label: "OK".into() });
+    assert!((desc.position.z + 0.003).abs() < TEST_EPSILON);
+    assert!(desc.haptics.is_none());
+}
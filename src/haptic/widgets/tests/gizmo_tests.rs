@@ -0,0 +1,157 @@
+use super::*;
+use crate::scene::{Node, NodeKind};
+use crate::ui::SnapKind;
+use std::sync::{Arc, Mutex};
+
+const TEST_EPSILON: f32 = 1e-4;
+
+/// A node at (0.1, 0, 0) with a gizmo on it.
+fn setup(mode: GizmoMode) -> (Scene, AnimatedProps, NodeId, Gizmo) {
+    let mut scene = Scene::new();
+    let mut node = Node::new(NodeKind::Panel);
+    node.position = Vec3::new(0.1, 0.0, 0.0);
+    let id = scene.insert(node, None).unwrap();
+    (scene, AnimatedProps::new(), id, Gizmo::new(id).with_mode(mode))
+}
+
+/// Point on the ring about Z at `degrees`, for a gizmo centered at (0.1, 0, 0).
+fn ring(degrees: f32) -> Meters3 {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    Meters3::new(0.1 + 0.05 * cos, 0.05 * sin, 0.0)
+}
+
+#[test]
+fn test_translate_drag_is_constrained_to_axis() {
+    let (mut scene, mut props, node, gizmo) = setup(GizmoMode::Translate);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let mut gizmo = gizmo.on_event(move |e| sink.lock().unwrap().push(e));
+
+    // Hovering the X arrow, then pressing away from every handle grabs nothing
+    gizmo.update(&mut scene, &mut props, Meters3::new(0.13, 0.002, 0.0), false);
+    assert_eq!(gizmo.hovered(), Some(GizmoAxis::X));
+    assert_eq!(gizmo.update(&mut scene, &mut props, Meters3::new(0.1, 0.1, 0.1), true), Newtons3::ZERO);
+    assert_eq!(gizmo.grabbed(), None);
+    gizmo.update(&mut scene, &mut props, Meters3::new(0.1, 0.1, 0.1), false);
+
+    gizmo.update(&mut scene, &mut props, Meters3::new(0.13, 0.0, 0.0), true);
+    assert_eq!(gizmo.grabbed(), Some(GizmoAxis::X));
+    // Sideways motion is resisted and ignored; motion along the axis moves the node
+    let force = gizmo.update(&mut scene, &mut props, Meters3::new(0.15, 0.01, 0.0), true);
+    assert!((scene.get(node).unwrap().position - Vec3::new(0.12, 0.0, 0.0)).length() < TEST_EPSILON);
+    assert!((force.0.y + 6.0).abs() < TEST_EPSILON);
+
+    gizmo.update(&mut scene, &mut props, Meters3::new(0.15, 0.0, 0.0), false);
+    let events = events.lock().unwrap();
+    assert_eq!(events.first(), Some(&GizmoEvent::Grabbed(GizmoAxis::X)));
+    assert!(matches!(events[1], GizmoEvent::Moved(p) if (p.x - 0.12).abs() < TEST_EPSILON));
+    assert_eq!(events.last(), Some(&GizmoEvent::Released(GizmoAxis::X)));
+}
+
+#[test]
+fn test_translate_snaps_with_detents() {
+    let (mut scene, mut props, node, gizmo) = setup(GizmoMode::Translate);
+    let mut gizmo = gizmo.with_translate_snap(Some(Meters(0.01)));
+    gizmo.update(&mut scene, &mut props, Meters3::new(0.1, 0.0, 0.02), true);
+    assert_eq!(gizmo.grabbed(), Some(GizmoAxis::Z));
+
+    let force = gizmo.update(&mut scene, &mut props, Meters3::new(0.1, 0.0, 0.0335), true);
+    assert!((scene.get(node).unwrap().position.z - 0.01).abs() < TEST_EPSILON);
+    // Just past a notch, the detent pulls back toward it
+    let back = gizmo.update(&mut scene, &mut props, Meters3::new(0.1, 0.0, 0.031), true);
+    assert!(back.0.z < 0.0);
+    assert!(force.0.z.abs() <= 0.5 + TEST_EPSILON);
+}
+
+#[test]
+fn test_rotate_ring_snaps_to_fifteen_degrees() {
+    let (mut scene, mut props, node, mut gizmo) = setup(GizmoMode::Rotate);
+    // Off the X axis, where the Y and Z rings cross
+    gizmo.update(&mut scene, &mut props, ring(5.0), true);
+    assert_eq!(gizmo.grabbed(), Some(GizmoAxis::Z));
+
+    for degrees in [15.0, 25.0, 37.0] {
+        gizmo.update(&mut scene, &mut props, ring(degrees), true);
+    }
+    let turned = props.get(node).rotation.rotate(Vec3::unit_x());
+    let expected = Vec3::new(30f32.to_radians().cos(), 30f32.to_radians().sin(), 0.0);
+    assert!((turned - expected).length() < TEST_EPSILON);
+
+    // The detent pulls back toward 30° and the rail back onto the ring
+    let (sin, cos) = 37f32.to_radians().sin_cos();
+    let force = gizmo.update(&mut scene, &mut props, Meters3::new(0.1 + 0.06 * cos, 0.06 * sin, 0.0), true);
+    let tangent = Vec3::new(-sin, cos, 0.0);
+    assert!(force.0.dot(tangent) < 0.0);
+    assert!(force.0.dot(Vec3::new(cos, sin, 0.0)) < 0.0);
+    assert_eq!(scene.get(node).unwrap().position, Vec3::new(0.1, 0.0, 0.0));
+}
+
+#[test]
+fn test_rotate_crosses_the_seam() {
+    let (mut scene, mut props, node, gizmo) = setup(GizmoMode::Rotate);
+    let mut gizmo = gizmo.with_rotate_snap(None);
+    for degrees in [170.0, 180.0, 190.0, 200.0] {
+        gizmo.update(&mut scene, &mut props, ring(degrees), true);
+    }
+    let turned = props.get(node).rotation.rotate(Vec3::unit_x());
+    let expected = Vec3::new(30f32.to_radians().cos(), 30f32.to_radians().sin(), 0.0);
+    assert!((turned - expected).length() < TEST_EPSILON);
+}
+
+#[test]
+fn test_scale_stretches_along_axis() {
+    let (mut scene, mut props, node, gizmo) = setup(GizmoMode::Scale);
+    let mut gizmo = gizmo.with_scale_snap(Some(0.25));
+    gizmo.update(&mut scene, &mut props, Meters3::new(0.1, 0.05, 0.0), true);
+    assert_eq!(gizmo.grabbed(), Some(GizmoAxis::Y));
+    gizmo.update(&mut scene, &mut props, Meters3::new(0.1, 0.074, 0.0), true);
+    assert_eq!(props.get(node).scale, Vec3::new(1.0, 1.5, 1.0));
+
+    // Changing mode ends the drag
+    gizmo.set_mode(GizmoMode::Translate);
+    assert_eq!(gizmo.grabbed(), None);
+}
+
+#[test]
+fn test_removed_target_releases() {
+    let (mut scene, mut props, node, mut gizmo) = setup(GizmoMode::Translate);
+    gizmo.update(&mut scene, &mut props, Meters3::new(0.13, 0.0, 0.0), true);
+    scene.remove(node).unwrap();
+    assert_eq!(gizmo.update(&mut scene, &mut props, Meters3::new(0.14, 0.0, 0.0), true), Newtons3::ZERO);
+    assert_eq!(gizmo.grabbed(), None);
+}
+
+#[test]
+fn test_snapper_takes_over_translate_and_rotate() {
+    let (mut scene, mut props, node, gizmo) = setup(GizmoMode::Translate);
+    let mut wall = Node::new(NodeKind::Panel);
+    wall.position = Vec3::new(0.25, 0.0, 0.0);
+    wall.size = Vec3::new(0.02, 0.2, 0.2);
+    scene.insert(wall, None).unwrap();
+    let snapper = Snapper::new().with_grid(Some(0.05)).with_angle_step(Some(Deg(45.0).into()));
+    let mut gizmo = gizmo.with_snapper(snapper);
+
+    // Dragged along X: grid steps, then onto the face of the wall, projected on the axis
+    gizmo.update(&mut scene, &mut props, Meters3::new(0.13, 0.0, 0.0), true);
+    gizmo.update(&mut scene, &mut props, Meters3::new(0.15, 0.0, 0.0), true);
+    assert!((scene.get(node).unwrap().position - Vec3::new(0.1, 0.0, 0.0)).length() < TEST_EPSILON);
+    gizmo.update(&mut scene, &mut props, Meters3::new(0.1705, 0.0, 0.0), true);
+    assert!((scene.get(node).unwrap().position - Vec3::new(0.15, 0.0, 0.0)).length() < TEST_EPSILON);
+    gizmo.update(&mut scene, &mut props, Meters3::new(0.265, 0.0, 0.0), true);
+    assert!((scene.get(node).unwrap().position - Vec3::new(0.24, 0.0, 0.0)).length() < TEST_EPSILON);
+    assert_eq!(gizmo.snapper().unwrap().current().map(|s| s.kind), Some(SnapKind::Surface));
+    assert_eq!(gizmo.take_feedback().len(), 3);
+    gizmo.update(&mut scene, &mut props, Meters3::new(0.265, 0.0, 0.0), false);
+    assert!(gizmo.snapper().unwrap().current().is_none());
+
+    // Rings turn in the snapper's 45° steps
+    scene.get_mut(node).unwrap().position = Vec3::new(0.1, 0.0, 0.0);
+    gizmo.set_mode(GizmoMode::Rotate);
+    gizmo.update(&mut scene, &mut props, ring(5.0), true);
+    assert_eq!(gizmo.grabbed(), Some(GizmoAxis::Z));
+    for degrees in [15.0, 25.0, 35.0] {
+        gizmo.update(&mut scene, &mut props, ring(degrees), true);
+    }
+    let expected = Quat::from_axis_angle(Vec3::unit_z(), Deg(45.0));
+    assert!((props.get(node).rotation.dot(expected).abs() - 1.0).abs() < TEST_EPSILON);
+}
@@ -0,0 +1,175 @@
+use super::*;
+use crate::core::Deg;
+use std::sync::{Arc, Mutex};
+
+const TEST_EPSILON: f32 = 1e-4;
+
+/// Knob at the origin facing +Z, 0 to 270 over its 270° sweep.
+fn knob() -> Knob3D {
+    Knob3D::new(Vec3::zero(), Vec3::unit_z(), 0.0, 270.0)
+}
+
+/// Point on the 2 cm grip ring at `degrees`, `depth` pushed in.
+fn ring(degrees: f32, depth: f32) -> Meters3 {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    Meters3::new(0.02 * cos, 0.02 * sin, -depth)
+}
+
+/// Drags from `from` to `to` degrees in 5° steps and returns the last force.
+fn drag(knob: &mut Knob3D, from: f32, to: f32, depth: f32) -> Newtons3 {
+    let steps = ((to - from) / 5.0).abs().round() as i32;
+    let mut force = Newtons3::ZERO;
+    for i in 0..=steps {
+        force = knob.update(ring(from + (to - from) * i as f32 / steps as f32, depth), true);
+    }
+    force
+}
+
+#[test]
+fn test_circular_drag_turns_the_knob() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let mut knob = knob().on_event(move |e| sink.lock().unwrap().push(e));
+
+    // Pressing away from the ring does not grab
+    assert_eq!(knob.update(Meters3::ZERO, true), Newtons3::ZERO);
+    assert!(!knob.is_grabbed());
+    knob.update(Meters3::ZERO, false);
+
+    drag(&mut knob, 30.0, 120.0, 0.0);
+    assert!(knob.is_grabbed());
+    assert!((knob.value() - 90.0).abs() < 1e-2);
+
+    // Turning clockwise lowers the value again, across the ±180° seam
+    knob.update(Meters3::ZERO, false);
+    drag(&mut knob, 170.0, 200.0, 0.0);
+    assert!((knob.value() - 120.0).abs() < 1e-2);
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.first(), Some(&KnobEvent::Grabbed));
+    assert!(events.contains(&KnobEvent::Released));
+    assert!(events.iter().any(|e| matches!(e, KnobEvent::Changed(_))));
+}
+
+#[test]
+fn test_rail_pulls_onto_the_ring() {
+    let mut knob = knob();
+    knob.update(ring(0.0, 0.0), true);
+    // 5 mm outside the ring
+    let force = knob.update(Meters3::new(0.025, 0.0, 0.0), true);
+    assert!((force.0.x + 3.0).abs() < TEST_EPSILON);
+    assert!(force.0.y.abs() < TEST_EPSILON);
+}
+
+#[test]
+fn test_end_stops() {
+    let mut knob = knob().with_value(260.0);
+    let force = drag(&mut knob, 0.0, 30.0, 0.0);
+    assert_eq!(knob.value(), 270.0);
+    // 20° past the stop pushes back clockwise at the ring
+    let tangent = Vec3::unit_z().cross(ring(30.0, 0.0).value().normalize());
+    let expected = -2000.0 * 20f32.to_radians() * 0.02;
+    assert!((force.0.dot(tangent) - expected).abs() < 1e-2);
+
+    knob.update(Meters3::ZERO, false);
+    knob.set_value(10.0);
+    let force = drag(&mut knob, 0.0, -30.0, 0.0);
+    assert_eq!(knob.value(), 0.0);
+    let tangent = Vec3::unit_z().cross(ring(-30.0, 0.0).value().normalize());
+    assert!(force.0.dot(tangent) > 0.0);
+
+    // Releasing forgets the overshoot
+    knob.update(Meters3::ZERO, false);
+    assert_eq!(knob.angle(), Rad(0.0));
+}
+
+#[test]
+fn test_pushing_in_selects_fine_mode() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let mut knob = knob().with_value(100.0).on_event(move |e| sink.lock().unwrap().push(e));
+
+    let force = drag(&mut knob, 0.0, 90.0, 0.006);
+    assert!(knob.is_fine());
+    assert!((knob.value() - 109.0).abs() < 1e-2);
+    // The push is resisted along the axis
+    assert!((force.0.z - 300.0 * 0.006).abs() < TEST_EPSILON);
+
+    drag(&mut knob, 90.0, 120.0, 0.0);
+    assert!(!knob.is_fine());
+    assert!((knob.value() - 139.0).abs() < 1e-2);
+
+    knob.update(Meters3::ZERO, false);
+    let events = events.lock().unwrap();
+    assert!(events.contains(&KnobEvent::Fine(true)));
+    assert!(events.contains(&KnobEvent::Fine(false)));
+}
+
+#[test]
+fn test_end_stops_stay_hard_in_fine_mode() {
+    let mut knob = knob().with_value(265.0);
+    // 50° of hand travel turns the last 5° in fine mode; the other 40° press on the wall
+    let force = drag(&mut knob, 0.0, 90.0, 0.006);
+    assert!(knob.is_fine());
+    assert_eq!(knob.value(), 270.0);
+    let tangent = Vec3::unit_z().cross(ring(90.0, 0.0).value().normalize());
+    let expected = -2000.0 * 40f32.to_radians() * 0.02;
+    assert!((force.0.dot(tangent) - expected).abs() < 1e-2, "{}", force.0.dot(tangent));
+
+    // Letting go of fine mode past the stop keeps the same wall
+    let coarse = knob.update(ring(90.0, 0.0), true);
+    assert!(!knob.is_fine());
+    assert!((coarse.0.dot(tangent) - expected).abs() < 1e-2);
+
+    // Backing off leaves the wall at the hand's own pace, then turns through the gear
+    drag(&mut knob, 90.0, 40.0, 0.006);
+    assert!((knob.value() - 269.0).abs() < 1e-2);
+}
+
+#[test]
+fn test_tool_twist() {
+    let mut knob = knob().with_value(90.0);
+    for degrees in (0..=45).step_by(5) {
+        let twist = Quat::from_axis_angle(Vec3::unit_z(), Deg(degrees as f32));
+        assert_eq!(knob.update_twist(twist, false, true), 0.0);
+    }
+    assert!((knob.value() - 135.0).abs() < 1e-2);
+
+    // Twisting about another axis does not turn the knob
+    knob.update_twist(Quat::from_axis_angle(Vec3::unit_x(), Deg(30.0)), false, true);
+    assert!((knob.value() - 90.0).abs() < 1e-2);
+
+    assert_eq!(knob.update_twist(Quat::identity(), false, false), 0.0);
+    assert!(!knob.is_grabbed());
+
+    let mut knob = knob.with_value(270.0);
+    knob.update_twist(Quat::identity(), false, true);
+    let torque = knob.update_twist(Quat::from_axis_angle(Vec3::unit_z(), Deg(10.0)), false, true);
+    assert!(torque < 0.0);
+}
+
+#[test]
+fn test_steps_and_detents() {
+    let mut knob = knob().with_step(27.0).with_detents(Newtons(1.0)).with_value(50.0);
+    assert_eq!(knob.value(), 54.0);
+
+    // Just past the notch at 54 the detent pulls back
+    knob.update(ring(0.0, 0.0), true);
+    let force = knob.update(ring(5.0, 0.0), true);
+    assert_eq!(knob.value(), 54.0);
+    let tangent = Vec3::unit_z().cross(ring(5.0, 0.0).value().normalize());
+    assert!(force.0.dot(tangent) < 0.0);
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(knob().with_value(12.345).display(), "12.35");
+    assert_eq!(knob().with_step(1.0).with_value(12.345).display(), "12");
+    assert_eq!(knob().with_step(0.5).with_value(12.345).display(), "12.5");
+    assert_eq!(knob().with_step(0.25).with_value(0.25).display(), "0.25");
+    assert_eq!(knob().with_step(0.25).with_value(0.75).display(), "0.75");
+    assert_eq!(knob().with_step(0.1).with_value(0.3).display(), "0.3");
+    assert_eq!(knob().with_step(2.5).with_value(7.5).display(), "7.5");
+    let knob = knob().with_value(40.0).with_formatter(|v| format!("{v:.0} Hz"));
+    assert_eq!(knob.display(), "40 Hz");
+}
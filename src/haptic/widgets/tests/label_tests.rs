@@ -0,0 +1,99 @@
+use super::*;
+use crate::text::FontFace;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+/// Every glyph 5 mm wide at the default 1 cm size.
+fn label(text: &str) -> Label3D {
+    let fonts = FontFallback::new(vec![FontFace::new("Mono", vec![' '..='~'], 0.5)]);
+    Label3D::new(text, Vec3::zero()).with_fonts(fonts)
+}
+
+/// Camera 1 m in front of the origin, looking back at it.
+fn camera() -> CameraPose {
+    CameraPose::new(Vec3::unit_z(), -Vec3::unit_z())
+}
+
+fn close(a: Vec3, b: Vec3) -> bool {
+    a.distance_to(b) < TEST_EPSILON
+}
+
+#[test]
+fn test_quads_are_centered_on_the_position() {
+    let mut atlas = GlyphAtlas::new(256, 32);
+    let quads = label("ab").quads(&camera(), &mut atlas);
+    assert_eq!(quads.len(), 2);
+    assert_eq!(atlas.len(), 2);
+
+    // Block is 1 cm wide and one 1.2 cm line tall, with glyphs hanging from its top
+    assert!(close(quads[0].corners[0], Vec3::new(-0.005, -0.004, 0.0)));
+    assert!(close(quads[1].corners[2], Vec3::new(0.005, 0.006, 0.0)));
+    assert_eq!(quads[1].uv, atlas.get(0, 'b').unwrap().uv(256));
+}
+
+#[test]
+fn test_whitespace_makes_no_quads() {
+    let mut atlas = GlyphAtlas::new(256, 32);
+    let quads = label("a b").quads(&camera(), &mut atlas);
+    assert_eq!(quads.iter().map(|q| q.ch).collect::<String>(), "ab");
+}
+
+#[test]
+fn test_wrapping_and_alignment() {
+    let label = label("one two three").with_wrap(Meters(0.03)).with_align(TextAlign::Start);
+    assert_eq!(label.layout().line_count, 3);
+    let (width, height) = label.extent();
+    assert!((width.value() - 0.03).abs() < TEST_EPSILON);
+    assert!((height.value() - 0.036).abs() < TEST_EPSILON);
+
+    // Start aligned lines begin at the left edge of the block
+    let quads = label.quads(&camera(), &mut GlyphAtlas::new(512, 32));
+    assert!((quads[0].corners[0].x + 0.015).abs() < TEST_EPSILON);
+    assert!((quads[3].corners[0].x + 0.015).abs() < TEST_EPSILON);
+    assert!(quads[3].corners[0].y < quads[0].corners[0].y);
+}
+
+#[test]
+fn test_orientation_and_billboard() {
+    let turned = Quat::from_axis_angle(Vec3::unit_y(), crate::core::Deg(90.0));
+    let label = label("ab").with_orientation(turned);
+    let quads = label.quads(&camera(), &mut GlyphAtlas::new(256, 32));
+    // Text runs along -Z once turned about Y
+    assert!(quads[1].corners[1].z < quads[0].corners[0].z);
+
+    let billboard = label.with_billboard(BillboardMode::Full);
+    let camera = CameraPose::new(Vec3::new(1.0, 0.0, 0.0), -Vec3::unit_x());
+    let (rotation, scale) = billboard.pose_for(&camera);
+    assert_eq!(scale, 1.0);
+    assert!(close(rotation.rotate(Vec3::unit_z()), Vec3::unit_x()));
+    assert!(close(rotation.rotate(Vec3::unit_y()), Vec3::unit_y()));
+}
+
+#[test]
+fn test_fixed_pixel_size_billboard() {
+    let label = label("ab").with_billboard(BillboardMode::FixedPixelSize { pixels: 24.0 });
+    let near = CameraPose::new(Vec3::unit_z(), -Vec3::unit_z()).with_projection(crate::core::Deg(90.0), 1000.0);
+    let far = CameraPose { position: Vec3::new(0.0, 0.0, 2.0), ..near };
+    let mut atlas = GlyphAtlas::new(256, 32);
+    let height = |camera: &CameraPose| {
+        let quads = label.quads(camera, &mut GlyphAtlas::new(256, 32));
+        quads[0].corners[3].y - quads[0].corners[0].y
+    };
+    // 24 px of a 2 m tall view at 1 m is 4.8 cm for the 1.2 cm line
+    assert!((label.pose_for(&near).1 - 4.0).abs() < TEST_EPSILON);
+    assert!((height(&far) / height(&near) - 2.0).abs() < TEST_EPSILON);
+    assert_eq!(label.quads(&near, &mut atlas).len(), 2);
+}
+
+#[test]
+fn test_runtime_text_updates() {
+    let mut label = label("1.0");
+    assert!(!label.set_text("1.0"));
+    assert!(label.set_text("12.5"));
+    assert_eq!(label.text(), "12.5");
+    assert!((label.extent().0.value() - 0.02).abs() < TEST_EPSILON);
+    match label.desc().kind {
+        NodeKind::Label { text } => assert_eq!(text, "12.5"),
+        kind => panic!("unexpected {kind:?}"),
+    }
+}
@@ -0,0 +1,149 @@
+use super::*;
+use crate::scene::Scene;
+use crate::ui::WidgetTree;
+use std::sync::{Arc, Mutex};
+
+const TEST_EPSILON: f32 = 1e-4;
+
+const FRAME: Seconds = Seconds(0.001);
+
+/// 4 cm tall panel at the origin with twenty 1 cm rows.
+fn list() -> ListView3D {
+    ListView3D::new(Vec3::zero(), Meters(0.06), Meters(0.04), Meters(0.01))
+        .with_items((0..20).map(|i| format!("item {i}")))
+}
+
+/// Device at height `y`, pressed 1 mm into the panel.
+fn press(y: f32) -> Meters3 {
+    Meters3::new(0.0, y, -0.001)
+}
+
+fn lift(y: f32) -> Meters3 {
+    Meters3::new(0.0, y, 0.01)
+}
+
+#[test]
+fn test_drag_scrolls_and_ticks() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let mut list = list().on_event(move |e| sink.lock().unwrap().push(e));
+    assert_eq!(list.current(), Some(2));
+
+    let force = list.update(press(-0.015), FRAME);
+    assert!((force.0.z - 0.5).abs() < TEST_EPSILON);
+    for i in 1..=30 {
+        list.update(press(-0.015 + i as f32 * 0.001), FRAME);
+    }
+    assert!((list.offset().value() - 0.03).abs() < TEST_EPSILON);
+    assert_eq!(list.current(), Some(5));
+    assert_eq!(list.take_feedback().len(), 3);
+    assert!(list.take_feedback().is_empty());
+
+    let events = events.lock().unwrap();
+    assert_eq!(*events, vec![ListEvent::Crossed(3), ListEvent::Crossed(4), ListEvent::Crossed(5)]);
+}
+
+#[test]
+fn test_release_coasts_to_a_stop() {
+    let mut list = list();
+    // Flicked up at 0.1 m/s
+    for i in 0..=5 {
+        list.update(press(i as f32 * 0.001), Seconds(0.01));
+    }
+    let held = list.offset().value();
+
+    list.update(lift(0.005), FRAME);
+    assert!(list.is_scrolling());
+    assert!(list.selected().is_none());
+    for _ in 0..2000 {
+        list.update(lift(0.005), FRAME);
+    }
+    assert!(!list.is_scrolling());
+    // 0.1 m/s decaying at 4/s coasts about 2.5 cm
+    let coasted = list.offset().value() - held;
+    assert!((coasted - 0.025).abs() < 1e-3, "{coasted}");
+}
+
+#[test]
+fn test_ends_stretch_and_spring_back() {
+    let mut list = list();
+    list.update(press(0.0), FRAME);
+    list.update(press(-0.01), FRAME);
+    // Pulled past the top the rows stretch, less than the drag
+    assert!(list.offset().value() < 0.0 && list.offset().value() > -0.01);
+    assert!(!list.is_scrolling());
+    list.update(press(-0.01), Seconds(0.05));
+    list.update(lift(-0.01), FRAME);
+    assert!(list.is_scrolling());
+    for _ in 0..1000 {
+        list.update(lift(-0.01), FRAME);
+    }
+    assert!(!list.is_scrolling());
+    assert_eq!(list.offset(), Meters(0.0));
+    list.take_feedback();
+
+    // Flicked into the bottom end: a thunk as well as the row ticks
+    list.scroll_to(15);
+    for i in 0..=5 {
+        list.update(press(-0.015 + i as f32 * 0.005), Seconds(0.01));
+    }
+    for _ in 0..2000 {
+        list.update(lift(0.01), FRAME);
+    }
+    assert!((list.offset().value() - 0.16).abs() < TEST_EPSILON);
+    let thunks = list.take_feedback().into_iter().filter(|w| w.sharpness < 0.5).count();
+    assert_eq!(thunks, 1);
+
+    list.scroll_to(19);
+    assert!((list.offset().value() - 0.16).abs() < TEST_EPSILON);
+    assert_eq!(list.current(), Some(18));
+    list.scroll_to(10);
+    assert!((list.offset().value() - 0.085).abs() < TEST_EPSILON);
+    assert_eq!(list.current(), Some(10));
+}
+
+#[test]
+fn test_tap_selects_row() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let mut list = list().on_event(move |e| sink.lock().unwrap().push(e));
+    list.update(press(0.015), FRAME);
+    list.update(press(0.016), FRAME);
+    list.update(lift(0.016), FRAME);
+    assert_eq!(list.selected(), Some(0));
+    assert_eq!(*events.lock().unwrap(), vec![ListEvent::Selected(0)]);
+
+    // Touching outside the panel does nothing
+    list.update(Meters3::new(0.05, 0.0, -0.001), FRAME);
+    list.update(Meters3::new(0.05, 0.0, 0.01), FRAME);
+    assert_eq!(events.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn test_rows_are_clipped_to_the_panel() {
+    let mut list = list();
+    list.scroll_to(2);
+    let rows: Vec<_> = list.visible_rows().collect();
+    assert_eq!(rows.len(), 5);
+    assert_eq!(rows[0].0, 0);
+    assert!((rows[0].1 - 0.02).abs() < TEST_EPSILON);
+    assert!((rows[0].2 - 0.015).abs() < TEST_EPSILON);
+    assert!((rows[4].2 + 0.02).abs() < TEST_EPSILON);
+
+    let mut scene = Scene::new();
+    let mut tree = WidgetTree::new();
+    tree.update(&mut scene, &[list.desc()]).unwrap();
+    assert_eq!(tree.len(), 6);
+}
+
+#[test]
+fn test_set_items_clamps_scroll() {
+    let mut list = list();
+    list.scroll_to(15);
+    list.set_items(["a", "b", "c"]);
+    assert_eq!(list.offset(), Meters(0.0));
+    assert_eq!(list.current(), Some(2));
+    list.set_items(Vec::<String>::new());
+    assert_eq!(list.current(), None);
+    assert_eq!(list.visible_rows().count(), 0);
+}
@@ -0,0 +1,70 @@
+use super::*;
+use std::sync::{Arc, Mutex};
+
+const TEST_EPSILON: f32 = 1e-4;
+
+/// 10 cm track along X from 0 to 100.
+fn slider() -> Slider3D {
+    Slider3D::new(Vec3::zero(), Vec3::new(0.1, 0.0, 0.0), 0.0, 100.0)
+}
+
+fn at(x: f32, y: f32, z: f32) -> Meters3 {
+    Meters3::new(x, y, z)
+}
+
+#[test]
+fn test_grab_drag_and_release() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let mut slider = slider().with_value(50.0).on_event(move |e| sink.lock().unwrap().push(e));
+    assert!((slider.handle_position().x - 0.05).abs() < TEST_EPSILON);
+
+    // Pressing away from the handle does not grab it
+    assert_eq!(slider.update(at(0.02, 0.0, 0.0), true), Newtons3::ZERO);
+    assert!(!slider.is_grabbed());
+    slider.update(at(0.0, 0.0, 0.0), false);
+
+    slider.update(at(0.052, 0.0, 0.0), true);
+    assert!(slider.is_grabbed());
+    slider.update(at(0.075, 0.0, 0.0), true);
+    assert!((slider.value() - 75.0).abs() < 1e-3);
+    slider.update(at(0.075, 0.0, 0.0), false);
+
+    let events = events.lock().unwrap();
+    assert_eq!(events[0], SliderEvent::Grabbed);
+    assert!(matches!(events[1], SliderEvent::Changed(v) if (v - 52.0).abs() < 1e-3));
+    assert!(matches!(events[2], SliderEvent::Changed(v) if (v - 75.0).abs() < 1e-3));
+    assert_eq!(events[3], SliderEvent::Released);
+    assert_eq!(events.len(), 4);
+}
+
+#[test]
+fn test_rail_and_end_stops() {
+    let mut slider = slider();
+    slider.update(at(0.0, 0.0, 0.0), true);
+    let rail = slider.update(at(0.05, 0.01, 0.0), true);
+    assert!((rail.0.y + 6.0).abs() < TEST_EPSILON && rail.0.x.abs() < TEST_EPSILON);
+
+    let wall = slider.update(at(0.105, 0.0, 0.0), true);
+    assert!((wall.0.x + 10.0).abs() < 1e-3);
+    assert_eq!(slider.value(), 100.0);
+    let wall = slider.update(at(-0.002, 0.0, 0.0), true);
+    assert!((wall.0.x - 4.0).abs() < 1e-3);
+    assert_eq!(slider.value(), 0.0);
+}
+
+#[test]
+fn test_step_and_detents() {
+    let mut slider = slider().with_step(25.0).with_detents(Newtons(0.5)).with_value(40.0);
+    assert_eq!(slider.value(), 50.0);
+    slider.set_value(1000.0);
+    assert_eq!(slider.value(), 100.0);
+
+    slider.update(at(0.1, 0.0, 0.0), true);
+    slider.update(at(0.06, 0.0, 0.0), true);
+    assert_eq!(slider.value(), 50.0);
+    // Pulled back toward the notch at 5 cm
+    let force = slider.update(at(0.052, 0.0, 0.0), true);
+    assert!(force.0.x < -0.1, "{force:?}");
+    assert!(slider.update(at(0.0625, 0.0, 0.0), true).0.x.abs() < TEST_EPSILON);
+}
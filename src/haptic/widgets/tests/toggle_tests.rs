@@ -0,0 +1,137 @@
+use super::*;
+use std::sync::Mutex;
+
+const TEST_EPSILON: f32 = 1e-4;
+
+fn at(x: f32, y: f32, z: f32) -> Meters3 {
+    Meters3::new(x, y, z)
+}
+
+#[test]
+fn test_over_center_profile() {
+    let spring = OverCenter::new(Meters(0.01), Newtons(1.0));
+    assert!(spring.force(Meters(0.0)).value().abs() < TEST_EPSILON);
+    assert!((spring.force(Meters(0.0025)).value() + 1.0).abs() < TEST_EPSILON);
+    assert!(spring.force(Meters(0.005)).value().abs() < TEST_EPSILON);
+    assert!((spring.force(Meters(0.0075)).value() - 1.0).abs() < TEST_EPSILON);
+    // Stops at both ends
+    assert!((spring.force(Meters(-0.001)).value() - 3.0).abs() < TEST_EPSILON);
+    assert!((spring.force(Meters(0.011)).value() + 3.0).abs() < TEST_EPSILON);
+    assert!(!spring.is_over(Meters(0.005)));
+    assert!(spring.is_over(Meters(0.0051)));
+}
+
+#[test]
+fn test_toggle_flips_past_center() {
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let sink = changes.clone();
+    let mut toggle = Toggle3D::new("power", Vec3::zero()).on_change(move |on| sink.lock().unwrap().push(on));
+
+    // Pressing away from the tip does not grab it
+    assert_eq!(toggle.update(at(0.0, 0.02, 0.0), true), Newtons3::ZERO);
+    toggle.update(at(0.0, 0.02, 0.0), false);
+
+    // Resists on the way up to the center
+    let force = toggle.update(at(0.0, 0.0025, 0.0), true);
+    assert!(toggle.is_grabbed());
+    assert!((force.0.y + 1.0).abs() < TEST_EPSILON);
+    assert!(!toggle.is_on());
+
+    // Snaps over once past it
+    let force = toggle.update(at(0.0, 0.0075, 0.0), true);
+    assert!((force.0.y - 1.0).abs() < TEST_EPSILON);
+    assert!(toggle.is_on());
+
+    // Held off the throw, the rail pulls back
+    let force = toggle.update(at(0.002, 0.0075, 0.0), true);
+    assert!((force.0.x + 1.2).abs() < TEST_EPSILON);
+
+    // Dropped short of the center the lever springs back, not through
+    toggle.update(at(0.0, 0.0075, 0.0), false);
+    assert!((toggle.tip().y - 0.01).abs() < TEST_EPSILON);
+    toggle.update(at(0.0, 0.01, 0.0), true);
+    toggle.update(at(0.0, 0.006, 0.0), true);
+    toggle.update(at(0.0, 0.006, 0.0), false);
+    assert!(toggle.is_on());
+
+    assert_eq!(*changes.lock().unwrap(), vec![true]);
+}
+
+#[test]
+fn test_toggle_binding() {
+    let flag = Arc::new(AtomicBool::new(true));
+    let mut toggle = Toggle3D::new("power", Vec3::zero()).bind(flag.clone());
+    assert!(toggle.is_on());
+    assert!((toggle.tip().y - 0.01).abs() < TEST_EPSILON);
+
+    // Outside changes are followed
+    flag.store(false, Ordering::Relaxed);
+    toggle.update(Meters3::ZERO, false);
+    assert!(!toggle.is_on());
+    assert!(toggle.tip().y.abs() < TEST_EPSILON);
+
+    // Flips are written back
+    toggle.update(at(0.0, 0.0, 0.0), true);
+    toggle.update(at(0.0, 0.008, 0.0), true);
+    assert!(flag.load(Ordering::Relaxed));
+}
+
+#[test]
+fn test_checkbox_press_flips_once() {
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let sink = changes.clone();
+    let mut checkbox = Checkbox3D::new("agree", Vec3::zero()).on_change(move |on| sink.lock().unwrap().push(on));
+
+    // Resists before the center
+    let force = checkbox.update(at(0.0, 0.0, -0.00075));
+    assert!((force.0.z - 1.2).abs() < TEST_EPSILON);
+    assert!(!checkbox.is_checked());
+
+    // Pulls in past it, and flips
+    let force = checkbox.update(at(0.0, 0.0, -0.00225));
+    assert!((force.0.z + 1.2).abs() < TEST_EPSILON);
+    assert!(checkbox.is_checked());
+    assert!((checkbox.displacement().value() - 0.00225).abs() < TEST_EPSILON);
+
+    // Bottoming out and wobbling across the center does not flip it back
+    let force = checkbox.update(at(0.0, 0.0, -0.004));
+    assert!(force.0.z > 0.0);
+    checkbox.update(at(0.0, 0.0, -0.001));
+    checkbox.update(at(0.0, 0.0, -0.002));
+    assert!(checkbox.is_checked());
+
+    // A second press unchecks
+    checkbox.update(at(0.0, 0.0, 0.001));
+    assert_eq!(checkbox.displacement(), Meters::ZERO);
+    checkbox.update(at(0.0, 0.0, -0.002));
+    assert!(!checkbox.is_checked());
+
+    assert_eq!(*changes.lock().unwrap(), vec![true, false]);
+}
+
+#[test]
+fn test_checkbox_ignores_presses_beside_it() {
+    let mut checkbox = Checkbox3D::new("agree", Vec3::zero());
+    assert_eq!(checkbox.update(at(0.01, 0.0, -0.002)), Newtons3::ZERO);
+    assert!(!checkbox.is_checked());
+}
+
+#[test]
+fn test_checkbox_binding_and_desc() {
+    let flag = Arc::new(AtomicBool::new(false));
+    let mut checkbox = Checkbox3D::new("agree", Vec3::zero()).bind(flag.clone());
+    flag.store(true, Ordering::Relaxed);
+    checkbox.update(at(0.0, 0.0, 0.01));
+    assert!(checkbox.is_checked());
+
+    checkbox.update(at(0.0, 0.0, -0.002));
+    assert!(!flag.load(Ordering::Relaxed));
+
+    let mut scene = crate::scene::Scene::new();
+    let mut tree = crate::ui::WidgetTree::new();
+    tree.update(&mut scene, &[checkbox.desc()]).unwrap();
+    assert_eq!(tree.len(), 1);
+    checkbox.set_checked(true);
+    tree.update(&mut scene, &[checkbox.desc()]).unwrap();
+    assert_eq!(tree.len(), 2);
+}
@@ -0,0 +1,407 @@
+//! Two-state switches with snap-through feel.
+//!
+//! Both widgets here render an [`OverCenter`] spring: pushing them resists with a
+//! force that peaks a quarter of the way through the throw, falls to zero halfway,
+//! then reverses and pulls the rest of the way, the way a real switch snaps over
+//! its center.
+//!
+//! - [`Toggle3D`] is a lever flipped by grabbing its tip and dragging it along its
+//!   throw. Past the middle it snaps to the other side and changes state.
+//! - [`Checkbox3D`] is a square pressed into the panel. Each press past the middle
+//!   of its travel flips it; it must come back up before it can flip again.
+//!
+//! Either can be bound to an `Arc<AtomicBool>` shared with the application. The
+//! widget follows outside changes to the flag silently and writes its own flips
+//! back, reporting them to the change callback.
+
+use std::f32::consts::TAU;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::core::{Meters, Meters3, Newtons, Newtons3, NewtonsPerMeter, Vec3};
+use crate::scene::NodeKind;
+use crate::ui::WidgetDesc;
+
+/// Stiffness of the spring holding a grabbed lever tip on its throw.
+const RAIL_STIFFNESS: NewtonsPerMeter = NewtonsPerMeter(600.0);
+
+/// Distance from the lever tip within which pressing grabs it.
+const GRAB_RADIUS: Meters = Meters(0.01);
+
+/// Size of the lever tip, for drawing.
+const TIP_SIZE: Meters = Meters(0.006);
+
+// ============================================================================
+// Over-center spring
+// ============================================================================
+
+/// A bistable spring along a throw from 0 to `travel`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OverCenter {
+    pub travel: Meters,
+    /// Largest force pulling toward either end.
+    pub peak: Newtons,
+    /// Stiffness of the stops at both ends.
+    pub stop: NewtonsPerMeter,
+}
+
+impl OverCenter {
+    pub const fn new(travel: Meters, peak: Newtons) -> Self {
+        Self { travel, peak, stop: NewtonsPerMeter(3000.0) }
+    }
+
+    pub const fn with_stop(mut self, stop: NewtonsPerMeter) -> Self {
+        self.stop = stop;
+        self
+    }
+
+    /// Force at `position` along the throw; positive pushes toward `travel`.
+    pub fn force(&self, position: Meters) -> Newtons {
+        let travel = self.travel.value();
+        let x = position.value();
+        if travel <= 0.0 {
+            return Newtons::ZERO;
+        }
+        if x < 0.0 || x > travel {
+            return Newtons(self.stop.value() * (x.clamp(0.0, travel) - x));
+        }
+        Newtons(-self.peak.value() * (TAU * x / travel).sin())
+    }
+
+    /// Whether `position` is past the middle of the throw.
+    #[inline]
+    pub fn is_over(&self, position: Meters) -> bool {
+        position.value() > self.travel.value() * 0.5
+    }
+}
+
+// ============================================================================
+// Shared state
+// ============================================================================
+
+/// On/off state with an optional binding and change callback.
+struct Switch {
+    on: bool,
+    binding: Option<Arc<AtomicBool>>,
+    on_change: Option<Box<dyn FnMut(bool) + Send>>,
+}
+
+impl Switch {
+    fn new() -> Self {
+        Self { on: false, binding: None, on_change: None }
+    }
+
+    /// Takes up outside changes to the bound flag.
+    fn sync(&mut self) {
+        if let Some(binding) = &self.binding {
+            self.on = binding.load(Ordering::Relaxed);
+        }
+    }
+
+    fn set(&mut self, on: bool) {
+        self.on = on;
+        if let Some(binding) = &self.binding {
+            binding.store(on, Ordering::Relaxed);
+        }
+    }
+
+    /// Sets the state, reporting it if it changed.
+    fn flip_to(&mut self, on: bool) {
+        if self.on != on {
+            self.set(on);
+            if let Some(handler) = self.on_change.as_mut() {
+                handler(on);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Toggle
+// ============================================================================
+
+/// A lever that flips between off and on.
+pub struct Toggle3D {
+    pub label: String,
+    /// Lever tip position when off.
+    pub position: Vec3,
+    /// Unit direction from the off to the on position.
+    pub direction: Vec3,
+    spring: OverCenter,
+    /// Lever tip along the throw; away from an end only while grabbed.
+    lever: f32,
+    grabbed: bool,
+    switch: Switch,
+}
+
+impl Toggle3D {
+    /// Lever throwing 1 cm along +Y.
+    pub fn new(label: impl Into<String>, position: Vec3) -> Self {
+        Self {
+            label: label.into(),
+            position,
+            direction: Vec3::unit_y(),
+            spring: OverCenter::new(Meters(0.01), Newtons(1.0)),
+            lever: 0.0,
+            grabbed: false,
+            switch: Switch::new(),
+        }
+    }
+
+    pub fn with_direction(mut self, direction: Vec3) -> Self {
+        self.direction = direction.try_normalize().unwrap_or(self.direction);
+        self
+    }
+
+    pub fn with_spring(mut self, spring: OverCenter) -> Self {
+        self.spring = spring;
+        self.lever = self.rest();
+        self
+    }
+
+    pub fn with_on(mut self, on: bool) -> Self {
+        self.set_on(on);
+        self
+    }
+
+    /// Shares the state with `flag`, taking its current value.
+    pub fn bind(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.switch.binding = Some(flag);
+        self.switch.sync();
+        self.lever = self.rest();
+        self
+    }
+
+    pub fn on_change(mut self, handler: impl FnMut(bool) + Send + 'static) -> Self {
+        self.switch.on_change = Some(Box::new(handler));
+        self
+    }
+
+    #[inline]
+    pub fn is_on(&self) -> bool {
+        self.switch.on
+    }
+
+    /// Sets the state without reporting a change.
+    pub fn set_on(&mut self, on: bool) {
+        self.switch.set(on);
+        if !self.grabbed {
+            self.lever = self.rest();
+        }
+    }
+
+    #[inline]
+    pub fn is_grabbed(&self) -> bool {
+        self.grabbed
+    }
+
+    #[inline]
+    pub fn spring(&self) -> &OverCenter {
+        &self.spring
+    }
+
+    /// Where the lever tip is; for drawing the lever.
+    pub fn tip(&self) -> Vec3 {
+        self.position + self.direction * self.lever
+    }
+
+    /// Follows the device at `device`, with `grab` held down, and returns the force
+    /// on it. Only a grabbed lever pushes back.
+    pub fn update(&mut self, device: Meters3, grab: bool) -> Newtons3 {
+        let p = device.value();
+        if !grab {
+            self.grabbed = false;
+            self.switch.sync();
+            self.lever = self.rest();
+            return Newtons3::ZERO;
+        }
+        if !self.grabbed {
+            self.switch.sync();
+            self.lever = self.rest();
+            if p.distance_to(self.tip()) > GRAB_RADIUS.value() {
+                return Newtons3::ZERO;
+            }
+            self.grabbed = true;
+        }
+
+        let offset = p - self.position;
+        let along = offset.dot(self.direction);
+        self.lever = along.clamp(0.0, self.spring.travel.value());
+        self.switch.flip_to(self.spring.is_over(Meters(along)));
+
+        let rail = offset.reject_from(self.direction) * -RAIL_STIFFNESS.value();
+        Newtons3(rail + self.direction * self.spring.force(Meters(along)).value())
+    }
+
+    /// Description of the lever tip for a widget tree.
+    pub fn desc(&self) -> WidgetDesc {
+        let tip = self.tip();
+        let size = TIP_SIZE.value();
+        WidgetDesc::new(NodeKind::Button { label: self.label.clone() })
+            .name(self.label.clone())
+            .at(tip.x, tip.y, tip.z)
+            .size(size, size, size)
+    }
+
+    /// Lever position for the current state.
+    fn rest(&self) -> f32 {
+        if self.switch.on {
+            self.spring.travel.value()
+        } else {
+            0.0
+        }
+    }
+}
+
+impl fmt::Debug for Toggle3D {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Toggle3D")
+            .field("label", &self.label)
+            .field("position", &self.position)
+            .field("direction", &self.direction)
+            .field("spring", &self.spring)
+            .field("lever", &self.lever)
+            .field("grabbed", &self.grabbed)
+            .field("on", &self.switch.on)
+            .finish()
+    }
+}
+
+// ============================================================================
+// Checkbox
+// ============================================================================
+
+/// A square that flips each time it is pressed through.
+pub struct Checkbox3D {
+    pub label: String,
+    /// Center of the box face at rest; it is pressed along -Z.
+    pub position: Vec3,
+    /// Width and height of the box face.
+    pub size: Meters,
+    spring: OverCenter,
+    displacement: Meters,
+    /// Whether the current press already flipped the box.
+    latched: bool,
+    switch: Switch,
+}
+
+impl Checkbox3D {
+    pub fn new(label: impl Into<String>, position: Vec3) -> Self {
+        Self {
+            label: label.into(),
+            position,
+            size: Meters(0.015),
+            spring: OverCenter::new(Meters(0.003), Newtons(1.2)),
+            displacement: Meters::ZERO,
+            latched: false,
+            switch: Switch::new(),
+        }
+    }
+
+    pub fn with_size(mut self, size: Meters) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_spring(mut self, spring: OverCenter) -> Self {
+        self.spring = spring;
+        self
+    }
+
+    pub fn with_checked(mut self, checked: bool) -> Self {
+        self.switch.set(checked);
+        self
+    }
+
+    /// Shares the state with `flag`, taking its current value.
+    pub fn bind(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.switch.binding = Some(flag);
+        self.switch.sync();
+        self
+    }
+
+    pub fn on_change(mut self, handler: impl FnMut(bool) + Send + 'static) -> Self {
+        self.switch.on_change = Some(Box::new(handler));
+        self
+    }
+
+    #[inline]
+    pub fn is_checked(&self) -> bool {
+        self.switch.on
+    }
+
+    /// Sets the state without reporting a change.
+    pub fn set_checked(&mut self, checked: bool) {
+        self.switch.set(checked);
+    }
+
+    #[inline]
+    pub fn spring(&self) -> &OverCenter {
+        &self.spring
+    }
+
+    /// How far the box is pushed in; for drawing it.
+    #[inline]
+    pub fn displacement(&self) -> Meters {
+        self.displacement
+    }
+
+    /// Follows the device at `device` and returns the force the box pushes back with.
+    pub fn update(&mut self, device: Meters3) -> Newtons3 {
+        self.switch.sync();
+        let offset = device.value() - self.position;
+        let depth = -offset.z;
+        let half = self.size.value() * 0.5;
+        let over = offset.x.abs() <= half && offset.y.abs() <= half;
+
+        // A press that already flipped the box keeps it even if it slips off the edge
+        if !(over || self.latched) || depth <= 0.0 {
+            self.displacement = Meters::ZERO;
+            self.latched = false;
+            return Newtons3::ZERO;
+        }
+        self.displacement = Meters(depth.min(self.spring.travel.value()));
+        if !self.latched && self.spring.is_over(Meters(depth)) {
+            self.latched = true;
+            self.switch.flip_to(!self.switch.on);
+        }
+        // The spring pushes toward the far end of the travel; the face pushes back out
+        Newtons3(Vec3::unit_z() * -self.spring.force(Meters(depth)).value())
+    }
+
+    /// Description of the box for a widget tree, pushed in, with a check mark
+    /// child while checked.
+    pub fn desc(&self) -> WidgetDesc {
+        let size = self.size.value();
+        let depth = self.spring.travel.value();
+        let center = self.position - Vec3::unit_z() * (self.displacement.value() + depth * 0.5);
+        let desc = WidgetDesc::new(NodeKind::Button { label: self.label.clone() })
+            .name(self.label.clone())
+            .at(center.x, center.y, center.z)
+            .size(size, size, depth);
+        if self.switch.on {
+            desc.child(WidgetDesc::label("\u{2713}").key("check").at(0.0, 0.0, depth * 0.5))
+        } else {
+            desc
+        }
+    }
+}
+
+impl fmt::Debug for Checkbox3D {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Checkbox3D")
+            .field("label", &self.label)
+            .field("position", &self.position)
+            .field("size", &self.size)
+            .field("spring", &self.spring)
+            .field("displacement", &self.displacement)
+            .field("latched", &self.latched)
+            .field("checked", &self.switch.on)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/toggle_tests.rs"]
+mod tests;